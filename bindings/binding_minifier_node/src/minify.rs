@@ -165,6 +165,7 @@ fn do_work(
                     unresolved_mark,
                     top_level_mark,
                     mangle_name_cache: extras.mangle_name_cache.map(|s| (*s).clone()),
+                    size_reporter: None,
                 },
             );
 
@@ -11,6 +11,7 @@ use std::{env, panic::set_hook};
 use backtrace::Backtrace;
 
 mod support;
+mod transform;
 
 #[napi_derive::module_init]
 fn init() {
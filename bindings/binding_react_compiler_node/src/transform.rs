@@ -0,0 +1,218 @@
+use std::sync::{Arc, Mutex};
+
+use napi::bindgen_prelude::*;
+use serde::Deserialize;
+use swc_core::{
+    common::{
+        comments::SingleThreadedComments,
+        errors::{DiagnosticBuilder, Emitter, Handler, Level, HANDLER},
+        source_map::DefaultSourceMapGenConfig,
+        sync::Lrc,
+        FileName, Mark, SourceMap, GLOBALS,
+    },
+    ecma::{
+        ast::{EsVersion, Program},
+        codegen::{text_writer::JsWriter, Emitter as CodeEmitter},
+        parser::{parse_file_as_module, Syntax, TsSyntax},
+        transforms::base::{fixer::fixer, hygiene::hygiene, resolver},
+    },
+};
+use swc_ecma_react_compiler::{react_compiler, Config};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransformOptions {
+    #[serde(default)]
+    filename: Option<String>,
+
+    #[serde(default)]
+    source_maps: bool,
+
+    #[serde(flatten)]
+    config: Config,
+}
+
+#[napi(object)]
+pub struct TransformOutput {
+    pub code: String,
+    pub map: Option<String>,
+    /// Functions which were not compiled, with the reason.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[napi(object)]
+#[derive(Clone)]
+pub struct Diagnostic {
+    /// `error` or `warning`
+    pub level: String,
+    pub message: String,
+    /// 1-based line of the start of the span.
+    pub line: Option<u32>,
+    /// 0-based column of the start of the span.
+    pub column: Option<u32>,
+}
+
+#[derive(Clone)]
+struct Collector {
+    cm: Lrc<SourceMap>,
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl Emitter for Collector {
+    fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+        let loc = db
+            .span
+            .primary_span()
+            .and_then(|span| self.cm.try_lookup_char_pos(span.lo()).ok());
+
+        self.diagnostics.lock().unwrap().push(Diagnostic {
+            level: match db.level {
+                Level::Warning => "warning",
+                _ => "error",
+            }
+            .to_string(),
+            message: db.message(),
+            line: loc.as_ref().map(|loc| loc.line as u32),
+            column: loc.as_ref().map(|loc| loc.col_display as u32),
+        });
+    }
+}
+
+struct TransformTask {
+    code: String,
+    options: String,
+}
+
+#[napi]
+impl Task for TransformTask {
+    type JsValue = TransformOutput;
+    type Output = TransformOutput;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        transform_inner(std::mem::take(&mut self.code), &self.options)
+    }
+
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+fn transform_inner(code: String, options: &str) -> napi::Result<TransformOutput> {
+    let options: TransformOptions = if options.trim().is_empty() {
+        Default::default()
+    } else {
+        serde_json::from_str(options)
+            .map_err(|err| Error::from_reason(format!("failed to parse options: {err}")))?
+    };
+
+    let cm = Lrc::new(SourceMap::default());
+    let filename = match options.filename {
+        Some(filename) => FileName::Real(filename.into()),
+        None => FileName::Anon,
+    };
+    let fm = cm.new_source_file(filename.into(), code);
+
+    let collector = Collector {
+        cm: cm.clone(),
+        diagnostics: Default::default(),
+    };
+    let handler = Handler::with_emitter(true, false, Box::new(collector.clone()));
+
+    let comments = SingleThreadedComments::default();
+
+    let (code, mappings) = GLOBALS.set(&Default::default(), || {
+        HANDLER.set(&handler, || -> napi::Result<_> {
+            let module = parse_file_as_module(
+                &fm,
+                Syntax::Typescript(TsSyntax {
+                    decorators: true,
+                    tsx: true,
+                    ..Default::default()
+                }),
+                EsVersion::latest(),
+                Some(&comments),
+                &mut vec![],
+            )
+            .map_err(|err| {
+                Error::from_reason(format!("failed to parse input: {}", err.kind().msg()))
+            })?;
+
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            let program = Program::Module(module).apply((
+                resolver(unresolved_mark, top_level_mark, true),
+                react_compiler(options.config),
+                hygiene(),
+                fixer(Some(&comments)),
+            ));
+
+            let mut buf = vec![];
+            let mut mappings = vec![];
+            {
+                let mut emitter = CodeEmitter {
+                    cfg: Default::default(),
+                    cm: cm.clone(),
+                    comments: Some(&comments),
+                    wr: JsWriter::new(
+                        cm.clone(),
+                        "\n",
+                        &mut buf,
+                        options.source_maps.then_some(&mut mappings),
+                    ),
+                };
+
+                emitter
+                    .emit_program(&program)
+                    .map_err(|err| Error::from_reason(err.to_string()))?;
+            }
+
+            let code = String::from_utf8(buf).map_err(|err| Error::from_reason(err.to_string()))?;
+
+            Ok((code, mappings))
+        })
+    })?;
+
+    let map = if options.source_maps {
+        let mut buf = vec![];
+        cm.build_source_map(&mappings, None, DefaultSourceMapGenConfig)
+            .to_writer(&mut buf)
+            .map_err(|err| Error::from_reason(err.to_string()))?;
+
+        Some(String::from_utf8(buf).map_err(|err| Error::from_reason(err.to_string()))?)
+    } else {
+        None
+    };
+
+    let diagnostics = collector.diagnostics.lock().unwrap().clone();
+
+    Ok(TransformOutput {
+        code,
+        map,
+        diagnostics,
+    })
+}
+
+/// Compiles components and hooks in `code`.
+///
+/// `options` is a JSON-encoded object, which may contain `filename`,
+/// `sourceMaps`, `runtimeModule` and `compilationMode`.
+#[napi]
+fn transform(
+    code: Buffer,
+    options: Buffer,
+    signal: Option<AbortSignal>,
+) -> AsyncTask<TransformTask> {
+    let code = String::from_utf8_lossy(code.as_ref()).into_owned();
+    let options = String::from_utf8_lossy(options.as_ref()).into_owned();
+
+    AsyncTask::with_optional_signal(TransformTask { code, options }, signal)
+}
+
+#[napi]
+pub fn transform_sync(code: Buffer, options: Buffer) -> napi::Result<TransformOutput> {
+    let code = String::from_utf8_lossy(code.as_ref()).into_owned();
+    let options = String::from_utf8_lossy(options.as_ref()).into_owned();
+
+    transform_inner(code, &options)
+}
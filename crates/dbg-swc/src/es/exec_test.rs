@@ -74,6 +74,7 @@ impl TestMinifiedBundleCommand {
                     unresolved_mark: bundle.unresolved_mark,
                     top_level_mark: bundle.top_level_mark,
                     mangle_name_cache: None,
+                    size_reporter: None,
                 },
             )
             .expect_module()
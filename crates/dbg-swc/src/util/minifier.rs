@@ -60,6 +60,7 @@ pub fn get_minified_with_opts(
                 unresolved_mark: m.unresolved_mark,
                 top_level_mark: m.top_level_mark,
                 mangle_name_cache: None,
+                size_reporter: None,
             },
         )
         .expect_module()
@@ -74,6 +74,7 @@ impl Pass for MinifierPass<'_> {
                     unresolved_mark,
                     top_level_mark,
                     mangle_name_cache: None,
+                    size_reporter: None,
                 },
             )
         }
@@ -81,7 +81,7 @@ use swc_visit::Optional;
 
 #[cfg(feature = "module")]
 use crate::SwcImportResolver;
-pub use crate::plugin::PluginConfig;
+pub use crate::plugin::{PluginConfig, PluginLimits};
 use crate::{builder::MinifierPass, dropped_comments_preserver::dropped_comments_preserver};
 
 #[cfg(test)]
@@ -218,11 +218,24 @@ impl RuntimeOptions {
 #[allow(clippy::derivable_impls)]
 impl Default for RuntimeOptions {
     fn default() -> Self {
+        #[cfg(all(feature = "plugin", feature = "plugin_backend_wasmer"))]
+        let plugin_runtime: Option<Arc<dyn swc_plugin_runner::runtime::Runtime>> =
+            Some(Arc::new(swc_plugin_backend_wasmer::WasmerRuntime));
+        #[cfg(all(feature = "plugin", not(feature = "plugin_backend_wasmer")))]
+        let plugin_runtime: Option<Arc<dyn swc_plugin_runner::runtime::Runtime>> = None;
+
+        // Shared libraries are loaded natively, and Wasm modules are delegated to
+        // the runtime above.
+        #[cfg(all(feature = "plugin", feature = "plugin_backend_native"))]
+        let plugin_runtime: Option<Arc<dyn swc_plugin_runner::runtime::Runtime>> =
+            Some(match plugin_runtime {
+                Some(wasm) => Arc::new(swc_plugin_backend_native::NativeOrWasmRuntime::new(wasm)),
+                None => Arc::new(swc_plugin_backend_native::NativeRuntime),
+            });
+
         RuntimeOptions {
-            #[cfg(all(feature = "plugin", feature = "plugin_backend_wasmer"))]
-            plugin_runtime: Some(Arc::new(swc_plugin_backend_wasmer::WasmerRuntime)),
-            #[cfg(all(feature = "plugin", not(feature = "plugin_backend_wasmer")))]
-            plugin_runtime: None,
+            #[cfg(feature = "plugin")]
+            plugin_runtime,
         }
     }
 }
@@ -367,7 +380,11 @@ impl Options {
 
                     if matches!(
                         cfg.module,
-                        None | Some(ModuleConfig::Es6(..) | ModuleConfig::NodeNext(..))
+                        None | Some(
+                            ModuleConfig::Es6(..)
+                                | ModuleConfig::NodeNext(..)
+                                | ModuleConfig::Browser(..)
+                        )
                     ) {
                         c.module = true;
                     }
@@ -553,7 +570,9 @@ impl Options {
         );
 
         let import_export_assign_config = match cfg.module {
-            Some(ModuleConfig::Es6(..)) => TsImportExportAssignConfig::EsNext,
+            Some(ModuleConfig::Es6(..)) | Some(ModuleConfig::Browser(..)) => {
+                TsImportExportAssignConfig::EsNext
+            }
             Some(ModuleConfig::CommonJs(..))
             | Some(ModuleConfig::Amd(..))
             | Some(ModuleConfig::Umd(..)) => TsImportExportAssignConfig::Preserve,
@@ -617,6 +636,7 @@ impl Options {
             Some(ModuleConfig::SystemJs(_))
             | Some(ModuleConfig::Es6(..))
             | Some(ModuleConfig::NodeNext(..))
+            | Some(ModuleConfig::Browser(..))
             | None => (false, true.into(), true),
         };
 
@@ -651,14 +671,25 @@ impl Options {
 
         #[cfg(feature = "module")]
         let rewrite_import_pass: Box<dyn Pass> = {
-            let swc_import_rewriter: Box<dyn Pass> = match resolver.clone() {
-                Some((base, resolver)) => match cfg.module {
-                    None | Some(ModuleConfig::Es6(..)) | Some(ModuleConfig::NodeNext(..)) => {
-                        Box::new(import_rewriter(base, resolver))
-                    }
-                    _ => Box::new(noop_pass()),
-                },
-                None => Box::new(noop_pass()),
+            let swc_import_rewriter: Box<dyn Pass> = match (resolver.clone(), &cfg.module) {
+                (resolver, Some(ModuleConfig::Browser(config))) => {
+                    let resolver = match resolver {
+                        Some((base, resolver)) => Resolver::Real { base, resolver },
+                        None => Resolver::Default,
+                    };
+
+                    Box::new(modules::browser_esm(
+                        base.clone(),
+                        self.cwd.clone(),
+                        resolver,
+                        config.clone(),
+                    ))
+                }
+                (
+                    Some((base, resolver)),
+                    None | Some(ModuleConfig::Es6(..)) | Some(ModuleConfig::NodeNext(..)),
+                ) => Box::new(import_rewriter(base, resolver)),
+                _ => Box::new(noop_pass()),
             };
 
             let typescript_import_rewriter = Optional::new(
@@ -770,6 +801,13 @@ impl Options {
                     .plugin_runtime
                     .clone()
                     .context("plugin runtime not configured")?;
+                // Plugins are only metered when a fuel limit is set, as it slows them down.
+                let plugin_runtime = match experimental.plugin_limits {
+                    Some(PluginLimits { fuel: Some(_), .. }) => {
+                        plugin_runtime.metered().unwrap_or(plugin_runtime)
+                    }
+                    _ => plugin_runtime,
+                };
 
                 if let Some(plugins) = &experimental.plugins {
                     crate::plugin::compile_wasm_plugins(
@@ -783,6 +821,8 @@ impl Options {
                 Box::new(crate::plugin::plugins(
                     experimental.plugins,
                     experimental.plugin_env_vars,
+                    experimental.plugin_limits,
+                    experimental.reuse_plugin_instances.into_bool(),
                     transform_metadata_context,
                     comments.cloned(),
                     cm.clone(),
@@ -1421,6 +1461,17 @@ pub struct JscExperimental {
     pub plugins: Option<Vec<PluginConfig>>,
     #[serde(default)]
     pub plugin_env_vars: Option<Vec<Atom>>,
+    /// Resource limits applied to each invocation of a plugin.
+    ///
+    /// This requires cargo feature `plugin`.
+    #[serde(default)]
+    pub plugin_limits: Option<PluginLimits>,
+    /// If true, instances of plugins are reused across files instead of being
+    /// created for each file.
+    ///
+    /// This requires cargo feature `plugin`.
+    #[serde(default)]
+    pub reuse_plugin_instances: BoolConfig<false>,
     /// If true, keeps import assertions in the output.
     #[serde(default, alias = "keepImportAssertions")]
     pub keep_import_attributes: BoolConfig<false>,
@@ -1507,6 +1558,8 @@ pub enum ModuleConfig {
     Es6(EsModuleConfig),
     #[serde(rename = "nodenext")]
     NodeNext(EsModuleConfig),
+    #[serde(rename = "browser")]
+    Browser(modules::browser_esm::Config),
 }
 
 /// Stub enum when module feature is disabled.
@@ -1528,6 +1581,8 @@ pub enum ModuleConfig {
     Es6(serde_json::Value),
     #[serde(rename = "nodenext")]
     NodeNext(serde_json::Value),
+    #[serde(rename = "browser")]
+    Browser(serde_json::Value),
 }
 
 #[cfg(feature = "module")]
@@ -1643,6 +1698,15 @@ impl ModuleConfig {
                 config.config.resolve_fully,
                 &config.config.out_file_extension,
             ),
+            Some(ModuleConfig::Browser(config)) => build_resolver(
+                base_url,
+                paths,
+                false,
+                config
+                    .out_file_extension
+                    .as_deref()
+                    .unwrap_or(util::Config::default_js_ext().as_str()),
+            ),
         };
 
         Some((base, resolver))
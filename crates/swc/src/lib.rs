@@ -127,6 +127,7 @@ use common::{
 };
 use jsonc_parser::{ParseOptions, parse_to_serde_value};
 use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
 use serde_json::error::Category;
 use swc_common::{
     FileName, GLOBALS, Mark, SourceFile, SourceMap, Spanned, comments::Comments, errors::Handler,
@@ -140,7 +141,10 @@ use swc_ecma_codegen::Node;
 use swc_ecma_loader::resolvers::{
     lru::CachingResolver, node::NodeModulesResolver, tsc::TsConfigResolver,
 };
-use swc_ecma_minifier::option::{MangleCache, MinifyOptions, TopLevelOptions};
+use swc_ecma_minifier::{
+    option::{FileMangleCache, MangleCache, MinifyOptions, TopLevelOptions},
+    size_report::SizeReporter,
+};
 use swc_ecma_parser::{EsSyntax, Syntax};
 use swc_ecma_transforms::{
     fixer,
@@ -875,6 +879,22 @@ impl Compiler {
 
             let is_mangler_enabled = min_opts.mangle.is_some();
 
+            // A cache passed by the caller takes precedence over `nameCache`.
+            let file_cache = match (&extras.mangle_name_cache, &opts.name_cache) {
+                (None, Some(path)) => Some(Arc::new(
+                    FileMangleCache::load(path, top_level_mark)
+                        .with_context(|| format!("failed to load the name cache from {path}"))?,
+                )),
+                _ => None,
+            };
+            let mangle_name_cache = extras.mangle_name_cache.or_else(|| {
+                file_cache
+                    .clone()
+                    .map(|cache| cache as Arc<dyn MangleCache>)
+            });
+
+            let size_reporter = opts.size_report.then(|| Arc::new(SizeReporter::new()));
+
             program = self.run_transform(handler, false, || {
                 program.mutate(&mut paren_remover(Some(&comments)));
 
@@ -889,7 +909,8 @@ impl Compiler {
                     &swc_ecma_minifier::option::ExtraOptions {
                         unresolved_mark,
                         top_level_mark,
-                        mangle_name_cache: extras.mangle_name_cache,
+                        mangle_name_cache,
+                        size_reporter: size_reporter.clone(),
                     },
                 );
 
@@ -900,6 +921,15 @@ impl Compiler {
                 program
             });
 
+            if let Some(cache) = &file_cache {
+                cache.save().with_context(|| {
+                    format!(
+                        "failed to save the name cache to {}",
+                        cache.path().display()
+                    )
+                })?;
+            }
+
             let preserve_comments = opts
                 .format
                 .comments
@@ -941,7 +971,14 @@ impl Compiler {
                                 .experimental
                                 .reduce_escaped_newline,
                         ),
-                    output: None,
+                    output: size_reporter
+                        .map(|reporter| {
+                            let report = serde_json::to_string(&reporter.report())
+                                .context("failed to serialize the size report")?;
+
+                            Ok::<_, Error>(FxHashMap::from_iter([("sizeReport".into(), report)]))
+                        })
+                        .transpose()?,
                     source_map_url,
                 },
             );
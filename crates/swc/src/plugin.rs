@@ -42,14 +42,27 @@ static SHARED_RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| {
 /// Type of plugin's configuration is up to each plugin - swc/core does not have
 /// strong type and it'll be serialized into plain string when it's passed to
 /// plugin's entrypoint function.
+///
+/// With the `plugin_backend_native` feature, the plugin can also be a shared
+/// library (`.so`, `.dylib` or `.dll`) built from the same source, which is
+/// loaded in the process instead of a Wasm runtime. Such plugins are not
+/// sandboxed, so they must be trusted.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct PluginConfig(pub String, pub serde_json::Value);
 
+/// Resource limits applied to each invocation of a plugin.
+///
+/// A plugin hitting one of these limits is cancelled, and the error names the
+/// plugin.
+pub use swc_common::plugin::limits::Limits as PluginLimits;
+
 #[cfg(feature = "plugin")]
 pub(crate) fn plugins(
     configured_plugins: Option<Vec<PluginConfig>>,
     plugin_env_vars: Option<Vec<Atom>>,
+    plugin_limits: Option<PluginLimits>,
+    reuse_plugin_instances: bool,
     metadata_context: std::sync::Arc<swc_common::plugin::metadata::TransformPluginMetadataContext>,
     comments: Option<swc_common::comments::SingleThreadedComments>,
     source_map: std::sync::Arc<swc_common::SourceMap>,
@@ -59,6 +72,8 @@ pub(crate) fn plugins(
     fold_pass(RustPlugins {
         plugins: configured_plugins,
         plugin_env_vars: plugin_env_vars.map(std::sync::Arc::new),
+        plugin_limits: plugin_limits.unwrap_or_default(),
+        reuse_plugin_instances,
         metadata_context,
         comments,
         source_map,
@@ -71,6 +86,8 @@ pub(crate) fn plugins(
 struct RustPlugins {
     plugins: Option<Vec<PluginConfig>>,
     plugin_env_vars: Option<std::sync::Arc<Vec<Atom>>>,
+    plugin_limits: PluginLimits,
+    reuse_plugin_instances: bool,
     metadata_context: std::sync::Arc<swc_common::plugin::metadata::TransformPluginMetadataContext>,
     comments: Option<swc_common::comments::SingleThreadedComments>,
     source_map: std::sync::Arc<swc_common::SourceMap>,
@@ -156,7 +173,14 @@ impl RustPlugins {
                                 plugin_module_bytes,
                                 Some(p.1),
                                 self.plugin_runtime.clone(),
-                            );
+                            )
+                            .with_limits(self.plugin_limits);
+                        if self.reuse_plugin_instances {
+                            transform_plugin_executor = transform_plugin_executor
+                                .with_instance_pool(
+                                    crate::config::PLUGIN_MODULE_CACHE.instance_pool.clone(),
+                                );
+                        }
 
                         let span = tracing::span!(
                             tracing::Level::INFO,
@@ -192,6 +216,23 @@ impl RustPlugins {
     }
 }
 
+#[cfg(feature = "plugin")]
+fn emit_plugin_error(err: &anyhow::Error) {
+    // The outermost context doesn't tell which plugin was cancelled.
+    let msg = if err
+        .downcast_ref::<swc_plugin_runner::runtime::LimitExceeded>()
+        .is_some()
+    {
+        format!("{err:#}")
+    } else {
+        err.to_string()
+    };
+
+    HANDLER.with(|handler| {
+        handler.err_with_code(&msg, DiagnosticId::Error("plugin".into()));
+    });
+}
+
 #[cfg(feature = "plugin")]
 impl Fold for RustPlugins {
     noop_fold_type!();
@@ -200,9 +241,7 @@ impl Fold for RustPlugins {
         match self.apply(Program::Module(n)) {
             Ok(program) => program.expect_module(),
             Err(err) => {
-                HANDLER.with(|handler| {
-                    handler.err_with_code(&err.to_string(), DiagnosticId::Error("plugin".into()));
-                });
+                emit_plugin_error(&err);
                 Module::default()
             }
         }
@@ -212,9 +251,7 @@ impl Fold for RustPlugins {
         match self.apply(Program::Script(n)) {
             Ok(program) => program.expect_script(),
             Err(err) => {
-                HANDLER.with(|handler| {
-                    handler.err_with_code(&err.to_string(), DiagnosticId::Error("plugin".into()));
-                });
+                emit_plugin_error(&err);
                 Script::default()
             }
         }
@@ -4,10 +4,14 @@ use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use common::{
-    GLOBALS, Mark, SourceFile,
+    GLOBALS, Mark, SourceFile, SourceMap,
     comments::SingleThreadedComments,
-    errors::Handler,
-    plugin::{metadata::TransformPluginMetadataContext, serialized::PluginSerializedBytes},
+    errors::{DiagnosticBuilder, Handler},
+    plugin::{
+        diagnostics::{PluginAnalysisDiagnostic, PluginDiagnosticSeverity},
+        metadata::TransformPluginMetadataContext,
+        serialized::PluginSerializedBytes,
+    },
 };
 use rustc_hash::FxHashMap;
 use serde::Deserialize;
@@ -15,6 +19,10 @@ use swc_config::is_module::IsModule;
 use swc_ecma_ast::EsVersion;
 use swc_ecma_parser::Syntax;
 use swc_ecma_transforms::resolver;
+use swc_error_reporters::{
+    ToPrettyDiagnostic,
+    handler::{HandlerOpts, to_pretty_handler},
+};
 use swc_plugin_proxy::HostCommentsStorage;
 
 use crate::{
@@ -25,6 +33,9 @@ use crate::{
 
 impl Compiler {
     /// Run analysis using Wasm plugins.
+    ///
+    /// Returns the output emitted by the plugins, serialized as JSON. The
+    /// diagnostics reported by the plugins are emitted to `handler`.
     pub fn run_wasm_analysis(
         &self,
         fm: Arc<SourceFile>,
@@ -32,6 +43,27 @@ impl Compiler {
         opts: &WasmAnalysisOptions,
         comments: &SingleThreadedComments,
     ) -> Result<String> {
+        let result = self.analyze_with_wasm_plugins(fm, handler, opts, comments)?;
+
+        for diagnostic in &result.diagnostics {
+            DiagnosticBuilder::new_diagnostic(handler, diagnostic.to_diagnostic()).emit();
+        }
+
+        serde_json::to_string(&result.output)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize output: {e}"))
+    }
+
+    /// Run analysis using Wasm plugins, returning the diagnostics they
+    /// reported along with their output.
+    ///
+    /// Use [WasmAnalysisReport] to aggregate the diagnostics of multiple files.
+    pub fn analyze_with_wasm_plugins(
+        &self,
+        fm: Arc<SourceFile>,
+        handler: &Handler,
+        opts: &WasmAnalysisOptions,
+        comments: &SingleThreadedComments,
+    ) -> Result<WasmAnalysisResult> {
         if cfg!(feature = "manual-tokio-runtime") {
             self.run_wasm_analysis_inner(fm.clone(), handler, opts, comments)
         } else {
@@ -53,7 +85,7 @@ impl Compiler {
         handler: &Handler,
         opts: &WasmAnalysisOptions,
         comments: &SingleThreadedComments,
-    ) -> Result<String> {
+    ) -> Result<WasmAnalysisResult> {
         let plugin_runtime = opts
             .runtime_options
             .plugin_runtime
@@ -94,7 +126,7 @@ impl Compiler {
                     None,
                 ));
 
-                let result = opts
+                let results = opts
                     .plugins
                     .iter()
                     .map(|p| {
@@ -111,10 +143,13 @@ impl Compiler {
                     })
                     .collect::<Result<Vec<_>>>()?;
 
-                let result = result.into_iter().flatten().collect::<FxHashMap<_, _>>();
+                let mut result = WasmAnalysisResult::default();
+                for (output, diagnostics) in results {
+                    result.output.extend(output);
+                    result.diagnostics.extend(diagnostics);
+                }
 
-                serde_json::to_string(&result)
-                    .map_err(|e| anyhow::anyhow!("Failed to serialize output: {e}"))
+                Ok(result)
             })
         })
     }
@@ -127,7 +162,7 @@ impl Compiler {
         transform_metadata_context: &Arc<TransformPluginMetadataContext>,
         p: &PluginConfig,
         comments: &SingleThreadedComments,
-    ) -> Result<FxHashMap<String, String>> {
+    ) -> Result<(FxHashMap<String, String>, Vec<PluginAnalysisDiagnostic>)> {
         swc_plugin_proxy::COMMENTS.set(
             &HostCommentsStorage {
                 inner: Some(comments.clone()),
@@ -160,20 +195,23 @@ impl Compiler {
                 )
                 .entered();
 
-                let (result, output) = swc_transform_common::output::capture(|| {
-                    transform_plugin_executor
-                        .transform(serialized, Some(true))
-                        .with_context(|| {
-                            format!(
-                                "failed to invoke `{}` as js analysis plugin at {}",
-                                &p.0, plugin_name
-                            )
+                let ((result, output), diagnostics) =
+                    swc_transform_common::diagnostics::capture(|| {
+                        swc_transform_common::output::capture(|| {
+                            transform_plugin_executor
+                                .transform(serialized, Some(true))
+                                .with_context(|| {
+                                    format!(
+                                        "failed to invoke `{}` as js analysis plugin at {}",
+                                        &p.0, plugin_name
+                                    )
+                                })
                         })
-                });
+                    });
                 result?;
                 drop(span);
 
-                Ok(output)
+                Ok((output, diagnostics))
             },
         )
     }
@@ -201,3 +239,63 @@ pub struct WasmAnalysisOptions {
     #[serde(skip, default)]
     pub runtime_options: RuntimeOptions,
 }
+
+/// The result of [Compiler::analyze_with_wasm_plugins] for a single file.
+#[derive(Debug, Default)]
+pub struct WasmAnalysisResult {
+    /// The output emitted by the plugins with
+    /// `swc_transform_common::output::experimental_emit`.
+    pub output: FxHashMap<String, String>,
+    /// The diagnostics reported by the plugins with
+    /// `swc_transform_common::diagnostics::report`.
+    pub diagnostics: Vec<PluginAnalysisDiagnostic>,
+}
+
+/// Diagnostics reported by analysis plugins, aggregated across files.
+///
+/// Spans are resolved with the [SourceMap] of the [Compiler] which analyzed the
+/// files, so all of them should be analyzed by the same compiler.
+#[derive(Debug, Default)]
+pub struct WasmAnalysisReport {
+    diagnostics: Vec<PluginAnalysisDiagnostic>,
+}
+
+impl WasmAnalysisReport {
+    pub fn add(&mut self, result: WasmAnalysisResult) {
+        self.diagnostics.extend(result.diagnostics);
+    }
+
+    /// Returns the diagnostics, ordered by their position.
+    pub fn diagnostics(&self) -> Vec<&PluginAnalysisDiagnostic> {
+        let mut diagnostics = self.diagnostics.iter().collect::<Vec<_>>();
+        diagnostics.sort_by_key(|d| (d.span.lo, d.span.hi));
+        diagnostics
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity.is_error())
+            .count()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == PluginDiagnosticSeverity::Warning)
+            .count()
+    }
+
+    /// Renders the diagnostics, ordered by their position.
+    pub fn render(&self, cm: &SourceMap, opts: HandlerOpts) -> String {
+        let handler = to_pretty_handler(opts.color);
+
+        self.diagnostics()
+            .into_iter()
+            .map(|d| {
+                d.to_diagnostic()
+                    .to_pretty_string(cm, opts.skip_filename, &handler)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,10 @@
+import React from "react";
+import debounce from "lodash/debounce.js";
+import type { Props } from "./types";
+import { format } from "@lib/format";
+import { App } from "./app.tsx";
+export * from "./utils";
+
+export function render(props: Props) {
+    return import("./lazy").then(() => format(App, React, debounce, props));
+}
@@ -0,0 +1 @@
+export const format = (...args: unknown[]) => args;
@@ -0,0 +1,8 @@
+import React from "https://esm.sh/react@19";
+import debounce from "/vendor/lodash/debounce.js";
+import { format } from "./lib/format.js";
+import { App } from "./app.js";
+export * from "./utils.js";
+export function render(props) {
+    return import("./lazy.js").then(()=>format(App, React, debounce, props));
+}
@@ -15,7 +15,7 @@ use swc::{
     try_with_handler,
 };
 use swc_common::{
-    BytePos, FileName, GLOBALS, Globals, SourceMap,
+    BytePos, FileName, GLOBALS, Globals, Mark, SourceMap, SyntaxContext,
     comments::{Comment, SingleThreadedComments},
     errors::{EmitterWriter, HANDLER, Handler},
     sync::Lrc,
@@ -1183,6 +1183,94 @@ fn issue_7513_2() {
     );
 }
 
+#[test]
+fn minify_name_cache() {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("minify_name_cache.json");
+    let _ = std::fs::remove_file(&path);
+
+    // `unused_marks` changes the syntax context of the top-level variables.
+    let minify = |src: &str, unused_marks: usize| {
+        let cm = Lrc::<SourceMap>::default();
+        let c = swc::Compiler::new(cm.clone());
+
+        GLOBALS
+            .set(&Default::default(), || {
+                for _ in 0..unused_marks {
+                    SyntaxContext::empty().apply_mark(Mark::new());
+                }
+
+                try_with_handler(cm.clone(), Default::default(), |handler| {
+                    let fm = cm.new_source_file(FileName::Anon.into(), src.to_string());
+
+                    c.minify(
+                        fm,
+                        handler,
+                        &JsMinifyOptions {
+                            compress: BoolOrDataConfig::from_bool(false),
+                            mangle: BoolOrDataConfig::from_bool(true),
+                            toplevel: Some(true),
+                            name_cache: Some(path.to_string_lossy().into_owned()),
+                            ..Default::default()
+                        },
+                        Default::default(),
+                    )
+                    .context("failed to minify")
+                })
+            })
+            .unwrap()
+            .code
+    };
+
+    assert_eq!(
+        minify("var alpha = 1; console.log(alpha);", 0),
+        "var o=1;console.log(o);"
+    );
+    assert!(std::fs::read_to_string(&path).unwrap().contains("alpha"));
+
+    assert_eq!(
+        minify("var beta = 2; var alpha = 1; console.log(beta, alpha);", 3),
+        "var a=2;var o=1;console.log(a,o);"
+    );
+}
+
+#[test]
+fn minify_size_report() {
+    let cm = Lrc::<SourceMap>::default();
+    let c = swc::Compiler::new(cm.clone());
+
+    let output = GLOBALS
+        .set(&Default::default(), || {
+            try_with_handler(cm.clone(), Default::default(), |handler| {
+                let fm = cm.new_source_file(
+                    FileName::Anon.into(),
+                    "function add(a, b) { if (true) { return a + b; } return 0; }
+console.log(add(1, 2));",
+                );
+
+                c.minify(
+                    fm,
+                    handler,
+                    &JsMinifyOptions {
+                        compress: BoolOrDataConfig::from_bool(true),
+                        mangle: BoolOrDataConfig::from_bool(true),
+                        size_report: true,
+                        ..Default::default()
+                    },
+                    Default::default(),
+                )
+                .context("failed to minify")
+            })
+        })
+        .unwrap();
+
+    let output: serde_json::Value = serde_json::from_str(&output.output.unwrap()).unwrap();
+    let report: serde_json::Value =
+        serde_json::from_str(output["sizeReport"].as_str().unwrap()).unwrap();
+
+    assert!(!report["passes"].as_array().unwrap().is_empty(), "{report}");
+    assert_eq!(report["functions"][0]["name"], "add", "{report}");
+}
+
 #[test]
 fn issue_8674_1() {
     static INPUT: &str = "import { foo } from 'src/foo'";
@@ -0,0 +1,64 @@
+#![cfg(feature = "plugin")]
+
+use swc::{
+    HandlerOpts,
+    wasm_analysis::{WasmAnalysisReport, WasmAnalysisResult},
+};
+use swc_common::{
+    FileName, SourceFile, Span,
+    errors::ColorConfig,
+    plugin::diagnostics::{PluginAnalysisDiagnostic, PluginDiagnosticSeverity},
+};
+
+fn result(fm: &SourceFile, severity: PluginDiagnosticSeverity) -> WasmAnalysisResult {
+    WasmAnalysisResult {
+        diagnostics: vec![PluginAnalysisDiagnostic {
+            span: Span::new(fm.start_pos, fm.end_pos),
+            severity,
+            code: Some("no-debugger".into()),
+            message: "Unexpected debugger statement".into(),
+            fixes: Vec::new(),
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn aggregated_across_files() {
+    testing::run_test(false, |cm, _handler| {
+        let a = cm.new_source_file(FileName::Real("a.js".into()).into(), "debugger;");
+        let b = cm.new_source_file(FileName::Real("b.js".into()).into(), "debugger;");
+
+        let mut report = WasmAnalysisReport::default();
+        report.add(result(&b, PluginDiagnosticSeverity::Warning));
+        report.add(result(&a, PluginDiagnosticSeverity::Error));
+
+        assert_eq!(report.error_count(), 1);
+        assert_eq!(report.warning_count(), 1);
+        assert_eq!(
+            report
+                .diagnostics()
+                .iter()
+                .map(|d| d.severity)
+                .collect::<Vec<_>>(),
+            [
+                PluginDiagnosticSeverity::Error,
+                PluginDiagnosticSeverity::Warning
+            ]
+        );
+
+        let rendered = report.render(
+            &cm,
+            HandlerOpts {
+                color: ColorConfig::Never,
+                skip_filename: false,
+            },
+        );
+        assert!(rendered.contains("no-debugger"), "{rendered}");
+        assert!(rendered.contains("a.js"), "{rendered}");
+        assert!(rendered.contains("b.js"), "{rendered}");
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -87,6 +87,7 @@ fn do_test(_entry: &Path, entries: HashMap<String, FileName>, inline: bool, mini
                 disable_hygiene: minify,
                 disable_dce: false,
                 module: Default::default(),
+                code_splitting: None,
             },
             Box::new(Hook),
         );
@@ -132,6 +133,7 @@ fn do_test(_entry: &Path, entries: HashMap<String, FileName>, inline: bool, mini
                                 unresolved_mark: Mark::new(),
                                 top_level_mark: Mark::new(),
                                 mangle_name_cache: None,
+                                size_reporter: None,
                             },
                         )
                         .expect_module();
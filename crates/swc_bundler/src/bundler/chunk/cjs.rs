@@ -204,7 +204,7 @@ where
                 if i.sym == *"require" && node.args.len() == 1 {
                     if let Expr::Lit(Lit::Str(module_name)) = &*node.args[0].expr {
                         let module_atom = module_name.value.to_atom_lossy();
-                        if self.bundler.is_external(module_atom.as_ref()) {
+                        if self.bundler.is_external(self.base.id, module_atom.as_ref()) {
                             return;
                         }
                         let load = CallExpr {
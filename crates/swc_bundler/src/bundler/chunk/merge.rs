@@ -167,14 +167,26 @@ where
             let info = self.scope.get_module(module_id).unwrap();
 
             let mut entry: Module = (*info.module).clone();
-            entry.visit_mut_with(&mut ImportMetaHandler {
+            let mut import_meta_handler = ImportMetaHandler {
                 file: &info.fm.name,
                 hook: &self.hook,
                 is_entry,
                 inline_ident: private_ident!("importMeta"),
                 occurred: false,
                 err: None,
-            });
+            };
+            entry.visit_mut_with(&mut import_meta_handler);
+
+            if import_meta_handler.occurred {
+                // Used to rewrite `import.meta` for each chunk while finalizing.
+                self.scope.store_import_meta_var(
+                    import_meta_handler.inline_ident.into(),
+                    ModuleRecord {
+                        file_name: (*info.fm.name).clone(),
+                        is_entry,
+                    },
+                );
+            }
 
             let module = Modules::from(module_id, entry, self.injected_ctxt);
 
@@ -449,6 +461,20 @@ where
 
         inline(self.injected_ctxt, entry);
 
+        // Imports are owned by the entry once sorted, so the module which
+        // contains each of them is checked beforehand.
+        let externals = entry
+            .iter()
+            .filter_map(|(module_id, item)| {
+                let src = module_decl_src(item)?;
+                let src_atom = src.value.to_atom_lossy().into_owned();
+                self.is_external(module_id, &src_atom)
+                    .then_some((src.span, src_atom))
+            })
+            .collect::<FxHashSet<_>>();
+        let is_external =
+            |src: &Str| externals.contains(&(src.span, src.value.to_atom_lossy().into_owned()));
+
         entry.sort(id, &ctx.graph, &ctx.cycles, &self.cm);
 
         // crate::debug::print_hygiene("done", &self.cm, &entry.clone().into());
@@ -456,8 +482,7 @@ where
         entry.retain_mut(|_, item| {
             match item {
                 ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => {
-                    let src_atom = export.src.value.to_atom_lossy().into_owned();
-                    if self.config.external_modules.contains(&src_atom) {
+                    if is_external(&export.src) {
                         return true;
                     }
 
@@ -466,8 +491,7 @@ where
 
                 ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
                     if let Some(src) = &export.src {
-                        let src_atom = src.value.to_atom_lossy().into_owned();
-                        if self.config.external_modules.contains(&src_atom) {
+                        if is_external(src) {
                             return true;
                         }
                     }
@@ -480,8 +504,7 @@ where
                 }
 
                 ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
-                    let src_atom = import.src.value.to_atom_lossy().into_owned();
-                    if self.config.external_modules.contains(&src_atom) {
+                    if is_external(&import.src) {
                         return true;
                     }
 
@@ -594,7 +617,7 @@ where
 
         let mut extra = Vec::new();
 
-        module.map_any_items(|_, items| {
+        module.map_any_items(|module_id, items| {
             let mut new = Vec::with_capacity(items.len() * 11 / 10);
 
             for item in items {
@@ -602,7 +625,7 @@ where
                     ModuleItem::ModuleDecl(ModuleDecl::Import(mut import)) => {
                         let src_atom = import.src.value.to_atom_lossy().into_owned();
                         // Preserve imports from node.js builtin modules.
-                        if self.config.external_modules.contains(&src_atom) {
+                        if self.is_external(module_id, &src_atom) {
                             new.push(import.into());
                             continue;
                         }
@@ -1281,7 +1304,7 @@ where
             for stmt in stmts {
                 if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = &stmt {
                     let src_atom = import.src.value.to_atom_lossy().into_owned();
-                    if self.config.external_modules.contains(&src_atom) {
+                    if self.is_external(module_id, &src_atom) {
                         new.push(stmt);
                         continue;
                     }
@@ -1438,3 +1461,13 @@ impl VisitMut for ImportMetaHandler<'_, '_> {
         }
     }
 }
+
+/// Returns the source of an import or a re-export.
+fn module_decl_src(item: &ModuleItem) -> Option<&Str> {
+    match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl { src, .. }))
+        | ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll { src, .. })) => Some(src),
+        ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport { src, .. })) => src.as_deref(),
+        _ => None,
+    }
+}
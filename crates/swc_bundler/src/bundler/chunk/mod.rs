@@ -111,6 +111,8 @@ where
                         kind,
                         id,
                         module: module.into(),
+                        banner: None,
+                        footer: None,
                     }
                 })
                 .collect()
@@ -137,6 +139,8 @@ where
                         kind,
                         id,
                         module: module.into(),
+                        banner: None,
+                        footer: None,
                     }
                 })
                 .collect()
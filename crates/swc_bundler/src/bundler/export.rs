@@ -10,7 +10,12 @@ use super::{
     Bundler,
     load::{Source, Specifier},
 };
-use crate::{id::Id, load::Load, resolve::Resolve, util::ExportMetadata};
+use crate::{
+    id::{Id, ModuleId},
+    load::Load,
+    resolve::Resolve,
+    util::ExportMetadata,
+};
 
 impl<L, R> Bundler<'_, L, R>
 where
@@ -26,8 +31,10 @@ where
         export_ctxt: SyntaxContext,
     ) -> RawExports {
         self.run(|| {
+            let (module_id, _, _) = self.scope.module_id_gen.r#gen(file_name);
             let mut v = ExportFinder {
                 info: Default::default(),
+                module_id,
                 file_name,
                 bundler: self,
                 export_ctxt,
@@ -58,6 +65,7 @@ where
     R: Resolve,
 {
     info: RawExports,
+    module_id: ModuleId,
     file_name: &'a FileName,
     bundler: &'a Bundler<'b, L, R>,
     export_ctxt: SyntaxContext,
@@ -71,13 +79,7 @@ where
     /// Returns `(local, export)`.
     fn ctxt_for(&self, src: &Atom) -> Option<(SyntaxContext, SyntaxContext)> {
         // Don't apply mark if it's a core module.
-        if self
-            .bundler
-            .config
-            .external_modules
-            .iter()
-            .any(|v| v == src)
-        {
+        if self.bundler.is_external(self.module_id, src) {
             return None;
         }
         let path = self.bundler.resolve(self.file_name, src).ok()?;
@@ -91,13 +93,7 @@ where
 
     fn mark_as_wrapping_required(&self, src: &Atom) {
         // Don't apply mark if it's a core module.
-        if self
-            .bundler
-            .config
-            .external_modules
-            .iter()
-            .any(|v| v == src)
-        {
+        if self.bundler.is_external(self.module_id, src) {
             return;
         }
         let path = self.bundler.resolve(self.file_name, src);
@@ -12,9 +12,13 @@ use swc_ecma_transforms_base::{
     hygiene::hygiene,
 };
 use swc_ecma_utils::{ExprFactory, contains_top_level_await, find_pat_ids, private_ident};
-use swc_ecma_visit::{Fold, FoldWith, VisitMutWith, noop_fold_type};
+use swc_ecma_visit::{Fold, FoldWith, VisitMut, VisitMutWith, noop_fold_type, noop_visit_mut_type};
 
-use crate::{Bundle, BundleKind, Bundler, Load, ModuleType, Resolve, hash::calc_hash};
+use super::scope::Scope;
+use crate::{
+    Bundle, BundleKind, Bundler, ChunkRecord, Hook, ImportMetaProp, Load, ModuleType, Resolve,
+    hash::calc_hash,
+};
 
 impl<L, R> Bundler<'_, L, R>
 where
@@ -36,6 +40,8 @@ where
             let mut renamed = FxHashMap::default();
 
             for mut bundle in bundles {
+                self.rewrite_import_meta(&mut bundle)?;
+
                 bundle.module = self.optimize(bundle.module);
 
                 if !self.config.disable_hygiene {
@@ -75,30 +81,48 @@ where
 
                         helpers.add_to(&mut bundle.module.body);
 
+                        if let Some(name) = self.chunk_file_name(&bundle)? {
+                            bundle.kind = BundleKind::Named { name };
+                        }
+
                         new.push(bundle);
                     }
-                    BundleKind::Lib { name } => {
-                        let hash = calc_hash(self.cm.clone(), &bundle.module)?;
+                    BundleKind::Lib { .. } => {
+                        let file_name = self.chunk_file_name(&bundle)?;
+                        let BundleKind::Lib { name } = bundle.kind else {
+                            unreachable!()
+                        };
+
                         let mut new_name = PathBuf::from(name);
                         let key = new_name.clone();
-                        let file_name = new_name
-                            .file_name()
-                            .map(|path| -> PathBuf {
-                                let path = Path::new(path);
-                                let ext = path.extension();
-                                if let Some(ext) = ext {
-                                    return format!(
-                                        "{}-{}.{}",
-                                        path.file_stem().unwrap().to_string_lossy(),
-                                        hash,
-                                        ext.to_string_lossy()
-                                    )
-                                    .into();
-                                }
-                                format!("{}-{}", path.file_stem().unwrap().to_string_lossy(), hash,)
-                                    .into()
-                            })
-                            .expect("javascript file should have name");
+                        let file_name = match file_name {
+                            Some(file_name) => PathBuf::from(file_name),
+                            None => {
+                                let hash = calc_hash(self.cm.clone(), &bundle.module)?;
+                                new_name
+                                    .file_name()
+                                    .map(|path| -> PathBuf {
+                                        let path = Path::new(path);
+                                        let ext = path.extension();
+                                        if let Some(ext) = ext {
+                                            return format!(
+                                                "{}-{}.{}",
+                                                path.file_stem().unwrap().to_string_lossy(),
+                                                hash,
+                                                ext.to_string_lossy()
+                                            )
+                                            .into();
+                                        }
+                                        format!(
+                                            "{}-{}",
+                                            path.file_stem().unwrap().to_string_lossy(),
+                                            hash,
+                                        )
+                                        .into()
+                                    })
+                                    .expect("javascript file should have name")
+                            }
+                        };
                         new_name.pop();
                         new_name = new_name.join(file_name.clone());
 
@@ -115,6 +139,10 @@ where
                 }
             }
 
+            for bundle in &mut new {
+                self.add_banner_and_footer(bundle)?;
+            }
+
             if new.len() == 1 {
                 return Ok(new);
             }
@@ -145,6 +173,51 @@ where
         })
     }
 
+    /// Applies [Hook::rewrite_import_meta] to the variables which replace
+    /// `import.meta`.
+    fn rewrite_import_meta(&self, bundle: &mut Bundle) -> Result<(), Error> {
+        let mut v = ImportMetaRewriter {
+            hook: &*self.hook,
+            scope: &self.scope,
+            chunk_record: ChunkRecord {
+                kind: bundle.kind.clone(),
+            },
+            err: None,
+        };
+        bundle.module.visit_mut_with(&mut v);
+
+        match v.err {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the name of the output file of `bundle` returned by
+    /// [Hook::get_chunk_file_name].
+    pub(super) fn chunk_file_name(&self, bundle: &Bundle) -> Result<Option<String>, Error> {
+        let name = self.hook.get_chunk_file_name(&ChunkRecord {
+            kind: bundle.kind.clone(),
+        })?;
+
+        match name {
+            Some(name) if name.contains("[hash]") => {
+                let hash = calc_hash(self.cm.clone(), &bundle.module)?;
+                Ok(Some(name.replace("[hash]", &hash)))
+            }
+            _ => Ok(name),
+        }
+    }
+
+    pub(super) fn add_banner_and_footer(&self, bundle: &mut Bundle) -> Result<(), Error> {
+        let chunk_record = ChunkRecord {
+            kind: bundle.kind.clone(),
+        };
+
+        bundle.banner = self.hook.get_banner(&chunk_record)?;
+        bundle.footer = self.hook.get_footer(&chunk_record)?;
+        Ok(())
+    }
+
     fn may_wrap_with_iife(&self, module: Module) -> Module {
         if self.config.module != ModuleType::Iife {
             return module;
@@ -394,6 +467,72 @@ where
     }
 }
 
+struct ImportMetaRewriter<'a> {
+    hook: &'a dyn Hook,
+    scope: &'a Scope,
+    chunk_record: ChunkRecord,
+    err: Option<Error>,
+}
+
+impl ImportMetaRewriter<'_> {
+    fn rewrite(&self, e: &Expr) -> Result<Option<Box<Expr>>, Error> {
+        let (span, obj, prop) = match e {
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop: MemberProp::Ident(prop),
+            }) if prop.sym == "url" => (*span, obj, ImportMetaProp::Url),
+            Expr::Call(CallExpr {
+                span,
+                callee: Callee::Expr(callee),
+                args,
+                ..
+            }) => match (&**callee, &**args) {
+                (
+                    Expr::Member(MemberExpr {
+                        obj,
+                        prop: MemberProp::Ident(prop),
+                        ..
+                    }),
+                    [ExprOrSpread { spread: None, expr }],
+                ) if prop.sym == "resolve" => {
+                    (*span, obj, ImportMetaProp::Resolve { specifier: expr })
+                }
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        let Expr::Ident(obj) = &**obj else {
+            return Ok(None);
+        };
+        let Some(module_record) = self.scope.get_import_meta_var(&obj.into()) else {
+            return Ok(None);
+        };
+
+        self.hook
+            .rewrite_import_meta(span, prop, &module_record, &self.chunk_record)
+    }
+}
+
+impl VisitMut for ImportMetaRewriter<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_expr(&mut self, e: &mut Expr) {
+        e.visit_mut_children_with(self);
+
+        if self.err.is_some() {
+            return;
+        }
+
+        match self.rewrite(e) {
+            Ok(Some(new)) => *e = *new,
+            Ok(None) => {}
+            Err(err) => self.err = Some(err),
+        }
+    }
+}
+
 /// Import renamer. This pass changes import path.
 struct Renamer<'a, R>
 where
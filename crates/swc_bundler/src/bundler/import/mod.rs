@@ -1,4 +1,4 @@
-use anyhow::{Context, Error};
+use anyhow::{Context, Error, bail};
 use rustc_hash::{FxHashMap, FxHashSet};
 use swc_atoms::Atom;
 use swc_common::{DUMMY_SP, FileName, Mark, Spanned, SyntaxContext, sync::Lrc};
@@ -7,7 +7,7 @@ use swc_ecma_utils::find_pat_ids;
 use swc_ecma_visit::{VisitMut, VisitMutWith, noop_visit_mut_type};
 
 use super::Bundler;
-use crate::{load::Load, resolve::Resolve, util::ExportMetadata};
+use crate::{UnresolvedImport, id::ModuleId, load::Load, resolve::Resolve, util::ExportMetadata};
 
 #[cfg(test)]
 mod tests;
@@ -25,8 +25,10 @@ where
         module_local_mark: Mark,
     ) -> RawImports {
         self.run(|| {
+            let (module_id, _, _) = self.scope.module_id_gen.r#gen(path);
             let mut v = ImportHandler {
                 module_ctxt: SyntaxContext::empty().apply_mark(module_local_mark),
+                module_id,
                 path,
                 bundler: self,
                 top_level: false,
@@ -51,11 +53,25 @@ where
         module_specifier: &str,
     ) -> Result<Lrc<FileName>, Error> {
         self.run(|| {
-            let path = self
+            let path = match self
                 .resolver
                 .resolve(base, module_specifier)
-                .map(|v| v.filename)
-                .with_context(|| format!("failed to resolve {module_specifier} from {base}"))?;
+                .with_context(|| format!("failed to resolve {module_specifier} from {base}"))
+            {
+                Ok(v) => v.filename,
+                Err(err) => match self
+                    .hook
+                    .on_unresolved_import(base, module_specifier, err)?
+                {
+                    UnresolvedImport::External => {
+                        let (importer, _, _) = self.scope.module_id_gen.r#gen(base);
+                        self.scope
+                            .mark_as_unresolved_external(importer, module_specifier.into());
+                        bail!("{module_specifier} is an external module")
+                    }
+                    UnresolvedImport::Replace(path) => path,
+                },
+            };
 
             let path = Lrc::new(path);
 
@@ -105,6 +121,7 @@ where
     /// The [SyntaxContext] for the top level module items.
     //// The top level module items includes imported bindings.
     module_ctxt: SyntaxContext,
+    module_id: ModuleId,
     path: &'a FileName,
     bundler: &'a Bundler<'b, L, R>,
     top_level: bool,
@@ -148,7 +165,7 @@ where
     /// Returns (local, export)
     fn ctxt_for(&self, src: &Atom) -> Option<(SyntaxContext, SyntaxContext)> {
         // Don't apply mark if it's a core module.
-        if self.bundler.is_external(src) {
+        if self.bundler.is_external(self.module_id, src) {
             return None;
         }
         let path = self.bundler.resolve(self.path, src).ok()?;
@@ -162,7 +179,7 @@ where
 
     fn mark_as_wrapping_required(&self, src: &Atom) {
         // Don't apply mark if it's a core module.
-        if self.bundler.is_external(src) {
+        if self.bundler.is_external(self.module_id, src) {
             return;
         }
         let path = self.bundler.resolve(self.path, src);
@@ -223,7 +240,7 @@ where
                         if self.bundler.config.require && callee.is_ident_ref_to("require") =>
                     {
                         let src_atom = src.value.to_atom_lossy();
-                        if self.bundler.is_external(src_atom.as_ref()) {
+                        if self.bundler.is_external(self.module_id, src_atom.as_ref()) {
                             return;
                         }
                         if let Expr::Ident(i) = &mut **callee {
@@ -252,19 +269,14 @@ where
                         self.info.lazy_imports.push(decl);
                     }
 
-                    // TODO: Uncomment this after implementing an option to make swc_bundler
-                    // includes dynamic imports
-                    //
-                    //
-                    // ExprOrSuper::Expr(ref e) => match &**e {
-                    //     Expr::Ident(Ident {
-                    //         sym: "import",
-                    //         ..
-                    //     }) => {
-                    //         self.info.dynamic_imports.push(src.clone());
-                    //     }
-                    //     _ => {}
-                    // },
+                    Callee::Import(_) if self.bundler.splits_dynamic_imports() => {
+                        let src_atom = src.value.to_atom_lossy();
+                        if self.bundler.is_external(self.module_id, src_atom.as_ref()) {
+                            return;
+                        }
+
+                        self.info.dynamic_imports.push(src.clone());
+                    }
                     _ => {}
                 }
             }
@@ -420,7 +432,7 @@ where
     fn visit_mut_import_decl(&mut self, import: &mut ImportDecl) {
         let src_atom = import.src.value.to_atom_lossy().into_owned();
         // Ignore if it's a core module.
-        if self.bundler.is_external(&src_atom) {
+        if self.bundler.is_external(self.module_id, &src_atom) {
             return;
         }
 
@@ -558,7 +570,7 @@ where
             for import in self.info.imports.iter_mut() {
                 let src_atom = import.src.value.to_atom_lossy().into_owned();
                 let use_ns = self.info.forced_ns.contains(&import.src.value)
-                    || self.bundler.config.external_modules.contains(&src_atom);
+                    || self.bundler.is_external(self.module_id, &src_atom);
 
                 if use_ns {
                     wrapping_required.push(import.src.value.clone());
@@ -626,12 +638,7 @@ where
                     };
                     // Ignore core modules.
                     let src_atom = src.value.to_atom_lossy();
-                    if self
-                        .bundler
-                        .config
-                        .external_modules
-                        .contains(src_atom.as_ref())
-                    {
+                    if self.bundler.is_external(self.module_id, src_atom.as_ref()) {
                         return;
                     }
 
@@ -226,18 +226,39 @@ where
     ) -> Result<(Exports, Vec<(Source, Lrc<FileName>)>), Error> {
         self.run(|| {
             tracing::trace!("resolve_exports({})", base);
+            let (importer, _, _) = self.scope.module_id_gen.r#gen(base);
             let mut files = Vec::new();
 
             let mut exports = Exports::default();
 
             let items = raw
                 .items
+                .into_iter()
+                // Re-exports of external modules are preserved as is by code
+                // splitting.
+                .filter(|(src, _)| {
+                    self.config.code_splitting.is_none()
+                        || src.as_ref().map_or(true, |src| {
+                            !self.is_external(importer, &src.value.to_atom_lossy())
+                        })
+                })
+                .collect::<Vec<_>>()
                 .into_par_iter()
                 .map(|(src, ss)| -> Result<_, Error> {
                     self.run(|| {
                         let info = match src {
                             Some(src) => {
-                                let name = self.resolve(base, &src.value.to_string_lossy())?;
+                                let name = match self.resolve(base, &src.value.to_string_lossy()) {
+                                    Ok(name) => name,
+                                    // Made external by the hook.
+                                    Err(_)
+                                        if self
+                                            .is_external(importer, &src.value.to_atom_lossy()) =>
+                                    {
+                                        return Ok(None);
+                                    }
+                                    Err(err) => return Err(err),
+                                };
                                 let (id, local_mark, export_mark) =
                                     self.scope.module_id_gen.r#gen(&name);
                                 Some((id, local_mark, export_mark, name, src))
@@ -245,13 +266,15 @@ where
                             None => None,
                         };
 
-                        Ok((info, ss))
+                        Ok(Some((info, ss)))
                     })
                 })
                 .collect::<Vec<_>>();
 
             for res in items {
-                let (info, specifiers) = res?;
+                let Some((info, specifiers)) = res? else {
+                    continue;
+                };
 
                 match info {
                     None => exports.items.extend(specifiers),
@@ -283,6 +306,7 @@ where
     ) -> Result<(Imports, Vec<(Source, Lrc<FileName>)>), Error> {
         self.run(|| {
             tracing::trace!("resolve_imports({})", base);
+            let (importer, _, _) = self.scope.module_id_gen.r#gen(base);
             let mut files = Vec::new();
 
             let mut merged = Imports::default();
@@ -314,11 +338,21 @@ where
                 .map(|(decl, dynamic, unconditional)| -> Result<_, Error> {
                     self.run(|| {
                         //
-                        let file_name = self.resolve(base, &decl.src.value.to_string_lossy())?;
+                        let file_name = match self.resolve(base, &decl.src.value.to_string_lossy())
+                        {
+                            Ok(file_name) => file_name,
+                            // Made external by the hook.
+                            Err(_)
+                                if self.is_external(importer, &decl.src.value.to_atom_lossy()) =>
+                            {
+                                return Ok(None);
+                            }
+                            Err(err) => return Err(err),
+                        };
                         let (id, local_mark, export_mark) =
                             self.scope.module_id_gen.r#gen(&file_name);
 
-                        Ok((
+                        Ok(Some((
                             id,
                             local_mark,
                             export_mark,
@@ -326,15 +360,25 @@ where
                             decl,
                             dynamic,
                             unconditional,
-                        ))
+                        )))
                     })
                 })
                 .collect::<Vec<_>>();
 
             for res in loaded {
                 // TODO: Report error and proceed instead of returning an error
-                let (id, local_mark, export_mark, file_name, decl, is_dynamic, is_unconditional) =
-                    res?;
+                let Some((
+                    id,
+                    local_mark,
+                    export_mark,
+                    file_name,
+                    decl,
+                    is_dynamic,
+                    is_unconditional,
+                )) = res?
+                else {
+                    continue;
+                };
 
                 let src = Source {
                     is_loaded_synchronously: !is_dynamic,
@@ -18,6 +18,7 @@ mod keywords;
 mod load;
 mod optimize;
 mod scope;
+mod split;
 #[cfg(test)]
 pub(crate) mod tests;
 
@@ -45,9 +46,53 @@ pub struct Config {
 
     /// Type of emitted module
     pub module: ModuleType,
+
+    /// If set, modules shared by multiple entries are emitted as separate
+    /// chunks instead of being duplicated into each entry.
+    ///
+    /// This requires [ModuleType::Es].
+    pub code_splitting: Option<CodeSplittingConfig>,
+}
+
+/// Configuration for code splitting.
+///
+/// Each entry is bundled into its own chunk, and a module imported by more
+/// than one chunk gets a chunk of its own, which is emitted as
+/// [BundleKind::Lib]. Imports of a module in another chunk are rewritten to
+/// import the chunk, using a path relative to the name of the importing chunk.
+///
+/// Only ES module imports are split. Modules imported with `require` are
+/// merged into each chunk which requires them.
+#[derive(Debug, Default)]
+pub struct CodeSplittingConfig {
+    /// If true, modules imported with `import()` get chunks of their own, and
+    /// the `import()` calls are rewritten to import the chunks.
+    pub dynamic_imports: bool,
+
+    /// A shared module is duplicated instead of getting a chunk of its own if
+    /// the source code of it and its dependencies is smaller than this, in
+    /// bytes.
+    pub min_chunk_size: usize,
+
+    /// Groups of modules which should be emitted as a single chunk, like
+    /// vendor libraries.
+    pub manual_chunks: Vec<ManualChunk>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ManualChunk {
+    /// Name of the chunk, like `vendor.js`.
+    pub name: String,
+
+    /// Modules of the chunk. Dependencies of these modules which are not used
+    /// by other chunks are also included.
+    ///
+    /// If there is more than one module, the chunk re-exports them under
+    /// generated names, and imports of them are rewritten accordingly.
+    pub modules: Vec<FileName>,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum ModuleType {
     #[default]
     Es,
@@ -61,6 +106,8 @@ pub enum BundleKind {
     /// Auto-generated entry (created by import expression)
     Dynamic,
     /// A lazy-loaded shared library
+    ///
+    /// Chunks created by code splitting use this kind.
     Lib { name: String },
 }
 
@@ -71,6 +118,10 @@ pub struct Bundle {
     pub id: ModuleId,
     /// Merged module.
     pub module: Module,
+    /// Code to prepend to the output file, returned by [Hook::get_banner].
+    pub banner: Option<String>,
+    /// Code to append to the output file, returned by [Hook::get_footer].
+    pub footer: Option<String>,
 }
 
 pub struct Bundler<'a, L, R>
@@ -139,8 +190,19 @@ where
         })
     }
 
-    pub(crate) fn is_external(&self, src: &Atom) -> bool {
+    /// Returns true if `src` imported by the module `importer` is preserved
+    /// as an import.
+    pub(crate) fn is_external(&self, importer: ModuleId, src: &Atom) -> bool {
         self.config.external_modules.iter().any(|v| v == src)
+            || self.scope.is_unresolved_external(importer, src)
+    }
+
+    /// Returns true if `import()` should be followed.
+    pub(crate) fn splits_dynamic_imports(&self) -> bool {
+        self.config
+            .code_splitting
+            .as_ref()
+            .is_some_and(|config| config.dynamic_imports)
     }
 
     ///
@@ -150,6 +212,10 @@ where
     /// circular manner. However, it applies only to the provided `entries`, and
     /// dependencies with circular reference is ok.
     pub fn bundle(&mut self, entries: HashMap<String, FileName>) -> Result<Vec<Bundle>, Error> {
+        if self.config.code_splitting.is_some() {
+            return self.bundle_with_code_splitting(entries);
+        }
+
         let results = entries
             .into_iter()
             .map(|(name, path)| -> Result<_, Error> {
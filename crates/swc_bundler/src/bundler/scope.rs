@@ -1,10 +1,11 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use swc_atoms::atom;
+use swc_atoms::{Atom, atom};
 use swc_common::{FileName, sync::Lrc};
 
 use super::load::TransformedModule;
 use crate::{
+    ModuleRecord,
     id::{Id, ModuleId, ModuleIdGenerator},
     util::CloneMap,
 };
@@ -20,6 +21,13 @@ pub(super) struct Scope {
 
     accessed_with_computed_key: CloneMap<ModuleId, Lrc<AtomicBool>>,
     is_cjs: CloneMap<ModuleId, Lrc<AtomicBool>>,
+
+    /// Variables which replace `import.meta` in each module.
+    import_meta_vars: CloneMap<Id, ModuleRecord>,
+
+    /// Imports made external by [crate::Hook::on_unresolved_import], keyed by
+    /// the importing module and the specifier.
+    unresolved_externals: CloneMap<(ModuleId, Atom), ()>,
 }
 
 impl Scope {
@@ -43,6 +51,25 @@ impl Scope {
         self.transformed_modules.get(&id)
     }
 
+    pub fn store_import_meta_var(&self, var: Id, module_record: ModuleRecord) {
+        self.import_meta_vars.insert(var, module_record);
+    }
+
+    /// Returns the module of `var` if it replaces `import.meta`.
+    pub fn get_import_meta_var(&self, var: &Id) -> Option<ModuleRecord> {
+        self.import_meta_vars.get(var)
+    }
+
+    pub fn mark_as_unresolved_external(&self, importer: ModuleId, src: Atom) {
+        self.unresolved_externals.insert((importer, src), ());
+    }
+
+    pub fn is_unresolved_external(&self, importer: ModuleId, src: &Atom) -> bool {
+        self.unresolved_externals
+            .get(&(importer, src.clone()))
+            .is_some()
+    }
+
     pub fn is_cjs(&self, id: ModuleId) -> bool {
         if let Some(v) = self.is_cjs.get(&id) {
             v.load(Ordering::SeqCst)
@@ -0,0 +1,540 @@
+use std::fmt::Write;
+
+use anyhow::{Error, anyhow, bail};
+use swc_atoms::{Atom, atom};
+use swc_common::{FileName, SourceMap, Span, sync::Lrc, util::take::Take};
+use swc_ecma_ast::*;
+use swc_ecma_loader::resolve::Resolution;
+use swc_ecma_parser::parse_file_as_module;
+use swc_ecma_transforms_base::helpers::Helpers;
+use swc_ecma_utils::ExprFactory;
+use swc_ecma_visit::{VisitMut, VisitMutWith, noop_visit_mut_type};
+
+use super::Plan;
+use crate::{
+    BundleKind, ChunkRecord, Hook, ImportMetaProp, Load, ModuleData, ModuleRecord, Resolve,
+    UnresolvedImport,
+};
+
+/// Specifier used by the facade of a chunk to import the `n`-th root.
+const ROOT_PREFIX: &str = "swc-chunk-root:";
+
+/// Loads the modules of a chunk, while rewriting imports of other chunks.
+pub(super) struct ChunkLoader<'a, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    pub cm: Lrc<SourceMap>,
+    pub loader: &'a L,
+    pub resolver: &'a R,
+    pub external_modules: &'a [Atom],
+    pub hook: &'a dyn Hook,
+    pub plan: &'a Plan,
+    pub chunk: usize,
+    /// The entry of the chunk, if the chunk has multiple roots.
+    pub facade: Option<FileName>,
+}
+
+impl<L, R> Load for ChunkLoader<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    fn load(&self, file: &FileName) -> Result<ModuleData, Error> {
+        if self.facade.as_ref() == Some(file) {
+            return self.load_facade(file);
+        }
+
+        let mut data = self.loader.load(file)?;
+
+        let mut rewriter = Rewriter {
+            loader: self,
+            file,
+            reexport_cnt: 0,
+            error: None,
+        };
+        data.module.visit_mut_with(&mut rewriter);
+
+        if let Some(err) = rewriter.error {
+            return Err(err);
+        }
+
+        Ok(data)
+    }
+}
+
+impl<L, R> ChunkLoader<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Creates a module which re-exports the names imported from the roots by
+    /// other chunks.
+    fn load_facade(&self, file: &FileName) -> Result<ModuleData, Error> {
+        let mut code = String::new();
+
+        for (idx, names) in self.plan.chunks[self.chunk]
+            .imported_names
+            .iter()
+            .enumerate()
+        {
+            let names = names
+                .iter()
+                .map(|name| format!("{} as {}", name, facade_export(idx, name)))
+                .collect::<Vec<_>>();
+
+            if !names.is_empty() {
+                writeln!(
+                    code,
+                    "export {{ {} }} from '{}{}';",
+                    names.join(", "),
+                    ROOT_PREFIX,
+                    idx
+                )
+                .unwrap();
+            }
+
+            writeln!(
+                code,
+                "export * as {} from '{}{}';",
+                facade_namespace(idx),
+                ROOT_PREFIX,
+                idx
+            )
+            .unwrap();
+        }
+
+        let fm = self.cm.new_source_file(Lrc::new(file.clone()), code);
+        let module = parse_file_as_module(
+            &fm,
+            Default::default(),
+            Default::default(),
+            None,
+            &mut Vec::new(),
+        )
+        .map_err(|err| anyhow!("failed to parse the facade of a chunk: {:?}", err))?;
+
+        Ok(ModuleData {
+            fm,
+            module,
+            helpers: Helpers::new(false),
+        })
+    }
+
+    /// Returns the chunk and the index of the root of the chunk, if `src`
+    /// imports a root.
+    fn chunk_of(&self, base: &FileName, src: &Atom) -> Option<(usize, usize)> {
+        if self.external_modules.contains(src) {
+            return None;
+        }
+
+        let resolved = match self.resolver.resolve(base, src) {
+            Ok(resolved) => resolved.filename,
+            // Resolution errors are reported by the bundler.
+            Err(err) => match self.hook.on_unresolved_import(base, src, err).ok()? {
+                UnresolvedImport::External => return None,
+                UnresolvedImport::Replace(file_name) => file_name,
+            },
+        };
+
+        self.plan.roots.get(&resolved).copied()
+    }
+
+    fn is_facade(&self, chunk: usize) -> bool {
+        self.plan.chunks[chunk].roots.len() > 1
+    }
+}
+
+fn facade_export(root: usize, name: &Atom) -> Atom {
+    format!("__swc_{root}_{name}").into()
+}
+
+fn facade_namespace(root: usize) -> Atom {
+    format!("__swc_ns_{root}").into()
+}
+
+fn export_name_sym(name: &ModuleExportName) -> Atom {
+    match name {
+        ModuleExportName::Ident(i) => i.sym.clone(),
+        ModuleExportName::Str(s) => s.value.to_atom_lossy().into_owned(),
+        #[cfg(swc_ast_unknown)]
+        _ => panic!("unable to access unknown nodes"),
+    }
+}
+
+fn ident_name(span: Span, sym: Atom) -> ModuleExportName {
+    ModuleExportName::Ident(Ident::new_no_ctxt(sym, span))
+}
+
+struct Rewriter<'a, 'b, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    loader: &'a ChunkLoader<'b, L, R>,
+    file: &'a FileName,
+    reexport_cnt: usize,
+    error: Option<Error>,
+}
+
+impl<L, R> Rewriter<'_, '_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Returns the specifier of the other chunk and the index of the imported
+    /// root, if it has a facade.
+    fn target(&self, src: &Str, dynamic: bool) -> Option<(Atom, Option<usize>)> {
+        let loader = self.loader;
+        let (chunk, root) = loader.chunk_of(self.file, &src.value.to_atom_lossy())?;
+
+        // The roots of the current chunk are bundled as usual, but dynamic
+        // imports always load the chunk.
+        if chunk == loader.chunk && !dynamic {
+            return None;
+        }
+
+        let spec = loader.plan.specifier(loader.chunk, chunk).into();
+
+        Some((spec, loader.is_facade(chunk).then_some(root)))
+    }
+
+    fn imported_name(&self, root: Option<usize>, name: Atom) -> Atom {
+        match root {
+            Some(root) => facade_export(root, &name),
+            None => name,
+        }
+    }
+
+    fn rewrite_reexport(
+        &mut self,
+        export: &NamedExport,
+        spec: Atom,
+        root: Option<usize>,
+        to: &mut Vec<ModuleItem>,
+    ) {
+        let span = export.span;
+        let mut import_specifiers = Vec::new();
+        let mut export_specifiers = Vec::new();
+
+        for s in &export.specifiers {
+            let tmp = Ident::new_no_ctxt(
+                format!("__swc_chunk_reexport_{}", self.reexport_cnt).into(),
+                span,
+            );
+            self.reexport_cnt += 1;
+
+            let (import, exported) = match s {
+                ExportSpecifier::Namespace(s) => {
+                    let import = match root {
+                        Some(root) => ImportSpecifier::Named(ImportNamedSpecifier {
+                            span,
+                            local: tmp.clone(),
+                            imported: Some(ident_name(span, facade_namespace(root))),
+                            is_type_only: false,
+                        }),
+                        None => ImportSpecifier::Namespace(ImportStarAsSpecifier {
+                            span,
+                            local: tmp.clone(),
+                        }),
+                    };
+
+                    (import, s.name.clone())
+                }
+                ExportSpecifier::Default(s) => (
+                    ImportSpecifier::Named(ImportNamedSpecifier {
+                        span,
+                        local: tmp.clone(),
+                        imported: Some(ident_name(
+                            span,
+                            self.imported_name(root, atom!("default")),
+                        )),
+                        is_type_only: false,
+                    }),
+                    ModuleExportName::Ident(s.exported.clone()),
+                ),
+                ExportSpecifier::Named(s) => {
+                    if s.is_type_only {
+                        continue;
+                    }
+
+                    (
+                        ImportSpecifier::Named(ImportNamedSpecifier {
+                            span,
+                            local: tmp.clone(),
+                            imported: Some(ident_name(
+                                span,
+                                self.imported_name(root, export_name_sym(&s.orig)),
+                            )),
+                            is_type_only: false,
+                        }),
+                        s.exported.clone().unwrap_or_else(|| s.orig.clone()),
+                    )
+                }
+                #[cfg(swc_ast_unknown)]
+                _ => panic!("unable to access unknown nodes"),
+            };
+
+            import_specifiers.push(import);
+            export_specifiers.push(ExportSpecifier::Named(ExportNamedSpecifier {
+                span,
+                orig: ModuleExportName::Ident(tmp),
+                exported: Some(exported),
+                is_type_only: false,
+            }));
+        }
+
+        to.push(
+            ImportDecl {
+                span,
+                specifiers: import_specifiers,
+                src: Box::new(spec.into()),
+                type_only: false,
+                with: None,
+                phase: Default::default(),
+            }
+            .into(),
+        );
+        to.push(
+            NamedExport {
+                span,
+                specifiers: export_specifiers,
+                src: None,
+                type_only: false,
+                with: None,
+            }
+            .into(),
+        );
+    }
+}
+
+impl<L, R> VisitMut for Rewriter<'_, '_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    noop_visit_mut_type!();
+
+    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+        call.visit_mut_children_with(self);
+
+        if !call.callee.is_import() {
+            return;
+        }
+
+        let Some(Expr::Lit(Lit::Str(src))) = call.args.first().map(|arg| &*arg.expr) else {
+            return;
+        };
+
+        let Some((spec, root)) = self.target(src, true) else {
+            return;
+        };
+
+        let span = call.span;
+        call.args[0].expr = Lit::Str(spec.into()).into();
+
+        // `import('./chunk.js').then((m) => m.__swc_ns_0)`
+        if let Some(root) = root {
+            let m = Ident::new_no_ctxt(atom!("m"), span);
+            let select = ArrowExpr {
+                span,
+                params: vec![m.clone().into()],
+                body: Box::new(BlockStmtOrExpr::Expr(Box::new(
+                    m.make_member(IdentName::new(facade_namespace(root), span))
+                        .into(),
+                ))),
+                ..Default::default()
+            };
+
+            *call = CallExpr {
+                span,
+                callee: call
+                    .take()
+                    .make_member(IdentName::new(atom!("then"), span))
+                    .as_callee(),
+                args: vec![select.as_arg()],
+                ..Default::default()
+            };
+        }
+    }
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        let mut buf = Vec::with_capacity(items.len());
+
+        for mut item in items.drain(..) {
+            match &mut item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) if !import.type_only => {
+                    if let Some((spec, root)) = self.target(&import.src, false) {
+                        *import.src = spec.into();
+
+                        if let Some(root) = root {
+                            for s in &mut import.specifiers {
+                                let (span, local, imported) = match s {
+                                    ImportSpecifier::Named(s) => {
+                                        let name = s
+                                            .imported
+                                            .as_ref()
+                                            .map(export_name_sym)
+                                            .unwrap_or_else(|| s.local.sym.clone());
+
+                                        (s.span, s.local.clone(), facade_export(root, &name))
+                                    }
+                                    ImportSpecifier::Default(s) => (
+                                        s.span,
+                                        s.local.clone(),
+                                        facade_export(root, &atom!("default")),
+                                    ),
+                                    ImportSpecifier::Namespace(s) => {
+                                        (s.span, s.local.clone(), facade_namespace(root))
+                                    }
+                                    #[cfg(swc_ast_unknown)]
+                                    _ => panic!("unable to access unknown nodes"),
+                                };
+
+                                *s = ImportSpecifier::Named(ImportNamedSpecifier {
+                                    span,
+                                    local,
+                                    imported: Some(ident_name(span, imported)),
+                                    is_type_only: false,
+                                });
+                            }
+                        }
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                    src: Some(src),
+                    type_only: false,
+                    ..
+                })) => {
+                    if let Some((spec, root)) = self.target(src, false) {
+                        let ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) = &item else {
+                            unreachable!()
+                        };
+                        self.rewrite_reexport(export, spec, root, &mut buf);
+                        continue;
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) if !export.type_only => {
+                    if let Some((spec, root)) = self.target(&export.src, false) {
+                        // `export *` is preserved only if it's a re-export of the current
+                        // chunk.
+                        let is_root =
+                            self.loader
+                                .plan
+                                .roots
+                                .get(self.file)
+                                .is_some_and(|&(chunk, _)| {
+                                    chunk == self.loader.chunk && !self.loader.is_facade(chunk)
+                                });
+
+                        if !is_root || root.is_some() {
+                            if self.error.is_none() {
+                                self.error = Some(anyhow!(
+                                    "`export * from '{}'` in {} re-exports another chunk, which \
+                                     is not supported",
+                                    export.src.value.to_string_lossy(),
+                                    self.file
+                                ));
+                            }
+                        } else {
+                            *export.src = spec.into();
+                        }
+                    }
+                }
+
+                _ => {}
+            }
+
+            item.visit_mut_with(self);
+            buf.push(item);
+        }
+
+        *items = buf;
+    }
+}
+
+/// Resolves the roots imported by a facade.
+pub(super) struct ChunkResolver<'a, R>
+where
+    R: Resolve,
+{
+    pub resolver: &'a R,
+    pub facade: Option<FileName>,
+    pub roots: &'a [FileName],
+}
+
+impl<R> Resolve for ChunkResolver<'_, R>
+where
+    R: Resolve,
+{
+    fn resolve(&self, base: &FileName, src: &str) -> Result<Resolution, Error> {
+        if self.facade.as_ref() == Some(base) {
+            if let Some(idx) = src.strip_prefix(ROOT_PREFIX) {
+                let Some(root) = idx
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|idx| self.roots.get(idx))
+                else {
+                    bail!("invalid root of a chunk: {}", src)
+                };
+
+                return Ok(Resolution {
+                    filename: root.clone(),
+                    slug: None,
+                });
+            }
+        }
+
+        self.resolver.resolve(base, src)
+    }
+}
+
+/// Shares the hook of the bundler with the bundlers of chunks.
+///
+/// The chunks are renamed by the bundler after bundling all chunks, so
+/// [Hook::get_chunk_file_name], [Hook::get_banner] and [Hook::get_footer] are
+/// not forwarded.
+pub(super) struct HookRef<'a> {
+    pub hook: &'a dyn Hook,
+    /// Kind of the chunk in the output of the bundler.
+    pub kind: &'a BundleKind,
+}
+
+impl Hook for HookRef<'_> {
+    fn get_import_meta_props(
+        &self,
+        span: Span,
+        module_record: &ModuleRecord,
+    ) -> Result<Vec<KeyValueProp>, Error> {
+        self.hook.get_import_meta_props(span, module_record)
+    }
+
+    fn rewrite_import_meta(
+        &self,
+        span: Span,
+        prop: ImportMetaProp<'_>,
+        module_record: &ModuleRecord,
+        _chunk_record: &ChunkRecord,
+    ) -> Result<Option<Box<Expr>>, Error> {
+        self.hook.rewrite_import_meta(
+            span,
+            prop,
+            module_record,
+            &ChunkRecord {
+                kind: self.kind.clone(),
+            },
+        )
+    }
+
+    fn on_unresolved_import(
+        &self,
+        importer: &FileName,
+        specifier: &str,
+        err: Error,
+    ) -> Result<UnresolvedImport, Error> {
+        self.hook.on_unresolved_import(importer, specifier, err)
+    }
+}
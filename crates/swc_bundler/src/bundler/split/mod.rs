@@ -0,0 +1,554 @@
+//! Code splitting.
+//!
+//! Modules are split into chunks, and each chunk is bundled by a separate
+//! [Bundler]. A chunk is created for
+//!
+//!  - each entry,
+//!  - each group of [CodeSplittingConfig::manual_chunks],
+//!  - each module imported with `import()`, if
+//!    [CodeSplittingConfig::dynamic_imports] is enabled,
+//!  - each module imported by more than one chunk.
+//!
+//! The modules a chunk starts from are called roots. While bundling a chunk,
+//! imports of the roots of other chunks are rewritten to import the other
+//! chunks, which are treated as external modules.
+//!
+//! [CodeSplittingConfig::dynamic_imports]: crate::CodeSplittingConfig::dynamic_imports
+//! [CodeSplittingConfig::manual_chunks]: crate::CodeSplittingConfig::manual_chunks
+
+use std::{
+    collections::{HashMap, VecDeque, hash_map::Entry},
+    path::Path,
+};
+
+use anyhow::{Context, Error, bail};
+use petgraph::{EdgeDirection::Incoming, algo::tarjan_scc};
+use relative_path::RelativePath;
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_atoms::Atom;
+use swc_common::FileName;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{VisitMut, VisitMutWith, noop_visit_mut_type};
+
+use self::loader::{ChunkLoader, ChunkResolver, HookRef};
+use super::{Bundler, load::Specifier};
+use crate::{
+    Bundle, BundleKind, Config, Load, ModuleId, ModuleType, Resolve, dep_graph::ModuleGraph,
+};
+
+mod loader;
+
+pub(super) struct Plan {
+    chunks: Vec<Chunk>,
+    /// Chunk index and the index in [Chunk::roots] of each root.
+    roots: FxHashMap<FileName, (usize, usize)>,
+}
+
+struct Chunk {
+    /// Name of the output file.
+    name: String,
+    kind: BundleKind,
+    roots: Vec<FileName>,
+    /// Names imported from each root by other chunks.
+    ///
+    /// Used only if there are multiple roots, in which case a facade module
+    /// re-exports the roots under generated names.
+    imported_names: Vec<Vec<Atom>>,
+}
+
+impl Plan {
+    fn facade_name(&self, chunk: usize) -> Option<FileName> {
+        if self.chunks[chunk].roots.len() > 1 {
+            Some(FileName::Custom(format!(
+                "swc-chunk:{}",
+                self.chunks[chunk].name
+            )))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the module specifier used to import `to` from `from`.
+    fn specifier(&self, from: usize, to: usize) -> String {
+        specifier(&self.chunks[from].name, &self.chunks[to].name)
+    }
+}
+
+/// Returns the module specifier used to import the file named `to` from the
+/// file named `from`.
+fn specifier(from: &str, to: &str) -> String {
+    let base = Path::new(from)
+        .parent()
+        .map(|dir| dir.to_string_lossy())
+        .unwrap_or_default();
+    let path = RelativePath::new(&*base).relative(to);
+
+    if path.as_str().starts_with('.') {
+        path.into_string()
+    } else {
+        format!("./{path}")
+    }
+}
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    pub(super) fn bundle_with_code_splitting(
+        &mut self,
+        entries: HashMap<String, FileName>,
+    ) -> Result<Vec<Bundle>, Error> {
+        if self.config.module != ModuleType::Es {
+            bail!("code splitting requires es modules")
+        }
+
+        let mut entries = entries
+            .into_iter()
+            .map(|(name, path)| -> Result<_, Error> {
+                let module = self
+                    .load_transformed(&path)
+                    .context("load_transformed failed")?
+                    .unwrap();
+
+                Ok((name, module.id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        entries.sort();
+
+        let plan = self.plan_chunks(entries)?;
+
+        let mut bundles = (0..plan.chunks.len())
+            .map(|chunk| self.bundle_chunk(&plan, chunk))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.rename_chunks(&plan, &mut bundles)?;
+
+        for bundle in &mut bundles {
+            self.add_banner_and_footer(bundle)?;
+        }
+
+        #[cfg(feature = "concurrent")]
+        {
+            let scope = std::mem::take(&mut self.scope);
+            rayon::spawn(move || drop(scope))
+        }
+
+        Ok(bundles)
+    }
+
+    fn plan_chunks(&self, entries: Vec<(String, ModuleId)>) -> Result<Plan, Error> {
+        let config = self.config.code_splitting.as_ref().unwrap();
+
+        // Static imports only. `import()` starts a new chunk.
+        let mut graph = ModuleGraph::default();
+        let mut dynamic_imports = Vec::new();
+        let mut queue = entries.iter().map(|(_, id)| *id).collect::<VecDeque<_>>();
+        let mut visited = queue.iter().copied().collect::<FxHashSet<_>>();
+
+        while let Some(id) = queue.pop_front() {
+            graph.add_node(id);
+
+            let module = self.scope.get_module(id).unwrap();
+
+            for (src, _) in module
+                .imports
+                .specifiers
+                .iter()
+                .chain(module.exports.reexports.iter())
+            {
+                if src.is_loaded_synchronously {
+                    graph.add_edge(id, src.module_id, ());
+                } else if !dynamic_imports.contains(&src.module_id) {
+                    dynamic_imports.push(src.module_id);
+                }
+
+                if visited.insert(src.module_id) {
+                    queue.push_back(src.module_id);
+                }
+            }
+        }
+
+        let mut chunks = Vec::new();
+        let mut chunk_of_root = FxHashMap::default();
+        let mut names = FxHashSet::default();
+
+        for (name, id) in entries {
+            chunk_of_root.insert(id, chunks.len());
+            names.insert(name.clone());
+            chunks.push((name.clone(), BundleKind::Named { name }, vec![id]));
+        }
+
+        for manual in &config.manual_chunks {
+            let mut roots = Vec::new();
+
+            for path in &manual.modules {
+                // Not used by any entry.
+                let Some(module) = self.scope.get_module_by_path(path) else {
+                    continue;
+                };
+
+                if let Some(&chunk) = chunk_of_root.get(&module.id) {
+                    bail!(
+                        "`{}` cannot be a part of the chunk `{}` because it's a part of `{}`",
+                        path,
+                        manual.name,
+                        chunks[chunk].0
+                    )
+                }
+
+                chunk_of_root.insert(module.id, chunks.len());
+                roots.push(module.id);
+            }
+
+            if !roots.is_empty() {
+                if !names.insert(manual.name.clone()) {
+                    bail!("multiple chunks are named `{}`", manual.name)
+                }
+
+                chunks.push((
+                    manual.name.clone(),
+                    BundleKind::Lib {
+                        name: manual.name.clone(),
+                    },
+                    roots,
+                ));
+            }
+        }
+
+        for id in dynamic_imports {
+            if let Entry::Vacant(e) = chunk_of_root.entry(id) {
+                e.insert(chunks.len());
+
+                let name = self.chunk_name(id, &mut names);
+                chunks.push((name.clone(), BundleKind::Lib { name }, vec![id]));
+            }
+        }
+
+        // Importers come first.
+        let components = {
+            let mut components = tarjan_scc(&graph);
+            components.reverse();
+            components
+        };
+
+        // Chunks which use a module which is not a root.
+        let mut used_by = FxHashMap::<ModuleId, FxHashSet<usize>>::default();
+
+        for component in components {
+            let mut users = FxHashSet::default();
+            let mut roots = Vec::new();
+
+            for &id in &component {
+                if let Some(&chunk) = chunk_of_root.get(&id) {
+                    users.insert(chunk);
+                    continue;
+                }
+
+                for importer in graph.neighbors_directed(id, Incoming) {
+                    if component.contains(&importer) {
+                        continue;
+                    }
+
+                    roots.push(id);
+
+                    match chunk_of_root.get(&importer) {
+                        Some(&chunk) => {
+                            users.insert(chunk);
+                        }
+                        None => users.extend(used_by[&importer].iter().copied()),
+                    }
+                }
+            }
+
+            roots.sort_unstable();
+            roots.dedup();
+
+            if users.len() > 1
+                && roots.len() == 1
+                && self.size_of_chunk(&graph, roots[0], &chunk_of_root) >= config.min_chunk_size
+            {
+                let id = roots[0];
+                chunk_of_root.insert(id, chunks.len());
+
+                let name = self.chunk_name(id, &mut names);
+                chunks.push((name.clone(), BundleKind::Lib { name }, vec![id]));
+
+                users = FxHashSet::from_iter([chunks.len() - 1]);
+            }
+
+            for id in component {
+                if !chunk_of_root.contains_key(&id) {
+                    used_by.insert(id, users.clone());
+                }
+            }
+        }
+
+        let mut plan = Plan {
+            chunks: Vec::with_capacity(chunks.len()),
+            roots: Default::default(),
+        };
+
+        for (idx, (name, kind, roots)) in chunks.into_iter().enumerate() {
+            let roots = roots
+                .into_iter()
+                .enumerate()
+                .map(|(root_idx, id)| {
+                    let path = (*self.scope.get_module(id).unwrap().fm.name).clone();
+                    plan.roots.insert(path.clone(), (idx, root_idx));
+                    path
+                })
+                .collect::<Vec<_>>();
+
+            plan.chunks.push(Chunk {
+                name,
+                kind,
+                imported_names: vec![Vec::new(); roots.len()],
+                roots,
+            });
+        }
+
+        self.collect_imported_names(&graph, &mut plan);
+
+        Ok(plan)
+    }
+
+    /// Returns the size of the source code of `root` and its dependencies which
+    /// are not roots.
+    fn size_of_chunk(
+        &self,
+        graph: &ModuleGraph,
+        root: ModuleId,
+        chunk_of_root: &FxHashMap<ModuleId, usize>,
+    ) -> usize {
+        let mut size = 0;
+        let mut visited = FxHashSet::from_iter([root]);
+        let mut stack = vec![root];
+
+        while let Some(id) = stack.pop() {
+            size += self.scope.get_module(id).unwrap().fm.src.len();
+
+            for dep in graph.neighbors(id) {
+                if !chunk_of_root.contains_key(&dep) && visited.insert(dep) {
+                    stack.push(dep);
+                }
+            }
+        }
+
+        size
+    }
+
+    fn chunk_name(&self, id: ModuleId, names: &mut FxHashSet<String>) -> String {
+        let module = self.scope.get_module(id).unwrap();
+        let stem = match &*module.fm.name {
+            FileName::Real(path) => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned()),
+            _ => None,
+        }
+        .unwrap_or_else(|| "chunk".into());
+
+        let mut name = format!("{stem}.js");
+        let mut suffix = 0;
+
+        while !names.insert(name.clone()) {
+            suffix += 1;
+            name = format!("{stem}-{suffix}.js");
+        }
+
+        name
+    }
+
+    /// Renames chunks using [Hook::get_chunk_file_name], and rewrites imports
+    /// of the renamed chunks.
+    ///
+    /// [Hook::get_chunk_file_name]: crate::Hook::get_chunk_file_name
+    fn rename_chunks(&self, plan: &Plan, bundles: &mut [Bundle]) -> Result<(), Error> {
+        let names = plan
+            .chunks
+            .iter()
+            .zip(&*bundles)
+            .map(|(chunk, bundle)| {
+                Ok(self
+                    .chunk_file_name(bundle)?
+                    .unwrap_or_else(|| chunk.name.clone()))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        for (from, bundle) in bundles.iter_mut().enumerate() {
+            let specifiers = (0..plan.chunks.len())
+                .filter_map(|to| {
+                    let old = plan.specifier(from, to);
+                    let new = specifier(&names[from], &names[to]);
+
+                    (old != new).then(|| (Atom::from(old), Atom::from(new)))
+                })
+                .collect::<FxHashMap<_, _>>();
+
+            if !specifiers.is_empty() {
+                bundle
+                    .module
+                    .visit_mut_with(&mut ChunkImportRenamer { specifiers });
+            }
+
+            match &mut bundle.kind {
+                BundleKind::Named { name } | BundleKind::Lib { name } => {
+                    name.clone_from(&names[from]);
+                }
+                BundleKind::Dynamic => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fills [Chunk::imported_names] of chunks with multiple roots.
+    fn collect_imported_names(&self, graph: &ModuleGraph, plan: &mut Plan) {
+        for id in graph.nodes() {
+            let module = self.scope.get_module(id).unwrap();
+
+            for (src, specifiers) in module
+                .imports
+                .specifiers
+                .iter()
+                .chain(module.exports.reexports.iter())
+            {
+                let dep = self.scope.get_module(src.module_id).unwrap();
+                let Some(&(chunk, root)) = plan.roots.get(&*dep.fm.name) else {
+                    continue;
+                };
+                let chunk = &mut plan.chunks[chunk];
+
+                if chunk.roots.len() == 1 {
+                    continue;
+                }
+
+                for specifier in specifiers {
+                    if let Specifier::Specific { local, alias } = specifier {
+                        let name = alias.as_ref().unwrap_or(local).sym();
+
+                        if !chunk.imported_names[root].contains(name) {
+                            chunk.imported_names[root].push(name.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        for chunk in &mut plan.chunks {
+            for names in &mut chunk.imported_names {
+                names.sort();
+            }
+        }
+    }
+
+    fn bundle_chunk(&self, plan: &Plan, chunk: usize) -> Result<Bundle, Error> {
+        self.run(|| {
+            let facade = plan.facade_name(chunk);
+            let entry = facade
+                .clone()
+                .unwrap_or_else(|| plan.chunks[chunk].roots[0].clone());
+
+            let mut external_modules = self.config.external_modules.clone();
+            external_modules
+                .extend((0..plan.chunks.len()).map(|to| plan.specifier(chunk, to).into()));
+
+            // The loader and the resolver are boxed, as the bundlers of chunks would
+            // otherwise be instantiated recursively.
+            let mut bundler = Bundler::<Box<dyn Load + '_>, Box<dyn Resolve + '_>>::new(
+                self.globals,
+                self.cm.clone(),
+                Box::new(ChunkLoader {
+                    cm: self.cm.clone(),
+                    loader: &self.loader,
+                    resolver: &self.resolver,
+                    external_modules: &self.config.external_modules,
+                    hook: &*self.hook,
+                    plan,
+                    chunk,
+                    facade: facade.clone(),
+                }),
+                Box::new(ChunkResolver {
+                    resolver: &self.resolver,
+                    facade: facade.clone(),
+                    roots: &plan.chunks[chunk].roots,
+                }),
+                Config {
+                    require: self.config.require,
+                    disable_inliner: self.config.disable_inliner,
+                    disable_hygiene: self.config.disable_hygiene,
+                    disable_fixer: self.config.disable_fixer,
+                    disable_dce: self.config.disable_dce,
+                    external_modules,
+                    module: self.config.module,
+                    code_splitting: None,
+                },
+                Box::new(HookRef {
+                    hook: &*self.hook,
+                    kind: &plan.chunks[chunk].kind,
+                }),
+            );
+
+            let name = plan.chunks[chunk].name.clone();
+            let mut bundles = bundler
+                .bundle(HashMap::from_iter([(name.clone(), entry.clone())]))
+                .with_context(|| format!("failed to bundle the chunk `{name}`"))?;
+            debug_assert_eq!(bundles.len(), 1);
+
+            let bundle = bundles.pop().unwrap();
+
+            Ok(Bundle {
+                kind: plan.chunks[chunk].kind.clone(),
+                id: self.scope.module_id_gen.r#gen(&entry).0,
+                module: bundle.module,
+                banner: None,
+                footer: None,
+            })
+        })
+    }
+}
+
+/// Rewrites the module specifiers of imports of other chunks.
+struct ChunkImportRenamer {
+    /// The old and new module specifiers of renamed chunks.
+    specifiers: FxHashMap<Atom, Atom>,
+}
+
+impl ChunkImportRenamer {
+    fn rename(&self, src: &mut Str) {
+        if let Some(new) = self.specifiers.get(&*src.value.to_atom_lossy()) {
+            src.value = new.clone().into();
+            src.raw = None;
+        }
+    }
+}
+
+impl VisitMut for ChunkImportRenamer {
+    noop_visit_mut_type!();
+
+    fn visit_mut_import_decl(&mut self, n: &mut ImportDecl) {
+        self.rename(&mut n.src);
+    }
+
+    fn visit_mut_named_export(&mut self, n: &mut NamedExport) {
+        if let Some(src) = &mut n.src {
+            self.rename(src);
+        }
+    }
+
+    fn visit_mut_export_all(&mut self, n: &mut ExportAll) {
+        self.rename(&mut n.src);
+    }
+
+    fn visit_mut_call_expr(&mut self, n: &mut CallExpr) {
+        n.visit_mut_children_with(self);
+
+        if let Callee::Import(..) = n.callee {
+            if let Some(ExprOrSpread { spread: None, expr }) = n.args.first_mut() {
+                if let Expr::Lit(Lit::Str(src)) = &mut **expr {
+                    self.rename(src);
+                }
+            }
+        }
+    }
+}
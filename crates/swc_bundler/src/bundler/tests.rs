@@ -145,6 +145,7 @@ impl TestBuilder {
                         disable_dce: false,
                         external_modules: Vec::new(),
                         module: Default::default(),
+                        code_splitting: None,
                     },
                     Box::new(Hook),
                 );
@@ -1,17 +1,105 @@
 use anyhow::Error;
 use swc_common::{FileName, Span};
-use swc_ecma_ast::KeyValueProp;
+use swc_ecma_ast::{Expr, KeyValueProp};
 
+use crate::BundleKind;
+
+#[derive(Debug, Clone)]
 #[non_exhaustive]
 pub struct ModuleRecord {
     pub file_name: FileName,
     pub is_entry: bool,
 }
 
+/// An output of the bundler.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ChunkRecord {
+    pub kind: BundleKind,
+}
+
+/// A property of `import.meta` which can be rewritten by
+/// [Hook::rewrite_import_meta].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum ImportMetaProp<'a> {
+    /// `import.meta.url`
+    Url,
+    /// `import.meta.resolve(specifier)`
+    Resolve { specifier: &'a Expr },
+}
+
+/// How to handle an import which cannot be resolved, returned by
+/// [Hook::on_unresolved_import].
+#[derive(Debug, Clone)]
+pub enum UnresolvedImport {
+    /// Preserve the import, as if the module specifier was in
+    /// [Config::external_modules](crate::Config::external_modules).
+    External,
+    /// Load the given module instead.
+    Replace(FileName),
+}
+
 pub trait Hook: swc_common::sync::Sync + swc_common::sync::Send {
     fn get_import_meta_props(
         &self,
         span: Span,
         module_record: &ModuleRecord,
     ) -> Result<Vec<KeyValueProp>, Error>;
+
+    /// Rewrites `import.meta.url` or `import.meta.resolve(specifier)` in the
+    /// chunk described by `chunk_record`, before the chunk is renamed by
+    /// [Hook::get_chunk_file_name].
+    ///
+    /// A module bundled into multiple chunks is rewritten once per chunk.
+    /// Returns `None` to use the object returned by
+    /// [Hook::get_import_meta_props].
+    fn rewrite_import_meta(
+        &self,
+        _span: Span,
+        _prop: ImportMetaProp<'_>,
+        _module_record: &ModuleRecord,
+        _chunk_record: &ChunkRecord,
+    ) -> Result<Option<Box<Expr>>, Error> {
+        Ok(None)
+    }
+
+    /// Returns the name of the output file of a chunk, or `None` to use the
+    /// default name.
+    ///
+    /// `[hash]` in the returned name is replaced with a hash of the content of
+    /// the chunk. Imports of the chunk in other chunks are rewritten to use
+    /// the new name.
+    ///
+    /// By default, chunks of [BundleKind::Lib] are named `name-[hash].ext`.
+    fn get_chunk_file_name(&self, _chunk_record: &ChunkRecord) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+
+    /// Returns the code to prepend to a chunk, stored in [Bundle::banner].
+    ///
+    /// [Bundle::banner]: crate::Bundle::banner
+    fn get_banner(&self, _chunk_record: &ChunkRecord) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+
+    /// Returns the code to append to a chunk, stored in [Bundle::footer].
+    ///
+    /// [Bundle::footer]: crate::Bundle::footer
+    fn get_footer(&self, _chunk_record: &ChunkRecord) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+
+    /// Called if `specifier` imported by `importer` cannot be resolved.
+    ///
+    /// This may be called multiple times for the same import. Returns `err` by
+    /// default.
+    fn on_unresolved_import(
+        &self,
+        _importer: &FileName,
+        _specifier: &str,
+        err: Error,
+    ) -> Result<UnresolvedImport, Error> {
+        Err(err)
+    }
 }
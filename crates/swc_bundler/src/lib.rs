@@ -4,8 +4,8 @@
 #![cfg_attr(not(test), allow(unused))]
 
 pub use self::{
-    bundler::{Bundle, BundleKind, Bundler, Config, ModuleType},
-    hook::{Hook, ModuleRecord},
+    bundler::{Bundle, BundleKind, Bundler, CodeSplittingConfig, Config, ManualChunk, ModuleType},
+    hook::{ChunkRecord, Hook, ImportMetaProp, ModuleRecord, UnresolvedImport},
     id::ModuleId,
     load::{Load, ModuleData},
     resolve::Resolve,
@@ -0,0 +1,8 @@
+import { log } from './log';
+
+log('start');
+
+export async function load() {
+    const { run } = await import('./lazy');
+    run();
+}
@@ -0,0 +1,5 @@
+import { log } from './log';
+
+export function run() {
+    log('lazy');
+}
@@ -0,0 +1,7 @@
+import { log } from "./log.js";
+log('start');
+async function load() {
+    const { run } = await import("./lazy.js");
+    run();
+}
+export { load as load };
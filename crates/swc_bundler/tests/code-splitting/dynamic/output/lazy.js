@@ -0,0 +1,5 @@
+import { log } from "./log.js";
+function run() {
+    log('lazy');
+}
+export { run as run };
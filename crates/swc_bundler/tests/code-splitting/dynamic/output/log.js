@@ -0,0 +1,4 @@
+function log(message) {
+    console.log(message);
+}
+export { log as log };
@@ -0,0 +1,4 @@
+import { x } from './lib/x';
+import * as y from './lib/y';
+
+console.log(x, y.y);
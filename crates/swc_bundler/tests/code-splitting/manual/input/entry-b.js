@@ -0,0 +1,4 @@
+import { x as x2 } from './lib/x';
+export { default as y } from './lib/y';
+
+console.log(x2);
@@ -0,0 +1,5 @@
+export const y = 'y';
+
+export default function () {
+    return y;
+}
@@ -0,0 +1,3 @@
+import { __swc_0_x as x } from "./vendor.js";
+import { __swc_ns_1 as y } from "./vendor.js";
+console.log(x, y.y);
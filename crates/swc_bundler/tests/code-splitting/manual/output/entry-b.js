@@ -0,0 +1,4 @@
+import { __swc_0_x as x2 } from "./vendor.js";
+import { __swc_1_default as __swc_chunk_reexport_0 } from "./vendor.js";
+export { __swc_chunk_reexport_0 as y };
+console.log(x2);
@@ -0,0 +1,16 @@
+const x = 'x';
+const mod = {
+    x: x
+};
+const y = 'y';
+function __default() {
+    return y;
+}
+const mod1 = {
+    y: y,
+    default: __default
+};
+export { x as __swc_0_x };
+export { __default as __swc_1_default, y as __swc_1_y };
+export { mod as __swc_ns_0 };
+export { mod1 as __swc_ns_1 };
@@ -0,0 +1,3 @@
+import { greet, VERSION } from './shared';
+
+console.log(greet('b'), VERSION);
@@ -0,0 +1,7 @@
+import { format } from './util';
+
+export const VERSION = '1.0.0';
+
+export function greet(name) {
+    return format(`Hello, ${name}`);
+}
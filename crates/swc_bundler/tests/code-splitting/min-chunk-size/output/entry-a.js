@@ -0,0 +1,7 @@
+function format(s) {
+    return `[${s}]`;
+}
+function greet(name) {
+    return format(`Hello, ${name}`);
+}
+console.log(greet('a'));
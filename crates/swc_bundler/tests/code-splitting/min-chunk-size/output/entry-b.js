@@ -0,0 +1,8 @@
+function format(s) {
+    return `[${s}]`;
+}
+const VERSION = '1.0.0';
+function greet(name) {
+    return format(`Hello, ${name}`);
+}
+console.log(greet('b'), VERSION);
@@ -0,0 +1,2 @@
+import { greet, VERSION } from "./shared.js";
+console.log(greet('b'), VERSION);
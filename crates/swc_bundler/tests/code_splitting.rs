@@ -0,0 +1,208 @@
+use std::{
+    collections::HashMap,
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Error;
+use swc_bundler::{
+    BundleKind, Bundler, CodeSplittingConfig, Config, ManualChunk, ModuleRecord, ModuleType,
+};
+use swc_common::{FileName, Globals, Span, errors::HANDLER};
+use swc_ecma_ast::{KeyValueProp, Program};
+use swc_ecma_codegen::{Emitter, text_writer::JsWriter};
+use swc_ecma_transforms_base::fixer::fixer;
+use testing::NormalizedOutput;
+
+use self::common::*;
+
+#[path = "common/mod.rs"]
+mod common;
+
+fn dir(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("code-splitting")
+        .join(name)
+}
+
+/// Bundles `entry*.js` in `tests/code-splitting/<name>/input` and compares the
+/// chunks with the files in `output`.
+fn do_test(name: &str, config: CodeSplittingConfig) {
+    let dir = dir(name);
+    let entries = read_dir(dir.join("input"))
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|path| {
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("entry")
+        })
+        .map(|path| {
+            (
+                path.file_name().unwrap().to_string_lossy().into_owned(),
+                FileName::Real(path),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    testing::run_test2(false, |cm, handler| {
+        HANDLER.set(&handler, || {
+            let globals = Globals::default();
+            let mut bundler = Bundler::new(
+                &globals,
+                cm.clone(),
+                Loader { cm: cm.clone() },
+                NodeResolver,
+                Config {
+                    module: ModuleType::Es,
+                    code_splitting: Some(config),
+                    ..Default::default()
+                },
+                Box::new(Hook),
+            );
+
+            let chunks = bundler.bundle(entries).map_err(|err| println!("{err:?}"))?;
+
+            let mut error = false;
+
+            for chunk in chunks {
+                let code = {
+                    let mut buf = Vec::new();
+
+                    {
+                        let mut emitter = Emitter {
+                            cfg: Default::default(),
+                            cm: cm.clone(),
+                            comments: None,
+                            wr: Box::new(JsWriter::new(cm.clone(), "\n", &mut buf, None)),
+                        };
+
+                        emitter
+                            .emit_program(&Program::Module(chunk.module).apply(fixer(None)))
+                            .unwrap();
+                    }
+
+                    String::from_utf8_lossy(&buf).to_string()
+                };
+
+                let name = match chunk.kind {
+                    BundleKind::Named { name } | BundleKind::Lib { name } => name,
+                    BundleKind::Dynamic => unreachable!("code splitting creates no dynamic bundle"),
+                };
+
+                if let Err(err) =
+                    NormalizedOutput::from(code).compare_to_file(dir.join("output").join(name))
+                {
+                    println!("Diff: {err:?}");
+                    error = true;
+                }
+            }
+
+            if error {
+                return Err(());
+            }
+
+            Ok(())
+        })
+    })
+    .expect("failed to split modules");
+}
+
+#[test]
+fn shared() {
+    do_test("shared", Default::default());
+}
+
+#[test]
+fn min_chunk_size() {
+    do_test(
+        "min-chunk-size",
+        CodeSplittingConfig {
+            min_chunk_size: 10_000,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn dynamic() {
+    do_test(
+        "dynamic",
+        CodeSplittingConfig {
+            dynamic_imports: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn manual() {
+    let lib = dir("manual").join("input").join("lib");
+
+    do_test(
+        "manual",
+        CodeSplittingConfig {
+            manual_chunks: vec![ManualChunk {
+                name: "vendor.js".into(),
+                modules: vec![
+                    FileName::Real(lib.join("x.js")),
+                    FileName::Real(lib.join("y.js")),
+                ],
+            }],
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn manual_chunk_with_entry() {
+    let input = dir("shared").join("input");
+
+    testing::run_test2(false, |cm, _| {
+        let globals = Globals::default();
+        let mut bundler = Bundler::new(
+            &globals,
+            cm.clone(),
+            Loader { cm: cm.clone() },
+            NodeResolver,
+            Config {
+                module: ModuleType::Es,
+                code_splitting: Some(CodeSplittingConfig {
+                    manual_chunks: vec![ManualChunk {
+                        name: "vendor.js".into(),
+                        modules: vec![FileName::Real(input.join("entry-a.js"))],
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            Box::new(Hook),
+        );
+
+        let err = bundler
+            .bundle(HashMap::from_iter([(
+                "entry-a.js".into(),
+                FileName::Real(input.join("entry-a.js")),
+            )]))
+            .unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("cannot be a part of the chunk `vendor.js`"),
+            "{err:?}"
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+struct Hook;
+
+impl swc_bundler::Hook for Hook {
+    fn get_import_meta_props(&self, _: Span, _: &ModuleRecord) -> Result<Vec<KeyValueProp>, Error> {
+        Ok(Vec::new())
+    }
+}
@@ -1071,6 +1071,7 @@ fn bundle(url: &str, minify: bool) -> String {
                             unresolved_mark,
                             top_level_mark,
                             mangle_name_cache: None,
+                            size_reporter: None,
                         },
                     )
                     .expect_module();
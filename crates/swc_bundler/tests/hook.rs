@@ -0,0 +1,348 @@
+use std::{
+    collections::HashMap,
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Error;
+use swc_bundler::{
+    BundleKind, Bundler, ChunkRecord, CodeSplittingConfig, Config, ImportMetaProp, ModuleRecord,
+    ModuleType, UnresolvedImport,
+};
+use swc_common::{FileName, Globals, Span, errors::HANDLER};
+use swc_ecma_ast::{
+    BinExpr, BinaryOp, Bool, Expr, IdentName, KeyValueProp, Lit, Program, PropName, Str,
+};
+use swc_ecma_codegen::{Emitter, text_writer::JsWriter};
+use swc_ecma_transforms_base::fixer::fixer;
+use testing::NormalizedOutput;
+
+use self::common::*;
+
+#[path = "common/mod.rs"]
+mod common;
+
+fn dir(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("hook")
+        .join(name)
+}
+
+/// Bundles `entry*.js` in `tests/hook/<name>/input` and compares the bundles,
+/// with their banners and footers, with the files in `output`.
+fn do_test(name: &str, config: Config, hook: Hook) {
+    let dir = dir(name);
+    let entries = read_dir(dir.join("input"))
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|path| {
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("entry")
+        })
+        .map(|path| {
+            (
+                path.file_name().unwrap().to_string_lossy().into_owned(),
+                FileName::Real(path),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    testing::run_test2(false, |cm, handler| {
+        HANDLER.set(&handler, || {
+            let globals = Globals::default();
+            let mut bundler = Bundler::new(
+                &globals,
+                cm.clone(),
+                Loader { cm: cm.clone() },
+                NodeResolver,
+                config,
+                Box::new(hook),
+            );
+
+            let bundles = bundler.bundle(entries).map_err(|err| println!("{err:?}"))?;
+
+            let mut error = false;
+
+            for bundle in bundles {
+                let mut code = {
+                    let mut buf = Vec::new();
+
+                    {
+                        let mut emitter = Emitter {
+                            cfg: Default::default(),
+                            cm: cm.clone(),
+                            comments: None,
+                            wr: Box::new(JsWriter::new(cm.clone(), "\n", &mut buf, None)),
+                        };
+
+                        emitter
+                            .emit_program(&Program::Module(bundle.module).apply(fixer(None)))
+                            .unwrap();
+                    }
+
+                    String::from_utf8_lossy(&buf).to_string()
+                };
+
+                if let Some(banner) = bundle.banner {
+                    code = format!("{banner}\n{code}");
+                }
+                if let Some(footer) = bundle.footer {
+                    code = format!("{code}{footer}\n");
+                }
+
+                let name = match bundle.kind {
+                    BundleKind::Named { name } | BundleKind::Lib { name } => name,
+                    BundleKind::Dynamic => unreachable!("no dynamic import is bundled"),
+                };
+
+                if let Err(err) =
+                    NormalizedOutput::from(code).compare_to_file(dir.join("output").join(name))
+                {
+                    println!("Diff: {err:?}");
+                    error = true;
+                }
+            }
+
+            if error {
+                return Err(());
+            }
+
+            Ok(())
+        })
+    })
+    .expect("failed to bundle");
+}
+
+#[test]
+fn file_name() {
+    do_test(
+        "file-name",
+        Config {
+            module: ModuleType::Es,
+            code_splitting: Some(Default::default()),
+            ..Default::default()
+        },
+        Hook {
+            file_name: true,
+            banner: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn import_meta() {
+    do_test(
+        "import-meta",
+        Config {
+            module: ModuleType::Es,
+            ..Default::default()
+        },
+        Hook {
+            import_meta: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn import_meta_code_splitting() {
+    do_test(
+        "import-meta-code-splitting",
+        Config {
+            module: ModuleType::Es,
+            code_splitting: Some(CodeSplittingConfig {
+                dynamic_imports: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        Hook {
+            import_meta: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn unresolved() {
+    do_test(
+        "unresolved",
+        Config {
+            module: ModuleType::Es,
+            ..Default::default()
+        },
+        Hook {
+            unresolved: true,
+            ..Default::default()
+        },
+    );
+}
+
+/// `./util.js` only exists next to `a/index.js`, so it must only be external
+/// when imported by `b/index.js`.
+#[test]
+fn unresolved_relative() {
+    do_test(
+        "unresolved-relative",
+        Config {
+            module: ModuleType::Es,
+            ..Default::default()
+        },
+        Hook {
+            unresolved: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn unresolved_error() {
+    let input = dir("unresolved").join("input");
+
+    testing::run_test2(false, |cm, _| {
+        let globals = Globals::default();
+        let mut bundler = Bundler::new(
+            &globals,
+            cm.clone(),
+            Loader { cm: cm.clone() },
+            NodeResolver,
+            Default::default(),
+            Box::new(Hook::default()),
+        );
+
+        let err = bundler
+            .bundle(HashMap::from_iter([(
+                "entry.js".into(),
+                FileName::Real(input.join("entry.js")),
+            )]))
+            .unwrap_err();
+
+        assert!(format!("{err:?}").contains("failed to resolve"), "{err:?}");
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[derive(Default)]
+struct Hook {
+    file_name: bool,
+    banner: bool,
+    import_meta: bool,
+    unresolved: bool,
+}
+
+fn chunk_name(chunk_record: &ChunkRecord) -> &str {
+    match &chunk_record.kind {
+        BundleKind::Named { name } | BundleKind::Lib { name } => name,
+        BundleKind::Dynamic => "dynamic",
+    }
+}
+
+impl swc_bundler::Hook for Hook {
+    fn get_import_meta_props(
+        &self,
+        span: Span,
+        module_record: &ModuleRecord,
+    ) -> Result<Vec<KeyValueProp>, Error> {
+        Ok(vec![KeyValueProp {
+            key: PropName::Ident(IdentName::new("main".into(), span)),
+            value: Box::new(Expr::Lit(Lit::Bool(Bool {
+                span,
+                value: module_record.is_entry,
+            }))),
+        }])
+    }
+
+    fn rewrite_import_meta(
+        &self,
+        span: Span,
+        prop: ImportMetaProp<'_>,
+        module_record: &ModuleRecord,
+        chunk_record: &ChunkRecord,
+    ) -> Result<Option<Box<Expr>>, Error> {
+        if !self.import_meta {
+            return Ok(None);
+        }
+
+        let FileName::Real(path) = &module_record.file_name else {
+            unreachable!()
+        };
+        let url = Box::new(Expr::Lit(Lit::Str(Str {
+            span,
+            raw: None,
+            value: format!(
+                "https://example.com/{}#{}",
+                chunk_name(chunk_record),
+                path.file_name().unwrap().to_string_lossy()
+            )
+            .into(),
+        })));
+
+        Ok(Some(match prop {
+            ImportMetaProp::Url => url,
+            ImportMetaProp::Resolve { specifier } => Box::new(Expr::Bin(BinExpr {
+                span,
+                op: BinaryOp::Add,
+                left: url,
+                right: Box::new(specifier.clone()),
+            })),
+            _ => return Ok(None),
+        }))
+    }
+
+    fn get_chunk_file_name(&self, chunk_record: &ChunkRecord) -> Result<Option<String>, Error> {
+        if !self.file_name {
+            return Ok(None);
+        }
+
+        Ok(match &chunk_record.kind {
+            BundleKind::Lib { name } => {
+                Some(format!("chunks/{}.[hash].js", name.trim_end_matches(".js")))
+            }
+            _ => None,
+        })
+    }
+
+    fn get_banner(&self, chunk_record: &ChunkRecord) -> Result<Option<String>, Error> {
+        if !self.banner {
+            return Ok(None);
+        }
+
+        Ok(Some(format!("/*! {} */", chunk_name(chunk_record))))
+    }
+
+    fn get_footer(&self, _: &ChunkRecord) -> Result<Option<String>, Error> {
+        if !self.banner {
+            return Ok(None);
+        }
+
+        Ok(Some("//# footer".into()))
+    }
+
+    fn on_unresolved_import(
+        &self,
+        importer: &FileName,
+        specifier: &str,
+        err: Error,
+    ) -> Result<UnresolvedImport, Error> {
+        if !self.unresolved {
+            return Err(err);
+        }
+
+        let FileName::Real(path) = importer else {
+            unreachable!()
+        };
+
+        Ok(match specifier {
+            "@virtual/config" => {
+                UnresolvedImport::Replace(FileName::Real(path.with_file_name("config.js")))
+            }
+            _ => UnresolvedImport::External,
+        })
+    }
+}
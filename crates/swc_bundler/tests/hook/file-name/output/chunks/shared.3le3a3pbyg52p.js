@@ -0,0 +1,11 @@
+/*! chunks/shared.3le3a3pbyg52p.js */
+function format(s) {
+    return `[${s}]`;
+}
+const VERSION = '1.0.0';
+function greet(name) {
+    return format(`Hello, ${name}`);
+}
+export { VERSION as VERSION };
+export { greet as greet };
+//# footer
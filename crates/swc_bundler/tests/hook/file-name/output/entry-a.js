@@ -0,0 +1,4 @@
+/*! entry-a.js */
+import { greet } from "./chunks/shared.3le3a3pbyg52p.js";
+console.log(greet('a'));
+//# footer
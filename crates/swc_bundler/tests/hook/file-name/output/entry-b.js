@@ -0,0 +1,4 @@
+/*! entry-b.js */
+import { greet, VERSION } from "./chunks/shared.3le3a3pbyg52p.js";
+console.log(greet('b'), VERSION);
+//# footer
@@ -0,0 +1,3 @@
+console.log(import.meta.url);
+
+import('./lazy').then(({ url }) => console.log(url));
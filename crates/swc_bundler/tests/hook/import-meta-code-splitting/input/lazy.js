@@ -0,0 +1 @@
+export const url = import.meta.resolve('./data.json');
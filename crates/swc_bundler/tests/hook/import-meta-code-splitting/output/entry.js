@@ -0,0 +1,2 @@
+console.log("https://example.com/entry.js#entry.js");
+import("./lazy.js").then(({ url })=>console.log(url));
@@ -0,0 +1,2 @@
+const url = "https://example.com/lazy.js#lazy.js" + './data.json';
+export { url as url };
@@ -0,0 +1 @@
+export const assetUrl = [import.meta.url, import.meta.resolve('./logo.svg'), import.meta.main];
@@ -0,0 +1,3 @@
+import { assetUrl } from './asset';
+
+console.log('a', assetUrl);
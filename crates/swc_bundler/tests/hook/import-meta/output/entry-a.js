@@ -0,0 +1,9 @@
+const importMeta = {
+    main: false
+};
+const assetUrl = [
+    "https://example.com/entry-a.js#asset.js",
+    "https://example.com/entry-a.js#asset.js" + './logo.svg',
+    importMeta.main
+];
+console.log('a', assetUrl);
@@ -0,0 +1,9 @@
+const importMeta = {
+    main: false
+};
+const assetUrl = [
+    "https://example.com/entry-b.js#asset.js",
+    "https://example.com/entry-b.js#asset.js" + './logo.svg',
+    importMeta.main
+];
+console.log('b', assetUrl, "https://example.com/entry-b.js#entry-b.js");
@@ -0,0 +1,4 @@
+import { b } from './b/index.js';
+import { a } from './a/index.js';
+
+console.log(a, b);
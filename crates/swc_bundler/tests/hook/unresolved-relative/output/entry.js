@@ -0,0 +1,4 @@
+import { util } from './util.js';
+const b = util;
+const util1 = 'a';
+console.log(util1, b);
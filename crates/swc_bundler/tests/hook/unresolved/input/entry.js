@@ -0,0 +1,4 @@
+import { debug } from '@virtual/config';
+import { render } from 'external-pkg';
+
+render(debug);
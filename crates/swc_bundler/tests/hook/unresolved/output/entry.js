@@ -0,0 +1,2 @@
+import { render } from 'external-pkg';
+render(true);
@@ -1,12 +1,543 @@
-use clap::Parser;
-use swc_core::trace_macro::swc_trace;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
+use anyhow::Context;
+use clap::{Parser, ValueEnum};
+use serde::Deserialize;
+use swc_core::{
+    atoms::Atom,
+    base::{
+        Compiler, HandlerOpts, PrintArgs,
+        config::{Options, SourceMapsConfig},
+        resolver::{environment_resolver, paths_resolver},
+        try_with_handler,
+    },
+    bundler::{
+        Bundle, BundleKind, Bundler, ChunkRecord, ModuleRecord, ModuleType, Resolve,
+        node::{loaders::swc::SwcLoader, v1::Config as BundlerConfigFile},
+    },
+    common::{
+        FileName, FilePathMapping, GLOBALS, Globals, Mark, SourceMap, Span, errors::ColorConfig,
+    },
+    ecma::{
+        ast::{
+            Bool, Expr, IdentName, KeyValueProp, Lit, MemberExpr, MemberProp, MetaPropExpr,
+            MetaPropKind, Program, PropName, Str,
+        },
+        loader::{NODE_BUILTINS, TargetEnv},
+        transforms::{
+            base::helpers::{HELPERS, Helpers, inject_helpers},
+            module::{
+                common_js, import_analysis::import_analyzer, path::Resolver, util::ImportInterop,
+            },
+        },
+    },
+    trace_macro::swc_trace,
+};
+
+use crate::util::{format_size, trace::init_trace};
+
+/// Configuration option for bundling files.
 #[derive(Parser)]
-pub struct BundleOptions {}
+pub struct BundleOptions {
+    /// Path to a JSON config file. It has the same shape as the config of
+    /// `swc.bundle()` (`entry`, `output`, `externalModules`, `options`, ...),
+    /// with additional `format` and `sourceMaps` fields. Command line flags
+    /// take precedence over the config file.
+    #[clap(long)]
+    config_file: Option<PathBuf>,
+
+    /// Entry points in the form of `name=path`. The name is used as the name
+    /// of the output file. If the name is omitted, the file stem is used.
+    #[clap(long = "entry", value_parser = parse_entry)]
+    entries: Vec<(String, PathBuf)>,
+
+    /// The output directory
+    #[clap(long)]
+    out_dir: Option<PathBuf>,
+
+    /// Modules which should not be bundled.
+    #[clap(long, value_delimiter = ',')]
+    externals: Vec<String>,
+
+    /// The format of the output files.
+    #[clap(long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// The environment used to resolve modules. Values: browser|node
+    #[clap(long, value_parser = parse_target_env)]
+    target: Option<TargetEnv>,
+
+    /// Emit a source map next to each output file.
+    #[clap(long)]
+    source_maps: bool,
+
+    /// Code to prepend to each output file.
+    #[clap(long)]
+    banner: Option<String>,
+
+    /// Code to append to each output file.
+    #[clap(long)]
+    footer: Option<String>,
+
+    /// Enable experimental trace profiling
+    /// generates trace compatible with trace event format.
+    #[clap(long)]
+    experimental_trace: bool,
+
+    /// Set file name for the trace output. If not specified,
+    /// `trace-{unix epoch time}.json` will be used by default.
+    #[clap(long, requires = "experimental_trace")]
+    trace_out_file: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// ES module
+    #[default]
+    Esm,
+    /// CommonJS module
+    Cjs,
+    /// Immediately invoked function expression
+    Iife,
+}
+
+/// Content of the file passed via `--config-file`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigFile {
+    #[serde(flatten)]
+    config: BundlerConfigFile,
+
+    #[serde(default)]
+    format: Option<OutputFormat>,
+
+    #[serde(default)]
+    source_maps: Option<bool>,
+}
+
+fn parse_entry(s: &str) -> Result<(String, PathBuf), String> {
+    match s.split_once('=') {
+        Some((name, path)) if !name.is_empty() && !path.is_empty() => {
+            Ok((name.to_string(), PathBuf::from(path)))
+        }
+        Some(_) => Err(format!(
+            "Invalid entry '{s}'. Expected the form `name=path` or `path`"
+        )),
+        None => {
+            let path = PathBuf::from(s);
+            let name = path
+                .file_stem()
+                .ok_or_else(|| format!("Invalid entry '{s}'. Entry must be a file"))?
+                .to_string_lossy()
+                .to_string();
+
+            Ok((name, path))
+        }
+    }
+}
+
+fn parse_target_env(s: &str) -> Result<TargetEnv, String> {
+    match s {
+        "browser" => Ok(TargetEnv::Browser),
+        "node" => Ok(TargetEnv::Node),
+        _ => Err(format!(
+            "Invalid target '{s}'. Valid values are: browser, node"
+        )),
+    }
+}
+
+/// Options merged from the command line and the config file.
+struct ResolvedOptions {
+    entries: HashMap<String, FileName>,
+    out_dir: PathBuf,
+    external_modules: Vec<Atom>,
+    format: OutputFormat,
+    source_maps: bool,
+    target: TargetEnv,
+    swc_options: Options,
+    alias: HashMap<String, String>,
+    preserve_symlinks: bool,
+}
+
+/// Information about an emitted bundle, printed after bundling.
+struct EmittedFile {
+    path: PathBuf,
+    size: usize,
+    map_size: Option<usize>,
+}
+
+#[swc_trace]
+impl BundleOptions {
+    fn resolve_options(&self) -> anyhow::Result<ResolvedOptions> {
+        let config_file = self
+            .config_file
+            .as_ref()
+            .map(|path| -> anyhow::Result<ConfigFile> {
+                let content = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read config file {}", path.display()))?;
+
+                serde_json::from_str(&content)
+                    .with_context(|| format!("Failed to parse config file {}", path.display()))
+            })
+            .transpose()?;
+
+        let (config, format, source_maps) = match config_file {
+            Some(ConfigFile {
+                config,
+                format,
+                source_maps,
+            }) => (Some(config), format, source_maps),
+            None => (None, None, None),
+        };
+
+        let entries: HashMap<String, FileName> = if !self.entries.is_empty() {
+            self.entries
+                .iter()
+                .map(|(name, path)| (name.clone(), FileName::Real(path.clone())))
+                .collect()
+        } else if let Some(config) = &config {
+            config.entry.clone().into()
+        } else {
+            anyhow::bail!("No entry is specified. Use `--entry` or `--config-file`");
+        };
+
+        let out_dir = self
+            .out_dir
+            .clone()
+            .or_else(|| {
+                config
+                    .as_ref()
+                    .and_then(|c| c.output.as_ref())
+                    .map(|o| o.path.clone())
+            })
+            .context("No output directory is specified. Use `--out-dir` or `output.path`")?;
+
+        let target = self
+            .target
+            .or_else(|| config.as_ref().map(|c| c.target))
+            .unwrap_or_default();
+
+        let external_modules = config
+            .as_ref()
+            .map(|c| c.external_modules.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .chain(self.externals.iter().map(|s| Atom::from(&**s)))
+            .chain(
+                // Node.js builtins can't be bundled
+                (target == TargetEnv::Node)
+                    .then(|| NODE_BUILTINS.iter().copied().map(Atom::from))
+                    .into_iter()
+                    .flatten(),
+            )
+            .collect();
+
+        let (swc_options, alias, preserve_symlinks) = match config {
+            Some(config) => (
+                config.options.unwrap_or_else(|| Options {
+                    swcrc: true,
+                    ..Default::default()
+                }),
+                config
+                    .alias
+                    .get(&target)
+                    .map(|alias| alias.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+                    .unwrap_or_default(),
+                config.preserve_symlinks,
+            ),
+            None => (
+                Options {
+                    swcrc: true,
+                    ..Default::default()
+                },
+                Default::default(),
+                false,
+            ),
+        };
+
+        Ok(ResolvedOptions {
+            entries,
+            out_dir,
+            external_modules,
+            format: self.format.or(format).unwrap_or_default(),
+            source_maps: self.source_maps || source_maps.unwrap_or(false),
+            target,
+            swc_options,
+            alias,
+            preserve_symlinks,
+        })
+    }
+
+    fn execute_inner(&self) -> anyhow::Result<()> {
+        let ResolvedOptions {
+            entries,
+            out_dir,
+            external_modules,
+            format,
+            source_maps,
+            target,
+            swc_options,
+            alias,
+            preserve_symlinks,
+        } = self.resolve_options()?;
+
+        let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+        let compiler = Arc::new(Compiler::new(cm.clone()));
+
+        let codegen_target = swc_options.codegen_target().unwrap_or_default();
+        let minify = swc_options.config.minify.into_bool();
+
+        let resolver: Box<dyn Resolve> = if swc_options.config.jsc.paths.is_empty() {
+            Box::new(environment_resolver(
+                target,
+                alias.into_iter().collect(),
+                preserve_symlinks,
+            ))
+        } else {
+            Box::new(paths_resolver(
+                target,
+                alias.into_iter().collect(),
+                swc_options.config.jsc.base_url.clone(),
+                swc_options
+                    .config
+                    .jsc
+                    .paths
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect(),
+                preserve_symlinks,
+            ))
+        };
+        let loader = SwcLoader::new(compiler.clone(), swc_options);
+
+        let globals = Globals::default();
+
+        let emitted = try_with_handler(
+            cm.clone(),
+            HandlerOpts {
+                color: ColorConfig::Always,
+                skip_filename: false,
+            },
+            |_handler| {
+                GLOBALS.set(&globals, || {
+                    let mut bundler = Bundler::new(
+                        &globals,
+                        cm.clone(),
+                        &loader,
+                        &resolver,
+                        swc_core::bundler::Config {
+                            require: true,
+                            external_modules,
+                            module: match format {
+                                OutputFormat::Iife => ModuleType::Iife,
+                                OutputFormat::Esm | OutputFormat::Cjs => ModuleType::Es,
+                            },
+                            ..Default::default()
+                        },
+                        Box::new(Hook {
+                            banner: self.banner.clone(),
+                            footer: self.footer.clone(),
+                        }),
+                    );
+
+                    let bundles = bundler.bundle(entries)?;
+
+                    fs::create_dir_all(&out_dir).with_context(|| {
+                        format!("Failed to create output directory {}", out_dir.display())
+                    })?;
+
+                    let mut emitted = bundles
+                        .into_iter()
+                        .map(|bundle| {
+                            emit_bundle(
+                                &compiler,
+                                bundle,
+                                &out_dir,
+                                format,
+                                source_maps,
+                                swc_core::ecma::codegen::Config::default()
+                                    .with_target(codegen_target)
+                                    .with_minify(minify),
+                            )
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    emitted.sort_by(|a, b| a.path.cmp(&b.path));
+
+                    Ok(emitted)
+                })
+            },
+        )
+        .map_err(|e| e.to_pretty_error())?;
+
+        for file in emitted {
+            match file.map_size {
+                Some(map_size) => println!(
+                    "{}  {}  (map: {})",
+                    file.path.display(),
+                    format_size(file.size),
+                    format_size(map_size)
+                ),
+                None => println!("{}  {}", file.path.display(), format_size(file.size)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn emit_bundle(
+    compiler: &Compiler,
+    bundle: Bundle,
+    out_dir: &Path,
+    format: OutputFormat,
+    source_maps: bool,
+    codegen_config: swc_core::ecma::codegen::Config,
+) -> anyhow::Result<EmittedFile> {
+    let file_name = match &bundle.kind {
+        BundleKind::Named { name } | BundleKind::Lib { name } => name.clone(),
+        BundleKind::Dynamic => format!("dynamic.{}", bundle.id),
+    };
+
+    // The banner is printed as a preamble so that the source map accounts for
+    // it.
+    let preamble = bundle
+        .banner
+        .map(|banner| format!("{banner}\n"))
+        .unwrap_or_default();
+
+    let mut program = Program::Module(bundle.module);
+    if format == OutputFormat::Cjs {
+        let unresolved_mark = Mark::new();
+
+        program = HELPERS.set(&Helpers::new(true), || {
+            program.apply((
+                import_analyzer(ImportInterop::Swc, false),
+                inject_helpers(unresolved_mark),
+                common_js(
+                    Resolver::Default,
+                    unresolved_mark,
+                    Default::default(),
+                    Default::default(),
+                ),
+            ))
+        });
+    }
+
+    let mut output = compiler.print(
+        &program,
+        PrintArgs {
+            inline_sources_content: true,
+            source_map: SourceMapsConfig::Bool(source_maps),
+            emit_source_map_columns: true,
+            preamble: &preamble,
+            codegen_config,
+            ..Default::default()
+        },
+    )?;
+
+    if let Some(footer) = bundle.footer {
+        output.code.push_str(&footer);
+        output.code.push('\n');
+    }
+
+    let path = out_dir.join(format!("{file_name}.js"));
+
+    let map_size = match &output.map {
+        Some(map) => {
+            let map_path = out_dir.join(format!("{file_name}.js.map"));
+
+            output.code.push_str("\n//# sourceMappingURL=");
+            output
+                .code
+                .push_str(&map_path.file_name().unwrap().to_string_lossy());
+
+            fs::write(&map_path, map)?;
+            Some(map.len())
+        }
+        None => None,
+    };
+
+    fs::write(&path, &output.code)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    Ok(EmittedFile {
+        path,
+        size: output.code.len(),
+        map_size,
+    })
+}
+
+struct Hook {
+    banner: Option<String>,
+    footer: Option<String>,
+}
+
+impl swc_core::bundler::Hook for Hook {
+    fn get_banner(&self, _: &ChunkRecord) -> anyhow::Result<Option<String>> {
+        Ok(self.banner.clone())
+    }
+
+    fn get_footer(&self, _: &ChunkRecord) -> anyhow::Result<Option<String>> {
+        Ok(self.footer.clone())
+    }
+
+    fn get_import_meta_props(
+        &self,
+        span: Span,
+        module_record: &ModuleRecord,
+    ) -> anyhow::Result<Vec<KeyValueProp>> {
+        let file_name = module_record.file_name.to_string();
+
+        Ok(vec![
+            KeyValueProp {
+                key: PropName::Ident(IdentName::new("url".into(), span)),
+                value: Box::new(Expr::Lit(Lit::Str(Str {
+                    span,
+                    raw: None,
+                    value: file_name.into(),
+                }))),
+            },
+            KeyValueProp {
+                key: PropName::Ident(IdentName::new("main".into(), span)),
+                value: Box::new(if module_record.is_entry {
+                    Expr::Member(MemberExpr {
+                        span,
+                        obj: Box::new(Expr::MetaProp(MetaPropExpr {
+                            span,
+                            kind: MetaPropKind::ImportMeta,
+                        })),
+                        prop: MemberProp::Ident(IdentName::new("main".into(), span)),
+                    })
+                } else {
+                    Expr::Lit(Lit::Bool(Bool { span, value: false }))
+                }),
+            },
+        ])
+    }
+}
 
 #[swc_trace]
 impl super::CommandRunner for BundleOptions {
     fn execute(&self) -> anyhow::Result<()> {
-        unimplemented!("Bundle command is not yet implemented")
+        let guard = if self.experimental_trace {
+            init_trace(&self.trace_out_file)
+        } else {
+            None
+        };
+
+        let ret = self.execute_inner();
+
+        if let Some(guard) = guard {
+            guard.flush();
+            drop(guard);
+        }
+
+        ret
     }
 }
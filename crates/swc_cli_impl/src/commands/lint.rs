@@ -1,12 +1,351 @@
+use std::{
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context;
 use clap::Parser;
-use swc_core::trace_macro::swc_trace;
+use par_iter::prelude::*;
+use swc_core::{
+    base::{
+        Compiler,
+        config::{ConfigFile, Options, default_swcrc},
+    },
+    common::{
+        FilePathMapping, GLOBALS, Mark, SourceMap, SyntaxContext,
+        comments::SingleThreadedComments,
+        errors::{ColorConfig, HANDLER, Handler, Level},
+        sync::Lazy,
+    },
+    ecma::{
+        ast::Program,
+        lints::{
+            config::LintConfig,
+            rule::Rule,
+            rules::{LintParams, all},
+        },
+        transforms::base::resolver,
+    },
+    trace_macro::swc_trace,
+};
+use swc_error_reporters::{
+    ErrorEmitter, ToPrettyDiagnostic,
+    handler::{HandlerOpts, ThreadSafetyDiagnostics, to_pretty_handler},
+};
+
+use super::compile::DEFAULT_EXTENSIONS;
+use crate::util::{collect_files, trace::init_trace};
 
+/// Configuration option for linting files.
 #[derive(Parser)]
-pub struct LintOptions {}
+pub struct LintOptions {
+    /// Path to a .swcrc file to use. Lint rules are read from `jsc.lints`.
+    #[clap(long)]
+    config_file: Option<PathBuf>,
+
+    /// Override the level of a lint rule, i.e. `--rule no-console=error`.
+    /// Values: off|warn|error|0|1|2
+    #[clap(long = "rule", value_parser = parse_rule_override)]
+    rules: Vec<RuleOverride>,
+
+    /// List of glob paths to not lint.
+    #[clap(long)]
+    ignore: Option<String>,
+
+    /// Specify specific file extensions to lint when traversing directories.
+    #[clap(long)]
+    extensions: Option<Vec<String>>,
+
+    /// Files, directories or glob patterns to lint
+    #[clap(required = true)]
+    files: Vec<String>,
+
+    /// Enable experimental trace profiling
+    /// generates trace compatible with trace event format.
+    #[clap(long)]
+    experimental_trace: bool,
+
+    /// Set file name for the trace output. If not specified,
+    /// `trace-{unix epoch time}.json` will be used by default.
+    #[clap(long, requires = "experimental_trace")]
+    trace_out_file: Option<String>,
+}
+
+/// A `name=level` pair given via `--rule`.
+#[derive(Debug, Clone)]
+struct RuleOverride {
+    name: String,
+    level: serde_json::Value,
+}
+
+fn parse_rule_override(s: &str) -> Result<RuleOverride, String> {
+    let (name, level) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid rule '{s}'. Expected the form `name=level`"))?;
+
+    let level = match level.trim() {
+        "off" | "0" => serde_json::Value::from("off"),
+        "warn" | "warning" | "1" => serde_json::Value::from("warning"),
+        "error" | "2" => serde_json::Value::from("error"),
+        level => {
+            return Err(format!(
+                "Invalid level '{level}' for rule '{name}'. Valid values are: off, warn, error, \
+                 0, 1, 2"
+            ));
+        }
+    };
+
+    Ok(RuleOverride {
+        name: to_kebab_case(name.trim()),
+        level,
+    })
+}
+
+/// Rules are accepted both in the kebab-case form used by `.swcrc`
+/// (`no-console`), in camelCase (`noConsole`) and in PascalCase (`NoConsole`).
+fn to_kebab_case(name: &str) -> String {
+    let mut buf = String::with_capacity(name.len() + 4);
+
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i != 0 {
+                buf.push('-');
+            }
+            buf.push(c.to_ascii_lowercase());
+        } else {
+            buf.push(c);
+        }
+    }
+
+    buf
+}
+
+/// Applies `--rule` overrides on top of the `jsc.lints` section of .swcrc.
+fn apply_rule_overrides(
+    lints: &LintConfig,
+    overrides: &[RuleOverride],
+) -> anyhow::Result<LintConfig> {
+    if overrides.is_empty() {
+        return Ok(lints.clone());
+    }
+
+    let mut value = serde_json::to_value(lints).context("failed to serialize lint config")?;
+    let rules = value
+        .as_object_mut()
+        .expect("lint config should be serialized as an object");
+
+    for RuleOverride { name, level } in overrides {
+        // Each rule is serialized as `[level, options]`, and only the level is
+        // replaced so options from .swcrc are preserved.
+        let rule = rules
+            .get_mut(name)
+            .and_then(|rule| rule.as_array_mut())
+            .ok_or_else(|| anyhow::anyhow!("Unknown lint rule '{name}'"))?;
+
+        match rule.first_mut() {
+            Some(v) => *v = level.clone(),
+            None => rule.push(level.clone()),
+        }
+    }
+
+    serde_json::from_value(value).context("failed to apply lint rule overrides")
+}
+
+/// Lint result of a single file.
+struct FileReport {
+    errors: usize,
+    warnings: usize,
+    rendered: Vec<String>,
+}
+
+static COMPILER: Lazy<Arc<Compiler>> = Lazy::new(|| {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+
+    Arc::new(Compiler::new(cm))
+});
+
+#[swc_trace]
+impl LintOptions {
+    fn lint_file(
+        &self,
+        compiler: &Compiler,
+        file_path: &Path,
+    ) -> anyhow::Result<Option<FileReport>> {
+        let fm = compiler
+            .cm
+            .load_file(file_path)
+            .with_context(|| format!("Failed to open file {}", file_path.display()))?;
+
+        let options = Options {
+            config_file: self
+                .config_file
+                .as_ref()
+                .map(|p| ConfigFile::Str(p.to_string_lossy().to_string())),
+            swcrc: default_swcrc(),
+            filename: file_path.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+
+        let config = GLOBALS.set(&Default::default(), || {
+            compiler.read_config(&options, &fm.name)
+        })?;
+        let Some(config) = config else {
+            // The file is excluded by `test` / `exclude` of .swcrc
+            return Ok(None);
+        };
+
+        let lint_config = apply_rule_overrides(&config.jsc.lints, &self.rules)?;
+
+        let handler_opts = HandlerOpts {
+            // Diagnostics are printed to stderr
+            color: if io::stderr().is_terminal() {
+                ColorConfig::Auto
+            } else {
+                ColorConfig::Never
+            },
+            skip_filename: false,
+        };
+        let mut diagnostics = ThreadSafetyDiagnostics::default();
+        let handler = Handler::with_emitter(
+            true,
+            false,
+            Box::new(ErrorEmitter {
+                diagnostics: diagnostics.clone(),
+                cm: compiler.cm.clone(),
+                opts: handler_opts,
+            }),
+        );
+
+        let result = GLOBALS.set(&Default::default(), || {
+            HANDLER.set(&handler, || -> anyhow::Result<()> {
+                let syntax = config.jsc.syntax.unwrap_or_default();
+                let es_version = config.jsc.target.unwrap_or_default();
+                let comments = SingleThreadedComments::default();
+
+                let program = compiler.parse_js(
+                    fm.clone(),
+                    &handler,
+                    es_version,
+                    syntax,
+                    config.is_module.unwrap_or_default(),
+                    Some(&comments),
+                )?;
+
+                let unresolved_mark = Mark::new();
+                let top_level_mark = Mark::new();
+                let program = program.apply(resolver(
+                    unresolved_mark,
+                    top_level_mark,
+                    syntax.typescript(),
+                ));
+
+                let mut rules = all(LintParams {
+                    program: &program,
+                    lint_config: &lint_config,
+                    unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+                    top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+                    es_version,
+                    source_map: compiler.cm.clone(),
+                });
+
+                match &program {
+                    Program::Module(m) => rules.lint_module(m),
+                    Program::Script(s) => rules.lint_script(s),
+                }
+
+                Ok(())
+            })
+        });
+
+        let diagnostics = diagnostics.take();
+
+        if let Err(err) = result {
+            // Parse errors are reported as diagnostics, and other failures are fatal.
+            if !diagnostics.iter().any(|d| d.level == Level::Error) {
+                return Err(err.context(format!("Failed to lint {}", file_path.display())));
+            }
+        }
+
+        let pretty_handler = to_pretty_handler(handler_opts.color);
+
+        Ok(Some(FileReport {
+            errors: diagnostics
+                .iter()
+                .filter(|d| matches!(d.level, Level::Error | Level::Fatal | Level::Bug))
+                .count(),
+            warnings: diagnostics
+                .iter()
+                .filter(|d| d.level == Level::Warning)
+                .count(),
+            rendered: diagnostics
+                .iter()
+                .map(|d| {
+                    d.to_pretty_string(&compiler.cm, handler_opts.skip_filename, &pretty_handler)
+                })
+                .collect(),
+        }))
+    }
+
+    fn execute_inner(&self) -> anyhow::Result<()> {
+        let extensions = if let Some(extensions) = &self.extensions {
+            extensions.clone()
+        } else {
+            DEFAULT_EXTENSIONS.iter().map(|v| v.to_string()).collect()
+        };
+
+        let files = collect_files(&self.files, &extensions, self.ignore.as_deref())?;
+        if files.is_empty() {
+            anyhow::bail!("No files matched the given inputs");
+        }
+
+        let compiler = COMPILER.clone();
+
+        let reports = files
+            .into_par_iter()
+            .map(|file_path| self.lint_file(&compiler, &file_path))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut errors = 0;
+        let mut warnings = 0;
+
+        for report in reports.into_iter().flatten() {
+            for rendered in &report.rendered {
+                eprintln!("{rendered}");
+            }
+
+            errors += report.errors;
+            warnings += report.warnings;
+        }
+
+        if errors > 0 || warnings > 0 {
+            eprintln!("Found {errors} error(s) and {warnings} warning(s)");
+        }
+
+        if errors > 0 {
+            anyhow::bail!("Lint failed with {errors} error(s)");
+        }
+
+        Ok(())
+    }
+}
 
 #[swc_trace]
 impl super::CommandRunner for LintOptions {
     fn execute(&self) -> anyhow::Result<()> {
-        unimplemented!("Lint command is not yet implemented")
+        let guard = if self.experimental_trace {
+            init_trace(&self.trace_out_file)
+        } else {
+            None
+        };
+
+        let ret = self.execute_inner();
+
+        if let Some(guard) = guard {
+            guard.flush();
+            drop(guard);
+        }
+
+        ret
     }
 }
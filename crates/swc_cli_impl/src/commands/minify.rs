@@ -1,12 +1,360 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context;
 use clap::Parser;
-use swc_core::trace_macro::swc_trace;
+use flate2::{Compression, write::GzEncoder};
+use par_iter::prelude::*;
+use swc_core::{
+    base::{
+        Compiler, HandlerOpts, JsMinifyExtras, TransformOutput,
+        config::{JsMinifyOptions, TerserSourceMapOption},
+        try_with_handler,
+    },
+    common::{FilePathMapping, GLOBALS, SourceMap, errors::ColorConfig, sync::Lazy},
+    config::{is_module::IsModule, source_map::SourceMapContent},
+    trace_macro::swc_trace,
+};
+
+use crate::util::{collect_files, format_size, trace::init_trace};
+
+/// List of file extensions minified by default when traversing directories.
+static DEFAULT_EXTENSIONS: &[&str] = &["js", "mjs", "cjs"];
 
+/// Configuration option for minifying files.
 #[derive(Parser)]
-pub struct MinifyOptions {}
+pub struct MinifyOptions {
+    /// Path to a JSON file with the minify options. It has the same shape as
+    /// the options of `swc.minify()` (`compress`, `mangle`, `format`, ...).
+    #[clap(long)]
+    config_file: Option<PathBuf>,
+
+    /// Parse the inputs as ES modules. `.mjs` files are always parsed as ES
+    /// modules.
+    #[clap(long)]
+    module: bool,
+
+    /// Generate source maps for the minified files.
+    #[clap(long)]
+    source_maps: bool,
+
+    /// Source map of the input file, which is chained into the generated
+    /// source map. Can be used only with a single input file. If not
+    /// specified, `<file>.map` next to each input file is used if it exists.
+    #[clap(long, requires = "source_maps")]
+    input_source_map: Option<PathBuf>,
+
+    /// Write the output to the given file. Can be used only with a single
+    /// input file.
+    #[clap(long, short = 'o', group = "output")]
+    out_file: Option<PathBuf>,
+
+    /// The output directory. The directory structure of the inputs is
+    /// preserved.
+    #[clap(long, group = "output")]
+    out_dir: Option<PathBuf>,
+
+    /// List of glob paths to not minify.
+    #[clap(long)]
+    ignore: Option<String>,
+
+    /// Specify specific file extensions to minify when traversing directories.
+    #[clap(long)]
+    extensions: Option<Vec<String>>,
+
+    /// Files, directories or glob patterns to minify
+    #[clap(required = true)]
+    files: Vec<String>,
+
+    /// Enable experimental trace profiling
+    /// generates trace compatible with trace event format.
+    #[clap(long)]
+    experimental_trace: bool,
+
+    /// Set file name for the trace output. If not specified,
+    /// `trace-{unix epoch time}.json` will be used by default.
+    #[clap(long, requires = "experimental_trace")]
+    trace_out_file: Option<String>,
+}
+
+static COMPILER: Lazy<Arc<Compiler>> = Lazy::new(|| {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+
+    Arc::new(Compiler::new(cm))
+});
+
+struct InputFile {
+    file_path: PathBuf,
+    /// `None` if the output should be printed to stdout.
+    output_path: Option<PathBuf>,
+}
+
+/// Size statistics of a single minified file.
+struct FileStats {
+    file_path: PathBuf,
+    original_size: usize,
+    minified_size: usize,
+    original_gzip_size: usize,
+    minified_gzip_size: usize,
+}
+
+fn gzip_size(data: &[u8]) -> anyhow::Result<usize> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+
+    Ok(encoder.finish()?.len())
+}
+
+#[swc_trace]
+impl MinifyOptions {
+    fn read_config(&self) -> anyhow::Result<JsMinifyOptions> {
+        let config = match &self.config_file {
+            Some(path) => fs::read_to_string(path)
+                .with_context(|| format!("Failed to read config file {}", path.display()))?,
+            None => "{}".to_string(),
+        };
+
+        serde_json::from_str(&config).context("Failed to parse minify options")
+    }
+
+    fn collect_inputs(&self) -> anyhow::Result<Vec<InputFile>> {
+        let extensions = if let Some(extensions) = &self.extensions {
+            extensions.clone()
+        } else {
+            DEFAULT_EXTENSIONS.iter().map(|v| v.to_string()).collect()
+        };
+
+        let mut inputs = Vec::new();
+
+        for input in &self.files {
+            let base_dir = Path::new(input);
+            let base_dir = base_dir.is_dir().then_some(base_dir);
+
+            for file_path in collect_files(
+                std::slice::from_ref(input),
+                &extensions,
+                self.ignore.as_deref(),
+            )? {
+                let output_path = match (&self.out_file, &self.out_dir) {
+                    (Some(out_file), _) => Some(out_file.clone()),
+                    (None, Some(out_dir)) => {
+                        let relative = match base_dir {
+                            Some(base_dir) => file_path.strip_prefix(base_dir)?,
+                            None => Path::new(
+                                file_path
+                                    .file_name()
+                                    .context("Filename should be available")?,
+                            ),
+                        };
+
+                        Some(out_dir.join(relative))
+                    }
+                    (None, None) => None,
+                };
+
+                inputs.push(InputFile {
+                    file_path,
+                    output_path,
+                });
+            }
+        }
+
+        if inputs.is_empty() {
+            anyhow::bail!("No files matched the given inputs");
+        }
+
+        if inputs.len() > 1 {
+            if self.out_dir.is_none() {
+                anyhow::bail!("Use `--out-dir` to minify multiple files");
+            }
+            if self.input_source_map.is_some() {
+                anyhow::bail!("`--input-source-map` can be used only with a single input file");
+            }
+        }
+
+        Ok(inputs)
+    }
+
+    /// Reads the source map of the input file, if any.
+    fn read_input_source_map(&self, file_path: &Path) -> anyhow::Result<Option<SourceMapContent>> {
+        let path = match &self.input_source_map {
+            Some(path) => path.clone(),
+            None => {
+                let mut path = file_path.as_os_str().to_owned();
+                path.push(".map");
+
+                let path = PathBuf::from(path);
+                if !path.is_file() {
+                    return Ok(None);
+                }
+                path
+            }
+        };
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read source map {}", path.display()))?;
+
+        Ok(Some(SourceMapContent::Json(content)))
+    }
+
+    fn minify_file(
+        &self,
+        compiler: &Arc<Compiler>,
+        options: &JsMinifyOptions,
+        input: &InputFile,
+    ) -> anyhow::Result<(TransformOutput, FileStats)> {
+        let fm = compiler
+            .cm
+            .load_file(&input.file_path)
+            .with_context(|| format!("Failed to open file {}", input.file_path.display()))?;
+
+        let mut options = options.clone();
+
+        if self.module || input.file_path.extension().is_some_and(|ext| ext == "mjs") {
+            options.module = IsModule::Bool(true);
+        }
+
+        if let Some(output_path) = &input.output_path {
+            options.output_path = Some(output_path.to_string_lossy().to_string());
+        }
+
+        let source_maps =
+            self.source_maps || options.source_map.is_true() || options.source_map.is_obj();
+
+        if source_maps {
+            if input.output_path.is_none() {
+                anyhow::bail!("Source maps require `--out-file` or `--out-dir`");
+            }
+
+            let mut source_map: TerserSourceMapOption = options
+                .source_map
+                .unwrap_as_option(|_| None)
+                .unwrap_or_default();
+
+            if source_map.content.is_none() {
+                source_map.content = self.read_input_source_map(&input.file_path)?;
+            }
+
+            if source_map.url.is_none() {
+                source_map.url = input.output_path.as_ref().and_then(|p| {
+                    p.file_name()
+                        .map(|name| format!("{}.map", name.to_string_lossy()))
+                });
+            }
+
+            options.source_map = source_map.into();
+        }
+
+        let output = try_with_handler(
+            compiler.cm.clone(),
+            HandlerOpts {
+                color: ColorConfig::Always,
+                skip_filename: false,
+            },
+            |handler| {
+                GLOBALS.set(&Default::default(), || {
+                    compiler.minify(fm.clone(), handler, &options, JsMinifyExtras::default())
+                })
+            },
+        )
+        .map_err(|e| e.to_pretty_error())?;
+
+        let stats = FileStats {
+            file_path: input.file_path.clone(),
+            original_size: fm.src.len(),
+            minified_size: output.code.len(),
+            original_gzip_size: gzip_size(fm.src.as_bytes())?,
+            minified_gzip_size: gzip_size(output.code.as_bytes())?,
+        };
+
+        Ok((output, stats))
+    }
+
+    fn emit_output(&self, output: TransformOutput, input: &InputFile) -> anyhow::Result<()> {
+        let Some(output_path) = &input.output_path else {
+            println!("{}", output.code);
+            return Ok(());
+        };
+
+        if let Some(output_dir) = output_path.parent() {
+            fs::create_dir_all(output_dir)?;
+        }
+
+        if let Some(source_map) = &output.map {
+            let mut source_map_path = output_path.as_os_str().to_owned();
+            source_map_path.push(".map");
+
+            fs::write(source_map_path, source_map)?;
+        }
+
+        fs::write(output_path, &output.code)
+            .with_context(|| format!("Failed to write {}", output_path.display()))
+    }
+
+    fn execute_inner(&self) -> anyhow::Result<()> {
+        let options = self.read_config()?;
+        let inputs = self.collect_inputs()?;
+
+        let compiler = COMPILER.clone();
+
+        let minify = |input: InputFile| -> anyhow::Result<FileStats> {
+            let (output, stats) = self.minify_file(&compiler, &options, &input)?;
+            self.emit_output(output, &input)?;
+
+            Ok(stats)
+        };
+
+        // Each file reads and updates the name cache, so they are minified one
+        // after another.
+        let stats = if options.name_cache.is_some() {
+            inputs
+                .into_iter()
+                .map(minify)
+                .collect::<anyhow::Result<Vec<_>>>()?
+        } else {
+            inputs
+                .into_par_iter()
+                .map(minify)
+                .collect::<anyhow::Result<Vec<_>>>()?
+        };
+
+        // Stats are printed to stderr so they don't interfere with the code printed
+        // to stdout.
+        for stats in stats {
+            eprintln!(
+                "{}  {} -> {}  (gzip: {} -> {})",
+                stats.file_path.display(),
+                format_size(stats.original_size),
+                format_size(stats.minified_size),
+                format_size(stats.original_gzip_size),
+                format_size(stats.minified_gzip_size),
+            );
+        }
+
+        Ok(())
+    }
+}
 
 #[swc_trace]
 impl super::CommandRunner for MinifyOptions {
     fn execute(&self) -> anyhow::Result<()> {
-        unimplemented!("Minify command is not yet implemented")
+        let guard = if self.experimental_trace {
+            init_trace(&self.trace_out_file)
+        } else {
+            None
+        };
+
+        let ret = self.execute_inner();
+
+        if let Some(guard) = guard {
+            guard.flush();
+            drop(guard);
+        }
+
+        ret
     }
 }
@@ -1 +1,66 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use glob::glob;
+use walkdir::WalkDir;
+
 pub(crate) mod trace;
+
+/// Expands the cli inputs into a list of files. Directories are traversed and
+/// filtered by `extensions`, and glob patterns are expanded.
+#[tracing::instrument(level = "info", skip_all)]
+pub(crate) fn collect_files(
+    inputs: &[String],
+    extensions: &[String],
+    ignore_pattern: Option<&str>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let has_extension = |path: &Path| {
+        extensions
+            .iter()
+            .any(|ext| path.extension().map(|v| v == &**ext).unwrap_or(false))
+    };
+
+    let mut files = Vec::new();
+
+    for input in inputs {
+        let path = Path::new(input);
+
+        if path.is_dir() {
+            files.extend(
+                WalkDir::new(path)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.into_path())
+                    .filter(|e| e.is_file() && has_extension(e)),
+            );
+        } else if input.contains(['*', '?', '[']) {
+            for entry in glob(input).with_context(|| format!("Invalid glob pattern '{input}'"))? {
+                let entry = entry?;
+                if entry.is_file() && has_extension(&entry) {
+                    files.push(entry);
+                }
+            }
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+
+    if let Some(ignore_pattern) = ignore_pattern {
+        let pattern: Vec<PathBuf> = glob(ignore_pattern)?.filter_map(|p| p.ok()).collect();
+
+        files.retain(|file_path| !pattern.iter().any(|p| p.eq(file_path)));
+    }
+
+    files.sort();
+    files.dedup();
+
+    Ok(files)
+}
+
+pub(crate) fn format_size(size: usize) -> String {
+    if size < 1024 {
+        format!("{size} B")
+    } else {
+        format!("{:.2} kB", size as f64 / 1024.0)
+    }
+}
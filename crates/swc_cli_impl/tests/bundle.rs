@@ -0,0 +1,135 @@
+use std::{
+    fs,
+    process::{Command, Stdio},
+};
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::TempDir;
+
+fn cli() -> Result<Command> {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("swc"));
+    cmd.stderr(Stdio::piped());
+    Ok(cmd)
+}
+
+fn setup() -> Result<TempDir> {
+    let tmp = TempDir::new()?;
+
+    fs::create_dir_all(tmp.path().join("src"))?;
+    fs::write(
+        tmp.path().join("src/index.js"),
+        "import { foo } from './foo';\nimport React from 'react';\nconsole.log(foo, React);\n",
+    )?;
+    fs::write(tmp.path().join("src/foo.js"), "export const foo = 1;\n")?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn bundle_esm() -> Result<()> {
+    let tmp = setup()?;
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("bundle")
+        .arg("--entry")
+        .arg("main=src/index.js")
+        .arg("--out-dir")
+        .arg("dist")
+        .arg("--externals")
+        .arg("react")
+        .arg("--source-maps")
+        .assert()
+        .success();
+
+    let output = fs::read_to_string(tmp.path().join("dist/main.js"))?;
+    assert!(output.contains("foo = 1"), "{}", output);
+    assert!(output.contains("from 'react'"), "{}", output);
+    assert!(
+        output.contains("//# sourceMappingURL=main.js.map"),
+        "{}",
+        output
+    );
+    assert!(tmp.path().join("dist/main.js.map").is_file());
+
+    Ok(())
+}
+
+#[test]
+fn bundle_cjs() -> Result<()> {
+    let tmp = setup()?;
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("bundle")
+        .arg("--entry")
+        .arg("src/index.js")
+        .arg("--out-dir")
+        .arg("out")
+        .arg("--externals")
+        .arg("react")
+        .arg("--format")
+        .arg("cjs")
+        .assert()
+        .success();
+
+    let output = fs::read_to_string(tmp.path().join("out/index.js"))?;
+    assert!(output.contains("require(\"react\")"), "{}", output);
+    assert!(!output.contains("import "), "{}", output);
+
+    Ok(())
+}
+
+#[test]
+fn bundle_banner_and_footer() -> Result<()> {
+    let tmp = setup()?;
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("bundle")
+        .arg("--entry")
+        .arg("main=src/index.js")
+        .arg("--out-dir")
+        .arg("dist")
+        .arg("--externals")
+        .arg("react")
+        .arg("--source-maps")
+        .arg("--banner")
+        .arg("/* banner */")
+        .arg("--footer")
+        .arg("/* footer */")
+        .assert()
+        .success();
+
+    let output = fs::read_to_string(tmp.path().join("dist/main.js"))?;
+    assert!(output.starts_with("/* banner */\n"), "{}", output);
+    assert!(
+        output.ends_with("/* footer */\n\n//# sourceMappingURL=main.js.map"),
+        "{}",
+        output
+    );
+
+    // Mappings of the first line of the module start after the banner.
+    let map = fs::read_to_string(tmp.path().join("dist/main.js.map"))?;
+    assert!(map.contains(r#""mappings":";"#), "{}", map);
+
+    Ok(())
+}
+
+#[test]
+fn bundle_missing_entry_fails() -> Result<()> {
+    let tmp = setup()?;
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("bundle")
+        .arg("--entry")
+        .arg("src/missing.js")
+        .arg("--out-dir")
+        .arg("dist")
+        .assert()
+        .failure();
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::{
+    fs,
+    process::{Command, Stdio},
+};
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::TempDir;
+
+fn cli() -> Result<Command> {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("swc"));
+    cmd.stderr(Stdio::piped());
+    Ok(cmd)
+}
+
+fn setup(swcrc: &str) -> Result<TempDir> {
+    let tmp = TempDir::new()?;
+
+    fs::create_dir_all(tmp.path().join("src"))?;
+    fs::write(tmp.path().join(".swcrc"), swcrc)?;
+    fs::write(
+        tmp.path().join("src/index.js"),
+        "console.log('foo');\ndebugger;\n",
+    )?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn lint_error_fails() -> Result<()> {
+    let tmp = setup(r#"{ "jsc": { "lints": { "no-debugger": ["error"] } } }"#)?;
+
+    let output = cli()?.current_dir(&tmp).arg("lint").arg("src").output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("debugger"), "{}", stderr);
+
+    Ok(())
+}
+
+#[test]
+fn lint_warning_passes() -> Result<()> {
+    let tmp = setup(r#"{ "jsc": { "lints": { "no-console": ["warning"] } } }"#)?;
+
+    let output = cli()?
+        .current_dir(&tmp)
+        .arg("lint")
+        .arg("src/*.js")
+        .output()?;
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("0 error(s) and 1 warning(s)"), "{}", stderr);
+
+    Ok(())
+}
+
+#[test]
+fn lint_rule_override() -> Result<()> {
+    let tmp = setup(r#"{ "jsc": { "lints": { "no-console": ["warning"] } } }"#)?;
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("lint")
+        .arg("--rule")
+        .arg("noConsole=error")
+        .arg("src/index.js")
+        .assert()
+        .failure();
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("lint")
+        .arg("--rule")
+        .arg("no-console=off")
+        .arg("src/index.js")
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn lint_rule_override_pascal_case() -> Result<()> {
+    let tmp = setup(r#"{ "jsc": { "lints": { "no-console": ["error"] } } }"#)?;
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("lint")
+        .arg("--rule")
+        .arg("NoConsole=off")
+        .arg("src/index.js")
+        .assert()
+        .success();
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use std::{
+    fs,
+    process::{Command, Stdio},
+};
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::TempDir;
+
+fn cli() -> Result<Command> {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("swc"));
+    cmd.stderr(Stdio::piped());
+    Ok(cmd)
+}
+
+fn setup() -> Result<TempDir> {
+    let tmp = TempDir::new()?;
+
+    fs::create_dir_all(tmp.path().join("src/nested"))?;
+    fs::write(
+        tmp.path().join("src/index.js"),
+        "function hello(longName) {\n  return 'hi ' + longName;\n}\nconsole.log(hello('x'));\n",
+    )?;
+    fs::write(
+        tmp.path().join("src/nested/lib.mjs"),
+        "export function double(value) {\n  return value * 2;\n}\n",
+    )?;
+
+    Ok(tmp)
+}
+
+#[test]
+fn minify_single_file() -> Result<()> {
+    let tmp = setup()?;
+
+    let output = cli()?
+        .current_dir(&tmp)
+        .arg("minify")
+        .arg("src/index.js")
+        .arg("-o")
+        .arg("out/index.min.js")
+        .output()?;
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("gzip:"), "{}", stderr);
+
+    let code = fs::read_to_string(tmp.path().join("out/index.min.js"))?;
+    assert!(!code.contains("longName"), "{}", code);
+
+    Ok(())
+}
+
+#[test]
+fn minify_directory() -> Result<()> {
+    let tmp = setup()?;
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("minify")
+        .arg("src")
+        .arg("--out-dir")
+        .arg("dist")
+        .assert()
+        .success();
+
+    assert!(tmp.path().join("dist/index.js").is_file());
+    let code = fs::read_to_string(tmp.path().join("dist/nested/lib.mjs"))?;
+    assert!(code.starts_with("export function double"), "{}", code);
+
+    Ok(())
+}
+
+#[test]
+fn minify_chains_input_source_map() -> Result<()> {
+    let tmp = setup()?;
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("compile")
+        .arg("src/index.js")
+        .arg("--out-dir")
+        .arg("build")
+        .arg("--source-maps")
+        .arg("true")
+        .assert()
+        .success();
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("minify")
+        .arg("build/src/index.js")
+        .arg("-o")
+        .arg("out/index.min.js")
+        .arg("--source-maps")
+        .assert()
+        .success();
+
+    let code = fs::read_to_string(tmp.path().join("out/index.min.js"))?;
+    assert!(
+        code.ends_with("//# sourceMappingURL=index.min.js.map"),
+        "{}",
+        code
+    );
+
+    let map = fs::read_to_string(tmp.path().join("out/index.min.js.map"))?;
+    let map: serde_json::Value = serde_json::from_str(&map)?;
+    assert_eq!(map["sources"], serde_json::json!(["src/index.js"]));
+
+    Ok(())
+}
+
+#[test]
+fn minify_multiple_files_requires_out_dir() -> Result<()> {
+    let tmp = setup()?;
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("minify")
+        .arg("src")
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
+#[test]
+fn minify_multiple_files_with_name_cache() -> Result<()> {
+    let tmp = setup()?;
+
+    fs::write(
+        tmp.path().join("src/twice.js"),
+        "function twice(value) {\n  return value * 2;\n}\nconsole.log(twice(1));\n",
+    )?;
+    fs::write(
+        tmp.path().join("minify.json"),
+        r#"{ "compress": false, "mangle": true, "toplevel": true, "nameCache": "names.json" }"#,
+    )?;
+
+    cli()?
+        .current_dir(&tmp)
+        .arg("minify")
+        .arg("src")
+        .arg("--out-dir")
+        .arg("dist")
+        .arg("--config-file")
+        .arg("minify.json")
+        .assert()
+        .success();
+
+    // The names of both files are kept.
+    let cache = fs::read_to_string(tmp.path().join("names.json"))?;
+    let cache: serde_json::Value = serde_json::from_str(&cache)?;
+    assert!(cache["vars"].get("hello").is_some(), "{}", cache);
+    assert!(cache["vars"].get("twice").is_some(), "{}", cache);
+
+    Ok(())
+}
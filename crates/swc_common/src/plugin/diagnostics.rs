@@ -1,3 +1,8 @@
+use crate::{
+    Span,
+    errors::{Applicability, Diagnostic, DiagnosticId, Level},
+};
+
 /// A serializable, wrapped struct for the diagnostics information
 /// included in plugin binaries.
 /// TODO: Must implement bytecheck with forward-compatible schema changes to
@@ -19,3 +24,130 @@ pub struct PluginCorePkgDiagnostics {
     pub cargo_features: String,
     pub ast_schema_version: u32,
 }
+
+/// A diagnostic reported by an analysis plugin.
+///
+/// Unlike [crate::errors::Diagnostic], this is a small and stable structure, so
+/// plugins built against a different version of `swc_common` can still report
+/// it to the host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "diagnostic-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "diagnostic-serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "encoding-impl",
+    derive(::ast_node::Encode, ::ast_node::Decode)
+)]
+pub struct PluginAnalysisDiagnostic {
+    pub span: Span,
+    pub severity: PluginDiagnosticSeverity,
+    /// An identifier of the rule which reported the diagnostic, like
+    /// `no-console`.
+    #[cfg_attr(
+        feature = "encoding-impl",
+        encoding(with = "cbor4ii::core::types::Maybe")
+    )]
+    pub code: Option<String>,
+    pub message: String,
+    pub fixes: Vec<PluginSuggestedFix>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "diagnostic-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "diagnostic-serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "encoding-impl",
+    derive(::ast_node::Encode, ::ast_node::Decode)
+)]
+pub enum PluginDiagnosticSeverity {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+/// A fix suggested by a [PluginAnalysisDiagnostic], made of edits which should
+/// be applied together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "diagnostic-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(
+    feature = "encoding-impl",
+    derive(::ast_node::Encode, ::ast_node::Decode)
+)]
+pub struct PluginSuggestedFix {
+    pub message: String,
+    pub edits: Vec<PluginTextEdit>,
+}
+
+/// Replaces the source code at `span` with `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "diagnostic-serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(
+    feature = "encoding-impl",
+    derive(::ast_node::Encode, ::ast_node::Decode)
+)]
+pub struct PluginTextEdit {
+    pub span: Span,
+    pub text: String,
+}
+
+impl PluginDiagnosticSeverity {
+    pub fn is_error(self) -> bool {
+        self == PluginDiagnosticSeverity::Error
+    }
+}
+
+impl From<PluginDiagnosticSeverity> for Level {
+    fn from(severity: PluginDiagnosticSeverity) -> Self {
+        match severity {
+            PluginDiagnosticSeverity::Error => Level::Error,
+            PluginDiagnosticSeverity::Warning => Level::Warning,
+            PluginDiagnosticSeverity::Note => Level::Note,
+            PluginDiagnosticSeverity::Help => Level::Help,
+        }
+    }
+}
+
+impl PluginAnalysisDiagnostic {
+    /// Converts into a [Diagnostic], which can be emitted by a
+    /// [crate::errors::Handler].
+    ///
+    /// Each fix is added as a help message and, if it has edits, as a
+    /// suggestion.
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new_with_code(
+            self.severity.into(),
+            self.code.clone().map(DiagnosticId::Lint),
+            &self.message,
+        );
+        diagnostic.set_span(self.span);
+
+        for fix in &self.fixes {
+            diagnostic.help(&fix.message);
+
+            if !fix.edits.is_empty() {
+                diagnostic.multipart_suggestion_with_applicability(
+                    &fix.message,
+                    fix.edits
+                        .iter()
+                        .map(|edit| (edit.span, edit.text.clone()))
+                        .collect(),
+                    Applicability::MachineApplicable,
+                );
+            }
+        }
+
+        diagnostic
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// Resource limits applied to a single plugin invocation.
+///
+/// A limit of `None` means the resource is unlimited.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Limits {
+    /// Instruction fuel. Roughly, one unit of fuel is consumed per executed
+    /// Wasm instruction.
+    #[serde(default)]
+    pub fuel: Option<u64>,
+
+    /// Maximum size of the linear memory, in bytes.
+    #[serde(default)]
+    pub max_memory: Option<u64>,
+}
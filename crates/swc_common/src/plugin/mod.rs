@@ -1,6 +1,9 @@
 pub mod diagnostics;
 pub mod emit;
+pub mod limits;
 pub mod metadata;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod native;
 #[cfg(feature = "__plugin")]
 #[cfg_attr(docsrs, doc(cfg(feature = "__plugin")))]
 pub mod serialized;
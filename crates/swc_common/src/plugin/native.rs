@@ -0,0 +1,205 @@
+//! C ABI of the plugins built as native shared libraries.
+//!
+//! A native plugin receives the same serialized bytes as a Wasm plugin, through
+//! the entrypoints generated by `#[plugin_transform]`. Unlike a Wasm plugin, it
+//! doesn't share a linear memory with the host, so the host passes a
+//! [NativePluginHost] to the entrypoints, which copies the bytes exchanged with
+//! the functions imported from the host.
+//!
+//! Native plugins are loaded in the process of the host, so they must be
+//! trusted.
+
+use std::ffi::c_void;
+
+/// Version of the C ABI, returned by `__swc_native_plugin_abi_version`.
+///
+/// This is bumped when [NativePluginHost] or the signature of the entrypoints
+/// change. The AST schema version is checked separately, as for Wasm plugins.
+pub const NATIVE_PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Returned by `__swc_native_transform_plugin_process_impl` if the plugin
+/// panicked.
+pub const NATIVE_PLUGIN_PANICKED: u32 = u32::MAX;
+
+/// Functions of the host, passed to the entrypoints of a native plugin.
+///
+/// All functions return a negative value if `name` is not a function imported
+/// from the host, or if the host function failed.
+#[repr(C)]
+pub struct NativePluginHost {
+    /// Opaque pointer passed back to the functions.
+    pub ctx: *mut c_void,
+
+    /// Copies `bytes_len` bytes at `bytes_ptr` to the host, then calls the host
+    /// function `name` with the copied bytes, like
+    /// `__set_transform_result(bytes_ptr, bytes_len)`.
+    pub send: unsafe extern "C" fn(
+        ctx: *mut c_void,
+        name_ptr: *const u8,
+        name_len: usize,
+        bytes_ptr: *const u8,
+        bytes_len: usize,
+    ) -> i32,
+
+    /// Calls the host function `name` with `args`, followed by a pointer to
+    /// receive the bytes returned by the host, like
+    /// `__get_transform_context(key, allocated_ret_ptr)`.
+    ///
+    /// Returns the value returned by the host function. If it's not zero,
+    /// `ret_ptr` and `ret_len` are set to the returned bytes, which are valid
+    /// until the next call to the host.
+    pub request: unsafe extern "C" fn(
+        ctx: *mut c_void,
+        name_ptr: *const u8,
+        name_len: usize,
+        args_ptr: *const i32,
+        args_len: usize,
+        ret_ptr: *mut *const u8,
+        ret_len: *mut usize,
+    ) -> i32,
+
+    /// Calls the host function `name` with `args` and returns its return
+    /// value as an unsigned integer, or `0` if it doesn't return anything.
+    ///
+    /// If `buf_ptr` is not null, a copy of the `buf_len` bytes at `buf_ptr` is
+    /// passed after `args`, like
+    /// `__mark_is_descendant_of_proxy(mark, ancestor, allocated_ptr)`, and the
+    /// bytes written by the host are copied back to `buf_ptr`.
+    pub call: unsafe extern "C" fn(
+        ctx: *mut c_void,
+        name_ptr: *const u8,
+        name_len: usize,
+        args_ptr: *const i32,
+        args_len: usize,
+        buf_ptr: *mut u8,
+        buf_len: usize,
+    ) -> i64,
+}
+
+#[cfg(feature = "__plugin_mode")]
+thread_local! {
+    static HOST: std::cell::Cell<*const NativePluginHost> = const {
+        std::cell::Cell::new(std::ptr::null())
+    };
+}
+
+/// Sets the host of the plugin while running `f`.
+///
+/// This should not be called directly; it's used by the entrypoints generated
+/// by `#[plugin_transform]`.
+#[cfg(feature = "__plugin_mode")]
+pub fn with_host<Ret>(host: *const NativePluginHost, f: impl FnOnce() -> Ret) -> Ret {
+    struct Restore(*const NativePluginHost);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            HOST.with(|host| host.set(self.0));
+        }
+    }
+
+    let _restore = Restore(HOST.with(|current| current.replace(host)));
+
+    f()
+}
+
+#[cfg(feature = "__plugin_mode")]
+fn host() -> Option<&'static NativePluginHost> {
+    // Safety: the host outlives the call to the entrypoint which set it.
+    HOST.with(|host| unsafe { host.get().as_ref() })
+}
+
+/// Returns `true` if the plugin is running in a host.
+#[cfg(feature = "__plugin_mode")]
+pub fn has_host() -> bool {
+    host().is_some()
+}
+
+/// Calls the host function `name` with `bytes`, see [NativePluginHost::send].
+///
+/// Does nothing if the plugin is not running in a host, to allow testing.
+#[cfg(feature = "__plugin_mode")]
+pub fn send_to_host(name: &str, bytes: &[u8]) {
+    let Some(host) = host() else {
+        return;
+    };
+
+    let ret = unsafe {
+        (host.send)(
+            host.ctx,
+            name.as_ptr(),
+            name.len(),
+            bytes.as_ptr(),
+            bytes.len(),
+        )
+    };
+    assert!(ret >= 0, "Host function `{name}` failed");
+}
+
+/// Calls the host function `name`, see [NativePluginHost::call].
+///
+/// Returns `None` if the plugin is not running in a host, in which case the
+/// caller should fall back to its own implementation.
+#[cfg(feature = "__plugin_mode")]
+pub fn call_host(name: &str, args: &[i32], buf: Option<&mut [u8]>) -> Option<u32> {
+    let host = host()?;
+
+    let (buf_ptr, buf_len) = match buf {
+        Some(buf) => (buf.as_mut_ptr(), buf.len()),
+        None => (std::ptr::null_mut(), 0),
+    };
+    let ret = unsafe {
+        (host.call)(
+            host.ctx,
+            name.as_ptr(),
+            name.len(),
+            args.as_ptr(),
+            args.len(),
+            buf_ptr,
+            buf_len,
+        )
+    };
+    assert!(ret >= 0, "Host function `{name}` failed");
+
+    Some(ret as u32)
+}
+
+/// Calls the host function `name` and deserializes the returned bytes, see
+/// [NativePluginHost::request].
+///
+/// Returns `None` if the host didn't return a value, or if the plugin is not
+/// running in a host.
+#[cfg(all(feature = "__plugin_mode", feature = "__plugin"))]
+pub fn read_returned_result_from_host<R>(name: &str, args: &[i32]) -> Option<R>
+where
+    R: for<'de> cbor4ii::core::dec::Decode<'de>,
+{
+    let host = host()?;
+
+    let mut ret_ptr = std::ptr::null();
+    let mut ret_len = 0;
+    let ret = unsafe {
+        (host.request)(
+            host.ctx,
+            name.as_ptr(),
+            name.len(),
+            args.as_ptr(),
+            args.len(),
+            &mut ret_ptr,
+            &mut ret_len,
+        )
+    };
+    assert!(ret >= 0, "Host function `{name}` failed");
+
+    if ret == 0 {
+        return None;
+    }
+
+    // The bytes are copied, as they are only valid until the next call to the
+    // host.
+    Some(
+        super::serialized::PluginSerializedBytes::from_raw_ptr(ret_ptr, ret_len)
+            .deserialize()
+            .expect("Returned value should be serializable")
+            .into_inner(),
+    )
+}
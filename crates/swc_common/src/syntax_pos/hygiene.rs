@@ -153,6 +153,13 @@ impl Mark {
         // https://github.com/swc-project/swc/pull/3492#discussion_r802224857
         // We loosen conditions here for the cases like running plugin's test without
         // targeting wasm32-*.
+        #[cfg(all(feature = "__plugin_mode", not(target_arch = "wasm32")))]
+        if let Some(mark) =
+            crate::plugin::native::call_host("__mark_fresh_proxy", &[parent.0 as _], None)
+        {
+            return Mark(mark);
+        }
+
         #[cfg(not(all(feature = "__plugin_mode", target_arch = "wasm32")))]
         return with_marks(|marks| {
             marks.push(MarkData { parent });
@@ -182,6 +189,13 @@ impl Mark {
         #[cfg(all(feature = "__plugin_mode", target_arch = "wasm32"))]
         return Mark(unsafe { __mark_parent_proxy(self.0) });
 
+        #[cfg(all(feature = "__plugin_mode", not(target_arch = "wasm32")))]
+        if let Some(mark) =
+            crate::plugin::native::call_host("__mark_parent_proxy", &[self.0 as _], None)
+        {
+            return Mark(mark);
+        }
+
         #[cfg(not(all(feature = "__plugin_mode", target_arch = "wasm32")))]
         return with_marks(|marks| marks[self.0 as usize].parent);
     }
@@ -222,6 +236,13 @@ impl Mark {
 
     #[cfg(not(all(feature = "__plugin_mode", target_arch = "wasm32")))]
     pub fn is_descendant_of(mut self, ancestor: Mark) -> bool {
+        #[cfg(feature = "__plugin_mode")]
+        if let Some(context) =
+            native_mark_context_proxy("__mark_is_descendant_of_proxy", &[self.0, ancestor.0])
+        {
+            return context.2 != 0;
+        }
+
         with_marks(|marks| {
             while self != ancestor {
                 if self == Mark::root() {
@@ -273,6 +294,11 @@ impl Mark {
     #[allow(unused_mut)]
     #[cfg(not(all(feature = "__plugin_mode", target_arch = "wasm32")))]
     pub fn least_ancestor(mut a: Mark, mut b: Mark) -> Mark {
+        #[cfg(feature = "__plugin_mode")]
+        if let Some(context) = native_mark_context_proxy("__mark_least_ancestor", &[a.0, b.0]) {
+            return Mark(context.2);
+        }
+
         with_marks(|marks| {
             // Compute the path from a to the root
             let mut a_path = HashSet::<Mark>::default();
@@ -321,6 +347,33 @@ impl HygieneData {
     }
 }
 
+/// Calls a hygiene function of the host of a native plugin, which writes its
+/// result into a [MutableMarkContext].
+///
+/// Returns `None` if the plugin is not running in a host.
+#[cfg(all(feature = "__plugin_mode", not(target_arch = "wasm32")))]
+fn native_mark_context_proxy(name: &str, args: &[u32]) -> Option<MutableMarkContext> {
+    use crate::plugin::serialized::{PluginSerializedBytes, VersionedSerializable};
+
+    // The context written by the host is never larger than this one.
+    let mut buf = PluginSerializedBytes::try_serialize(&VersionedSerializable::new(
+        MutableMarkContext(u32::MAX, u32::MAX, u32::MAX),
+    ))
+    .expect("Should be serializable")
+    .as_slice()
+    .to_vec();
+    let args = args.iter().map(|&arg| arg as i32).collect::<Vec<_>>();
+
+    crate::plugin::native::call_host(name, &args, Some(&mut buf))?;
+
+    Some(
+        PluginSerializedBytes::from_bytes(buf)
+            .deserialize()
+            .expect("Should able to deserialize")
+            .into_inner(),
+    )
+}
+
 #[track_caller]
 #[allow(unused)]
 pub(crate) fn with_marks<T, F: FnOnce(&mut Vec<MarkData>) -> T>(f: F) -> T {
@@ -383,6 +436,15 @@ impl SyntaxContext {
 
         #[cfg(not(all(feature = "__plugin_mode", target_arch = "wasm32")))]
         {
+            #[cfg(feature = "__plugin_mode")]
+            if let Some(ctxt) = crate::plugin::native::call_host(
+                "__syntax_context_apply_mark_proxy",
+                &[self.0 as _, mark.0 as _],
+                None,
+            ) {
+                return SyntaxContext(ctxt);
+            }
+
             assert_ne!(mark, Mark::root());
             self.apply_mark_internal(mark)
         }
@@ -448,6 +510,14 @@ impl SyntaxContext {
     /// Returns the mark that was removed.
     #[cfg(not(all(feature = "__plugin_mode", target_arch = "wasm32")))]
     pub fn remove_mark(&mut self) -> Mark {
+        #[cfg(feature = "__plugin_mode")]
+        if let Some(context) =
+            native_mark_context_proxy("__syntax_context_remove_mark_proxy", &[self.0])
+        {
+            *self = SyntaxContext(context.0);
+            return Mark(context.2);
+        }
+
         HygieneData::with(|data| {
             let outer_mark = data.syntax_contexts[self.0 as usize].outer_mark;
             *self = data.syntax_contexts[self.0 as usize].prev_ctxt;
@@ -460,6 +530,13 @@ impl SyntaxContext {
         #[cfg(all(feature = "__plugin_mode", target_arch = "wasm32"))]
         return unsafe { Mark(__syntax_context_outer_proxy(self.0)) };
 
+        #[cfg(all(feature = "__plugin_mode", not(target_arch = "wasm32")))]
+        if let Some(mark) =
+            crate::plugin::native::call_host("__syntax_context_outer_proxy", &[self.0 as _], None)
+        {
+            return Mark(mark);
+        }
+
         #[cfg(not(all(feature = "__plugin_mode", target_arch = "wasm32")))]
         HygieneData::with(|data| data.syntax_contexts[self.0 as usize].outer_mark)
     }
@@ -13,5 +13,8 @@ pub extern crate swc_css_parser as parser;
 #[cfg(feature = "swc_css_prefixer")]
 #[cfg_attr(docsrs, doc(cfg(feature = "prefixer")))]
 pub extern crate swc_css_prefixer as prefixer;
+#[cfg(feature = "swc_css_preset_env")]
+#[cfg_attr(docsrs, doc(cfg(feature = "preset-env")))]
+pub extern crate swc_css_preset_env as preset_env;
 pub extern crate swc_css_utils as utils;
 pub extern crate swc_css_visit as visit;
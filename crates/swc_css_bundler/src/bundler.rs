@@ -0,0 +1,255 @@
+use anyhow::{Context as _, Error, anyhow, bail};
+use rustc_hash::FxHashMap;
+use swc_atoms::Atom;
+use swc_common::FileName;
+use swc_css_ast::{AtRulePrelude, ImportHref, ImportPrelude, Rule, Stylesheet, UrlValue};
+
+use crate::{
+    conditions::{has_conditions, wrap},
+    load::Load,
+    resolve::Resolve,
+};
+
+#[derive(Debug, Default)]
+pub struct Config {
+    /// List of stylesheets which should be preserved as `@import` rules.
+    ///
+    /// `@import` rules of absolute URLs, e.g. `https://example.com/a.css`, are
+    /// always preserved.
+    pub external_modules: Vec<Atom>,
+}
+
+#[derive(Debug)]
+pub struct Bundle {
+    pub stylesheet: Stylesheet,
+
+    /// The bundled files, in the order they are loaded.
+    pub files: Vec<FileName>,
+}
+
+/// Inlines the stylesheets imported by `@import` rules, in the order of the
+/// cascade.
+///
+/// The rules of a stylesheet imported with `layer()`, `supports()` or media
+/// queries are wrapped in the matching `@layer`, `@supports` and `@media`
+/// rules. Preserved `@import` rules are hoisted to the top of the bundle, so
+/// they must not be imported by a stylesheet imported with such conditions.
+///
+/// `@import` rules which are not at the top of a stylesheet are invalid, and
+/// are kept as is.
+pub struct Bundler<L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    config: Config,
+
+    loader: L,
+    resolver: R,
+}
+
+#[derive(Default)]
+struct Context {
+    stylesheets: FxHashMap<FileName, Stylesheet>,
+    files: Vec<FileName>,
+    /// The files being inlined, used to detect cycles.
+    stack: Vec<FileName>,
+    /// `@charset` and `@layer` statements at the top of the entry.
+    head: Vec<Rule>,
+    /// Preserved `@import` rules.
+    imports: Vec<Rule>,
+}
+
+impl<L, R> Bundler<L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    pub fn new(loader: L, resolver: R, config: Config) -> Self {
+        Bundler {
+            config,
+            loader,
+            resolver,
+        }
+    }
+
+    pub fn bundle(&self, entry: &FileName) -> Result<Bundle, Error> {
+        let mut ctx = Context::default();
+
+        let stylesheet = self.load(&mut ctx, entry)?;
+        let span = stylesheet.span;
+        let rules = self.inline(&mut ctx, entry, stylesheet, false)?;
+
+        let mut stylesheet = Stylesheet {
+            span,
+            rules: ctx.head,
+        };
+
+        stylesheet.rules.extend(ctx.imports);
+        stylesheet.rules.extend(rules);
+
+        Ok(Bundle {
+            stylesheet,
+            files: ctx.files,
+        })
+    }
+
+    fn load(&self, ctx: &mut Context, file: &FileName) -> Result<Stylesheet, Error> {
+        if let Some(stylesheet) = ctx.stylesheets.get(file) {
+            return Ok(stylesheet.clone());
+        }
+
+        let stylesheet = self
+            .loader
+            .load(file)
+            .with_context(|| format!("failed to load `{file}`"))?;
+
+        ctx.stylesheets.insert(file.clone(), stylesheet.clone());
+        ctx.files.push(file.clone());
+
+        Ok(stylesheet)
+    }
+
+    /// Returns the rules of `stylesheet` with its `@import` rules inlined.
+    ///
+    /// `conditional` is true if `stylesheet` is imported with conditions.
+    fn inline(
+        &self,
+        ctx: &mut Context,
+        file: &FileName,
+        stylesheet: Stylesheet,
+        conditional: bool,
+    ) -> Result<Vec<Rule>, Error> {
+        let is_entry = ctx.stack.is_empty();
+
+        ctx.stack.push(file.clone());
+
+        let mut rules = Vec::with_capacity(stylesheet.rules.len());
+        // `@import` rules must precede all other rules, except `@charset` and
+        // `@layer` statements
+        let mut in_imports = true;
+        let mut in_head = is_entry;
+
+        for rule in stylesheet.rules {
+            let at_rule = match rule {
+                Rule::AtRule(at_rule) if in_imports => at_rule,
+                _ => {
+                    in_imports = false;
+                    rules.push(rule);
+
+                    continue;
+                }
+            };
+
+            if at_rule.name == *"charset" {
+                // `@charset` is only valid at the start of the bundle
+                if in_head {
+                    ctx.head.push(Rule::AtRule(at_rule));
+                }
+
+                continue;
+            }
+
+            if at_rule.name == *"layer" && at_rule.block.is_none() {
+                if in_head {
+                    ctx.head.push(Rule::AtRule(at_rule));
+                } else {
+                    rules.push(Rule::AtRule(at_rule));
+                }
+
+                continue;
+            }
+
+            in_head = false;
+
+            let Some(AtRulePrelude::ImportPrelude(import)) = at_rule.prelude.as_deref() else {
+                in_imports = false;
+                rules.push(Rule::AtRule(at_rule));
+
+                continue;
+            };
+
+            let href = href(import)?;
+
+            if self.is_external(&href) {
+                if conditional {
+                    bail!(
+                        "`@import` of `{href}` in `{file}` can't be hoisted out of the conditions \
+                         of the stylesheets importing `{file}`"
+                    );
+                }
+
+                ctx.imports.push(Rule::AtRule(at_rule));
+
+                continue;
+            }
+
+            let resolved = self
+                .resolver
+                .resolve(file, &href)
+                .with_context(|| format!("failed to resolve `{href}` from `{file}`"))?
+                .filename;
+
+            if let Some(index) = ctx.stack.iter().position(|f| *f == resolved) {
+                let cycle = ctx.stack[index..]
+                    .iter()
+                    .chain([&resolved])
+                    .map(|f| format!("`{f}`"))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+
+                bail!("circular `@import`: {cycle}");
+            }
+
+            let imported = self.load(ctx, &resolved)?;
+            let imported = self.inline(
+                ctx,
+                &resolved,
+                imported,
+                conditional || has_conditions(import),
+            )?;
+
+            rules.extend(
+                wrap(imported, import, at_rule.span)
+                    .with_context(|| format!("failed to inline `{href}` in `{file}`"))?,
+            );
+        }
+
+        ctx.stack.pop();
+
+        Ok(rules)
+    }
+
+    fn is_external(&self, href: &str) -> bool {
+        self.config.external_modules.iter().any(|v| v == href) || is_absolute_url(href)
+    }
+}
+
+fn href(import: &ImportPrelude) -> Result<Atom, Error> {
+    match &*import.href {
+        ImportHref::Str(s) => Ok(s.value.clone()),
+        ImportHref::Url(url) => match url.value.as_deref() {
+            Some(UrlValue::Str(s)) => Ok(s.value.clone()),
+            Some(UrlValue::Raw(raw)) => Ok(raw.value.clone()),
+            None => Err(anyhow!("empty url in `@import`")),
+        },
+    }
+}
+
+/// Returns true for URLs with a scheme, e.g. `https:` or `data:`, and
+/// protocol-relative URLs.
+fn is_absolute_url(href: &str) -> bool {
+    if href.starts_with("//") {
+        return true;
+    }
+
+    match href.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::{Error, bail};
+use swc_atoms::{Atom, atom};
+use swc_common::Span;
+use swc_css_ast::{
+    AtRule, AtRuleName, AtRulePrelude, ComponentValue, Ident, ImportLayerName, ImportPrelude,
+    LayerPrelude, Rule, SimpleBlock, SupportsCondition, SupportsConditionType, SupportsFeature,
+    SupportsInParens, Token, TokenAndSpan,
+};
+
+/// Returns whether the imported stylesheet only applies under some conditions
+/// or in a cascade layer.
+pub(crate) fn has_conditions(import: &ImportPrelude) -> bool {
+    import.layer_name.is_some()
+        || import.import_conditions.as_ref().is_some_and(|conditions| {
+            conditions.supports.is_some()
+                || conditions
+                    .media
+                    .as_ref()
+                    .is_some_and(|media| !media.queries.is_empty())
+        })
+}
+
+/// Wraps the rules of an imported stylesheet in the at-rules matching the
+/// conditions of the `@import` rule, e.g. the rules of
+/// `@import "a.css" layer(base) supports(display: grid) screen` are wrapped in
+/// `@media screen { @supports (display: grid) { @layer base { ... } } }`.
+pub(crate) fn wrap(
+    mut rules: Vec<Rule>,
+    import: &ImportPrelude,
+    span: Span,
+) -> Result<Vec<Rule>, Error> {
+    if let Some(layer_name) = &import.layer_name {
+        let prelude = match &**layer_name {
+            // Anonymous layer
+            ImportLayerName::Ident(..) => None,
+            ImportLayerName::Function(function) => match &*function.value {
+                [ComponentValue::LayerName(name)] => Some(AtRulePrelude::LayerPrelude(
+                    LayerPrelude::Name(*name.clone()),
+                )),
+                _ => bail!("invalid `layer()` in `@import`"),
+            },
+        };
+
+        rules = vec![at_rule(atom!("layer"), prelude, rules, span)];
+    }
+
+    let Some(conditions) = &import.import_conditions else {
+        return Ok(rules);
+    };
+
+    if let Some(supports) = &conditions.supports {
+        let condition = match &*supports.value {
+            [ComponentValue::SupportsCondition(condition)] => *condition.clone(),
+            [ComponentValue::Declaration(declaration)] => SupportsCondition {
+                span: declaration.span,
+                conditions: vec![SupportsConditionType::SupportsInParens(
+                    SupportsInParens::Feature(SupportsFeature::Declaration(declaration.clone())),
+                )],
+            },
+            _ => bail!("invalid `supports()` in `@import`"),
+        };
+
+        rules = vec![at_rule(
+            atom!("supports"),
+            Some(AtRulePrelude::SupportsPrelude(condition)),
+            rules,
+            span,
+        )];
+    }
+
+    if let Some(media) = &conditions.media {
+        if !media.queries.is_empty() {
+            rules = vec![at_rule(
+                atom!("media"),
+                Some(AtRulePrelude::MediaPrelude(*media.clone())),
+                rules,
+                span,
+            )];
+        }
+    }
+
+    Ok(rules)
+}
+
+fn at_rule(name: Atom, prelude: Option<AtRulePrelude>, rules: Vec<Rule>, span: Span) -> Rule {
+    Rule::AtRule(Box::new(AtRule {
+        span,
+        name: AtRuleName::Ident(Ident {
+            span,
+            value: name,
+            raw: None,
+        }),
+        prelude: prelude.map(Box::new),
+        block: Some(SimpleBlock {
+            span,
+            name: TokenAndSpan {
+                span,
+                token: Token::LBrace,
+            },
+            value: rules.into_iter().map(From::from).collect(),
+        }),
+    }))
+}
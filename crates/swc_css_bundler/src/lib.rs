@@ -0,0 +1,12 @@
+#![deny(clippy::all)]
+
+pub use self::{
+    bundler::{Bundle, Bundler, Config},
+    load::Load,
+    resolve::Resolve,
+};
+
+mod bundler;
+mod conditions;
+mod load;
+mod resolve;
@@ -0,0 +1,25 @@
+use anyhow::Error;
+use swc_common::FileName;
+use swc_css_ast::Stylesheet;
+
+/// Responsible for providing stylesheets to the bundler.
+///
+/// Note: Stylesheets should be parsed with a single
+/// [SourceMap](swc_common::SourceMap), so the spans of the bundled stylesheet
+/// keep referring to the files they come from and the source map of the
+/// bundle maps each rule to its original file.
+pub trait Load: swc_common::sync::Send + swc_common::sync::Sync {
+    fn load(&self, file: &FileName) -> Result<Stylesheet, Error>;
+}
+
+impl<T: ?Sized + Load> Load for Box<T> {
+    fn load(&self, file: &FileName) -> Result<Stylesheet, Error> {
+        (**self).load(file)
+    }
+}
+
+impl<T: ?Sized + Load> Load for &T {
+    fn load(&self, file: &FileName) -> Result<Stylesheet, Error> {
+        (**self).load(file)
+    }
+}
@@ -0,0 +1 @@
+pub use swc_ecma_loader::resolve::Resolve;
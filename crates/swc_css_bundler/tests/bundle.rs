@@ -0,0 +1,180 @@
+#![deny(warnings)]
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Error, anyhow, bail};
+use swc_common::{
+    BytePos, FileName, LineCol, SourceMap, source_map::DefaultSourceMapGenConfig, sync::Lrc,
+};
+use swc_css_ast::Stylesheet;
+use swc_css_bundler::{Bundle, Bundler, Config, Load, Resolve};
+use swc_css_codegen::{
+    CodegenConfig, Emit,
+    writer::basic::{BasicCssWriter, BasicCssWriterConfig},
+};
+use swc_css_parser::parse_file;
+use swc_ecma_loader::resolve::Resolution;
+use testing::NormalizedOutput;
+
+struct Loader {
+    cm: Lrc<SourceMap>,
+}
+
+impl Load for Loader {
+    fn load(&self, file: &FileName) -> Result<Stylesheet, Error> {
+        let FileName::Real(path) = file else {
+            bail!("unexpected file name: {file}");
+        };
+
+        let fm = self.cm.load_file(path)?;
+        let mut errors = Vec::new();
+
+        parse_file(&fm, None, Default::default(), &mut errors)
+            .map_err(|err| anyhow!("failed to parse `{file}`: {err:?}"))
+    }
+}
+
+struct Resolver;
+
+impl Resolve for Resolver {
+    fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<Resolution, Error> {
+        let FileName::Real(base) = base else {
+            bail!("unexpected file name: {base}");
+        };
+
+        let path = base.parent().unwrap().join(module_specifier);
+
+        if !path.is_file() {
+            bail!("`{}` does not exist", path.display());
+        }
+
+        Ok(Resolution {
+            filename: FileName::Real(path.canonicalize()?),
+            slug: None,
+        })
+    }
+}
+
+fn bundle(cm: Lrc<SourceMap>, entry: &Path) -> Result<Bundle, Error> {
+    let bundler = Bundler::new(Loader { cm }, Resolver, Config::default());
+
+    bundler.bundle(&FileName::Real(entry.canonicalize().unwrap()))
+}
+
+fn print(stylesheet: &Stylesheet, src_map_buf: Option<&mut Vec<(BytePos, LineCol)>>) -> String {
+    let mut s = String::new();
+    {
+        let mut wr = BasicCssWriter::new(&mut s, src_map_buf, BasicCssWriterConfig::default());
+        let mut r#gen =
+            swc_css_codegen::CodeGenerator::new(&mut wr, CodegenConfig { minify: false });
+
+        r#gen.emit(stylesheet).unwrap();
+    }
+
+    s
+}
+
+#[testing::fixture("tests/fixture/**/input/index.css")]
+fn fixture(input: PathBuf) {
+    let output = input.parent().unwrap().parent().unwrap().join("output.css");
+
+    testing::run_test2(false, |cm, _| {
+        let bundle = bundle(cm, &input).unwrap();
+
+        NormalizedOutput::from(print(&bundle.stylesheet, None))
+            .compare_to_file(&output)
+            .unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn files_are_loaded_once() {
+    testing::run_test2(false, |cm, _| {
+        let bundle = bundle(cm, Path::new("tests/fixture/order/input/index.css")).unwrap();
+        let files = bundle
+            .files
+            .iter()
+            .map(|file| {
+                let FileName::Real(path) = file else {
+                    unreachable!()
+                };
+
+                path.file_name().unwrap().to_string_lossy().into_owned()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(files, vec!["index.css", "a.css", "b.css", "c.css"]);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn source_map_refers_to_imported_files() {
+    testing::run_test2(false, |cm, _| {
+        let bundle = bundle(cm.clone(), Path::new("tests/fixture/order/input/index.css")).unwrap();
+
+        let mut src_map_buf = Vec::new();
+        let code = print(&bundle.stylesheet, Some(&mut src_map_buf));
+        let source_map = cm.build_source_map(&src_map_buf, None, DefaultSourceMapGenConfig);
+
+        for (selector, file, src_line) in [
+            (".a", "a.css", 0),
+            (".c", "c.css", 2),
+            (".index", "index.css", 3),
+        ] {
+            let line = code
+                .lines()
+                .position(|line| line.starts_with(selector))
+                .unwrap();
+            let token = source_map
+                .lookup_token(line as u32, 0)
+                .expect("failed to find token");
+
+            assert!(
+                token.get_source().unwrap().ends_with(file),
+                "`{selector}` should be mapped to `{file}`, got {:?}",
+                token.get_source()
+            );
+            assert_eq!(token.get_src_line(), src_line);
+        }
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn circular_import() {
+    testing::run_test2(false, |cm, _| {
+        let err = bundle(cm, Path::new("tests/error/cycle/index.css")).unwrap_err();
+        let message = err.to_string();
+
+        assert!(message.starts_with("circular `@import`: "), "{message}");
+        assert!(message.contains("a.css` -> `"), "{message}");
+        assert!(message.contains("b.css` -> `"), "{message}");
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn external_import_in_condition() {
+    testing::run_test2(false, |cm, _| {
+        let err = bundle(cm, Path::new("tests/error/external-in-condition/index.css")).unwrap_err();
+
+        assert!(
+            err.to_string()
+                .starts_with("`@import` of `https://example.com/a.css` in "),
+            "{err}"
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
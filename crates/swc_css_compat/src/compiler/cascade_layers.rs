@@ -0,0 +1,338 @@
+use rustc_hash::FxHashMap;
+use swc_atoms::{Atom, atom};
+use swc_common::{DUMMY_SP, util::take::Take};
+use swc_css_ast::{
+    AtRule, AtRulePrelude, ComplexSelector, ComplexSelectorChildren, ComponentValue,
+    CompoundSelector, IdSelector, Ident, LayerName, LayerPrelude, ListOfComponentValues,
+    PseudoClassSelector, PseudoClassSelectorChildren, QualifiedRulePrelude, Rule, SelectorList,
+    Stylesheet, SubclassSelector,
+};
+use swc_css_visit::{Visit, VisitWith};
+
+use crate::{compiler::Compiler, feature::Features};
+
+/// The layers declared in a stylesheet, in the order of their first
+/// declaration.
+#[derive(Debug, Default)]
+struct Layer {
+    sublayers: Vec<(Atom, Layer)>,
+}
+
+impl Layer {
+    fn declare(&mut self, path: &[Atom]) {
+        let Some((name, rest)) = path.split_first() else {
+            return;
+        };
+
+        let index = match self.sublayers.iter().position(|(n, _)| n == name) {
+            Some(index) => index,
+            None => {
+                self.sublayers.push((name.clone(), Default::default()));
+
+                self.sublayers.len() - 1
+            }
+        };
+
+        self.sublayers[index].1.declare(rest);
+    }
+
+    /// Assigns the ranks of the layers in the cascade, from the lowest
+    /// priority to the highest: sublayers have a lower priority than the
+    /// styles of their parent layer, and unlayered styles (i.e. the root) have
+    /// the highest priority.
+    fn rank(&self, path: &mut Vec<Atom>, ranks: &mut FxHashMap<Vec<Atom>, usize>) {
+        for (name, sublayer) in &self.sublayers {
+            path.push(name.clone());
+            sublayer.rank(path, ranks);
+            path.pop();
+        }
+
+        let rank = ranks.len();
+
+        ranks.insert(path.clone(), rank);
+    }
+}
+
+/// Walks the rules of a stylesheet with the path of the current layer, giving
+/// the same names to anonymous layers on each walk.
+#[derive(Debug, Default)]
+struct LayerPath {
+    path: Vec<Atom>,
+    anonymous_layers: usize,
+}
+
+impl LayerPath {
+    /// Returns the path of the layer declared by a `@layer` rule with a block.
+    fn block_layer(&mut self, at_rule: &AtRule) -> Vec<Atom> {
+        let mut path = self.path.clone();
+
+        match at_rule.prelude.as_deref() {
+            Some(AtRulePrelude::LayerPrelude(LayerPrelude::Name(name))) => {
+                path.extend(name.name.iter().map(|ident| ident.value.clone()));
+            }
+            _ => {
+                // Anonymous layers can't be referenced, so their name must not be a
+                // valid identifier
+                path.push(format!("#{}", self.anonymous_layers).into());
+                self.anonymous_layers += 1;
+            }
+        }
+
+        path
+    }
+
+    /// Returns the paths of the layers declared by a `@layer` statement.
+    fn statement_layers<'a>(&'a self, at_rule: &'a AtRule) -> impl Iterator<Item = Vec<Atom>> + 'a {
+        let names: &[LayerName] = match at_rule.prelude.as_deref() {
+            Some(AtRulePrelude::LayerPrelude(LayerPrelude::Name(name))) => {
+                std::slice::from_ref(name)
+            }
+            Some(AtRulePrelude::LayerPrelude(LayerPrelude::NameList(list))) => &list.name_list,
+            _ => &[],
+        };
+
+        names.iter().map(|name| {
+            let mut path = self.path.clone();
+
+            path.extend(name.name.iter().map(|ident| ident.value.clone()));
+
+            path
+        })
+    }
+}
+
+fn is_layer(at_rule: &AtRule) -> bool {
+    at_rule.name == *"layer"
+}
+
+fn declare_layers(values: &[ComponentValue], layers: &mut Layer, walker: &mut LayerPath) {
+    for value in values {
+        let ComponentValue::AtRule(at_rule) = value else {
+            continue;
+        };
+
+        match &at_rule.block {
+            Some(block) if is_layer(at_rule) => {
+                let path = walker.block_layer(at_rule);
+
+                layers.declare(&path);
+
+                let parent = std::mem::replace(&mut walker.path, path);
+
+                declare_layers(&block.value, layers, walker);
+
+                walker.path = parent;
+            }
+            Some(block) => declare_layers(&block.value, layers, walker),
+            None if is_layer(at_rule) => {
+                for path in walker.statement_layers(at_rule) {
+                    layers.declare(&path);
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+#[derive(Default)]
+struct IdCounter {
+    count: usize,
+}
+
+impl Visit for IdCounter {
+    fn visit_id_selector(&mut self, _: &IdSelector) {
+        self.count += 1;
+    }
+}
+
+/// Returns the maximum number of ID selectors in the selectors of style rules.
+fn max_ids(values: &[ComponentValue]) -> usize {
+    values
+        .iter()
+        .map(|value| match value {
+            ComponentValue::QualifiedRule(rule) => match &rule.prelude {
+                QualifiedRulePrelude::SelectorList(selector_list) => selector_list
+                    .children
+                    .iter()
+                    .map(|selector| {
+                        let mut counter = IdCounter::default();
+
+                        selector.visit_with(&mut counter);
+
+                        counter.count
+                    })
+                    .max()
+                    .unwrap_or_default(),
+                _ => 0,
+            },
+            ComponentValue::AtRule(at_rule) => at_rule
+                .block
+                .as_ref()
+                .map_or(0, |block| max_ids(&block.value)),
+            _ => 0,
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+struct Flattener {
+    ranks: FxHashMap<Vec<Atom>, usize>,
+    /// The number of `:not(#\#)` added to the selectors per rank
+    ids_per_rank: usize,
+    walker: LayerPath,
+}
+
+impl Flattener {
+    fn flatten(&mut self, values: Vec<ComponentValue>) -> Vec<ComponentValue> {
+        let mut new = Vec::with_capacity(values.len());
+
+        for value in values {
+            match value {
+                ComponentValue::QualifiedRule(mut rule) => {
+                    if let QualifiedRulePrelude::SelectorList(selector_list) = &mut rule.prelude {
+                        let ids = self.ranks[&self.walker.path] * self.ids_per_rank;
+
+                        add_specificity(selector_list, ids);
+                    }
+
+                    new.push(ComponentValue::QualifiedRule(rule));
+                }
+                ComponentValue::AtRule(mut at_rule) if is_layer(&at_rule) => {
+                    let Some(block) = at_rule.block.take() else {
+                        continue;
+                    };
+
+                    let path = self.walker.block_layer(&at_rule);
+                    let parent = std::mem::replace(&mut self.walker.path, path);
+
+                    new.extend(self.flatten(block.value));
+
+                    self.walker.path = parent;
+                }
+                ComponentValue::AtRule(mut at_rule) => {
+                    if let Some(block) = &mut at_rule.block {
+                        block.value = self.flatten(block.value.take());
+                    }
+
+                    new.push(ComponentValue::AtRule(at_rule));
+                }
+                _ => new.push(value),
+            }
+        }
+
+        new
+    }
+}
+
+/// Adds `ids` ID selectors to the specificity of each selector of
+/// `selector_list`, by appending `:not(#\#)` to its last compound selector.
+fn add_specificity(selector_list: &mut SelectorList, ids: usize) {
+    if ids == 0 {
+        return;
+    }
+
+    for selector in &mut selector_list.children {
+        let Some(ComplexSelectorChildren::CompoundSelector(compound)) =
+            selector.children.last_mut()
+        else {
+            continue;
+        };
+
+        // Pseudo-elements must be at the end of the compound selector
+        let index = compound
+            .subclass_selectors
+            .iter()
+            .position(|selector| matches!(selector, SubclassSelector::PseudoElement(..)))
+            .unwrap_or(compound.subclass_selectors.len());
+
+        compound.subclass_selectors.splice(
+            index..index,
+            std::iter::repeat_with(not_id_selector).take(ids),
+        );
+    }
+}
+
+/// Creates `:not(#\#)`, which never matches any element with the ID `#`.
+fn not_id_selector() -> SubclassSelector {
+    SubclassSelector::PseudoClass(PseudoClassSelector {
+        span: DUMMY_SP,
+        name: Ident {
+            span: DUMMY_SP,
+            value: atom!("not"),
+            raw: None,
+        },
+        children: Some(vec![PseudoClassSelectorChildren::SelectorList(
+            SelectorList {
+                span: DUMMY_SP,
+                children: vec![ComplexSelector {
+                    span: DUMMY_SP,
+                    children: vec![ComplexSelectorChildren::CompoundSelector(
+                        CompoundSelector {
+                            span: DUMMY_SP,
+                            nesting_selector: None,
+                            type_selector: None,
+                            subclass_selectors: vec![SubclassSelector::Id(IdSelector {
+                                span: DUMMY_SP,
+                                text: Ident {
+                                    span: DUMMY_SP,
+                                    value: atom!("#"),
+                                    raw: None,
+                                },
+                            })],
+                        },
+                    )],
+                }],
+            },
+        )]),
+    })
+}
+
+impl Compiler {
+    /// Removes `@layer` rules, keeping the order of the cascade by increasing
+    /// the specificity of the selectors of each layer.
+    ///
+    /// Each layer adds as many ID selectors as the maximum number of ID
+    /// selectors in the stylesheet, plus one, to the specificity of the
+    /// selectors of the layers it precedes. Layers imported with
+    /// `@import layer()` and the reversed order of `!important` declarations
+    /// are not supported.
+    pub(crate) fn process_cascade_layers(&mut self, n: &mut Stylesheet) {
+        let values: Vec<ComponentValue> = n.rules.take().into_iter().map(From::from).collect();
+
+        let mut layers = Layer::default();
+
+        declare_layers(&values, &mut layers, &mut Default::default());
+
+        if layers.sublayers.is_empty() {
+            n.rules = values.into_iter().map(to_rule).collect();
+
+            return;
+        }
+
+        self.applied |= Features::CASCADE_LAYERS;
+
+        let mut ranks = FxHashMap::default();
+
+        layers.rank(&mut Vec::new(), &mut ranks);
+
+        let mut flattener = Flattener {
+            ranks,
+            ids_per_rank: max_ids(&values) + 1,
+            walker: Default::default(),
+        };
+
+        n.rules = flattener.flatten(values).into_iter().map(to_rule).collect();
+    }
+}
+
+fn to_rule(value: ComponentValue) -> Rule {
+    match value {
+        ComponentValue::QualifiedRule(rule) => Rule::QualifiedRule(rule),
+        ComponentValue::AtRule(at_rule) => Rule::AtRule(at_rule),
+        ComponentValue::ListOfComponentValues(list) => Rule::ListOfComponentValues(list),
+        value => Rule::ListOfComponentValues(Box::new(ListOfComponentValues {
+            span: DUMMY_SP,
+            children: vec![value],
+        })),
+    }
+}
@@ -0,0 +1,706 @@
+//! Conversions between the color spaces of
+//! [CSS Color 4](https://drafts.csswg.org/css-color-4/), used to lower colors
+//! which are not supported by old browsers to `rgb()`.
+
+use swc_atoms::{Atom, atom};
+use swc_css_ast::{
+    AbsoluteColorBase, AlphaValue, Angle, Color as ColorValue, ComponentValue, Delimiter,
+    DelimiterValue, Function, FunctionName, Hue, Ident, Number, Percentage,
+};
+use swc_css_utils::{NAMED_COLORS, angle_to_deg, hsl_to_rgb, hwb_to_rgb, round_alpha};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColorSpace {
+    Srgb,
+    SrgbLinear,
+    Hsl,
+    Hwb,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    XyzD50,
+    XyzD65,
+}
+
+impl ColorSpace {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Some(match &*name.to_ascii_lowercase() {
+            "srgb" => ColorSpace::Srgb,
+            "srgb-linear" => ColorSpace::SrgbLinear,
+            "hsl" => ColorSpace::Hsl,
+            "hwb" => ColorSpace::Hwb,
+            "lab" => ColorSpace::Lab,
+            "lch" => ColorSpace::Lch,
+            "oklab" => ColorSpace::Oklab,
+            "oklch" => ColorSpace::Oklch,
+            "xyz" | "xyz-d65" => ColorSpace::XyzD65,
+            "xyz-d50" => ColorSpace::XyzD50,
+            _ => return None,
+        })
+    }
+
+    /// The index of the hue in the coordinates of polar color spaces.
+    pub(crate) fn hue_index(self) -> Option<usize> {
+        match self {
+            ColorSpace::Hsl | ColorSpace::Hwb => Some(0),
+            ColorSpace::Lch | ColorSpace::Oklch => Some(2),
+            _ => None,
+        }
+    }
+}
+
+/// A color in a color space, missing components (i.e. `none` or powerless
+/// hues) are `NaN`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Color {
+    pub space: ColorSpace,
+    pub coords: [f64; 3],
+    pub alpha: f64,
+}
+
+type Matrix = [[f64; 3]; 3];
+
+const LIN_SRGB_TO_XYZ_D65: Matrix = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+
+const XYZ_D65_TO_LIN_SRGB: Matrix = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [
+        0.05563007969699366,
+        -0.20397695888897652,
+        1.0569715142428786,
+    ],
+];
+
+const XYZ_D50_TO_XYZ_D65: Matrix = [
+    [0.955473421488075, -0.02309845494876471, 0.06325924320057072],
+    [
+        -0.0283697093338637,
+        1.0099953980813041,
+        0.021041441191917323,
+    ],
+    [
+        0.012314014864481998,
+        -0.020507649298898964,
+        1.330365926242124,
+    ],
+];
+
+const XYZ_D65_TO_XYZ_D50: Matrix = [
+    [
+        1.0479297925449969,
+        0.022946870601609652,
+        -0.05019226628920524,
+    ],
+    [
+        0.02962780877005599,
+        0.9904344267538799,
+        -0.017073799063418826,
+    ],
+    [
+        -0.009243040646204504,
+        0.015055191490298152,
+        0.7518742814281371,
+    ],
+];
+
+const XYZ_D65_TO_LMS: Matrix = [
+    [0.819022437996703, 0.3619062600528904, -0.1288737815209879],
+    [0.0329836539323885, 0.9292868615863434, 0.0361446663506424],
+    [0.0481771893596242, 0.2642395317527308, 0.6335478284694309],
+];
+
+const LMS_TO_XYZ_D65: Matrix = [
+    [1.2268798758459243, -0.5578149944602171, 0.2812560490664678],
+    [-0.0405757452148008, 1.112286803280317, -0.071670565517509],
+    [-0.0763729366746601, -0.4214933324022432, 1.5869240198367816],
+];
+
+const LMS_TO_OKLAB: Matrix = [
+    [0.210454268309314, 0.7936177747023054, -0.0040720430116193],
+    [1.9779985324311684, -2.42859224204858, 0.450593709617411],
+    [0.0259040424655478, 0.7827717124575296, -0.8086757549230774],
+];
+
+const OKLAB_TO_LMS: Matrix = [
+    [1.0, 0.3963377773761749, 0.2158037573099136],
+    [1.0, -0.1055613458156586, -0.0638541728258133],
+    [1.0, -0.0894841775298119, -1.2914855480194092],
+];
+
+const D50_WHITE: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+
+fn multiply(m: &Matrix, v: [f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+fn srgb_to_linear(c: f64) -> f64 {
+    let abs = c.abs();
+
+    if abs <= 0.04045 {
+        c / 12.92
+    } else {
+        c.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f64) -> f64 {
+    let abs = c.abs();
+
+    if abs > 0.0031308 {
+        c.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    } else {
+        12.92 * c
+    }
+}
+
+fn lab_to_xyz_d50([l, a, b]: [f64; 3]) -> [f64; 3] {
+    let f1 = (l + 16.0) / 116.0;
+    let f0 = a / 500.0 + f1;
+    let f2 = f1 - b / 200.0;
+
+    let x = if f0.powi(3) > LAB_EPSILON {
+        f0.powi(3)
+    } else {
+        (116.0 * f0 - 16.0) / LAB_KAPPA
+    };
+    let y = if l > LAB_KAPPA * LAB_EPSILON {
+        f1.powi(3)
+    } else {
+        l / LAB_KAPPA
+    };
+    let z = if f2.powi(3) > LAB_EPSILON {
+        f2.powi(3)
+    } else {
+        (116.0 * f2 - 16.0) / LAB_KAPPA
+    };
+
+    [x * D50_WHITE[0], y * D50_WHITE[1], z * D50_WHITE[2]]
+}
+
+fn xyz_d50_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    let f = |i: usize| {
+        let v = xyz[i] / D50_WHITE[i];
+
+        if v > LAB_EPSILON {
+            v.cbrt()
+        } else {
+            (LAB_KAPPA * v + 16.0) / 116.0
+        }
+    };
+    let [f0, f1, f2] = [f(0), f(1), f(2)];
+
+    [116.0 * f1 - 16.0, 500.0 * (f0 - f1), 200.0 * (f1 - f2)]
+}
+
+fn oklab_to_xyz_d65(oklab: [f64; 3]) -> [f64; 3] {
+    let lms = multiply(&OKLAB_TO_LMS, oklab).map(|c| c.powi(3));
+
+    multiply(&LMS_TO_XYZ_D65, lms)
+}
+
+fn xyz_d65_to_oklab(xyz: [f64; 3]) -> [f64; 3] {
+    let lms = multiply(&XYZ_D65_TO_LMS, xyz).map(f64::cbrt);
+
+    multiply(&LMS_TO_OKLAB, lms)
+}
+
+fn polar_to_rectangular([l, c, h]: [f64; 3]) -> [f64; 3] {
+    let h = h.to_radians();
+
+    [l, c * h.cos(), c * h.sin()]
+}
+
+/// The hue is missing if the color is achromatic, i.e. if the chroma is lower
+/// than `epsilon`.
+fn rectangular_to_polar([l, a, b]: [f64; 3], epsilon: f64) -> [f64; 3] {
+    let c = (a * a + b * b).sqrt();
+    let h = if c < epsilon {
+        f64::NAN
+    } else {
+        normalize_hue(b.atan2(a).to_degrees())
+    };
+
+    [l, c, h]
+}
+
+fn srgb_to_hsl([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+
+    if d == 0.0 {
+        return [f64::NAN, 0.0, l];
+    }
+
+    let s = if l == 0.0 || l == 1.0 {
+        0.0
+    } else {
+        (max - l) / l.min(1.0 - l)
+    };
+    let h = if max == r {
+        (g - b) / d + if g < b { 6.0 } else { 0.0 }
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+
+    [h * 60.0, s, l]
+}
+
+fn srgb_to_hwb(rgb: [f64; 3]) -> [f64; 3] {
+    let [h, ..] = srgb_to_hsl(rgb);
+    let white = rgb[0].min(rgb[1]).min(rgb[2]);
+    let black = 1.0 - rgb[0].max(rgb[1]).max(rgb[2]);
+
+    [h, white, black]
+}
+
+pub(crate) fn normalize_hue(hue: f64) -> f64 {
+    let hue = hue % 360.0;
+
+    if hue < 0.0 { hue + 360.0 } else { hue }
+}
+
+impl Color {
+    fn to_xyz_d65(self) -> [f64; 3] {
+        let coords = self.coords.map(|c| if c.is_nan() { 0.0 } else { c });
+
+        match self.space {
+            ColorSpace::Srgb => multiply(&LIN_SRGB_TO_XYZ_D65, coords.map(srgb_to_linear)),
+            ColorSpace::SrgbLinear => multiply(&LIN_SRGB_TO_XYZ_D65, coords),
+            ColorSpace::Hsl => {
+                multiply(&LIN_SRGB_TO_XYZ_D65, hsl_to_rgb(coords).map(srgb_to_linear))
+            }
+            ColorSpace::Hwb => {
+                multiply(&LIN_SRGB_TO_XYZ_D65, hwb_to_rgb(coords).map(srgb_to_linear))
+            }
+            ColorSpace::Lab => multiply(&XYZ_D50_TO_XYZ_D65, lab_to_xyz_d50(coords)),
+            ColorSpace::Lch => multiply(
+                &XYZ_D50_TO_XYZ_D65,
+                lab_to_xyz_d50(polar_to_rectangular(coords)),
+            ),
+            ColorSpace::Oklab => oklab_to_xyz_d65(coords),
+            ColorSpace::Oklch => oklab_to_xyz_d65(polar_to_rectangular(coords)),
+            ColorSpace::XyzD50 => multiply(&XYZ_D50_TO_XYZ_D65, coords),
+            ColorSpace::XyzD65 => coords,
+        }
+    }
+
+    /// Converts the color to `space`, missing components are kept if the
+    /// color is already in `space`.
+    pub(crate) fn to(self, space: ColorSpace) -> Color {
+        if self.space == space {
+            return self;
+        }
+
+        let xyz = self.to_xyz_d65();
+        let srgb = || multiply(&XYZ_D65_TO_LIN_SRGB, xyz).map(linear_to_srgb);
+        let coords = match space {
+            ColorSpace::Srgb => srgb(),
+            ColorSpace::SrgbLinear => multiply(&XYZ_D65_TO_LIN_SRGB, xyz),
+            ColorSpace::Hsl => srgb_to_hsl(srgb()),
+            ColorSpace::Hwb => srgb_to_hwb(srgb()),
+            ColorSpace::Lab => xyz_d50_to_lab(multiply(&XYZ_D65_TO_XYZ_D50, xyz)),
+            ColorSpace::Lch => {
+                rectangular_to_polar(xyz_d50_to_lab(multiply(&XYZ_D65_TO_XYZ_D50, xyz)), 0.0015)
+            }
+            ColorSpace::Oklab => xyz_d65_to_oklab(xyz),
+            ColorSpace::Oklch => rectangular_to_polar(xyz_d65_to_oklab(xyz), 0.000004),
+            ColorSpace::XyzD50 => multiply(&XYZ_D65_TO_XYZ_D50, xyz),
+            ColorSpace::XyzD65 => xyz,
+        };
+
+        Color {
+            space,
+            coords,
+            alpha: self.alpha,
+        }
+    }
+
+    /// Converts the color to sRGB, mapping colors outside of the sRGB gamut
+    /// using the
+    /// [gamut mapping algorithm](https://drafts.csswg.org/css-color-4/#binsearch)
+    /// of CSS Color 4, which reduces the chroma in OKLCh until the color is
+    /// close enough to the sRGB gamut.
+    pub(crate) fn to_srgb_in_gamut(self) -> [f64; 3] {
+        const JND: f64 = 0.02;
+        const EPSILON: f64 = 0.0001;
+
+        let srgb = self.to(ColorSpace::Srgb);
+
+        if is_in_srgb_gamut(srgb.coords) {
+            return srgb.coords.map(|c| c.clamp(0.0, 1.0));
+        }
+
+        let mut current = self.to(ColorSpace::Oklch);
+        let [l, c, _] = current.coords;
+
+        if l >= 1.0 {
+            return [1.0; 3];
+        }
+
+        if l <= 0.0 {
+            return [0.0; 3];
+        }
+
+        let clip = |color: Color| color.to(ColorSpace::Srgb).coords.map(|c| c.clamp(0.0, 1.0));
+        let delta_eok = |a: [f64; 3], b: Color| {
+            let a = Color {
+                space: ColorSpace::Srgb,
+                coords: a,
+                alpha: 1.0,
+            }
+            .to(ColorSpace::Oklab)
+            .coords;
+            let b = b.to(ColorSpace::Oklab).coords;
+
+            ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+        };
+
+        let mut clipped = clip(current);
+
+        if delta_eok(clipped, current) < JND {
+            return clipped;
+        }
+
+        let mut min = 0.0;
+        let mut max = c;
+        let mut min_in_gamut = true;
+
+        while max - min > EPSILON {
+            let chroma = (min + max) / 2.0;
+
+            current.coords[1] = chroma;
+
+            if min_in_gamut && is_in_srgb_gamut(current.to(ColorSpace::Srgb).coords) {
+                min = chroma;
+
+                continue;
+            }
+
+            clipped = clip(current);
+
+            let e = delta_eok(clipped, current);
+
+            if e < JND {
+                if JND - e < EPSILON {
+                    break;
+                }
+
+                min_in_gamut = false;
+                min = chroma;
+            } else {
+                max = chroma;
+            }
+        }
+
+        clipped
+    }
+}
+
+fn is_in_srgb_gamut(coords: [f64; 3]) -> bool {
+    const EPSILON: f64 = 0.000_01;
+
+    coords
+        .iter()
+        .all(|c| (-EPSILON..=1.0 + EPSILON).contains(c))
+}
+
+/// Returns the value of a number or a percentage, where `100%` is
+/// `percentage_reference`, or `NaN` for `none`.
+fn get_number(value: &ComponentValue, percentage_reference: f64) -> Option<f64> {
+    match value {
+        ComponentValue::Number(number) => Some(number.value),
+        ComponentValue::Integer(integer) => Some(integer.value as f64),
+        ComponentValue::Percentage(percentage) => {
+            Some(percentage.value.value / 100.0 * percentage_reference)
+        }
+        ComponentValue::Ident(ident) if ident.value.eq_ignore_ascii_case("none") => Some(f64::NAN),
+        _ => None,
+    }
+}
+
+fn get_hue(value: &ComponentValue) -> Option<f64> {
+    match value {
+        ComponentValue::Hue(hue) => Some(normalize_hue(match &**hue {
+            Hue::Number(Number { value, .. }) => *value,
+            Hue::Angle(Angle {
+                value: Number { value, .. },
+                unit: Ident { value: unit, .. },
+                ..
+            }) => angle_to_deg(*value, unit),
+        })),
+        ComponentValue::Number(number) => Some(normalize_hue(number.value)),
+        ComponentValue::Ident(ident) if ident.value.eq_ignore_ascii_case("none") => Some(f64::NAN),
+        _ => None,
+    }
+}
+
+fn get_alpha(value: Option<&ComponentValue>) -> Option<f64> {
+    let alpha = match value {
+        None => 1.0,
+        Some(ComponentValue::AlphaValue(alpha_value)) => match &**alpha_value {
+            AlphaValue::Number(Number { value, .. }) => *value,
+            AlphaValue::Percentage(Percentage {
+                value: Number { value, .. },
+                ..
+            }) => *value / 100.0,
+        },
+        Some(value) => get_number(value, 1.0)?,
+    };
+
+    Some(if alpha.is_nan() {
+        alpha
+    } else {
+        alpha.clamp(0.0, 1.0)
+    })
+}
+
+/// Parses a color which can be resolved at compile time, i.e. which doesn't
+/// depend on custom properties or `currentcolor`.
+pub(crate) fn parse_color(value: &ComponentValue) -> Option<Color> {
+    match value {
+        ComponentValue::Color(color) => match &**color {
+            ColorValue::AbsoluteColorBase(base) => parse_absolute_color_base(base),
+            _ => None,
+        },
+        ComponentValue::Ident(ident) => parse_named_color(&ident.value),
+        _ => None,
+    }
+}
+
+fn parse_named_color(name: &Atom) -> Option<Color> {
+    let name = name.to_ascii_lowercase();
+
+    if name == "transparent" {
+        return Some(Color {
+            space: ColorSpace::Srgb,
+            coords: [0.0; 3],
+            alpha: 0.0,
+        });
+    }
+
+    let named_color = NAMED_COLORS.get(&name)?;
+
+    Some(Color {
+        space: ColorSpace::Srgb,
+        coords: [
+            named_color.rgb[0] as f64 / 255.0,
+            named_color.rgb[1] as f64 / 255.0,
+            named_color.rgb[2] as f64 / 255.0,
+        ],
+        alpha: 1.0,
+    })
+}
+
+pub(crate) fn parse_absolute_color_base(n: &AbsoluteColorBase) -> Option<Color> {
+    match n {
+        AbsoluteColorBase::HexColor(hex) => {
+            let hex = hex.value.as_bytes();
+            let digit = |i: usize| (hex[i] as char).to_digit(16).map(|d| d as f64);
+            let (rgba, double) = match hex.len() {
+                3 | 4 => (hex.len(), false),
+                6 | 8 => (hex.len() / 2, true),
+                _ => return None,
+            };
+            let mut channels = [0.0, 0.0, 0.0, 1.0];
+
+            for (i, channel) in channels.iter_mut().enumerate().take(rgba) {
+                *channel = if double {
+                    (digit(i * 2)? * 16.0 + digit(i * 2 + 1)?) / 255.0
+                } else {
+                    digit(i)? * 17.0 / 255.0
+                };
+            }
+
+            Some(Color {
+                space: ColorSpace::Srgb,
+                coords: [channels[0], channels[1], channels[2]],
+                alpha: channels[3],
+            })
+        }
+        AbsoluteColorBase::NamedColorOrTransparent(ident) => parse_named_color(&ident.value),
+        AbsoluteColorBase::Function(function) => parse_color_function(function),
+    }
+}
+
+fn parse_color_function(function: &Function) -> Option<Color> {
+    let name = match &function.name {
+        FunctionName::Ident(name) => name.value.to_ascii_lowercase(),
+        _ => return None,
+    };
+    let mut channels = Vec::with_capacity(3);
+    let mut alpha = None;
+    let mut after_slash = false;
+
+    for value in &function.value {
+        match value {
+            ComponentValue::Delimiter(delimiter) => match delimiter.value {
+                DelimiterValue::Comma => {}
+                DelimiterValue::Solidus => after_slash = true,
+                _ => return None,
+            },
+            _ if after_slash || channels.len() == 3 => {
+                if alpha.is_some() {
+                    return None;
+                }
+
+                alpha = Some(value);
+            }
+            _ => channels.push(value),
+        }
+    }
+
+    if channels.len() != 3 {
+        return None;
+    }
+
+    let (space, coords) = match &*name {
+        "rgb" | "rgba" => (
+            ColorSpace::Srgb,
+            [
+                get_number(channels[0], 255.0)? / 255.0,
+                get_number(channels[1], 255.0)? / 255.0,
+                get_number(channels[2], 255.0)? / 255.0,
+            ],
+        ),
+        "hsl" | "hsla" => (
+            ColorSpace::Hsl,
+            [
+                get_hue(channels[0])?,
+                get_number(channels[1], 1.0)?,
+                get_number(channels[2], 1.0)?,
+            ],
+        ),
+        "hwb" => (
+            ColorSpace::Hwb,
+            [
+                get_hue(channels[0])?,
+                get_number(channels[1], 1.0)?,
+                get_number(channels[2], 1.0)?,
+            ],
+        ),
+        "lab" => (
+            ColorSpace::Lab,
+            [
+                get_number(channels[0], 100.0)?,
+                get_number(channels[1], 125.0)?,
+                get_number(channels[2], 125.0)?,
+            ],
+        ),
+        "lch" => (
+            ColorSpace::Lch,
+            [
+                get_number(channels[0], 100.0)?,
+                get_number(channels[1], 150.0)?,
+                get_hue(channels[2])?,
+            ],
+        ),
+        "oklab" => (
+            ColorSpace::Oklab,
+            [
+                get_number(channels[0], 1.0)?,
+                get_number(channels[1], 0.4)?,
+                get_number(channels[2], 0.4)?,
+            ],
+        ),
+        "oklch" => (
+            ColorSpace::Oklch,
+            [
+                get_number(channels[0], 1.0)?,
+                get_number(channels[1], 0.4)?,
+                get_hue(channels[2])?,
+            ],
+        ),
+        _ => return None,
+    };
+
+    let coords = match space {
+        ColorSpace::Hsl | ColorSpace::Hwb => [
+            coords[0],
+            coords[1].clamp(0.0, 1.0),
+            coords[2].clamp(0.0, 1.0),
+        ],
+        ColorSpace::Lab | ColorSpace::Oklab => [coords[0].max(0.0), coords[1], coords[2]],
+        ColorSpace::Lch | ColorSpace::Oklch => [coords[0].max(0.0), coords[1].max(0.0), coords[2]],
+        _ => coords,
+    };
+
+    Some(Color {
+        space,
+        coords,
+        alpha: get_alpha(alpha)?,
+    })
+}
+
+/// Creates `rgb(r, g, b)` or `rgba(r, g, b, a)`, which are supported by all
+/// browsers, for a color in sRGB.
+pub(crate) fn to_legacy_rgb(rgb: [f64; 3], alpha: f64) -> AbsoluteColorBase {
+    let alpha = if alpha.is_nan() {
+        0.0
+    } else {
+        round_alpha(alpha)
+    };
+    let number = |value: f64| {
+        ComponentValue::Number(Box::new(Number {
+            value: (value * 255.0).round(),
+            span: Default::default(),
+            raw: None,
+        }))
+    };
+    let comma = || {
+        ComponentValue::Delimiter(Box::new(Delimiter {
+            value: DelimiterValue::Comma,
+            span: Default::default(),
+        }))
+    };
+
+    let mut value = vec![
+        number(rgb[0]),
+        comma(),
+        number(rgb[1]),
+        comma(),
+        number(rgb[2]),
+    ];
+
+    let name: Atom = if alpha == 1.0 {
+        atom!("rgb")
+    } else {
+        value.push(comma());
+        value.push(ComponentValue::AlphaValue(Box::new(AlphaValue::Number(
+            Number {
+                value: alpha,
+                span: Default::default(),
+                raw: None,
+            },
+        ))));
+
+        atom!("rgba")
+    };
+
+    AbsoluteColorBase::Function(Function {
+        name: FunctionName::Ident(Ident {
+            value: name,
+            span: Default::default(),
+            raw: None,
+        }),
+        value,
+        span: Default::default(),
+    })
+}
@@ -1,7 +1,7 @@
 use swc_atoms::atom;
 use swc_css_ast::{AbsoluteColorBase, ComponentValue, FunctionName};
 
-use crate::compiler::Compiler;
+use crate::{compiler::Compiler, feature::Features};
 
 impl Compiler {
     pub(crate) fn process_color_alpha_parameter(&mut self, n: &mut AbsoluteColorBase) {
@@ -22,6 +22,8 @@ impl Compiler {
                 } else if name.value.eq_ignore_ascii_case("hsl") {
                     name.value = atom!("hsla");
                     name.raw = None;
+                } else {
+                    return;
                 }
             } else {
                 let name = match &mut function.name {
@@ -37,8 +39,12 @@ impl Compiler {
                 } else if name.value.eq_ignore_ascii_case("hsla") {
                     name.value = atom!("hsl");
                     name.raw = None;
+                } else {
+                    return;
                 }
             }
+
+            self.applied |= Features::COLOR_ALPHA_PARAMETER;
         }
     }
 }
@@ -6,7 +6,7 @@ use swc_css_ast::{
 };
 use swc_css_utils::{hex_to_rgba, round_alpha};
 
-use crate::compiler::Compiler;
+use crate::{compiler::Compiler, feature::Features};
 
 fn shorten_hex_color(value: &str) -> Option<&str> {
     let length = value.len();
@@ -35,6 +35,8 @@ impl Compiler {
                 return;
             }
 
+            self.applied |= Features::COLOR_HEX_ALPHA;
+
             if let Some(shortened) = shorten_hex_color(&hex_color.value) {
                 hex_color.value = shortened.into();
                 hex_color.raw = None;
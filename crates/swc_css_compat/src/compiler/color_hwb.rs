@@ -5,7 +5,7 @@ use swc_css_ast::{
 };
 use swc_css_utils::{angle_to_deg, hwb_to_rgb, to_rgb255};
 
-use crate::compiler::Compiler;
+use crate::{compiler::Compiler, feature::Features};
 
 impl Compiler {
     fn get_hue(&self, hue: Option<&ComponentValue>) -> Option<f64> {
@@ -113,6 +113,8 @@ impl Compiler {
 
             let rgb = to_rgb255(hwb_to_rgb([h, w, b]));
 
+            self.applied |= Features::COLOR_HWB;
+
             if a == 1.0 {
                 *n = AbsoluteColorBase::Function(swc_css_ast::Function {
                     name: FunctionName::Ident(Ident {
@@ -0,0 +1,30 @@
+use swc_css_ast::AbsoluteColorBase;
+
+use crate::{
+    compiler::{
+        Compiler,
+        color::{ColorSpace, parse_absolute_color_base, to_legacy_rgb},
+    },
+    feature::Features,
+};
+
+impl Compiler {
+    pub(crate) fn process_color_lab(&mut self, n: &mut AbsoluteColorBase) {
+        let Some(color) = parse_absolute_color_base(n) else {
+            return;
+        };
+
+        let feature = match color.space {
+            ColorSpace::Lab | ColorSpace::Lch => Features::COLOR_LAB_AND_LCH,
+            ColorSpace::Oklab | ColorSpace::Oklch => Features::COLOR_OKLAB_AND_OKLCH,
+            _ => return,
+        };
+
+        if !self.c.process.contains(feature) {
+            return;
+        }
+
+        *n = to_legacy_rgb(color.to_srgb_in_gamut(), color.alpha);
+        self.applied |= feature;
+    }
+}
@@ -0,0 +1,219 @@
+use swc_css_ast::{AbsoluteColorBase, ComponentValue, DelimiterValue, Function};
+
+use crate::{
+    compiler::{
+        Compiler,
+        color::{Color, ColorSpace, normalize_hue, parse_color, to_legacy_rgb},
+    },
+    feature::Features,
+};
+
+#[derive(Debug, Clone, Copy)]
+enum HueInterpolationMethod {
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
+}
+
+struct MixComponent {
+    color: Color,
+    percentage: Option<f64>,
+}
+
+impl Compiler {
+    pub(crate) fn process_color_mix(&mut self, n: &mut AbsoluteColorBase) {
+        let AbsoluteColorBase::Function(function) = n else {
+            return;
+        };
+
+        if function.name != "color-mix" {
+            return;
+        }
+
+        let Some((rgb, alpha)) = mix(function) else {
+            return;
+        };
+
+        *n = to_legacy_rgb(rgb, alpha);
+        self.applied |= Features::COLOR_MIX;
+    }
+}
+
+fn mix(function: &Function) -> Option<([f64; 3], f64)> {
+    let mut arguments = function.value.split(|value| {
+        matches!(value, ComponentValue::Delimiter(delimiter) if delimiter.value == DelimiterValue::Comma)
+    });
+
+    let (space, hue_interpolation_method) = parse_interpolation_method(arguments.next()?)?;
+    let first = parse_mix_component(arguments.next()?)?;
+    let second = parse_mix_component(arguments.next()?)?;
+
+    if arguments.next().is_some() {
+        return None;
+    }
+
+    // https://drafts.csswg.org/css-color-5/#color-mix-percent-norm
+    let (p1, p2) = match (first.percentage, second.percentage) {
+        (None, None) => (0.5, 0.5),
+        (Some(p1), None) => (p1, 1.0 - p1),
+        (None, Some(p2)) => (1.0 - p2, p2),
+        (Some(p1), Some(p2)) => (p1, p2),
+    };
+
+    if !(0.0..=1.0).contains(&p1) || !(0.0..=1.0).contains(&p2) {
+        return None;
+    }
+
+    let sum = p1 + p2;
+
+    if sum == 0.0 {
+        return None;
+    }
+
+    let alpha_multiplier = sum.min(1.0);
+    let progress = p2 / sum;
+
+    let mut first = first.color.to(space);
+    let mut second = second.color.to(space);
+
+    // Missing components take the value of the other color
+    for (a, b) in first.coords.iter_mut().zip(second.coords.iter_mut()) {
+        match (a.is_nan(), b.is_nan()) {
+            (true, false) => *a = *b,
+            (false, true) => *b = *a,
+            _ => {}
+        }
+    }
+
+    match (first.alpha.is_nan(), second.alpha.is_nan()) {
+        (true, false) => first.alpha = second.alpha,
+        (false, true) => second.alpha = first.alpha,
+        _ => {}
+    }
+
+    let hue_index = space.hue_index();
+
+    if let Some(hue_index) = hue_index {
+        let h1 = &mut first.coords[hue_index];
+        let h2 = &mut second.coords[hue_index];
+
+        if !h1.is_nan() && !h2.is_nan() {
+            fix_up_hues(h1, h2, hue_interpolation_method);
+        }
+    }
+
+    let interpolate = |a: f64, b: f64| {
+        let a = if a.is_nan() { 0.0 } else { a };
+        let b = if b.is_nan() { 0.0 } else { b };
+
+        a + (b - a) * progress
+    };
+
+    let alpha = interpolate(first.alpha, second.alpha);
+    let mut coords = [0.0; 3];
+
+    for (i, coord) in coords.iter_mut().enumerate() {
+        let (a, b) = (first.coords[i], second.coords[i]);
+
+        *coord = if Some(i) == hue_index {
+            normalize_hue(interpolate(a, b))
+        } else if alpha == 0.0 {
+            interpolate(a, b)
+        } else {
+            // Interpolate premultiplied values
+            interpolate(a * first.alpha, b * second.alpha) / alpha
+        };
+    }
+
+    let color = Color {
+        space,
+        coords,
+        alpha: alpha * alpha_multiplier,
+    };
+
+    Some((color.to_srgb_in_gamut(), color.alpha))
+}
+
+/// Parses `in <color-space> [<hue-interpolation-method> hue]?`.
+fn parse_interpolation_method(
+    values: &[ComponentValue],
+) -> Option<(ColorSpace, HueInterpolationMethod)> {
+    let idents = values
+        .iter()
+        .map(|value| match value {
+            ComponentValue::Ident(ident) => Some(ident.value.to_ascii_lowercase()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let (space, hue_interpolation_method) = match &*idents {
+        [keyword, space] if keyword == "in" => (space, None),
+        [keyword, space, method, hue] if keyword == "in" && hue == "hue" => (space, Some(method)),
+        _ => return None,
+    };
+    let space = ColorSpace::from_name(space)?;
+
+    if hue_interpolation_method.is_some() && space.hue_index().is_none() {
+        return None;
+    }
+
+    let hue_interpolation_method = match hue_interpolation_method.map(|method| &**method) {
+        None | Some("shorter") => HueInterpolationMethod::Shorter,
+        Some("longer") => HueInterpolationMethod::Longer,
+        Some("increasing") => HueInterpolationMethod::Increasing,
+        Some("decreasing") => HueInterpolationMethod::Decreasing,
+        _ => return None,
+    };
+
+    Some((space, hue_interpolation_method))
+}
+
+/// Parses `<color> && <percentage [0,100]>?`.
+fn parse_mix_component(values: &[ComponentValue]) -> Option<MixComponent> {
+    let (color, percentage) = match values {
+        [color] => (color, None),
+        [ComponentValue::Percentage(percentage), color]
+        | [color, ComponentValue::Percentage(percentage)] => {
+            (color, Some(percentage.value.value / 100.0))
+        }
+        _ => return None,
+    };
+
+    Some(MixComponent {
+        color: parse_color(color)?,
+        percentage,
+    })
+}
+
+/// https://drafts.csswg.org/css-color-4/#hue-interpolation
+fn fix_up_hues(h1: &mut f64, h2: &mut f64, method: HueInterpolationMethod) {
+    let diff = *h2 - *h1;
+
+    match method {
+        HueInterpolationMethod::Shorter => {
+            if diff > 180.0 {
+                *h1 += 360.0;
+            } else if diff < -180.0 {
+                *h2 += 360.0;
+            }
+        }
+        HueInterpolationMethod::Longer => {
+            if 0.0 < diff && diff < 180.0 {
+                *h1 += 360.0;
+            } else if -180.0 < diff && diff <= 0.0 {
+                *h2 += 360.0;
+            }
+        }
+        HueInterpolationMethod::Increasing => {
+            if diff < 0.0 {
+                *h2 += 360.0;
+            }
+        }
+        HueInterpolationMethod::Decreasing => {
+            if diff > 0.0 {
+                *h1 += 360.0;
+            }
+        }
+    }
+}
@@ -5,7 +5,7 @@ use swc_css_ast::{
     AbsoluteColorBase, ComponentValue, Delimiter, DelimiterValue, matches_eq_ignore_ascii_case,
 };
 
-use crate::compiler::Compiler;
+use crate::{compiler::Compiler, feature::Features};
 
 impl Compiler {
     pub(crate) fn process_color_space_separated_function_notation(
@@ -58,6 +58,7 @@ impl Compiler {
                 .collect::<Vec<_>>();
 
             function.value = new_value;
+            self.applied |= Features::COLOR_SPACE_SEPARATED_PARAMETERS;
         }
     }
 }
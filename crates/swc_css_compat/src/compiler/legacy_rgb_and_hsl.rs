@@ -6,13 +6,14 @@ use swc_css_ast::{
 };
 use swc_css_utils::{clamp_unit_f64, round_alpha};
 
-use crate::compiler::Compiler;
+use crate::{compiler::Compiler, feature::Features};
 
 impl Compiler {
     pub(crate) fn process_rgb_and_hsl(&mut self, n: &mut AbsoluteColorBase) {
         if let AbsoluteColorBase::Function(function) = n {
             let is_rgb = matches_eq_ignore_ascii_case!(function.name.as_str(), "rgb", "rgba");
             let is_hsl = matches_eq_ignore_ascii_case!(function.name.as_str(), "hsl", "hsla");
+            let mut changed = false;
 
             if is_rgb {
                 function.value = function
@@ -26,6 +27,8 @@ impl Compiler {
                                 ..
                             } = &*percentage;
 
+                            changed = true;
+
                             ComponentValue::Number(Box::new(Number {
                                 span: *span,
                                 value: clamp_unit_f64(value / 100.0) as f64,
@@ -57,6 +60,8 @@ impl Compiler {
                                 }
                             };
 
+                            changed = true;
+
                             ComponentValue::Number(Box::new(Number {
                                 span: *span,
                                 value: value.round(),
@@ -87,9 +92,14 @@ impl Compiler {
                             value: round_alpha(*a / 100.0),
                             raw: None,
                         });
+                        changed = true;
                     }
                 }
             }
+
+            if changed {
+                self.applied |= Features::COLOR_LEGACY_RGB_AND_HSL;
+            }
         }
     }
 }
@@ -0,0 +1,213 @@
+use swc_atoms::{Atom, atom};
+use swc_common::DUMMY_SP;
+use swc_css_ast::{
+    AtRule, AtRuleName, AtRulePrelude, Color, ComponentValue, DashedIdent, Declaration,
+    DeclarationName, Delimiter, DelimiterValue, Function, FunctionName, Ident, MediaCondition,
+    MediaConditionAllType, MediaConditionType, MediaFeature, MediaFeatureName, MediaFeaturePlain,
+    MediaFeatureValue, MediaInParens, MediaQuery, MediaQueryList, QualifiedRule, SimpleBlock,
+    Token, TokenAndSpan,
+};
+
+use crate::{compiler::Compiler, feature::Features};
+
+impl Compiler {
+    /// Replaces `light-dark(<light>, <dark>)` with
+    /// `var(--swc-light, <light>) var(--swc-dark, <dark>)`, where one of the
+    /// custom properties is set to `initial` (i.e. the fallback is used) and
+    /// the other one to an empty value by [Compiler::process_color_scheme].
+    pub(crate) fn process_light_dark(&mut self, n: &mut Vec<ComponentValue>) {
+        if !n.iter().any(|value| get_light_dark(value).is_some()) {
+            return;
+        }
+
+        self.applied |= Features::LIGHT_DARK;
+
+        let mut new = Vec::with_capacity(n.len() + 1);
+
+        for value in n.drain(..) {
+            let Some((light, dark)) = get_light_dark(&value) else {
+                new.push(value);
+
+                continue;
+            };
+
+            new.push(var(atom!("swc-light"), light));
+            new.push(var(atom!("swc-dark"), dark));
+        }
+
+        *n = new;
+    }
+
+    /// Sets the custom properties used by `light-dark()` in rules with a
+    /// `color-scheme`, and returns a `@media (prefers-color-scheme: dark)`
+    /// rule for rules supporting both color schemes.
+    ///
+    /// Elements which don't inherit a `color-scheme` from such a rule have
+    /// none of the custom properties set, so a `color-scheme` should be set
+    /// on `:root` for `light-dark()` to work.
+    pub(crate) fn process_color_scheme(&mut self, n: &mut QualifiedRule) -> Option<AtRule> {
+        let (index, light, dark) = n
+            .block
+            .value
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, value)| match value {
+                ComponentValue::Declaration(declaration)
+                    if matches!(&declaration.name, DeclarationName::Ident(name) if name.value.eq_ignore_ascii_case("color-scheme")) =>
+                {
+                    let has = |scheme: &str| {
+                        declaration.value.iter().any(|value| {
+                            matches!(value, ComponentValue::Ident(ident) if ident.value.eq_ignore_ascii_case(scheme))
+                        })
+                    };
+
+                    Some((index, has("light"), has("dark")))
+                }
+                _ => None,
+            })?;
+
+        n.block
+            .value
+            .splice(index + 1..index + 1, custom_properties(!dark || light));
+        self.applied |= Features::LIGHT_DARK;
+
+        if !(light && dark) {
+            return None;
+        }
+
+        Some(AtRule {
+            span: DUMMY_SP,
+            name: AtRuleName::Ident(Ident {
+                span: DUMMY_SP,
+                value: atom!("media"),
+                raw: None,
+            }),
+            prelude: Some(Box::new(AtRulePrelude::MediaPrelude(MediaQueryList {
+                span: DUMMY_SP,
+                queries: vec![MediaQuery {
+                    span: DUMMY_SP,
+                    modifier: None,
+                    media_type: None,
+                    keyword: None,
+                    condition: Some(Box::new(MediaConditionType::All(MediaCondition {
+                        span: DUMMY_SP,
+                        conditions: vec![MediaConditionAllType::MediaInParens(
+                            MediaInParens::Feature(Box::new(MediaFeature::Plain(
+                                MediaFeaturePlain {
+                                    span: DUMMY_SP,
+                                    name: MediaFeatureName::Ident(Ident {
+                                        span: DUMMY_SP,
+                                        value: atom!("prefers-color-scheme"),
+                                        raw: None,
+                                    }),
+                                    value: Box::new(MediaFeatureValue::Ident(Ident {
+                                        span: DUMMY_SP,
+                                        value: atom!("dark"),
+                                        raw: None,
+                                    })),
+                                },
+                            ))),
+                        )],
+                    }))),
+                }],
+            }))),
+            block: Some(SimpleBlock {
+                span: DUMMY_SP,
+                name: TokenAndSpan {
+                    span: DUMMY_SP,
+                    token: Token::LBrace,
+                },
+                value: vec![ComponentValue::QualifiedRule(Box::new(QualifiedRule {
+                    span: DUMMY_SP,
+                    prelude: n.prelude.clone(),
+                    block: SimpleBlock {
+                        span: DUMMY_SP,
+                        name: n.block.name.clone(),
+                        value: custom_properties(false),
+                    },
+                }))],
+            }),
+        })
+    }
+}
+
+fn get_light_dark(value: &ComponentValue) -> Option<(&[ComponentValue], &[ComponentValue])> {
+    let function = match value {
+        ComponentValue::Function(function) => function,
+        ComponentValue::Color(color) => match &**color {
+            Color::Function(function) => function,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    if function.name != "light-dark" {
+        return None;
+    }
+
+    let comma = function.value.iter().position(|value| {
+        matches!(value, ComponentValue::Delimiter(delimiter) if delimiter.value == DelimiterValue::Comma)
+    })?;
+    let (light, dark) = (&function.value[..comma], &function.value[comma + 1..]);
+
+    if light.is_empty() || dark.is_empty() {
+        return None;
+    }
+
+    Some((light, dark))
+}
+
+fn var(name: Atom, fallback: &[ComponentValue]) -> ComponentValue {
+    let mut value = vec![
+        ComponentValue::DashedIdent(Box::new(DashedIdent {
+            span: DUMMY_SP,
+            value: name,
+            raw: None,
+        })),
+        ComponentValue::Delimiter(Box::new(Delimiter {
+            span: DUMMY_SP,
+            value: DelimiterValue::Comma,
+        })),
+    ];
+
+    value.extend(fallback.iter().cloned());
+
+    ComponentValue::Function(Box::new(Function {
+        span: DUMMY_SP,
+        name: FunctionName::Ident(Ident {
+            span: DUMMY_SP,
+            value: atom!("var"),
+            raw: None,
+        }),
+        value,
+    }))
+}
+
+fn custom_properties(light: bool) -> Vec<ComponentValue> {
+    let custom_property = |name: Atom, enabled: bool| {
+        ComponentValue::Declaration(Box::new(Declaration {
+            span: DUMMY_SP,
+            name: DeclarationName::DashedIdent(DashedIdent {
+                span: DUMMY_SP,
+                value: name,
+                raw: None,
+            }),
+            value: if enabled {
+                vec![ComponentValue::Ident(Box::new(Ident {
+                    span: DUMMY_SP,
+                    value: atom!("initial"),
+                    raw: None,
+                }))]
+            } else {
+                Vec::new()
+            },
+            important: None,
+        }))
+    };
+
+    vec![
+        custom_property(atom!("swc-light"), light),
+        custom_property(atom!("swc-dark"), !light),
+    ]
+}
@@ -0,0 +1,186 @@
+use swc_atoms::{Atom, atom};
+use swc_common::Spanned;
+use swc_css_ast::{ComponentValue, Declaration, DeclarationName, Ident};
+
+use crate::{compiler::Compiler, feature::Features};
+
+/// The physical properties a logical property maps to.
+enum PhysicalProperties {
+    /// The value is used for a single physical property.
+    Single(Atom),
+    /// `<start> <end>?`, the start value is used for the end if it is omitted.
+    StartAndEnd(Atom, Atom),
+    /// The value is used for both physical properties.
+    Both(Atom, Atom),
+}
+
+impl Compiler {
+    /// Replaces logical properties and values with physical ones, assuming
+    /// the `horizontal-tb` writing mode and the `ltr` direction.
+    pub(crate) fn process_logical_properties(&mut self, n: &mut Vec<ComponentValue>) {
+        let mut new = Vec::with_capacity(n.len());
+
+        for value in n.drain(..) {
+            let ComponentValue::Declaration(mut declaration) = value else {
+                new.push(value);
+
+                continue;
+            };
+
+            let DeclarationName::Ident(name) = &declaration.name else {
+                new.push(ComponentValue::Declaration(declaration));
+
+                continue;
+            };
+
+            let name = name.value.to_ascii_lowercase();
+
+            if (name == "float" || name == "clear") && process_logical_value(&mut declaration) {
+                self.applied |= Features::LOGICAL_PROPERTIES;
+            }
+
+            let Some(physical) = get_physical_properties(&name) else {
+                new.push(ComponentValue::Declaration(declaration));
+
+                continue;
+            };
+
+            match physical {
+                PhysicalProperties::Single(name) => {
+                    new.push(ComponentValue::Declaration(rename(&declaration, name)));
+                }
+                PhysicalProperties::Both(start, end) => {
+                    new.push(ComponentValue::Declaration(rename(&declaration, start)));
+                    new.push(ComponentValue::Declaration(rename(&declaration, end)));
+                }
+                PhysicalProperties::StartAndEnd(start, end) => {
+                    // A single `var()` can't be split as it may contain both values
+                    let has_var = declaration.value.iter().any(|value| {
+                        matches!(value, ComponentValue::Function(function) if function.name == "var" || function.name == "env")
+                    });
+
+                    match &*declaration.value {
+                        [value] if !has_var => {
+                            let mut start = rename(&declaration, start);
+                            let mut end = rename(&declaration, end);
+
+                            start.value = vec![value.clone()];
+                            end.value = vec![value.clone()];
+
+                            new.push(ComponentValue::Declaration(start));
+                            new.push(ComponentValue::Declaration(end));
+                        }
+                        [start_value, end_value] => {
+                            let mut start = rename(&declaration, start);
+                            let mut end = rename(&declaration, end);
+
+                            start.value = vec![start_value.clone()];
+                            end.value = vec![end_value.clone()];
+
+                            new.push(ComponentValue::Declaration(start));
+                            new.push(ComponentValue::Declaration(end));
+                        }
+                        _ => {
+                            new.push(ComponentValue::Declaration(declaration));
+
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            self.applied |= Features::LOGICAL_PROPERTIES;
+        }
+
+        *n = new;
+    }
+}
+
+/// Replaces `inline-start` and `inline-end` in `float` and `clear`, returns
+/// whether the value was replaced.
+fn process_logical_value(declaration: &mut Declaration) -> bool {
+    if let [ComponentValue::Ident(ident)] = &mut *declaration.value {
+        let value = match &*ident.value.to_ascii_lowercase() {
+            "inline-start" => atom!("left"),
+            "inline-end" => atom!("right"),
+            _ => return false,
+        };
+
+        ident.value = value;
+        ident.raw = None;
+
+        return true;
+    }
+
+    false
+}
+
+fn rename(declaration: &Declaration, name: Atom) -> Box<Declaration> {
+    Box::new(Declaration {
+        name: DeclarationName::Ident(Ident {
+            span: declaration.name.span(),
+            value: name,
+            raw: None,
+        }),
+        ..declaration.clone()
+    })
+}
+
+fn get_physical_properties(name: &str) -> Option<PhysicalProperties> {
+    let single = match name {
+        "inline-size" => Some("width"),
+        "block-size" => Some("height"),
+        "min-inline-size" => Some("min-width"),
+        "min-block-size" => Some("min-height"),
+        "max-inline-size" => Some("max-width"),
+        "max-block-size" => Some("max-height"),
+        "border-start-start-radius" => Some("border-top-left-radius"),
+        "border-start-end-radius" => Some("border-top-right-radius"),
+        "border-end-start-radius" => Some("border-bottom-left-radius"),
+        "border-end-end-radius" => Some("border-bottom-right-radius"),
+        _ => None,
+    };
+
+    if let Some(single) = single {
+        return Some(PhysicalProperties::Single(single.into()));
+    }
+
+    // `<property>-<axis>[-<side>][-<suffix>]`
+    let (property, rest) = ["margin", "padding", "inset", "border"]
+        .into_iter()
+        .find_map(|property| Some((property, name.strip_prefix(property)?.strip_prefix('-')?)))?;
+    let (axis, rest) = rest.split_once('-').unwrap_or((rest, ""));
+    let (start, end) = match axis {
+        "inline" => ("left", "right"),
+        "block" => ("top", "bottom"),
+        _ => return None,
+    };
+    let (side, suffix) = match rest.split_once('-').unwrap_or((rest, "")) {
+        (side @ ("start" | "end"), suffix) => (Some(side), suffix),
+        ("", "") => (None, ""),
+        _ => (None, rest),
+    };
+
+    if !suffix.is_empty()
+        && (property != "border" || !matches!(suffix, "width" | "style" | "color"))
+    {
+        return None;
+    }
+
+    let physical = |side: &str| -> Atom {
+        match (property, suffix) {
+            ("inset", _) => side.into(),
+            (_, "") => format!("{property}-{side}").into(),
+            _ => format!("{property}-{side}-{suffix}").into(),
+        }
+    };
+
+    Some(match side {
+        Some("start") => PhysicalProperties::Single(physical(start)),
+        Some(_) => PhysicalProperties::Single(physical(end)),
+        None if property == "border" && suffix.is_empty() => {
+            PhysicalProperties::Both(physical(start), physical(end))
+        }
+        None => PhysicalProperties::StartAndEnd(physical(start), physical(end)),
+    })
+}
@@ -1,22 +1,29 @@
 use swc_common::{DUMMY_SP, Spanned, util::take::Take};
 use swc_css_ast::{
     AbsoluteColorBase, AtRule, ComponentValue, CompoundSelector, MediaAnd, MediaCondition,
-    MediaConditionAllType, MediaConditionWithoutOr, MediaInParens, MediaQuery, Rule,
-    SupportsCondition,
+    MediaConditionAllType, MediaConditionWithoutOr, MediaInParens, MediaQuery, Rule, SelectorList,
+    Stylesheet, SupportsCondition,
 };
 use swc_css_visit::{VisitMut, VisitMutWith};
 
 use self::custom_media::CustomMediaHandler;
 use crate::feature::Features;
 
+mod cascade_layers;
+mod color;
 mod color_alpha_parameter;
 mod color_hex_alpha;
 mod color_hwb;
+mod color_lab;
+mod color_mix;
 mod color_space_separated_parameters;
 mod custom_media;
 mod legacy_rgb_and_hsl;
+mod light_dark;
+mod logical_properties;
 mod media_query_ranges;
 mod nesting;
+mod selector_is;
 mod selector_not;
 
 /// Compiles a modern CSS file to a CSS file which works with old browsers.
@@ -26,6 +33,7 @@ pub struct Compiler {
     c: Config,
     custom_media: CustomMediaHandler,
     in_supports_condition: bool,
+    applied: Features,
 }
 
 #[derive(Debug)]
@@ -40,11 +48,26 @@ impl Compiler {
             c: config,
             custom_media: Default::default(),
             in_supports_condition: Default::default(),
+            applied: Features::empty(),
         }
     }
+
+    /// Returns the features which were lowered, i.e. the ones which changed
+    /// the visited stylesheets.
+    pub fn applied(&self) -> Features {
+        self.applied
+    }
 }
 
 impl VisitMut for Compiler {
+    fn visit_mut_stylesheet(&mut self, n: &mut Stylesheet) {
+        n.visit_mut_children_with(self);
+
+        if self.c.process.contains(Features::CASCADE_LAYERS) {
+            self.process_cascade_layers(n);
+        }
+    }
+
     fn visit_mut_at_rule(&mut self, n: &mut AtRule) {
         n.visit_mut_children_with(self);
 
@@ -96,12 +119,19 @@ impl VisitMut for Compiler {
                     Rule::QualifiedRule(mut n) => {
                         let mut rules = self.extract_nested_rules(&mut n);
 
+                        if !rules.is_empty() {
+                            self.applied |= Features::NESTING;
+                        }
+
+                        n.visit_mut_with(self);
                         rules.visit_mut_with(self);
 
                         new.push(Rule::QualifiedRule(n));
                         new.extend(rules);
                     }
-                    _ => {
+                    mut n => {
+                        n.visit_mut_with(self);
+
                         new.push(n);
                     }
                 }
@@ -113,7 +143,76 @@ impl VisitMut for Compiler {
         }
 
         if self.c.process.contains(Features::CUSTOM_MEDIA) {
+            let len = n.len();
+
             self.custom_media.process_rules(n);
+
+            // References are replaced by the queries of the removed rules
+            if n.len() != len {
+                self.applied |= Features::CUSTOM_MEDIA;
+            }
+        }
+
+        if self.c.process.contains(Features::LIGHT_DARK) {
+            let mut new = Vec::with_capacity(n.len());
+
+            for mut rule in n.take() {
+                let media = match &mut rule {
+                    Rule::QualifiedRule(rule) => self.process_color_scheme(rule),
+                    _ => None,
+                };
+
+                new.push(rule);
+                new.extend(media.map(|media| Rule::AtRule(Box::new(media))));
+            }
+
+            *n = new;
+        }
+    }
+
+    fn visit_mut_component_values(&mut self, n: &mut Vec<ComponentValue>) {
+        n.visit_mut_children_with(self);
+
+        if self.in_supports_condition {
+            return;
+        }
+
+        if self.c.process.contains(Features::LIGHT_DARK) {
+            self.process_light_dark(n);
+
+            if n.iter()
+                .any(|value| matches!(value, ComponentValue::QualifiedRule(..)))
+            {
+                let mut new = Vec::with_capacity(n.len());
+
+                for mut value in n.take() {
+                    let media = match &mut value {
+                        ComponentValue::QualifiedRule(rule) => self.process_color_scheme(rule),
+                        _ => None,
+                    };
+
+                    new.push(value);
+                    new.extend(media.map(|media| ComponentValue::AtRule(Box::new(media))));
+                }
+
+                *n = new;
+            }
+        }
+
+        if self.c.process.contains(Features::LOGICAL_PROPERTIES) {
+            self.process_logical_properties(n);
+        }
+    }
+
+    fn visit_mut_selector_list(&mut self, n: &mut SelectorList) {
+        n.visit_mut_children_with(self);
+
+        if self.in_supports_condition {
+            return;
+        }
+
+        if self.c.process.contains(Features::SELECTOR_IS) {
+            self.process_selector_is(n);
         }
     }
 
@@ -123,6 +222,8 @@ impl VisitMut for Compiler {
         if self.c.process.contains(Features::MEDIA_QUERY_RANGES) {
             if let MediaInParens::Feature(media_feature) = n {
                 if let Some(legacy_media_feature) = self.get_legacy_media_feature(media_feature) {
+                    self.applied |= Features::MEDIA_QUERY_RANGES;
+
                     match legacy_media_feature {
                         (legacy_media_feature, None) => {
                             **media_feature = legacy_media_feature;
@@ -199,5 +300,13 @@ impl VisitMut for Compiler {
         if process.contains(Features::COLOR_HWB) {
             self.process_color_hwb(n);
         }
+
+        if process.intersects(Features::COLOR_LAB_AND_LCH | Features::COLOR_OKLAB_AND_OKLCH) {
+            self.process_color_lab(n);
+        }
+
+        if process.contains(Features::COLOR_MIX) {
+            self.process_color_mix(n);
+        }
     }
 }
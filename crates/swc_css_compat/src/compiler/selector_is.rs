@@ -0,0 +1,180 @@
+use swc_common::EqIgnoreSpan;
+use swc_css_ast::{
+    ComplexSelector, ComplexSelectorChildren, CompoundSelector, ForgivingComplexSelector,
+    ForgivingSelectorList, PseudoClassSelector, PseudoClassSelectorChildren, SelectorList,
+    SubclassSelector, TypeSelector,
+};
+
+use crate::{compiler::Compiler, feature::Features};
+
+impl Compiler {
+    /// Replaces `:is()` and `:where()` with their arguments, e.g.
+    /// `:is(.a, .b) > .c` becomes `.a > .c, .b > .c`.
+    ///
+    /// The specificity of `:where()` can't be preserved, so it becomes the
+    /// specificity of its arguments. Selectors are kept as is when their
+    /// arguments can't be merged, e.g. complex selectors in the middle of a
+    /// selector or `div:is(span)`.
+    pub(crate) fn process_selector_is(&mut self, n: &mut SelectorList) {
+        if !n
+            .children
+            .iter()
+            .any(|selector| find_is(selector).is_some())
+        {
+            return;
+        }
+
+        let mut children = Vec::with_capacity(n.children.len());
+        let mut changed = false;
+
+        for selector in n.children.drain(..) {
+            changed |= expand(selector, &mut children);
+        }
+
+        n.children = children;
+
+        if changed {
+            self.applied |= Features::SELECTOR_IS;
+        }
+    }
+}
+
+/// Returns whether `selector` was expanded.
+fn expand(selector: ComplexSelector, expanded: &mut Vec<ComplexSelector>) -> bool {
+    match expand_first(&selector) {
+        Some(selectors) => {
+            for selector in selectors {
+                expand(selector, expanded);
+            }
+
+            true
+        }
+        None => {
+            expanded.push(selector);
+
+            false
+        }
+    }
+}
+
+/// Returns the indexes of the first compound selector containing `:is()` or
+/// `:where()`, of the pseudo-class in this compound selector and its
+/// arguments.
+fn find_is(selector: &ComplexSelector) -> Option<(usize, usize, &ForgivingSelectorList)> {
+    selector
+        .children
+        .iter()
+        .enumerate()
+        .find_map(|(i, child)| match child {
+            ComplexSelectorChildren::CompoundSelector(compound) => compound
+                .subclass_selectors
+                .iter()
+                .enumerate()
+                .find_map(|(j, subclass_selector)| match subclass_selector {
+                    SubclassSelector::PseudoClass(PseudoClassSelector {
+                        name,
+                        children: Some(children),
+                        ..
+                    }) if name.value.eq_ignore_ascii_case("is")
+                        || name.value.eq_ignore_ascii_case("where") =>
+                    {
+                        match &**children {
+                            [PseudoClassSelectorChildren::ForgivingSelectorList(list)] => {
+                                Some((i, j, list))
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }),
+            _ => None,
+        })
+}
+
+fn expand_first(selector: &ComplexSelector) -> Option<Vec<ComplexSelector>> {
+    let (i, j, arguments) = find_is(selector)?;
+    let ComplexSelectorChildren::CompoundSelector(compound) = &selector.children[i] else {
+        unreachable!()
+    };
+
+    if arguments.children.is_empty() {
+        return None;
+    }
+
+    arguments
+        .children
+        .iter()
+        .map(|argument| {
+            let ForgivingComplexSelector::ComplexSelector(argument) = argument else {
+                return None;
+            };
+            let (ComplexSelectorChildren::CompoundSelector(last), ancestors) =
+                argument.children.split_last()?
+            else {
+                return None;
+            };
+
+            // `.a :is(.b .c)` matches `.b .a .c` too
+            if i != 0 && !ancestors.is_empty() {
+                return None;
+            }
+
+            let mut children = Vec::with_capacity(selector.children.len() + ancestors.len());
+
+            children.extend(ancestors.iter().cloned());
+            children.extend(selector.children[..i].iter().cloned());
+            children.push(ComplexSelectorChildren::CompoundSelector(merge(
+                compound, j, last,
+            )?));
+            children.extend(selector.children[i + 1..].iter().cloned());
+
+            Some(ComplexSelector {
+                span: selector.span,
+                children,
+            })
+        })
+        .collect()
+}
+
+/// Replaces the subclass selector at `index` in `compound` with `argument`.
+fn merge(
+    compound: &CompoundSelector,
+    index: usize,
+    argument: &CompoundSelector,
+) -> Option<CompoundSelector> {
+    if argument.nesting_selector.is_some()
+        || argument
+            .subclass_selectors
+            .iter()
+            .any(|selector| matches!(selector, SubclassSelector::PseudoElement(..)))
+    {
+        return None;
+    }
+
+    let is_universal = |type_selector: &TypeSelector| matches!(type_selector, TypeSelector::Universal(universal) if universal.prefix.is_none());
+    let type_selector = match (&compound.type_selector, &argument.type_selector) {
+        (None, type_selector) | (type_selector, None) => type_selector.clone(),
+        (Some(a), Some(b)) if a.eq_ignore_span(b) || is_universal(b) => Some(a.clone()),
+        (Some(a), Some(b)) if is_universal(a) => Some(b.clone()),
+        _ => return None,
+    };
+
+    // `&div` is not valid
+    if compound.nesting_selector.is_some() && argument.type_selector.is_some() {
+        return None;
+    }
+
+    let mut subclass_selectors =
+        Vec::with_capacity(compound.subclass_selectors.len() + argument.subclass_selectors.len());
+
+    subclass_selectors.extend(compound.subclass_selectors[..index].iter().cloned());
+    subclass_selectors.extend(argument.subclass_selectors.iter().cloned());
+    subclass_selectors.extend(compound.subclass_selectors[index + 1..].iter().cloned());
+
+    Some(CompoundSelector {
+        span: compound.span,
+        nesting_selector: compound.nesting_selector.clone(),
+        type_selector,
+        subclass_selectors,
+    })
+}
@@ -3,7 +3,7 @@ use swc_css_ast::{
     SubclassSelector,
 };
 
-use crate::compiler::Compiler;
+use crate::{compiler::Compiler, feature::Features};
 
 impl Compiler {
     pub(crate) fn process_selector_not(&mut self, n: &mut CompoundSelector) {
@@ -50,5 +50,6 @@ impl Compiler {
         }
 
         n.subclass_selectors = new_subclass_selectors;
+        self.applied |= Features::SELECTOR_NOT;
     }
 }
@@ -1,7 +1,13 @@
 use bitflags::bitflags;
+use once_cell::sync::Lazy;
+use preset_env_base::{
+    BrowserData, Versions,
+    version::{Version, should_enable},
+};
+use rustc_hash::FxHashMap;
 
 bitflags! {
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct Features: u64 {
         const NESTING = 1 << 0;
         const CUSTOM_MEDIA = 1 << 1;
@@ -12,5 +18,60 @@ bitflags! {
         const COLOR_LEGACY_RGB_AND_HSL = 1 << 6;
         const COLOR_HWB = 1 << 7;
         const SELECTOR_NOT = 1 << 8;
+        /// `color-mix()`
+        const COLOR_MIX = 1 << 9;
+        /// `lab()` and `lch()`
+        const COLOR_LAB_AND_LCH = 1 << 10;
+        /// `oklab()` and `oklch()`
+        const COLOR_OKLAB_AND_OKLCH = 1 << 11;
+        /// `light-dark()`
+        const LIGHT_DARK = 1 << 12;
+        /// Logical properties and values, e.g. `margin-inline-start`
+        const LOGICAL_PROPERTIES = 1 << 13;
+        /// `@layer`
+        const CASCADE_LAYERS = 1 << 14;
+        /// `:is()` and `:where()`
+        const SELECTOR_IS = 1 << 15;
+    }
+}
+
+/// The minimum versions of browsers supporting each feature, the keys are the
+/// names of the flags in kebab case.
+static FEATURES: Lazy<FxHashMap<String, Versions>> = Lazy::new(|| {
+    let map: FxHashMap<String, BrowserData<Option<String>>> =
+        serde_json::from_str(include_str!("../data/features.json")).expect("failed to parse json");
+
+    map.into_iter()
+        .map(|(feature, versions)| {
+            (
+                feature,
+                versions.map_value(|version| {
+                    version.map(|v| {
+                        v.parse::<Version>().unwrap_or_else(|err| {
+                            panic!("failed to parse `{v}` as a version: {err:?}")
+                        })
+                    })
+                }),
+            )
+        })
+        .collect()
+});
+
+impl Features {
+    /// Returns the features which are not supported by some of the browsers
+    /// of `targets`, i.e. the features to process for these browsers.
+    pub fn from_targets(targets: &Versions) -> Self {
+        Features::all()
+            .iter_names()
+            .filter(|(name, _)| {
+                let name = name.to_ascii_lowercase().replace('_', "-");
+
+                FEATURES
+                    .get(&name)
+                    .map_or(true, |versions| should_enable(targets, versions, false))
+            })
+            .fold(Features::empty(), |features, (_, feature)| {
+                features | feature
+            })
     }
 }
@@ -0,0 +1,53 @@
+#![deny(warnings)]
+
+use preset_env_base::{BrowserData, Versions};
+use swc_css_compat::feature::Features;
+
+fn targets(browsers: &[(&str, &str)]) -> Versions {
+    let mut targets = BrowserData::default();
+
+    for (browser, version) in browsers {
+        targets.insert(browser, Some(version.parse().unwrap()));
+    }
+
+    targets
+}
+
+#[test]
+fn old_browsers_process_all_features() {
+    assert_eq!(
+        Features::from_targets(&targets(&[("chrome", "40"), ("safari", "8")])),
+        Features::all()
+    );
+    assert_eq!(
+        Features::from_targets(&targets(&[("ie", "11")])),
+        Features::all()
+    );
+}
+
+#[test]
+fn recent_browsers_process_unsupported_features() {
+    assert_eq!(
+        Features::from_targets(&targets(&[("chrome", "130"), ("firefox", "130")])),
+        Features::CUSTOM_MEDIA
+    );
+    assert_eq!(
+        Features::from_targets(&targets(&[("chrome", "100"), ("safari", "15.4")])),
+        Features::NESTING
+            | Features::CUSTOM_MEDIA
+            | Features::MEDIA_QUERY_RANGES
+            | Features::COLOR_HWB
+            | Features::COLOR_MIX
+            | Features::COLOR_LAB_AND_LCH
+            | Features::COLOR_OKLAB_AND_OKLCH
+            | Features::LIGHT_DARK
+    );
+}
+
+#[test]
+fn android_falls_back_to_chrome() {
+    assert_eq!(
+        Features::from_targets(&targets(&[("android", "130")])),
+        Features::CUSTOM_MEDIA
+    );
+}
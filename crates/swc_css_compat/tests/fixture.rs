@@ -7,7 +7,7 @@
 
 use std::path::PathBuf;
 
-use swc_common::{SourceFile, errors::HANDLER, sync::Lrc};
+use swc_common::{FileName, SourceFile, errors::HANDLER, sync::Lrc};
 use swc_css_ast::Stylesheet;
 use swc_css_codegen::{
     CodegenConfig, Emit,
@@ -204,6 +204,132 @@ fn test_color_hwb(input: PathBuf) {
     .unwrap();
 }
 
+#[testing::fixture("tests/color-mix/**/*.css", exclude("expect.css"))]
+fn test_color_mix(input: PathBuf) {
+    let output = input.with_extension("expect.css");
+
+    testing::run_test(false, |cm, _| {
+        let fm = cm.load_file(&input).unwrap();
+        let mut ss = parse_stylesheet(&fm);
+
+        ss.visit_mut_with(&mut Compiler::new(Config {
+            process: Features::COLOR_MIX,
+        }));
+
+        let s = print_stylesheet(&ss);
+
+        NormalizedOutput::from(s).compare_to_file(&output).unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[testing::fixture("tests/color-lab/**/*.css", exclude("expect.css"))]
+fn test_color_lab(input: PathBuf) {
+    let output = input.with_extension("expect.css");
+
+    testing::run_test(false, |cm, _| {
+        let fm = cm.load_file(&input).unwrap();
+        let mut ss = parse_stylesheet(&fm);
+
+        ss.visit_mut_with(&mut Compiler::new(Config {
+            process: Features::COLOR_LAB_AND_LCH | Features::COLOR_OKLAB_AND_OKLCH,
+        }));
+
+        let s = print_stylesheet(&ss);
+
+        NormalizedOutput::from(s).compare_to_file(&output).unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[testing::fixture("tests/light-dark/**/*.css", exclude("expect.css"))]
+fn test_light_dark(input: PathBuf) {
+    let output = input.with_extension("expect.css");
+
+    testing::run_test(false, |cm, _| {
+        let fm = cm.load_file(&input).unwrap();
+        let mut ss = parse_stylesheet(&fm);
+
+        ss.visit_mut_with(&mut Compiler::new(Config {
+            process: Features::LIGHT_DARK,
+        }));
+
+        let s = print_stylesheet(&ss);
+
+        NormalizedOutput::from(s).compare_to_file(&output).unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[testing::fixture("tests/logical-properties/**/*.css", exclude("expect.css"))]
+fn test_logical_properties(input: PathBuf) {
+    let output = input.with_extension("expect.css");
+
+    testing::run_test(false, |cm, _| {
+        let fm = cm.load_file(&input).unwrap();
+        let mut ss = parse_stylesheet(&fm);
+
+        ss.visit_mut_with(&mut Compiler::new(Config {
+            process: Features::LOGICAL_PROPERTIES,
+        }));
+
+        let s = print_stylesheet(&ss);
+
+        NormalizedOutput::from(s).compare_to_file(&output).unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[testing::fixture("tests/cascade-layers/**/*.css", exclude("expect.css"))]
+fn test_cascade_layers(input: PathBuf) {
+    let output = input.with_extension("expect.css");
+
+    testing::run_test(false, |cm, _| {
+        let fm = cm.load_file(&input).unwrap();
+        let mut ss = parse_stylesheet(&fm);
+
+        ss.visit_mut_with(&mut Compiler::new(Config {
+            process: Features::CASCADE_LAYERS,
+        }));
+
+        let s = print_stylesheet(&ss);
+
+        NormalizedOutput::from(s).compare_to_file(&output).unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[testing::fixture("tests/selector-is/**/*.css", exclude("expect.css"))]
+fn test_selector_is(input: PathBuf) {
+    let output = input.with_extension("expect.css");
+
+    testing::run_test(false, |cm, _| {
+        let fm = cm.load_file(&input).unwrap();
+        let mut ss = parse_stylesheet(&fm);
+
+        ss.visit_mut_with(&mut Compiler::new(Config {
+            process: Features::SELECTOR_IS,
+        }));
+
+        let s = print_stylesheet(&ss);
+
+        NormalizedOutput::from(s).compare_to_file(&output).unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
+
 #[testing::fixture("tests/all/**/*.css", exclude("expect.css"))]
 fn test_all(input: PathBuf) {
     let output = input.with_extension("expect.css");
@@ -224,3 +350,27 @@ fn test_all(input: PathBuf) {
     })
     .unwrap();
 }
+
+#[test]
+fn applied_features() {
+    testing::run_test(false, |cm, _| {
+        let fm = cm.new_source_file(
+            FileName::Anon.into(),
+            ".a { margin-inline-start: 0; .b { color: hwb(0 0% 0%); } }",
+        );
+        let mut ss = parse_stylesheet(&fm);
+        let mut compiler = Compiler::new(Config {
+            process: Features::all(),
+        });
+
+        ss.visit_mut_with(&mut compiler);
+
+        assert_eq!(
+            compiler.applied(),
+            Features::NESTING | Features::LOGICAL_PROPERTIES | Features::COLOR_HWB
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,156 @@
+//! Resolution of `composes: a from "./other.css"` across files.
+
+use std::hash::Hash;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_atoms::Atom;
+use swc_common::errors::HANDLER;
+use swc_css_ast::Ident;
+
+use crate::{CssClassName, TransformResult};
+
+/// Replaces [CssClassName::Import] in `results` with the class names of the
+/// imported files, transitively.
+///
+/// `results` is a map from a file to the result of [crate::compile] for it,
+/// and `resolve` returns the file imported by a module specifier in another
+/// file.
+///
+/// Unresolvable files, missing classes and cycles are reported to
+/// [HANDLER](swc_common::errors::HANDLER), and the import is left as is.
+pub fn resolve_composes<K>(
+    results: &mut FxHashMap<K, TransformResult>,
+    mut resolve: impl FnMut(&K, &Atom) -> Option<K>,
+) where
+    K: Clone + Eq + Hash,
+{
+    let mut resolver = Resolver {
+        results,
+        resolve: &mut resolve,
+        resolved: Default::default(),
+        stack: Default::default(),
+    };
+
+    let mut resolved = Vec::new();
+
+    for (file, result) in resolver.results {
+        for class in result.renamed.keys() {
+            resolved.push((
+                file.clone(),
+                class.clone(),
+                resolver.class_names(file, class),
+            ));
+        }
+    }
+
+    for (file, class, class_names) in resolved {
+        if let Some(class_names) = class_names {
+            results
+                .get_mut(&file)
+                .unwrap()
+                .renamed
+                .insert(class, class_names);
+        }
+    }
+}
+
+struct Resolver<'a, K, F>
+where
+    K: Clone + Eq + Hash,
+    F: FnMut(&K, &Atom) -> Option<K>,
+{
+    results: &'a FxHashMap<K, TransformResult>,
+    resolve: &'a mut F,
+    resolved: FxHashMap<(K, Atom), Vec<CssClassName>>,
+    /// Classes being resolved, used to detect cycles.
+    stack: Vec<(K, Atom)>,
+}
+
+impl<K, F> Resolver<'_, K, F>
+where
+    K: Clone + Eq + Hash,
+    F: FnMut(&K, &Atom) -> Option<K>,
+{
+    /// Returns [None] if `class` is not exported by `file`.
+    fn class_names(&mut self, file: &K, class: &Atom) -> Option<Vec<CssClassName>> {
+        let key = (file.clone(), class.clone());
+
+        if let Some(class_names) = self.resolved.get(&key) {
+            return Some(class_names.clone());
+        }
+
+        let class_names = self.results.get(file)?.renamed.get(class)?;
+
+        self.stack.push(key.clone());
+
+        let mut resolved = Vec::with_capacity(class_names.len());
+        let mut seen = FxHashSet::default();
+
+        for class_name in class_names {
+            let CssClassName::Import { name, from } = class_name else {
+                if seen.insert(name_of(class_name).value.clone()) {
+                    resolved.push(class_name.clone());
+                }
+                continue;
+            };
+
+            match self.import(file, name, from) {
+                Some(imported) => {
+                    for class_name in imported {
+                        if seen.insert(name_of(&class_name).value.clone()) {
+                            resolved.push(class_name);
+                        }
+                    }
+                }
+                None => resolved.push(class_name.clone()),
+            }
+        }
+
+        self.stack.pop();
+        self.resolved.insert(key, resolved.clone());
+
+        Some(resolved)
+    }
+
+    fn import(&mut self, file: &K, name: &Ident, from: &Atom) -> Option<Vec<CssClassName>> {
+        let Some(imported) = (self.resolve)(file, from) else {
+            report(name, &format!("failed to resolve `{from}`"));
+            return None;
+        };
+
+        if self
+            .stack
+            .iter()
+            .any(|(file, class)| *file == imported && *class == name.value)
+        {
+            report(
+                name,
+                &format!("`{}` from `{}` is composed cyclically", name.value, from),
+            );
+            return None;
+        }
+
+        let class_names = self.class_names(&imported, &name.value);
+
+        if class_names.is_none() {
+            report(
+                name,
+                &format!("`{}` is not found in `{}`", name.value, from),
+            );
+        }
+
+        class_names
+    }
+}
+
+fn name_of(class_name: &CssClassName) -> &Ident {
+    match class_name {
+        CssClassName::Local { name }
+        | CssClassName::Global { name }
+        | CssClassName::Import { name, .. } => name,
+    }
+}
+
+fn report(name: &Ident, msg: &str) {
+    HANDLER.with(|handler| handler.struct_span_err(name.span, msg).emit());
+}
@@ -0,0 +1,118 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use swc_atoms::Atom;
+
+use crate::{CssClassName, TransformResult};
+
+impl TransformResult {
+    /// Returns the class names as a JSON object, like `{"a":"__a_1 global"}`.
+    ///
+    /// [CssClassName::Import] is skipped, so
+    /// [resolve_composes](crate::composes::resolve_composes) should be called
+    /// first.
+    pub fn to_json(&self) -> String {
+        let map = self
+            .sorted()
+            .into_iter()
+            .map(|(key, class_names)| {
+                let value = class_names
+                    .iter()
+                    .filter_map(|class_name| match class_name {
+                        CssClassName::Local { name } | CssClassName::Global { name } => {
+                            Some(&*name.value)
+                        }
+                        CssClassName::Import { .. } => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                (key, value)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        serde_json::to_string(&map).unwrap()
+    }
+
+    /// Returns an ES module which exports the class names as the default
+    /// export.
+    ///
+    /// [CssClassName::Import] is imported from the module specifier.
+    pub fn to_es_module(&self) -> String {
+        let mut imports = Vec::<&Atom>::new();
+        let mut body = String::new();
+
+        for (key, class_names) in self.sorted() {
+            let mut exprs = Vec::new();
+            let mut names = Vec::new();
+
+            for class_name in class_names {
+                match class_name {
+                    CssClassName::Local { name } | CssClassName::Global { name } => {
+                        names.push(&*name.value);
+                    }
+                    CssClassName::Import { name, from } => {
+                        if !names.is_empty() {
+                            exprs.push(string_literal(&names.join(" ")));
+                            names.clear();
+                        }
+
+                        let idx = match imports.iter().position(|src| *src == from) {
+                            Some(idx) => idx,
+                            None => {
+                                imports.push(from);
+                                imports.len() - 1
+                            }
+                        };
+
+                        exprs.push(format!(
+                            "__swc_css_modules_{}__[{}]",
+                            idx,
+                            string_literal(&name.value)
+                        ));
+                    }
+                }
+            }
+
+            if !names.is_empty() || exprs.is_empty() {
+                exprs.push(string_literal(&names.join(" ")));
+            }
+
+            writeln!(
+                body,
+                "    {}: {},",
+                string_literal(key),
+                exprs.join(" + \" \" + ")
+            )
+            .unwrap();
+        }
+
+        let mut code = String::new();
+
+        for (idx, src) in imports.iter().enumerate() {
+            writeln!(
+                code,
+                "import __swc_css_modules_{}__ from {};",
+                idx,
+                string_literal(src)
+            )
+            .unwrap();
+        }
+
+        code.push_str("export default {\n");
+        code.push_str(&body);
+        code.push_str("};\n");
+
+        code
+    }
+
+    fn sorted(&self) -> Vec<(&Atom, &Vec<CssClassName>)> {
+        let mut entries = self.renamed.iter().collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries
+    }
+}
+
+/// JSON strings are valid JavaScript string literals.
+fn string_literal(s: &str) -> String {
+    serde_json::to_string(s).unwrap()
+}
@@ -1,14 +1,19 @@
 use rustc_hash::FxHashMap;
 use swc_atoms::Atom;
-use swc_common::{Span, util::take::Take};
+use swc_common::{Span, errors::HANDLER, util::take::Take};
 use swc_css_ast::{
-    ComplexSelector, ComplexSelectorChildren, ComponentValue, Declaration, DeclarationName,
-    Delimiter, DelimiterValue, FunctionName, Ident, KeyframesName, PseudoClassSelectorChildren,
-    QualifiedRule, QualifiedRulePrelude, Stylesheet, SubclassSelector,
+    ClassSelector, ComplexSelector, ComplexSelectorChildren, ComponentValue, Declaration,
+    DeclarationName, Delimiter, DelimiterValue, FunctionName, IdSelector, Ident, KeyframesName,
+    PseudoClassSelectorChildren, QualifiedRule, QualifiedRulePrelude, Stylesheet, SubclassSelector,
 };
-use swc_css_visit::{VisitMut, VisitMutWith};
+use swc_css_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
 
+pub use self::pattern::PatternConfig;
+
+pub mod composes;
+mod export;
 pub mod imports;
+mod pattern;
 
 /// Various configurations for the css modules.
 ///
@@ -20,11 +25,34 @@ pub trait TransformConfig {
     /// Creates a class name for the given `local_name`.
     fn new_name_for(&self, local: &Atom) -> Atom;
 
+    /// The scope of selectors which are not wrapped in `:local` or `:global`.
+    fn mode(&self) -> Mode {
+        Mode::Local
+    }
+
     // /// Used for `@value` imports.
     // fn get_value(&self, import_source: &str, value_name: &Atom) ->
     // ComponentValue;
 }
 
+/// The default scope of selectors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Selectors are local unless they are wrapped in `:global`.
+    #[default]
+    Local,
+    /// Selectors are global unless they are wrapped in `:local`.
+    ///
+    /// Animation names are not renamed in this mode.
+    Global,
+    /// Same as [Mode::Local], but each selector must contain at least one
+    /// local class or id.
+    ///
+    /// Selectors violating this are reported to
+    /// [HANDLER](swc_common::errors::HANDLER).
+    Pure,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CssClassName {
     Local {
@@ -50,9 +78,14 @@ pub struct TransformResult {
 
 /// Returns a map from local name to exported name.
 pub fn compile<'a>(ss: &mut Stylesheet, config: impl 'a + TransformConfig) -> TransformResult {
+    let mode = config.mode();
     let mut compiler = Compiler {
         config,
-        data: Default::default(),
+        data: Data {
+            is_global_mode: mode == Mode::Global,
+            ..Default::default()
+        },
+        mode,
         result: TransformResult {
             renamed: Default::default(),
         },
@@ -113,6 +146,7 @@ where
 {
     config: C,
     data: Data,
+    mode: Mode,
     result: TransformResult,
 }
 
@@ -127,6 +161,7 @@ struct Data {
 
     is_global_mode: bool,
     is_in_local_pseudo_class: bool,
+    is_in_qualified_rule: bool,
 }
 
 impl<C> VisitMut for Compiler<C>
@@ -242,8 +277,38 @@ where
 
         self.data.composes_for_current = Some(Default::default());
 
+        let old_is_in_qualified_rule = self.data.is_in_qualified_rule;
+        self.data.is_in_qualified_rule = true;
+
         n.visit_mut_children_with(self);
 
+        self.data.is_in_qualified_rule = old_is_in_qualified_rule;
+
+        // Nested rules are scoped by the parent rule.
+        if self.mode == Mode::Pure && !old_is_in_qualified_rule {
+            if let QualifiedRulePrelude::SelectorList(sel) = &n.prelude {
+                for complex in &sel.children {
+                    let mut finder = LocalFinder {
+                        renamed_to_orig: &self.data.renamed_to_orig,
+                        found: false,
+                    };
+                    complex.visit_with(&mut finder);
+
+                    if !finder.found {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(
+                                    complex.span,
+                                    "Selector is not pure (pure selectors must contain at least \
+                                     one local class or id)",
+                                )
+                                .emit()
+                        });
+                    }
+                }
+            }
+        }
+
         if let QualifiedRulePrelude::SelectorList(sel) = &n.prelude {
             let composes = self.data.composes_for_current.take();
 
@@ -356,6 +421,10 @@ where
             }
         }
 
+        if self.data.is_global_mode {
+            return;
+        }
+
         if let DeclarationName::Ident(name) = &n.name {
             match &*name.value {
                 "animation" => {
@@ -613,6 +682,22 @@ where
     }
 }
 
+/// Finds a class or id renamed by the [Compiler].
+struct LocalFinder<'a> {
+    renamed_to_orig: &'a FxHashMap<Atom, Atom>,
+    found: bool,
+}
+
+impl Visit for LocalFinder<'_> {
+    fn visit_class_selector(&mut self, n: &ClassSelector) {
+        self.found |= self.renamed_to_orig.contains_key(&n.text.value);
+    }
+
+    fn visit_id_selector(&mut self, n: &IdSelector) {
+        self.found |= self.renamed_to_orig.contains_key(&n.text.value);
+    }
+}
+
 fn rename<C>(
     span: Span,
     config: &mut C,
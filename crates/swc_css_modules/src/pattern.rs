@@ -0,0 +1,115 @@
+use base64::prelude::{BASE64_URL_SAFE_NO_PAD, Engine};
+use sha1::{Digest, Sha1};
+use swc_atoms::Atom;
+
+use crate::{Mode, TransformConfig};
+
+/// A [TransformConfig] which creates class names from a pattern, like
+/// `[name]__[local]__[hash:5]`.
+///
+/// The pattern can contain
+///
+///  - `[local]`: The original class name.
+///  - `[name]`: The name of the file, without the extension.
+///  - `[ext]`: The extension of the file, without the leading dot.
+///  - `[hash]` or `[hash:<length>]`: A hash of the path of the file and the
+///    original class name.
+///
+/// Characters which are not valid in a class name are replaced with `-`, and
+/// `_` is prepended if the name would start with a digit.
+#[derive(Debug, Clone)]
+pub struct PatternConfig {
+    pub pattern: String,
+
+    /// The path of the file, used for `[name]`, `[ext]` and `[hash]`.
+    ///
+    /// This should be relative to the project root, so the hashes do not
+    /// depend on the machine.
+    pub path: String,
+
+    pub mode: Mode,
+}
+
+impl TransformConfig for PatternConfig {
+    fn new_name_for(&self, local: &Atom) -> Atom {
+        let file_name = self.path.rsplit(['/', '\\']).next().unwrap_or_default();
+        let (name, ext) = match file_name.rsplit_once('.') {
+            Some((name, ext)) if !name.is_empty() => (name, ext),
+            _ => (file_name, ""),
+        };
+
+        let mut buf = String::with_capacity(self.pattern.len() + local.len());
+        let mut rest = &*self.pattern;
+
+        while let Some(start) = rest.find('[') {
+            buf.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let Some(end) = rest.find(']') else {
+                break;
+            };
+
+            let placeholder = &rest[1..end];
+
+            match placeholder {
+                "local" => buf.push_str(local),
+                "name" => buf.push_str(name),
+                "ext" => buf.push_str(ext),
+                _ => match placeholder
+                    .strip_prefix("hash")
+                    .map(|len| len.strip_prefix(':').map(str::parse::<usize>))
+                {
+                    Some(None) => buf.push_str(&hash(&self.path, local)),
+                    Some(Some(Ok(len))) => {
+                        let hash = hash(&self.path, local);
+                        buf.push_str(&hash[..len.min(hash.len())]);
+                    }
+                    _ => buf.push_str(&rest[..=end]),
+                },
+            }
+
+            rest = &rest[end + 1..];
+        }
+        buf.push_str(rest);
+
+        escape(&buf).into()
+    }
+
+    fn mode(&self) -> Mode {
+        self.mode
+    }
+}
+
+fn hash(path: &str, local: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(path.as_bytes());
+    hasher.update([0]);
+    hasher.update(local.as_bytes());
+
+    BASE64_URL_SAFE_NO_PAD.encode(hasher.finalize())
+}
+
+fn escape(name: &str) -> String {
+    let mut buf = String::with_capacity(name.len() + 1);
+
+    let mut chars = name.chars();
+    if let Some(first) = chars.next() {
+        let second = chars.next();
+
+        if first.is_ascii_digit()
+            || (first == '-' && second.is_some_and(|c| c.is_ascii_digit() || c == '-'))
+        {
+            buf.push('_');
+        }
+    }
+
+    buf.extend(name.chars().map(|c| {
+        if c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() {
+            c
+        } else {
+            '-'
+        }
+    }));
+
+    buf
+}
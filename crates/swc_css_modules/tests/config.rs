@@ -0,0 +1,244 @@
+use rustc_hash::FxHashMap;
+use swc_atoms::Atom;
+use swc_common::{FileName, SourceMap, sync::Lrc};
+use swc_css_codegen::{
+    CodeGenerator, CodegenConfig, Emit,
+    writer::basic::{BasicCssWriter, BasicCssWriterConfig},
+};
+use swc_css_modules::{Mode, PatternConfig, TransformConfig, TransformResult};
+use swc_css_parser::parser::ParserConfig;
+
+struct TestConfig {
+    mode: Mode,
+}
+
+impl TransformConfig for TestConfig {
+    fn new_name_for(&self, local: &Atom) -> Atom {
+        format!("__local__{local}").into()
+    }
+
+    fn mode(&self) -> Mode {
+        self.mode
+    }
+}
+
+fn compile(
+    cm: &Lrc<SourceMap>,
+    path: &str,
+    src: &str,
+    config: impl TransformConfig,
+) -> (String, TransformResult) {
+    let fm = cm.new_source_file(FileName::Real(path.into()).into(), src.to_string());
+
+    let mut ss = swc_css_parser::parse_file(
+        &fm,
+        None,
+        ParserConfig {
+            css_modules: true,
+            ..Default::default()
+        },
+        &mut Vec::new(),
+    )
+    .unwrap();
+
+    let result = swc_css_modules::compile(&mut ss, config);
+
+    let mut buf = String::new();
+    {
+        let wr = BasicCssWriter::new(&mut buf, None, BasicCssWriterConfig::default());
+        let mut g = CodeGenerator::new(wr, CodegenConfig { minify: true });
+
+        g.emit(&ss).unwrap();
+    }
+
+    (buf, result)
+}
+
+#[test]
+fn global_mode() {
+    testing::run_test(false, |cm, _| {
+        let (css, result) = compile(
+            &cm,
+            "a.css",
+            ".a { animation: fade 1s } :local(.b) .c {} :local .d .e {} @keyframes fade {}",
+            TestConfig { mode: Mode::Global },
+        );
+
+        assert_eq!(
+            css,
+            ".a{animation:fade 1s}.__local__b .c{} .__local__d .__local__e{}@keyframes fade{}"
+        );
+        assert_eq!(
+            result.to_json(),
+            r#"{"b":"__local__b","d":"__local__d","e":"__local__e"}"#
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn pure_mode() {
+    let output = testing::run_test(false, |cm, _| -> Result<(), ()> {
+        let (css, _) = compile(
+            &cm,
+            "a.css",
+            ".a {} h1 .b {} :global(.c) .d {} .e { & h1 {} }\nh1 {}\n:global(.f) {}\n.g, \
+             :global .h {}",
+            TestConfig { mode: Mode::Pure },
+        );
+
+        assert_eq!(
+            css,
+            ".__local__a{}h1 .__local__b{}.c .__local__d{}.__local__e{& h1{}}h1{}.f{}.__local__g, .\
+             h{}"
+        );
+
+        Err(())
+    })
+    .unwrap_err();
+
+    assert_eq!(
+        output
+            .lines()
+            .filter(|line| line.contains("is not pure"))
+            .count(),
+        3,
+        "{output}"
+    );
+}
+
+#[test]
+fn pattern() {
+    let config = |pattern: &str, path: &str| PatternConfig {
+        pattern: pattern.into(),
+        path: path.into(),
+        mode: Mode::Local,
+    };
+
+    let name = config("[name]__[local]__[hash:5]", "src/button.module.css")
+        .new_name_for(&"primary".into());
+    assert!(name.starts_with("button-module__primary__"), "{name}");
+    assert_eq!(name.len(), "button-module__primary__".len() + 5);
+
+    // The hash depends on the path and the class name.
+    assert_eq!(
+        name,
+        config("[name]__[local]__[hash:5]", "src/button.module.css")
+            .new_name_for(&"primary".into())
+    );
+    assert_ne!(
+        name,
+        config("[name]__[local]__[hash:5]", "src/other/button.module.css")
+            .new_name_for(&"primary".into())
+    );
+    assert_ne!(
+        name,
+        config("[name]__[local]__[hash:5]", "src/button.module.css")
+            .new_name_for(&"secondary".into())
+    );
+
+    assert_eq!(
+        config("[local]-[ext]", "a.css").new_name_for(&"b".into()),
+        "b-css"
+    );
+    assert_eq!(
+        config("[name]_[local]", "1.css").new_name_for(&"b".into()),
+        "_1_b"
+    );
+    assert_eq!(
+        config("[unknown]", "a.css").new_name_for(&"b".into()),
+        "-unknown-"
+    );
+    assert!(
+        config("[hash]", "a.css")
+            .new_name_for(&"b".into())
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    );
+}
+
+#[test]
+fn resolve_composes() {
+    testing::run_test(false, |cm, _| {
+        let mut results = FxHashMap::default();
+
+        for (path, src) in [
+            (
+                "a.css",
+                ".a { composes: b from './b.css'; composes: g from global }",
+            ),
+            (
+                "b.css",
+                ".b { composes: c; composes: c from './c.css' } .c {}",
+            ),
+            ("c.css", ".c {}"),
+        ] {
+            let (_, result) = compile(&cm, path, src, TestConfig { mode: Mode::Local });
+            results.insert(path, result);
+        }
+
+        assert_eq!(
+            results["a.css"].to_es_module(),
+            "import __swc_css_modules_0__ from \"./b.css\";\nexport default {\n    \"a\": \
+             \"__local__a g\" + \" \" + __swc_css_modules_0__[\"b\"],\n};\n"
+        );
+
+        swc_css_modules::composes::resolve_composes(&mut results, |_, src| {
+            ["a.css", "b.css", "c.css"]
+                .into_iter()
+                .find(|path| src.ends_with(path))
+        });
+
+        assert_eq!(
+            results["a.css"].to_json(),
+            r#"{"a":"__local__a g __local__b __local__c"}"#
+        );
+        assert_eq!(
+            results["b.css"].to_es_module(),
+            "export default {\n    \"b\": \"__local__b __local__c\",\n    \"c\": \
+             \"__local__c\",\n};\n"
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn resolve_composes_errors() {
+    let output = testing::run_test(false, |cm, _| -> Result<(), ()> {
+        let mut results = FxHashMap::default();
+
+        for (path, src) in [
+            (
+                "a.css",
+                ".a { composes: b from './b.css'; composes: x from './x.css' }",
+            ),
+            (
+                "b.css",
+                ".b { composes: a from './a.css'; composes: missing from './a.css' }",
+            ),
+        ] {
+            let (_, result) = compile(&cm, path, src, TestConfig { mode: Mode::Local });
+            results.insert(path, result);
+        }
+
+        swc_css_modules::composes::resolve_composes(&mut results, |_, src| {
+            ["a.css", "b.css"]
+                .into_iter()
+                .find(|path| src.ends_with(path))
+        });
+
+        Err(())
+    })
+    .unwrap_err();
+
+    assert!(output.contains("failed to resolve `./x.css`"), "{output}");
+    assert!(output.contains("is composed cyclically"), "{output}");
+    assert!(
+        output.contains("`missing` is not found in `./a.css`"),
+        "{output}"
+    );
+}
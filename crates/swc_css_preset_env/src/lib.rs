@@ -0,0 +1,147 @@
+//! Compiles modern CSS for the browsers of a `browserslist` query, by running
+//! the compat compilers of `swc_css_compat` and the prefixer of
+//! `swc_css_prefixer` only where the targets need them.
+#![deny(clippy::all)]
+
+use std::{path::PathBuf, sync::Arc};
+
+use preset_env_base::query::targets_to_versions;
+pub use preset_env_base::{
+    BrowserData, Versions,
+    query::{Query, Targets},
+    version::Version,
+};
+use serde::Deserialize;
+use swc_css_ast::Stylesheet;
+use swc_css_compat::compiler::{self, Compiler};
+pub use swc_css_compat::feature::Features;
+use swc_css_prefixer::{options::Options, prefixer};
+use swc_css_visit::{VisitMut, VisitMutWith};
+
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The `browserslist` targets. If omitted, the `browserslist` config found
+    /// from [Config::path] is used.
+    #[serde(default)]
+    pub targets: Option<Targets>,
+
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+
+    /// Prints the targets and the features lowered in each stylesheet, like
+    /// the `debug` option of `swc_ecma_preset_env`.
+    #[serde(default)]
+    pub debug: bool,
+}
+
+/// [Config] with resolved targets.
+#[derive(Debug, Clone)]
+pub struct EnvConfig {
+    targets: Arc<Versions>,
+    /// True if the `browserslist` query returned an empty result (unknown
+    /// browser version), in which case the targets are assumed to support
+    /// everything.
+    unknown_version: bool,
+    features: Features,
+    debug: bool,
+}
+
+impl EnvConfig {
+    /// The resolved browser versions.
+    pub fn targets(&self) -> &Versions {
+        &self.targets
+    }
+
+    /// The features processed by [preset_env] for the targets.
+    pub fn features(&self) -> Features {
+        self.features
+    }
+
+    /// Returns whether vendor prefixes are added.
+    pub fn prefix(&self) -> bool {
+        !self.unknown_version
+    }
+}
+
+impl From<Config> for EnvConfig {
+    fn from(config: Config) -> Self {
+        let target_info =
+            targets_to_versions(config.targets, config.path).expect("failed to parse targets");
+
+        let features = if target_info.unknown_version {
+            Features::empty()
+        } else {
+            Features::from_targets(&target_info.versions)
+        };
+
+        Self {
+            targets: target_info.versions,
+            unknown_version: target_info.unknown_version,
+            features,
+            debug: config.debug,
+        }
+    }
+}
+
+/// Returns the names of the features, e.g. `color-mix` for
+/// [Features::COLOR_MIX].
+pub fn feature_names(features: Features) -> impl Iterator<Item = String> {
+    features.iter_names().map(|(name, _)| feature_name(name))
+}
+
+fn feature_name(name: &str) -> String {
+    name.to_ascii_lowercase().replace('_', "-")
+}
+
+pub fn preset_env(env_config: EnvConfig) -> impl VisitMut {
+    PresetEnv {
+        compiler: Compiler::new(compiler::Config {
+            process: env_config.features,
+        }),
+        prefixer: env_config.prefix().then(|| {
+            prefixer(Options {
+                env: Some(Targets::Versions(*env_config.targets)),
+            })
+        }),
+        debug: env_config.debug.then_some(env_config.targets),
+    }
+}
+
+struct PresetEnv<P>
+where
+    P: VisitMut,
+{
+    compiler: Compiler,
+    prefixer: Option<P>,
+    /// The targets to print if `debug` is enabled
+    debug: Option<Arc<Versions>>,
+}
+
+impl<P> VisitMut for PresetEnv<P>
+where
+    P: VisitMut,
+{
+    fn visit_mut_stylesheet(&mut self, n: &mut Stylesheet) {
+        // Prefixes are added to the lowered styles
+        n.visit_mut_with(&mut self.compiler);
+
+        if let Some(prefixer) = &mut self.prefixer {
+            n.visit_mut_with(prefixer);
+        }
+
+        if let Some(targets) = &self.debug {
+            println!("Targets: {targets:?}");
+
+            // Only the features which changed the stylesheet are reported
+            let applied = self.compiler.applied();
+
+            for (name, feature) in Features::all().iter_names() {
+                println!("{}: {:?}", feature_name(name), applied.contains(feature));
+            }
+
+            println!("prefixer: {:?}", self.prefixer.is_some());
+        }
+    }
+}
@@ -0,0 +1,38 @@
+#![deny(warnings)]
+
+use swc_css_preset_env::{Config, EnvConfig, Features, Query, Targets, feature_names};
+
+fn env_config(query: &str) -> EnvConfig {
+    Config {
+        targets: Some(Targets::Query(Query::Single(query.into()))),
+        ..Default::default()
+    }
+    .into()
+}
+
+#[test]
+fn old_browsers_lower_all_features() {
+    let env_config = env_config("chrome 40");
+
+    assert_eq!(env_config.features(), Features::all());
+    assert!(env_config.prefix());
+}
+
+#[test]
+fn recent_browsers_lower_unsupported_features() {
+    let env_config = env_config("chrome 130, firefox 130");
+
+    assert_eq!(env_config.features(), Features::CUSTOM_MEDIA);
+    assert_eq!(
+        feature_names(env_config.features()).collect::<Vec<_>>(),
+        vec!["custom-media"]
+    );
+}
+
+#[test]
+fn unknown_versions_lower_nothing() {
+    let env_config = env_config("chrome > 9999");
+
+    assert_eq!(env_config.features(), Features::empty());
+    assert!(!env_config.prefix());
+}
@@ -0,0 +1,48 @@
+#![deny(warnings)]
+
+use std::{fs, path::PathBuf};
+
+use swc_css_ast::Stylesheet;
+use swc_css_codegen::{
+    CodegenConfig, Emit,
+    writer::basic::{BasicCssWriter, BasicCssWriterConfig},
+};
+use swc_css_parser::{parse_file, parser::ParserConfig};
+use swc_css_preset_env::{Config, preset_env};
+use swc_css_visit::VisitMutWith;
+use testing::NormalizedOutput;
+
+#[testing::fixture("tests/fixture/**/input.css")]
+fn fixture(input: PathBuf) {
+    let parent = input.parent().unwrap();
+    let output = parent.join("output.css");
+    let config: Config =
+        serde_json::from_str(&fs::read_to_string(parent.join("config.json")).unwrap()).unwrap();
+
+    testing::run_test2(false, |cm, handler| {
+        let fm = cm.load_file(&input).unwrap();
+        let mut errors = Vec::new();
+        let mut ss: Stylesheet =
+            parse_file(&fm, None, ParserConfig::default(), &mut errors).unwrap();
+
+        for err in errors {
+            err.to_diagnostics(&handler).emit();
+        }
+
+        ss.visit_mut_with(&mut preset_env(config.into()));
+
+        let mut s = String::new();
+        {
+            let mut wr = BasicCssWriter::new(&mut s, None, BasicCssWriterConfig::default());
+            let mut r#gen =
+                swc_css_codegen::CodeGenerator::new(&mut wr, CodegenConfig { minify: false });
+
+            r#gen.emit(&ss).unwrap();
+        }
+
+        NormalizedOutput::from(s).compare_to_file(&output).unwrap();
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -1,8 +1,8 @@
-use swc_common::{DUMMY_SP, Mark, util::take::Take};
+use swc_common::{DUMMY_SP, Mark, SyntaxContext, util::take::Take};
 use swc_ecma_ast::*;
-use swc_ecma_compat_es2015::arrow;
+use swc_ecma_transformer::lower_arrows;
 use swc_ecma_utils::prepend_stmt;
-use swc_ecma_visit::{Fold, FoldWith, InjectVars, VisitMutWith, fold_pass, standard_only_fold};
+use swc_ecma_visit::{Fold, FoldWith, fold_pass, standard_only_fold};
 use swc_trace_macro::swc_trace;
 
 /// A bugfix pass for Safari 10.3.
@@ -53,9 +53,10 @@ impl Fold for AsyncArrowsInClass {
         match n {
             Expr::Arrow(ref a) => {
                 if a.is_async {
-                    let mut v = arrow(self.unresolved_mark);
-                    n.visit_mut_with(&mut v);
-                    self.vars.extend(v.take_vars());
+                    self.vars.extend(lower_arrows(
+                        &mut n,
+                        SyntaxContext::empty().apply_mark(self.unresolved_mark),
+                    ));
                     n
                 } else {
                     n
@@ -0,0 +1,22 @@
+use swc_common::{Mark, SyntaxContext};
+use swc_ecma_ast::Pass;
+
+///
+///
+/// TODO(kdy1): Optimization
+///
+/// ```js
+/// let functions = [];
+/// for (let i = 0; i < 10; i++) {
+///    functions.push(function() {
+///        let i = 1;
+///        console.log(i);
+///    });
+/// }
+/// ```
+pub fn block_scoping(unresolved_mark: Mark) -> impl Pass {
+    let mut options = swc_ecma_transformer::Options::default();
+    options.unresolved_ctxt = SyntaxContext::empty().apply_mark(unresolved_mark);
+    options.env.es2015.block_scoping = true;
+    options.into_pass()
+}
@@ -0,0 +1,56 @@
+use serde::Deserialize;
+use swc_ecma_ast::Pass;
+
+/// `@babel/plugin-transform-classes`
+///
+/// # In
+/// ```js
+/// class Test {
+///   constructor(name) {
+///     this.name = name;
+///   }
+///
+///   logger () {
+///     console.log("Hello", this.name);
+///   }
+/// }
+/// ```
+///
+/// # Out
+/// ```js
+/// var Test = function () {
+///   function Test(name) {
+///     _class_call_check(this, Test);
+///
+///     this.name = name;
+///   }
+///
+///   Test.prototype.logger = function logger() {
+///     console.log("Hello", this.name);
+///   };
+///
+///   return Test;
+/// }();
+/// ```
+pub fn classes(config: Config) -> impl Pass {
+    let mut options = swc_ecma_transformer::Options::default();
+    options.assumptions.constant_super = config.constant_super;
+    options.assumptions.no_class_calls = config.no_class_calls;
+    options.assumptions.set_class_methods = config.set_class_methods;
+    options.assumptions.super_is_callable_constructor = config.super_is_callable_constructor;
+    options.env.es2015.classes = true;
+    options.into_pass()
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    #[serde(default)]
+    pub constant_super: bool,
+    #[serde(default)]
+    pub no_class_calls: bool,
+    #[serde(default)]
+    pub set_class_methods: bool,
+    #[serde(default)]
+    pub super_is_callable_constructor: bool,
+}
@@ -2,7 +2,7 @@
 #![allow(clippy::boxed_local)]
 
 use serde::Deserialize;
-use swc_common::{Mark, comments::Comments};
+use swc_common::{Mark, SyntaxContext, comments::Comments};
 use swc_ecma_ast::Pass;
 
 pub use self::{
@@ -44,45 +44,54 @@ pub mod typeof_symbol;
 /// Used to generate `require` calls.
 /// See the documentation of [regenerator](self::regenerator::regenerator) for
 /// more details.
-pub fn es2015<C>(unresolved_mark: Mark, comments: Option<C>, c: Config) -> impl Pass
+///
+/// ## `comments`
+///
+/// Unused, because none of the ES2015 transforms add comments.
+pub fn es2015<C>(unresolved_mark: Mark, _comments: Option<C>, c: Config) -> impl Pass
 where
     C: Comments + Clone,
 {
     let mut options = swc_ecma_transformer::Options::default();
+    options.unresolved_ctxt = SyntaxContext::empty().apply_mark(unresolved_mark);
+
+    options.assumptions.constant_super = c.classes.constant_super;
+    options.assumptions.no_class_calls = c.classes.no_class_calls;
+    options.assumptions.set_class_methods = c.classes.set_class_methods;
+    options.assumptions.super_is_callable_constructor = c.classes.super_is_callable_constructor;
+    options.assumptions.ignore_to_primitive_hint = c.template_literal.ignore_to_primitive;
+    options.assumptions.mutable_template_object = c.template_literal.mutable_template;
+    options.assumptions.ignore_function_length = c.parameters.ignore_function_length;
+
     options.env.regexp.sticky_regex = true;
-    options.env.es2015.shorthand = true;
-    options.env.es2015.instanceof = true;
-    options.env.es2015.duplicate_keys = true;
-    options.env.es2015.typeof_symbol = true;
 
-    (
-        (
-            block_scoped_functions(),
-            template_literal(c.template_literal),
-            classes(c.classes),
-            new_target(),
-            spread(c.spread, unresolved_mark),
-        ),
-        // https://github.com/Microsoft/TypeScript/issues/5441
-        if !c.typescript {
-            Some(object_super())
-        } else {
-            None
-        },
-        function_name(),
-        for_of(c.for_of),
-        // Should come before parameters
-        // See: https://github.com/swc-project/swc/issues/1036
-        parameters(c.parameters, unresolved_mark),
-        (
-            arrow(unresolved_mark),
-            computed_properties(c.computed_props),
-            destructuring(c.destructuring),
-            block_scoping(unresolved_mark),
-            generator::generator(unresolved_mark, comments.clone()),
-        ),
-        options.into_pass(),
-    )
+    let es2015 = &mut options.env.es2015;
+    es2015.block_scoped_fn = true;
+    es2015.template_literal = true;
+    es2015.classes = true;
+    es2015.new_target = true;
+    es2015.spread = true;
+    es2015.spread_loose = c.spread.loose;
+    // https://github.com/Microsoft/TypeScript/issues/5441
+    es2015.object_super = !c.typescript;
+    es2015.function_name = true;
+    es2015.for_of = true;
+    es2015.for_of_loose = c.for_of.loose;
+    es2015.for_of_assume_array = c.for_of.assume_array;
+    es2015.parameters = true;
+    es2015.arrow = true;
+    es2015.computed_props = true;
+    es2015.computed_props_loose = c.computed_props.loose;
+    es2015.destructuring = true;
+    es2015.destructuring_loose = c.destructuring.loose;
+    es2015.block_scoping = true;
+    es2015.generator = true;
+    es2015.shorthand = true;
+    es2015.instanceof = true;
+    es2015.duplicate_keys = true;
+    es2015.typeof_symbol = true;
+
+    options.into_pass()
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
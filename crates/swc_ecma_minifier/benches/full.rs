@@ -191,6 +191,7 @@ fn run(src: &str) {
                 unresolved_mark,
                 top_level_mark,
                 mangle_name_cache: None,
+                size_reporter: None,
             },
         );
 
@@ -61,6 +61,7 @@ fn main() {
                 unresolved_mark,
                 top_level_mark,
                 mangle_name_cache: None,
+                size_reporter: None,
             },
         );
 
@@ -62,6 +62,7 @@ fn main() {
                     top_level_mark,
                     // Mangle name cache example. You may not need this.
                     mangle_name_cache: Some(Arc::new(SimpleMangleCache::default())),
+                    size_reporter: None,
                 },
             );
 
@@ -127,6 +127,7 @@ fn minify_all(files: &[PathBuf]) {
                         unresolved_mark,
                         top_level_mark,
                         mangle_name_cache: None,
+                        size_reporter: None,
                     },
                 );
 
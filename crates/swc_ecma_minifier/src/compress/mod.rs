@@ -24,6 +24,7 @@ use crate::{
     mode::Mode,
     option::{CompressOptions, MangleOptions},
     program_data::analyze,
+    size_report::{SizeReporter, phase},
     util::force_dump_program,
 };
 
@@ -37,6 +38,7 @@ pub(crate) fn compressor<'a, M>(
     options: &'a CompressOptions,
     mangle_options: Option<&'a MangleOptions>,
     mode: &'a M,
+    size_reporter: Option<&'a SizeReporter>,
 ) -> impl 'a + Pass
 where
     M: Mode,
@@ -49,6 +51,7 @@ where
         pass: 1,
         mode,
         static_alias_state: Default::default(),
+        size_reporter,
     }
 }
 
@@ -63,6 +66,8 @@ struct Compressor<'a> {
 
     /// State for static alias optimization, shared across passes.
     static_alias_state: StaticAliasState,
+
+    size_reporter: Option<&'a SizeReporter>,
 }
 
 impl CompilerPass for Compressor<'_> {
@@ -95,7 +100,9 @@ impl Compressor<'_> {
                 },
                 &data,
             );
-            n.visit_mut_with(&mut v);
+            phase(self.size_reporter, "hoist_decls", n, |n| {
+                n.visit_mut_with(&mut v)
+            });
             self.changed |= v.changed();
         }
 
@@ -155,7 +162,9 @@ impl Compressor<'_> {
                     enable_join_vars: self.pass > 1,
                 },
             );
-            n.visit_mut_with(&mut visitor);
+            phase(self.size_reporter, "pure", n, |n| {
+                n.visit_mut_with(&mut visitor)
+            });
 
             self.changed |= visitor.changed();
 
@@ -191,7 +200,9 @@ impl Compressor<'_> {
                 self.mode,
                 &mut self.static_alias_state,
             );
-            n.visit_mut_with(&mut visitor);
+            phase(self.size_reporter, "optimize", n, |n| {
+                n.visit_mut_with(&mut visitor)
+            });
 
             self.changed |= visitor.changed();
 
@@ -33,6 +33,7 @@ use crate::{
     mode::Mode,
     option::{CompressOptions, MangleOptions},
     program_data::{ProgramData, ScopeData, VarUsageInfoFlags},
+    size_report::enter_item,
     util::{ExprOptExt, ModuleItemExt, contains_leaping_continue_with_label, make_number},
 };
 
@@ -490,17 +491,27 @@ impl Optimizer<'_> {
                 }
             }
 
+            let is_top_level = self.ctx.bit_ctx.contains(BitCtx::TopLevel);
+
             let mut new = Vec::with_capacity(stmts.len() * 11 / 10);
             for (i, mut stmt) in stmts.take().into_iter().enumerate() {
                 // debug_assert_eq!(self.prepend_stmts, Vec::new());
                 // debug_assert_eq!(self.append_stmts, Vec::new());
 
-                if i < directive_count {
-                    // Don't set in_strict for directive itself.
-                    stmt.visit_mut_with(self);
+                let mut visit = |stmt: &mut T| {
+                    if i < directive_count {
+                        // Don't set in_strict for directive itself.
+                        stmt.visit_mut_with(&mut *self);
+                    } else {
+                        let child_optimizer = &mut *self.with_ctx(child_ctx.clone());
+                        stmt.visit_mut_with(child_optimizer);
+                    }
+                };
+
+                if is_top_level {
+                    enter_item(&mut stmt, visit);
                 } else {
-                    let child_optimizer = &mut *self.with_ctx(child_ctx.clone());
-                    stmt.visit_mut_with(child_optimizer);
+                    visit(&mut stmt);
                 }
 
                 #[cfg(debug_assertions)]
@@ -13,7 +13,13 @@ use swc_ecma_visit::{VisitMut, VisitMutWith, VisitWith, noop_visit_mut_type};
 use tracing::Level;
 
 use self::{ctx::Ctx, misc::DropOpts};
-use crate::{debug::AssertValid, maybe_par, option::CompressOptions, util::ModuleItemExt};
+use crate::{
+    debug::AssertValid,
+    maybe_par,
+    option::CompressOptions,
+    size_report::{self, Scope, enter_item},
+    util::ModuleItemExt,
+};
 
 mod arrows;
 mod bools;
@@ -174,6 +180,15 @@ impl Pure<'_> {
     where
         N: for<'aa> VisitMutWith<Pure<'aa>> + Send + Sync,
     {
+        if size_report::is_enabled() {
+            let scope = Scope::current();
+
+            self.maybe_par(cpu_count() * threshold_multiplier, nodes, |v, node| {
+                Scope::enter(scope.as_ref(), || node.visit_mut_with(v));
+            });
+            return;
+        }
+
         self.maybe_par(cpu_count() * threshold_multiplier, nodes, |v, node| {
             node.visit_mut_with(v);
         });
@@ -183,10 +198,34 @@ impl Pure<'_> {
     where
         N: for<'aa> VisitMutWith<Pure<'aa>> + Send + Sync,
     {
+        if size_report::is_enabled() {
+            let scope = Scope::current();
+
+            self.maybe_par(0, nodes, |v, node| {
+                Scope::enter(scope.as_ref(), || node.visit_mut_with(v));
+            });
+            return;
+        }
+
         self.maybe_par(0, nodes, |v, node| {
             node.visit_mut_with(v);
         });
     }
+
+    /// Same as `visit_par(1, nodes)`, but the changes are attributed to each
+    /// top-level item for [SizeReporter](crate::size_report::SizeReporter).
+    fn visit_items_par<N>(&mut self, nodes: &mut Vec<N>)
+    where
+        N: for<'aa> VisitMutWith<Pure<'aa>> + ModuleItemExt + Send + Sync,
+    {
+        let scope = Scope::current();
+
+        self.maybe_par(cpu_count(), nodes, |v, node| {
+            Scope::enter(scope.as_ref(), || {
+                enter_item(node, |node| node.visit_mut_with(v))
+            });
+        });
+    }
 }
 
 impl VisitMut for Pure<'_> {
@@ -796,7 +835,11 @@ impl VisitMut for Pure<'_> {
     }
 
     fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
-        self.visit_par(1, items);
+        if size_report::is_enabled() {
+            self.visit_items_par(items);
+        } else {
+            self.visit_par(1, items);
+        }
 
         self.handle_stmt_likes(items);
     }
@@ -1115,7 +1158,13 @@ impl VisitMut for Pure<'_> {
             }
         }
 
-        self.visit_par(1, items);
+        // Nested statements are already in a top-level item, so this only
+        // attributes the top-level statements of scripts.
+        if size_report::is_enabled() {
+            self.visit_items_par(items);
+        } else {
+            self.visit_par(1, items);
+        }
 
         self.handle_stmt_likes(items);
 
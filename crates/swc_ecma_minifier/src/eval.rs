@@ -92,6 +92,7 @@ impl Evaluator {
                 },
                 None,
                 &data,
+                None,
             ));
         }
     }
@@ -59,6 +59,20 @@ pub struct JsMinifyOptions {
 
     #[serde(default = "true_by_default")]
     pub emit_source_map_columns: bool,
+
+    /// Path to a file used to keep mangled names stable across invocations,
+    /// like `nameCache` of terser.
+    ///
+    /// See [FileMangleCache](crate::option::FileMangleCache).
+    #[serde(default)]
+    pub name_cache: Option<String>,
+
+    /// If true, a report of the size changes made by each pass of the
+    /// minifier is emitted as `sizeReport` of the output.
+    ///
+    /// See [SizeReporter](crate::size_report::SizeReporter).
+    #[serde(default)]
+    pub size_report: bool,
 }
 
 fn true_by_default() -> bool {
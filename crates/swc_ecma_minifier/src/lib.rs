@@ -58,6 +58,7 @@ use crate::{
         merge_exports::merge_exports, postcompress::postcompress_optimizer,
     },
     // program_data::ModuleInfo,
+    size_report::phase,
     timing::Timings,
     util::base54::CharFreq,
 };
@@ -75,6 +76,7 @@ mod mode;
 pub mod option;
 mod pass;
 mod program_data;
+pub mod size_report;
 pub mod timing;
 mod util;
 
@@ -98,6 +100,8 @@ pub fn optimize(
 ) -> Program {
     let _timer = timer!("minify");
 
+    let reporter = extra.size_reporter.as_deref();
+
     let mut marks = Marks::new();
     marks.top_level_ctxt = SyntaxContext::empty().apply_mark(extra.top_level_mark);
     marks.unresolved_mark = extra.unresolved_mark;
@@ -114,11 +118,13 @@ pub fn optimize(
 
         if !defs.is_empty() {
             let defs = defs.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-            n.visit_mut_with(&mut global_defs::globals_defs(
-                defs,
-                extra.unresolved_mark,
-                extra.top_level_mark,
-            ));
+            phase(reporter, "global_defs", &mut n, |n| {
+                n.visit_mut_with(&mut global_defs::globals_defs(
+                    defs,
+                    extra.unresolved_mark,
+                    extra.top_level_mark,
+                ))
+            });
         }
     }
 
@@ -157,31 +163,34 @@ pub fn optimize(
         {
             let _timer = timer!("compress ast");
 
-            perform_dce(&mut n, c, marks);
+            phase(reporter, "dce", &mut n, |n| perform_dce(n, c, marks));
 
             n.mutate(&mut compressor(
                 marks,
                 c,
                 options.mangle.as_ref(),
                 &Minification,
+                reporter,
             ));
 
-            perform_dce(&mut n, c, marks);
+            phase(reporter, "dce", &mut n, |n| perform_dce(n, c, marks));
         }
 
         // Again, we don't need to validate ast
 
         let _timer = timer!("postcompress");
 
-        postcompress_optimizer(&mut n, c);
+        phase(reporter, "postcompress", &mut n, |n| {
+            postcompress_optimizer(n, c);
 
-        n.visit_mut_with(&mut pure_optimizer(
-            c,
-            marks,
-            PureOptimizerConfig {
-                enable_join_vars: true,
-            },
-        ));
+            n.visit_mut_with(&mut pure_optimizer(
+                c,
+                marks,
+                PureOptimizerConfig {
+                    enable_join_vars: true,
+                },
+            ));
+        });
     }
 
     if let Some(ref mut _t) = timings {
@@ -209,21 +218,32 @@ pub fn optimize(
             CharFreq::default().compile()
         };
 
-        mangle_names(
-            &mut n,
-            mangle,
-            preserved.preserved,
-            chars,
-            extra.top_level_mark,
-            extra.mangle_name_cache.clone(),
-        );
+        phase(reporter, "mangle_names", &mut n, |n| {
+            mangle_names(
+                n,
+                mangle,
+                preserved.preserved,
+                chars,
+                extra.top_level_mark,
+                extra.mangle_name_cache.clone(),
+            )
+        });
 
         if let Some(property_mangle_options) = &mangle.props {
-            mangle_properties(&mut n, property_mangle_options, chars);
+            phase(reporter, "mangle_props", &mut n, |n| {
+                mangle_properties(
+                    n,
+                    property_mangle_options,
+                    chars,
+                    extra.mangle_name_cache.as_deref(),
+                )
+            });
         }
     }
 
-    n.visit_mut_with(&mut merge_exports());
+    phase(reporter, "merge_exports", &mut n, |n| {
+        n.visit_mut_with(&mut merge_exports())
+    });
 
     if let Some(ref mut t) = timings {
         t.section("hygiene");
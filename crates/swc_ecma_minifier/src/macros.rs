@@ -1,6 +1,13 @@
 /// Used when something is modified.
+///
+/// This also counts the rewrite for [SizeReporter](crate::size_report::SizeReporter)
+/// if one is active.
 macro_rules! report_change {
     ($($tt:tt)+) => {{
+        if $crate::size_report::is_enabled() {
+            $crate::size_report::record_change(module_path!());
+        }
+
         #[cfg(feature = "debug")]
         tracing::debug!(
             kind = "change",
@@ -0,0 +1,165 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use swc_atoms::Atom;
+use swc_common::{Mark, SyntaxContext};
+use swc_ecma_transforms_base::rename::RenameMap;
+
+use super::{MangleCache, SimpleMangleCache};
+
+/// The version of the format written by [FileMangleCache::save].
+const VERSION: u32 = 1;
+
+/// A [MangleCache] which can be stored in a file, like `nameCache` of terser.
+///
+/// The file is a JSON object like
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "vars": { "foo": "a" },
+///   "props": { "bar": "b" }
+/// }
+/// ```
+///
+/// Like terser, only top-level variables are stored, by name, as the
+/// [SyntaxContext] of other variables changes whenever the code before them
+/// changes.
+#[derive(Debug)]
+pub struct FileMangleCache {
+    path: PathBuf,
+    top_level_ctxt: SyntaxContext,
+    cache: SimpleMangleCache,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CacheFile {
+    version: u32,
+    #[serde(default)]
+    vars: BTreeMap<Atom, Atom>,
+    #[serde(default)]
+    props: BTreeMap<Atom, Atom>,
+}
+
+impl FileMangleCache {
+    /// Creates an empty cache which is saved to `path`.
+    ///
+    /// `top_level_mark` should be the one passed to the minifier.
+    pub fn new(path: impl Into<PathBuf>, top_level_mark: Mark) -> Self {
+        Self {
+            path: path.into(),
+            top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+            cache: Default::default(),
+        }
+    }
+
+    /// Loads the cache from `path`, or creates an empty cache if the file does
+    /// not exist.
+    ///
+    /// `top_level_mark` should be the one passed to the minifier.
+    pub fn load(path: impl Into<PathBuf>, top_level_mark: Mark) -> io::Result<Self> {
+        let path = path.into();
+
+        let data = match fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok(Self::new(path, top_level_mark));
+            }
+            Err(err) => return Err(err),
+        };
+
+        let file: CacheFile = serde_json::from_str(&data)?;
+
+        if file.version != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "{}: unsupported version of the mangle cache: {}",
+                    path.display(),
+                    file.version
+                ),
+            ));
+        }
+
+        let top_level_ctxt = SyntaxContext::empty().apply_mark(top_level_mark);
+        let vars = file
+            .vars
+            .into_iter()
+            .map(|(sym, mangled)| ((sym, top_level_ctxt), mangled))
+            .collect::<RenameMap>();
+
+        Ok(Self {
+            path,
+            top_level_ctxt,
+            cache: SimpleMangleCache {
+                vars: vars.into(),
+                props: file.props.into_iter().collect::<FxHashMap<_, _>>().into(),
+            },
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes the top-level variables and the properties of the cache to the
+    /// file.
+    ///
+    /// Entries are sorted, so the file does not change if the names do not
+    /// change.
+    pub fn save(&self) -> io::Result<()> {
+        let file = CacheFile {
+            version: VERSION,
+            vars: self
+                .cache
+                .vars
+                .read()
+                .iter()
+                .filter(|((_, ctxt), _)| *ctxt == self.top_level_ctxt)
+                .map(|((sym, _), mangled)| (sym.clone(), mangled.clone()))
+                .collect(),
+            props: self
+                .cache
+                .props
+                .read()
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        };
+
+        let mut data = serde_json::to_string_pretty(&file)?;
+        data.push('\n');
+
+        if let Some(dir) = self.path.parent() {
+            if !dir.as_os_str().is_empty() {
+                fs::create_dir_all(dir)?;
+            }
+        }
+
+        fs::write(&self.path, data)
+    }
+}
+
+impl MangleCache for FileMangleCache {
+    fn vars_cache(&self, op: &mut dyn FnMut(&RenameMap)) {
+        self.cache.vars_cache(op)
+    }
+
+    fn props_cache(&self, op: &mut dyn FnMut(&FxHashMap<Atom, Atom>)) {
+        self.cache.props_cache(op)
+    }
+
+    fn update_vars_cache(&self, new_data: &RenameMap) {
+        self.cache.update_vars_cache(new_data)
+    }
+
+    fn update_props_cache(&self, new_data: &FxHashMap<Atom, Atom>) {
+        self.cache.update_props_cache(new_data)
+    }
+}
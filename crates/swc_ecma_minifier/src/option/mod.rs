@@ -12,6 +12,8 @@ use swc_ecma_ast::{EsVersion, Expr};
 use swc_ecma_transforms_base::rename::RenameMap;
 use terser::TerserExperimentalOptions;
 
+use crate::size_report::SizeReporter;
+
 /// Implement default using serde.
 macro_rules! impl_default {
     ($T:ty) => {
@@ -23,6 +25,9 @@ macro_rules! impl_default {
     };
 }
 
+pub use self::mangle_cache::FileMangleCache;
+
+mod mangle_cache;
 pub mod terser;
 
 /// This is not serializable.
@@ -34,6 +39,9 @@ pub struct ExtraOptions {
     pub top_level_mark: Mark,
 
     pub mangle_name_cache: Option<Arc<dyn MangleCache>>,
+
+    /// If set, the size changes made by each pass are recorded to it.
+    pub size_reporter: Option<Arc<SizeReporter>>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -7,7 +7,11 @@ use swc_ecma_ast::*;
 use swc_ecma_usage_analyzer::util::get_mut_object_define_property_name_arg;
 use swc_ecma_visit::{VisitMut, VisitMutWith, noop_visit_mut_type};
 
-use crate::{option::ManglePropertiesOptions, program_data::analyze, util::base54::Base54Chars};
+use crate::{
+    option::{MangleCache, ManglePropertiesOptions},
+    program_data::analyze,
+    util::base54::Base54Chars,
+};
 
 pub static JS_ENVIRONMENT_PROPS: Lazy<FxHashSet<Atom>> = Lazy::new(|| {
     let domprops: Vec<Atom> = serde_json::from_str(include_str!("../lists/domprops.json"))
@@ -35,6 +39,9 @@ struct ManglePropertiesState<'a> {
     // Cache of already mangled names
     cache: FxHashMap<Wtf8Atom, Atom>,
 
+    /// Names loaded from [MangleCache], which should not be reused.
+    cached_names: FxHashSet<Atom>,
+
     // Numbers to pass to base54()
     n: usize,
 }
@@ -96,7 +103,10 @@ impl<'a> ManglePropertiesState<'a> {
             if let Some(cached) = self.cache.get(name) {
                 Some(cached.clone())
             } else {
-                let mangled_name = self.chars.encode(&mut self.n, true);
+                let mut mangled_name = self.chars.encode(&mut self.n, true);
+                while self.cached_names.contains(&mangled_name) {
+                    mangled_name = self.chars.encode(&mut self.n, true);
+                }
 
                 self.cache.insert(name.clone(), mangled_name.clone());
                 Some(mangled_name)
@@ -111,6 +121,7 @@ pub(crate) fn mangle_properties(
     m: &mut Program,
     options: &ManglePropertiesOptions,
     chars: Base54Chars,
+    mangle_cache: Option<&dyn MangleCache>,
 ) {
     let mut state = ManglePropertiesState {
         options,
@@ -118,9 +129,19 @@ pub(crate) fn mangle_properties(
         names_to_mangle: Default::default(),
         unmangleable: Default::default(),
         cache: Default::default(),
+        cached_names: Default::default(),
         n: 0,
     };
 
+    if let Some(mangle_cache) = mangle_cache {
+        mangle_cache.props_cache(&mut |props| {
+            for (name, mangled) in props {
+                state.cache.insert(name.clone().into(), mangled.clone());
+                state.cached_names.insert(mangled.clone());
+            }
+        });
+    }
+
     let mut data = analyze(&*m, None, true);
 
     for prop in std::mem::take(data.property_atoms.as_mut().unwrap()) {
@@ -128,6 +149,16 @@ pub(crate) fn mangle_properties(
     }
 
     m.visit_mut_with(&mut Mangler { state: &mut state });
+
+    if let Some(mangle_cache) = mangle_cache {
+        mangle_cache.update_props_cache(
+            &state
+                .cache
+                .iter()
+                .filter_map(|(name, mangled)| Some((name.as_atom()?.clone(), mangled.clone())))
+                .collect(),
+        );
+    }
 }
 
 struct Mangler<'a, 'b> {
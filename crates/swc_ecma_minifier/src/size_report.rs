@@ -0,0 +1,485 @@
+//! Attribution of code size changes to the passes of the minifier.
+//!
+//! This is opt-in, because the code is printed before and after each phase of
+//! the minifier. See [SizeReporter].
+
+use std::{
+    cell::RefCell,
+    cmp::Reverse,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use swc_atoms::{Atom, atom};
+use swc_common::{BytePos, SourceMap, sync::Lrc};
+use swc_ecma_ast::*;
+use swc_ecma_codegen::{Emitter, text_writer::JsWriter};
+
+use crate::util::ModuleItemExt;
+
+/// Collects the size changes made by [optimize](crate::optimize).
+///
+/// Pass this to [ExtraOptions](crate::option::ExtraOptions) and call
+/// [SizeReporter::report] after the minification.
+///
+/// The size of each top-level item is measured by printing it in the minified
+/// form, before and after each phase of the minifier. Rewrites are counted
+/// per module of the compressor, like `optimize::inline` or
+/// `pure::dead_code`. Passes of a phase run at the same time, so if multiple
+/// passes rewrite the same top-level function in a phase, the size change is
+/// divided between them by the number of rewrites. Changes without any
+/// rewrite are attributed to the phase itself, like `dce` or `mangle_names`.
+#[derive(Debug, Default)]
+pub struct SizeReporter {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    started: bool,
+    /// Top-level items in the order they are seen.
+    items: Vec<ItemKey>,
+    names: FxHashMap<ItemKey, Atom>,
+    original_sizes: FxHashMap<ItemKey, usize>,
+    sizes: FxHashMap<ItemKey, usize>,
+    passes: FxHashMap<(ItemKey, &'static str), PassStat>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct PassStat {
+    size_delta: isize,
+    rewrites: usize,
+}
+
+/// Identifies a top-level item across phases, even if it's renamed by the
+/// mangler.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ItemKey {
+    /// Statements which do not declare a function, like imports or top-level
+    /// expressions.
+    TopLevel,
+    Pos(BytePos),
+    Name(Atom),
+}
+
+/// The JSON report of [SizeReporter].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeReport {
+    /// The size of the input, in bytes.
+    pub original_size: usize,
+    /// The size of the output, in bytes.
+    pub minified_size: usize,
+    /// Passes sorted by the size change, so the most effective pass comes
+    /// first.
+    pub passes: Vec<PassSize>,
+    /// Top-level functions, in the order of the input.
+    pub functions: Vec<FunctionSize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PassSize {
+    pub name: String,
+    /// Negative if the pass made the code smaller.
+    pub size_delta: isize,
+    pub rewrites: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionSize {
+    /// `(top-level)` for statements which do not declare a function.
+    pub name: String,
+    /// `0` if the function is created by the minifier.
+    pub original_size: usize,
+    /// `0` if the function is removed by the minifier.
+    pub minified_size: usize,
+    pub passes: Vec<PassSize>,
+}
+
+impl SizeReporter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn report(&self) -> SizeReport {
+        let state = self.state.lock();
+
+        let index = state
+            .items
+            .iter()
+            .enumerate()
+            .map(|(idx, key)| (key, idx))
+            .collect::<FxHashMap<_, _>>();
+        let mut passes = FxHashMap::<&str, PassStat>::default();
+        let mut functions = state
+            .items
+            .iter()
+            .map(|key| FunctionSize {
+                name: state.names[key].to_string(),
+                original_size: state.original_sizes.get(key).copied().unwrap_or_default(),
+                minified_size: state.sizes.get(key).copied().unwrap_or_default(),
+                passes: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+
+        for ((key, pass), stat) in &state.passes {
+            let total = passes.entry(pass).or_default();
+            total.size_delta += stat.size_delta;
+            total.rewrites += stat.rewrites;
+
+            functions[index[key]].passes.push(PassSize {
+                name: pass.to_string(),
+                size_delta: stat.size_delta,
+                rewrites: stat.rewrites,
+            });
+        }
+
+        for function in &mut functions {
+            sort_passes(&mut function.passes);
+        }
+
+        let mut passes = passes
+            .into_iter()
+            .map(|(name, stat)| PassSize {
+                name: name.to_string(),
+                size_delta: stat.size_delta,
+                rewrites: stat.rewrites,
+            })
+            .collect::<Vec<_>>();
+        sort_passes(&mut passes);
+
+        SizeReport {
+            original_size: state.original_sizes.values().sum(),
+            minified_size: state.sizes.values().sum(),
+            passes,
+            functions,
+        }
+    }
+
+    /// Runs a phase of the minifier, and attributes the size changes to the
+    /// passes which rewrote the code.
+    pub(crate) fn phase<R>(
+        &self,
+        name: &'static str,
+        program: &mut Program,
+        op: impl FnOnce(&mut Program) -> R,
+    ) -> R {
+        {
+            let mut state = self.state.lock();
+            if !state.started {
+                state.started = true;
+                let sizes = state.measure(program);
+                state.original_sizes = sizes.clone();
+                state.sizes = sizes;
+            }
+        }
+
+        let scope = Scope {
+            rewrites: Default::default(),
+            item: None,
+        };
+        let rewrites = scope.rewrites.clone();
+
+        ACTIVE_PHASES.fetch_add(1, Ordering::Relaxed);
+        let ret = Scope::enter(Some(&scope), || op(program));
+        ACTIVE_PHASES.fetch_sub(1, Ordering::Relaxed);
+
+        let rewrites = Arc::try_unwrap(rewrites)
+            .map(Mutex::into_inner)
+            .unwrap_or_else(|rewrites| rewrites.lock().clone());
+
+        let mut state = self.state.lock();
+        let sizes = state.measure(program);
+        state.attribute(name, &sizes, rewrites);
+        state.sizes = sizes;
+
+        ret
+    }
+}
+
+/// Runs `op` as a phase of `reporter`, if it's enabled.
+pub(crate) fn phase<R>(
+    reporter: Option<&SizeReporter>,
+    name: &'static str,
+    program: &mut Program,
+    op: impl FnOnce(&mut Program) -> R,
+) -> R {
+    match reporter {
+        Some(reporter) => reporter.phase(name, program, op),
+        None => op(program),
+    }
+}
+
+impl State {
+    fn measure(&mut self, program: &Program) -> FxHashMap<ItemKey, usize> {
+        let mut sizes = FxHashMap::default();
+
+        match program {
+            Program::Module(m) => {
+                for item in &m.body {
+                    self.add_size(&mut sizes, item);
+                }
+            }
+            Program::Script(s) => {
+                for stmt in &s.body {
+                    self.add_size(&mut sizes, stmt);
+                }
+            }
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        }
+
+        sizes
+    }
+
+    fn add_size<T>(&mut self, sizes: &mut FxHashMap<ItemKey, usize>, item: &T)
+    where
+        T: ModuleItemExt + swc_ecma_codegen::Node,
+    {
+        let (key, name) = item_key(item);
+        self.add_item(key.clone(), name);
+
+        *sizes.entry(key).or_default() += code_size(item);
+    }
+
+    fn add_item(&mut self, key: ItemKey, name: Atom) {
+        if !self.names.contains_key(&key) {
+            self.items.push(key.clone());
+            self.names.insert(key, name);
+        }
+    }
+
+    fn attribute(
+        &mut self,
+        phase: &'static str,
+        sizes: &FxHashMap<ItemKey, usize>,
+        rewrites: Rewrites,
+    ) {
+        let mut by_item = FxHashMap::<ItemKey, Vec<(&'static str, usize)>>::default();
+
+        for ((key, pass), count) in rewrites.counts {
+            by_item.entry(key).or_default().push((pass, count));
+        }
+
+        for (key, name) in rewrites.names {
+            self.add_item(key, name);
+        }
+        if by_item.contains_key(&ItemKey::TopLevel) {
+            self.add_item(ItemKey::TopLevel, top_level_name());
+        }
+
+        for key in self.items.clone() {
+            let before = self.sizes.get(&key).copied().unwrap_or_default() as isize;
+            let after = sizes.get(&key).copied().unwrap_or_default() as isize;
+            let delta = after - before;
+
+            let mut passes = by_item.remove(&key).unwrap_or_default();
+
+            if passes.is_empty() {
+                if delta != 0 {
+                    self.passes.entry((key, phase)).or_default().size_delta += delta;
+                }
+                continue;
+            }
+
+            // The remainder goes to the pass with the most rewrites.
+            passes.sort_by_key(|&(pass, count)| (Reverse(count), pass));
+
+            let total = passes.iter().map(|(_, count)| count).sum::<usize>() as isize;
+            let mut remainder = delta;
+
+            for &(pass, count) in &passes {
+                let share = delta * count as isize / total;
+                remainder -= share;
+
+                let stat = self.passes.entry((key.clone(), pass)).or_default();
+                stat.size_delta += share;
+                stat.rewrites += count;
+            }
+
+            self.passes.get_mut(&(key, passes[0].0)).unwrap().size_delta += remainder;
+        }
+    }
+}
+
+fn sort_passes(passes: &mut [PassSize]) {
+    passes.sort_by(|a, b| {
+        a.size_delta
+            .cmp(&b.size_delta)
+            .then_with(|| b.rewrites.cmp(&a.rewrites))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+fn code_size<N>(node: &N) -> usize
+where
+    N: swc_ecma_codegen::Node,
+{
+    let cm = Lrc::new(SourceMap::default());
+    let mut buf = Vec::new();
+
+    {
+        let mut emitter = Emitter {
+            cfg: swc_ecma_codegen::Config::default().with_minify(true),
+            cm: cm.clone(),
+            comments: None,
+            wr: Box::new(JsWriter::new(cm, "\n", &mut buf, None)),
+        };
+
+        node.emit_with(&mut emitter).unwrap();
+    }
+
+    buf.len()
+}
+
+fn top_level_name() -> Atom {
+    atom!("(top-level)")
+}
+
+/// Returns the key and the name of the function declared by `item`.
+fn item_key<T>(item: &T) -> (ItemKey, Atom)
+where
+    T: ModuleItemExt,
+{
+    let ident = match item.as_module_decl() {
+        Ok(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => decl_ident(decl),
+        Ok(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl, .. })) => match decl {
+            DefaultDecl::Class(ClassExpr {
+                ident: Some(ident), ..
+            })
+            | DefaultDecl::Fn(FnExpr {
+                ident: Some(ident), ..
+            }) => Some(ident),
+            _ => None,
+        },
+        Ok(_) => None,
+        Err(Stmt::Decl(decl)) => decl_ident(decl),
+        Err(_) => None,
+    };
+
+    match ident {
+        Some(ident) if ident.span.is_dummy() => {
+            (ItemKey::Name(ident.sym.clone()), ident.sym.clone())
+        }
+        Some(ident) => (ItemKey::Pos(ident.span.lo), ident.sym.clone()),
+        None => (ItemKey::TopLevel, top_level_name()),
+    }
+}
+
+fn decl_ident(decl: &Decl) -> Option<&Ident> {
+    match decl {
+        Decl::Fn(f) => Some(&f.ident),
+        Decl::Class(c) => Some(&c.ident),
+        Decl::Var(v) => {
+            let first = v.decls.first()?;
+
+            match (&first.name, first.init.as_deref()) {
+                (Pat::Ident(name), Some(Expr::Fn(..) | Expr::Arrow(..) | Expr::Class(..))) => {
+                    Some(&name.id)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+struct Rewrites {
+    counts: FxHashMap<(ItemKey, &'static str), usize>,
+    names: Vec<(ItemKey, Atom)>,
+}
+
+/// The state of a running phase, shared with the threads which run the passes
+/// in parallel.
+#[derive(Clone)]
+pub(crate) struct Scope {
+    rewrites: Arc<Mutex<Rewrites>>,
+    /// The top-level item being optimized.
+    item: Option<ItemKey>,
+}
+
+/// The number of phases being measured, in any thread.
+static ACTIVE_PHASES: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns true if a phase of any [SizeReporter] is running, so the minifier
+/// can skip the bookkeeping otherwise.
+#[inline]
+pub(crate) fn is_enabled() -> bool {
+    ACTIVE_PHASES.load(Ordering::Relaxed) != 0
+}
+
+thread_local! {
+    static SCOPE: RefCell<Option<Scope>> = const { RefCell::new(None) };
+}
+
+impl Scope {
+    /// Returns the scope of the current thread, which should be passed to
+    /// [Scope::enter] in other threads.
+    pub(crate) fn current() -> Option<Scope> {
+        SCOPE.with(|scope| scope.borrow().clone())
+    }
+
+    /// Runs `op` with `scope` as the scope of the current thread.
+    ///
+    /// This also clears the scope if `scope` is [None], so a task stolen from
+    /// another minification is not counted.
+    pub(crate) fn enter<R>(scope: Option<&Scope>, op: impl FnOnce() -> R) -> R {
+        let prev = SCOPE.with(|s| s.replace(scope.cloned()));
+        let ret = op();
+        SCOPE.with(|s| *s.borrow_mut() = prev);
+        ret
+    }
+}
+
+/// Attributes rewrites in `op` to the top-level item `item`, if the reporter
+/// is enabled and a top-level item is not being optimized already.
+pub(crate) fn enter_item<T, R>(item: &mut T, op: impl FnOnce(&mut T) -> R) -> R
+where
+    T: ModuleItemExt,
+{
+    let scope = SCOPE.with(|scope| match &*scope.borrow() {
+        Some(scope) if scope.item.is_none() => {
+            let (key, name) = item_key(&*item);
+
+            scope.rewrites.lock().names.push((key.clone(), name));
+
+            Some(Scope {
+                rewrites: scope.rewrites.clone(),
+                item: Some(key),
+            })
+        }
+        _ => None,
+    });
+
+    match scope {
+        Some(scope) => Scope::enter(Some(&scope), || op(item)),
+        None => op(item),
+    }
+}
+
+/// Records a rewrite made by the module `module`.
+///
+/// Called by `report_change!`.
+#[inline]
+pub(crate) fn record_change(module: &'static str) {
+    SCOPE.with(|scope| {
+        if let Some(scope) = &*scope.borrow() {
+            let pass = module
+                .strip_prefix("swc_ecma_minifier::compress::")
+                .or_else(|| module.strip_prefix("swc_ecma_minifier::"))
+                .unwrap_or(module);
+
+            let key = scope.item.clone().unwrap_or(ItemKey::TopLevel);
+
+            *scope.rewrites.lock().counts.entry((key, pass)).or_default() += 1;
+        }
+    });
+}
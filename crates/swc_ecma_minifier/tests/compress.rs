@@ -8,6 +8,7 @@ use std::{
     fs::read_to_string,
     panic::catch_unwind,
     path::{Path, PathBuf},
+    sync::Arc,
     time::Instant,
 };
 
@@ -34,8 +35,9 @@ use swc_ecma_minifier::{
         CompressOptions, ExtraOptions, MangleOptions, MinifyOptions, TopLevelOptions,
         terser::TerserCompressorOptions,
     },
+    size_report::SizeReporter,
 };
-use swc_ecma_parser::{EsSyntax, Parser, Syntax, lexer::Lexer};
+use swc_ecma_parser::{EsSyntax, Parser, Syntax, lexer::Lexer, parse_file_as_program};
 use swc_ecma_testing::{JsExecOptions, exec_node_js};
 use swc_ecma_transforms_base::{
     fixer::{fixer, paren_remover},
@@ -245,6 +247,7 @@ fn run(
                 unresolved_mark,
                 top_level_mark,
                 mangle_name_cache: None,
+                size_reporter: None,
             },
         );
         let end = Instant::now();
@@ -779,3 +782,90 @@ impl VisitMut for DropParens {
         }
     }
 }
+
+#[test]
+fn size_report() {
+    let reporter = Arc::new(SizeReporter::new());
+
+    testing::run_test2(false, |cm, handler| {
+        let fm = cm.new_source_file(
+            FileName::Anon.into(),
+            "export function add(a, b) {
+    if (true) {
+        return a + b;
+    }
+    return 0;
+}
+export function unused() {
+    var value = 1;
+    return value;
+}
+console.log(add(1, 2));"
+                .to_string(),
+        );
+
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+
+        let program = parse_file_as_program(
+            &fm,
+            Default::default(),
+            EsVersion::latest(),
+            None,
+            &mut Vec::new(),
+        )
+        .map_err(|err| {
+            err.into_diagnostic(&handler).emit();
+        })?
+        .apply(&mut resolver(unresolved_mark, top_level_mark, false));
+
+        optimize(
+            program,
+            cm,
+            None,
+            None,
+            &MinifyOptions {
+                compress: Some(Default::default()),
+                mangle: Some(Default::default()),
+                ..Default::default()
+            },
+            &ExtraOptions {
+                unresolved_mark,
+                top_level_mark,
+                mangle_name_cache: None,
+                size_reporter: Some(reporter.clone()),
+            },
+        );
+
+        Ok(())
+    })
+    .unwrap();
+
+    let report = reporter.report();
+
+    assert!(report.minified_size < report.original_size, "{report:#?}");
+    assert_eq!(
+        report.passes.iter().map(|p| p.size_delta).sum::<isize>(),
+        report.minified_size as isize - report.original_size as isize,
+    );
+
+    let names = report
+        .functions
+        .iter()
+        .map(|f| &*f.name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["add", "unused", "(top-level)"]);
+
+    let add = &report.functions[0];
+    assert!(add.minified_size < add.original_size, "{add:#?}");
+    assert!(
+        add.passes
+            .iter()
+            .any(|p| p.name == "pure::dead_code" && p.rewrites > 0 && p.size_delta < 0),
+        "{add:#?}"
+    );
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert!(json["passes"][0]["sizeDelta"].is_i64(), "{json}");
+    assert!(json["functions"][0]["originalSize"].is_u64(), "{json}");
+}
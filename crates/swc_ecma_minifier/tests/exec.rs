@@ -148,6 +148,7 @@ fn run(
             unresolved_mark,
             top_level_mark,
             mangle_name_cache: None,
+            size_reporter: None,
         },
     );
 
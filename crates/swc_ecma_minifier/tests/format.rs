@@ -57,6 +57,7 @@ fn assert_format(src: &str, expected: &str, opts: Config) {
                 unresolved_mark,
                 top_level_mark,
                 mangle_name_cache: None,
+                size_reporter: None,
             },
         )
         .expect_module();
@@ -3,10 +3,11 @@
 use std::{
     path::{Path, PathBuf},
     process::{Command, Stdio},
+    sync::Arc,
 };
 
 use swc_atoms::atom;
-use swc_common::{FileName, Mark, SourceFile, SourceMap, errors::Handler, sync::Lrc};
+use swc_common::{FileName, GLOBALS, Mark, SourceFile, SourceMap, errors::Handler, sync::Lrc};
 use swc_ecma_ast::*;
 use swc_ecma_codegen::{
     Emitter,
@@ -14,7 +15,9 @@ use swc_ecma_codegen::{
 };
 use swc_ecma_minifier::{
     optimize,
-    option::{ExtraOptions, MangleOptions, ManglePropertiesOptions, MinifyOptions},
+    option::{
+        ExtraOptions, FileMangleCache, MangleOptions, ManglePropertiesOptions, MinifyOptions,
+    },
 };
 use swc_ecma_parser::parse_file_as_program;
 use swc_ecma_transforms_base::{fixer::paren_remover, resolver};
@@ -106,6 +109,7 @@ fn snapshot_compress_fixture(input: PathBuf) {
                 unresolved_mark,
                 top_level_mark,
                 mangle_name_cache: None,
+                size_reporter: None,
             },
         );
 
@@ -169,6 +173,7 @@ fn fixture(input: PathBuf) {
                 unresolved_mark,
                 top_level_mark,
                 mangle_name_cache: None,
+                size_reporter: None,
             },
         );
 
@@ -207,6 +212,7 @@ fn assert_mangled(src: &str, expected: &str, opts: MangleOptions) {
                 unresolved_mark,
                 top_level_mark,
                 mangle_name_cache: None,
+                size_reporter: None,
             },
         );
 
@@ -360,3 +366,151 @@ class s {
         },
     )
 }
+
+/// Mangles `src` with a [FileMangleCache] loaded from `path`, like a separate
+/// invocation of the minifier.
+fn mangle_with_file_cache(src: &str, path: &Path) -> String {
+    testing::run_test2(false, |cm, handler| {
+        let fm = cm.new_source_file(FileName::Anon.into(), src.to_string());
+
+        let mut p = parse_fm(&handler, fm)?;
+
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+
+        p.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, false));
+
+        let cache = Arc::new(FileMangleCache::load(path, top_level_mark).unwrap());
+
+        let p = optimize(
+            p,
+            cm.clone(),
+            None,
+            None,
+            &MinifyOptions {
+                mangle: Some(MangleOptions {
+                    top_level: Some(true),
+                    props: Some(Default::default()),
+                    ..Default::default()
+                }),
+                compress: None,
+                ..Default::default()
+            },
+            &ExtraOptions {
+                unresolved_mark,
+                top_level_mark,
+                mangle_name_cache: Some(cache.clone()),
+                size_reporter: None,
+            },
+        );
+
+        cache.save().unwrap();
+
+        Ok(print(cm, &p, false))
+    })
+    .unwrap()
+}
+
+#[test]
+fn file_mangle_cache() {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("file_mangle_cache.json");
+    let _ = std::fs::remove_file(&path);
+
+    let first = mangle_with_file_cache(
+        "var alpha = 1;
+var beta = { longProperty: 2 };
+console.log(alpha, beta.longProperty);",
+        &path,
+    );
+    assert_eq!(
+        first,
+        "var o = 1;
+var r = {
+    o: 2
+};
+console.log(o, r.o);
+"
+    );
+
+    let cache = std::fs::read_to_string(&path).unwrap();
+    assert!(cache.contains("\"version\": 1"), "{cache}");
+    assert!(cache.contains("\"longProperty\""), "{cache}");
+    assert!(cache.contains("\"alpha\": \"o\""), "{cache}");
+
+    // Cached names are reused, and new names do not collide with them.
+    let second = mangle_with_file_cache(
+        "var gamma = { otherProperty: 3 };
+var alpha = 1;
+var beta = { longProperty: 2 };
+console.log(gamma.otherProperty, alpha, beta.longProperty);",
+        &path,
+    );
+
+    assert_eq!(
+        second,
+        "var e = {
+    r: 3
+};
+var o = 1;
+var r = {
+    o: 2
+};
+console.log(e.r, o, r.o);
+"
+    );
+
+    // Saving the same names does not change the file.
+    let cache = std::fs::read_to_string(&path).unwrap();
+    mangle_with_file_cache(
+        "var alpha = 1;
+var beta = { longProperty: 2 };
+console.log(alpha, beta.longProperty);",
+        &path,
+    );
+    assert_eq!(cache, std::fs::read_to_string(&path).unwrap());
+}
+
+#[test]
+fn file_mangle_cache_version() {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("file_mangle_cache_version.json");
+    std::fs::write(&path, r#"{ "version": 0 }"#).unwrap();
+
+    let err = GLOBALS.set(&Default::default(), || {
+        FileMangleCache::load(&path, Mark::new()).unwrap_err()
+    });
+    assert!(err.to_string().contains("unsupported version"), "{err}");
+}
+
+#[test]
+fn file_mangle_cache_collision() {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("file_mangle_cache_collision.json");
+    let _ = std::fs::remove_file(&path);
+
+    let first = mangle_with_file_cache(
+        "var alpha = 1;
+console.log(alpha);",
+        &path,
+    );
+    assert_eq!(
+        first,
+        "var o = 1;
+console.log(o);
+"
+    );
+
+    // The cached name is now used by a global, so `alpha` is left as is
+    // instead of shadowing it.
+    let second = mangle_with_file_cache(
+        "var alpha = 1;
+var beta = 2;
+console.log(alpha, beta, o);",
+        &path,
+    );
+    assert_eq!(
+        second,
+        "var alpha = 1;
+var a = 2;
+console.log(alpha, a, o);
+"
+    );
+}
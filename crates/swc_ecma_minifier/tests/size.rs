@@ -176,6 +176,7 @@ fn run(src: &str) -> FileSize {
                 unresolved_mark,
                 top_level_mark,
                 mangle_name_cache: None,
+                size_reporter: None,
             },
         );
 
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use swc_atoms::{Atom, atom};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// The module to import the memo cache hook (`c`) from.
+    ///
+    /// Defaults to `react/compiler-runtime`.
+    #[serde(default = "default_runtime_module")]
+    pub runtime_module: Atom,
+
+    #[serde(default)]
+    pub compilation_mode: CompilationMode,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            runtime_module: default_runtime_module(),
+            compilation_mode: Default::default(),
+        }
+    }
+}
+
+fn default_runtime_module() -> Atom {
+    atom!("react/compiler-runtime")
+}
+
+/// Determines which functions are compiled.
+///
+/// Functions with a `"use no memo"` directive are never compiled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompilationMode {
+    /// Compile top-level functions which look like components or hooks, i.e.
+    /// functions named like `Component` or `useHook` which render JSX or call
+    /// hooks.
+    #[default]
+    Infer,
+
+    /// Compile only functions with a `"use memo"` directive.
+    Annotation,
+}
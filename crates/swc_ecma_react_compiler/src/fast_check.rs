@@ -9,8 +9,22 @@ pub fn is_required(program: &Program) -> bool {
     finder.found
 }
 
+/// Returns true if a function with the body `n` renders JSX or calls hooks,
+/// i.e. it's a component or a hook if it's named like one.
+pub(crate) fn is_component_or_hook_body<N>(n: &N) -> bool
+where
+    N: ?Sized + VisitWith<Finder>,
+{
+    let mut finder = Finder {
+        found: false,
+        is_interested: true,
+    };
+    n.visit_with(&mut finder);
+    finder.found
+}
+
 #[derive(Default)]
-struct Finder {
+pub(crate) struct Finder {
     found: bool,
 
     /// We are in a function that starts with a capital letter or it's a
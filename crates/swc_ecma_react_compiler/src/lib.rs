@@ -1 +1,8 @@
+pub use self::{
+    config::{CompilationMode, Config},
+    transform::react_compiler,
+};
+
+mod config;
 pub mod fast_check;
+mod transform;
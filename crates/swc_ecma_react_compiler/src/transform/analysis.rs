@@ -0,0 +1,517 @@
+use rustc_hash::FxHashSet;
+use swc_atoms::Atom;
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_utils::{collect_decls, find_pat_ids};
+use swc_ecma_visit::{Visit, VisitWith, noop_visit_type};
+
+use super::hooks::hook_name;
+
+/// A value read by a memoized expression.
+///
+/// `prop` is only used for the props of a component, so `props.a` and
+/// `props.b` can be tracked separately. Reading a property of the props
+/// object is always safe, while it may throw for other values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct Dependency {
+    pub root: Id,
+    pub prop: Option<Atom>,
+}
+
+impl Dependency {
+    pub fn to_expr(&self) -> Expr {
+        let root = Expr::Ident(Ident::new(self.root.0.clone(), DUMMY_SP, self.root.1));
+
+        match &self.prop {
+            Some(prop) => MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(root),
+                prop: MemberProp::Ident(IdentName::new(prop.clone(), DUMMY_SP)),
+            }
+            .into(),
+            None => root,
+        }
+    }
+}
+
+/// Returns true if evaluating `e` allocates a new value (or renders JSX), so
+/// it's worth caching the result.
+///
+/// Expressions which cannot be skipped safely, like hook calls or `await`,
+/// are never memoizable.
+pub(super) fn is_memoizable(e: &Expr) -> bool {
+    let mut v = MemoizableFinder::default();
+    e.visit_with(&mut v);
+    v.allocates && !v.unsupported
+}
+
+/// Returns true if `e` allocates a new value or calls a hook, i.e. it may
+/// evaluate to a different value on each render.
+pub(super) fn may_allocate(e: &Expr) -> bool {
+    let mut v = MemoizableFinder::default();
+    e.visit_with(&mut v);
+    v.allocates || v.calls_hook
+}
+
+#[derive(Default)]
+struct MemoizableFinder {
+    allocates: bool,
+    calls_hook: bool,
+    unsupported: bool,
+}
+
+impl Visit for MemoizableFinder {
+    noop_visit_type!();
+
+    fn visit_array_lit(&mut self, n: &ArrayLit) {
+        self.allocates = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+        self.allocates = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_await_expr(&mut self, n: &AwaitExpr) {
+        self.unsupported = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        match &n.callee {
+            Callee::Super(..) | Callee::Import(..) => self.unsupported = true,
+            callee if hook_name(callee).is_some() => {
+                self.calls_hook = true;
+                self.unsupported = true;
+            }
+            _ => self.allocates = true,
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_class(&mut self, n: &Class) {
+        self.allocates = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, n: &Function) {
+        self.allocates = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_ident(&mut self, n: &Ident) {
+        if n.sym == "arguments" {
+            self.unsupported = true;
+        }
+    }
+
+    fn visit_jsx_element(&mut self, n: &JSXElement) {
+        self.allocates = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_jsx_fragment(&mut self, n: &JSXFragment) {
+        self.allocates = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_meta_prop_expr(&mut self, _: &MetaPropExpr) {
+        self.unsupported = true;
+    }
+
+    fn visit_new_expr(&mut self, n: &NewExpr) {
+        self.allocates = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_object_lit(&mut self, n: &ObjectLit) {
+        self.allocates = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_super_prop_expr(&mut self, n: &SuperPropExpr) {
+        self.unsupported = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_tagged_tpl(&mut self, n: &TaggedTpl) {
+        self.allocates = true;
+        n.visit_children_with(self);
+    }
+
+    fn visit_this_expr(&mut self, _: &ThisExpr) {
+        self.unsupported = true;
+    }
+
+    fn visit_yield_expr(&mut self, n: &YieldExpr) {
+        self.unsupported = true;
+        n.visit_children_with(self);
+    }
+}
+
+/// Collects values declared outside of `e` and read by `e`, in the order of
+/// the first appearance.
+pub(super) fn collect_references(e: &Expr, props: Option<&Id>) -> Vec<Dependency> {
+    let mut v = ReferenceCollector {
+        declared: collect_decls(e),
+        props,
+        found: Vec::new(),
+    };
+    e.visit_with(&mut v);
+
+    // `props.a` is redundant if `props` is a dependency.
+    let roots: FxHashSet<Id> = v
+        .found
+        .iter()
+        .filter(|dep| dep.prop.is_none())
+        .map(|dep| dep.root.clone())
+        .collect();
+    v.found
+        .retain(|dep| dep.prop.is_none() || !roots.contains(&dep.root));
+
+    v.found
+}
+
+struct ReferenceCollector<'a> {
+    declared: FxHashSet<Id>,
+    props: Option<&'a Id>,
+    found: Vec<Dependency>,
+}
+
+impl ReferenceCollector<'_> {
+    fn add(&mut self, dep: Dependency) {
+        if !self.declared.contains(&dep.root) && !self.found.contains(&dep) {
+            self.found.push(dep);
+        }
+    }
+}
+
+impl Visit for ReferenceCollector<'_> {
+    noop_visit_type!();
+
+    fn visit_callee(&mut self, n: &Callee) {
+        // `props.onClick()` depends on `props`, as it's the receiver of the call.
+        if let Callee::Expr(callee) = n {
+            if let Expr::Member(m) = &**callee {
+                m.obj.visit_with(self);
+                m.prop.visit_with(self);
+                return;
+            }
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_ident(&mut self, n: &Ident) {
+        self.add(Dependency {
+            root: n.to_id(),
+            prop: None,
+        });
+    }
+
+    fn visit_member_expr(&mut self, n: &MemberExpr) {
+        if let (Some(props), Expr::Ident(obj), MemberProp::Ident(prop)) =
+            (self.props, &*n.obj, &n.prop)
+        {
+            if obj.ctxt == props.1 && obj.sym == props.0 {
+                self.add(Dependency {
+                    root: obj.to_id(),
+                    prop: Some(prop.sym.clone()),
+                });
+                return;
+            }
+        }
+
+        n.visit_children_with(self);
+    }
+}
+
+/// Collects bindings which may be reassigned.
+pub(super) fn collect_mutable_bindings<N>(n: &N) -> FxHashSet<Id>
+where
+    N: ?Sized + VisitWith<MutableBindingCollector>,
+{
+    let mut v = MutableBindingCollector::default();
+    n.visit_with(&mut v);
+    v.found
+}
+
+#[derive(Default)]
+pub(super) struct MutableBindingCollector {
+    found: FxHashSet<Id>,
+}
+
+impl Visit for MutableBindingCollector {
+    noop_visit_type!();
+
+    fn visit_assign_expr(&mut self, n: &AssignExpr) {
+        match &n.left {
+            AssignTarget::Simple(SimpleAssignTarget::Ident(i)) => {
+                self.found.insert(i.to_id());
+            }
+            AssignTarget::Pat(pat) => {
+                self.found.extend(find_pat_ids::<_, Id>(pat));
+            }
+            _ => {}
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_for_head(&mut self, n: &ForHead) {
+        if let ForHead::Pat(pat) = n {
+            self.found.extend(find_pat_ids::<_, Id>(pat));
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_update_expr(&mut self, n: &UpdateExpr) {
+        if let Expr::Ident(i) = &*n.arg {
+            self.found.insert(i.to_id());
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_var_decl(&mut self, n: &VarDecl) {
+        if n.kind != VarDeclKind::Const {
+            self.found.extend(find_pat_ids::<_, Id>(&n.decls));
+        }
+
+        n.visit_children_with(self);
+    }
+}
+
+/// Methods which do not mutate the receiver.
+static NON_MUTATING_METHODS: &[&str] = &[
+    "at",
+    "concat",
+    "entries",
+    "every",
+    "filter",
+    "find",
+    "findIndex",
+    "findLast",
+    "findLastIndex",
+    "flat",
+    "flatMap",
+    "forEach",
+    "hasOwnProperty",
+    "includes",
+    "indexOf",
+    "join",
+    "keys",
+    "lastIndexOf",
+    "map",
+    "reduce",
+    "reduceRight",
+    "slice",
+    "some",
+    "toLocaleString",
+    "toReversed",
+    "toSorted",
+    "toSpliced",
+    "toString",
+    "values",
+    "with",
+];
+
+/// Returns true if the value bound to `id` may be mutated, or may be aliased
+/// by something which can be mutated, in `stmts`.
+///
+/// Frozen values (JSX elements and functions) can be passed around freely,
+/// and only direct mutations are checked.
+pub(super) fn may_be_mutated(id: &Id, frozen: bool, stmts: &[Stmt]) -> bool {
+    let mut v = MutationFinder {
+        id,
+        frozen,
+        found: false,
+    };
+    stmts.visit_with(&mut v);
+    v.found
+}
+
+struct MutationFinder<'a> {
+    id: &'a Id,
+    frozen: bool,
+    found: bool,
+}
+
+impl MutationFinder<'_> {
+    fn is_target(&self, e: &Expr) -> bool {
+        match e {
+            Expr::Ident(i) => i.ctxt == self.id.1 && i.sym == self.id.0,
+            Expr::Paren(p) => self.is_target(&p.expr),
+            _ => false,
+        }
+    }
+
+    /// `x.a.b` where `x` is the target
+    fn is_target_member(&self, e: &MemberExpr) -> bool {
+        match &*e.obj {
+            Expr::Member(obj) => self.is_target_member(obj),
+            obj => self.is_target(obj),
+        }
+    }
+
+    /// Visits `e` unless it's the target itself.
+    fn visit_non_target(&mut self, e: &Expr) {
+        if !self.is_target(e) {
+            e.visit_with(self);
+        }
+    }
+}
+
+impl Visit for MutationFinder<'_> {
+    noop_visit_type!();
+
+    fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+        n.params.visit_with(self);
+
+        match &*n.body {
+            BlockStmtOrExpr::Expr(e) => self.visit_non_target(e),
+            body => body.visit_with(self),
+        }
+    }
+
+    fn visit_assign_expr(&mut self, n: &AssignExpr) {
+        if let AssignTarget::Simple(SimpleAssignTarget::Member(m)) = &n.left {
+            if self.is_target_member(m) {
+                self.found = true;
+                return;
+            }
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_bin_expr(&mut self, n: &BinExpr) {
+        self.visit_non_target(&n.left);
+        self.visit_non_target(&n.right);
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if let Callee::Expr(callee) = &n.callee {
+            match &**callee {
+                Expr::Member(m) if self.is_target_member(m) => {
+                    let is_safe_method = self.frozen
+                        || (self.is_target(&m.obj)
+                            && matches!(
+                                &m.prop,
+                                MemberProp::Ident(prop)
+                                    if NON_MUTATING_METHODS.contains(&&*prop.sym)
+                            ));
+
+                    if !is_safe_method {
+                        self.found = true;
+                        return;
+                    }
+
+                    m.prop.visit_with(self);
+                }
+                callee => self.visit_non_target(callee),
+            }
+        }
+
+        // Hooks do not mutate their arguments.
+        let is_hook = hook_name(&n.callee).is_some();
+
+        for arg in &n.args {
+            if is_hook && arg.spread.is_none() {
+                self.visit_non_target(&arg.expr);
+            } else {
+                arg.visit_with(self);
+            }
+        }
+    }
+
+    fn visit_cond_expr(&mut self, n: &CondExpr) {
+        self.visit_non_target(&n.test);
+        n.cons.visit_with(self);
+        n.alt.visit_with(self);
+    }
+
+    fn visit_expr(&mut self, n: &Expr) {
+        if self.found {
+            return;
+        }
+
+        if self.is_target(n) {
+            // The value escapes, and we cannot track it anymore.
+            if !self.frozen {
+                self.found = true;
+            }
+            return;
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_expr_or_spread(&mut self, n: &ExprOrSpread) {
+        if n.spread.is_some() {
+            self.visit_non_target(&n.expr);
+        } else {
+            n.expr.visit_with(self);
+        }
+    }
+
+    fn visit_if_stmt(&mut self, n: &IfStmt) {
+        self.visit_non_target(&n.test);
+        n.cons.visit_with(self);
+        n.alt.visit_with(self);
+    }
+
+    fn visit_jsx_expr(&mut self, n: &JSXExpr) {
+        if let JSXExpr::Expr(e) = n {
+            self.visit_non_target(e);
+        }
+    }
+
+    fn visit_member_expr(&mut self, n: &MemberExpr) {
+        self.visit_non_target(&n.obj);
+        n.prop.visit_with(self);
+    }
+
+    fn visit_return_stmt(&mut self, n: &ReturnStmt) {
+        if let Some(arg) = &n.arg {
+            self.visit_non_target(arg);
+        }
+    }
+
+    fn visit_spread_element(&mut self, n: &SpreadElement) {
+        self.visit_non_target(&n.expr);
+    }
+
+    fn visit_tpl(&mut self, n: &Tpl) {
+        for e in &n.exprs {
+            self.visit_non_target(e);
+        }
+    }
+
+    fn visit_unary_expr(&mut self, n: &UnaryExpr) {
+        if n.op == op!("delete") {
+            if let Expr::Member(m) = &*n.arg {
+                if self.is_target_member(m) {
+                    self.found = true;
+                    return;
+                }
+            }
+        }
+
+        self.visit_non_target(&n.arg);
+    }
+
+    fn visit_update_expr(&mut self, n: &UpdateExpr) {
+        if let Expr::Member(m) = &*n.arg {
+            if self.is_target_member(m) {
+                self.found = true;
+                return;
+            }
+        }
+
+        n.visit_children_with(self);
+    }
+}
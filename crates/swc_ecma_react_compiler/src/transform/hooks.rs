@@ -0,0 +1,223 @@
+use swc_common::{Span, errors::HANDLER};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+const CONDITIONAL_HOOK: &str = "React Compiler has skipped optimizing this function: Hooks must \
+                                always be called in a consistent order, and may not be called \
+                                conditionally. See the Rules of Hooks \
+                                (https://react.dev/warnings/invalid-hook-call-warning)";
+
+const NESTED_HOOK: &str = "React Compiler has skipped optimizing this function: Hooks must be \
+                           called at the top level in the body of a function component or custom \
+                           hook, and may not be called within function expressions. See the \
+                           Rules of Hooks (https://react.dev/warnings/invalid-hook-call-warning)";
+
+/// `useFoo`, `use0` or `use`.
+pub(crate) fn is_hook_name(sym: &str) -> bool {
+    match sym.strip_prefix("use") {
+        Some(rest) => {
+            rest.is_empty()
+                || rest.starts_with(|c: char| c.is_ascii_uppercase() || c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+/// Returns the name of the hook if `callee` is `useFoo` or `React.useFoo`.
+pub(crate) fn hook_name(callee: &Callee) -> Option<&str> {
+    let Callee::Expr(callee) = callee else {
+        return None;
+    };
+
+    let sym = match &**callee {
+        Expr::Ident(i) => &i.sym,
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if obj.is_ident() => &prop.sym,
+        _ => return None,
+    };
+
+    if is_hook_name(sym) { Some(sym) } else { None }
+}
+
+/// Validates the rules of hooks for the body of a component or a hook.
+///
+/// A diagnostic is emitted for each violation, and `false` is returned if the
+/// function should not be compiled.
+pub(super) fn validate_rules_of_hooks<N>(body: &N) -> bool
+where
+    N: ?Sized + VisitWith<RulesOfHooks>,
+{
+    let mut v = RulesOfHooks::default();
+    body.visit_with(&mut v);
+    v.valid
+}
+
+pub(super) struct RulesOfHooks {
+    valid: bool,
+
+    /// The current node may not be evaluated on every render.
+    in_conditional: bool,
+
+    /// A `return` statement was found before the current node.
+    after_return: bool,
+
+    in_nested_fn: bool,
+}
+
+impl Default for RulesOfHooks {
+    fn default() -> Self {
+        RulesOfHooks {
+            valid: true,
+            in_conditional: false,
+            after_return: false,
+            in_nested_fn: false,
+        }
+    }
+}
+
+impl RulesOfHooks {
+    fn report(&mut self, span: Span, msg: &str) {
+        self.valid = false;
+
+        HANDLER.with(|handler| handler.struct_span_warn(span, msg).emit());
+    }
+
+    fn visit_conditional<N>(&mut self, n: &N)
+    where
+        N: VisitWith<Self>,
+    {
+        let old = self.in_conditional;
+        self.in_conditional = true;
+        n.visit_with(self);
+        self.in_conditional = old;
+    }
+
+    fn visit_nested_fn<N>(&mut self, n: &N)
+    where
+        N: VisitWith<Self>,
+    {
+        let old_in_nested_fn = self.in_nested_fn;
+        let old_after_return = self.after_return;
+        self.in_nested_fn = true;
+
+        n.visit_children_with(self);
+
+        self.in_nested_fn = old_in_nested_fn;
+        self.after_return = old_after_return;
+    }
+}
+
+impl Visit for RulesOfHooks {
+    fn visit_arrow_expr(&mut self, n: &ArrowExpr) {
+        self.visit_nested_fn(n);
+    }
+
+    fn visit_assign_expr(&mut self, n: &AssignExpr) {
+        n.left.visit_with(self);
+
+        if matches!(n.op, op!("&&=") | op!("||=") | op!("??=")) {
+            self.visit_conditional(&n.right);
+        } else {
+            n.right.visit_with(self);
+        }
+    }
+
+    fn visit_bin_expr(&mut self, n: &BinExpr) {
+        n.left.visit_with(self);
+
+        if matches!(n.op, op!("&&") | op!("||") | op!("??")) {
+            self.visit_conditional(&n.right);
+        } else {
+            n.right.visit_with(self);
+        }
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if let Some(name) = hook_name(&n.callee) {
+            if self.in_nested_fn {
+                self.report(n.span, NESTED_HOOK);
+            } else if (self.in_conditional || self.after_return) && name != "use" {
+                // `use` is the only hook which can be called conditionally.
+                self.report(n.span, CONDITIONAL_HOOK);
+            }
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_class(&mut self, n: &Class) {
+        self.visit_nested_fn(n);
+    }
+
+    fn visit_cond_expr(&mut self, n: &CondExpr) {
+        n.test.visit_with(self);
+        self.visit_conditional(&n.cons);
+        self.visit_conditional(&n.alt);
+    }
+
+    fn visit_do_while_stmt(&mut self, n: &DoWhileStmt) {
+        self.visit_conditional(&n.body);
+        self.visit_conditional(&n.test);
+    }
+
+    fn visit_for_in_stmt(&mut self, n: &ForInStmt) {
+        n.right.visit_with(self);
+        self.visit_conditional(&n.left);
+        self.visit_conditional(&n.body);
+    }
+
+    fn visit_for_of_stmt(&mut self, n: &ForOfStmt) {
+        n.right.visit_with(self);
+        self.visit_conditional(&n.left);
+        self.visit_conditional(&n.body);
+    }
+
+    fn visit_for_stmt(&mut self, n: &ForStmt) {
+        n.init.visit_with(self);
+        self.visit_conditional(&n.test);
+        self.visit_conditional(&n.update);
+        self.visit_conditional(&n.body);
+    }
+
+    fn visit_function(&mut self, n: &Function) {
+        self.visit_nested_fn(n);
+    }
+
+    fn visit_if_stmt(&mut self, n: &IfStmt) {
+        n.test.visit_with(self);
+        self.visit_conditional(&n.cons);
+        self.visit_conditional(&n.alt);
+    }
+
+    fn visit_opt_chain_expr(&mut self, n: &OptChainExpr) {
+        // Everything after the first optional access may be skipped.
+        self.visit_conditional(&n.base);
+    }
+
+    fn visit_return_stmt(&mut self, n: &ReturnStmt) {
+        n.visit_children_with(self);
+
+        if !self.in_nested_fn {
+            self.after_return = true;
+        }
+    }
+
+    fn visit_switch_stmt(&mut self, n: &SwitchStmt) {
+        n.discriminant.visit_with(self);
+        self.visit_conditional(&n.cases);
+    }
+
+    fn visit_try_stmt(&mut self, n: &TryStmt) {
+        n.block.visit_with(self);
+        self.visit_conditional(&n.handler);
+        n.finalizer.visit_with(self);
+    }
+
+    fn visit_while_stmt(&mut self, n: &WhileStmt) {
+        self.visit_conditional(&n.test);
+        self.visit_conditional(&n.body);
+    }
+}
@@ -0,0 +1,660 @@
+use rustc_hash::FxHashSet;
+use swc_atoms::{Atom, atom};
+use swc_common::{DUMMY_SP, Spanned, util::take::Take};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{
+    ExprFactory, collect_decls, find_pat_ids, prepend_stmt, private_ident, quote_ident,
+};
+use swc_ecma_visit::{VisitMut, noop_visit_mut_type, visit_mut_pass};
+
+use self::{
+    analysis::{
+        Dependency, collect_mutable_bindings, collect_references, is_memoizable, may_allocate,
+        may_be_mutated,
+    },
+    hooks::{hook_name, is_hook_name, validate_rules_of_hooks},
+};
+use crate::{
+    config::{CompilationMode, Config},
+    fast_check::is_component_or_hook_body,
+};
+
+mod analysis;
+mod hooks;
+
+/// Memoizes values computed by components and hooks, so they are recomputed
+/// only if their dependencies change.
+///
+/// For each compiled function, a cache is created with `c` of the runtime
+/// module and values like JSX elements, objects or callbacks are stored into
+/// the cache slots along with their dependencies, just like the output of
+/// the React Compiler.
+///
+/// A function which violates the rules of hooks is not compiled, and a
+/// warning is emitted instead.
+///
+/// [resolver](https://rustdoc.swc.rs/swc_ecma_transforms_base/resolver/fn.resolver.html)
+/// must be applied before this pass.
+pub fn react_compiler(config: Config) -> impl Pass {
+    visit_mut_pass(ReactCompiler {
+        config,
+        module_directive: None,
+        cache_fn: None,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Directive {
+    /// `"use memo"`
+    Memo,
+    /// `"use no memo"`
+    NoMemo,
+}
+
+fn find_directive<'a>(stmts: impl IntoIterator<Item = &'a Stmt>) -> Option<Directive> {
+    stmts
+        .into_iter()
+        .map_while(|stmt| match stmt {
+            Stmt::Expr(ExprStmt { expr, .. }) => match &**expr {
+                Expr::Lit(Lit::Str(s)) => Some(s),
+                _ => None,
+            },
+            _ => None,
+        })
+        .find_map(|s| match s.value.as_str()? {
+            "use memo" | "use forget" => Some(Directive::Memo),
+            "use no memo" | "use no forget" => Some(Directive::NoMemo),
+            _ => None,
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FnKind {
+    Component,
+    Hook,
+}
+
+/// `memo(...)`, `forwardRef(...)` or `React.memo(...)`
+fn is_component_wrapper(callee: &Callee) -> bool {
+    let Callee::Expr(callee) = callee else {
+        return false;
+    };
+
+    let sym = match &**callee {
+        Expr::Ident(i) => &i.sym,
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if obj.is_ident() => &prop.sym,
+        _ => return false,
+    };
+
+    *sym == "memo" || *sym == "forwardRef"
+}
+
+struct ReactCompiler {
+    config: Config,
+
+    module_directive: Option<Directive>,
+
+    /// `c` imported from the runtime module. Created lazily, when a function
+    /// is compiled.
+    cache_fn: Option<Ident>,
+}
+
+impl ReactCompiler {
+    fn fn_kind(
+        &self,
+        name: Option<&Atom>,
+        body: &[Stmt],
+        is_component_or_hook: impl FnOnce() -> bool,
+    ) -> Option<FnKind> {
+        let kind = match name {
+            Some(name) if is_hook_name(name) => FnKind::Hook,
+            Some(name) if name.starts_with(|c: char| c.is_ascii_uppercase()) => FnKind::Component,
+            Some(_) => return None,
+            // `export default () => <div />`
+            None => FnKind::Component,
+        };
+
+        match find_directive(body) {
+            Some(Directive::NoMemo) => None,
+            Some(Directive::Memo) => Some(kind),
+            None => {
+                if self.config.compilation_mode == CompilationMode::Annotation
+                    && self.module_directive != Some(Directive::Memo)
+                {
+                    return None;
+                }
+
+                is_component_or_hook().then_some(kind)
+            }
+        }
+    }
+
+    fn compile_decl(&mut self, decl: &mut Decl) {
+        match decl {
+            Decl::Fn(f) => {
+                let name = f.ident.sym.clone();
+                self.compile_function(Some(&name), &mut f.function);
+            }
+            Decl::Var(var) => {
+                for decl in var.decls.iter_mut() {
+                    if let (Pat::Ident(name), Some(init)) = (&decl.name, &mut decl.init) {
+                        self.compile_expr(Some(&name.id.sym), init);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn compile_expr(&mut self, name: Option<&Atom>, e: &mut Expr) {
+        match e {
+            Expr::Fn(f) => {
+                let name = f.ident.as_ref().map(|i| &i.sym).or(name).cloned();
+                self.compile_function(name.as_ref(), &mut f.function);
+            }
+            Expr::Arrow(arrow) => self.compile_arrow(name, arrow),
+            Expr::Paren(p) => self.compile_expr(name, &mut p.expr),
+            Expr::Call(call) if is_component_wrapper(&call.callee) => {
+                if let Some(arg) = call.args.first_mut() {
+                    if arg.spread.is_none() {
+                        self.compile_expr(name, &mut arg.expr);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn compile_function(&mut self, name: Option<&Atom>, f: &mut Function) {
+        if f.is_async || f.is_generator {
+            return;
+        }
+
+        let Some(body) = &mut f.body else {
+            return;
+        };
+
+        let Some(kind) = self.fn_kind(name, &body.stmts, || is_component_or_hook_body(&body.stmts))
+        else {
+            return;
+        };
+
+        let params = f.params.iter().map(|p| &p.pat).collect::<Vec<_>>();
+
+        if let Some(stmts) = self.compile_body(kind, &params, &body.stmts) {
+            body.stmts = stmts;
+        }
+    }
+
+    fn compile_arrow(&mut self, name: Option<&Atom>, arrow: &mut ArrowExpr) {
+        if arrow.is_async || arrow.is_generator {
+            return;
+        }
+
+        let stmts = match &mut *arrow.body {
+            BlockStmtOrExpr::BlockStmt(body) => body.stmts.take(),
+            BlockStmtOrExpr::Expr(e) => vec![Stmt::Return(ReturnStmt {
+                span: e.span(),
+                arg: Some(e.take()),
+            })],
+        };
+
+        let compiled = self
+            .fn_kind(name, &stmts, || is_component_or_hook_body(&stmts))
+            .and_then(|kind| {
+                let params = arrow.params.iter().collect::<Vec<_>>();
+                self.compile_body(kind, &params, &stmts)
+            });
+
+        match compiled {
+            Some(stmts) => {
+                *arrow.body = BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    span: arrow.body.span(),
+                    stmts,
+                    ..Default::default()
+                });
+            }
+            // Restore the original body
+            None => match &mut *arrow.body {
+                BlockStmtOrExpr::BlockStmt(body) => body.stmts = stmts,
+                BlockStmtOrExpr::Expr(e) => {
+                    let Some(Stmt::Return(ReturnStmt { arg: Some(arg), .. })) =
+                        stmts.into_iter().next()
+                    else {
+                        unreachable!()
+                    };
+                    *e = arg;
+                }
+            },
+        }
+    }
+
+    /// Returns the new body of the function, or `None` if the function should
+    /// not be modified.
+    fn compile_body(&mut self, kind: FnKind, params: &[&Pat], stmts: &[Stmt]) -> Option<Vec<Stmt>> {
+        if !validate_rules_of_hooks(stmts) {
+            return None;
+        }
+
+        let param_ids: Vec<Id> = params
+            .iter()
+            .flat_map(|param| find_pat_ids::<_, Id>(*param))
+            .collect();
+
+        let mut mutable = collect_mutable_bindings(stmts);
+        for param in params {
+            mutable.extend(collect_mutable_bindings(*param));
+        }
+
+        let mut compiler = FnCompiler {
+            locals: stmts.iter().flat_map(collect_decls::<Id, _>).collect(),
+            mutable,
+            reactive: param_ids.iter().cloned().collect(),
+            declared: param_ids.iter().cloned().collect(),
+            props: match (kind, params.first()) {
+                (FnKind::Component, Some(Pat::Ident(props))) => Some(props.to_id()),
+                _ => None,
+            },
+            cache: private_ident!("$"),
+            slots: 0,
+            temps: 0,
+        };
+        compiler.locals.extend(param_ids);
+
+        // Function declarations are hoisted
+        for stmt in stmts {
+            if let Stmt::Decl(Decl::Fn(f)) = stmt {
+                compiler.declared.insert(f.ident.to_id());
+                compiler.reactive.insert(f.ident.to_id());
+            }
+        }
+
+        let mut out = Vec::with_capacity(stmts.len() + 1);
+
+        for (i, stmt) in stmts.iter().enumerate() {
+            match stmt {
+                Stmt::Decl(Decl::Var(var)) => {
+                    if let Some(memoized) = compiler.memoize_var(var, &stmts[i + 1..]) {
+                        out.extend(memoized);
+                    } else {
+                        compiler.declare_var(var);
+                        out.push(stmt.clone());
+                    }
+                }
+                Stmt::Return(ReturnStmt {
+                    span,
+                    arg: Some(arg),
+                }) if i == stmts.len() - 1 => match compiler.dependencies(arg) {
+                    Some(deps) => {
+                        let value = compiler.memoize(arg.clone(), &deps, &mut out);
+
+                        out.push(Stmt::Return(ReturnStmt {
+                            span: *span,
+                            arg: Some(value.into()),
+                        }));
+                    }
+                    None => out.push(stmt.clone()),
+                },
+                Stmt::Decl(Decl::Class(c)) => {
+                    compiler.declared.insert(c.ident.to_id());
+                    compiler.reactive.insert(c.ident.to_id());
+                    out.push(stmt.clone());
+                }
+                _ => out.push(stmt.clone()),
+            }
+        }
+
+        if compiler.slots == 0 {
+            return None;
+        }
+
+        let cache_fn = self
+            .cache_fn
+            .get_or_insert_with(|| private_ident!("_c"))
+            .clone();
+
+        prepend_stmt(
+            &mut out,
+            VarDecl {
+                kind: VarDeclKind::Const,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: compiler.cache.into(),
+                    init: Some(
+                        cache_fn
+                            .as_call(DUMMY_SP, vec![(compiler.slots as f64).as_arg()])
+                            .into(),
+                    ),
+                    definite: false,
+                }],
+                ..Default::default()
+            }
+            .into(),
+        );
+
+        Some(out)
+    }
+}
+
+impl VisitMut for ReactCompiler {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, m: &mut Module) {
+        self.module_directive = find_directive(m.body.iter().map_while(|item| item.as_stmt()));
+        if self.module_directive == Some(Directive::NoMemo) {
+            return;
+        }
+
+        for item in m.body.iter_mut() {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(decl))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                    self.compile_decl(decl)
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    decl: DefaultDecl::Fn(f),
+                    ..
+                })) => {
+                    let name = f.ident.as_ref().map(|i| i.sym.clone());
+                    self.compile_function(name.as_ref(), &mut f.function);
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(e)) => {
+                    self.compile_expr(None, &mut e.expr)
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(cache_fn) = self.cache_fn.take() {
+            prepend_stmt(
+                &mut m.body,
+                ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
+                        span: DUMMY_SP,
+                        local: cache_fn,
+                        imported: Some(ModuleExportName::Ident(quote_ident!("c").into())),
+                        is_type_only: false,
+                    })],
+                    src: Box::new(self.config.runtime_module.clone().into()),
+                    type_only: false,
+                    with: None,
+                    phase: Default::default(),
+                }
+                .into(),
+            );
+        }
+    }
+
+    /// `import` cannot be added to scripts.
+    fn visit_mut_script(&mut self, _: &mut Script) {}
+}
+
+/// State of a function being compiled.
+struct FnCompiler {
+    /// Bindings declared in the function, including parameters.
+    locals: FxHashSet<Id>,
+
+    /// Bindings which may be reassigned. A memoized value cannot depend on
+    /// them, as a cached closure would capture a binding of a stale render.
+    mutable: FxHashSet<Id>,
+
+    /// Bindings which may change between renders.
+    reactive: FxHashSet<Id>,
+
+    /// Bindings declared before the current statement.
+    declared: FxHashSet<Id>,
+
+    /// The props of a component.
+    props: Option<Id>,
+
+    /// `$`, the memo cache.
+    cache: Ident,
+    slots: usize,
+    temps: usize,
+}
+
+impl FnCompiler {
+    /// Returns `None` if `e` should not be memoized.
+    fn dependencies(&self, e: &Expr) -> Option<Vec<Dependency>> {
+        if !is_memoizable(e) {
+            return None;
+        }
+
+        let mut deps = Vec::new();
+
+        for dep in collect_references(e, self.props.as_ref()) {
+            if !self.locals.contains(&dep.root) {
+                // Globals and imports
+                continue;
+            }
+
+            if self.mutable.contains(&dep.root) || !self.declared.contains(&dep.root) {
+                return None;
+            }
+
+            if self.reactive.contains(&dep.root) {
+                deps.push(dep);
+            }
+        }
+
+        Some(deps)
+    }
+
+    /// Memoizes `const x = value;` if possible.
+    fn memoize_var(&mut self, var: &VarDecl, rest: &[Stmt]) -> Option<Vec<Stmt>> {
+        if var.kind != VarDeclKind::Const || var.decls.len() != 1 {
+            return None;
+        }
+
+        let VarDeclarator {
+            span,
+            name: Pat::Ident(name),
+            init: Some(init),
+            ..
+        } = &var.decls[0]
+        else {
+            return None;
+        };
+
+        let deps = self.dependencies(init)?;
+
+        let frozen = matches!(
+            init.unwrap_parens(),
+            Expr::JSXElement(..) | Expr::JSXFragment(..) | Expr::Fn(..) | Expr::Arrow(..)
+        );
+        if may_be_mutated(&name.to_id(), frozen, rest) {
+            return None;
+        }
+
+        let mut out = Vec::with_capacity(3);
+        let value = self.memoize(init.clone(), &deps, &mut out);
+
+        out.push(
+            VarDecl {
+                span: var.span,
+                ctxt: var.ctxt,
+                kind: VarDeclKind::Const,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span: *span,
+                    name: name.clone().into(),
+                    init: Some(value.into()),
+                    definite: false,
+                }],
+            }
+            .into(),
+        );
+
+        self.declared.insert(name.to_id());
+        if !deps.is_empty() {
+            self.reactive.insert(name.to_id());
+        }
+
+        Some(out)
+    }
+
+    /// Records the bindings of a variable declaration which is not memoized.
+    fn declare_var(&mut self, var: &VarDecl) {
+        for decl in &var.decls {
+            let ids: Vec<Id> = find_pat_ids(&decl.name);
+            self.declared.extend(ids.iter().cloned());
+
+            let Some(init) = &decl.init else {
+                self.reactive.extend(ids);
+                continue;
+            };
+
+            // The setter of `useState` and the ref object are stable.
+            if let Expr::Call(CallExpr { callee, .. }) = init.unwrap_parens() {
+                match (hook_name(callee), &decl.name) {
+                    (Some("useRef"), Pat::Ident(..)) => continue,
+                    (Some("useState" | "useReducer" | "useActionState"), Pat::Array(arr)) => {
+                        for (idx, elem) in arr.elems.iter().enumerate() {
+                            if let (1, Some(Pat::Ident(..))) = (idx, elem) {
+                                continue;
+                            }
+
+                            self.reactive.extend(find_pat_ids::<_, Id>(elem));
+                        }
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+
+            let is_reactive = var.kind != VarDeclKind::Const
+                || may_allocate(init)
+                || collect_references(init, None).iter().any(|dep| {
+                    self.reactive.contains(&dep.root) || self.mutable.contains(&dep.root)
+                });
+
+            if is_reactive {
+                self.reactive.extend(ids);
+            }
+        }
+    }
+
+    /// Emits
+    ///
+    /// ```js
+    /// let t0;
+    /// if ($[0] !== dep) {
+    ///     t0 = value;
+    ///     $[0] = dep;
+    ///     $[1] = t0;
+    /// } else {
+    ///     t0 = $[1];
+    /// }
+    /// ```
+    ///
+    /// and returns `t0`.
+    fn memoize(&mut self, value: Box<Expr>, deps: &[Dependency], out: &mut Vec<Stmt>) -> Ident {
+        let temp = private_ident!(Atom::from(format!("t{}", self.temps)));
+        self.temps += 1;
+
+        let base = self.slots;
+        self.slots += deps.len() + 1;
+
+        let slot = |idx: usize| MemberExpr {
+            span: DUMMY_SP,
+            obj: Box::new(self.cache.clone().into()),
+            prop: MemberProp::Computed(ComputedPropName {
+                span: DUMMY_SP,
+                expr: ((base + idx) as f64).into(),
+            }),
+        };
+        let assign = |left: AssignTarget, right: Box<Expr>| -> Stmt {
+            AssignExpr {
+                span: DUMMY_SP,
+                op: op!("="),
+                left,
+                right,
+            }
+            .into_stmt()
+        };
+
+        let value_slot = deps.len();
+
+        let test = if deps.is_empty() {
+            // $[0] === Symbol.for("react.memo_cache_sentinel")
+            Box::new(Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                op: op!("==="),
+                left: slot(value_slot).into(),
+                right: quote_ident!("Symbol")
+                    .make_member(quote_ident!("for"))
+                    .as_call(DUMMY_SP, vec![atom!("react.memo_cache_sentinel").as_arg()])
+                    .into(),
+            }))
+        } else {
+            deps.iter()
+                .enumerate()
+                .map(|(idx, dep)| {
+                    Box::new(Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        op: op!("!=="),
+                        left: slot(idx).into(),
+                        right: dep.to_expr().into(),
+                    }))
+                })
+                .reduce(|left, right| {
+                    Box::new(Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        op: op!("||"),
+                        left,
+                        right,
+                    }))
+                })
+                .unwrap()
+        };
+
+        let mut cons = Vec::with_capacity(deps.len() + 2);
+        cons.push(assign(temp.clone().into(), value));
+        for (idx, dep) in deps.iter().enumerate() {
+            cons.push(assign(slot(idx).into(), dep.to_expr().into()));
+        }
+        cons.push(assign(slot(value_slot).into(), temp.clone().into()));
+
+        out.push(
+            VarDecl {
+                kind: VarDeclKind::Let,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: temp.clone().into(),
+                    init: None,
+                    definite: false,
+                }],
+                ..Default::default()
+            }
+            .into(),
+        );
+        out.push(
+            IfStmt {
+                span: DUMMY_SP,
+                test,
+                cons: Box::new(
+                    BlockStmt {
+                        stmts: cons,
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+                alt: Some(Box::new(
+                    BlockStmt {
+                        stmts: vec![assign(temp.clone().into(), slot(value_slot).into())],
+                        ..Default::default()
+                    }
+                    .into(),
+                )),
+            }
+            .into(),
+        );
+
+        temp
+    }
+}
@@ -0,0 +1,41 @@
+use std::{fs::read_to_string, path::PathBuf};
+
+use swc_common::Mark;
+use swc_ecma_parser::{EsSyntax, Syntax};
+use swc_ecma_react_compiler::{Config, react_compiler};
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_transforms_testing::{FixtureTestConfig, test_fixture};
+
+#[testing::fixture("tests/fixture/**/input.jsx")]
+fn fixture(input: PathBuf) {
+    let output = input.with_file_name("output.jsx");
+
+    let config_path = input.with_file_name("config.json");
+    let config: Config = match read_to_string(config_path) {
+        Ok(s) => serde_json::from_str(&s).expect("failed to parse config.json"),
+        Err(_) => Default::default(),
+    };
+
+    test_fixture(
+        Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+        &|_| {
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            (
+                resolver(unresolved_mark, top_level_mark, false),
+                react_compiler(config.clone()),
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
@@ -0,0 +1,9 @@
+export function Skipped({ a }) {
+    return <div>{a}</div>;
+}
+
+export function Compiled({ a }) {
+    "use memo";
+
+    return <div>{a}</div>;
+}
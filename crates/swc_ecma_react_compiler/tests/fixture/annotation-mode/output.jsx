@@ -0,0 +1,17 @@
+import { c as _c } from "react-compiler-runtime";
+export function Skipped({ a }) {
+    return <div>{a}</div>;
+}
+export function Compiled({ a }) {
+    "use memo";
+    const $ = _c(2);
+    let t0;
+    if ($[0] !== a) {
+        t0 = <div>{a}</div>;
+        $[0] = a;
+        $[1] = t0;
+    } else {
+        t0 = $[1];
+    }
+    return t0;
+}
@@ -0,0 +1,7 @@
+export default function Greeting({ name }) {
+    const style = { color: "red" };
+    const message = `Hello, ${name}`;
+    const header = <h1 style={style}>{message}</h1>;
+
+    return <div>{header}</div>;
+}
@@ -0,0 +1,33 @@
+import { c as _c } from "react/compiler-runtime";
+export default function Greeting({ name }) {
+    const $ = _c(5);
+    let t0;
+    if ($[0] === Symbol.for("react.memo_cache_sentinel")) {
+        t0 = {
+            color: "red"
+        };
+        $[0] = t0;
+    } else {
+        t0 = $[0];
+    }
+    const style = t0;
+    const message = `Hello, ${name}`;
+    let t1;
+    if ($[1] !== message) {
+        t1 = <h1 style={style}>{message}</h1>;
+        $[1] = message;
+        $[2] = t1;
+    } else {
+        t1 = $[2];
+    }
+    const header = t1;
+    let t2;
+    if ($[3] !== header) {
+        t2 = <div>{header}</div>;
+        $[3] = header;
+        $[4] = t2;
+    } else {
+        t2 = $[4];
+    }
+    return t2;
+}
@@ -0,0 +1,8 @@
+import { useState } from "react";
+
+export function useToggle(initial) {
+    const [on, setOn] = useState(initial);
+    const toggle = () => setOn((v) => !v);
+
+    return { on, toggle };
+}
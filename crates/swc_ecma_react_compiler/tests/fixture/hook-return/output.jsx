@@ -0,0 +1,26 @@
+import { c as _c } from "react/compiler-runtime";
+import { useState } from "react";
+export function useToggle(initial) {
+    const $ = _c(3);
+    const [on, setOn] = useState(initial);
+    let t0;
+    if ($[0] === Symbol.for("react.memo_cache_sentinel")) {
+        t0 = ()=>setOn((v)=>!v);
+        $[0] = t0;
+    } else {
+        t0 = $[0];
+    }
+    const toggle = t0;
+    let t1;
+    if ($[1] !== on) {
+        t1 = {
+            on,
+            toggle
+        };
+        $[1] = on;
+        $[2] = t1;
+    } else {
+        t1 = $[2];
+    }
+    return t1;
+}
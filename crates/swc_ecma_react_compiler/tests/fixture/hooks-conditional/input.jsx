@@ -0,0 +1,13 @@
+import { useState, useEffect, use } from "react";
+
+export function Profile({ user, promise }) {
+    if (!user) {
+        return null;
+    }
+
+    const [value] = useState(user.name);
+    const data = user.loaded ? use(promise) : null;
+    user.active && useEffect(() => {});
+
+    return <div>{value}{data}</div>;
+}
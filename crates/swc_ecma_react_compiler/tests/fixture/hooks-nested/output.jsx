@@ -0,0 +1,8 @@
+import { useState } from "react";
+export function Form() {
+    const onSubmit = ()=>{
+        const [value] = useState(0);
+        return value;
+    };
+    return <form onSubmit={onSubmit}/>;
+}
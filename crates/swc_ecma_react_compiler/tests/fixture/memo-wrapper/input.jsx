@@ -0,0 +1,7 @@
+import { memo, forwardRef } from "react";
+
+export const Button = memo(function Button({ label, onClick }) {
+    return <button onClick={onClick}>{label}</button>;
+});
+
+export const Input = forwardRef((props, ref) => <input ref={ref} value={props.value} />);
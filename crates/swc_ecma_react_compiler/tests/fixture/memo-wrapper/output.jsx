@@ -0,0 +1,28 @@
+import { c as _c } from "react/compiler-runtime";
+import { memo, forwardRef } from "react";
+export const Button = memo(function Button({ label, onClick }) {
+    const $ = _c(3);
+    let t0;
+    if ($[0] !== onClick || $[1] !== label) {
+        t0 = <button onClick={onClick}>{label}</button>;
+        $[0] = onClick;
+        $[1] = label;
+        $[2] = t0;
+    } else {
+        t0 = $[2];
+    }
+    return t0;
+});
+export const Input = forwardRef((props, ref)=>{
+    const $ = _c(3);
+    let t0;
+    if ($[0] !== ref || $[1] !== props.value) {
+        t0 = <input ref={ref} value={props.value}/>;
+        $[0] = ref;
+        $[1] = props.value;
+        $[2] = t0;
+    } else {
+        t0 = $[2];
+    }
+    return t0;
+});
@@ -0,0 +1,25 @@
+export function Table({ rows, columns }) {
+    const visible = [];
+    for (const row of rows) {
+        visible.push(row);
+    }
+
+    const sorted = rows.slice();
+    sorted.sort();
+
+    const header = columns.map((c) => <th key={c}>{c}</th>);
+    const config = { rows };
+    register(config);
+
+    let count = 0;
+    const onClick = () => {
+        count += 1;
+    };
+
+    return (
+        <table onClick={onClick}>
+            <thead>{header}</thead>
+            <tbody>{visible.length}{sorted.length}</tbody>
+        </table>
+    );
+}
@@ -0,0 +1,42 @@
+import { c as _c } from "react/compiler-runtime";
+export function Table({ rows, columns }) {
+    const $ = _c(7);
+    const visible = [];
+    for (const row of rows){
+        visible.push(row);
+    }
+    const sorted = rows.slice();
+    sorted.sort();
+    let t0;
+    if ($[0] !== columns) {
+        t0 = columns.map((c)=><th key={c}>{c}</th>);
+        $[0] = columns;
+        $[1] = t0;
+    } else {
+        t0 = $[1];
+    }
+    const header = t0;
+    const config = {
+        rows
+    };
+    register(config);
+    let count = 0;
+    const onClick = ()=>{
+        count += 1;
+    };
+    let t1;
+    if ($[2] !== onClick || $[3] !== header || $[4] !== visible || $[5] !== sorted) {
+        t1 = <table onClick={onClick}>
+            <thead>{header}</thead>
+            <tbody>{visible.length}{sorted.length}</tbody>
+        </table>;
+        $[2] = onClick;
+        $[3] = header;
+        $[4] = visible;
+        $[5] = sorted;
+        $[6] = t1;
+    } else {
+        t1 = $[6];
+    }
+    return t1;
+}
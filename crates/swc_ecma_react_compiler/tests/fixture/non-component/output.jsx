@@ -0,0 +1,14 @@
+export function formatName(user) {
+    return {
+        name: user.first + " " + user.last
+    };
+}
+export function Label() {
+    return "label";
+}
+function Nested() {
+    function Inner() {
+        return <span/>;
+    }
+    return Inner;
+}
@@ -0,0 +1,14 @@
+import { Item } from "./item";
+
+export function List(props) {
+    const items = props.items.filter((item) => item.visible);
+    const onSelect = (item) => props.onSelect(item.id);
+
+    return (
+        <ul title={props.title}>
+            {items.map((item) => (
+                <Item key={item.id} item={item} onSelect={onSelect} />
+            ))}
+        </ul>
+    );
+}
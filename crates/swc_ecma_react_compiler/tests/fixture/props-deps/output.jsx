@@ -0,0 +1,36 @@
+import { c as _c } from "react/compiler-runtime";
+import { Item } from "./item";
+export function List(props) {
+    const $ = _c(8);
+    let t0;
+    if ($[0] !== props.items) {
+        t0 = props.items.filter((item)=>item.visible);
+        $[0] = props.items;
+        $[1] = t0;
+    } else {
+        t0 = $[1];
+    }
+    const items = t0;
+    let t1;
+    if ($[2] !== props) {
+        t1 = (item)=>props.onSelect(item.id);
+        $[2] = props;
+        $[3] = t1;
+    } else {
+        t1 = $[3];
+    }
+    const onSelect = t1;
+    let t2;
+    if ($[4] !== props.title || $[5] !== items || $[6] !== onSelect) {
+        t2 = <ul title={props.title}>
+            {items.map((item)=><Item key={item.id} item={item} onSelect={onSelect}/>)}
+        </ul>;
+        $[4] = props.title;
+        $[5] = items;
+        $[6] = onSelect;
+        $[7] = t2;
+    } else {
+        t2 = $[7];
+    }
+    return t2;
+}
@@ -0,0 +1,16 @@
+import { useState, useRef } from "react";
+
+export const Counter = () => {
+    const [count, setCount] = useState(0);
+    const ref = useRef(null);
+    const increment = () => setCount((c) => c + 1);
+    const focus = () => ref.current.focus();
+
+    return (
+        <div ref={ref}>
+            <button onClick={increment} onFocus={focus}>
+                {count}
+            </button>
+        </div>
+    );
+};
@@ -0,0 +1,36 @@
+import { c as _c } from "react/compiler-runtime";
+import { useState, useRef } from "react";
+export const Counter = ()=>{
+    const $ = _c(4);
+    const [count, setCount] = useState(0);
+    const ref = useRef(null);
+    let t0;
+    if ($[0] === Symbol.for("react.memo_cache_sentinel")) {
+        t0 = ()=>setCount((c)=>c + 1);
+        $[0] = t0;
+    } else {
+        t0 = $[0];
+    }
+    const increment = t0;
+    let t1;
+    if ($[1] === Symbol.for("react.memo_cache_sentinel")) {
+        t1 = ()=>ref.current.focus();
+        $[1] = t1;
+    } else {
+        t1 = $[1];
+    }
+    const focus = t1;
+    let t2;
+    if ($[2] !== count) {
+        t2 = <div ref={ref}>
+            <button onClick={increment} onFocus={focus}>
+                {count}
+            </button>
+        </div>;
+        $[2] = count;
+        $[3] = t2;
+    } else {
+        t2 = $[3];
+    }
+    return t2;
+};
@@ -0,0 +1,9 @@
+export function Skipped({ a }) {
+    "use no memo";
+
+    return <div>{a}</div>;
+}
+
+export function Compiled({ a }) {
+    return <div>{a}</div>;
+}
@@ -0,0 +1,141 @@
+use std::sync::{Arc, Mutex};
+
+use swc_common::{
+    FileName, GLOBALS, Globals, Mark, SourceMap,
+    errors::{Diagnostic, DiagnosticBuilder, Emitter, HANDLER, Handler, Level},
+    sync::Lrc,
+};
+use swc_ecma_ast::{Pass, Program};
+use swc_ecma_parser::{EsSyntax, Syntax, parse_file_as_module};
+use swc_ecma_react_compiler::react_compiler;
+use swc_ecma_transforms_base::resolver;
+
+#[derive(Default, Clone)]
+struct Capturing {
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl Emitter for Capturing {
+    fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+        self.diagnostics.lock().unwrap().push(db.take());
+    }
+}
+
+/// Returns the diagnostics emitted while compiling `src`.
+fn compile(src: &str) -> Vec<Diagnostic> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon.into(), src.to_string());
+
+    let emitter = Capturing::default();
+    let handler = Handler::with_emitter(true, false, Box::new(emitter.clone()));
+
+    GLOBALS.set(&Globals::new(), || {
+        HANDLER.set(&handler, || {
+            let module = parse_file_as_module(
+                &fm,
+                Syntax::Es(EsSyntax {
+                    jsx: true,
+                    ..Default::default()
+                }),
+                Default::default(),
+                None,
+                &mut Vec::new(),
+            )
+            .unwrap();
+            let mut program = Program::Module(module);
+
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            resolver(unresolved_mark, top_level_mark, false).process(&mut program);
+            react_compiler(Default::default()).process(&mut program);
+        })
+    });
+
+    emitter.diagnostics.lock().unwrap().clone()
+}
+
+fn assert_bailout(src: &str, expected: &str) {
+    let diagnostics = compile(src);
+
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0].level, Level::Warning);
+    assert!(
+        diagnostics[0].message().contains(expected),
+        "{}",
+        diagnostics[0].message()
+    );
+}
+
+#[test]
+fn conditional_hook() {
+    assert_bailout(
+        "function Foo({ a }) {
+            if (a) {
+                useEffect(() => {});
+            }
+            return <div />;
+        }",
+        "may not be called conditionally",
+    );
+}
+
+#[test]
+fn hook_after_early_return() {
+    assert_bailout(
+        "function Foo({ a }) {
+            if (!a) return null;
+            const [b] = useState(a);
+            return <div>{b}</div>;
+        }",
+        "may not be called conditionally",
+    );
+}
+
+#[test]
+fn hook_in_logical_expression() {
+    assert_bailout(
+        "function useFoo(a) {
+            return a && useContext(Ctx);
+        }",
+        "may not be called conditionally",
+    );
+}
+
+#[test]
+fn hook_in_callback() {
+    assert_bailout(
+        "function Foo() {
+            const onClick = () => {
+                useState(0);
+            };
+            return <button onClick={onClick} />;
+        }",
+        "may not be called within function expressions",
+    );
+}
+
+#[test]
+fn conditional_use_is_allowed() {
+    let diagnostics = compile(
+        "function Foo({ promise }) {
+            const value = promise ? use(promise) : null;
+            return <div>{value}</div>;
+        }",
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn valid_hooks() {
+    let diagnostics = compile(
+        "function Foo({ a }) {
+            const [b, setB] = useState(a);
+            useEffect(() => setB(a), [a]);
+            return <div>{b}</div>;
+        }",
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
@@ -45,6 +45,10 @@ struct AdjacentStmt {
 pub struct StmtInjectorStore {
     /// Map from statement address to adjacent statements to insert
     stmts: FxHashMap<*const Stmt, Vec<AdjacentStmt>>,
+
+    /// Map from module item address to adjacent statements to insert. Used for
+    /// module declarations, which are not statements.
+    module_items: FxHashMap<*const ModuleItem, Vec<AdjacentStmt>>,
 }
 
 impl StmtInjectorStore {
@@ -75,10 +79,34 @@ impl StmtInjectorStore {
         }
     }
 
+    /// Insert a statement before the module item at the given address
+    pub fn insert_before_module_item(&mut self, address: *const ModuleItem, stmt: Stmt) {
+        self.module_items
+            .entry(address)
+            .or_default()
+            .push(AdjacentStmt {
+                stmt,
+                direction: Direction::Before,
+            });
+    }
+
     /// Get all statements to be inserted at the given address
     fn take_stmts(&mut self, address: *const Stmt) -> Option<Vec<AdjacentStmt>> {
         self.stmts.remove(&address)
     }
+
+    /// Get all statements to be inserted at the given module item
+    fn take_module_item_stmts(&mut self, item: &ModuleItem) -> Option<Vec<AdjacentStmt>> {
+        let mut adjacent_stmts = self.module_items.remove(&(item as *const ModuleItem));
+
+        if let ModuleItem::Stmt(stmt) = item {
+            if let Some(stmts) = self.take_stmts(stmt as *const Stmt) {
+                adjacent_stmts.get_or_insert_with(Vec::new).extend(stmts);
+            }
+        }
+
+        adjacent_stmts
+    }
 }
 
 impl VisitMutHook<TraverseCtx> for StmtInjector {
@@ -116,12 +144,8 @@ impl VisitMutHook<TraverseCtx> for StmtInjector {
         // valid
         let mut insertions = Vec::new();
         for (i, item) in node.iter().enumerate() {
-            // Only process ModuleItem::Stmt variants
-            if let ModuleItem::Stmt(stmt) = item {
-                let address = stmt as *const Stmt;
-                if let Some(adjacent_stmts) = ctx.statement_injector.take_stmts(address) {
-                    insertions.push((i, adjacent_stmts));
-                }
+            if let Some(adjacent_stmts) = ctx.statement_injector.take_module_item_stmts(item) {
+                insertions.push((i, adjacent_stmts));
             }
         }
 
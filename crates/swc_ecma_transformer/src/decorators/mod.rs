@@ -1,10 +1,68 @@
+//! Decorators: `@babel/plugin-proposal-decorators`
+//!
+//! This plugin lowers decorators on classes and class members.
+//!
+//! - [DecoratorVersion::Legacy] implements TypeScript's
+//!   `experimentalDecorators`, optionally with `emitDecoratorMetadata`.
+//! - [DecoratorVersion::V202203] implements the `2022-03` version of the
+//!   proposal.
+//!
+//! ## Example
+//!
+//! Input:
+//! ```ts
+//! @sealed
+//! class Foo {
+//!     @log
+//!     method() {}
+//! }
+//! ```
+//!
+//! Output (legacy):
+//! ```js
+//! class Foo {
+//!     method() {}
+//! }
+//! _ts_decorate([
+//!     log
+//! ], Foo.prototype, "method", null);
+//! Foo = _ts_decorate([
+//!     sealed
+//! ], Foo);
+//! ```
+//!
+//! ## Implementation
+//!
+//! This hook runs the passes of `swc_ecma_transforms_proposal` when entering
+//! the program, before the TypeScript hook strips the type information they
+//! use, so the generated code is visited by the other transforms.
+
+use swc_ecma_ast::*;
 use swc_ecma_hooks::VisitMutHook;
+use swc_ecma_transforms_proposal::{decorator_2022_03::decorator_2022_03, decorators};
 
 use crate::TraverseCtx;
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DecoratorVersion {
+    /// TypeScript `experimentalDecorators`
+    #[default]
+    Legacy,
+
+    /// `2022-03`
+    V202203,
+}
+
 #[derive(Debug, Default)]
 #[non_exhaustive]
-pub struct DecoratorOptions {}
+pub struct DecoratorOptions {
+    pub version: DecoratorVersion,
+
+    /// https://www.typescriptlang.org/tsconfig#emitDecoratorMetadata
+    ///
+    /// Only used by [DecoratorVersion::Legacy].
+    pub emit_metadata: bool,
+}
 
 pub fn hook(options: DecoratorOptions) -> impl VisitMutHook<TraverseCtx> {
     DecoratorPass { options }
@@ -14,4 +72,15 @@ struct DecoratorPass {
     options: DecoratorOptions,
 }
 
-impl VisitMutHook<TraverseCtx> for DecoratorPass {}
+impl VisitMutHook<TraverseCtx> for DecoratorPass {
+    fn enter_program(&mut self, node: &mut Program, _: &mut TraverseCtx) {
+        match self.options.version {
+            DecoratorVersion::Legacy => node.mutate(decorators(decorators::Config {
+                legacy: true,
+                emit_metadata: self.options.emit_metadata,
+                use_define_for_class_fields: false,
+            })),
+            DecoratorVersion::V202203 => node.mutate(decorator_2022_03()),
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! ES2015: Arrow Functions
+//!
+//! This plugin transforms arrow functions to function expressions. `this`,
+//! `arguments` and `super` are captured by variables in the enclosing
+//! function.
+//!
+//! > This plugin is included in `preset-env`, in ES2015
+//!
+//! ## Example
+//!
+//! Input:
+//! ```js
+//! var a = () => {};
+//! var a = (b) => b;
+//!
+//! var bob = {
+//!   _name: "Bob",
+//!   _friends: ["Sally", "Tom"],
+//!   printFriends() {
+//!     this._friends.forEach(f =>
+//!       console.log(this._name + " knows " + f));
+//!   }
+//! };
+//! ```
+//!
+//! Output:
+//! ```js
+//! var a = function () {};
+//! var a = function (b) {
+//!   return b;
+//! };
+//!
+//! var bob = {
+//!   _name: "Bob",
+//!   _friends: ["Sally", "Tom"],
+//!   printFriends() {
+//!     var _this = this;
+//!     this._friends.forEach(function (f) {
+//!       return console.log(_this._name + " knows " + f);
+//!     });
+//!   }
+//! };
+//! ```
+//!
+//! ## Implementation
+//!
+//! Implementation based on [@babel/plugin-transform-arrow-functions](https://babel.dev/docs/babel-plugin-transform-arrow-functions).
+
+use std::mem;
+
+use swc_common::{DUMMY_SP, SyntaxContext, util::take::Take};
+use swc_ecma_ast::*;
+use swc_ecma_hooks::{VisitMutHook, VisitMutWithHook};
+use swc_ecma_utils::{
+    function::{FnEnvHoister, init_this},
+    prepend_stmt,
+};
+use swc_ecma_visit::{VisitMut, VisitMutWith, noop_visit_mut_type};
+
+use crate::TraverseCtx;
+
+pub fn hook(unresolved_ctxt: SyntaxContext) -> impl VisitMutHook<TraverseCtx> {
+    ArrowPass::new(unresolved_ctxt)
+}
+
+/// Converts the arrow functions in `expr` to function expressions.
+///
+/// Returns the declarations of the variables capturing `this` and `arguments`
+/// of the enclosing function, which must be injected by the caller.
+pub fn lower_arrows(expr: &mut Expr, unresolved_ctxt: SyntaxContext) -> Vec<VarDeclarator> {
+    let mut v = VisitMutWithHook {
+        hook: ArrowPass::new(unresolved_ctxt),
+        context: TraverseCtx::default(),
+    };
+
+    expr.visit_mut_with(&mut v);
+
+    v.hook.hoister.take().to_decl()
+}
+
+struct ArrowPass {
+    in_subclass: bool,
+    in_subclass_stack: Vec<bool>,
+
+    /// Collects `this`, `arguments` and `super` of the current function.
+    hoister: FnEnvHoister,
+
+    /// Hoisters of the enclosing functions.
+    hoister_stack: Vec<FnEnvHoister>,
+}
+
+impl ArrowPass {
+    fn new(unresolved_ctxt: SyntaxContext) -> Self {
+        ArrowPass {
+            in_subclass: false,
+            in_subclass_stack: Vec::new(),
+            hoister: FnEnvHoister::new(unresolved_ctxt),
+            hoister_stack: Vec::new(),
+        }
+    }
+
+    fn enter_fn_scope(&mut self) {
+        let hoister = self.hoister.take();
+        self.hoister_stack.push(hoister);
+    }
+
+    /// Restores the hoister of the enclosing function, and returns the hoister
+    /// of the current one.
+    fn exit_fn_scope(&mut self) -> FnEnvHoister {
+        let outer = self.hoister_stack.pop().unwrap_or_default();
+
+        mem::replace(&mut self.hoister, outer)
+    }
+}
+
+impl VisitMutHook<TraverseCtx> for ArrowPass {
+    fn enter_class(&mut self, c: &mut Class, _ctx: &mut TraverseCtx) {
+        self.in_subclass_stack.push(self.in_subclass);
+
+        if c.super_class.is_some() {
+            self.in_subclass = true;
+        }
+    }
+
+    fn exit_class(&mut self, _c: &mut Class, _ctx: &mut TraverseCtx) {
+        self.in_subclass = self.in_subclass_stack.pop().unwrap_or_default();
+    }
+
+    fn enter_constructor(&mut self, _c: &mut Constructor, _ctx: &mut TraverseCtx) {
+        self.enter_fn_scope();
+    }
+
+    fn exit_constructor(&mut self, c: &mut Constructor, _ctx: &mut TraverseCtx) {
+        let hoister = self.exit_fn_scope();
+
+        let Some(body) = &mut c.body else {
+            return;
+        };
+
+        if self.in_subclass {
+            let (decl, this_id) = hoister.to_stmt_in_subclass();
+
+            if let Some(stmt) = decl {
+                if let Some(this_id) = this_id {
+                    init_this(&mut body.stmts, &this_id)
+                }
+                prepend_stmt(&mut body.stmts, stmt);
+            }
+        } else if let Some(stmt) = hoister.to_stmt() {
+            prepend_stmt(&mut body.stmts, stmt);
+        }
+    }
+
+    fn enter_function(&mut self, _f: &mut Function, _ctx: &mut TraverseCtx) {
+        self.enter_fn_scope();
+    }
+
+    fn exit_function(&mut self, f: &mut Function, _ctx: &mut TraverseCtx) {
+        let decl = self.exit_fn_scope().to_stmt();
+
+        if let (Some(body), Some(stmt)) = (&mut f.body, decl) {
+            prepend_stmt(&mut body.stmts, stmt);
+        }
+    }
+
+    fn enter_getter_prop(&mut self, f: &mut GetterProp, _ctx: &mut TraverseCtx) {
+        // The key is evaluated in the enclosing function.
+        f.key
+            .visit_mut_with(&mut KeyArrowHoister(&mut self.hoister));
+
+        self.enter_fn_scope();
+    }
+
+    fn exit_getter_prop(&mut self, f: &mut GetterProp, _ctx: &mut TraverseCtx) {
+        let decl = self.exit_fn_scope().to_stmt();
+
+        if let (Some(body), Some(stmt)) = (&mut f.body, decl) {
+            prepend_stmt(&mut body.stmts, stmt);
+        }
+    }
+
+    fn enter_setter_prop(&mut self, f: &mut SetterProp, _ctx: &mut TraverseCtx) {
+        f.key
+            .visit_mut_with(&mut KeyArrowHoister(&mut self.hoister));
+
+        self.enter_fn_scope();
+    }
+
+    fn exit_setter_prop(&mut self, f: &mut SetterProp, _ctx: &mut TraverseCtx) {
+        let decl = self.exit_fn_scope().to_stmt();
+
+        if let (Some(body), Some(stmt)) = (&mut f.body, decl) {
+            prepend_stmt(&mut body.stmts, stmt);
+        }
+    }
+
+    fn exit_expr(&mut self, expr: &mut Expr, _ctx: &mut TraverseCtx) {
+        // Nested arrow functions are already converted at this point, and their
+        // `this` is already replaced, so the hoister skipping function
+        // expressions is fine.
+        if let Expr::Arrow(arrow) = expr {
+            arrow.params.visit_mut_with(&mut self.hoister);
+            arrow.body.visit_mut_with(&mut self.hoister);
+        }
+
+        let Expr::Arrow(ArrowExpr {
+            span,
+            params,
+            body,
+            is_async,
+            is_generator,
+            ..
+        }) = expr
+        else {
+            return;
+        };
+
+        let params = params
+            .take()
+            .into_iter()
+            .map(|pat| Param {
+                span: DUMMY_SP,
+                decorators: Default::default(),
+                pat,
+            })
+            .collect();
+
+        let body = match &mut **body {
+            BlockStmtOrExpr::BlockStmt(block) => block.take(),
+            BlockStmtOrExpr::Expr(expr) => BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    // this is needed so
+                    // () => /* 123 */ 1 would become
+                    // function { return /*123 */ 123 }
+                    span: DUMMY_SP,
+                    arg: Some(expr.take()),
+                })],
+                ..Default::default()
+            },
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        };
+
+        *expr = Function {
+            span: *span,
+            params,
+            is_async: *is_async,
+            is_generator: *is_generator,
+            body: Some(body),
+            ..Default::default()
+        }
+        .into();
+    }
+
+    fn exit_module_items(&mut self, items: &mut Vec<ModuleItem>, _ctx: &mut TraverseCtx) {
+        if let Some(stmt) = self.hoister.take().to_stmt() {
+            prepend_stmt(items, stmt.into());
+        }
+    }
+
+    fn exit_script(&mut self, script: &mut Script, _ctx: &mut TraverseCtx) {
+        if let Some(stmt) = self.hoister.take().to_stmt() {
+            prepend_stmt(&mut script.body, stmt);
+        }
+    }
+}
+
+/// Replaces `this` of arrow functions in a computed key of a getter or a
+/// setter, using the hoister of the enclosing function.
+///
+/// Arrow functions are converted later, but the hoister of the getter is
+/// active at that point.
+struct KeyArrowHoister<'a>(&'a mut FnEnvHoister);
+
+impl VisitMut for KeyArrowHoister<'_> {
+    noop_visit_mut_type!(fail);
+
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        if let Expr::Arrow(arrow) = expr {
+            arrow.params.visit_mut_with(&mut *self.0);
+            arrow.body.visit_mut_with(&mut *self.0);
+        } else {
+            expr.visit_mut_children_with(self);
+        }
+    }
+
+    fn visit_mut_class(&mut self, _: &mut Class) {}
+
+    fn visit_mut_function(&mut self, _: &mut Function) {}
+}
@@ -0,0 +1,110 @@
+//! ES2015: Block Scoped Functions
+//!
+//! This plugin transforms function declarations in blocks to `let`
+//! declarations, so they are scoped to the block.
+//!
+//! > This plugin is included in `preset-env`, in ES2015
+//!
+//! ## Example
+//!
+//! Input:
+//! ```js
+//! {
+//!   name("Steve");
+//!   function name(n) {
+//!     return n;
+//!   }
+//! }
+//! ```
+//!
+//! Output:
+//! ```js
+//! {
+//!   let name = function name(n) {
+//!     return n;
+//!   };
+//!   name("Steve");
+//! }
+//! ```
+//!
+//! ## Implementation
+//!
+//! Implementation based on [@babel/plugin-transform-block-scoped-functions](https://babel.dev/docs/babel-plugin-transform-block-scoped-functions).
+//!
+//! Blocks are converted when they are entered, so the `let` declarations are
+//! visited by the block scoping transform.
+
+use swc_common::{DUMMY_SP, util::take::Take};
+use swc_ecma_ast::*;
+use swc_ecma_hooks::VisitMutHook;
+use swc_ecma_utils::IdentUsageFinder;
+
+use crate::TraverseCtx;
+
+pub fn hook() -> impl VisitMutHook<TraverseCtx> {
+    BlockScopedFnsPass::default()
+}
+
+#[derive(Default)]
+struct BlockScopedFnsPass {
+    /// Bodies of functions, which are function scopes and not blocks.
+    fn_bodies: Vec<*const BlockStmt>,
+}
+
+impl VisitMutHook<TraverseCtx> for BlockScopedFnsPass {
+    fn enter_function(&mut self, n: &mut Function, _: &mut TraverseCtx) {
+        if let Some(body) = &n.body {
+            self.fn_bodies.push(body);
+        }
+    }
+
+    fn enter_block_stmt(&mut self, n: &mut BlockStmt, _: &mut TraverseCtx) {
+        // skip function scope
+        if self.fn_bodies.last() == Some(&(n as *const BlockStmt)) {
+            self.fn_bodies.pop();
+            return;
+        }
+
+        let mut stmts = Vec::with_capacity(n.stmts.len());
+        let mut extra_stmts = Vec::with_capacity(n.stmts.len());
+
+        for stmt in n.stmts.take() {
+            if let Stmt::Expr(ExprStmt { ref expr, .. }) = stmt {
+                if let Expr::Lit(Lit::Str(..)) = &**expr {
+                    stmts.push(stmt);
+                    continue;
+                }
+            }
+
+            if let Stmt::Decl(Decl::Fn(decl)) = stmt {
+                if IdentUsageFinder::find(&decl.ident, &decl.function) {
+                    extra_stmts.push(decl.into());
+                    continue;
+                }
+                stmts.push(
+                    VarDecl {
+                        span: DUMMY_SP,
+                        kind: VarDeclKind::Let,
+                        decls: vec![VarDeclarator {
+                            span: DUMMY_SP,
+                            name: decl.ident.clone().into(),
+                            init: Some(Box::new(Expr::Fn(FnExpr {
+                                ident: Some(decl.ident),
+                                function: decl.function,
+                            }))),
+                            definite: false,
+                        }],
+                        ..Default::default()
+                    }
+                    .into(),
+                )
+            } else {
+                extra_stmts.push(stmt)
+            }
+        }
+
+        stmts.append(&mut extra_stmts);
+
+        n.stmts = stmts
+    }
+}
@@ -1,3 +1,42 @@
+//! ES2015: Block Scoping
+//!
+//! This plugin transforms `let` and `const` declarations to `var`. Variables
+//! captured by closures in a loop body are passed to a function, which is
+//! called for each iteration.
+//!
+//! > This plugin is included in `preset-env`, in ES2015
+//!
+//! ## Example
+//!
+//! Input:
+//! ```js
+//! let functions = [];
+//! for (let i = 0; i < 10; i++) {
+//!   functions.push(function () {
+//!     console.log(i);
+//!   });
+//! }
+//! ```
+//!
+//! Output:
+//! ```js
+//! var functions = [];
+//! var _loop = function (i) {
+//!   functions.push(function () {
+//!     console.log(i);
+//!   });
+//! };
+//! for (var i = 0; i < 10; i++) _loop(i);
+//! ```
+//!
+//! ## Implementation
+//!
+//! Implementation based on [@babel/plugin-transform-block-scoping](https://babel.dev/docs/babel-plugin-transform-block-scoping).
+//!
+//! Declarations which would conflict after being converted to `var` are
+//! renamed when the program is entered, because the analysis requires the
+//! whole program.
+
 use std::{iter::once, mem::take};
 
 use indexmap::IndexMap;
@@ -6,41 +45,27 @@ use smallvec::SmallVec;
 use swc_atoms::{Atom, atom};
 use swc_common::{DUMMY_SP, Mark, Spanned, SyntaxContext, util::take::Take};
 use swc_ecma_ast::*;
+use swc_ecma_hooks::VisitMutHook;
 use swc_ecma_transforms_base::helper;
 use swc_ecma_utils::{
-    ExprFactory, StmtLike, find_pat_ids, function::FnEnvHoister, prepend_stmt, private_ident,
-    quote_ident, quote_str,
-};
-use swc_ecma_visit::{
-    VisitMut, VisitMutWith, noop_visit_mut_type, visit_mut_obj_and_computed, visit_mut_pass,
+    ExprFactory, find_pat_ids, function::FnEnvHoister, prepend_stmt, private_ident, quote_ident,
+    quote_str,
 };
-use swc_trace_macro::swc_trace;
+use swc_ecma_visit::{VisitMut, VisitMutWith, noop_visit_mut_type, visit_mut_obj_and_computed};
+
+use crate::TraverseCtx;
 
 mod vars;
 
-///
-///
-/// TODO(kdy1): Optimization
-///
-/// ```js
-/// let functions = [];
-/// for (let i = 0; i < 10; i++) {
-///    functions.push(function() {
-///        let i = 1;
-///        console.log(i);
-///    });
-/// }
-/// ```
-pub fn block_scoping(unresolved_mark: Mark) -> impl Pass {
-    (
-        visit_mut_pass(self::vars::block_scoped_vars()),
-        visit_mut_pass(BlockScoping {
-            unresolved_mark,
-            scope: Default::default(),
-            vars: Vec::new(),
-            var_decl_kind: VarDeclKind::Var,
-        }),
-    )
+pub fn hook(unresolved_ctxt: SyntaxContext) -> impl VisitMutHook<TraverseCtx> {
+    BlockScopingPass {
+        unresolved_ctxt,
+        scope: Default::default(),
+        vars: Vec::new(),
+        vars_stack: Vec::new(),
+        loop_body: None,
+        blockified: Vec::new(),
+    }
 }
 
 type ScopeStack = SmallVec<[ScopeKind; 8]>;
@@ -60,38 +85,69 @@ enum ScopeKind {
 }
 
 impl ScopeKind {
-    fn new_loop() -> Self {
+    fn new_loop(lexical_var: Vec<Id>) -> Self {
         ScopeKind::Loop {
-            lexical_var: Vec::new(),
-            args: Vec::new(),
+            args: lexical_var.clone(),
+            lexical_var,
             used: Vec::new(),
             mutated: Default::default(),
         }
     }
 }
 
-struct BlockScoping {
-    unresolved_mark: Mark,
+struct BlockScopingPass {
+    unresolved_ctxt: SyntaxContext,
     scope: ScopeStack,
+
+    /// `_loop` functions, which are declared at the top of the current
+    /// statement list.
     vars: Vec<VarDeclarator>,
-    var_decl_kind: VarDeclKind,
+    vars_stack: Vec<Vec<VarDeclarator>>,
+
+    /// The body of the loop being entered, and the scope of the body.
+    loop_body: Option<(*const Stmt, ScopeKind)>,
+
+    /// Whether the body of each loop was wrapped in a block statement.
+    blockified: Vec<bool>,
 }
 
-impl BlockScoping {
-    /// This methods remove [ScopeKind::Loop] and [ScopeKind::Fn], but not
-    /// [ScopeKind::ForLetLoop]
-    fn visit_mut_with_scope<T>(&mut self, kind: ScopeKind, node: &mut T)
-    where
-        T: VisitMutWith<Self>,
-    {
-        let remove = !matches!(kind, ScopeKind::Loop { .. });
-        self.scope.push(kind);
+impl BlockScopingPass {
+    fn enter_loop(&mut self, body: &mut Box<Stmt>, lexical_var: Vec<Id>, blockify: bool) {
+        let blockified = blockify && self.blockify_for_stmt_body(body);
+        self.blockified.push(blockified);
+
+        self.loop_body = Some((&**body, ScopeKind::new_loop(lexical_var)));
+    }
+
+    fn exit_loop(&mut self, body: &mut Box<Stmt>) {
+        self.handle_capture_of_vars(body);
 
-        node.visit_mut_with(self);
+        let blockified = self.blockified.pop().unwrap_or_default();
+        self.undo_blockify_for_stmt_body(body, blockified);
+    }
 
-        if remove {
-            self.scope.pop();
+    fn enter_stmts_like(&mut self) {
+        self.vars_stack.push(take(&mut self.vars));
+    }
+
+    fn exit_stmts_like(&mut self) -> Option<Stmt> {
+        let vars = take(&mut self.vars);
+        self.vars = self.vars_stack.pop().unwrap_or_default();
+
+        if vars.is_empty() {
+            return None;
         }
+
+        Some(
+            VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vars,
+                ..Default::default()
+            }
+            .into(),
+        )
     }
 
     fn mark_as_used(&mut self, i: Id) {
@@ -136,8 +192,7 @@ impl BlockScoping {
                 return;
             }
 
-            let mut env_hoister =
-                FnEnvHoister::new(SyntaxContext::empty().apply_mark(self.unresolved_mark));
+            let mut env_hoister = FnEnvHoister::new(self.unresolved_ctxt);
             body_stmt.visit_mut_with(&mut env_hoister);
             let mut inits: Vec<Box<Expr>> = Vec::new();
 
@@ -425,206 +480,166 @@ impl BlockScoping {
     }
 }
 
-#[swc_trace]
-impl VisitMut for BlockScoping {
-    noop_visit_mut_type!(fail);
-
-    fn visit_mut_arrow_expr(&mut self, n: &mut ArrowExpr) {
-        n.params.visit_mut_with(self);
-        self.visit_mut_with_scope(ScopeKind::Fn, &mut n.body);
+impl VisitMutHook<TraverseCtx> for BlockScopingPass {
+    fn enter_program(&mut self, node: &mut Program, _: &mut TraverseCtx) {
+        node.visit_mut_with(&mut self::vars::block_scoped_vars());
     }
 
-    fn visit_mut_block_stmt(&mut self, n: &mut BlockStmt) {
-        let vars = take(&mut self.vars);
-        n.visit_mut_children_with(self);
-        debug_assert_eq!(self.vars, Vec::new());
-        self.vars = vars;
+    fn enter_stmts(&mut self, _: &mut Vec<Stmt>, _: &mut TraverseCtx) {
+        self.enter_stmts_like();
     }
 
-    fn visit_mut_constructor(&mut self, f: &mut Constructor) {
-        f.key.visit_mut_with(self);
-        f.params.visit_mut_with(self);
-        self.visit_mut_with_scope(ScopeKind::Fn, &mut f.body);
+    fn exit_stmts(&mut self, stmts: &mut Vec<Stmt>, _: &mut TraverseCtx) {
+        if let Some(decl) = self.exit_stmts_like() {
+            prepend_stmt(stmts, decl);
+        }
     }
 
-    fn visit_mut_do_while_stmt(&mut self, node: &mut DoWhileStmt) {
-        self.visit_mut_with_scope(ScopeKind::new_loop(), &mut node.body);
+    fn enter_module_items(&mut self, _: &mut Vec<ModuleItem>, _: &mut TraverseCtx) {
+        self.enter_stmts_like();
+    }
 
-        node.test.visit_mut_with(self);
-        self.handle_capture_of_vars(&mut node.body);
+    fn exit_module_items(&mut self, items: &mut Vec<ModuleItem>, _: &mut TraverseCtx) {
+        if let Some(decl) = self.exit_stmts_like() {
+            prepend_stmt(items, decl.into());
+        }
     }
 
-    fn visit_mut_for_in_stmt(&mut self, node: &mut ForInStmt) {
-        let blockifyed = self.blockify_for_stmt_body(&mut node.body);
-        let lexical_var = if let ForHead::VarDecl(decl) = &node.left {
-            find_lexical_vars(decl)
-        } else {
-            Vec::new()
-        };
-        let args = lexical_var.clone();
+    fn enter_stmt(&mut self, stmt: &mut Stmt, _: &mut TraverseCtx) {
+        if let Some((body, _)) = &self.loop_body {
+            if std::ptr::eq(*body, stmt) {
+                let (_, kind) = self.loop_body.take().unwrap();
+                self.scope.push(kind);
+            }
+        }
+    }
 
-        self.visit_mut_with_scope(ScopeKind::Block, &mut node.left);
+    fn enter_function(&mut self, _: &mut Function, _: &mut TraverseCtx) {
+        self.scope.push(ScopeKind::Fn);
+    }
 
-        node.right.visit_mut_with(self);
+    fn exit_function(&mut self, _: &mut Function, _: &mut TraverseCtx) {
+        self.scope.pop();
+    }
 
-        let kind = ScopeKind::Loop {
-            lexical_var,
-            args,
-            used: Vec::new(),
-            mutated: Default::default(),
-        };
+    fn enter_arrow_expr(&mut self, _: &mut ArrowExpr, _: &mut TraverseCtx) {
+        self.scope.push(ScopeKind::Fn);
+    }
 
-        self.visit_mut_with_scope(kind, &mut node.body);
-        self.handle_capture_of_vars(&mut node.body);
-        self.undo_blockify_for_stmt_body(&mut node.body, blockifyed);
+    fn exit_arrow_expr(&mut self, _: &mut ArrowExpr, _: &mut TraverseCtx) {
+        self.scope.pop();
     }
 
-    fn visit_mut_for_of_stmt(&mut self, node: &mut ForOfStmt) {
-        let blockifyed = self.blockify_for_stmt_body(&mut node.body);
-        let vars = if let ForHead::VarDecl(decl) = &node.left {
-            find_lexical_vars(decl)
-        } else {
-            Vec::new()
-        };
+    fn enter_constructor(&mut self, _: &mut Constructor, _: &mut TraverseCtx) {
+        self.scope.push(ScopeKind::Fn);
+    }
 
-        self.visit_mut_with_scope(ScopeKind::Block, &mut node.left);
+    fn exit_constructor(&mut self, _: &mut Constructor, _: &mut TraverseCtx) {
+        self.scope.pop();
+    }
 
-        let args = vars.clone();
+    fn enter_getter_prop(&mut self, _: &mut GetterProp, _: &mut TraverseCtx) {
+        self.scope.push(ScopeKind::Fn);
+    }
 
-        node.right.visit_mut_with(self);
+    fn exit_getter_prop(&mut self, _: &mut GetterProp, _: &mut TraverseCtx) {
+        self.scope.pop();
+    }
 
-        let kind = ScopeKind::Loop {
-            lexical_var: vars,
-            args,
-            used: Vec::new(),
-            mutated: Default::default(),
-        };
+    fn enter_setter_prop(&mut self, _: &mut SetterProp, _: &mut TraverseCtx) {
+        self.scope.push(ScopeKind::Fn);
+    }
 
-        self.visit_mut_with_scope(kind, &mut node.body);
-        self.handle_capture_of_vars(&mut node.body);
-        self.undo_blockify_for_stmt_body(&mut node.body, blockifyed);
+    fn exit_setter_prop(&mut self, _: &mut SetterProp, _: &mut TraverseCtx) {
+        self.scope.pop();
     }
 
-    fn visit_mut_for_stmt(&mut self, node: &mut ForStmt) {
-        let blockifyed = self.blockify_for_stmt_body(&mut node.body);
+    fn enter_for_stmt(&mut self, node: &mut ForStmt, _: &mut TraverseCtx) {
         let lexical_var = if let Some(VarDeclOrExpr::VarDecl(decl)) = &node.init {
             find_lexical_vars(decl)
         } else {
             Vec::new()
         };
 
-        node.init.visit_mut_with(self);
-        let args = lexical_var.clone();
+        self.enter_loop(&mut node.body, lexical_var, true);
+    }
 
-        node.test.visit_mut_with(self);
-        node.update.visit_mut_with(self);
+    fn exit_for_stmt(&mut self, node: &mut ForStmt, _: &mut TraverseCtx) {
+        self.exit_loop(&mut node.body);
+    }
 
-        let kind = ScopeKind::Loop {
-            lexical_var,
-            args,
-            used: Vec::new(),
-            mutated: Default::default(),
+    fn enter_for_in_stmt(&mut self, node: &mut ForInStmt, _: &mut TraverseCtx) {
+        let lexical_var = if let ForHead::VarDecl(decl) = &node.left {
+            find_lexical_vars(decl)
+        } else {
+            Vec::new()
         };
-        self.visit_mut_with_scope(kind, &mut node.body);
-        self.handle_capture_of_vars(&mut node.body);
-        self.undo_blockify_for_stmt_body(&mut node.body, blockifyed);
+
+        self.enter_loop(&mut node.body, lexical_var, true);
     }
 
-    fn visit_mut_function(&mut self, f: &mut Function) {
-        f.params.visit_mut_with(self);
-        f.decorators.visit_mut_with(self);
-        self.visit_mut_with_scope(ScopeKind::Fn, &mut f.body);
+    fn exit_for_in_stmt(&mut self, node: &mut ForInStmt, _: &mut TraverseCtx) {
+        self.exit_loop(&mut node.body);
     }
 
-    fn visit_mut_getter_prop(&mut self, f: &mut GetterProp) {
-        f.key.visit_mut_with(self);
-        self.visit_mut_with_scope(ScopeKind::Fn, &mut f.body);
+    fn enter_for_of_stmt(&mut self, node: &mut ForOfStmt, _: &mut TraverseCtx) {
+        let lexical_var = if let ForHead::VarDecl(decl) = &node.left {
+            find_lexical_vars(decl)
+        } else {
+            Vec::new()
+        };
+
+        self.enter_loop(&mut node.body, lexical_var, true);
     }
 
-    fn visit_mut_ident(&mut self, node: &mut Ident) {
-        let id = node.to_id();
-        self.mark_as_used(id);
+    fn exit_for_of_stmt(&mut self, node: &mut ForOfStmt, _: &mut TraverseCtx) {
+        self.exit_loop(&mut node.body);
     }
 
-    fn visit_mut_module_items(&mut self, stmts: &mut Vec<ModuleItem>) {
-        self.visit_mut_stmt_like(stmts);
+    fn enter_for_head(&mut self, _: &mut ForHead, _: &mut TraverseCtx) {
+        self.scope.push(ScopeKind::Block);
     }
 
-    fn visit_mut_setter_prop(&mut self, f: &mut SetterProp) {
-        f.key.visit_mut_with(self);
-        f.param.visit_mut_with(self);
-        self.visit_mut_with_scope(ScopeKind::Fn, &mut f.body);
+    fn exit_for_head(&mut self, _: &mut ForHead, _: &mut TraverseCtx) {
+        self.scope.pop();
     }
 
-    fn visit_mut_stmts(&mut self, n: &mut Vec<Stmt>) {
-        self.visit_mut_stmt_like(n);
+    fn enter_while_stmt(&mut self, node: &mut WhileStmt, _: &mut TraverseCtx) {
+        self.enter_loop(&mut node.body, Vec::new(), false);
     }
 
-    fn visit_mut_switch_case(&mut self, n: &mut SwitchCase) {
-        let old_vars = self.vars.take();
+    fn exit_while_stmt(&mut self, node: &mut WhileStmt, _: &mut TraverseCtx) {
+        self.exit_loop(&mut node.body);
+    }
 
-        n.visit_mut_children_with(self);
+    fn enter_do_while_stmt(&mut self, node: &mut DoWhileStmt, _: &mut TraverseCtx) {
+        self.enter_loop(&mut node.body, Vec::new(), false);
+    }
+
+    fn exit_do_while_stmt(&mut self, node: &mut DoWhileStmt, _: &mut TraverseCtx) {
+        self.exit_loop(&mut node.body);
+    }
 
-        self.vars = old_vars;
+    fn enter_ident(&mut self, node: &mut Ident, _: &mut TraverseCtx) {
+        self.mark_as_used(node.to_id());
     }
 
-    fn visit_mut_var_decl(&mut self, var: &mut VarDecl) {
-        let old = self.var_decl_kind;
-        self.var_decl_kind = var.kind;
+    fn enter_var_decl(&mut self, var: &mut VarDecl, _: &mut TraverseCtx) {
         if let Some(ScopeKind::Loop { lexical_var, .. }) = self.scope.last_mut() {
             lexical_var.extend(find_lexical_vars(var));
         }
-
-        var.visit_mut_children_with(self);
-
-        self.var_decl_kind = old;
-
-        var.kind = VarDeclKind::Var;
     }
 
-    fn visit_mut_var_declarator(&mut self, var: &mut VarDeclarator) {
-        var.visit_mut_children_with(self);
-
-        if self.in_loop_body() && var.init.is_none() {
-            if self.var_decl_kind == VarDeclKind::Var {
-                var.init = None
-            } else {
-                var.init = Some(Expr::undefined(var.span()))
+    fn exit_var_decl(&mut self, var: &mut VarDecl, _: &mut TraverseCtx) {
+        if self.in_loop_body() && var.kind != VarDeclKind::Var {
+            for decl in &mut var.decls {
+                if decl.init.is_none() {
+                    decl.init = Some(Expr::undefined(decl.span()));
+                }
             }
         }
-    }
-
-    fn visit_mut_while_stmt(&mut self, node: &mut WhileStmt) {
-        self.visit_mut_with_scope(ScopeKind::new_loop(), &mut node.body);
 
-        node.test.visit_mut_with(self);
-        self.handle_capture_of_vars(&mut node.body);
-    }
-}
-
-impl BlockScoping {
-    fn visit_mut_stmt_like<T>(&mut self, stmts: &mut Vec<T>)
-    where
-        T: StmtLike,
-        Vec<T>: VisitMutWith<Self>,
-    {
-        stmts.visit_mut_children_with(self);
-
-        if !self.vars.is_empty() {
-            prepend_stmt(
-                stmts,
-                T::from(
-                    VarDecl {
-                        span: DUMMY_SP,
-                        kind: VarDeclKind::Var,
-                        declare: false,
-                        decls: take(&mut self.vars),
-                        ..Default::default()
-                    }
-                    .into(),
-                ),
-            );
-        }
+        var.kind = VarDeclKind::Var;
     }
 }
 
@@ -675,9 +690,8 @@ impl FlowHelper<'_> {
     }
 }
 
-#[swc_trace]
 impl VisitMut for FlowHelper<'_> {
-    noop_visit_mut_type!(fail);
+    noop_visit_mut_type!();
 
     /// noop
     fn visit_mut_arrow_expr(&mut self, _n: &mut ArrowExpr) {}
@@ -908,9 +922,8 @@ impl MutationHandler<'_> {
     }
 }
 
-#[swc_trace]
 impl VisitMut for MutationHandler<'_> {
-    noop_visit_mut_type!(fail);
+    noop_visit_mut_type!();
 
     visit_mut_obj_and_computed!();
 
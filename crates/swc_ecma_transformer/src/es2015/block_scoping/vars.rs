@@ -5,7 +5,6 @@ use swc_common::{Mark, SyntaxContext};
 use swc_ecma_ast::*;
 use swc_ecma_transforms_base::{rename::rename_with_config, scope::ScopeKind};
 use swc_ecma_visit::{VisitMut, VisitMutWith, noop_visit_mut_type};
-use swc_trace_macro::swc_trace;
 
 pub(super) fn block_scoped_vars() -> impl VisitMut {
     BlockScopedVars::default()
@@ -36,7 +35,6 @@ struct ParentScope<'a> {
     vars: &'a IndexMap<Id, VarDeclKind, FxBuildHasher>,
 }
 
-#[swc_trace]
 impl BlockScopedVars {
     fn add_usage(&mut self, id: Id) {
         if !self.scope.usages.contains(&id) {
@@ -103,7 +101,6 @@ impl BlockScopedVars {
     }
 }
 
-#[swc_trace]
 impl Scope {
     fn rename(&mut self, parent: ParentScope, rename_map: &mut FxHashMap<Id, Id>, fn_only: bool) {
         for s in self.children.iter_mut() {
@@ -234,9 +231,8 @@ impl ParentScope<'_> {
     }
 }
 
-#[swc_trace]
 impl VisitMut for BlockScopedVars {
-    noop_visit_mut_type!(fail);
+    noop_visit_mut_type!();
 
     fn visit_mut_arrow_expr(&mut self, n: &mut ArrowExpr) {
         self.with_scope(ScopeKind::Fn, |v| {
@@ -6,7 +6,6 @@ use swc_ecma_transforms_base::{helper, helper_expr};
 use swc_ecma_transforms_classes::super_field::SuperFieldAccessFolder;
 use swc_ecma_utils::{ExprFactory, default_constructor_with_span, private_ident, quote_ident};
 use swc_ecma_visit::{VisitMut, VisitMutWith, noop_visit_mut_type};
-use swc_trace_macro::swc_trace;
 use tracing::debug;
 
 use super::Config;
@@ -231,9 +230,8 @@ struct ConstructorFolder {
     this_ref_count: usize,
 }
 
-#[swc_trace]
 impl VisitMut for ConstructorFolder {
-    noop_visit_mut_type!(fail);
+    noop_visit_mut_type!();
 
     fn visit_mut_constructor(&mut self, _: &mut Constructor) {
         // skip
@@ -420,7 +418,6 @@ impl VisitMut for ConstructorFolder {
     }
 }
 
-#[swc_trace]
 impl ConstructorFolder {
     fn get_this(&mut self) -> &Ident {
         self.this_ref_count += 1;
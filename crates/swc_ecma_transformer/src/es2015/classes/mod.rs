@@ -1,94 +1,110 @@
+//! ES2015: Classes
+//!
+//! This plugin transforms classes to constructor functions with helpers for
+//! inheritance and method definitions.
+//!
+//! > This plugin is included in `preset-env`, in ES2015
+//!
+//! ## Example
+//!
+//! Input:
+//! ```js
+//! class Test {
+//!   constructor(name) {
+//!     this.name = name;
+//!   }
+//!
+//!   logger() {
+//!     console.log("Hello", this.name);
+//!   }
+//! }
+//! ```
+//!
+//! Output:
+//! ```js
+//! var Test = function () {
+//!   function Test(name) {
+//!     _class_call_check(this, Test);
+//!
+//!     this.name = name;
+//!   }
+//!
+//!   Test.prototype.logger = function logger() {
+//!     console.log("Hello", this.name);
+//!   };
+//!
+//!   return Test;
+//! }();
+//! ```
+//!
+//! ## Implementation
+//!
+//! Implementation based on [@babel/plugin-transform-classes](https://babel.dev/docs/babel-plugin-transform-classes).
+//!
+//! Classes are converted when they are entered, so the generated functions are
+//! visited by the other ES2015 transforms. Class features which are lowered
+//! when a class is exited, like static blocks, should be lowered by an earlier
+//! pass.
+
 use std::iter;
 
 use rustc_hash::FxBuildHasher;
-use serde::Deserialize;
 use swc_atoms::{Atom, atom};
 use swc_common::{BytePos, DUMMY_SP, Mark, Span, Spanned, SyntaxContext, util::take::Take};
 use swc_ecma_ast::*;
-use swc_ecma_transforms_base::{helper, native::is_native, perf::Check};
+use swc_ecma_hooks::VisitMutHook;
+use swc_ecma_transforms_base::{assumptions::Assumptions, helper, native::is_native};
 use swc_ecma_transforms_classes::super_field::SuperFieldAccessFolder;
-use swc_ecma_transforms_macros::fast_path;
 use swc_ecma_utils::{
-    ExprFactory, ModuleItemLike, StmtLike, alias_if_required, contains_this_expr, is_valid_ident,
-    is_valid_prop_ident, prepend_stmt, private_ident, prop_name_to_expr, quote_expr, quote_ident,
-    quote_str, replace_ident,
-};
-use swc_ecma_visit::{
-    Visit, VisitMut, VisitMutWith, noop_visit_mut_type, noop_visit_type, visit_mut_pass,
+    ExprFactory, alias_if_required, contains_this_expr, is_valid_ident, is_valid_prop_ident,
+    prepend_stmt, private_ident, prop_name_to_expr, quote_expr, quote_ident, quote_str,
+    replace_ident,
 };
-use swc_trace_macro::swc_trace;
+use swc_ecma_visit::VisitMutWith;
 
 use self::{
     constructor::fold_constructor,
     prop_name::{HashKey, is_pure_prop_name, should_extract_class_prop_key},
 };
+use crate::TraverseCtx;
 
 mod constructor;
 mod prop_name;
 
-pub fn classes(config: Config) -> impl Pass {
-    visit_mut_pass(Classes {
+pub fn hook(assumptions: Assumptions) -> impl VisitMutHook<TraverseCtx> {
+    ClassesPass {
         in_strict: false,
-        config,
+        in_strict_stack: Vec::new(),
+        config: Config {
+            constant_super: assumptions.constant_super,
+            no_class_calls: assumptions.no_class_calls,
+            set_class_methods: assumptions.set_class_methods,
+            super_is_callable_constructor: assumptions.super_is_callable_constructor,
+        },
 
         params: Default::default(),
         args: Default::default(),
-    })
+    }
 }
 
 type IndexMap<K, V> = indexmap::IndexMap<K, V, FxBuildHasher>;
 
-/// `@babel/plugin-transform-classes`
-///
-/// # In
-/// ```js
-/// class Test {
-///   constructor(name) {
-///     this.name = name;
-///   }
-///
-///   logger () {
-///     console.log("Hello", this.name);
-///   }
-/// }
-/// ```
-///
-/// # Out
-/// ```js
-/// var Test = function () {
-///   function Test(name) {
-///     _class_call_check(this, Test);
-///
-///     this.name = name;
-///   }
-///
-///   Test.prototype.logger = function logger() {
-///     console.log("Hello", this.name);
-///   };
-///
-///   return Test;
-/// }();
-/// ```
-#[derive(Default, Clone)]
-struct Classes {
+struct ClassesPass {
     in_strict: bool,
+    in_strict_stack: Vec<bool>,
     config: Config,
 
+    /// Parameters and arguments of the IIFE, for extracted method keys.
     params: Vec<Param>,
     args: Vec<ExprOrSpread>,
 }
 
-#[derive(Debug, Clone, Copy, Default, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Config {
-    #[serde(default)]
-    pub constant_super: bool,
-    #[serde(default)]
-    pub no_class_calls: bool,
-    #[serde(default)]
-    pub set_class_methods: bool,
-    #[serde(default)]
-    pub super_is_callable_constructor: bool,
+#[derive(Debug, Clone, Copy, Default)]
+struct Config {
+    constant_super: bool,
+    no_class_calls: bool,
+    set_class_methods: bool,
+    super_is_callable_constructor: bool,
 }
 
 struct Data {
@@ -98,150 +114,131 @@ struct Data {
     get: Option<Box<Expr>>,
 }
 
-#[swc_trace]
-impl Classes {
-    fn visit_mut_stmt_like<T>(&mut self, stmts: &mut Vec<T>)
-    where
-        T: StmtLike + ModuleItemLike + VisitMutWith<Self> + Take,
-    {
-        let mut buf = Vec::with_capacity(stmts.len());
-        let mut first = true;
-        let old = self.in_strict;
-
-        for stmt in stmts.iter_mut() {
-            match T::try_into_stmt(stmt.take()) {
-                Err(node) => match node.try_into_module_decl() {
-                    Ok(mut decl) => {
-                        match decl {
-                            ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
-                                decl: DefaultDecl::Class(ClassExpr { ident, class }),
-                                ..
-                            }) => {
-                                let ident =
-                                    ident.unwrap_or_else(|| quote_ident!("_default").into());
-
-                                let mut decl = self.fold_class_as_var_decl(ident.clone(), class);
-                                decl.visit_mut_children_with(self);
-                                buf.push(T::from(decl.into()));
-
-                                buf.push(
-                                    match T::try_from_module_decl(
-                                        NamedExport {
-                                            span: DUMMY_SP,
-                                            specifiers: vec![
-                                                ExportNamedSpecifier {
-                                                    span: DUMMY_SP,
-                                                    orig: ModuleExportName::Ident(ident),
-                                                    exported: Some(ModuleExportName::Ident(
-                                                        quote_ident!("default").into(),
-                                                    )),
-                                                    is_type_only: false,
-                                                }
-                                                .into(),
-                                            ],
-                                            src: None,
-                                            type_only: false,
-                                            with: None,
-                                        }
-                                        .into(),
-                                    ) {
-                                        Ok(t) => t,
-                                        Err(..) => unreachable!(),
-                                    },
-                                );
-                            }
-                            ModuleDecl::ExportDecl(ExportDecl {
-                                span,
-                                decl:
-                                    Decl::Class(ClassDecl {
-                                        ident,
-                                        declare: false,
-                                        class,
-                                    }),
-                                ..
-                            }) => {
-                                let mut decl = self.fold_class_as_var_decl(ident, class);
-                                decl.visit_mut_children_with(self);
-                                buf.push(
-                                    match T::try_from_module_decl(
-                                        ExportDecl {
-                                            span,
-                                            decl: decl.into(),
-                                        }
-                                        .into(),
-                                    ) {
-                                        Ok(t) => t,
-                                        Err(..) => unreachable!(),
-                                    },
-                                );
-                            }
-                            _ => buf.push({
-                                decl.visit_mut_children_with(self);
-                                match T::try_from_module_decl(decl) {
-                                    Ok(t) => t,
-                                    Err(..) => unreachable!(),
-                                }
-                            }),
-                        };
-                    }
-                    Err(..) => unreachable!(),
-                },
-                Ok(mut stmt) => {
-                    if first {
-                        self.in_strict |= stmt.is_use_strict();
-                    }
+impl ClassesPass {
+    fn enter_stmts_like(&mut self, first: Option<&Stmt>) {
+        self.in_strict_stack.push(self.in_strict);
 
-                    stmt.visit_mut_children_with(self);
-                    buf.push(T::from(stmt));
-                }
-            }
-            first = false;
+        if first.is_some_and(|stmt| stmt.is_use_strict()) {
+            self.in_strict = true;
         }
+    }
 
-        self.in_strict = old;
-        *stmts = buf;
+    fn exit_stmts_like(&mut self) {
+        self.in_strict = self.in_strict_stack.pop().unwrap_or_default();
     }
 }
 
-#[swc_trace]
-#[fast_path(ClassFinder)]
-impl VisitMut for Classes {
-    noop_visit_mut_type!(fail);
-
-    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
-        self.visit_mut_stmt_like(items)
+impl VisitMutHook<TraverseCtx> for ClassesPass {
+    fn enter_stmts(&mut self, stmts: &mut Vec<Stmt>, _: &mut TraverseCtx) {
+        self.enter_stmts_like(stmts.first());
     }
 
-    fn visit_mut_stmts(&mut self, items: &mut Vec<Stmt>) {
-        self.visit_mut_stmt_like(items)
+    fn exit_stmts(&mut self, _: &mut Vec<Stmt>, _: &mut TraverseCtx) {
+        self.exit_stmts_like();
     }
 
-    fn visit_mut_decl(&mut self, n: &mut Decl) {
-        if let Decl::Class(decl) = n {
-            *n = self
-                .fold_class_as_var_decl(decl.ident.take(), decl.class.take())
-                .into()
-        };
+    fn enter_module_items(&mut self, items: &mut Vec<ModuleItem>, _: &mut TraverseCtx) {
+        self.enter_stmts_like(items.first().and_then(ModuleItem::as_stmt));
+
+        if !items.iter().any(|item| {
+            matches!(
+                item,
+                ModuleItem::ModuleDecl(
+                    ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                        decl: DefaultDecl::Class(..),
+                        ..
+                    }) | ModuleDecl::ExportDecl(ExportDecl {
+                        decl: Decl::Class(ClassDecl { declare: false, .. }),
+                        ..
+                    })
+                )
+            )
+        }) {
+            return;
+        }
 
-        n.visit_mut_children_with(self);
-    }
+        let mut buf = Vec::with_capacity(items.len() + 1);
+
+        for item in items.drain(..) {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    decl: DefaultDecl::Class(ClassExpr { ident, class }),
+                    ..
+                })) => {
+                    let ident = ident.unwrap_or_else(|| quote_ident!("_default").into());
 
-    fn visit_mut_expr(&mut self, n: &mut Expr) {
-        match n {
-            Expr::Class(e) => {
-                let mut class = self.fold_class(e.ident.take(), e.class.take());
-                if let Expr::Call(call) = &mut class {
-                    self.add_pure_comments(&mut call.span.lo)
+                    let decl = self.fold_class_as_var_decl(ident.clone(), class);
+                    buf.push(ModuleItem::Stmt(decl.into()));
+
+                    buf.push(
+                        NamedExport {
+                            span: DUMMY_SP,
+                            specifiers: vec![
+                                ExportNamedSpecifier {
+                                    span: DUMMY_SP,
+                                    orig: ModuleExportName::Ident(ident),
+                                    exported: Some(ModuleExportName::Ident(
+                                        quote_ident!("default").into(),
+                                    )),
+                                    is_type_only: false,
+                                }
+                                .into(),
+                            ],
+                            src: None,
+                            type_only: false,
+                            with: None,
+                        }
+                        .into(),
+                    );
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    span,
+                    decl:
+                        Decl::Class(ClassDecl {
+                            ident,
+                            declare: false,
+                            class,
+                        }),
+                })) => {
+                    let decl = self.fold_class_as_var_decl(ident, class);
+                    buf.push(
+                        ExportDecl {
+                            span,
+                            decl: decl.into(),
+                        }
+                        .into(),
+                    );
                 }
-                *n = class;
-                n.visit_mut_children_with(self)
+                _ => buf.push(item),
             }
+        }
 
-            _ => n.visit_mut_children_with(self),
+        *items = buf;
+    }
+
+    fn exit_module_items(&mut self, _: &mut Vec<ModuleItem>, _: &mut TraverseCtx) {
+        self.exit_stmts_like();
+    }
+
+    fn enter_stmt(&mut self, stmt: &mut Stmt, _: &mut TraverseCtx) {
+        if let Stmt::Decl(Decl::Class(decl)) = stmt {
+            *stmt = self
+                .fold_class_as_var_decl(decl.ident.take(), decl.class.take())
+                .into();
         }
     }
 
-    fn visit_mut_var_declarator(&mut self, d: &mut VarDeclarator) {
+    fn enter_expr(&mut self, n: &mut Expr, _: &mut TraverseCtx) {
+        if let Expr::Class(e) = n {
+            let mut class = self.fold_class(e.ident.take(), e.class.take());
+            if let Expr::Call(call) = &mut class {
+                self.add_pure_comments(&mut call.span.lo)
+            }
+            *n = class;
+        }
+    }
+
+    fn enter_var_declarator(&mut self, d: &mut VarDeclarator, _: &mut TraverseCtx) {
         // will there be anything else in var name at this point?
         if let VarDeclarator {
             name: Pat::Ident(i),
@@ -253,31 +250,25 @@ impl VisitMut for Classes {
                 c.ident = Some(Ident::from(&*i).into_private())
             }
         }
-
-        d.visit_mut_children_with(self)
     }
 
     /// `let { f = class /* f */ {} } = {};`
-    fn visit_mut_assign_pat_prop(&mut self, n: &mut AssignPatProp) {
+    fn enter_assign_pat_prop(&mut self, n: &mut AssignPatProp, _: &mut TraverseCtx) {
         if let Some(value) = &mut n.value {
             if let Expr::Class(c @ ClassExpr { ident: None, .. }) = &mut **value {
                 c.ident = Some(Ident::from(&n.key).into_private());
             }
         }
-
-        n.visit_mut_children_with(self);
     }
 
     /// `let [c = class /* c */ {}] = [];`
     /// `function foo(bar = class /* bar */ {}) {}`
-    fn visit_mut_assign_pat(&mut self, n: &mut AssignPat) {
+    fn enter_assign_pat(&mut self, n: &mut AssignPat, _: &mut TraverseCtx) {
         if let (Pat::Ident(id), Expr::Class(c @ ClassExpr { ident: None, .. })) =
             (&*n.left, &mut *n.right)
         {
             c.ident = Some(Ident::from(id).into_private());
         }
-
-        n.visit_mut_children_with(self);
     }
 
     /// {
@@ -285,7 +276,7 @@ impl VisitMut for Classes {
     ///     "foo": class {},
     ///     ["x"]: class {}
     /// }
-    fn visit_mut_key_value_prop(&mut self, n: &mut KeyValueProp) {
+    fn enter_key_value_prop(&mut self, n: &mut KeyValueProp, _: &mut TraverseCtx) {
         if let Expr::Class(c @ ClassExpr { ident: None, .. }) = &mut *n.value {
             match &n.key {
                 PropName::Ident(ident) => {
@@ -310,11 +301,9 @@ impl VisitMut for Classes {
                 _ => {}
             }
         }
-
-        n.visit_mut_children_with(self)
     }
 
-    fn visit_mut_assign_expr(&mut self, a: &mut AssignExpr) {
+    fn enter_assign_expr(&mut self, a: &mut AssignExpr, _: &mut TraverseCtx) {
         if let AssignExpr {
             op: op!("=") | op!("||=") | op!("??="),
             left,
@@ -328,13 +317,10 @@ impl VisitMut for Classes {
                 }
             }
         }
-
-        a.visit_mut_children_with(self)
     }
 }
 
-#[swc_trace]
-impl Classes {
+impl ClassesPass {
     fn add_pure_comments(&mut self, start: &mut BytePos) {
         *start = BytePos::PURE;
     }
@@ -505,6 +491,7 @@ impl Classes {
     }
 
     /// Returned `stmts` contains `return Foo`
+    #[allow(clippy::boxed_local)]
     fn class_to_stmts(
         &mut self,
         class_name: Option<Ident>,
@@ -972,22 +959,3 @@ fn escape_keywords(mut e: Box<Expr>) -> Box<Expr> {
 
     e
 }
-
-#[derive(Default)]
-struct ClassFinder {
-    found: bool,
-}
-
-impl Visit for ClassFinder {
-    noop_visit_type!(fail);
-
-    fn visit_class(&mut self, _: &Class) {
-        self.found = true
-    }
-}
-
-impl Check for ClassFinder {
-    fn should_handle(&self) -> bool {
-        self.found
-    }
-}
@@ -0,0 +1,410 @@
+//! ES2015: Computed Properties
+//!
+//! This plugin transforms object literals with computed property names.
+//!
+//! > This plugin is included in `preset-env`, in ES2015
+//!
+//! ## Example
+//!
+//! Input:
+//! ```js
+//! var obj = {
+//!   ["x" + foo]: "heh",
+//!   ["y" + bar]: "noo",
+//!   foo: "foo",
+//!   bar: "bar"
+//! };
+//! ```
+//!
+//! Output:
+//! ```js
+//! var _obj;
+//! var obj = (
+//!   _obj = {},
+//!   _define_property(_obj, "x" + foo, "heh"),
+//!   _define_property(_obj, "y" + bar, "noo"),
+//!   _define_property(_obj, "foo", "foo"),
+//!   _define_property(_obj, "bar", "bar"),
+//!   _obj
+//! );
+//! ```
+//!
+//! ## Implementation
+//!
+//! Implementation based on [@babel/plugin-transform-computed-properties](https://babel.dev/docs/babel-plugin-transform-computed-properties).
+
+use swc_common::{DUMMY_SP, Mark, Spanned, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_hooks::VisitMutHook;
+use swc_ecma_transforms_base::helper;
+use swc_ecma_utils::{ExprFactory, quote_ident};
+use swc_ecma_visit::{Visit, VisitWith, noop_visit_type};
+
+use crate::{TraverseCtx, utils::StmtVars};
+
+pub fn hook(loose: bool) -> impl VisitMutHook<TraverseCtx> {
+    ComputedPropsPass {
+        loose,
+        vars: StmtVars::default(),
+    }
+}
+
+struct ComputedPropsPass {
+    loose: bool,
+    vars: StmtVars,
+}
+
+impl VisitMutHook<TraverseCtx> for ComputedPropsPass {
+    fn enter_stmts(&mut self, stmts: &mut Vec<Stmt>, _: &mut TraverseCtx) {
+        self.vars.enter_stmts(stmts);
+    }
+
+    fn exit_stmts(&mut self, _: &mut Vec<Stmt>, _: &mut TraverseCtx) {
+        self.vars.exit_stmts();
+    }
+
+    fn enter_module_items(&mut self, _: &mut Vec<ModuleItem>, _: &mut TraverseCtx) {
+        self.vars.enter_stmts(&[]);
+    }
+
+    fn exit_module_items(&mut self, _: &mut Vec<ModuleItem>, _: &mut TraverseCtx) {
+        self.vars.exit_stmts();
+    }
+
+    fn enter_module_item(&mut self, _: &mut ModuleItem, _: &mut TraverseCtx) {
+        self.vars.enter_item();
+    }
+
+    fn exit_module_item(&mut self, item: &mut ModuleItem, ctx: &mut TraverseCtx) {
+        self.vars.exit_module_item(item, ctx);
+    }
+
+    fn enter_stmt(&mut self, _: &mut Stmt, _: &mut TraverseCtx) {
+        self.vars.enter_item();
+    }
+
+    fn exit_stmt(&mut self, stmt: &mut Stmt, ctx: &mut TraverseCtx) {
+        self.vars.exit_stmt(stmt, ctx);
+    }
+
+    fn exit_expr(&mut self, expr: &mut Expr, _ctx: &mut TraverseCtx) {
+        let mut vars = Vec::new();
+
+        if let Expr::Object(ObjectLit { props, span }) = expr {
+            if !is_complex(props) {
+                return;
+            }
+
+            let mark = Mark::fresh(Mark::root());
+            let obj_ident = quote_ident!(SyntaxContext::empty().apply_mark(mark), *span, "_obj");
+
+            let mut exprs: Vec<Box<Expr>> = Vec::with_capacity(props.len() + 2);
+            let mutator_map = quote_ident!(
+                SyntaxContext::empty().apply_mark(mark),
+                *span,
+                "_mutatorMap"
+            );
+
+            // Optimization
+            let obj_props = {
+                let idx = props.iter().position(is_complex).unwrap_or(0);
+
+                props.drain(0..idx).collect()
+            };
+
+            let props_cnt = props.len();
+
+            let mut used_define_enum_props = props.iter().any(
+                |pp| matches!(*pp, PropOrSpread::Prop(ref p) if p.is_getter() || p.is_setter()),
+            );
+
+            exprs.push(
+                if !self.loose && props_cnt == 1 && !used_define_enum_props {
+                    ObjectLit {
+                        span: DUMMY_SP,
+                        props: obj_props,
+                    }
+                    .into()
+                } else {
+                    AssignExpr {
+                        span: DUMMY_SP,
+                        left: obj_ident.clone().into(),
+                        op: op!("="),
+                        right: Box::new(
+                            ObjectLit {
+                                span: DUMMY_SP,
+                                props: obj_props,
+                            }
+                            .into(),
+                        ),
+                    }
+                    .into()
+                },
+            );
+
+            let mut single_cnt_prop = None;
+
+            for prop in props.drain(..) {
+                let span = prop.span();
+
+                let ((key, is_compute), value) = match prop {
+                    PropOrSpread::Prop(prop) => match *prop {
+                        Prop::Shorthand(ident) => (
+                            (
+                                if self.loose {
+                                    ident.clone().into()
+                                } else {
+                                    Lit::Str(Str {
+                                        span: ident.span,
+                                        raw: None,
+                                        value: ident.sym.clone().into(),
+                                    })
+                                    .into()
+                                },
+                                false,
+                            ),
+                            ident.into(),
+                        ),
+                        Prop::KeyValue(KeyValueProp { key, value }) => {
+                            (prop_name_to_expr(key, self.loose), *value)
+                        }
+                        Prop::Assign(..) => {
+                            unreachable!("assign property in object literal is invalid")
+                        }
+                        prop @ Prop::Getter(GetterProp { .. })
+                        | prop @ Prop::Setter(SetterProp { .. }) => {
+                            used_define_enum_props = true;
+
+                            // getter/setter property name
+                            let gs_prop_name = match prop {
+                                Prop::Getter(..) => Some("get"),
+                                Prop::Setter(..) => Some("set"),
+                                _ => None,
+                            };
+                            let (key, function) = match prop {
+                                Prop::Getter(GetterProp {
+                                    span, body, key, ..
+                                }) => (
+                                    key,
+                                    Box::new(Function {
+                                        span,
+                                        body,
+                                        is_async: false,
+                                        is_generator: false,
+                                        params: Vec::new(),
+                                        ..Default::default()
+                                    }),
+                                ),
+                                Prop::Setter(SetterProp {
+                                    span,
+                                    body,
+                                    param,
+                                    key,
+                                    ..
+                                }) => (
+                                    key,
+                                    Box::new(Function {
+                                        span,
+                                        body,
+                                        is_async: false,
+                                        is_generator: false,
+                                        params: vec![(*param).into()],
+                                        ..Default::default()
+                                    }),
+                                ),
+                                _ => unreachable!(),
+                            };
+
+                            // mutator[f]
+                            let mutator_elem = mutator_map
+                                .clone()
+                                .computed_member(prop_name_to_expr(key, false).0);
+
+                            // mutator[f] = mutator[f] || {}
+                            exprs.push(
+                                AssignExpr {
+                                    span,
+                                    left: mutator_elem.clone().into(),
+                                    op: op!("="),
+                                    right: Box::new(
+                                        BinExpr {
+                                            span,
+                                            left: mutator_elem.clone().into(),
+                                            op: op!("||"),
+                                            right: Box::new(Expr::Object(ObjectLit {
+                                                span,
+                                                props: Vec::new(),
+                                            })),
+                                        }
+                                        .into(),
+                                    ),
+                                }
+                                .into(),
+                            );
+
+                            // mutator[f].get = function(){}
+                            exprs.push(
+                                AssignExpr {
+                                    span,
+                                    left: mutator_elem
+                                        .make_member(quote_ident!(gs_prop_name.unwrap()))
+                                        .into(),
+                                    op: op!("="),
+                                    right: Box::new(
+                                        FnExpr {
+                                            ident: None,
+                                            function,
+                                        }
+                                        .into(),
+                                    ),
+                                }
+                                .into(),
+                            );
+
+                            continue;
+                            // unimplemented!("getter /setter property")
+                        }
+                        Prop::Method(MethodProp { key, function }) => (
+                            prop_name_to_expr(key, self.loose),
+                            FnExpr {
+                                ident: None,
+                                function,
+                            }
+                            .into(),
+                        ),
+                        #[cfg(swc_ast_unknown)]
+                        _ => panic!("unable to access unknown nodes"),
+                    },
+                    PropOrSpread::Spread(..) => unimplemented!("computed spread property"),
+                    #[cfg(swc_ast_unknown)]
+                    _ => panic!("unable to access unknown nodes"),
+                };
+
+                if !self.loose && props_cnt == 1 {
+                    single_cnt_prop = Some(
+                        CallExpr {
+                            span,
+                            callee: helper!(define_property),
+                            args: vec![exprs.pop().unwrap().as_arg(), key.as_arg(), value.as_arg()],
+                            ..Default::default()
+                        }
+                        .into(),
+                    );
+                    break;
+                }
+                exprs.push(if self.loose {
+                    let left = if is_compute {
+                        obj_ident.clone().computed_member(key)
+                    } else {
+                        obj_ident.clone().make_member(key.ident().unwrap().into())
+                    };
+                    AssignExpr {
+                        span,
+                        op: op!("="),
+                        left: left.into(),
+                        right: value.into(),
+                    }
+                    .into()
+                } else {
+                    CallExpr {
+                        span,
+                        callee: helper!(define_property),
+                        args: vec![obj_ident.clone().as_arg(), key.as_arg(), value.as_arg()],
+                        ..Default::default()
+                    }
+                    .into()
+                });
+            }
+
+            if let Some(single_expr) = single_cnt_prop {
+                *expr = single_expr;
+                return;
+            }
+
+            vars.push(VarDeclarator {
+                span: *span,
+                name: obj_ident.clone().into(),
+                init: None,
+                definite: false,
+            });
+            if used_define_enum_props {
+                vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: mutator_map.clone().into(),
+                    init: Some(
+                        ObjectLit {
+                            span: DUMMY_SP,
+                            props: Vec::new(),
+                        }
+                        .into(),
+                    ),
+                    definite: false,
+                });
+                exprs.push(
+                    CallExpr {
+                        span: *span,
+                        callee: helper!(define_enumerable_properties),
+                        args: vec![obj_ident.clone().as_arg(), mutator_map.as_arg()],
+                        ..Default::default()
+                    }
+                    .into(),
+                );
+            }
+
+            // Last value
+            exprs.push(obj_ident.into());
+            *expr = SeqExpr {
+                span: DUMMY_SP,
+                exprs,
+            }
+            .into();
+        };
+
+        self.vars.extend(vars);
+    }
+}
+
+fn is_complex<T: VisitWith<ComplexVisitor>>(node: &T) -> bool {
+    let mut visitor = ComplexVisitor::default();
+    node.visit_children_with(&mut visitor);
+    visitor.found
+}
+
+#[derive(Default)]
+struct ComplexVisitor {
+    found: bool,
+}
+
+impl Visit for ComplexVisitor {
+    noop_visit_type!();
+
+    fn visit_prop_name(&mut self, pn: &PropName) {
+        if let PropName::Computed(..) = *pn {
+            self.found = true
+        }
+    }
+}
+
+fn prop_name_to_expr(p: PropName, loose: bool) -> (Expr, bool) {
+    match p {
+        PropName::Ident(i) => (
+            if loose {
+                i.into()
+            } else {
+                Lit::Str(Str {
+                    raw: None,
+                    value: i.sym.into(),
+                    span: i.span,
+                })
+                .into()
+            },
+            false,
+        ),
+        PropName::Str(s) => (Lit::Str(s).into(), true),
+        PropName::Num(n) => (Lit::Num(n).into(), true),
+        PropName::BigInt(b) => (Lit::BigInt(b).into(), true),
+        PropName::Computed(c) => (*c.expr, true),
+        #[cfg(swc_ast_unknown)]
+        _ => panic!("unable to access unknown nodes"),
+    }
+}
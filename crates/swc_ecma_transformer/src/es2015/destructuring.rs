@@ -0,0 +1,1507 @@
+//! ES2015: Destructuring
+//!
+//! This plugin transforms destructuring patterns in declarations, assignments
+//! and parameters.
+//!
+//! > This plugin is included in `preset-env`, in ES2015
+//!
+//! ## Example
+//!
+//! Input:
+//! ```js
+//! let { x, y } = obj;
+//!
+//! let [a, b, ...rest] = arr;
+//! ```
+//!
+//! Output:
+//! ```js
+//! let _obj = obj,
+//!   x = _obj.x,
+//!   y = _obj.y;
+//!
+//! let _arr = _to_array(arr),
+//!   a = _arr[0],
+//!   b = _arr[1],
+//!   rest = _arr.slice(2);
+//! ```
+//!
+//! ## Implementation
+//!
+//! Implementation based on [@babel/plugin-transform-destructuring](https://babel.dev/docs/babel-plugin-transform-destructuring).
+//!
+//! Destructured parameters and loop heads are moved into the body when the
+//! node is entered, so they are converted like other declarations.
+
+use std::{iter, mem};
+
+use rustc_hash::FxHashSet;
+use swc_common::{DUMMY_SP, Span, Spanned, SyntaxContext, util::take::Take};
+use swc_ecma_ast::*;
+use swc_ecma_hooks::VisitMutHook;
+use swc_ecma_transforms_base::{helper, helper_expr};
+use swc_ecma_utils::{
+    ExprFactory, alias_ident_for, alias_if_required, has_rest_pat, is_literal, member_expr,
+    private_ident, prop_name_to_expr, quote_ident,
+};
+use swc_ecma_visit::{VisitMut, VisitMutWith, noop_visit_mut_type};
+
+use crate::{TraverseCtx, utils::StmtVars};
+
+pub fn hook(loose: bool) -> impl VisitMutHook<TraverseCtx> {
+    DestructuringPass {
+        loose,
+        vars: StmtVars::default(),
+        unused_exprs: FxHashSet::default(),
+        exported_decl: None,
+    }
+}
+
+struct DestructuringPass {
+    loose: bool,
+
+    /// `ref` variables of assignments.
+    vars: StmtVars,
+
+    /// Expressions whose value is not used.
+    unused_exprs: FxHashSet<*const Expr>,
+
+    /// The variable declaration of the current `export var` statement.
+    exported_decl: Option<*const VarDecl>,
+}
+
+impl DestructuringPass {
+    fn folder(&self, exporting: bool) -> AssignFolder {
+        AssignFolder {
+            loose: self.loose,
+            exporting,
+            vars: Vec::new(),
+            ignore_return_value: None,
+        }
+    }
+
+    /// Moves the destructuring pattern of a `for-in` or `for-of` loop into the
+    /// body.
+    fn fold_for_head(&mut self, left: &mut ForHead, body: &mut Box<Stmt>) {
+        let (new_left, mut stmt) = match left {
+            ForHead::VarDecl(var_decl) => {
+                let has_complex = var_decl
+                    .decls
+                    .iter()
+                    .any(|d| !matches!(d.name, Pat::Ident(_)));
+
+                if !has_complex {
+                    return;
+                }
+                let ref_ident = make_ref_ident_for_for_stmt();
+                let left = VarDecl {
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: ref_ident.clone().into(),
+                        init: None,
+                        definite: false,
+                    }],
+                    span: var_decl.span,
+                    kind: var_decl.kind,
+                    declare: var_decl.declare,
+                    ..Default::default()
+                }
+                .into();
+
+                // I(kdy1) guess var_decl.len() == 1
+                let decls = var_decl
+                    .decls
+                    .take()
+                    .into_iter()
+                    .map(|decl| VarDeclarator {
+                        init: Some(Box::new(Expr::Ident(ref_ident.clone()))),
+                        ..decl
+                    })
+                    .collect::<Vec<_>>();
+
+                // Unpack variables
+                let stmt: Stmt = VarDecl {
+                    span: var_decl.span(),
+                    kind: VarDeclKind::Let,
+                    decls,
+                    ..Default::default()
+                }
+                .into();
+                (left, stmt)
+            }
+            ForHead::Pat(pat) => match **pat {
+                Pat::Ident(..) => {
+                    return;
+                }
+                _ => {
+                    let left_ident = make_ref_ident_for_for_stmt();
+                    let left = ForHead::Pat(left_ident.clone().into());
+                    // Unpack variables
+                    let stmt = AssignExpr {
+                        span: DUMMY_SP,
+                        left: pat.take().try_into().unwrap(),
+                        op: op!("="),
+                        right: Box::new(left_ident.into()),
+                    }
+                    .into_stmt();
+                    (left, stmt)
+                }
+            },
+
+            ForHead::UsingDecl(..) => {
+                unreachable!("using declaration must be removed by previous pass")
+            }
+
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        };
+
+        *left = new_left;
+
+        // The unpacking statement is converted here, so `ref` variables are
+        // declared before the loop.
+        let mut folder = self.folder(false);
+        stmt.visit_mut_with(&mut folder);
+        self.vars.extend(folder.vars);
+
+        **body = Stmt::Block(match &mut **body {
+            Stmt::Block(BlockStmt { span, stmts, ctxt }) => BlockStmt {
+                span: *span,
+                stmts: iter::once(stmt).chain(stmts.take()).collect(),
+                ctxt: *ctxt,
+            },
+            body => BlockStmt {
+                stmts: vec![stmt, body.take()],
+                ..Default::default()
+            },
+        });
+    }
+}
+
+impl VisitMutHook<TraverseCtx> for DestructuringPass {
+    fn enter_stmts(&mut self, stmts: &mut Vec<Stmt>, _: &mut TraverseCtx) {
+        self.vars.enter_stmts(stmts);
+    }
+
+    fn exit_stmts(&mut self, _: &mut Vec<Stmt>, _: &mut TraverseCtx) {
+        self.vars.exit_stmts();
+    }
+
+    fn enter_module_items(&mut self, _: &mut Vec<ModuleItem>, _: &mut TraverseCtx) {
+        self.vars.enter_stmts(&[]);
+    }
+
+    fn exit_module_items(&mut self, _: &mut Vec<ModuleItem>, _: &mut TraverseCtx) {
+        self.vars.exit_stmts();
+    }
+
+    fn enter_module_item(&mut self, item: &mut ModuleItem, _: &mut TraverseCtx) {
+        self.vars.enter_item();
+
+        if let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Var(var),
+            ..
+        })) = item
+        {
+            self.exported_decl = Some(&**var);
+        }
+    }
+
+    fn exit_module_item(&mut self, item: &mut ModuleItem, ctx: &mut TraverseCtx) {
+        self.exported_decl = None;
+        self.vars.exit_module_item(item, ctx);
+    }
+
+    fn enter_stmt(&mut self, s: &mut Stmt, _: &mut TraverseCtx) {
+        self.vars.enter_item();
+
+        if let Stmt::Expr(e) = s {
+            self.unused_exprs.insert(&*e.expr);
+        }
+    }
+
+    fn exit_stmt(&mut self, s: &mut Stmt, ctx: &mut TraverseCtx) {
+        self.vars.exit_stmt(s, ctx);
+    }
+
+    fn enter_expr(&mut self, expr: &mut Expr, _: &mut TraverseCtx) {
+        let is_unused = self.unused_exprs.contains(&(expr as *const Expr));
+
+        match expr {
+            // Transparent wrapper expressions pass the flag to the inner expression.
+            Expr::Paren(ParenExpr { expr, .. }) if is_unused => {
+                self.unused_exprs.insert(&**expr);
+            }
+            // In a sequence expression like (a, b, c), only the last expression's
+            // return value matters.
+            Expr::Seq(SeqExpr { exprs, .. }) => {
+                if let Some((last, rest)) = exprs.split_last() {
+                    self.unused_exprs
+                        .extend(rest.iter().map(|e| &**e as *const Expr));
+
+                    if is_unused {
+                        self.unused_exprs.insert(&**last);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn exit_expr(&mut self, expr: &mut Expr, _: &mut TraverseCtx) {
+        let ignore_return_value = self.unused_exprs.remove(&(expr as *const Expr));
+
+        if !matches!(
+            expr,
+            Expr::Assign(AssignExpr {
+                left: AssignTarget::Pat(..),
+                op: op!("="),
+                ..
+            })
+        ) {
+            return;
+        }
+
+        let mut folder = self.folder(false);
+        folder.fold_assign(expr, ignore_return_value);
+        self.vars.extend(folder.vars);
+    }
+
+    fn exit_var_decl(&mut self, var_decl: &mut VarDecl, _: &mut TraverseCtx) {
+        let exporting = self.exported_decl == Some(var_decl as *const VarDecl);
+
+        if var_decl
+            .decls
+            .iter()
+            .any(|d| !matches!(d.name, Pat::Ident(..)))
+        {
+            let mut folder = self.folder(exporting);
+
+            let mut decls = Vec::with_capacity(var_decl.decls.len());
+            for decl in var_decl.decls.drain(..) {
+                folder.visit_mut_var_decl(&mut decls, decl);
+            }
+            var_decl.decls = decls;
+
+            self.vars.extend(folder.vars);
+
+            if var_decl.kind == VarDeclKind::Const {
+                var_decl.decls.iter_mut().for_each(|v| {
+                    if v.init.is_none() {
+                        v.init = Some(Expr::undefined(DUMMY_SP));
+                    }
+                })
+            }
+        }
+    }
+
+    fn enter_for_in_stmt(&mut self, for_stmt: &mut ForInStmt, _: &mut TraverseCtx) {
+        self.fold_for_head(&mut for_stmt.left, &mut for_stmt.body);
+    }
+
+    fn enter_for_of_stmt(&mut self, for_stmt: &mut ForOfStmt, _: &mut TraverseCtx) {
+        self.fold_for_head(&mut for_stmt.left, &mut for_stmt.body);
+    }
+
+    fn enter_function(&mut self, f: &mut Function, _: &mut TraverseCtx) {
+        if let Some(body) = &mut f.body {
+            fold_fn_like(&mut f.params, body);
+        }
+    }
+
+    fn enter_constructor(&mut self, f: &mut Constructor, _: &mut TraverseCtx) {
+        let Some(body) = &mut f.body else {
+            return;
+        };
+
+        let mut params = f
+            .params
+            .take()
+            .into_iter()
+            .map(|param| match param {
+                ParamOrTsParamProp::Param(p) => p,
+                _ => {
+                    unreachable!("TsParameterProperty should be removed by typescript::strip pass")
+                }
+            })
+            .collect();
+
+        fold_fn_like(&mut params, body);
+
+        f.params = params.into_iter().map(ParamOrTsParamProp::Param).collect();
+    }
+
+    fn enter_arrow_expr(&mut self, f: &mut ArrowExpr, _: &mut TraverseCtx) {
+        let was_expr = f.body.is_expr();
+
+        let mut params = f
+            .params
+            .take()
+            .into_iter()
+            .map(|pat| Param {
+                span: DUMMY_SP,
+                decorators: Default::default(),
+                pat,
+            })
+            .collect();
+
+        let mut body = match &mut *f.body {
+            BlockStmtOrExpr::BlockStmt(block) => block.take(),
+            BlockStmtOrExpr::Expr(expr) => BlockStmt {
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(expr.take()),
+                })],
+                ..Default::default()
+            },
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        };
+
+        fold_fn_like(&mut params, &mut body);
+
+        f.params = params.into_iter().map(|param| param.pat).collect();
+        f.body = if was_expr
+            && body.stmts.len() == 1
+            && matches!(
+                body.stmts[0],
+                Stmt::Return(ReturnStmt { arg: Some(..), .. })
+            ) {
+            match body.stmts.pop().unwrap() {
+                Stmt::Return(ReturnStmt { arg: Some(arg), .. }) => {
+                    Box::new(BlockStmtOrExpr::Expr(arg))
+                }
+                _ => unreachable!(),
+            }
+        } else {
+            Box::new(BlockStmtOrExpr::BlockStmt(body))
+        };
+    }
+
+    fn enter_setter_prop(&mut self, f: &mut SetterProp, _: &mut TraverseCtx) {
+        let Some(body) = &mut f.body else {
+            return;
+        };
+
+        let mut params = vec![Param {
+            span: DUMMY_SP,
+            decorators: Vec::new(),
+            pat: *f.param.take(),
+        }];
+        fold_fn_like(&mut params, body);
+        debug_assert!(params.len() == 1);
+
+        *f.param = params.pop().unwrap().pat;
+    }
+
+    fn enter_catch_clause(&mut self, f: &mut CatchClause, _: &mut TraverseCtx) {
+        let mut params = Vec::new();
+        if let Some(pat) = f.param.take() {
+            params.push(Param {
+                span: DUMMY_SP,
+                decorators: Vec::new(),
+                pat,
+            });
+        }
+
+        fold_fn_like(&mut params, &mut f.body);
+        assert!(
+            params.is_empty() || params.len() == 1,
+            "fold_fn_like should return 0 ~ 1 parameter while handling catch clause"
+        );
+
+        f.param = params.pop().map(|param| param.pat);
+    }
+}
+
+/// Moves destructured parameters to a `let` declaration in the body.
+fn fold_fn_like(ps: &mut Vec<Param>, body: &mut BlockStmt) {
+    let mut params = Vec::new();
+    let mut decls = Vec::new();
+
+    for param in ps.drain(..) {
+        let span = param.span();
+        match param.pat {
+            Pat::Ident(..) => params.push(param),
+            Pat::Array(..) | Pat::Object(..) | Pat::Assign(..) => {
+                let ref_ident = private_ident!(span, "ref");
+
+                params.push(Param {
+                    span: DUMMY_SP,
+                    decorators: Default::default(),
+                    pat: ref_ident.clone().into(),
+                });
+                decls.push(VarDeclarator {
+                    span,
+                    name: param.pat,
+                    init: Some(ref_ident.into()),
+                    definite: false,
+                })
+            }
+            Pat::Rest(..) | Pat::Expr(..) => params.push(param),
+            Pat::Invalid(..) => {}
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        }
+    }
+
+    if !decls.is_empty() {
+        body.stmts.insert(
+            0,
+            VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Let,
+                decls,
+                declare: false,
+                ..Default::default()
+            }
+            .into(),
+        );
+    }
+
+    *ps = params;
+}
+
+fn make_ref_ident_for_for_stmt() -> Ident {
+    private_ident!("ref")
+}
+
+impl AssignFolder {
+    fn visit_mut_var_decl(&mut self, decls: &mut Vec<VarDeclarator>, decl: VarDeclarator) {
+        match decl.name {
+            Pat::Ident(..) => decls.push(decl),
+            Pat::Rest(..) => unreachable!(
+                "rest pattern should handled by array pattern handler: {:?}",
+                decl.name
+            ),
+            Pat::Array(ArrayPat { elems, .. }) => {
+                assert!(
+                    decl.init.is_some(),
+                    "destructuring pattern binding requires initializer"
+                );
+
+                let init = decl.init.unwrap();
+
+                if is_literal(&init) {
+                    match *init {
+                        Expr::Array(arr)
+                            if !elems.is_empty()
+                                && (elems.len() == arr.elems.len()
+                                    || (elems.len() < arr.elems.len() && has_rest_pat(&elems))) =>
+                        {
+                            let mut arr_elems = Some(arr.elems.into_iter());
+                            elems.into_iter().for_each(|p| match p {
+                                Some(Pat::Rest(p)) => {
+                                    self.visit_mut_var_decl(
+                                        decls,
+                                        VarDeclarator {
+                                            span: p.span(),
+                                            name: *p.arg,
+                                            init: Some(
+                                                ArrayLit {
+                                                    span: DUMMY_SP,
+                                                    elems: arr_elems
+                                                        .take()
+                                                        .expect("two rest element?")
+                                                        .collect(),
+                                                }
+                                                .into(),
+                                            ),
+                                            definite: false,
+                                        },
+                                    );
+                                }
+                                Some(p) => {
+                                    let e = arr_elems
+                                        .as_mut()
+                                        .expect("pattern after rest element?")
+                                        .next()
+                                        .unwrap();
+                                    self.visit_mut_var_decl(
+                                        decls,
+                                        VarDeclarator {
+                                            span: p.span(),
+                                            init: e.map(|e| {
+                                                debug_assert_eq!(e.spread, None);
+                                                e.expr
+                                            }),
+                                            name: p,
+                                            definite: false,
+                                        },
+                                    )
+                                }
+
+                                None => {
+                                    arr_elems
+                                        .as_mut()
+                                        .expect("pattern after rest element?")
+                                        .next();
+                                }
+                            });
+                            return;
+                        }
+                        _ => {}
+                    }
+                }
+                // Make ref var if required
+                let ref_ident = make_ref_ident_for_array(
+                    self.loose,
+                    if self.exporting {
+                        &mut self.vars
+                    } else {
+                        decls
+                    },
+                    Some(init),
+                    Some(if has_rest_pat(&elems) {
+                        usize::MAX
+                    } else {
+                        elems.len()
+                    }),
+                );
+
+                for (i, elem) in elems.into_iter().enumerate() {
+                    let elem: Pat = match elem {
+                        Some(elem) => elem,
+                        None => continue,
+                    };
+
+                    let var_decl = match elem {
+                        Pat::Rest(RestPat {
+                            dot3_token, arg, ..
+                        }) => VarDeclarator {
+                            span: dot3_token,
+                            name: *arg,
+                            init: Some(
+                                CallExpr {
+                                    span: DUMMY_SP,
+                                    callee: ref_ident
+                                        .clone()
+                                        .make_member(quote_ident!("slice"))
+                                        .as_callee(),
+                                    args: vec![
+                                        Number {
+                                            value: i as f64,
+                                            span: dot3_token,
+                                            raw: None,
+                                        }
+                                        .as_arg(),
+                                    ],
+                                    ..Default::default()
+                                }
+                                .into(),
+                            ),
+                            definite: false,
+                        },
+                        _ => VarDeclarator {
+                            span: elem.span(),
+                            // This might be pattern.
+                            // So we fold it again.
+                            name: elem,
+                            init: Some(make_ref_idx_expr(&ref_ident, i).into()),
+                            definite: false,
+                        },
+                    };
+
+                    let mut var_decls = vec![var_decl];
+                    var_decls.visit_mut_with(self);
+                    decls.extend(var_decls);
+                }
+            }
+            Pat::Object(ObjectPat { span, props, .. }) if props.is_empty() => {
+                // We should convert
+                //
+                //      var {} = null;
+                //
+                // to
+                //
+                //      var _ref = null;
+                //      _object_destructuring_empty(_ref);
+                //
+
+                let expr = helper_expr!(object_destructuring_empty).as_call(
+                    DUMMY_SP,
+                    vec![
+                        decl.init
+                            .expect("destructuring must be initialized")
+                            .as_arg(),
+                    ],
+                );
+
+                let var_decl = VarDeclarator {
+                    span: DUMMY_SP,
+                    name: private_ident!(span, "ref").into(),
+                    init: Some(Box::new(expr)),
+                    definite: false,
+                };
+
+                decls.push(var_decl);
+            }
+
+            Pat::Object(ObjectPat { props, .. }) => {
+                assert!(
+                    decl.init.is_some(),
+                    "destructuring pattern binding requires initializer"
+                );
+
+                if props.len() == 1 {
+                    if let ObjectPatProp::Assign(p @ AssignPatProp { value: None, .. }) = &props[0]
+                    {
+                        decls.push(VarDeclarator {
+                            span: decl.span,
+                            name: p.key.clone().into(),
+                            init: Some(decl.init.unwrap().make_member(p.key.clone().into()).into()),
+                            definite: false,
+                        });
+                        return;
+                    }
+                }
+
+                let can_be_null = can_be_null(decl.init.as_ref().unwrap());
+
+                let ref_decls = if self.exporting {
+                    &mut self.vars
+                } else {
+                    &mut *decls
+                };
+
+                let ref_ident = make_ref_ident(self.loose, ref_decls, decl.init);
+
+                let ref_ident = if can_be_null {
+                    let init = ref_ident.into();
+                    make_ref_ident(self.loose, ref_decls, Some(init))
+                } else {
+                    ref_ident
+                };
+
+                for prop in props {
+                    let prop_span = prop.span();
+
+                    match prop {
+                        ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                            let computed = matches!(key, PropName::Computed(..));
+
+                            let var_decl = VarDeclarator {
+                                span: prop_span,
+                                name: *value,
+                                init: Some(Box::new(make_ref_prop_expr(
+                                    &ref_ident,
+                                    Box::new(prop_name_to_expr(key)),
+                                    computed,
+                                ))),
+                                definite: false,
+                            };
+
+                            let mut var_decls = vec![var_decl];
+                            var_decls.visit_mut_with(self);
+                            decls.extend(var_decls);
+                        }
+                        ObjectPatProp::Assign(AssignPatProp { key, value, .. }) => {
+                            let computed = false;
+
+                            match value {
+                                Some(value) => {
+                                    let ref_ident = make_ref_ident(
+                                        self.loose,
+                                        decls,
+                                        Some(Box::new(make_ref_prop_expr(
+                                            &ref_ident,
+                                            key.clone().into(),
+                                            computed,
+                                        ))),
+                                    );
+
+                                    let var_decl = VarDeclarator {
+                                        span: prop_span,
+                                        name: key.clone().into(),
+                                        init: Some(Box::new(make_cond_expr(ref_ident, value))),
+                                        definite: false,
+                                    };
+                                    let mut var_decls = vec![var_decl];
+                                    var_decls.visit_mut_with(self);
+                                    decls.extend(var_decls);
+                                }
+                                None => {
+                                    let var_decl = VarDeclarator {
+                                        span: prop_span,
+                                        name: key.clone().into(),
+                                        init: Some(Box::new(make_ref_prop_expr(
+                                            &ref_ident,
+                                            key.clone().into(),
+                                            computed,
+                                        ))),
+                                        definite: false,
+                                    };
+                                    let mut var_decls = vec![var_decl];
+                                    var_decls.visit_mut_with(self);
+                                    decls.extend(var_decls);
+                                }
+                            }
+                        }
+                        ObjectPatProp::Rest(..) => unreachable!(
+                            "Object rest pattern should be removed by es2018::object_rest_spread \
+                             pass"
+                        ),
+                        #[cfg(swc_ast_unknown)]
+                        _ => panic!("unable to access unknown nodes"),
+                    }
+                }
+            }
+            Pat::Assign(AssignPat {
+                span,
+                left,
+                right: def_value,
+                ..
+            }) => {
+                let init = if let Some(init) = decl.init {
+                    let tmp_ident = match &*init {
+                        Expr::Ident(i) if i.ctxt != SyntaxContext::empty() => i.clone(),
+
+                        _ => {
+                            let tmp_ident = private_ident!(span, "tmp");
+                            decls.push(VarDeclarator {
+                                span: DUMMY_SP,
+                                name: tmp_ident.clone().into(),
+                                init: Some(init),
+                                definite: false,
+                            });
+
+                            tmp_ident
+                        }
+                    };
+
+                    // tmp === void 0 ? def_value : tmp
+                    Some(Box::new(make_cond_expr(tmp_ident, def_value)))
+                } else {
+                    Some(def_value)
+                };
+
+                let var_decl = VarDeclarator {
+                    span,
+                    name: *left,
+                    init,
+                    definite: false,
+                };
+
+                let mut var_decls = vec![var_decl];
+                var_decls.visit_mut_with(self);
+                decls.extend(var_decls);
+            }
+
+            _ => unimplemented!("Pattern {:?}", decl),
+        }
+    }
+}
+
+struct AssignFolder {
+    loose: bool,
+    exporting: bool,
+    vars: Vec<VarDeclarator>,
+    /// Used like `.take().is_some()`.
+    ignore_return_value: Option<()>,
+}
+
+impl AssignFolder {
+    fn handle_assign_pat(&mut self, span: Span, mut pat: AssignPat, right: &mut Box<Expr>) -> Expr {
+        let ref_ident = make_ref_ident(self.loose, &mut self.vars, None);
+
+        let mut exprs = vec![Box::new(
+            AssignExpr {
+                span,
+                left: ref_ident.clone().into(),
+                op: op!("="),
+                right: right.take(),
+            }
+            .into(),
+        )];
+
+        let mut assign_cond_expr: Expr = AssignExpr {
+            span,
+            left: pat.left.take().try_into().unwrap(),
+            op: op!("="),
+            right: Box::new(make_cond_expr(ref_ident, pat.right.take())),
+        }
+        .into();
+
+        // The return value of intermediate expressions is not used
+        let ignore_return_value = self.ignore_return_value.replace(());
+        assign_cond_expr.visit_mut_with(self);
+        self.ignore_return_value = ignore_return_value;
+        exprs.push(Box::new(assign_cond_expr));
+
+        SeqExpr {
+            span: DUMMY_SP,
+            exprs,
+        }
+        .into()
+    }
+}
+
+impl VisitMut for AssignFolder {
+    noop_visit_mut_type!();
+
+    fn visit_mut_export_decl(&mut self, decl: &mut ExportDecl) {
+        let old = self.exporting;
+        self.exporting = true;
+        decl.visit_mut_children_with(self);
+        self.exporting = old;
+    }
+
+    fn visit_mut_function(&mut self, f: &mut Function) {
+        let exporting = mem::replace(&mut self.exporting, false);
+        f.visit_mut_children_with(self);
+        self.exporting = exporting;
+    }
+
+    fn visit_mut_class(&mut self, f: &mut Class) {
+        let exporting = mem::replace(&mut self.exporting, false);
+        f.visit_mut_children_with(self);
+        self.exporting = exporting;
+    }
+
+    fn visit_mut_object_lit(&mut self, f: &mut ObjectLit) {
+        let exporting = mem::replace(&mut self.exporting, false);
+        f.visit_mut_children_with(self);
+        self.exporting = exporting;
+    }
+
+    fn visit_mut_arrow_expr(&mut self, f: &mut ArrowExpr) {
+        let exporting = mem::replace(&mut self.exporting, false);
+        f.visit_mut_children_with(self);
+        self.exporting = exporting;
+    }
+
+    fn visit_mut_seq_expr(&mut self, seq: &mut SeqExpr) {
+        // In a sequence expression like (a, b, c), only the last expression's
+        // return value matters. All previous expressions should ignore their
+        // return value.
+        let ignore_return_value = self.ignore_return_value.take();
+
+        if let Some((last, rest)) = seq.exprs.split_last_mut() {
+            for expr in rest {
+                self.ignore_return_value = Some(());
+                expr.visit_mut_with(self);
+            }
+            self.ignore_return_value = ignore_return_value;
+            last.visit_mut_with(self);
+        }
+    }
+
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        // For transparent wrapper expressions, pass through ignore_return_value
+        // to the inner expression without consuming it.
+        if let Expr::Paren(..) = expr {
+            expr.visit_mut_children_with(self);
+            return;
+        }
+
+        let ignore_return_value = self.ignore_return_value.take().is_some();
+
+        expr.visit_mut_children_with(self);
+
+        self.fold_assign(expr, ignore_return_value);
+    }
+
+    fn visit_mut_stmt(&mut self, s: &mut Stmt) {
+        match s {
+            Stmt::Expr(e) => {
+                self.ignore_return_value = Some(());
+                e.visit_mut_with(self);
+                assert_eq!(self.ignore_return_value, None);
+            }
+            _ => s.visit_mut_children_with(self),
+        };
+    }
+
+    fn visit_mut_var_declarators(&mut self, declarators: &mut Vec<VarDeclarator>) {
+        declarators.visit_mut_children_with(self);
+
+        let is_complex = declarators
+            .iter()
+            .any(|d| !matches!(d.name, Pat::Ident(..)));
+        if !is_complex {
+            return;
+        }
+
+        let mut decls = Vec::with_capacity(declarators.len());
+        for decl in declarators.drain(..) {
+            self.visit_mut_var_decl(&mut decls, decl);
+        }
+
+        *declarators = decls;
+    }
+
+    fn visit_mut_var_decl(&mut self, var_decl: &mut VarDecl) {
+        var_decl.decls.visit_mut_with(self);
+
+        if var_decl.kind == VarDeclKind::Const {
+            var_decl.decls.iter_mut().for_each(|v| {
+                if v.init.is_none() {
+                    v.init = Some(Expr::undefined(DUMMY_SP));
+                }
+            })
+        }
+    }
+}
+
+impl AssignFolder {
+    /// Converts an assignment with a pattern. Generated nodes are visited, but
+    /// the children of `expr` are not.
+    fn fold_assign(&mut self, expr: &mut Expr, ignore_return_value: bool) {
+        if let Expr::Assign(AssignExpr {
+            span,
+            left: AssignTarget::Pat(pat),
+            op: op!("="),
+            right,
+        }) = expr
+        {
+            match pat {
+                // Pat::Expr(pat_expr) => {
+                //     *expr = Expr::Assign(AssignExpr {
+                //         span: *span,
+                //         left: pat_expr.take().try_into().unwrap(),
+                //         op: op!("="),
+                //         right: right.take(),
+                //     });
+                // }
+                AssignTargetPat::Array(ArrayPat { elems, .. }) => {
+                    let mut exprs = Vec::with_capacity(elems.len() + 1);
+
+                    if is_literal(right) && ignore_return_value {
+                        match &mut **right {
+                            Expr::Array(arr)
+                                if elems.len() == arr.elems.len()
+                                    || (elems.len() < arr.elems.len() && has_rest_pat(elems)) =>
+                            {
+                                let mut arr_elems = Some(arr.elems.take().into_iter());
+                                elems.iter_mut().for_each(|p| match p {
+                                    Some(Pat::Rest(p)) => {
+                                        exprs.push(
+                                            AssignExpr {
+                                                span: p.span(),
+                                                left: p.arg.take().try_into().unwrap(),
+                                                op: op!("="),
+                                                right: Box::new(
+                                                    ArrayLit {
+                                                        span: DUMMY_SP,
+                                                        elems: arr_elems
+                                                            .take()
+                                                            .expect("two rest element?")
+                                                            .collect(),
+                                                    }
+                                                    .into(),
+                                                ),
+                                            }
+                                            .into(),
+                                        );
+                                    }
+                                    Some(p) => {
+                                        let e = arr_elems
+                                            .as_mut()
+                                            .expect("pattern after rest element?")
+                                            .next()
+                                            .and_then(|v| v);
+                                        let mut right = e
+                                            .map(|e| {
+                                                debug_assert_eq!(e.spread, None);
+                                                e.expr
+                                            })
+                                            .unwrap_or_else(|| Expr::undefined(p.span()));
+
+                                        let p = p.take();
+                                        let mut expr = if let Pat::Assign(pat) = p {
+                                            self.handle_assign_pat(*span, pat, &mut right)
+                                        } else {
+                                            AssignExpr {
+                                                span: p.span(),
+                                                left: p.try_into().unwrap(),
+                                                op: op!("="),
+                                                right,
+                                            }
+                                            .into()
+                                        };
+
+                                        self.visit_mut_expr(&mut expr);
+
+                                        exprs.push(Box::new(expr));
+                                    }
+
+                                    None => {
+                                        arr_elems
+                                            .as_mut()
+                                            .expect("pattern after rest element?")
+                                            .next();
+                                    }
+                                });
+                                *expr = SeqExpr { span: *span, exprs }.into();
+                                return;
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    // initialized by first element of sequence expression
+                    let ref_ident = make_ref_ident_for_array(
+                        self.loose,
+                        &mut self.vars,
+                        None,
+                        Some(if has_rest_pat(elems) {
+                            usize::MAX
+                        } else {
+                            elems.len()
+                        }),
+                    );
+                    exprs.push(
+                        AssignExpr {
+                            span: DUMMY_SP,
+                            op: op!("="),
+                            left: ref_ident.clone().into(),
+                            right: if self.loose {
+                                right.take()
+                            } else {
+                                match &mut **right {
+                                    Expr::Ident(Ident { sym, .. }) if &**sym == "arguments" => {
+                                        Box::new(
+                                            CallExpr {
+                                                span: DUMMY_SP,
+                                                callee: member_expr!(
+                                                    Default::default(),
+                                                    Default::default(),
+                                                    Array.prototype.slice.call
+                                                )
+                                                .as_callee(),
+                                                args: vec![right.take().as_arg()],
+                                                ..Default::default()
+                                            }
+                                            .into(),
+                                        )
+                                    }
+                                    Expr::Array(..) => right.take(),
+                                    _ => {
+                                        // if left has rest then need `_to_array`
+                                        // else `_sliced_to_array`
+                                        if elems
+                                            .iter()
+                                            .any(|elem| matches!(elem, Some(Pat::Rest(..))))
+                                        {
+                                            Box::new(
+                                                CallExpr {
+                                                    span: DUMMY_SP,
+                                                    callee: helper!(to_array),
+                                                    args: vec![right.take().as_arg()],
+                                                    ..Default::default()
+                                                }
+                                                .into(),
+                                            )
+                                        } else {
+                                            Box::new(
+                                                CallExpr {
+                                                    span: DUMMY_SP,
+                                                    callee: helper!(sliced_to_array),
+                                                    args: vec![
+                                                        right.take().as_arg(),
+                                                        elems.len().as_arg(),
+                                                    ],
+                                                    ..Default::default()
+                                                }
+                                                .into(),
+                                            )
+                                        }
+                                    }
+                                }
+                            },
+                        }
+                        .into(),
+                    );
+
+                    for (i, elem) in elems.iter_mut().enumerate() {
+                        let elem = match elem {
+                            Some(elem) => elem,
+                            None => continue,
+                        };
+                        let elem_span = elem.span();
+
+                        match elem {
+                            Pat::Assign(AssignPat {
+                                span, left, right, ..
+                            }) => {
+                                // initialized by sequence expression.
+                                let assign_ref_ident =
+                                    make_ref_ident(self.loose, &mut self.vars, None);
+                                exprs.push(
+                                    AssignExpr {
+                                        span: DUMMY_SP,
+                                        left: assign_ref_ident.clone().into(),
+                                        op: op!("="),
+                                        right: ref_ident.clone().computed_member(i as f64).into(),
+                                    }
+                                    .into(),
+                                );
+
+                                let mut assign_expr: Expr = AssignExpr {
+                                    span: *span,
+                                    left: left.take().try_into().unwrap(),
+                                    op: op!("="),
+                                    right: Box::new(make_cond_expr(assign_ref_ident, right.take())),
+                                }
+                                .into();
+                                assign_expr.visit_mut_with(self);
+
+                                exprs.push(Box::new(assign_expr));
+                            }
+                            Pat::Rest(RestPat { arg, .. }) => {
+                                let mut assign_expr: Expr = AssignExpr {
+                                    span: elem_span,
+                                    op: op!("="),
+                                    left: arg.take().try_into().unwrap(),
+                                    right: CallExpr {
+                                        span: DUMMY_SP,
+                                        callee: ref_ident
+                                            .clone()
+                                            .make_member(quote_ident!("slice"))
+                                            .as_callee(),
+                                        args: vec![(i as f64).as_arg()],
+                                        ..Default::default()
+                                    }
+                                    .into(),
+                                }
+                                .into();
+
+                                assign_expr.visit_mut_with(self);
+                                exprs.push(Box::new(assign_expr));
+                            }
+                            _ => {
+                                let mut assign_expr: Expr = AssignExpr {
+                                    span: elem_span,
+                                    op: op!("="),
+                                    left: elem.take().try_into().unwrap(),
+                                    right: make_ref_idx_expr(&ref_ident, i).into(),
+                                }
+                                .into();
+
+                                assign_expr.visit_mut_with(self);
+                                exprs.push(Box::new(assign_expr))
+                            }
+                        }
+                    }
+
+                    // last one should be `ref`
+                    exprs.push(ref_ident.into());
+
+                    *expr = SeqExpr {
+                        span: DUMMY_SP,
+                        exprs,
+                    }
+                    .into()
+                }
+                AssignTargetPat::Object(ObjectPat { props, .. }) if props.is_empty() => {
+                    let mut right = right.take();
+                    right.visit_mut_with(self);
+
+                    *expr = helper_expr!(object_destructuring_empty)
+                        .as_call(DUMMY_SP, vec![right.as_arg()]);
+                }
+                AssignTargetPat::Object(ObjectPat { span, props, .. }) => {
+                    if ignore_return_value && props.len() == 1 {
+                        if let ObjectPatProp::Assign(p @ AssignPatProp { value: None, .. }) =
+                            &props[0]
+                        {
+                            *expr = AssignExpr {
+                                span: *span,
+                                op: op!("="),
+                                left: p.key.clone().into(),
+                                right: right.take().make_member(p.key.clone().into()).into(),
+                            }
+                            .into();
+                            return;
+                        }
+                    }
+
+                    let ref_ident = make_ref_ident(self.loose, &mut self.vars, None);
+
+                    let mut exprs = vec![Box::new(Expr::Assign(AssignExpr {
+                        span: *span,
+                        left: ref_ident.clone().into(),
+                        op: op!("="),
+                        right: right.take(),
+                    }))];
+
+                    for prop in props {
+                        let span = prop.span();
+                        match prop {
+                            ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                                let computed = matches!(key, PropName::Computed(..));
+
+                                let mut right = Box::new(make_ref_prop_expr(
+                                    &ref_ident,
+                                    Box::new(prop_name_to_expr(key.take())),
+                                    computed,
+                                ));
+                                let value = value.take();
+
+                                let mut expr = if let Pat::Assign(pat) = *value {
+                                    self.handle_assign_pat(span, pat, &mut right)
+                                } else {
+                                    AssignExpr {
+                                        span,
+                                        left: value.try_into().unwrap(),
+                                        op: op!("="),
+                                        right,
+                                    }
+                                    .into()
+                                };
+
+                                // The return value of intermediate expressions is not used
+                                let ignore_return_value = self.ignore_return_value.replace(());
+                                expr.visit_mut_with(self);
+                                self.ignore_return_value = ignore_return_value;
+                                exprs.push(Box::new(expr));
+                            }
+                            ObjectPatProp::Assign(AssignPatProp { key, value, .. }) => {
+                                let computed = false;
+
+                                match value {
+                                    Some(value) => {
+                                        let prop_ident =
+                                            make_ref_ident(self.loose, &mut self.vars, None);
+
+                                        exprs.push(
+                                            AssignExpr {
+                                                span,
+                                                left: prop_ident.clone().into(),
+                                                op: op!("="),
+                                                right: Box::new(make_ref_prop_expr(
+                                                    &ref_ident,
+                                                    key.clone().into(),
+                                                    computed,
+                                                )),
+                                            }
+                                            .into(),
+                                        );
+
+                                        exprs.push(
+                                            AssignExpr {
+                                                span,
+                                                left: key.clone().into(),
+                                                op: op!("="),
+                                                right: Box::new(make_cond_expr(
+                                                    prop_ident,
+                                                    value.take(),
+                                                )),
+                                            }
+                                            .into(),
+                                        );
+                                    }
+                                    None => {
+                                        exprs.push(
+                                            AssignExpr {
+                                                span,
+                                                left: key.clone().into(),
+                                                op: op!("="),
+                                                right: Box::new(make_ref_prop_expr(
+                                                    &ref_ident,
+                                                    key.clone().into(),
+                                                    computed,
+                                                )),
+                                            }
+                                            .into(),
+                                        );
+                                    }
+                                }
+                            }
+                            ObjectPatProp::Rest(_) => unreachable!(
+                                "object rest pattern should be removed by \
+                                 es2018::object_rest_spread pass"
+                            ),
+                            #[cfg(swc_ast_unknown)]
+                            _ => panic!("unable to access unknown nodes"),
+                        }
+                    }
+
+                    // Last one should be object itself.
+                    exprs.push(ref_ident.into());
+
+                    *expr = SeqExpr {
+                        span: DUMMY_SP,
+                        exprs,
+                    }
+                    .into();
+                }
+
+                AssignTargetPat::Invalid(..) => unreachable!(),
+                #[cfg(swc_ast_unknown)]
+                _ => panic!("unable to access unknown nodes"),
+            }
+        };
+    }
+}
+
+fn make_ref_idx_expr(ref_ident: &Ident, i: usize) -> MemberExpr {
+    ref_ident.clone().computed_member(i as f64)
+}
+
+fn make_ref_ident(loose: bool, decls: &mut Vec<VarDeclarator>, init: Option<Box<Expr>>) -> Ident {
+    make_ref_ident_for_array(loose, decls, init, None)
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+fn make_ref_ident_for_array(
+    loose: bool,
+    decls: &mut Vec<VarDeclarator>,
+    mut init: Option<Box<Expr>>,
+    elem_cnt: Option<usize>,
+) -> Ident {
+    if elem_cnt.is_none() {
+        if let Some(e) = init {
+            match *e {
+                Expr::Ident(i) => return i,
+                _ => init = Some(e),
+            }
+        }
+    }
+
+    let span = init.span();
+
+    let (ref_ident, aliased) = if loose {
+        if let Some(ref init) = init {
+            alias_if_required(init, "ref")
+        } else {
+            (private_ident!(span, "ref"), true)
+        }
+    } else if let Some(ref init) = init {
+        (alias_ident_for(init, "ref"), true)
+    } else {
+        (private_ident!(span, "ref"), true)
+    };
+
+    if aliased {
+        decls.push(VarDeclarator {
+            span,
+            name: ref_ident.clone().into(),
+            init: init.map(|v| {
+                if loose || matches!(*v, Expr::Array(..)) {
+                    v
+                } else {
+                    match elem_cnt {
+                        None => v,
+                        Some(std::usize::MAX) => Box::new(
+                            CallExpr {
+                                span: DUMMY_SP,
+                                callee: helper!(to_array),
+                                args: vec![v.as_arg()],
+                                ..Default::default()
+                            }
+                            .into(),
+                        ),
+                        Some(value) => Box::new(
+                            CallExpr {
+                                span: DUMMY_SP,
+                                callee: helper!(sliced_to_array),
+                                args: vec![v.as_arg(), value.as_arg()],
+                                ..Default::default()
+                            }
+                            .into(),
+                        ),
+                    }
+                }
+            }),
+            definite: false,
+        });
+    }
+
+    ref_ident
+}
+
+fn make_ref_prop_expr(ref_ident: &Ident, prop: Box<Expr>, mut computed: bool) -> Expr {
+    computed |= !matches!(*prop, Expr::Ident(..));
+
+    MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(ref_ident.clone().into()),
+        prop: if computed {
+            MemberProp::Computed(ComputedPropName {
+                span: DUMMY_SP,
+                expr: prop,
+            })
+        } else {
+            MemberProp::Ident(prop.ident().unwrap().into())
+        },
+    }
+    .into()
+}
+
+/// Creates `tmp === void 0 ? def_value : tmp`
+fn make_cond_expr(tmp: Ident, def_value: Box<Expr>) -> Expr {
+    CondExpr {
+        span: DUMMY_SP,
+        test: BinExpr {
+            span: DUMMY_SP,
+            left: Box::new(Expr::Ident(tmp.clone())),
+            op: op!("==="),
+            right: Box::new(Expr::Unary(UnaryExpr {
+                span: DUMMY_SP,
+                op: op!("void"),
+                arg: 0.0.into(),
+            })),
+        }
+        .into(),
+        cons: def_value,
+        alt: tmp.into(),
+    }
+    .into()
+}
+
+fn can_be_null(e: &Expr) -> bool {
+    match *e {
+        Expr::Lit(Lit::Null(..))
+        | Expr::This(..)
+        | Expr::Ident(..)
+        | Expr::PrivateName(..)
+        | Expr::Member(..)
+        | Expr::SuperProp(..)
+        | Expr::Call(..)
+        | Expr::OptChain(..)
+        | Expr::New(..)
+        | Expr::Yield(..)
+        | Expr::Await(..)
+        | Expr::MetaProp(..) => true,
+
+        Expr::Lit(..) => false,
+
+        Expr::Array(..)
+        | Expr::Arrow(..)
+        | Expr::Object(..)
+        | Expr::Fn(..)
+        | Expr::Class(..)
+        | Expr::Tpl(..) => false,
+
+        Expr::TaggedTpl(..) => true,
+
+        Expr::Paren(ParenExpr { ref expr, .. }) => can_be_null(expr),
+        Expr::Seq(SeqExpr { ref exprs, .. }) => {
+            exprs.last().map(|e| can_be_null(e)).unwrap_or(true)
+        }
+        Expr::Assign(AssignExpr { ref right, .. }) => can_be_null(right),
+        Expr::Cond(CondExpr {
+            ref cons, ref alt, ..
+        }) => can_be_null(cons) || can_be_null(alt),
+
+        Expr::Unary(..) | Expr::Update(..) | Expr::Bin(..) => true,
+
+        Expr::JSXMember(..)
+        | Expr::JSXNamespacedName(..)
+        | Expr::JSXEmpty(..)
+        | Expr::JSXElement(..)
+        | Expr::JSXFragment(..) => unreachable!("destructuring jsx"),
+
+        Expr::TsNonNull(..) => false,
+        Expr::TsAs(TsAsExpr { ref expr, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { ref expr, .. })
+        | Expr::TsConstAssertion(TsConstAssertion { ref expr, .. })
+        | Expr::TsInstantiation(TsInstantiation { ref expr, .. })
+        | Expr::TsSatisfies(TsSatisfiesExpr { ref expr, .. }) => can_be_null(expr),
+
+        Expr::Invalid(..) => unreachable!(),
+        #[cfg(swc_ast_unknown)]
+        _ => panic!("unable to access unknown nodes"),
+    }
+}
@@ -35,10 +35,18 @@ use swc_ecma_utils::{IdentUsageFinder, private_ident};
 use crate::TraverseCtx;
 
 pub fn hook() -> impl VisitMutHook<TraverseCtx> {
-    FunctionNamePass
+    FunctionNamePass::default()
 }
 
-struct FunctionNamePass;
+#[derive(Default)]
+struct FunctionNamePass {
+    /// Whether the values of the enclosing variable declarators, assignments
+    /// and properties were arrow functions.
+    ///
+    /// Arrow functions are converted to function expressions before the
+    /// declarators are exited, but they should not be named.
+    arrows: Vec<bool>,
+}
 
 /// This function makes a new private identifier if required.
 fn prepare(i: Ident) -> Ident {
@@ -50,8 +58,12 @@ fn prepare(i: Ident) -> Ident {
 }
 
 impl VisitMutHook<TraverseCtx> for FunctionNamePass {
+    fn enter_assign_expr(&mut self, expr: &mut AssignExpr, _ctx: &mut TraverseCtx) {
+        self.arrows.push(expr.right.is_arrow());
+    }
+
     fn exit_assign_expr(&mut self, expr: &mut AssignExpr, _ctx: &mut TraverseCtx) {
-        if expr.op != op!("=") {
+        if self.arrows.pop().unwrap_or_default() || expr.op != op!("=") {
             return;
         }
 
@@ -61,20 +73,34 @@ impl VisitMutHook<TraverseCtx> for FunctionNamePass {
         }
     }
 
-    fn exit_key_value_prop(&mut self, p: &mut KeyValueProp, _ctx: &mut TraverseCtx) {
-        rename_key_value_prop(p);
+    fn enter_prop(&mut self, prop: &mut Prop, _ctx: &mut TraverseCtx) {
+        self.arrows
+            .push(matches!(prop, Prop::KeyValue(p) if p.value.is_arrow()));
     }
 
     fn exit_prop(&mut self, prop: &mut Prop, _ctx: &mut TraverseCtx) {
-        // Handle KeyValue props here too, because shorthand transform may have
+        // Handle KeyValue props here, because shorthand transform may have
         // converted Method to KeyValue in its exit_prop handler, which runs
         // before this one in the chain.
+        if self.arrows.pop().unwrap_or_default() {
+            return;
+        }
+
         if let Prop::KeyValue(p) = prop {
             rename_key_value_prop(p);
         }
     }
 
+    fn enter_var_declarator(&mut self, decl: &mut VarDeclarator, _ctx: &mut TraverseCtx) {
+        self.arrows
+            .push(decl.init.as_deref().is_some_and(Expr::is_arrow));
+    }
+
     fn exit_var_declarator(&mut self, decl: &mut VarDeclarator, _ctx: &mut TraverseCtx) {
+        if self.arrows.pop().unwrap_or_default() {
+            return;
+        }
+
         if let Pat::Ident(ref ident) = decl.name {
             let name = prepare(Ident::from(ident));
             if let Some(ref mut init) = decl.init {
@@ -1,17 +1,34 @@
+use swc_common::SyntaxContext;
 use swc_ecma_hooks::VisitMutHook;
+use swc_ecma_transforms_base::assumptions::Assumptions;
 
 use crate::{
     TraverseCtx,
     hook_utils::{HookBuilder, NoopHook},
 };
 
+mod arrow;
+mod block_scoped_fn;
+mod block_scoping;
+mod classes;
+mod computed_props;
+mod destructuring;
 mod duplicate_keys;
+mod for_of;
 mod function_name;
+mod generator;
 mod instanceof;
+mod new_target;
+mod object_super;
+mod parameters;
 mod shorthand;
+mod spread;
 mod sticky_regex;
+mod template_literal;
 mod typeof_symbol;
 
+pub use self::arrow::lower_arrows;
+
 #[derive(Debug, Default)]
 #[non_exhaustive]
 pub struct Es2015Options {
@@ -32,6 +49,60 @@ pub struct Es2015Options {
 
     /// Enable typeof symbol transformation
     pub typeof_symbol: bool,
+
+    /// Enable block scoped functions transformation
+    pub block_scoped_fn: bool,
+
+    /// Enable template literal transformation
+    pub template_literal: bool,
+
+    /// Enable classes transformation
+    pub classes: bool,
+
+    /// Enable new.target transformation
+    pub new_target: bool,
+
+    /// Enable spread transformation
+    pub spread: bool,
+
+    /// Use `[].concat` instead of `_to_consumable_array` for spread elements
+    pub spread_loose: bool,
+
+    /// Enable object super transformation
+    pub object_super: bool,
+
+    /// Enable for-of transformation
+    pub for_of: bool,
+
+    /// Use `_create_for_of_iterator_helper_loose` for for-of loops
+    pub for_of_loose: bool,
+
+    /// Assume the iterables of for-of loops are arrays
+    pub for_of_assume_array: bool,
+
+    /// Enable parameters transformation
+    pub parameters: bool,
+
+    /// Enable arrow functions transformation
+    pub arrow: bool,
+
+    /// Enable computed properties transformation
+    pub computed_props: bool,
+
+    /// Use assignments instead of `_define_property` for computed properties
+    pub computed_props_loose: bool,
+
+    /// Enable destructuring transformation
+    pub destructuring: bool,
+
+    /// Assume destructured values are arrays when converting array patterns
+    pub destructuring_loose: bool,
+
+    /// Enable block scoping transformation
+    pub block_scoping: bool,
+
+    /// Enable generator transformation
+    pub generator: bool,
 }
 
 impl Es2015Options {
@@ -43,13 +114,77 @@ impl Es2015Options {
             || self.sticky_regex
             || self.instanceof
             || self.typeof_symbol
+            || self.block_scoped_fn
+            || self.template_literal
+            || self.classes
+            || self.new_target
+            || self.spread
+            || self.object_super
+            || self.for_of
+            || self.parameters
+            || self.arrow
+            || self.computed_props
+            || self.destructuring
+            || self.block_scoping
+            || self.generator
     }
 }
 
-pub fn hook(options: Es2015Options) -> impl VisitMutHook<TraverseCtx> {
+pub fn hook(
+    options: Es2015Options,
+    unresolved_ctxt: SyntaxContext,
+    assumptions: Assumptions,
+) -> impl VisitMutHook<TraverseCtx> {
     let hook = HookBuilder::new(NoopHook);
 
+    // Block scoped functions: { function f() {} } -> { let f = function f() {} }
+    let hook = hook.chain(if options.block_scoped_fn {
+        Some(self::block_scoped_fn::hook())
+    } else {
+        None
+    });
+
+    // Template literals: `a${b}` -> "a".concat(b)
+    let hook = hook.chain(if options.template_literal {
+        Some(self::template_literal::hook(
+            assumptions.ignore_to_primitive_hint,
+            assumptions.mutable_template_object,
+        ))
+    } else {
+        None
+    });
+
+    // Classes: class A {} -> var A = function A() { _class_call_check(this, A); }
+    let hook = hook.chain(if options.classes {
+        Some(self::classes::hook(assumptions))
+    } else {
+        None
+    });
+
+    // new.target: function F() { new.target } -> function F() { this instanceof F ? ... }
+    let hook = hook.chain(if options.new_target {
+        Some(self::new_target::hook())
+    } else {
+        None
+    });
+
+    // Spread: f(...a) -> f.apply(void 0, _to_consumable_array(a))
+    let hook = hook.chain(if options.spread {
+        Some(self::spread::hook(options.spread_loose, unresolved_ctxt))
+    } else {
+        None
+    });
+
+    // Object super: ({ f() { super.f() } }) -> _obj = { f() { _get(...).call(this) } }
+    let hook = hook.chain(if options.object_super {
+        Some(self::object_super::hook())
+    } else {
+        None
+    });
+
     // Shorthand properties: { a } -> { a: a }
+    //
+    // This should come before function name, so methods are named as well.
     let hook = hook.chain(if options.shorthand {
         Some(self::shorthand::hook())
     } else {
@@ -63,6 +198,61 @@ pub fn hook(options: Es2015Options) -> impl VisitMutHook<TraverseCtx> {
         None
     });
 
+    // For of: for (a of b) {} -> for (var _iterator = b[Symbol.iterator](), ...) {}
+    let hook = hook.chain(if options.for_of {
+        Some(self::for_of::hook(
+            options.for_of_loose,
+            options.for_of_assume_array,
+        ))
+    } else {
+        None
+    });
+
+    // Parameters: function f(a = 1) {} -> function f() { var a = ... }
+    let hook = hook.chain(if options.parameters {
+        Some(self::parameters::hook(
+            assumptions.ignore_function_length,
+            unresolved_ctxt,
+        ))
+    } else {
+        None
+    });
+
+    // Arrow functions: (a) => a -> function (a) { return a; }
+    let hook = hook.chain(if options.arrow {
+        Some(self::arrow::hook(unresolved_ctxt))
+    } else {
+        None
+    });
+
+    // Computed properties: { [a]: 1 } -> _define_property({}, a, 1)
+    let hook = hook.chain(if options.computed_props {
+        Some(self::computed_props::hook(options.computed_props_loose))
+    } else {
+        None
+    });
+
+    // Destructuring: var { a } = b -> var a = b.a
+    let hook = hook.chain(if options.destructuring {
+        Some(self::destructuring::hook(options.destructuring_loose))
+    } else {
+        None
+    });
+
+    // Block scoping: let a = 1 -> var a = 1
+    let hook = hook.chain(if options.block_scoping {
+        Some(self::block_scoping::hook(unresolved_ctxt))
+    } else {
+        None
+    });
+
+    // Generators: function* f() {} -> function f() { return _ts_generator(...) }
+    let hook = hook.chain(if options.generator {
+        Some(self::generator::hook(unresolved_ctxt))
+    } else {
+        None
+    });
+
     // Duplicate keys: { a: 1, a: 2 } -> { a: 1, ["a"]: 2 }
     let hook = hook.chain(if options.duplicate_keys {
         Some(self::duplicate_keys::hook())
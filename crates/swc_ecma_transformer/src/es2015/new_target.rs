@@ -0,0 +1,215 @@
+//! ES2015: new.target
+//!
+//! This plugin transforms `new.target` meta properties.
+//!
+//! > This plugin is included in `preset-env`, in ES2015
+//!
+//! ## Example
+//!
+//! Input:
+//! ```js
+//! function Foo() {
+//!   console.log(new.target);
+//! }
+//! ```
+//!
+//! Output:
+//! ```js
+//! function Foo() {
+//!   console.log(this instanceof Foo ? this.constructor : void 0);
+//! }
+//! ```
+//!
+//! ## Implementation
+//!
+//! Implementation based on [@babel/plugin-transform-new-target](https://babel.dev/docs/babel-plugin-transform-new-target).
+
+use swc_common::DUMMY_SP;
+use swc_ecma_ast::*;
+use swc_ecma_hooks::VisitMutHook;
+use swc_ecma_utils::{ExprFactory, private_ident, quote_ident};
+use swc_ecma_visit::{Visit, VisitWith, noop_visit_type};
+
+use crate::TraverseCtx;
+
+pub fn hook() -> impl VisitMutHook<TraverseCtx> {
+    NewTargetPass::default()
+}
+
+#[derive(Default)]
+struct NewTargetPass {
+    /// Innermost scope last. `new.target` outside of any scope is treated as
+    /// if it's in a constructor.
+    scopes: Vec<Scope>,
+
+    /// Functions of methods, which are not entered yet.
+    method_fns: Vec<*const Function>,
+}
+
+struct Scope {
+    node: *const (),
+    ctx: Ctx,
+}
+
+enum Ctx {
+    Constructor,
+    Method,
+    Function(Ident),
+}
+
+impl NewTargetPass {
+    fn enter_scope<T>(&mut self, node: &T, ctx: Ctx) {
+        self.scopes.push(Scope {
+            node: node as *const T as *const (),
+            ctx,
+        });
+    }
+
+    fn exit_scope<T>(&mut self, node: &T) {
+        if self
+            .scopes
+            .last()
+            .is_some_and(|scope| scope.node == node as *const T as *const ())
+        {
+            self.scopes.pop();
+        }
+    }
+}
+
+impl VisitMutHook<TraverseCtx> for NewTargetPass {
+    fn enter_class_method(&mut self, c: &mut ClassMethod, _: &mut TraverseCtx) {
+        self.method_fns.push(&*c.function);
+    }
+
+    fn enter_method_prop(&mut self, m: &mut MethodProp, _: &mut TraverseCtx) {
+        self.method_fns.push(&*m.function);
+    }
+
+    fn enter_function(&mut self, f: &mut Function, _: &mut TraverseCtx) {
+        let ptr = f as *const Function;
+        if let Some(pos) = self.method_fns.iter().rposition(|&p| p == ptr) {
+            self.method_fns.remove(pos);
+            self.enter_scope(f, Ctx::Method);
+        }
+    }
+
+    fn exit_function(&mut self, f: &mut Function, _: &mut TraverseCtx) {
+        self.exit_scope(f);
+    }
+
+    fn enter_getter_prop(&mut self, m: &mut GetterProp, _: &mut TraverseCtx) {
+        self.enter_scope(m, Ctx::Method);
+    }
+
+    fn exit_getter_prop(&mut self, m: &mut GetterProp, _: &mut TraverseCtx) {
+        self.exit_scope(m);
+    }
+
+    fn enter_setter_prop(&mut self, m: &mut SetterProp, _: &mut TraverseCtx) {
+        self.enter_scope(m, Ctx::Method);
+    }
+
+    fn exit_setter_prop(&mut self, m: &mut SetterProp, _: &mut TraverseCtx) {
+        self.exit_scope(m);
+    }
+
+    fn enter_constructor(&mut self, c: &mut Constructor, _: &mut TraverseCtx) {
+        self.enter_scope(c, Ctx::Constructor);
+    }
+
+    fn exit_constructor(&mut self, c: &mut Constructor, _: &mut TraverseCtx) {
+        self.exit_scope(c);
+    }
+
+    fn enter_fn_decl(&mut self, f: &mut FnDecl, _: &mut TraverseCtx) {
+        // Ensure that `f` contains `new.target`.
+        if !contains_new_target(&*f) {
+            return;
+        }
+
+        let ctx = Ctx::Function(f.ident.clone());
+        self.enter_scope(f, ctx);
+    }
+
+    fn exit_fn_decl(&mut self, f: &mut FnDecl, _: &mut TraverseCtx) {
+        self.exit_scope(f);
+    }
+
+    fn enter_fn_expr(&mut self, f: &mut FnExpr, _: &mut TraverseCtx) {
+        // Ensure that `f` contains `new.target`.
+        if !contains_new_target(&*f) {
+            return;
+        }
+
+        let i = f
+            .ident
+            .get_or_insert_with(|| private_ident!("_target"))
+            .clone();
+
+        self.enter_scope(f, Ctx::Function(i));
+    }
+
+    fn exit_fn_expr(&mut self, f: &mut FnExpr, _: &mut TraverseCtx) {
+        self.exit_scope(f);
+    }
+
+    fn exit_expr(&mut self, e: &mut Expr, _: &mut TraverseCtx) {
+        if let Expr::MetaProp(MetaPropExpr {
+            kind: MetaPropKind::NewTarget,
+            span,
+        }) = e
+        {
+            let this_ctor = |span| {
+                ThisExpr { span }
+                    .make_member(quote_ident!("constructor"))
+                    .into()
+            };
+            match self.scopes.last().map(|scope| &scope.ctx) {
+                None | Some(Ctx::Constructor) => *e = this_ctor(*span),
+                Some(Ctx::Method) => *e = *Expr::undefined(DUMMY_SP),
+                Some(Ctx::Function(i)) => {
+                    *e = CondExpr {
+                        span: *span,
+                        // this instanceof Foo
+                        test: BinExpr {
+                            span: DUMMY_SP,
+                            op: op!("instanceof"),
+                            left: Box::new(Expr::This(ThisExpr { span: DUMMY_SP })),
+                            right: Box::new(Expr::Ident(i.clone())),
+                        }
+                        .into(),
+                        cons: Box::new(this_ctor(DUMMY_SP)),
+                        // void 0
+                        alt: Expr::undefined(DUMMY_SP),
+                    }
+                    .into()
+                }
+            }
+        }
+    }
+}
+
+fn contains_new_target<N: VisitWith<NewTargetFinder>>(node: &N) -> bool {
+    let mut v = NewTargetFinder::default();
+    node.visit_with(&mut v);
+    v.found
+}
+
+#[derive(Default)]
+struct NewTargetFinder {
+    found: bool,
+}
+
+impl Visit for NewTargetFinder {
+    noop_visit_type!();
+
+    fn visit_meta_prop_expr(&mut self, n: &MetaPropExpr) {
+        if let MetaPropExpr {
+            kind: MetaPropKind::NewTarget,
+            ..
+        } = n
+        {
+            self.found = true;
+        }
+    }
+}
@@ -0,0 +1,961 @@
+//! ES2015: Parameters
+//!
+//! This plugin transforms default, destructured and rest parameters.
+//!
+//! > This plugin is included in `preset-env`, in ES2015
+//!
+//! ## Example
+//!
+//! Input:
+//! ```js
+//! function test(x = "hello", { a, b }, ...args) {
+//!   console.log(x, a, b, args);
+//! }
+//! ```
+//!
+//! Output:
+//! ```js
+//! function test() {
+//!   let x = arguments.length > 0 && arguments[0] !== void 0 ? arguments[0] : "hello",
+//!     { a, b } = arguments.length > 1 ? arguments[1] : void 0;
+//!   for (var _len = arguments.length, args = new Array(_len > 2 ? _len - 2 : 0), _key = 2; _key < _len; _key++) {
+//!     args[_key - 2] = arguments[_key];
+//!   }
+//!   console.log(x, a, b, args);
+//! }
+//! ```
+//!
+//! ## Implementation
+//!
+//! Implementation based on [@babel/plugin-transform-parameters](https://babel.dev/docs/babel-plugin-transform-parameters).
+//!
+//! Parameters are moved into the function body when the function is entered,
+//! so the generated `let` declarations are visited by the other transforms.
+
+use std::{mem, ptr};
+
+use arrayvec::ArrayVec;
+use swc_atoms::atom;
+use swc_common::{DUMMY_SP, Mark, Spanned, SyntaxContext, util::take::Take};
+use swc_ecma_ast::*;
+use swc_ecma_hooks::VisitMutHook;
+use swc_ecma_utils::{
+    ExprFactory,
+    function::{FnEnvHoister, init_this},
+    member_expr, prepend_stmt, prepend_stmts, private_ident, quote_ident,
+};
+use swc_ecma_visit::{VisitMut, VisitMutWith, noop_visit_mut_type};
+
+use crate::TraverseCtx;
+
+pub fn hook(
+    ignore_function_length: bool,
+    unresolved_ctxt: SyntaxContext,
+) -> impl VisitMutHook<TraverseCtx> {
+    ParametersPass {
+        ignore_function_length,
+        unresolved_ctxt,
+        hoister: FnEnvHoister::new(unresolved_ctxt),
+        hoister_stack: Vec::new(),
+        ctor_bodies: Vec::new(),
+        in_subclass: false,
+        in_prop: false,
+        ctx_stack: Vec::new(),
+        converted_arrows: Vec::new(),
+    }
+}
+
+struct ParametersPass {
+    ignore_function_length: bool,
+    unresolved_ctxt: SyntaxContext,
+
+    /// Used to store `this, in case if `arguments` is used and we should
+    /// transform an arrow expression to a function expression.
+    hoister: FnEnvHoister,
+    hoister_stack: Vec<FnEnvHoister>,
+
+    /// Bodies of the constructors being visited. The hoister of a constructor
+    /// body is handled by the constructor itself.
+    ctor_bodies: Vec<*const Vec<Stmt>>,
+
+    in_subclass: bool,
+    in_prop: bool,
+
+    /// `(in_subclass, in_prop)` of the enclosing nodes.
+    ctx_stack: Vec<(bool, bool)>,
+
+    /// Arrow functions converted to functions, innermost last. `None` is pushed
+    /// for functions and classes, which have their own `this`.
+    converted_arrows: Vec<Option<ConvertedArrow>>,
+}
+
+struct ConvertedArrow {
+    expr: *const Expr,
+    function: *const Function,
+
+    /// Index of the hoister which captured `this` of the arrow function, i.e.
+    /// the hoister of the enclosing scope.
+    hoister: usize,
+}
+
+impl ParametersPass {
+    fn push_ctx(&mut self, in_subclass: bool, in_prop: bool) {
+        self.ctx_stack.push((self.in_subclass, self.in_prop));
+        self.in_subclass = in_subclass;
+        self.in_prop = in_prop;
+    }
+
+    fn pop_ctx(&mut self) {
+        (self.in_subclass, self.in_prop) = self.ctx_stack.pop().unwrap_or_default();
+    }
+
+    fn enter_hoister_scope(&mut self) {
+        let hoister = self.hoister.take();
+        self.hoister_stack.push(hoister);
+    }
+
+    /// Restores the hoister of the enclosing scope, and returns the hoister of
+    /// the current one.
+    fn exit_hoister_scope(&mut self) -> FnEnvHoister {
+        let outer = self.hoister_stack.pop().unwrap_or_default();
+
+        mem::replace(&mut self.hoister, outer)
+    }
+
+    /// Returns the variable declarations captured by the hoister of the scope
+    /// being exited.
+    ///
+    /// If the scope is in a converted arrow function, `this` and `arguments`
+    /// of the declarations are those of the arrow function, so they are
+    /// captured by the hoister of the arrow function.
+    fn exit_hoister_scope_to_stmt(&mut self) -> Option<Stmt> {
+        let mut stmt = self.exit_hoister_scope().to_stmt()?;
+
+        if let Some(Some(arrow)) = self.converted_arrows.last() {
+            let hoister = if arrow.hoister == self.hoister_stack.len() {
+                &mut self.hoister
+            } else {
+                &mut self.hoister_stack[arrow.hoister]
+            };
+
+            stmt.visit_mut_with(hoister);
+        }
+
+        Some(stmt)
+    }
+
+    /// Returns true if `f` is an arrow function converted to a function.
+    fn is_converted_arrow(&self, f: &Function) -> bool {
+        matches!(
+            self.converted_arrows.last(),
+            Some(Some(arrow)) if ptr::eq(arrow.function, f)
+        )
+    }
+
+    fn convert_fn_like(&self, ps: &mut Vec<Param>, body: &mut BlockStmt, is_setter: bool) {
+        let mut params = Vec::new();
+        let mut decls = Vec::new();
+        let mut loose_stmt = Vec::new();
+        let mut unpack_rest = None;
+        let mut decls_after_unpack = Vec::new();
+
+        let mut after_default = false;
+
+        for (i, param) in ps.drain(..).enumerate() {
+            let span = param.span();
+
+            match param.pat {
+                Pat::Ident(..) => {
+                    if after_default && !self.ignore_function_length {
+                        decls.push(VarDeclarator {
+                            span,
+                            name: param.pat,
+                            init: Some(Box::new(check_arg_len_or_undef(i))),
+                            definite: false,
+                        })
+                    } else {
+                        params.push(param)
+                    }
+                }
+                Pat::Array(..) | Pat::Object(..) => {
+                    if after_default && !self.ignore_function_length {
+                        decls.push(VarDeclarator {
+                            span,
+                            name: param.pat,
+                            init: Some(Box::new(check_arg_len_or_undef(i))),
+                            definite: false,
+                        })
+                    } else {
+                        let binding = private_ident!(span, "param");
+
+                        params.push(Param {
+                            pat: binding.clone().into(),
+                            ..param
+                        });
+                        let decl = VarDeclarator {
+                            span,
+                            name: param.pat,
+                            init: Some(binding.into()),
+                            definite: false,
+                        };
+                        if self.ignore_function_length {
+                            loose_stmt.push(
+                                VarDecl {
+                                    span,
+                                    kind: VarDeclKind::Let,
+                                    decls: vec![decl],
+                                    declare: false,
+                                    ..Default::default()
+                                }
+                                .into(),
+                            )
+                        } else {
+                            decls.push(decl)
+                        }
+                    }
+                }
+                Pat::Assign(AssignPat { left, right, .. }) => {
+                    // arguments.length will always be 1 in setter
+                    if !(self.ignore_function_length || is_setter) {
+                        after_default = true;
+                        // access non-existent element in `arguments` is expensive
+                        decls.push(VarDeclarator {
+                            span,
+                            name: *left,
+                            init: Some(
+                                CondExpr {
+                                    span,
+                                    test: Box::new(
+                                        BinExpr {
+                                            left: Box::new(check_arg_len(i)),
+                                            op: op!("&&"),
+                                            right: Box::new(Expr::Bin(BinExpr {
+                                                left: make_arg_nth(i).into(),
+                                                op: op!("!=="),
+                                                right: Expr::undefined(DUMMY_SP),
+                                                span: DUMMY_SP,
+                                            })),
+                                            span,
+                                        }
+                                        .into(),
+                                    ),
+                                    cons: make_arg_nth(i).into(),
+                                    alt: right,
+                                }
+                                .into(),
+                            ),
+                            definite: false,
+                        })
+                    } else if let Pat::Ident(ident) = left.as_ref() {
+                        params.push(Param {
+                            span,
+                            pat: ident.clone().into(),
+                            decorators: Vec::new(),
+                        });
+                        loose_stmt.push(
+                            IfStmt {
+                                span,
+                                test: BinExpr {
+                                    span: DUMMY_SP,
+                                    left: Box::new(Ident::from(ident).into()),
+                                    op: op!("==="),
+                                    right: Expr::undefined(DUMMY_SP),
+                                }
+                                .into(),
+                                cons: Box::new(Stmt::Expr(ExprStmt {
+                                    span,
+                                    expr: AssignExpr {
+                                        span,
+                                        left: left.try_into().unwrap(),
+                                        op: op!("="),
+                                        right,
+                                    }
+                                    .into(),
+                                })),
+                                alt: None,
+                            }
+                            .into(),
+                        )
+                    } else {
+                        let binding = private_ident!(span, "param");
+                        params.push(Param {
+                            span: DUMMY_SP,
+                            decorators: Default::default(),
+                            pat: binding.clone().into(),
+                        });
+                        loose_stmt.push(
+                            VarDecl {
+                                span,
+                                kind: VarDeclKind::Let,
+                                decls: vec![VarDeclarator {
+                                    span,
+                                    name: *left,
+                                    init: Some(Box::new(Expr::Cond(CondExpr {
+                                        span,
+                                        test: Box::new(Expr::Bin(BinExpr {
+                                            span: DUMMY_SP,
+                                            left: Box::new(Expr::Ident(binding.clone())),
+                                            op: op!("==="),
+                                            right: Expr::undefined(DUMMY_SP),
+                                        })),
+                                        cons: right,
+                                        alt: Box::new(Expr::Ident(binding)),
+                                    }))),
+                                    definite: false,
+                                }],
+                                declare: false,
+                                ..Default::default()
+                            }
+                            .into(),
+                        )
+                    }
+                }
+                Pat::Rest(RestPat { arg, .. }) => {
+                    // Inject a for statement
+                    //
+                    // for(var _len = arguments.length, a1 = new Array(_len), _key = 0; _key <
+                    // _len; _key++){
+                    //      a1[_key] = arguments[_key];
+                    // }
+                    assert!(unpack_rest.is_none());
+
+                    // TODO: Optimize (use `arguments` instead of rest argument)
+
+                    let mark = Mark::fresh(Mark::root());
+                    let idx_ident =
+                        quote_ident!(SyntaxContext::empty().apply_mark(mark), span, "_key");
+                    let len_ident =
+                        quote_ident!(SyntaxContext::empty().apply_mark(mark), span, "_len");
+
+                    let arg = match *arg {
+                        Pat::Ident(ident) => ident.into(),
+                        arg => {
+                            let tmp_ident =
+                                quote_ident!(SyntaxContext::empty().apply_mark(mark), span, "_tmp");
+                            decls_after_unpack.push(VarDeclarator {
+                                span: DUMMY_SP,
+                                name: arg,
+                                init: Some(Box::new(tmp_ident.clone().into())),
+                                definite: false,
+                            });
+                            tmp_ident
+                        }
+                    };
+
+                    let make_minus_i = |ident: &Ident, min_zero: bool| -> Expr {
+                        if i == 0 {
+                            // `len`
+                            ident.clone().into()
+                        } else {
+                            // `len - $i`
+                            let bin: Expr = BinExpr {
+                                span,
+                                left: ident.clone().into(),
+                                op: op!(bin, "-"),
+                                right: Lit::Num(Number {
+                                    span,
+                                    value: i as f64,
+                                    raw: None,
+                                })
+                                .into(),
+                            }
+                            .into();
+                            if !min_zero {
+                                return bin;
+                            }
+
+                            CondExpr {
+                                span,
+                                test: Box::new(
+                                    BinExpr {
+                                        span,
+                                        left: Box::new(len_ident.clone().into()),
+                                        op: op!(">"),
+                                        right: Lit::Num(Number {
+                                            span,
+                                            value: i as _,
+                                            raw: None,
+                                        })
+                                        .into(),
+                                    }
+                                    .into(),
+                                ),
+                                cons: Box::new(bin),
+                                alt: 0.into(),
+                            }
+                            .into()
+                        }
+                    };
+
+                    unpack_rest = Some(
+                        ForStmt {
+                            span,
+                            init: Some(
+                                VarDecl {
+                                    kind: VarDeclKind::Var,
+                                    span,
+                                    decls: vec![
+                                        // _len = arguments.length - i
+                                        VarDeclarator {
+                                            span,
+                                            name: len_ident.clone().into(),
+                                            init: Some(
+                                                member_expr!(
+                                                    Default::default(),
+                                                    span,
+                                                    arguments.length
+                                                )
+                                                .into(),
+                                            ),
+                                            definite: false,
+                                        },
+                                        // a1 = new Array(_len - $i)
+                                        VarDeclarator {
+                                            span,
+                                            name: arg.clone().into(),
+                                            init: Some(Box::new(Expr::New(NewExpr {
+                                                span,
+                                                callee: Box::new(
+                                                    quote_ident!(self.unresolved_ctxt, "Array")
+                                                        .into(),
+                                                ),
+                                                args: Some(vec![{
+                                                    // `len` or  `len - $i`
+                                                    make_minus_i(&len_ident, true).as_arg()
+                                                }]),
+                                                ..Default::default()
+                                            }))),
+                                            definite: false,
+                                        },
+                                        // _key = 0
+                                        VarDeclarator {
+                                            span,
+                                            name: idx_ident.clone().into(),
+                                            init: Some(Box::new(Expr::Lit(Lit::Num(Number {
+                                                span,
+                                                value: i as f64,
+                                                raw: None,
+                                            })))),
+                                            definite: false,
+                                        },
+                                    ],
+                                    declare: false,
+                                    ..Default::default()
+                                }
+                                .into(),
+                            ),
+                            // `_key < _len`
+                            test: Some(
+                                BinExpr {
+                                    span,
+                                    left: Box::new(idx_ident.clone().into()),
+                                    op: op!("<"),
+                                    right: Box::new(len_ident.clone().into()),
+                                }
+                                .into(),
+                            ),
+                            // _key++
+                            update: Some(
+                                UpdateExpr {
+                                    span,
+                                    op: op!("++"),
+                                    prefix: false,
+                                    arg: Box::new(idx_ident.clone().into()),
+                                }
+                                .into(),
+                            ),
+                            body: Box::new(Stmt::Block(BlockStmt {
+                                span: DUMMY_SP,
+                                stmts: vec![{
+                                    let prop = Box::new(Expr::Ident(idx_ident.clone()));
+                                    // a1[_key - i] = arguments[_key];
+
+                                    AssignExpr {
+                                        span,
+                                        left: arg
+                                            .computed_member(make_minus_i(&idx_ident, false))
+                                            .into(),
+                                        op: op!("="),
+                                        right: Box::new(
+                                            MemberExpr {
+                                                span: DUMMY_SP,
+                                                obj: Box::new(
+                                                    quote_ident!(
+                                                        Default::default(),
+                                                        span,
+                                                        "arguments"
+                                                    )
+                                                    .into(),
+                                                ),
+                                                prop: MemberProp::Computed(ComputedPropName {
+                                                    span,
+                                                    expr: prop,
+                                                }),
+                                            }
+                                            .into(),
+                                        ),
+                                    }
+                                    .into_stmt()
+                                }],
+                                ..Default::default()
+                            })),
+                        }
+                        .into(),
+                    )
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        let mut iter: ArrayVec<_, 3> = Default::default();
+
+        if !decls.is_empty() {
+            iter.push(
+                VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Let,
+                    decls,
+                    declare: false,
+                    ..Default::default()
+                }
+                .into(),
+            )
+        }
+        iter.extend(unpack_rest);
+        if !decls_after_unpack.is_empty() {
+            iter.push(
+                VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Let,
+                    decls: decls_after_unpack,
+                    declare: false,
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+        if (is_setter || self.ignore_function_length) && !loose_stmt.is_empty() {
+            loose_stmt.extend(iter);
+            prepend_stmts(&mut body.stmts, loose_stmt.into_iter());
+        } else {
+            prepend_stmts(&mut body.stmts, iter.into_iter());
+        };
+
+        *ps = params;
+    }
+
+    fn convert_arrow(&mut self, e: &mut Expr) {
+        let Expr::Arrow(f) = e else {
+            return;
+        };
+
+        let was_expr = f.body.is_expr();
+
+        let need_arrow_to_function = need_arrow_to_function(f, self.ignore_function_length);
+
+        let mut local_vars = None;
+
+        // this needs to happen before rest parameter transform
+        if need_arrow_to_function {
+            if !self.in_prop {
+                // `this` of the nested arrow functions which are converted as well is
+                // captured when they are visited, by the hoister of their scope.
+                let mut nested = NestedArrows::new(self.ignore_function_length);
+                f.visit_mut_children_with(&mut nested);
+                f.visit_mut_children_with(&mut self.hoister);
+                nested.restore = true;
+                f.visit_mut_children_with(&mut nested);
+            } else {
+                let mut hoister = FnEnvHoister::new(self.unresolved_ctxt);
+                f.visit_mut_children_with(&mut hoister);
+                local_vars = hoister.to_stmt();
+            }
+        }
+
+        let mut params = f
+            .params
+            .take()
+            .into_iter()
+            .map(|pat| Param {
+                span: DUMMY_SP,
+                decorators: Default::default(),
+                pat,
+            })
+            .collect();
+
+        let mut body = match *f.body.take() {
+            BlockStmtOrExpr::BlockStmt(block) => block,
+            BlockStmtOrExpr::Expr(expr) => BlockStmt {
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(expr),
+                })],
+                ..Default::default()
+            },
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        };
+
+        self.convert_fn_like(&mut params, &mut body, false);
+
+        if need_arrow_to_function {
+            let func: Expr = Function {
+                params,
+                decorators: Default::default(),
+                span: f.span,
+                body: Some(body),
+                is_generator: f.is_generator,
+                is_async: f.is_async,
+                ..Default::default()
+            }
+            .into();
+            *e = match (self.in_prop, local_vars) {
+                (true, Some(var_decl)) => ArrowExpr {
+                    span: f.span,
+                    params: Vec::new(),
+                    is_async: false,
+                    is_generator: false,
+                    body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                        span: f.span,
+                        stmts: vec![
+                            var_decl,
+                            Stmt::Return(ReturnStmt {
+                                span: f.span,
+                                arg: Some(Box::new(func)),
+                            }),
+                        ],
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }
+                .as_iife()
+                .into(),
+                _ => func,
+            };
+
+            if !self.in_prop {
+                let expr = &*e as *const Expr;
+
+                if let Expr::Fn(FnExpr { function, .. }) = e {
+                    self.converted_arrows.push(Some(ConvertedArrow {
+                        expr,
+                        function: &**function,
+                        hoister: self.hoister_stack.len(),
+                    }));
+                }
+            }
+            return;
+        }
+
+        let body = if was_expr
+            && body.stmts.len() == 1
+            && matches!(
+                body.stmts[0],
+                Stmt::Return(ReturnStmt { arg: Some(..), .. })
+            ) {
+            match body.stmts.pop().unwrap() {
+                Stmt::Return(ReturnStmt { arg: Some(arg), .. }) => {
+                    Box::new(BlockStmtOrExpr::Expr(arg))
+                }
+                _ => unreachable!(),
+            }
+        } else {
+            Box::new(BlockStmtOrExpr::BlockStmt(body))
+        };
+
+        f.params = params.into_iter().map(|param| param.pat).collect();
+        f.body = body;
+    }
+}
+
+impl VisitMutHook<TraverseCtx> for ParametersPass {
+    // generally speaking, there won't be class field in here, but Safari 14.1
+    // still has bugs in parameters
+    fn enter_class_prop(&mut self, prop: &mut ClassProp, _ctx: &mut TraverseCtx) {
+        self.push_ctx(self.in_subclass, !prop.is_static);
+    }
+
+    fn exit_class_prop(&mut self, _prop: &mut ClassProp, _ctx: &mut TraverseCtx) {
+        self.pop_ctx();
+    }
+
+    // same for private prop
+    fn enter_private_prop(&mut self, prop: &mut PrivateProp, _ctx: &mut TraverseCtx) {
+        self.push_ctx(self.in_subclass, !prop.is_static);
+    }
+
+    fn exit_private_prop(&mut self, _prop: &mut PrivateProp, _ctx: &mut TraverseCtx) {
+        self.pop_ctx();
+    }
+
+    fn enter_class(&mut self, c: &mut Class, _ctx: &mut TraverseCtx) {
+        self.push_ctx(c.super_class.is_some(), false);
+        self.converted_arrows.push(None);
+    }
+
+    fn exit_class(&mut self, _c: &mut Class, _ctx: &mut TraverseCtx) {
+        self.pop_ctx();
+        self.converted_arrows.pop();
+    }
+
+    fn enter_class_method(&mut self, m: &mut ClassMethod, _ctx: &mut TraverseCtx) {
+        if let MethodKind::Setter = m.kind {
+            let f = &mut m.function;
+
+            if let Some(body) = &mut f.body {
+                self.convert_fn_like(&mut f.params, body, true);
+            }
+        }
+    }
+
+    fn enter_function(&mut self, f: &mut Function, _ctx: &mut TraverseCtx) {
+        self.push_ctx(false, false);
+
+        if !self.is_converted_arrow(f) {
+            self.converted_arrows.push(None);
+        }
+
+        if let Some(body) = &mut f.body {
+            self.convert_fn_like(&mut f.params, body, false);
+        }
+    }
+
+    fn exit_function(&mut self, f: &mut Function, _ctx: &mut TraverseCtx) {
+        self.pop_ctx();
+
+        if !self.is_converted_arrow(f) {
+            self.converted_arrows.pop();
+        }
+    }
+
+    fn enter_getter_prop(&mut self, _f: &mut GetterProp, _ctx: &mut TraverseCtx) {
+        self.converted_arrows.push(None);
+    }
+
+    fn exit_getter_prop(&mut self, _f: &mut GetterProp, _ctx: &mut TraverseCtx) {
+        self.converted_arrows.pop();
+    }
+
+    fn enter_setter_prop(&mut self, f: &mut SetterProp, _ctx: &mut TraverseCtx) {
+        self.converted_arrows.push(None);
+
+        let Some(body) = &mut f.body else {
+            return;
+        };
+
+        let mut params = vec![Param {
+            span: DUMMY_SP,
+            decorators: Default::default(),
+            pat: *f.param.take(),
+        }];
+
+        self.convert_fn_like(&mut params, body, true);
+
+        debug_assert!(params.len() == 1);
+
+        *f.param = params.pop().unwrap().pat;
+    }
+
+    fn exit_setter_prop(&mut self, _f: &mut SetterProp, _ctx: &mut TraverseCtx) {
+        self.converted_arrows.pop();
+    }
+
+    fn enter_catch_clause(&mut self, f: &mut CatchClause, _ctx: &mut TraverseCtx) {
+        let mut params = Vec::new();
+        if f.param.is_some() {
+            params.push(Param {
+                span: DUMMY_SP,
+                decorators: Vec::new(),
+                pat: f.param.take().unwrap(),
+            });
+        }
+
+        self.convert_fn_like(&mut params, &mut f.body, false);
+
+        assert!(
+            params.is_empty() || params.len() == 1,
+            "convert_fn_like should return 0 ~ 1 parameter while handling catch clause"
+        );
+
+        f.param = params.pop().map(|param| param.pat);
+    }
+
+    fn enter_constructor(&mut self, f: &mut Constructor, _ctx: &mut TraverseCtx) {
+        if let Some(body) = &f.body {
+            self.ctor_bodies.push(&body.stmts);
+            self.enter_hoister_scope();
+        }
+    }
+
+    fn exit_constructor(&mut self, f: &mut Constructor, _ctx: &mut TraverseCtx) {
+        let Some(BlockStmt { stmts, .. }) = &mut f.body else {
+            return;
+        };
+
+        self.ctor_bodies.pop();
+        let hoister = self.exit_hoister_scope();
+
+        if self.in_subclass {
+            let (decl, this_id) = hoister.to_stmt_in_subclass();
+
+            if let Some(stmt) = decl {
+                if let Some(this_id) = this_id {
+                    init_this(stmts, &this_id)
+                }
+                prepend_stmt(stmts, stmt);
+            }
+        } else if let Some(stmt) = hoister.to_stmt() {
+            prepend_stmt(stmts, stmt);
+        }
+    }
+
+    fn enter_expr(&mut self, e: &mut Expr, _ctx: &mut TraverseCtx) {
+        if e.is_arrow() {
+            self.convert_arrow(e);
+        }
+    }
+
+    fn exit_expr(&mut self, e: &mut Expr, _ctx: &mut TraverseCtx) {
+        if let Some(Some(arrow)) = self.converted_arrows.last() {
+            if ptr::eq(arrow.expr, e) {
+                self.converted_arrows.pop();
+            }
+        }
+    }
+
+    fn enter_block_stmt_or_expr(&mut self, _body: &mut BlockStmtOrExpr, _ctx: &mut TraverseCtx) {
+        self.enter_hoister_scope();
+    }
+
+    fn exit_block_stmt_or_expr(&mut self, body: &mut BlockStmtOrExpr, _ctx: &mut TraverseCtx) {
+        let Some(stmt) = self.exit_hoister_scope_to_stmt() else {
+            return;
+        };
+
+        if let BlockStmtOrExpr::Expr(e) = body {
+            *body = BlockStmtOrExpr::BlockStmt(BlockStmt {
+                stmts: vec![
+                    stmt,
+                    ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(e.take()),
+                    }
+                    .into(),
+                ],
+                ..Default::default()
+            });
+        }
+    }
+
+    fn enter_stmts(&mut self, stmts: &mut Vec<Stmt>, _ctx: &mut TraverseCtx) {
+        if self.ctor_bodies.last() != Some(&(stmts as *const _)) {
+            self.enter_hoister_scope();
+        }
+    }
+
+    fn exit_stmts(&mut self, stmts: &mut Vec<Stmt>, _ctx: &mut TraverseCtx) {
+        if self.ctor_bodies.last() == Some(&(stmts as *const _)) {
+            return;
+        }
+
+        if let Some(stmt) = self.exit_hoister_scope_to_stmt() {
+            prepend_stmt(stmts, stmt);
+        }
+    }
+
+    fn exit_module_items(&mut self, items: &mut Vec<ModuleItem>, _ctx: &mut TraverseCtx) {
+        if let Some(stmt) = self.hoister.take().to_stmt() {
+            prepend_stmt(items, stmt.into());
+        }
+    }
+}
+
+fn need_arrow_to_function(f: &ArrowExpr, ignore_function_length: bool) -> bool {
+    f.params.iter().any(|p| match p {
+        Pat::Rest(..) => true,
+        Pat::Assign(..) => !ignore_function_length,
+        _ => false,
+    })
+}
+
+/// Takes out the nested arrow functions which are converted to functions as
+/// well, and puts them back if [NestedArrows::restore] is set.
+///
+/// Their `this` is captured when they are visited, like the arrow functions are
+/// converted from the innermost one.
+struct NestedArrows {
+    ignore_function_length: bool,
+    arrows: Vec<Expr>,
+    restore: bool,
+}
+
+impl NestedArrows {
+    fn new(ignore_function_length: bool) -> Self {
+        NestedArrows {
+            ignore_function_length,
+            arrows: Vec::new(),
+            restore: false,
+        }
+    }
+}
+
+impl VisitMut for NestedArrows {
+    noop_visit_mut_type!(fail);
+
+    fn visit_mut_class(&mut self, _: &mut Class) {}
+
+    fn visit_mut_expr(&mut self, e: &mut Expr) {
+        if self.restore {
+            if e.is_invalid() {
+                *e = self
+                    .arrows
+                    .pop()
+                    .expect("nested arrow function was not taken");
+                return;
+            }
+        } else if let Expr::Arrow(f) = e {
+            if need_arrow_to_function(f, self.ignore_function_length) {
+                self.arrows.insert(0, e.take());
+                return;
+            }
+        }
+
+        e.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_function(&mut self, _: &mut Function) {}
+
+    fn visit_mut_getter_prop(&mut self, p: &mut GetterProp) {
+        p.key.visit_mut_with(self);
+    }
+
+    fn visit_mut_setter_prop(&mut self, p: &mut SetterProp) {
+        p.key.visit_mut_with(self);
+    }
+}
+
+fn make_arg_nth(n: usize) -> MemberExpr {
+    Ident::new_no_ctxt(atom!("arguments"), DUMMY_SP).computed_member(n)
+}
+
+fn check_arg_len(n: usize) -> Expr {
+    BinExpr {
+        left: Expr::Ident(Ident::new_no_ctxt(atom!("arguments"), DUMMY_SP))
+            .make_member(IdentName::new(atom!("length"), DUMMY_SP))
+            .into(),
+        op: op!(">"),
+        right: n.into(),
+        span: DUMMY_SP,
+    }
+    .into()
+}
+
+fn check_arg_len_or_undef(n: usize) -> Expr {
+    CondExpr {
+        test: Box::new(check_arg_len(n)),
+        cons: make_arg_nth(n).into(),
+        alt: Expr::undefined(DUMMY_SP),
+        span: DUMMY_SP,
+    }
+    .into()
+}
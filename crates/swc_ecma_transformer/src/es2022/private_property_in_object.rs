@@ -560,7 +560,7 @@ struct ClassAnalyzer<'a> {
 }
 
 impl Visit for ClassAnalyzer<'_> {
-    noop_visit_type!(fail);
+    noop_visit_type!();
 
     fn visit_bin_expr(&mut self, n: &BinExpr) {
         n.visit_children_with(self);
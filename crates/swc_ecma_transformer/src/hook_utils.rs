@@ -62,6 +62,10 @@ where
 
     chained_method!(enter_constructor, exit_constructor, Constructor);
 
+    chained_method!(enter_private_method, exit_private_method, PrivateMethod);
+
+    chained_method!(enter_static_block, exit_static_block, StaticBlock);
+
     chained_method!(enter_getter_prop, exit_getter_prop, GetterProp);
 
     chained_method!(enter_setter_prop, exit_setter_prop, SetterProp);
@@ -74,15 +78,29 @@ where
 
     chained_method!(enter_for_of_stmt, exit_for_of_stmt, ForOfStmt);
 
+    chained_method!(enter_for_stmt, exit_for_stmt, ForStmt);
+
+    chained_method!(enter_for_head, exit_for_head, ForHead);
+
+    chained_method!(enter_while_stmt, exit_while_stmt, WhileStmt);
+
+    chained_method!(enter_do_while_stmt, exit_do_while_stmt, DoWhileStmt);
+
     chained_method!(enter_class_decl, exit_class_decl, ClassDecl);
 
     chained_method!(enter_class_expr, exit_class_expr, ClassExpr);
 
     chained_method!(enter_assign_pat, exit_assign_pat, AssignPat);
+    chained_method!(enter_assign_pat_prop, exit_assign_pat_prop, AssignPatProp);
 
     chained_method!(enter_private_prop, exit_private_prop, PrivateProp);
 
+    chained_method!(enter_class_prop, exit_class_prop, ClassProp);
+
+    chained_method!(enter_class_method, exit_class_method, ClassMethod);
+
     chained_method!(enter_prop, exit_prop, Prop);
+    chained_method!(enter_method_prop, exit_method_prop, MethodProp);
 
     chained_method!(enter_assign_expr, exit_assign_expr, AssignExpr);
 
@@ -93,6 +111,7 @@ where
     chained_method!(enter_bin_expr, exit_bin_expr, BinExpr);
 
     chained_method!(enter_fn_decl, exit_fn_decl, FnDecl);
+    chained_method!(enter_fn_expr, exit_fn_expr, FnExpr);
 
     // ES3 hooks: member expression literals
     chained_method!(enter_member_expr, exit_member_expr, MemberExpr);
@@ -126,6 +145,59 @@ where
         exit_module_export_name,
         ModuleExportName
     );
+
+    // TypeScript hooks: type annotations
+    chained_method!(enter_opt_ts_type, exit_opt_ts_type, Option<Box<TsType>>);
+
+    chained_method!(
+        enter_opt_ts_type_ann,
+        exit_opt_ts_type_ann,
+        Option<Box<TsTypeAnn>>
+    );
+
+    chained_method!(
+        enter_opt_ts_type_param_decl,
+        exit_opt_ts_type_param_decl,
+        Option<Box<TsTypeParamDecl>>
+    );
+
+    chained_method!(
+        enter_opt_ts_type_param_instantiation,
+        exit_opt_ts_type_param_instantiation,
+        Option<Box<TsTypeParamInstantiation>>
+    );
+
+    // TypeScript hooks: namespace exports
+    chained_method!(enter_param, exit_param, Param);
+
+    chained_method!(
+        enter_simple_assign_target,
+        exit_simple_assign_target,
+        SimpleAssignTarget
+    );
+
+    chained_method!(enter_object_pat_prop, exit_object_pat_prop, ObjectPatProp);
+
+    chained_method!(
+        enter_jsx_element_name,
+        exit_jsx_element_name,
+        JSXElementName
+    );
+
+    chained_method!(enter_jsx_object, exit_jsx_object, JSXObject);
+
+    // TypeScript hooks: type-only specifiers
+    chained_method!(
+        enter_import_specifiers,
+        exit_import_specifiers,
+        Vec<ImportSpecifier>
+    );
+
+    chained_method!(
+        enter_export_specifiers,
+        exit_export_specifiers,
+        Vec<ExportSpecifier>
+    );
 }
 
 pub(crate) struct NoopHook;
@@ -0,0 +1,104 @@
+//! Development mode: `@babel/plugin-transform-react-jsx-source` and
+//! `@babel/plugin-transform-react-jsx-self`
+
+use swc_common::{DUMMY_SP, comments::Comments};
+use swc_ecma_ast::*;
+use swc_ecma_utils::quote_ident;
+
+use super::JsxTransform;
+
+/// See <https://github.com/babel/babel/blob/1bdb1a4175ed1fc40751fb84dc4ad1900260f28f/packages/babel-plugin-transform-react-jsx-self/src/index.ts#L27>
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct DevContext {
+    in_constructor: bool,
+    in_derived_class: bool,
+}
+
+impl<C> JsxTransform<C>
+where
+    C: Comments,
+{
+    fn current_dev_ctx(&self) -> DevContext {
+        *self
+            .dev_ctx_stack
+            .last()
+            .expect("context stack should never be empty")
+    }
+
+    pub(super) fn enter_dev_class(&mut self, class: &Class) {
+        if !self.development_attrs {
+            return;
+        }
+
+        let mut ctx = self.current_dev_ctx();
+        ctx.in_derived_class = class.super_class.is_some();
+        self.dev_ctx_stack.push(ctx);
+    }
+
+    /// Enters a function-like scope with its own `this`.
+    pub(super) fn enter_dev_function(&mut self, is_constructor: bool) {
+        if !self.development_attrs {
+            return;
+        }
+
+        let mut ctx = self.current_dev_ctx();
+        ctx.in_constructor = is_constructor;
+        self.dev_ctx_stack.push(ctx);
+    }
+
+    pub(super) fn exit_dev_scope(&mut self) {
+        if self.development_attrs && self.dev_ctx_stack.len() > 1 {
+            self.dev_ctx_stack.pop();
+        }
+    }
+
+    /// Adds `__source` and `__self` to `el`.
+    pub(super) fn add_development_attrs(&self, el: &mut JSXOpeningElement) {
+        if el.span != DUMMY_SP {
+            let loc = self.cm.lookup_char_pos(el.span.lo);
+            let file_name = loc.file.name.to_string();
+
+            let source = ObjectLit {
+                span: DUMMY_SP,
+                props: vec![
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("fileName")),
+                        value: Box::new(Expr::Lit(Lit::Str(Str {
+                            span: DUMMY_SP,
+                            raw: None,
+                            value: file_name.into(),
+                        }))),
+                    }))),
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("lineNumber")),
+                        value: loc.line.into(),
+                    }))),
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("columnNumber")),
+                        value: (loc.col.0 + 1).into(),
+                    }))),
+                ],
+            };
+
+            el.attrs.push(dev_attr("__source", source.into()));
+        }
+
+        // https://github.com/babel/babel/blob/1bdb1a4175ed1fc40751fb84dc4ad1900260f28f/packages/babel-plugin-transform-react-jsx-self/src/index.ts#L50
+        let ctx = self.current_dev_ctx();
+        if !(ctx.in_constructor && ctx.in_derived_class) {
+            el.attrs
+                .push(dev_attr("__self", ThisExpr { span: DUMMY_SP }.into()));
+        }
+    }
+}
+
+fn dev_attr(name: &str, value: Expr) -> JSXAttrOrSpread {
+    JSXAttrOrSpread::JSXAttr(JSXAttr {
+        span: DUMMY_SP,
+        name: JSXAttrName::Ident(quote_ident!(name)),
+        value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            span: DUMMY_SP,
+            expr: JSXExpr::Expr(Box::new(value)),
+        })),
+    })
+}
@@ -1,17 +1,1524 @@
+#![allow(clippy::redundant_allocation)]
+
+//! JSX: `@babel/plugin-transform-react-jsx`
+//!
+//! This plugin converts JSX elements and fragments to function calls, using
+//! either the classic or the automatic runtime.
+//!
+//! - The classic runtime calls `pragma` (`React.createElement` by default) and
+//!   `pragmaFrag` (`React.Fragment` by default).
+//! - The automatic runtime imports `jsx`, `jsxs` and `Fragment` from
+//!   `<importSource>/jsx-runtime`.
+//! - In development mode, `__source` and `__self` are added to every element
+//!   (`@babel/plugin-transform-react-jsx-source` and
+//!   `@babel/plugin-transform-react-jsx-self`), and the automatic runtime uses
+//!   `jsxDEV` from `<importSource>/jsx-dev-runtime`.
+//!
+//! `@jsx`, `@jsxFrag`, `@jsxRuntime` and `@jsxImportSource` comment directives
+//! override the options for a file.
+//!
+//! ## Example
+//!
+//! Input:
+//! ```jsx
+//! const profile = (
+//!     <div>
+//!         <img src="avatar.png" />
+//!         <h3>{[user.firstName, user.lastName].join(" ")}</h3>
+//!     </div>
+//! );
+//! ```
+//!
+//! Output (classic runtime):
+//! ```js
+//! const profile = /*#__PURE__*/ React.createElement("div", null,
+//!     /*#__PURE__*/ React.createElement("img", { src: "avatar.png" }),
+//!     /*#__PURE__*/ React.createElement("h3", null, [user.firstName, user.lastName].join(" ")));
+//! ```
+//!
+//! ## Implementation
+//!
+//! The conversion is done by [JsxTransform], which `swc_ecma_transforms_react`
+//! also uses.
+//!
+//! Elements are converted when they are entered, so the generated calls and
+//! props objects are visited by the ES transforms in the same traversal.
+
+use std::fmt;
+#[cfg(feature = "concurrent")]
+use std::sync::RwLock;
+
+use bytes_str::BytesStr;
+#[cfg(feature = "concurrent")]
+use once_cell::sync::Lazy;
+#[cfg(feature = "concurrent")]
+use rustc_hash::FxHashMap;
+use swc_atoms::{Atom, atom};
+use swc_common::{
+    DUMMY_SP, FileName, SourceMap, Span, Spanned, SyntaxContext,
+    comments::{Comment, CommentKind, Comments},
+    errors::HANDLER,
+    sync::Lrc,
+    util::take::Take,
+};
+use swc_ecma_ast::*;
 use swc_ecma_hooks::VisitMutHook;
+use swc_ecma_parser::{Syntax, parse_file_as_expr};
+use swc_ecma_utils::{ExprFactory, StmtLike, drop_span, prepend_stmt, private_ident, quote_ident};
 
+use self::development::DevContext;
+pub use self::{
+    static_check::should_use_create_element,
+    text::{
+        jsx_attr_value_to_expr, jsx_text_to_str, jsx_text_to_str_with_raw, transform_jsx_attr_str,
+    },
+};
 use crate::TraverseCtx;
 
-#[derive(Debug, Default)]
+mod development;
+mod static_check;
+mod text;
+
+/// https://babeljs.io/docs/en/babel-plugin-transform-react-jsx#runtime
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsxRuntime {
+    /// `classic`
+    #[default]
+    Classic,
+    /// `automatic`
+    Automatic,
+}
+
 #[non_exhaustive]
-pub struct JsxOptions {}
+pub struct JsxOptions<C = Box<dyn Comments>> {
+    /// Syntax context of top-level bindings, as applied by the resolver.
+    ///
+    /// This is used to reference `React` defined by the user.
+    pub top_level_ctxt: SyntaxContext,
+
+    pub runtime: Option<JsxRuntime>,
+
+    /// For automatic runtime
+    pub import_source: Option<Atom>,
+
+    pub pragma: Option<BytesStr>,
+    pub pragma_frag: Option<BytesStr>,
+
+    pub throw_if_namespace: Option<bool>,
+
+    pub development: Option<bool>,
+
+    /// Used to parse `pragma` and `pragmaFrag`, and to resolve the location of
+    /// elements for `__source` in development mode.
+    pub cm: Lrc<SourceMap>,
+
+    /// Used to read comment directives and to add `/*#__PURE__*/`
+    /// annotations.
+    pub comments: Option<C>,
+}
+
+impl<C> Default for JsxOptions<C> {
+    fn default() -> Self {
+        Self {
+            top_level_ctxt: Default::default(),
+            runtime: Default::default(),
+            import_source: Default::default(),
+            pragma: Default::default(),
+            pragma_frag: Default::default(),
+            throw_if_namespace: Default::default(),
+            development: Default::default(),
+            cm: Default::default(),
+            comments: None,
+        }
+    }
+}
+
+impl<C> fmt::Debug for JsxOptions<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsxOptions")
+            .field("top_level_ctxt", &self.top_level_ctxt)
+            .field("runtime", &self.runtime)
+            .field("import_source", &self.import_source)
+            .field("pragma", &self.pragma)
+            .field("pragma_frag", &self.pragma_frag)
+            .field("throw_if_namespace", &self.throw_if_namespace)
+            .field("development", &self.development)
+            .field("comments", &self.comments.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+pub fn hook(options: JsxOptions, unresolved_ctxt: SyntaxContext) -> impl VisitMutHook<TraverseCtx> {
+    let mut transform = JsxTransform::new(options, unresolved_ctxt);
+    transform.development_attrs = transform.development;
+    transform
+}
+
+/// Converts JSX elements and fragments to function calls.
+///
+/// This is used by the `jsx` hook, and by `swc_ecma_transforms_react`, which
+/// drives it from its own hook.
+pub struct JsxTransform<C> {
+    cm: Lrc<SourceMap>,
+    comments: Option<C>,
+
+    top_level_ctxt: SyntaxContext,
+    unresolved_ctxt: SyntaxContext,
+
+    runtime: JsxRuntime,
+    /// For automatic runtime.
+    import_source: Atom,
+    /// For automatic runtime.
+    import_jsx: Option<Ident>,
+    /// For automatic runtime.
+    import_jsxs: Option<Ident>,
+    /// For automatic runtime.
+    import_create_element: Option<Ident>,
+    /// For automatic runtime.
+    import_fragment: Option<Ident>,
+
+    pragma: Lrc<Box<Expr>>,
+    pragma_frag: Lrc<Box<Expr>>,
+    development: bool,
+    throw_if_namespace: bool,
+
+    /// Whether `__source` and `__self` are added in development mode, instead
+    /// of being added by other passes.
+    development_attrs: bool,
+    /// For `__self` in development mode.
+    dev_ctx_stack: Vec<DevContext>,
+}
+
+impl<C> JsxTransform<C>
+where
+    C: Comments,
+{
+    /// Creates a transform which expects `__source` and `__self` to be added
+    /// by other passes in development mode.
+    pub fn new(options: JsxOptions<C>, unresolved_ctxt: SyntaxContext) -> Self {
+        let JsxOptions {
+            top_level_ctxt,
+            runtime,
+            import_source,
+            pragma,
+            pragma_frag,
+            throw_if_namespace,
+            development,
+            cm,
+            comments,
+        } = options;
+
+        let pragma = parse_expr_for_jsx(
+            &cm,
+            "pragma",
+            pragma.unwrap_or_else(|| "React.createElement".into()),
+            top_level_ctxt,
+        );
+        let pragma_frag = parse_expr_for_jsx(
+            &cm,
+            "pragmaFrag",
+            pragma_frag.unwrap_or_else(|| "React.Fragment".into()),
+            top_level_ctxt,
+        );
+
+        JsxTransform {
+            cm,
+            comments,
+            top_level_ctxt,
+            unresolved_ctxt,
+            runtime: runtime.unwrap_or_default(),
+            import_source: import_source.unwrap_or_else(|| atom!("react")),
+            import_jsx: None,
+            import_jsxs: None,
+            import_create_element: None,
+            import_fragment: None,
+            pragma: Lrc::new(pragma),
+            pragma_frag: Lrc::new(pragma_frag),
+            development: development.unwrap_or_default(),
+            throw_if_namespace: throw_if_namespace.unwrap_or(true),
+            development_attrs: false,
+            dev_ctx_stack: vec![DevContext::default()],
+        }
+    }
+
+    /// Converts `expr` if it's a JSX element or fragment, optionally wrapped in
+    /// parentheses.
+    pub fn transform_expr(&mut self, expr: &mut Expr) {
+        let jsx = match expr {
+            Expr::Paren(ParenExpr { expr: inner, .. })
+                if matches!(&**inner, Expr::JSXElement(..) | Expr::JSXFragment(..)) =>
+            {
+                &mut **inner
+            }
+            _ => expr,
+        };
+
+        let converted = match jsx {
+            // <div></div> => React.createElement('div', null);
+            Expr::JSXElement(el) => self.jsx_elem_to_expr(*el.take()),
+            // <></> => React.createElement(React.Fragment, null);
+            Expr::JSXFragment(frag) => self.jsx_frag_to_expr(frag.take()),
+            _ => return,
+        };
+
+        *expr = converted;
+    }
+
+    /// Reads the comment directives of `module`, which override the options.
+    pub fn parse_module_directives(&mut self, module: &Module) {
+        self.parse_directives(module.span);
+
+        for item in &module.body {
+            let span = item.span();
+            if self.parse_directives(span) {
+                break;
+            }
+        }
+    }
+
+    /// Reads the comment directives of `script`, which override the options.
+    pub fn parse_script_directives(&mut self, script: &Script) {
+        self.parse_directives(script.span);
+
+        for item in &script.body {
+            let span = item.span();
+            if self.parse_directives(span) {
+                break;
+            }
+        }
+    }
+
+    /// Imports the functions used by the automatic runtime.
+    pub fn inject_module_imports(&mut self, module: &mut Module) {
+        if self.runtime == JsxRuntime::Automatic {
+            self.inject_runtime(&mut module.body, |imports, src, stmts| {
+                let specifiers = imports
+                    .into_iter()
+                    .map(|(local, imported)| {
+                        ImportSpecifier::Named(ImportNamedSpecifier {
+                            span: DUMMY_SP,
+                            local,
+                            imported: Some(ModuleExportName::Ident(imported.into())),
+                            is_type_only: false,
+                        })
+                    })
+                    .collect();
+
+                prepend_stmt(
+                    stmts,
+                    ImportDecl {
+                        span: DUMMY_SP,
+                        specifiers,
+                        src: Str {
+                            span: DUMMY_SP,
+                            raw: None,
+                            value: src.into(),
+                        }
+                        .into(),
+                        type_only: Default::default(),
+                        with: Default::default(),
+                        phase: Default::default(),
+                    }
+                    .into(),
+                )
+            });
+        }
+    }
+
+    /// Requires the functions used by the automatic runtime.
+    pub fn inject_script_requires(&mut self, script: &mut Script) {
+        if self.runtime == JsxRuntime::Automatic {
+            let unresolved_ctxt = self.unresolved_ctxt;
+            self.inject_runtime(&mut script.body, |imports, src, stmts| {
+                prepend_stmt(stmts, add_require(imports, src, unresolved_ctxt))
+            });
+        }
+    }
+}
+
+/// Parse `src` to use as a `pragma` or `pragmaFrag` in jsx.
+pub fn parse_expr_for_jsx(
+    cm: &SourceMap,
+    name: &str,
+    src: BytesStr,
+    top_level_ctxt: SyntaxContext,
+) -> Box<Expr> {
+    let fm = cm.new_source_file(cache_filename(name), src);
+
+    parse_file_as_expr(
+        &fm,
+        Syntax::default(),
+        Default::default(),
+        None,
+        &mut Vec::new(),
+    )
+    .map_err(|e| {
+        if HANDLER.is_set() {
+            HANDLER.with(|h| {
+                e.into_diagnostic(h)
+                    .note("Failed to parse jsx pragma")
+                    .emit()
+            })
+        }
+    })
+    .map(drop_span)
+    .map(|mut expr| {
+        apply_ctxt(&mut expr, top_level_ctxt);
+        expr
+    })
+    .unwrap_or_else(|()| {
+        panic!(
+            "failed to parse jsx option {}: '{}' is not an expression",
+            name, fm.src,
+        )
+    })
+}
+
+fn apply_ctxt(e: &mut Expr, ctxt: SyntaxContext) {
+    match e {
+        Expr::Ident(i) => {
+            i.ctxt = ctxt;
+        }
+        Expr::Member(MemberExpr { obj, .. }) => {
+            apply_ctxt(obj, ctxt);
+        }
+        _ => {}
+    }
+}
+
+/// Options overridden by the comment directives of a file.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct JsxDirectives {
+    pub runtime: Option<JsxRuntime>,
+
+    /// For automatic runtime.
+    pub import_source: Option<Atom>,
+
+    /// Parsed from `@jsx`
+    pub pragma: Option<Lrc<Box<Expr>>>,
+
+    /// Parsed from `@jsxFrag`
+    pub pragma_frag: Option<Lrc<Box<Expr>>>,
+}
+
+impl JsxDirectives {
+    pub fn from_comments(
+        cm: &SourceMap,
+        comments: &[Comment],
+        top_level_ctxt: SyntaxContext,
+    ) -> Self {
+        let mut res = JsxDirectives::default();
+
+        for cmt in comments {
+            if cmt.kind != CommentKind::Block {
+                continue;
+            }
+
+            for line in cmt.text.lines() {
+                let mut line = line.trim();
+                if line.starts_with('*') {
+                    line = line[1..].trim();
+                }
+
+                if !line.starts_with("@jsx") {
+                    continue;
+                }
+
+                let mut words = line.split_whitespace();
+                loop {
+                    let pragma = words.next();
+                    if pragma.is_none() {
+                        break;
+                    }
+                    let val = words.next();
+
+                    match pragma {
+                        Some("@jsxRuntime") => match val {
+                            Some("classic") => res.runtime = Some(JsxRuntime::Classic),
+                            Some("automatic") => res.runtime = Some(JsxRuntime::Automatic),
+                            None => {}
+                            _ => {
+                                if HANDLER.is_set() {
+                                    HANDLER.with(|handler| {
+                                        handler
+                                            .struct_span_err(
+                                                cmt.span,
+                                                "Runtime must be either `classic` or `automatic`.",
+                                            )
+                                            .emit()
+                                    });
+                                }
+                            }
+                        },
+                        Some("@jsxImportSource") => {
+                            if let Some(src) = val {
+                                res.runtime = Some(JsxRuntime::Automatic);
+                                res.import_source = Some(Atom::new(src));
+                            }
+                        }
+                        Some("@jsxFrag") => {
+                            if let Some(src) = val {
+                                if is_valid_for_pragma(src) {
+                                    let mut e = parse_expr_for_jsx(
+                                        cm,
+                                        "module-jsx-pragma-frag",
+                                        cache_source(src),
+                                        top_level_ctxt,
+                                    );
+                                    e.set_span(cmt.span);
+                                    res.pragma_frag = Some(e.into())
+                                }
+                            }
+                        }
+                        Some("@jsx") => {
+                            if let Some(src) = val {
+                                if is_valid_for_pragma(src) {
+                                    let mut e = parse_expr_for_jsx(
+                                        cm,
+                                        "module-jsx-pragma",
+                                        cache_source(src),
+                                        top_level_ctxt,
+                                    );
+                                    e.set_span(cmt.span);
+                                    res.pragma = Some(e.into());
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        res
+    }
+}
+
+#[cfg(feature = "concurrent")]
+fn cache_filename(name: &str) -> Lrc<FileName> {
+    static FILENAME_CACHE: Lazy<RwLock<FxHashMap<String, Lrc<FileName>>>> =
+        Lazy::new(|| RwLock::new(FxHashMap::default()));
+
+    {
+        let cache = FILENAME_CACHE
+            .read()
+            .expect("Failed to read FILENAME_CACHE");
+        if let Some(f) = cache.get(name) {
+            return f.clone();
+        }
+    }
+
+    let file = Lrc::new(FileName::Internal(format!("jsx-config-{name}.js")));
+
+    {
+        let mut cache = FILENAME_CACHE
+            .write()
+            .expect("Failed to write FILENAME_CACHE");
+        cache.insert(name.to_string(), file.clone());
+    }
+
+    file
+}
+
+#[cfg(not(feature = "concurrent"))]
+fn cache_filename(name: &str) -> Lrc<FileName> {
+    Lrc::new(FileName::Internal(format!("jsx-config-{name}.js")))
+}
+
+#[cfg(feature = "concurrent")]
+fn cache_source(src: &str) -> BytesStr {
+    use rustc_hash::FxHashSet;
+
+    static CACHE: Lazy<RwLock<FxHashSet<BytesStr>>> =
+        Lazy::new(|| RwLock::new(FxHashSet::default()));
+
+    {
+        let cache = CACHE.write().unwrap();
+
+        if let Some(cached) = cache.get(src) {
+            return cached.clone();
+        }
+    }
+
+    let cached: BytesStr = src.to_string().into();
+    {
+        let mut cache = CACHE.write().unwrap();
+        cache.insert(cached.clone());
+    }
+    cached
+}
+
+#[cfg(not(feature = "concurrent"))]
+fn cache_source(src: &str) -> BytesStr {
+    // We cannot cache because Rc does not implement Send.
+    src.to_string().into()
+}
+
+fn is_valid_for_pragma(s: &str) -> bool {
+    if s.is_empty() {
+        return false;
+    }
+
+    if !s.starts_with(|c: char| Ident::is_valid_start(c)) {
+        return false;
+    }
+
+    for c in s.chars() {
+        if !Ident::is_valid_continue(c) && c != '.' {
+            return false;
+        }
+    }
+
+    true
+}
+
+impl<C> JsxTransform<C>
+where
+    C: Comments,
+{
+    /// Process JSX attribute value, handling JSXElements and JSXFragments
+    fn process_attr_value(&mut self, value: Option<JSXAttrValue>) -> Box<Expr> {
+        match value {
+            Some(JSXAttrValue::JSXElement(el)) => Box::new(self.jsx_elem_to_expr(*el)),
+            Some(JSXAttrValue::JSXFragment(frag)) => Box::new(self.jsx_frag_to_expr(frag)),
+            Some(JSXAttrValue::JSXExprContainer(container)) => match container.expr {
+                JSXExpr::Expr(e) => e,
+                JSXExpr::JSXEmptyExpr(_) => panic!("empty expression container"),
+                #[cfg(swc_ast_unknown)]
+                _ => panic!("unable to access unknown nodes"),
+            },
+            Some(v) => jsx_attr_value_to_expr(v).expect("empty expression container?"),
+            None => true.into(),
+        }
+    }
+
+    fn inject_runtime<T, F>(&mut self, body: &mut Vec<T>, inject: F)
+    where
+        T: StmtLike,
+        // Fn(Vec<(local, imported)>, src, body)
+        F: Fn(Vec<(Ident, IdentName)>, &str, &mut Vec<T>),
+    {
+        if self.runtime == JsxRuntime::Automatic {
+            if let Some(local) = self.import_create_element.take() {
+                inject(
+                    vec![(local, quote_ident!("createElement"))],
+                    &self.import_source,
+                    body,
+                );
+            }
+
+            let imports = self.import_jsx.take();
+            let imports = if self.development {
+                imports
+                    .map(|local| (local, quote_ident!("jsxDEV")))
+                    .into_iter()
+                    .chain(
+                        self.import_fragment
+                            .take()
+                            .map(|local| (local, quote_ident!("Fragment"))),
+                    )
+                    .collect::<Vec<_>>()
+            } else {
+                imports
+                    .map(|local| (local, quote_ident!("jsx")))
+                    .into_iter()
+                    .chain(
+                        self.import_jsxs
+                            .take()
+                            .map(|local| (local, quote_ident!("jsxs"))),
+                    )
+                    .chain(
+                        self.import_fragment
+                            .take()
+                            .map(|local| (local, quote_ident!("Fragment"))),
+                    )
+                    .collect::<Vec<_>>()
+            };
+
+            if !imports.is_empty() {
+                let jsx_runtime = if self.development {
+                    "jsx-dev-runtime"
+                } else {
+                    "jsx-runtime"
+                };
+
+                let value = format!("{}/{}", self.import_source, jsx_runtime);
+                inject(imports, &value, body)
+            }
+        }
+    }
+
+    fn jsx_frag_to_expr(&mut self, el: JSXFragment) -> Expr {
+        let mut span = el.span();
+
+        if let Some(comments) = &self.comments {
+            if span.lo.is_dummy() {
+                span.lo = Span::dummy_with_cmt().lo;
+            }
+
+            comments.add_pure_comment(span.lo);
+        }
+
+        match self.runtime {
+            JsxRuntime::Automatic => {
+                let fragment = self
+                    .import_fragment
+                    .get_or_insert_with(|| private_ident!("_Fragment"))
+                    .clone();
+
+                let mut props_obj = ObjectLit {
+                    span: DUMMY_SP,
+                    props: Vec::new(),
+                };
+
+                let children = el
+                    .children
+                    .into_iter()
+                    .filter_map(|child| self.jsx_elem_child_to_expr(child))
+                    .map(Some)
+                    .collect::<Vec<_>>();
+
+                let use_jsxs = match children.len() {
+                    0 => false,
+                    1 if matches!(children.first(), Some(Some(child)) if child.spread.is_none()) => {
+                        props_obj
+                            .props
+                            .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                                key: PropName::Ident(quote_ident!("children")),
+                                value: children.into_iter().next().flatten().unwrap().expr,
+                            }))));
 
-pub fn hook(options: JsxOptions) -> impl VisitMutHook<TraverseCtx> {
-    JsxPass { options }
+                        false
+                    }
+                    _ => {
+                        props_obj
+                            .props
+                            .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                                key: PropName::Ident(quote_ident!("children")),
+                                value: ArrayLit {
+                                    span: DUMMY_SP,
+                                    elems: children,
+                                }
+                                .into(),
+                            }))));
+                        true
+                    }
+                };
+
+                let jsx = if use_jsxs && !self.development {
+                    self.import_jsxs
+                        .get_or_insert_with(|| private_ident!("_jsxs"))
+                        .clone()
+                } else {
+                    let jsx = if self.development { "_jsxDEV" } else { "_jsx" };
+                    self.import_jsx
+                        .get_or_insert_with(|| private_ident!(jsx))
+                        .clone()
+                };
+
+                // Build args Vec directly
+                let args = if self.development {
+                    vec![
+                        fragment.as_arg(),
+                        props_obj.as_arg(),
+                        Expr::undefined(DUMMY_SP).as_arg(),
+                        use_jsxs.as_arg(),
+                    ]
+                } else {
+                    vec![fragment.as_arg(), props_obj.as_arg()]
+                };
+
+                CallExpr {
+                    span,
+                    callee: jsx.as_callee(),
+                    args,
+                    ..Default::default()
+                }
+                .into()
+            }
+            JsxRuntime::Classic => {
+                // Build args Vec directly for better performance
+                let children_capacity = el.children.len();
+                let mut args = Vec::with_capacity(2 + children_capacity);
+
+                args.push((*self.pragma_frag).clone().as_arg());
+                args.push(Lit::Null(Null { span: DUMMY_SP }).as_arg());
+
+                // Add children
+                for child in el.children {
+                    if let Some(expr) = self.jsx_elem_child_to_expr(child) {
+                        args.push(expr);
+                    }
+                }
+
+                CallExpr {
+                    span,
+                    callee: (*self.pragma).clone().as_callee(),
+                    args,
+                    ..Default::default()
+                }
+                .into()
+            }
+        }
+    }
+
+    /// # Automatic
+    ///
+    /// <div></div> => jsx('div', null);
+    ///
+    /// # Classic
+    ///
+    /// <div></div> => React.createElement('div', null);
+    fn jsx_elem_to_expr(&mut self, mut el: JSXElement) -> Expr {
+        if self.development_attrs {
+            self.add_development_attrs(&mut el.opening);
+        }
+
+        let mut span = el.span();
+        let use_create_element = should_use_create_element(&el.opening.attrs);
+
+        let name = self.jsx_name(el.opening.name);
+
+        if let Some(comments) = &self.comments {
+            if span.lo.is_dummy() {
+                span.lo = Span::dummy_with_cmt().lo;
+            }
+
+            comments.add_pure_comment(span.lo);
+        }
+
+        match self.runtime {
+            JsxRuntime::Automatic => {
+                // function jsx(tagName: string, props: { children: Node[], ... }, key: string)
+
+                // Pre-allocate with estimated capacity
+                let estimated_props_capacity = el.opening.attrs.len() + 1; // attrs + potential children
+                let mut props_obj = ObjectLit {
+                    span: DUMMY_SP,
+                    props: Vec::with_capacity(estimated_props_capacity),
+                };
+
+                let mut key = None;
+                let mut source_props = None;
+                let mut self_props = None;
+
+                for attr in el.opening.attrs {
+                    match attr {
+                        JSXAttrOrSpread::JSXAttr(attr) => {
+                            //
+                            match attr.name {
+                                JSXAttrName::Ident(i) => {
+                                    //
+                                    if !use_create_element && i.sym == "key" {
+                                        key = attr
+                                            .value
+                                            .and_then(jsx_attr_value_to_expr)
+                                            .map(|expr| expr.as_arg());
+
+                                        if key.is_none() && HANDLER.is_set() {
+                                            HANDLER.with(|handler| {
+                                                handler
+                                                    .struct_span_err(
+                                                        i.span,
+                                                        "The value of property 'key' should not \
+                                                         be empty",
+                                                    )
+                                                    .emit();
+                                            });
+                                        }
+                                        continue;
+                                    }
+
+                                    if !use_create_element
+                                        && *i.sym == *"__source"
+                                        && self.development
+                                    {
+                                        if source_props.is_some() {
+                                            panic!("Duplicate __source is found");
+                                        }
+                                        source_props = attr
+                                            .value
+                                            .and_then(jsx_attr_value_to_expr)
+                                            .map(|expr| expr.as_arg());
+                                        assert_ne!(
+                                            source_props, None,
+                                            "value of property '__source' should not be empty"
+                                        );
+                                        continue;
+                                    }
+
+                                    if !use_create_element
+                                        && *i.sym == *"__self"
+                                        && self.development
+                                    {
+                                        if self_props.is_some() {
+                                            panic!("Duplicate __self is found");
+                                        }
+                                        self_props = attr
+                                            .value
+                                            .and_then(jsx_attr_value_to_expr)
+                                            .map(|expr| expr.as_arg());
+                                        assert_ne!(
+                                            self_props, None,
+                                            "value of property '__self' should not be empty"
+                                        );
+                                        continue;
+                                    }
+
+                                    let value = self.process_attr_value(attr.value);
+
+                                    // TODO: Check if `i` is a valid identifier.
+                                    let key = if i.sym.contains('-') {
+                                        PropName::Str(Str {
+                                            span: i.span,
+                                            raw: None,
+                                            value: i.sym.into(),
+                                        })
+                                    } else {
+                                        PropName::Ident(i)
+                                    };
+                                    props_obj.props.push(PropOrSpread::Prop(Box::new(
+                                        Prop::KeyValue(KeyValueProp { key, value }),
+                                    )));
+                                }
+                                JSXAttrName::JSXNamespacedName(JSXNamespacedName {
+                                    ns,
+                                    name,
+                                    ..
+                                }) => {
+                                    if self.throw_if_namespace && HANDLER.is_set() {
+                                        HANDLER.with(|handler| {
+                                            handler
+                                                .struct_span_err(
+                                                    span,
+                                                    "JSX Namespace is disabled by default because \
+                                                     react does not support it yet. You can \
+                                                     specify jsc.transform.react.throwIfNamespace \
+                                                     to false to override default behavior",
+                                                )
+                                                .emit()
+                                        });
+                                    }
+
+                                    let value = self.process_attr_value(attr.value);
+
+                                    let str_value = format!("{}:{}", ns.sym, name.sym);
+                                    let key = Str {
+                                        span,
+                                        raw: None,
+                                        value: str_value.into(),
+                                    };
+                                    let key = PropName::Str(key);
+
+                                    props_obj.props.push(PropOrSpread::Prop(Box::new(
+                                        Prop::KeyValue(KeyValueProp { key, value }),
+                                    )));
+                                }
+                                #[cfg(swc_ast_unknown)]
+                                _ => panic!("unable to access unknown nodes"),
+                            }
+                        }
+                        JSXAttrOrSpread::SpreadElement(attr) => match *attr.expr {
+                            Expr::Object(obj) => {
+                                props_obj.props.extend(obj.props);
+                            }
+                            _ => {
+                                props_obj.props.push(PropOrSpread::Spread(attr));
+                            }
+                        },
+                        #[cfg(swc_ast_unknown)]
+                        _ => panic!("unable to access unknown nodes"),
+                    }
+                }
+
+                let mut children = el
+                    .children
+                    .into_iter()
+                    .filter_map(|child| self.jsx_elem_child_to_expr(child))
+                    .map(Some)
+                    .collect::<Vec<_>>();
+
+                let use_jsxs = match children.len() {
+                    0 => false,
+                    1 if matches!(children.first(), Some(Some(child)) if child.spread.is_none()) => {
+                        if !use_create_element {
+                            props_obj
+                                .props
+                                .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                                    key: PropName::Ident(quote_ident!("children")),
+                                    value: children
+                                        .take()
+                                        .into_iter()
+                                        .next()
+                                        .flatten()
+                                        .unwrap()
+                                        .expr,
+                                }))));
+                        }
+
+                        false
+                    }
+                    _ => {
+                        props_obj
+                            .props
+                            .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                                key: PropName::Ident(quote_ident!("children")),
+                                value: ArrayLit {
+                                    span: DUMMY_SP,
+                                    elems: children.take(),
+                                }
+                                .into(),
+                            }))));
+                        true
+                    }
+                };
+
+                let jsx = if use_create_element {
+                    self.import_create_element
+                        .get_or_insert_with(|| private_ident!("_createElement"))
+                        .clone()
+                } else if use_jsxs && !self.development {
+                    self.import_jsxs
+                        .get_or_insert_with(|| private_ident!("_jsxs"))
+                        .clone()
+                } else {
+                    let jsx = if self.development { "_jsxDEV" } else { "_jsx" };
+                    self.import_jsx
+                        .get_or_insert_with(|| private_ident!(jsx))
+                        .clone()
+                };
+
+                // Build args Vec directly instead of using iterator chains
+                let args = if use_create_element {
+                    let mut args = Vec::with_capacity(2 + children.len());
+                    args.push(name.as_arg());
+                    args.push(props_obj.as_arg());
+                    args.extend(children.into_iter().flatten());
+                    args
+                } else if self.development {
+                    let mut args = Vec::with_capacity(6);
+                    args.push(name.as_arg());
+                    args.push(props_obj.as_arg());
+
+                    // set undefined literal to key if key is None
+                    let key = key.unwrap_or_else(|| Expr::undefined(DUMMY_SP).as_arg());
+                    args.push(key);
+
+                    args.push(use_jsxs.as_arg());
+
+                    // set undefined literal to __source if __source is None
+                    let source_props =
+                        source_props.unwrap_or_else(|| Expr::undefined(DUMMY_SP).as_arg());
+                    args.push(source_props);
+
+                    // set undefined literal to __self if __self is None
+                    let self_props =
+                        self_props.unwrap_or_else(|| Expr::undefined(DUMMY_SP).as_arg());
+                    args.push(self_props);
+
+                    args
+                } else {
+                    let mut args = Vec::with_capacity(if key.is_some() { 3 } else { 2 });
+                    args.push(name.as_arg());
+                    args.push(props_obj.as_arg());
+                    if let Some(key) = key {
+                        args.push(key);
+                    }
+                    args
+                };
+                CallExpr {
+                    span,
+                    callee: jsx.as_callee(),
+                    args,
+                    ..Default::default()
+                }
+                .into()
+            }
+            JsxRuntime::Classic => {
+                // Build args Vec directly for better performance
+                let children_capacity = el.children.len();
+                let mut args = Vec::with_capacity(2 + children_capacity);
+
+                args.push(name.as_arg());
+                args.push(self.fold_attrs_for_classic(el.opening.attrs).as_arg());
+
+                // Add children
+                for child in el.children {
+                    if let Some(expr) = self.jsx_elem_child_to_expr(child) {
+                        args.push(expr);
+                    }
+                }
+
+                CallExpr {
+                    span,
+                    callee: (*self.pragma).clone().as_callee(),
+                    args,
+                    ..Default::default()
+                }
+                .into()
+            }
+        }
+    }
+
+    fn jsx_elem_child_to_expr(&mut self, c: JSXElementChild) -> Option<ExprOrSpread> {
+        Some(match c {
+            JSXElementChild::JSXText(text) => {
+                // TODO(kdy1): Optimize
+                let value = jsx_text_to_str_with_raw(&text.value, &text.raw);
+                let s = Str {
+                    span: text.span,
+                    raw: None,
+                    value,
+                };
+
+                if s.value.is_empty() {
+                    return None;
+                }
+
+                Lit::Str(s).as_arg()
+            }
+            JSXElementChild::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(e),
+                ..
+            }) => e.as_arg(),
+            JSXElementChild::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::JSXEmptyExpr(..),
+                ..
+            }) => return None,
+            JSXElementChild::JSXElement(el) => self.jsx_elem_to_expr(*el).as_arg(),
+            JSXElementChild::JSXFragment(el) => self.jsx_frag_to_expr(el).as_arg(),
+            JSXElementChild::JSXSpreadChild(JSXSpreadChild { span, expr, .. }) => ExprOrSpread {
+                spread: Some(span),
+                expr,
+            },
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        })
+    }
+
+    fn fold_attrs_for_classic(&mut self, attrs: Vec<JSXAttrOrSpread>) -> Box<Expr> {
+        if attrs.is_empty() {
+            return Lit::Null(Null { span: DUMMY_SP }).into();
+        }
+        let attr_cnt = attrs.len();
+
+        let mut props = Vec::new();
+        for attr in attrs {
+            match attr {
+                JSXAttrOrSpread::JSXAttr(attr) => {
+                    props.push(PropOrSpread::Prop(Box::new(self.attr_to_prop(attr))))
+                }
+                JSXAttrOrSpread::SpreadElement(spread) => {
+                    if attr_cnt == 1 {
+                        return spread.expr;
+                    }
+                    // babel does some optimizations
+                    match *spread.expr {
+                        Expr::Object(obj) => props.extend(obj.props),
+                        _ => props.push(PropOrSpread::Spread(spread)),
+                    }
+                }
+                #[cfg(swc_ast_unknown)]
+                _ => panic!("unable to access unknown nodes"),
+            }
+        }
+
+        let obj = ObjectLit {
+            span: DUMMY_SP,
+            props,
+        };
+
+        obj.into()
+    }
+
+    fn attr_to_prop(&mut self, a: JSXAttr) -> Prop {
+        let key = to_prop_name(a.name);
+        let value = a
+            .value
+            .map(|v| match v {
+                JSXAttrValue::Str(s) => {
+                    let value = transform_jsx_attr_str(&s.value);
+
+                    Lit::Str(Str {
+                        span: s.span,
+                        raw: None,
+                        value: value.into(),
+                    })
+                    .into()
+                }
+                JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(e),
+                    ..
+                }) => e,
+                JSXAttrValue::JSXElement(element) => Box::new(self.jsx_elem_to_expr(*element)),
+                JSXAttrValue::JSXFragment(fragment) => Box::new(self.jsx_frag_to_expr(fragment)),
+                JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    span: _,
+                    expr: JSXExpr::JSXEmptyExpr(_),
+                }) => unreachable!("attr_to_prop(JSXEmptyExpr)"),
+                #[cfg(swc_ast_unknown)]
+                _ => panic!("unable to access unknown nodes"),
+            })
+            .unwrap_or_else(|| {
+                Lit::Bool(Bool {
+                    span: key.span(),
+                    value: true,
+                })
+                .into()
+            });
+        Prop::KeyValue(KeyValueProp { key, value })
+    }
 }
 
-struct JsxPass {
-    options: JsxOptions,
+impl<C> JsxTransform<C>
+where
+    C: Comments,
+{
+    /// If we found required jsx directives, we returns true.
+    fn parse_directives(&mut self, span: Span) -> bool {
+        let mut found = false;
+
+        let directives = self.comments.with_leading(span.lo, |comments| {
+            JsxDirectives::from_comments(&self.cm, comments, self.top_level_ctxt)
+        });
+
+        let JsxDirectives {
+            runtime,
+            import_source,
+            pragma,
+            pragma_frag,
+        } = directives;
+
+        if let Some(runtime) = runtime {
+            found = true;
+            self.runtime = runtime;
+        }
+
+        if let Some(import_source) = import_source {
+            found = true;
+            self.import_source = import_source;
+        }
+
+        if let Some(pragma) = pragma {
+            if let JsxRuntime::Automatic = self.runtime {
+                if HANDLER.is_set() {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(
+                                pragma.span(),
+                                "pragma cannot be set when runtime is automatic",
+                            )
+                            .emit()
+                    });
+                }
+            }
+
+            found = true;
+            self.pragma = pragma;
+        }
+
+        if let Some(pragma_frag) = pragma_frag {
+            if let JsxRuntime::Automatic = self.runtime {
+                if HANDLER.is_set() {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(
+                                pragma_frag.span(),
+                                "pragmaFrag cannot be set when runtime is automatic",
+                            )
+                            .emit()
+                    });
+                }
+            }
+
+            found = true;
+            self.pragma_frag = pragma_frag;
+        }
+
+        found
+    }
 }
 
-impl VisitMutHook<TraverseCtx> for JsxPass {}
+impl<C> VisitMutHook<TraverseCtx> for JsxTransform<C>
+where
+    C: Comments,
+{
+    /// JSX is converted before its children are visited, so the generated
+    /// calls and objects are processed by the transforms that run after this
+    /// one.
+    fn enter_expr(&mut self, expr: &mut Expr, _ctx: &mut TraverseCtx) {
+        self.transform_expr(expr);
+    }
+
+    fn enter_class(&mut self, n: &mut Class, _ctx: &mut TraverseCtx) {
+        self.enter_dev_class(n);
+    }
+
+    fn exit_class(&mut self, _: &mut Class, _ctx: &mut TraverseCtx) {
+        self.exit_dev_scope();
+    }
+
+    fn enter_constructor(&mut self, _: &mut Constructor, _ctx: &mut TraverseCtx) {
+        self.enter_dev_function(true);
+    }
+
+    fn exit_constructor(&mut self, _: &mut Constructor, _ctx: &mut TraverseCtx) {
+        self.exit_dev_scope();
+    }
+
+    fn enter_fn_decl(&mut self, _: &mut FnDecl, _ctx: &mut TraverseCtx) {
+        self.enter_dev_function(false);
+    }
+
+    fn exit_fn_decl(&mut self, _: &mut FnDecl, _ctx: &mut TraverseCtx) {
+        self.exit_dev_scope();
+    }
+
+    fn enter_fn_expr(&mut self, _: &mut FnExpr, _ctx: &mut TraverseCtx) {
+        self.enter_dev_function(false);
+    }
+
+    fn exit_fn_expr(&mut self, _: &mut FnExpr, _ctx: &mut TraverseCtx) {
+        self.exit_dev_scope();
+    }
+
+    fn enter_getter_prop(&mut self, _: &mut GetterProp, _ctx: &mut TraverseCtx) {
+        self.enter_dev_function(false);
+    }
+
+    fn exit_getter_prop(&mut self, _: &mut GetterProp, _ctx: &mut TraverseCtx) {
+        self.exit_dev_scope();
+    }
+
+    fn enter_setter_prop(&mut self, _: &mut SetterProp, _ctx: &mut TraverseCtx) {
+        self.enter_dev_function(false);
+    }
+
+    fn exit_setter_prop(&mut self, _: &mut SetterProp, _ctx: &mut TraverseCtx) {
+        self.exit_dev_scope();
+    }
+
+    fn enter_method_prop(&mut self, _: &mut MethodProp, _ctx: &mut TraverseCtx) {
+        self.enter_dev_function(false);
+    }
+
+    fn exit_method_prop(&mut self, _: &mut MethodProp, _ctx: &mut TraverseCtx) {
+        self.exit_dev_scope();
+    }
+
+    fn enter_class_method(&mut self, _: &mut ClassMethod, _ctx: &mut TraverseCtx) {
+        self.enter_dev_function(false);
+    }
+
+    fn exit_class_method(&mut self, _: &mut ClassMethod, _ctx: &mut TraverseCtx) {
+        self.exit_dev_scope();
+    }
+
+    fn enter_private_method(&mut self, _: &mut PrivateMethod, _ctx: &mut TraverseCtx) {
+        self.enter_dev_function(false);
+    }
+
+    fn exit_private_method(&mut self, _: &mut PrivateMethod, _ctx: &mut TraverseCtx) {
+        self.exit_dev_scope();
+    }
+
+    fn enter_static_block(&mut self, _: &mut StaticBlock, _ctx: &mut TraverseCtx) {
+        self.enter_dev_function(false);
+    }
+
+    fn exit_static_block(&mut self, _: &mut StaticBlock, _ctx: &mut TraverseCtx) {
+        self.exit_dev_scope();
+    }
+
+    fn enter_module(&mut self, module: &mut Module, _ctx: &mut TraverseCtx) {
+        self.parse_module_directives(module);
+    }
+
+    fn exit_module(&mut self, module: &mut Module, _ctx: &mut TraverseCtx) {
+        self.inject_module_imports(module);
+    }
+
+    fn enter_script(&mut self, script: &mut Script, _ctx: &mut TraverseCtx) {
+        self.parse_script_directives(script);
+    }
+
+    fn exit_script(&mut self, script: &mut Script, _ctx: &mut TraverseCtx) {
+        self.inject_script_requires(script);
+    }
+}
+
+// const { createElement } = require('react')
+// const { jsx: jsx } = require('react/jsx-runtime')
+fn add_require(
+    imports: Vec<(Ident, IdentName)>,
+    src: &str,
+    unresolved_ctxt: SyntaxContext,
+) -> Stmt {
+    VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Const,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Object(ObjectPat {
+                span: DUMMY_SP,
+                props: imports
+                    .into_iter()
+                    .map(|(local, imported)| {
+                        if imported.sym != local.sym {
+                            ObjectPatProp::KeyValue(KeyValuePatProp {
+                                key: PropName::Ident(imported),
+                                value: Box::new(Pat::Ident(local.into())),
+                            })
+                        } else {
+                            ObjectPatProp::Assign(AssignPatProp {
+                                span: DUMMY_SP,
+                                key: local.into(),
+                                value: None,
+                            })
+                        }
+                    })
+                    .collect(),
+                optional: false,
+                type_ann: None,
+            }),
+            // require('react')
+            init: Some(Box::new(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: Callee::Expr(Box::new(Expr::Ident(Ident {
+                    ctxt: unresolved_ctxt,
+                    sym: atom!("require"),
+                    optional: false,
+                    ..Default::default()
+                }))),
+                args: vec![ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Lit(Lit::Str(Str {
+                        span: DUMMY_SP,
+                        value: src.into(),
+                        raw: None,
+                    }))),
+                }],
+                ..Default::default()
+            }))),
+            definite: false,
+        }],
+        ..Default::default()
+    }
+    .into()
+}
+
+impl<C> JsxTransform<C>
+where
+    C: Comments,
+{
+    fn jsx_name(&self, name: JSXElementName) -> Box<Expr> {
+        let span = name.span();
+        match name {
+            JSXElementName::Ident(i) => {
+                if i.sym == "this" {
+                    return ThisExpr { span }.into();
+                }
+
+                // If it starts with lowercase
+                if i.as_ref().starts_with(|c: char| c.is_ascii_lowercase()) {
+                    Lit::Str(Str {
+                        span,
+                        raw: None,
+                        value: i.sym.into(),
+                    })
+                    .into()
+                } else {
+                    i.into()
+                }
+            }
+            JSXElementName::JSXNamespacedName(JSXNamespacedName {
+                ref ns, ref name, ..
+            }) => {
+                if self.throw_if_namespace && HANDLER.is_set() {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(
+                                span,
+                                "JSX Namespace is disabled by default because react does not \
+                                 support it yet. You can specify \
+                                 jsc.transform.react.throwIfNamespace to false to override \
+                                 default behavior",
+                            )
+                            .emit()
+                    });
+                }
+
+                let value = format!("{}:{}", ns.sym, name.sym);
+
+                Lit::Str(Str {
+                    span,
+                    raw: None,
+                    value: value.into(),
+                })
+                .into()
+            }
+            JSXElementName::JSXMemberExpr(JSXMemberExpr { obj, prop, .. }) => {
+                fn convert_obj(obj: JSXObject) -> Box<Expr> {
+                    let span = obj.span();
+
+                    (match obj {
+                        JSXObject::Ident(i) => {
+                            if i.sym == "this" {
+                                Expr::This(ThisExpr { span })
+                            } else {
+                                i.into()
+                            }
+                        }
+                        JSXObject::JSXMemberExpr(e) => MemberExpr {
+                            span,
+                            obj: convert_obj(e.obj),
+                            prop: MemberProp::Ident(e.prop),
+                        }
+                        .into(),
+                        #[cfg(swc_ast_unknown)]
+                        _ => panic!("unable to access unknown nodes"),
+                    })
+                    .into()
+                }
+                MemberExpr {
+                    span,
+                    obj: convert_obj(obj),
+                    prop: MemberProp::Ident(prop),
+                }
+                .into()
+            }
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        }
+    }
+}
+
+fn to_prop_name(n: JSXAttrName) -> PropName {
+    let span = n.span();
+
+    match n {
+        JSXAttrName::Ident(i) => {
+            if i.sym.contains('-') {
+                PropName::Str(Str {
+                    span,
+                    raw: None,
+                    value: i.sym.into(),
+                })
+            } else {
+                PropName::Ident(i)
+            }
+        }
+        JSXAttrName::JSXNamespacedName(JSXNamespacedName { ns, name, .. }) => {
+            let value = format!("{}:{}", ns.sym, name.sym);
+
+            PropName::Str(Str {
+                span,
+                raw: None,
+                value: value.into(),
+            })
+        }
+        #[cfg(swc_ast_unknown)]
+        _ => panic!("unable to access unknown nodes"),
+    }
+}
@@ -5,7 +5,7 @@ use swc_ecma_ast::*;
 /// from <div key={key} {...props} />. This is an intermediary
 /// step while we deprecate key spread from props. Afterwards,
 /// we will stop using createElement in the transform.
-pub(super) fn should_use_create_element(attrs: &[JSXAttrOrSpread]) -> bool {
+pub fn should_use_create_element(attrs: &[JSXAttrOrSpread]) -> bool {
     let mut seen_prop_spread = false;
     for attr in attrs {
         if seen_prop_spread
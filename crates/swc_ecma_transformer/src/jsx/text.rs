@@ -0,0 +1,809 @@
+//! Conversion of JSX text and attribute strings to string literals.
+
+use swc_atoms::{
+    Atom, Wtf8Atom,
+    wtf8::{Wtf8, Wtf8Buf},
+};
+use swc_ecma_ast::*;
+use swc_ecma_utils::str::is_line_terminator;
+
+/// https://github.com/microsoft/TypeScript/blob/9e20e032effad965567d4a1e1c30d5433b0a3332/src/compiler/transformers/jsx.ts#L572-L608
+///
+/// JSX trims whitespace at the end and beginning of lines, except that the
+/// start/end of a tag is considered a start/end of a line only if that line is
+/// on the same line as the closing tag. See examples in
+/// tests/cases/conformance/jsx/tsxReactEmitWhitespace.tsx
+/// See also https://www.w3.org/TR/html4/struct/text.html#h-9.1 and https://www.w3.org/TR/CSS2/text.html#white-space-model
+///
+/// An equivalent algorithm would be:
+/// - If there is only one line, return it.
+/// - If there is only whitespace (but multiple lines), return `undefined`.
+/// - Split the text into lines.
+/// - 'trimRight' the first line, 'trimLeft' the last line, 'trim' middle lines.
+/// - Decode entities on each line (individually).
+/// - Remove empty lines and join the rest with " ".
+///
+/// This version takes both `value` (decoded) and `raw` (original source) to
+/// preserve whitespace that was explicitly encoded as HTML entities like
+/// `&#32;`, `&#9;`, `&#10;`, `&#13;`.
+#[inline]
+pub fn jsx_text_to_str_with_raw(value: &Atom, raw: &Atom) -> Wtf8Atom {
+    // Fast path: if no HTML entities (raw == value), use the simple algorithm
+    if value.as_str() == raw.as_str() {
+        return jsx_text_to_str_impl(value).into();
+    }
+
+    // Build a mask of which byte positions in value came from HTML entities
+    let entity_mask = build_entity_mask(value, raw);
+
+    jsx_text_to_str_with_entity_mask(value, &entity_mask).into()
+}
+
+/// Build a mask indicating which character positions in `value` came from HTML
+/// entities in `raw`.
+///
+/// Returns a Vec<bool> where true means the character at that index was from an
+/// entity.
+fn build_entity_mask(value: &str, raw: &str) -> Vec<bool> {
+    let mut mask = vec![false; value.chars().count()];
+    let mut value_char_idx = 0;
+    let mut raw_chars = raw.chars().peekable();
+
+    while let Some(raw_c) = raw_chars.next() {
+        if raw_c == '&' {
+            // Possible HTML entity
+            let mut entity_chars: Vec<char> = vec!['&'];
+            let mut found_semicolon = false;
+
+            // Collect up to 10 characters to match entity pattern
+            for _ in 0..10 {
+                if let Some(&next_c) = raw_chars.peek() {
+                    entity_chars.push(next_c);
+                    raw_chars.next();
+                    if next_c == ';' {
+                        found_semicolon = true;
+                        break;
+                    }
+                } else {
+                    break;
+                }
+            }
+
+            if found_semicolon && is_valid_entity(&entity_chars) {
+                // This was a valid entity - mark this position in value as from
+                // entity
+                if value_char_idx < mask.len() {
+                    mask[value_char_idx] = true;
+                }
+                value_char_idx += 1;
+            } else {
+                // Not a valid entity, the '&' is literal
+                value_char_idx += 1;
+                // The other characters we consumed are also literal
+                for _ in 1..entity_chars.len() {
+                    value_char_idx += 1;
+                }
+            }
+        } else {
+            // Regular character
+            value_char_idx += 1;
+        }
+    }
+
+    mask
+}
+
+/// Check if the collected characters form a valid HTML entity
+fn is_valid_entity(chars: &[char]) -> bool {
+    if chars.len() < 3 {
+        return false;
+    }
+    if chars[0] != '&' || chars[chars.len() - 1] != ';' {
+        return false;
+    }
+
+    let inner: String = chars[1..chars.len() - 1].iter().collect();
+
+    if let Some(stripped) = inner.strip_prefix('#') {
+        // Numeric entity
+        if let Some(hex) = stripped
+            .strip_prefix('x')
+            .or_else(|| stripped.strip_prefix('X'))
+        {
+            // Hex: &#xHHHH;
+            !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit())
+        } else {
+            // Decimal: &#DDDD;
+            !stripped.is_empty() && stripped.chars().all(|c| c.is_ascii_digit())
+        }
+    } else {
+        // Named entity - check against known entities
+        is_known_html_entity(&inner)
+    }
+}
+
+/// Check if name is a known HTML entity
+fn is_known_html_entity(name: &str) -> bool {
+    // Common HTML entities that decode to whitespace or other characters
+    // This list matches the entities defined in swc_ecma_lexer jsx.rs xhtml!
+    // macro
+    matches!(
+        name,
+        "nbsp"
+            | "iexcl"
+            | "cent"
+            | "pound"
+            | "curren"
+            | "yen"
+            | "brvbar"
+            | "sect"
+            | "uml"
+            | "copy"
+            | "ordf"
+            | "laquo"
+            | "not"
+            | "shy"
+            | "reg"
+            | "macr"
+            | "deg"
+            | "plusmn"
+            | "sup2"
+            | "sup3"
+            | "acute"
+            | "micro"
+            | "para"
+            | "middot"
+            | "cedil"
+            | "sup1"
+            | "ordm"
+            | "raquo"
+            | "frac14"
+            | "frac12"
+            | "frac34"
+            | "iquest"
+            | "Agrave"
+            | "Aacute"
+            | "Acirc"
+            | "Atilde"
+            | "Auml"
+            | "Aring"
+            | "AElig"
+            | "Ccedil"
+            | "Egrave"
+            | "Eacute"
+            | "Ecirc"
+            | "Euml"
+            | "Igrave"
+            | "Iacute"
+            | "Icirc"
+            | "Iuml"
+            | "ETH"
+            | "Ntilde"
+            | "Ograve"
+            | "Oacute"
+            | "Ocirc"
+            | "Otilde"
+            | "Ouml"
+            | "times"
+            | "Oslash"
+            | "Ugrave"
+            | "Uacute"
+            | "Ucirc"
+            | "Uuml"
+            | "Yacute"
+            | "THORN"
+            | "szlig"
+            | "agrave"
+            | "aacute"
+            | "acirc"
+            | "atilde"
+            | "auml"
+            | "aring"
+            | "aelig"
+            | "ccedil"
+            | "egrave"
+            | "eacute"
+            | "ecirc"
+            | "euml"
+            | "igrave"
+            | "iacute"
+            | "icirc"
+            | "iuml"
+            | "eth"
+            | "ntilde"
+            | "ograve"
+            | "oacute"
+            | "ocirc"
+            | "otilde"
+            | "ouml"
+            | "divide"
+            | "oslash"
+            | "ugrave"
+            | "uacute"
+            | "ucirc"
+            | "uuml"
+            | "yacute"
+            | "thorn"
+            | "yuml"
+            | "OElig"
+            | "oelig"
+            | "Scaron"
+            | "scaron"
+            | "Yuml"
+            | "fnof"
+            | "circ"
+            | "tilde"
+            | "Alpha"
+            | "Beta"
+            | "Gamma"
+            | "Delta"
+            | "Epsilon"
+            | "Zeta"
+            | "Eta"
+            | "Theta"
+            | "Iota"
+            | "Kappa"
+            | "Lambda"
+            | "Mu"
+            | "Nu"
+            | "Xi"
+            | "Omicron"
+            | "Pi"
+            | "Rho"
+            | "Sigma"
+            | "Tau"
+            | "Upsilon"
+            | "Phi"
+            | "Chi"
+            | "Psi"
+            | "Omega"
+            | "alpha"
+            | "beta"
+            | "gamma"
+            | "delta"
+            | "epsilon"
+            | "zeta"
+            | "eta"
+            | "theta"
+            | "iota"
+            | "kappa"
+            | "lambda"
+            | "mu"
+            | "nu"
+            | "xi"
+            | "omicron"
+            | "pi"
+            | "rho"
+            | "sigmaf"
+            | "sigma"
+            | "tau"
+            | "upsilon"
+            | "phi"
+            | "chi"
+            | "psi"
+            | "omega"
+            | "thetasym"
+            | "upsih"
+            | "piv"
+            | "ensp"
+            | "emsp"
+            | "thinsp"
+            | "zwnj"
+            | "zwj"
+            | "lrm"
+            | "rlm"
+            | "ndash"
+            | "mdash"
+            | "lsquo"
+            | "rsquo"
+            | "sbquo"
+            | "ldquo"
+            | "rdquo"
+            | "bdquo"
+            | "dagger"
+            | "Dagger"
+            | "bull"
+            | "hellip"
+            | "permil"
+            | "prime"
+            | "Prime"
+            | "lsaquo"
+            | "rsaquo"
+            | "oline"
+            | "frasl"
+            | "euro"
+            | "image"
+            | "weierp"
+            | "real"
+            | "trade"
+            | "alefsym"
+            | "larr"
+            | "uarr"
+            | "rarr"
+            | "darr"
+            | "harr"
+            | "crarr"
+            | "lArr"
+            | "uArr"
+            | "rArr"
+            | "dArr"
+            | "hArr"
+            | "forall"
+            | "part"
+            | "exist"
+            | "empty"
+            | "nabla"
+            | "isin"
+            | "notin"
+            | "ni"
+            | "prod"
+            | "sum"
+            | "minus"
+            | "lowast"
+            | "radic"
+            | "prop"
+            | "infin"
+            | "ang"
+            | "and"
+            | "or"
+            | "cap"
+            | "cup"
+            | "int"
+            | "there4"
+            | "sim"
+            | "cong"
+            | "asymp"
+            | "ne"
+            | "equiv"
+            | "le"
+            | "ge"
+            | "sub"
+            | "sup"
+            | "nsub"
+            | "sube"
+            | "supe"
+            | "oplus"
+            | "otimes"
+            | "perp"
+            | "sdot"
+            | "lceil"
+            | "rceil"
+            | "lfloor"
+            | "rfloor"
+            | "lang"
+            | "rang"
+            | "loz"
+            | "spades"
+            | "clubs"
+            | "hearts"
+            | "diams"
+            | "quot"
+            | "amp"
+            | "lt"
+            | "gt"
+    )
+}
+
+/// JSX text processing with entity mask - preserves whitespace from HTML
+/// entities
+fn jsx_text_to_str_with_entity_mask(t: &str, entity_mask: &[bool]) -> Atom {
+    // Fast path: if no line terminators and no trimmable whitespace
+    // (whitespace that's not from entities at the leading edge)
+    let chars: Vec<char> = t.chars().collect();
+    let has_line_terminator = chars.iter().any(|&c| is_line_terminator(c));
+
+    // For single-line text, we keep all whitespace (matching original behavior)
+    // The original jsx_text_to_str_impl preserves leading/trailing whitespace on
+    // single-line text
+    if !t.is_empty() && !has_line_terminator {
+        return t.into();
+    }
+
+    let mut acc: Option<String> = None;
+    let mut only_line: Option<String> = None;
+    let mut line_start: Option<usize> = Some(0);
+    let mut line_end: Option<usize> = None;
+    // Track if we've seen any line terminators - used to decide whether to trim
+    // leading whitespace on the final line
+    let mut seen_line_terminator = false;
+
+    for (char_idx, c) in chars.iter().enumerate() {
+        let is_from_entity = *entity_mask.get(char_idx).unwrap_or(&false);
+
+        if is_line_terminator(*c) {
+            seen_line_terminator = true;
+            // Process current line - trim both leading AND trailing (intermediate
+            // line)
+            if let (Some(start), Some(end)) = (line_start, line_end) {
+                let line_text = extract_line_content(&chars, start, end, entity_mask, true, true);
+                add_line_of_jsx_text_owned(line_text, &mut acc, &mut only_line);
+            }
+            line_start = None;
+            line_end = None;
+        } else if !is_white_space_single_line(*c) || is_from_entity {
+            // Non-whitespace or entity-derived whitespace - counts as content
+            line_end = Some(char_idx + 1);
+            if line_start.is_none() {
+                line_start = Some(char_idx);
+            }
+        }
+    }
+
+    // Handle final line - only trim leading if we've seen line terminators
+    // (matching original jsx_text_to_str_impl behavior where leading whitespace
+    // is preserved on single-line text)
+    if let Some(start) = line_start {
+        let line_text = extract_line_content(
+            &chars,
+            start,
+            chars.len(),
+            entity_mask,
+            seen_line_terminator,
+            false,
+        );
+        add_line_of_jsx_text_owned(line_text, &mut acc, &mut only_line);
+    }
+
+    if let Some(acc) = acc {
+        acc.into()
+    } else if let Some(only_line) = only_line {
+        only_line.into()
+    } else {
+        "".into()
+    }
+}
+
+/// Extract line content, optionally trimming non-entity whitespace from edges
+///
+/// - `trim_leading`: if true, trim leading non-entity whitespace
+/// - `trim_trailing`: if true, trim trailing non-entity whitespace
+fn extract_line_content(
+    chars: &[char],
+    start: usize,
+    end: usize,
+    entity_mask: &[bool],
+    trim_leading: bool,
+    trim_trailing: bool,
+) -> String {
+    // Find first non-trimmable position (if trim_leading is true)
+    let mut actual_start = start;
+    if trim_leading {
+        while actual_start < end {
+            let c = chars[actual_start];
+            let is_from_entity = *entity_mask.get(actual_start).unwrap_or(&false);
+            if !is_white_space_single_line(c) || is_from_entity {
+                break;
+            }
+            actual_start += 1;
+        }
+    }
+
+    // Find last non-trimmable position (if trim_trailing is true)
+    let mut actual_end = end;
+    if trim_trailing {
+        while actual_end > actual_start {
+            let c = chars[actual_end - 1];
+            let is_from_entity = *entity_mask.get(actual_end - 1).unwrap_or(&false);
+            if !is_white_space_single_line(c) || is_from_entity {
+                break;
+            }
+            actual_end -= 1;
+        }
+    }
+
+    chars[actual_start..actual_end].iter().collect()
+}
+
+/// Owned version of add_line_of_jsx_text for use with entity mask processing
+fn add_line_of_jsx_text_owned(
+    line: String,
+    acc: &mut Option<String>,
+    only_line: &mut Option<String>,
+) {
+    if line.is_empty() {
+        return;
+    }
+
+    if let Some(buffer) = acc.as_mut() {
+        buffer.push(' ');
+        buffer.push_str(&line);
+    } else if let Some(only_line_content) = only_line.take() {
+        let mut buffer = String::with_capacity(line.len() * 2);
+        buffer.push_str(&only_line_content);
+        buffer.push(' ');
+        buffer.push_str(&line);
+        *acc = Some(buffer);
+    } else {
+        *only_line = Some(line);
+    }
+}
+
+/// Same as [jsx_text_to_str_with_raw], but without the raw source, so HTML
+/// entities are not distinguished from the literal characters.
+#[inline]
+pub fn jsx_text_to_str<'a, T>(t: &'a T) -> Wtf8Atom
+where
+    &'a T: Into<&'a Wtf8>,
+    T: ?Sized,
+{
+    let t = t.into();
+    // Fast path: JSX text is almost always valid UTF-8
+    if let Some(s) = t.as_str() {
+        return jsx_text_to_str_impl(s).into();
+    }
+
+    // Slow path: Handle Wtf8 with surrogates (extremely rare)
+    jsx_text_to_str_wtf8_impl(t)
+}
+
+/// Handle JSX text with surrogates
+fn jsx_text_to_str_wtf8_impl(t: &Wtf8) -> Wtf8Atom {
+    let mut acc: Option<Wtf8Buf> = None;
+    let mut only_line: Option<(usize, usize)> = None; // (start, end) byte positions
+    let mut first_non_whitespace: Option<usize> = Some(0);
+    let mut last_non_whitespace: Option<usize> = None;
+
+    let mut byte_pos = 0;
+    for cp in t.code_points() {
+        let c = cp.to_char_lossy();
+        let cp_value = cp.to_u32();
+
+        // Calculate byte length of this code point in WTF-8
+        let cp_byte_len = if cp_value < 0x80 {
+            1
+        } else if cp_value < 0x800 {
+            2
+        } else if cp_value < 0x10000 {
+            3
+        } else {
+            4
+        };
+
+        if is_line_terminator(c) {
+            if let (Some(first), Some(last)) = (first_non_whitespace, last_non_whitespace) {
+                add_line_of_jsx_text_wtf8(first, last, t, &mut acc, &mut only_line);
+            }
+            first_non_whitespace = None;
+        } else if !is_white_space_single_line(c) {
+            last_non_whitespace = Some(byte_pos + cp_byte_len);
+            if first_non_whitespace.is_none() {
+                first_non_whitespace.replace(byte_pos);
+            }
+        }
+
+        byte_pos += cp_byte_len;
+    }
+
+    // Handle final line
+    if let Some(first) = first_non_whitespace {
+        add_line_of_jsx_text_wtf8(first, t.len(), t, &mut acc, &mut only_line);
+    }
+
+    if let Some(acc) = acc {
+        acc.into()
+    } else if let Some((start, end)) = only_line {
+        t.slice(start, end).into()
+    } else {
+        Wtf8Atom::default()
+    }
+}
+
+/// Helper for adding lines of JSX text when handling Wtf8 with surrogates
+fn add_line_of_jsx_text_wtf8(
+    line_start: usize,
+    line_end: usize,
+    source: &Wtf8,
+    acc: &mut Option<Wtf8Buf>,
+    only_line: &mut Option<(usize, usize)>,
+) {
+    if let Some((only_start, only_end)) = only_line.take() {
+        // Second line - create accumulator
+        let mut buffer = Wtf8Buf::with_capacity(source.len());
+        buffer.push_wtf8(source.slice(only_start, only_end));
+        buffer.push_str(" ");
+        buffer.push_wtf8(source.slice(line_start, line_end));
+        *acc = Some(buffer);
+    } else if let Some(buffer) = acc {
+        // Subsequent lines
+        buffer.push_str(" ");
+        buffer.push_wtf8(source.slice(line_start, line_end));
+    } else {
+        // First line
+        *only_line = Some((line_start, line_end));
+    }
+}
+
+/// Internal implementation that works with &str
+#[inline]
+fn jsx_text_to_str_impl(t: &str) -> Atom {
+    // Fast path: if no line terminators and no leading/trailing whitespace
+    if !t.is_empty()
+        && !t.chars().any(is_line_terminator)
+        && !t.starts_with(is_white_space_single_line)
+        && !t.ends_with(is_white_space_single_line)
+    {
+        return t.into();
+    }
+
+    let mut acc: Option<String> = None;
+    let mut only_line: Option<&str> = None;
+    let mut first_non_whitespace: Option<usize> = Some(0);
+    let mut last_non_whitespace: Option<usize> = None;
+
+    for (index, c) in t.char_indices() {
+        if is_line_terminator(c) {
+            if let (Some(first), Some(last)) = (first_non_whitespace, last_non_whitespace) {
+                let line_text = &t[first..last];
+                add_line_of_jsx_text(line_text, &mut acc, &mut only_line);
+            }
+            first_non_whitespace = None;
+        } else if !is_white_space_single_line(c) {
+            last_non_whitespace = Some(index + c.len_utf8());
+            if first_non_whitespace.is_none() {
+                first_non_whitespace.replace(index);
+            }
+        }
+    }
+
+    if let Some(first) = first_non_whitespace {
+        let line_text = &t[first..];
+        add_line_of_jsx_text(line_text, &mut acc, &mut only_line);
+    }
+
+    if let Some(acc) = acc {
+        acc.into()
+    } else if let Some(only_line) = only_line {
+        only_line.into()
+    } else {
+        "".into()
+    }
+}
+
+/// [TODO]: Re-validate this whitespace handling logic.
+///
+/// We cannot use [swc_ecma_utils::str::is_white_space_single_line] because
+/// HTML entities (like `&nbsp;` → `\u{00a0}`) are pre-processed by the parser,
+/// making it impossible to distinguish them from literal Unicode characters. We
+/// should never trim HTML entities.
+///
+/// As a reference, Babel only trims regular spaces and tabs, so this is a
+/// simplified implementation already in use.
+/// https://github.com/babel/babel/blob/e5c8dc7330cb2f66c37637677609df90b31ff0de/packages/babel-types/src/utils/react/cleanJSXElementLiteralChild.ts#L28-L39
+fn is_white_space_single_line(c: char) -> bool {
+    matches!(c, ' ' | '\t')
+}
+
+// less allocations trick from OXC
+// https://github.com/oxc-project/oxc/blob/4c35f4abb6874bd741b84b34df7889637425e9ea/crates/oxc_transformer/src/jsx/jsx_impl.rs#L1061-L1091
+fn add_line_of_jsx_text<'a>(
+    trimmed_line: &'a str,
+    acc: &mut Option<String>,
+    only_line: &mut Option<&'a str>,
+) {
+    if let Some(buffer) = acc.as_mut() {
+        // Already some text in accumulator. Push a space before this line is added to
+        // `acc`.
+        buffer.push(' ');
+    } else if let Some(only_line_content) = only_line.take() {
+        // This is the 2nd line containing text. Previous line did not contain any HTML
+        // entities. Generate an accumulator containing previous line and a
+        // trailing space. Current line will be added to the accumulator after
+        // it.
+        let mut buffer = String::with_capacity(trimmed_line.len() * 2); // rough estimate
+        buffer.push_str(only_line_content);
+        buffer.push(' ');
+        *acc = Some(buffer);
+    }
+
+    // [TODO]: Decode any HTML entities in this line
+
+    // For now, just use the trimmed line directly
+    if let Some(buffer) = acc.as_mut() {
+        buffer.push_str(trimmed_line);
+    } else {
+        // This is the first line containing text, and there are no HTML entities in
+        // this line. Record this line in `only_line`.
+        // If this turns out to be the only line, we won't need to construct a String,
+        // so avoid all copying.
+        *only_line = Some(trimmed_line);
+    }
+}
+
+/// Converts the value of a JSX attribute to an expression.
+///
+/// Returns [None] for an empty expression container.
+pub fn jsx_attr_value_to_expr(v: JSXAttrValue) -> Option<Box<Expr>> {
+    Some(match v {
+        JSXAttrValue::Str(s) => {
+            let value = transform_jsx_attr_str(&s.value);
+
+            Lit::Str(Str {
+                span: s.span,
+                raw: None,
+                value: value.into(),
+            })
+            .into()
+        }
+        JSXAttrValue::JSXExprContainer(e) => match e.expr {
+            JSXExpr::JSXEmptyExpr(_) => None?,
+            JSXExpr::Expr(e) => e,
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        },
+        JSXAttrValue::JSXElement(e) => e.into(),
+        JSXAttrValue::JSXFragment(f) => f.into(),
+        #[cfg(swc_ast_unknown)]
+        _ => panic!("unable to access unknown nodes"),
+    })
+}
+
+/// Normalizes the whitespace and escapes the control characters of a JSX
+/// attribute string.
+pub fn transform_jsx_attr_str(v: &Wtf8) -> Wtf8Buf {
+    // Fast path: check if transformation is needed
+    let needs_transform = v.code_points().any(|cp| {
+        if let Some(c) = cp.to_char() {
+            matches!(
+                c,
+                '\u{0008}' | '\u{000c}' | '\n' | '\r' | '\t' | '\u{000b}' | '\0'
+            )
+        } else {
+            false
+        }
+    });
+
+    if !needs_transform {
+        return v.to_owned();
+    }
+
+    let single_quote = false;
+    let mut buf = Wtf8Buf::with_capacity(v.len());
+    let mut iter = v.code_points().peekable();
+
+    while let Some(code_point) = iter.next() {
+        if let Some(c) = code_point.to_char() {
+            match c {
+                '\u{0008}' => buf.push_str("\\b"),
+                '\u{000c}' => buf.push_str("\\f"),
+                ' ' => buf.push_char(' '),
+
+                '\n' | '\r' | '\t' => {
+                    buf.push_char(' ');
+
+                    while let Some(next) = iter.peek() {
+                        if next.to_char() == Some(' ') {
+                            iter.next();
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                '\u{000b}' => buf.push_str("\\v"),
+                '\0' => buf.push_str("\\x00"),
+
+                '\'' if single_quote => buf.push_str("\\'"),
+                '"' if !single_quote => buf.push_char('"'),
+
+                '\x01'..='\x0f' | '\x10'..='\x1f' => {
+                    buf.push_char(c);
+                }
+
+                '\x20'..='\x7e' => {
+                    //
+                    buf.push_char(c);
+                }
+                '\u{7f}'..='\u{ff}' => {
+                    buf.push_char(c);
+                }
+
+                _ => {
+                    buf.push_char(c);
+                }
+            }
+        } else {
+            buf.push(code_point);
+        }
+    }
+
+    buf
+}
@@ -5,7 +5,15 @@ use swc_ecma_hooks::{VisitMutHook, VisitMutWithHook};
 use swc_ecma_visit::visit_mut_pass;
 
 use crate::hook_utils::{HookBuilder, NoopHook};
-pub use crate::options::*;
+pub use crate::{
+    es2015::lower_arrows,
+    jsx::{
+        JsxDirectives, JsxTransform, jsx_attr_value_to_expr, jsx_text_to_str,
+        jsx_text_to_str_with_raw, parse_expr_for_jsx, should_use_create_element,
+        transform_jsx_attr_str,
+    },
+    options::*,
+};
 
 mod bugfix;
 mod common;
@@ -28,6 +36,13 @@ mod regexp;
 mod typescript;
 mod utils;
 
+/// Analysis and lowering shared by the `typescript` hook and
+/// `swc_ecma_transforms_typescript`.
+#[doc(hidden)]
+pub mod typescript_strip {
+    pub use crate::typescript::{retain, semantic, shared, ts_enum};
+}
+
 #[derive(Default)]
 pub struct TraverseCtx {
     pub(crate) statement_injector: common::StmtInjectorStore,
@@ -39,9 +54,18 @@ pub struct TraverseCtx {
 pub fn transform_hook(options: Options) -> impl VisitMutHook<TraverseCtx> {
     let hook = HookBuilder::new(NoopHook);
 
-    let hook = hook.chain_optional(options.typescript.map(crate::typescript::hook));
+    // Decorators may use type information
     let hook = hook.chain_optional(options.decorator.map(crate::decorators::hook));
-    let hook = hook.chain_optional(options.jsx.map(crate::jsx::hook));
+    let hook = hook.chain_optional(
+        options
+            .typescript
+            .map(|typescript| crate::typescript::hook(typescript, options.unresolved_ctxt)),
+    );
+    let hook = hook.chain_optional(
+        options
+            .jsx
+            .map(|jsx| crate::jsx::hook(jsx, options.unresolved_ctxt)),
+    );
 
     let hook = hook.chain(crate::es2026::hook(options.env.es2026));
     let hook = hook.chain(crate::es2022::hook(options.env.es2022));
@@ -55,7 +79,11 @@ pub fn transform_hook(options: Options) -> impl VisitMutHook<TraverseCtx> {
         options.assumptions.ignore_function_length,
     ));
     let hook = hook.chain(crate::es2016::hook(options.env.es2016));
-    let hook = hook.chain(crate::es2015::hook(options.env.es2015));
+    let hook = hook.chain(crate::es2015::hook(
+        options.env.es2015,
+        options.unresolved_ctxt,
+        options.assumptions,
+    ));
     let hook = hook.chain_optional(crate::regexp::hook(options.env.regexp));
     let hook = hook.chain(crate::bugfix::hook(options.env.bugfix));
 
@@ -0,0 +1,308 @@
+use std::mem;
+
+use swc_common::{DUMMY_SP, Spanned, util::take::Take};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{ExprFactory, alias_ident_for, constructor::inject_after_super, is_literal};
+
+use super::TypeScriptPass;
+
+impl TypeScriptPass {
+    /// Removes TypeScript only class members and converts parameter properties.
+    ///
+    /// This is called before the class is processed by other transforms, and
+    /// it's idempotent.
+    pub(super) fn strip_class(&mut self, class: &mut Class) {
+        class.is_abstract = false;
+        class.implements.clear();
+
+        class.body.retain(|member| match member {
+            ClassMember::TsIndexSignature(..) => false,
+            ClassMember::Constructor(Constructor { body: None, .. }) => false,
+            ClassMember::Method(ClassMethod {
+                is_abstract,
+                function,
+                ..
+            })
+            | ClassMember::PrivateMethod(PrivateMethod {
+                is_abstract,
+                function,
+                ..
+            }) => !is_abstract && function.body.is_some(),
+            ClassMember::ClassProp(
+                ClassProp { declare: true, .. }
+                | ClassProp {
+                    is_abstract: true, ..
+                },
+            )
+            | ClassMember::AutoAccessor(AutoAccessor {
+                is_abstract: true, ..
+            }) => false,
+            _ => true,
+        });
+
+        let mut prop_list = Vec::new();
+        let mut init_list = Vec::new();
+
+        for member in class.body.iter_mut() {
+            match member {
+                ClassMember::Constructor(constructor) => {
+                    constructor.accessibility = None;
+                    convert_param_props(constructor, &mut prop_list, &mut init_list);
+                }
+                ClassMember::Method(method) => {
+                    method.accessibility = None;
+                    method.is_override = false;
+                    method.is_abstract = false;
+                    method.is_optional = false;
+                }
+                ClassMember::PrivateMethod(method) => {
+                    method.accessibility = None;
+                    method.is_abstract = false;
+                    method.is_optional = false;
+                    method.is_override = false;
+                }
+                ClassMember::ClassProp(prop) => {
+                    prop.declare = false;
+                    prop.readonly = false;
+                    prop.is_override = false;
+                    prop.is_optional = false;
+                    prop.is_abstract = false;
+                    prop.definite = false;
+                    prop.accessibility = None;
+                }
+                ClassMember::PrivateProp(prop) => {
+                    prop.readonly = false;
+                    prop.is_override = false;
+                    prop.is_optional = false;
+                    prop.definite = false;
+                    prop.accessibility = None;
+                }
+                ClassMember::AutoAccessor(accessor) => {
+                    accessor.type_ann = None;
+                    accessor.accessibility = None;
+                    accessor.definite = false;
+                    accessor.is_override = false;
+                    accessor.is_abstract = false;
+                }
+                _ => {}
+            }
+        }
+
+        if prop_list.is_empty() {
+            return;
+        }
+
+        if self.options.native_class_properties {
+            reorder_class_prop_decls(&mut class.body, prop_list, init_list);
+        } else {
+            self.reorder_class_prop_decls_and_inits(&mut class.body, prop_list, init_list);
+        }
+    }
+
+    #[allow(clippy::vec_box)]
+    fn reorder_class_prop_decls_and_inits(
+        &mut self,
+        class_member_list: &mut Vec<ClassMember>,
+        prop_list: Vec<Id>,
+        mut init_list: Vec<Box<Expr>>,
+    ) {
+        let mut constructor = None;
+        let mut cons_index = 0;
+        for (index, member) in class_member_list.iter_mut().enumerate() {
+            match member {
+                ClassMember::Constructor(..) => {
+                    let empty = EmptyStmt {
+                        span: member.span(),
+                    }
+                    .into();
+                    constructor = mem::replace(member, empty).constructor();
+                    cons_index = index;
+                }
+                ClassMember::ClassProp(ClassProp {
+                    key,
+                    value: value @ Some(..),
+                    is_static: false,
+                    span,
+                    ..
+                }) => {
+                    let key = match &mut *key {
+                        PropName::Computed(ComputedPropName { span, expr })
+                            if !is_literal(expr) =>
+                        {
+                            let ident = alias_ident_for(expr, "_key");
+
+                            self.push_var(ident.to_id());
+
+                            **expr = expr.take().make_assign_to(op!("="), ident.clone().into());
+
+                            PropName::Computed(ComputedPropName {
+                                span: *span,
+                                expr: ident.into(),
+                            })
+                        }
+                        _ => key.clone(),
+                    };
+
+                    let mut init = assign_value_to_this_prop(key, *value.take().unwrap());
+                    init.set_span(*span);
+
+                    init_list.push(init);
+                }
+                ClassMember::PrivateProp(PrivateProp {
+                    key,
+                    value: value @ Some(..),
+                    is_static: false,
+                    span,
+                    ..
+                }) => {
+                    let mut init =
+                        assign_value_to_this_private_prop(key.clone(), *value.take().unwrap());
+                    init.set_span(*span);
+                    init_list.push(init);
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(mut constructor) = constructor {
+            inject_after_super(&mut constructor, init_list);
+
+            if let Some(c) = class_member_list
+                .get_mut(cons_index)
+                .filter(|m| m.is_empty() && m.span() == constructor.span)
+            {
+                *c = constructor.into();
+            } else {
+                class_member_list.push(constructor.into());
+            }
+        }
+
+        prepend_prop_decls(class_member_list, prop_list);
+    }
+}
+
+/// Converts parameter properties of `constructor` to plain parameters, and
+/// collects the assignments to `this`.
+#[allow(clippy::vec_box)]
+fn convert_param_props(
+    constructor: &mut Constructor,
+    prop_list: &mut Vec<Id>,
+    init_list: &mut Vec<Box<Expr>>,
+) {
+    for param in constructor.params.iter_mut() {
+        let ParamOrTsParamProp::TsParamProp(ts_param_prop) = param else {
+            continue;
+        };
+
+        let TsParamProp {
+            span,
+            decorators,
+            param: ts_param,
+            ..
+        } = ts_param_prop;
+
+        let (pat, expr, id) = match ts_param {
+            TsParamPropParam::Ident(binding_ident) => {
+                let id = binding_ident.to_id();
+                let prop_name = PropName::Ident(IdentName::from(&*binding_ident));
+                let mut value_ident = Ident::from(&*binding_ident);
+                value_ident.optional = false;
+                let value = value_ident.into();
+
+                (
+                    binding_ident.clone().into(),
+                    assign_value_to_this_prop(prop_name, value),
+                    id,
+                )
+            }
+            TsParamPropParam::Assign(assign_pat) => {
+                let AssignPat { left, .. } = &assign_pat;
+
+                let Pat::Ident(binding_ident) = &**left else {
+                    unreachable!("destructuring pattern inside TsParameterProperty");
+                };
+
+                let id = binding_ident.id.to_id();
+                let prop_name = PropName::Ident(binding_ident.id.clone().into());
+                let mut value_ident = binding_ident.id.clone();
+                value_ident.optional = false;
+                let value = value_ident.into();
+
+                (
+                    assign_pat.clone().into(),
+                    assign_value_to_this_prop(prop_name, value),
+                    id,
+                )
+            }
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        };
+
+        prop_list.push(id);
+        init_list.push(expr);
+
+        *param = Param {
+            span: *span,
+            decorators: decorators.take(),
+            pat,
+        }
+        .into();
+    }
+}
+
+#[allow(clippy::vec_box)]
+fn reorder_class_prop_decls(
+    class_member_list: &mut Vec<ClassMember>,
+    prop_list: Vec<Id>,
+    init_list: Vec<Box<Expr>>,
+) {
+    if let Some(constructor) = class_member_list
+        .iter_mut()
+        .find_map(|m| m.as_mut_constructor())
+    {
+        inject_after_super(constructor, init_list);
+    }
+
+    prepend_prop_decls(class_member_list, prop_list);
+}
+
+fn prepend_prop_decls(class_member_list: &mut Vec<ClassMember>, prop_list: Vec<Id>) {
+    class_member_list.splice(
+        0..0,
+        prop_list
+            .into_iter()
+            .map(Ident::from)
+            .map(PropName::from)
+            .map(|key| ClassProp {
+                key,
+                ..Default::default()
+            })
+            .map(ClassMember::ClassProp),
+    );
+}
+
+/// this.prop = value
+fn assign_value_to_this_prop(prop_name: PropName, value: Expr) -> Box<Expr> {
+    let target = MemberExpr {
+        obj: ThisExpr { span: DUMMY_SP }.into(),
+        span: DUMMY_SP,
+        prop: prop_name.into(),
+    };
+
+    let expr = value.make_assign_to(op!("="), target.into());
+
+    Box::new(expr)
+}
+
+/// this.#prop = value
+fn assign_value_to_this_private_prop(private_name: PrivateName, value: Expr) -> Box<Expr> {
+    let target = MemberExpr {
+        obj: ThisExpr { span: DUMMY_SP }.into(),
+        span: DUMMY_SP,
+        prop: MemberProp::PrivateName(private_name),
+    };
+
+    let expr = value.make_assign_to(op!("="), target.into());
+
+    Box::new(expr)
+}
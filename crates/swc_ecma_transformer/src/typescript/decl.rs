@@ -0,0 +1,626 @@
+use std::iter;
+
+use swc_atoms::Atom;
+use swc_common::{DUMMY_SP, Span, Spanned, errors::HANDLER, source_map::PURE_SP};
+use swc_ecma_ast::*;
+use swc_ecma_utils::ExprFactory;
+
+use super::{
+    FoldedDecl, TypeScriptPass,
+    retain::should_retain_module_item,
+    shared::enum_member_id_atom,
+    ts_enum::{TsEnumRecordKey, TsEnumRecordValue},
+};
+
+impl TypeScriptPass {
+    /// Lowers `enum` and `namespace` declarations, and records the bindings
+    /// they may be merged with.
+    ///
+    /// `namespace_id` is the namespace containing the declaration.
+    pub(super) fn fold_decl(
+        &mut self,
+        node: Decl,
+        is_export: bool,
+        namespace_id: Option<&Id>,
+    ) -> FoldedDecl {
+        match node {
+            Decl::TsModule(ts_module) => {
+                let id = ts_module.id.to_id();
+
+                if self.decl_id_record.insert(id.clone()) {
+                    if is_export {
+                        if namespace_id.is_none() {
+                            self.export_var_list.push(id);
+                        }
+                    } else {
+                        self.push_var(id);
+                    }
+                }
+
+                FoldedDecl::Expr(self.transform_ts_module(*ts_module, is_export, namespace_id))
+            }
+            Decl::TsEnum(ts_enum) => {
+                let id = ts_enum.id.to_id();
+
+                let is_first = self.decl_id_record.insert(id);
+
+                self.transform_ts_enum(*ts_enum, is_first, is_export, namespace_id)
+            }
+            Decl::Class(mut class_decl) => {
+                self.decl_id_record.insert(class_decl.ident.to_id());
+                self.strip_class(&mut class_decl.class);
+                FoldedDecl::Decl(class_decl.into())
+            }
+            Decl::Fn(FnDecl { ref ident, .. }) => {
+                self.decl_id_record.insert(ident.to_id());
+                FoldedDecl::Decl(node)
+            }
+            decl => FoldedDecl::Decl(decl),
+        }
+    }
+
+    fn transform_ts_enum(
+        &mut self,
+        ts_enum: TsEnumDecl,
+        is_first: bool,
+        is_export: bool,
+        namespace_id: Option<&Id>,
+    ) -> FoldedDecl {
+        let TsEnumDecl {
+            span,
+            declare,
+            is_const,
+            id,
+            members,
+        } = ts_enum;
+
+        debug_assert!(!declare);
+
+        let ts_enum_safe_remove = !self.options.verbatim_module_syntax
+            && is_const
+            && !is_export
+            && !self.semantic.exported_binding.contains_key(&id.to_id());
+
+        let member_list: Vec<_> = members
+            .into_iter()
+            .map(|m| {
+                let span = m.span;
+                let name = enum_member_id_atom(&m.id);
+
+                let key = TsEnumRecordKey {
+                    enum_id: id.to_id(),
+                    member_name: name.clone(),
+                };
+
+                let value = self.semantic.enum_record.get(&key).unwrap().clone();
+
+                EnumMemberItem { span, name, value }
+            })
+            .filter(|m| !ts_enum_safe_remove || !m.is_const())
+            .collect();
+
+        if member_list.is_empty() && is_const {
+            return FoldedDecl::Empty;
+        }
+
+        let opaque = member_list
+            .iter()
+            .any(|item| matches!(item.value, TsEnumRecordValue::Opaque(..)));
+
+        let stmts = member_list
+            .into_iter()
+            .map(|item| item.build_assign(&id.to_id()));
+
+        let namespace_export = namespace_id.is_some() && is_export;
+        let iife = !is_first || namespace_export;
+
+        let body = if !iife {
+            let return_stmt: Stmt = ReturnStmt {
+                arg: Some(id.clone().into()),
+                ..Default::default()
+            }
+            .into();
+
+            let stmts = stmts.chain(iter::once(return_stmt)).collect();
+
+            BlockStmt {
+                stmts,
+                ..Default::default()
+            }
+        } else {
+            BlockStmt {
+                stmts: stmts.collect(),
+                ..Default::default()
+            }
+        };
+
+        let var_kind = if is_export || id.ctxt == self.options.top_level_ctxt {
+            VarDeclKind::Var
+        } else {
+            VarDeclKind::Let
+        };
+
+        let init_arg = 'init_arg: {
+            let init_arg = InitArg {
+                id: &id,
+                namespace_id: namespace_id.filter(|_| is_export),
+            };
+            if !is_first {
+                break 'init_arg init_arg.get();
+            }
+
+            if namespace_export {
+                break 'init_arg init_arg.or_assign_empty();
+            }
+
+            if is_export || var_kind == VarDeclKind::Let {
+                InitArg::empty()
+            } else {
+                init_arg.or_empty()
+            }
+        };
+
+        let expr = iife_function(vec![id.clone().into()], body).as_call(
+            if iife || opaque { DUMMY_SP } else { PURE_SP },
+            vec![init_arg],
+        );
+
+        if iife {
+            FoldedDecl::Expr(
+                ExprStmt {
+                    span,
+                    expr: expr.into(),
+                }
+                .into(),
+            )
+        } else {
+            let var_declarator = VarDeclarator {
+                span,
+                name: id.into(),
+                init: Some(expr.into()),
+                definite: false,
+            };
+
+            FoldedDecl::Decl(
+                VarDecl {
+                    span,
+                    kind: var_kind,
+                    decls: vec![var_declarator],
+                    ..Default::default()
+                }
+                .into(),
+            )
+        }
+    }
+
+    fn transform_ts_module(
+        &mut self,
+        ts_module: TsModuleDecl,
+        is_export: bool,
+        namespace_id: Option<&Id>,
+    ) -> Stmt {
+        debug_assert!(!ts_module.declare);
+        debug_assert!(!ts_module.global);
+
+        let TsModuleDecl {
+            span,
+            id: TsModuleName::Ident(module_ident),
+            body: Some(body),
+            ..
+        } = ts_module
+        else {
+            unreachable!();
+        };
+
+        let body = self.transform_ts_namespace_body(module_ident.to_id(), body);
+
+        let init_arg = InitArg {
+            id: &module_ident,
+            namespace_id: namespace_id.filter(|_| is_export),
+        }
+        .or_assign_empty();
+
+        let expr = iife_function(vec![module_ident.clone().into()], body)
+            .as_call(DUMMY_SP, vec![init_arg])
+            .into();
+
+        ExprStmt { span, expr }.into()
+    }
+
+    fn transform_ts_namespace_body(&mut self, id: Id, body: TsNamespaceBody) -> BlockStmt {
+        let TsNamespaceDecl {
+            span,
+            declare,
+            global,
+            id: local_name,
+            body,
+        } = match body {
+            TsNamespaceBody::TsModuleBlock(ts_module_block) => {
+                return self.transform_ts_module_block(id, ts_module_block);
+            }
+            TsNamespaceBody::TsNamespaceDecl(ts_namespace_decl) => ts_namespace_decl,
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        };
+
+        debug_assert!(!declare);
+        debug_assert!(!global);
+
+        let body = self.transform_ts_namespace_body(local_name.to_id(), *body);
+
+        let init_arg = InitArg {
+            id: &local_name,
+            namespace_id: Some(&id),
+        }
+        .or_assign_empty();
+
+        let expr = iife_function(vec![local_name.into()], body).as_call(DUMMY_SP, vec![init_arg]);
+
+        BlockStmt {
+            span,
+            stmts: vec![expr.into_stmt()],
+            ..Default::default()
+        }
+    }
+
+    /// Note:
+    /// All exported variable declarations are transformed into assignment to
+    /// the namespace. All references to the exported binding will be
+    /// replaced with qualified access to the namespace property while the
+    /// body is visited.
+    ///
+    /// Exported function and class will be treat as const exported which is in
+    /// line with how the TypeScript compiler handles exports.
+    ///
+    /// Input:
+    /// ```TypeScript
+    /// export const foo = init, { bar: baz = init } = init;
+    ///
+    /// export function a() {}
+    ///
+    /// export let b = init;
+    /// ```
+    ///
+    /// Output:
+    /// ```TypeScript
+    /// NS.foo = init, { bar: NS.baz = init } = init;
+    ///
+    /// function a() {}
+    /// NS.a = a;
+    ///
+    /// NS.b = init;
+    /// ```
+    fn transform_ts_module_block(
+        &mut self,
+        id: Id,
+        TsModuleBlock { span, mut body }: TsModuleBlock,
+    ) -> BlockStmt {
+        if !self.options.verbatim_module_syntax {
+            self.strip_namespace_module_items_with_semantic(&mut body);
+        }
+
+        body.retain(|item| should_retain_module_item(item, true));
+
+        self.var_lists.push(Vec::new());
+
+        let mut stmts = Vec::with_capacity(body.len());
+
+        for module_item in body {
+            match module_item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                    match self.fold_decl(decl, false, Some(&id)) {
+                        FoldedDecl::Decl(decl) => stmts.push(decl.into()),
+                        FoldedDecl::Expr(stmt) => stmts.push(stmt),
+                        FoldedDecl::Empty => {}
+                    }
+                }
+                ModuleItem::Stmt(stmt) => stmts.push(stmt),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl, span, ..
+                })) => match decl {
+                    Decl::Class(ClassDecl { ref ident, .. })
+                    | Decl::Fn(FnDecl { ref ident, .. }) => {
+                        let assign_stmt = assign_prop(&id, ident, span);
+                        if let FoldedDecl::Decl(decl) = self.fold_decl(decl, true, Some(&id)) {
+                            stmts.push(decl.into());
+                        }
+                        stmts.push(assign_stmt);
+                    }
+                    Decl::Var(var_decl) => {
+                        let mut exprs: Vec<Box<_>> = var_decl
+                            .decls
+                            .into_iter()
+                            .flat_map(
+                                |VarDeclarator {
+                                     span, name, init, ..
+                                 }| {
+                                    let right = init?;
+                                    let left = name.try_into().unwrap();
+
+                                    Some(
+                                        AssignExpr {
+                                            span,
+                                            left,
+                                            op: op!("="),
+                                            right,
+                                        }
+                                        .into(),
+                                    )
+                                },
+                            )
+                            .collect();
+
+                        if exprs.is_empty() {
+                            continue;
+                        }
+
+                        let expr = if exprs.len() == 1 {
+                            exprs.pop().unwrap()
+                        } else {
+                            SeqExpr {
+                                span: DUMMY_SP,
+                                exprs,
+                            }
+                            .into()
+                        };
+
+                        stmts.push(
+                            ExprStmt {
+                                span: var_decl.span,
+                                expr,
+                            }
+                            .into(),
+                        );
+                    }
+                    decl => match self.fold_decl(decl, true, Some(&id)) {
+                        FoldedDecl::Decl(decl) => stmts.push(decl.into()),
+                        FoldedDecl::Expr(stmt) => stmts.push(stmt),
+                        FoldedDecl::Empty => {}
+                    },
+                },
+                ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(decl)) => {
+                    match decl.module_ref {
+                        TsModuleRef::TsEntityName(ts_entity_name) => {
+                            let init = ts_entity_name_to_expr(ts_entity_name);
+
+                            // export impot foo = bar.baz
+                            let stmt = if decl.is_export {
+                                // Foo.foo = bar.baz
+                                let left = id.clone().make_member(decl.id.clone().into());
+                                let expr = init.make_assign_to(op!("="), left.into());
+
+                                ExprStmt {
+                                    span: decl.span,
+                                    expr: expr.into(),
+                                }
+                                .into()
+                            } else {
+                                // const foo = bar.baz
+                                let mut var_decl =
+                                    init.into_var_decl(VarDeclKind::Const, decl.id.clone().into());
+
+                                var_decl.span = decl.span;
+
+                                var_decl.into()
+                            };
+
+                            stmts.push(stmt);
+                        }
+                        TsModuleRef::TsExternalModuleRef(..) => {
+                            // TS1147
+                            if HANDLER.is_set() {
+                                HANDLER.with(|handler| {
+                                    handler
+                                    .struct_span_err(
+                                        decl.span,
+                                        r#"Import declarations in a namespace cannot reference a module."#,
+                                    )
+                                    .emit();
+                                });
+                            }
+                        }
+                        #[cfg(swc_ast_unknown)]
+                        _ => panic!("unable to access unknown nodes"),
+                    }
+                }
+                item => {
+                    if HANDLER.is_set() {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(
+                                    item.span(),
+                                    r#"ESM-style module declarations are not permitted in a namespace."#,
+                                )
+                                .emit();
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(var_decl) = self.take_var_decl() {
+            stmts.push(var_decl);
+        }
+
+        BlockStmt {
+            span,
+            stmts,
+            ..Default::default()
+        }
+    }
+}
+
+struct InitArg<'a> {
+    id: &'a Ident,
+    namespace_id: Option<&'a Id>,
+}
+
+impl InitArg<'_> {
+    // {}
+    fn empty() -> ExprOrSpread {
+        ExprOrSpread {
+            spread: None,
+            expr: ObjectLit::default().into(),
+        }
+    }
+
+    // N
+    fn get(&self) -> ExprOrSpread {
+        self.namespace_id
+            .cloned()
+            .map_or_else(
+                || -> Expr { self.id.clone().into() },
+                |namespace_id| namespace_id.make_member(self.id.clone().into()).into(),
+            )
+            .into()
+    }
+
+    // N || {}
+    fn or_empty(&self) -> ExprOrSpread {
+        let expr = self.namespace_id.cloned().map_or_else(
+            || -> Expr { self.id.clone().into() },
+            |namespace_id| namespace_id.make_member(self.id.clone().into()).into(),
+        );
+
+        let bin = BinExpr {
+            op: op!("||"),
+            left: expr.into(),
+            right: ObjectLit::default().into(),
+            ..Default::default()
+        };
+
+        ExprOrSpread {
+            spread: None,
+            expr: bin.into(),
+        }
+    }
+
+    // N || (N = {})
+    fn or_assign_empty(&self) -> ExprOrSpread {
+        let expr = self.namespace_id.cloned().map_or_else(
+            || -> Expr { self.id.clone().into() },
+            |namespace_id| namespace_id.make_member(self.id.clone().into()).into(),
+        );
+
+        let assign = self.namespace_id.cloned().map_or_else(
+            || ObjectLit::default().make_assign_to(op!("="), self.id.clone().into()),
+            |namespace_id| {
+                ObjectLit::default().make_assign_to(
+                    op!("="),
+                    namespace_id.make_member(self.id.clone().into()).into(),
+                )
+            },
+        );
+
+        let bin = BinExpr {
+            op: op!("||"),
+            left: expr.into(),
+            right: assign.into(),
+            ..Default::default()
+        };
+
+        ExprOrSpread {
+            spread: None,
+            expr: bin.into(),
+        }
+    }
+}
+
+struct EnumMemberItem {
+    span: Span,
+    name: Atom,
+    value: TsEnumRecordValue,
+}
+
+impl EnumMemberItem {
+    fn is_const(&self) -> bool {
+        self.value.is_const()
+    }
+
+    fn build_assign(self, enum_id: &Id) -> Stmt {
+        let is_string = self.value.is_string();
+        let value: Expr = self.value.into();
+
+        let inner_assign = value.make_assign_to(
+            op!("="),
+            Ident::from(enum_id.clone())
+                .computed_member(self.name.clone())
+                .into(),
+        );
+
+        let outer_assign = if is_string {
+            inner_assign
+        } else {
+            let value: Expr = self.name.clone().into();
+
+            value.make_assign_to(
+                op!("="),
+                Ident::from(enum_id.clone())
+                    .computed_member(inner_assign)
+                    .into(),
+            )
+        };
+
+        ExprStmt {
+            span: self.span,
+            expr: outer_assign.into(),
+        }
+        .into()
+    }
+}
+
+trait ModuleId {
+    fn to_id(&self) -> Id;
+}
+
+impl ModuleId for TsModuleName {
+    fn to_id(&self) -> Id {
+        self.as_ident()
+            .expect("Only ambient modules can use quoted names.")
+            .to_id()
+    }
+}
+
+fn iife_function(params: Vec<Param>, body: BlockStmt) -> Function {
+    Function {
+        params,
+        span: DUMMY_SP,
+        body: Some(body),
+        ..Default::default()
+    }
+}
+
+// Foo.x = x;
+fn assign_prop(id: &Id, prop: &Ident, span: Span) -> Stmt {
+    // The value is a generated reference to the local binding, so it must not be
+    // rewritten to the namespace member.
+    let value = Ident {
+        span: DUMMY_SP,
+        ..prop.clone()
+    };
+    let expr = value.make_assign_to(op!("="), id.clone().make_member(prop.clone().into()).into());
+
+    ExprStmt {
+        span,
+        expr: expr.into(),
+    }
+    .into()
+}
+
+pub(super) fn ts_entity_name_to_expr(n: TsEntityName) -> Expr {
+    match n {
+        TsEntityName::Ident(i) => i.into(),
+        TsEntityName::TsQualifiedName(q) => {
+            let TsQualifiedName { span, left, right } = *q;
+
+            MemberExpr {
+                span,
+                obj: Box::new(ts_entity_name_to_expr(left)),
+                prop: MemberProp::Ident(right),
+            }
+            .into()
+        }
+        #[cfg(swc_ast_unknown)]
+        _ => panic!("unable to access unknown nodes"),
+    }
+}
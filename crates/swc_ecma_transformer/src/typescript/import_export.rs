@@ -0,0 +1,360 @@
+use swc_common::{DUMMY_SP, errors::HANDLER, util::take::Take};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{
+    ExprFactory, StmtLikeInjector, member_expr, private_ident, quote_ident, quote_str,
+};
+
+use super::{
+    ImportsNotUsedAsValues, TsImportExportAssignConfig, TypeScriptPass,
+    decl::ts_entity_name_to_expr,
+};
+
+impl TypeScriptPass {
+    pub(super) fn strip_module_items_with_semantic(&self, items: &mut Vec<ModuleItem>) {
+        items.retain_mut(|module_item| match module_item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                specifiers,
+                type_only: false,
+                ..
+            })) if !specifiers.is_empty() => {
+                // Match tsc behavior: keep empty original imports, but strip specifiers by
+                // usage.
+                specifiers.retain(|import_specifier| match import_specifier {
+                    ImportSpecifier::Named(named) => {
+                        if named.is_type_only {
+                            return false;
+                        }
+
+                        let id = named.local.to_id();
+
+                        if self.semantic.has_value(&id) {
+                            return false;
+                        }
+
+                        self.semantic.has_usage(&id)
+                    }
+                    ImportSpecifier::Default(default) => {
+                        let id = default.local.to_id();
+
+                        if self.semantic.has_value(&id) {
+                            return false;
+                        }
+
+                        self.semantic.has_usage(&id)
+                    }
+                    ImportSpecifier::Namespace(namespace) => {
+                        let id = namespace.local.to_id();
+
+                        if self.semantic.has_value(&id) {
+                            return false;
+                        }
+
+                        self.semantic.has_usage(&id)
+                    }
+                    #[cfg(swc_ast_unknown)]
+                    _ => panic!("unable to access unknown nodes"),
+                });
+
+                self.options.import_not_used_as_values == ImportsNotUsedAsValues::Preserve
+                    || !specifiers.is_empty()
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                specifiers,
+                src,
+                type_only: false,
+                ..
+            })) => {
+                specifiers.retain(|export_specifier| match export_specifier {
+                    ExportSpecifier::Namespace(..) | ExportSpecifier::Default(..) => true,
+                    ExportSpecifier::Named(ExportNamedSpecifier {
+                        orig: ModuleExportName::Ident(ident),
+                        is_type_only: false,
+                        ..
+                    }) if src.is_none() => !self.semantic.has_pure_type(&ident.to_id()),
+                    ExportSpecifier::Named(ExportNamedSpecifier { is_type_only, .. }) => {
+                        !is_type_only
+                    }
+                    #[cfg(swc_ast_unknown)]
+                    _ => panic!("unable to access unknown nodes"),
+                });
+
+                !specifiers.is_empty()
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport { type_only, .. })) => {
+                !*type_only
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                expr,
+                ..
+            })) => expr
+                .as_ident()
+                .map(|ident| !self.semantic.has_pure_type(&ident.to_id()))
+                .unwrap_or(true),
+            ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(ts_import_equals_decl)) => {
+                if ts_import_equals_decl.is_type_only {
+                    return false;
+                }
+
+                if ts_import_equals_decl.is_export {
+                    return true;
+                }
+
+                self.semantic.has_usage(&ts_import_equals_decl.id.to_id())
+            }
+            ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(ts_module))) if ts_module.body.is_some() => {
+                if let Some(body) = &mut ts_module.body {
+                    self.strip_namespace_body_with_semantic(body);
+                }
+
+                true
+            }
+            _ => true,
+        });
+    }
+
+    fn strip_namespace_body_with_semantic(&self, body: &mut TsNamespaceBody) {
+        match body {
+            TsNamespaceBody::TsModuleBlock(block) => {
+                self.strip_namespace_module_items_with_semantic(&mut block.body);
+
+                for module_item in &mut block.body {
+                    if let ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(ts_module))) = module_item {
+                        if let Some(body) = &mut ts_module.body {
+                            self.strip_namespace_body_with_semantic(body);
+                        }
+                    }
+                }
+            }
+            TsNamespaceBody::TsNamespaceDecl(namespace_decl) => {
+                self.strip_namespace_body_with_semantic(&mut namespace_decl.body);
+            }
+            #[cfg(swc_ast_unknown)]
+            _ => panic!("unable to access unknown nodes"),
+        }
+    }
+
+    pub(super) fn strip_namespace_module_items_with_semantic(&self, items: &mut Vec<ModuleItem>) {
+        items.retain(|module_item| match module_item {
+            ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(ts_import_equals_decl)) => {
+                if ts_import_equals_decl.is_type_only {
+                    return false;
+                }
+
+                ts_import_equals_decl.is_export
+                    || self
+                        .semantic
+                        .has_namespace_import_equals_usage(ts_import_equals_decl.span)
+            }
+            _ => true,
+        });
+    }
+
+    pub(super) fn transform_import_export_assign(&mut self, node: &mut Module) {
+        let mut should_inject = false;
+        let create_require = private_ident!("_createRequire");
+        let require = private_ident!("__require");
+
+        // NOTE: This is not correct!
+        // However, all unresolved_span are used in TsImportExportAssignConfig::Classic
+        // which is deprecated and not used in real world.
+        let unresolved_ctxt = self.unresolved_ctxt;
+        let cjs_require = quote_ident!(unresolved_ctxt, "require");
+        let cjs_exports = quote_ident!(unresolved_ctxt, "exports");
+
+        let mut cjs_export_assign = None;
+
+        for module_item in &mut node.body {
+            match module_item {
+                ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(decl)) if !decl.is_type_only => {
+                    debug_assert_ne!(
+                        decl.id.ctxt, self.unresolved_ctxt,
+                        "TsImportEquals has top-level context and it should not be identical to \
+                         the unresolved mark"
+                    );
+                    debug_assert_eq!(decl.id.ctxt, self.options.top_level_ctxt);
+
+                    match &mut decl.module_ref {
+                        // import foo = bar.baz
+                        TsModuleRef::TsEntityName(ts_entity_name) => {
+                            let init = ts_entity_name_to_expr(ts_entity_name.clone());
+
+                            let mut var_decl =
+                                init.into_var_decl(VarDeclKind::Const, decl.id.take().into());
+
+                            *module_item = if decl.is_export {
+                                ExportDecl {
+                                    span: decl.span,
+                                    decl: var_decl.into(),
+                                }
+                                .into()
+                            } else {
+                                var_decl.span = decl.span;
+                                var_decl.into()
+                            };
+                        }
+                        // import foo = require("foo")
+                        TsModuleRef::TsExternalModuleRef(TsExternalModuleRef { expr, .. }) => {
+                            match self.options.import_export_assign_config {
+                                TsImportExportAssignConfig::Classic => {
+                                    // require("foo");
+                                    let mut init = cjs_require
+                                        .clone()
+                                        .as_call(DUMMY_SP, vec![expr.take().as_arg()]);
+
+                                    // exports.foo = require("foo");
+                                    if decl.is_export {
+                                        init = init.make_assign_to(
+                                            op!("="),
+                                            cjs_exports
+                                                .clone()
+                                                .make_member(decl.id.clone().into())
+                                                .into(),
+                                        )
+                                    }
+
+                                    // const foo = require("foo");
+                                    // const foo = exports.foo = require("foo");
+                                    let mut var_decl = init
+                                        .into_var_decl(VarDeclKind::Const, decl.id.take().into());
+                                    var_decl.span = decl.span;
+
+                                    *module_item = var_decl.into();
+                                }
+                                TsImportExportAssignConfig::Preserve => {}
+                                TsImportExportAssignConfig::NodeNext => {
+                                    should_inject = true;
+
+                                    let mut var_decl = require
+                                        .clone()
+                                        .as_call(DUMMY_SP, vec![expr.take().as_arg()])
+                                        .into_var_decl(VarDeclKind::Const, decl.id.take().into());
+
+                                    *module_item = if decl.is_export {
+                                        ExportDecl {
+                                            span: decl.span,
+                                            decl: var_decl.into(),
+                                        }
+                                        .into()
+                                    } else {
+                                        var_decl.span = decl.span;
+                                        var_decl.into()
+                                    };
+                                }
+                                TsImportExportAssignConfig::EsNext => {
+                                    // TS1202
+                                    if HANDLER.is_set() {
+                                        HANDLER.with(|handler| {
+                                            handler.struct_span_err(
+                                                decl.span,
+                                                r#"Import assignment cannot be used when targeting ECMAScript modules. Consider using `import * as ns from "mod"`, `import {a} from "mod"`, `import d from "mod"`, or another module format instead."#,
+                                            )
+                                            .emit();
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                        #[cfg(swc_ast_unknown)]
+                        _ => panic!("unable to access unknown nodes"),
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(..)) => {
+                    let ts_export_assign = module_item
+                        .take()
+                        .module_decl()
+                        .unwrap()
+                        .ts_export_assignment()
+                        .unwrap();
+
+                    cjs_export_assign.get_or_insert(ts_export_assign);
+                }
+                _ => {}
+            }
+        }
+
+        if should_inject {
+            node.body.prepend_stmts([
+                // import { createRequire } from "module";
+                ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers: vec![
+                        ImportNamedSpecifier {
+                            span: DUMMY_SP,
+                            local: create_require.clone(),
+                            imported: Some(quote_ident!("createRequire").into()),
+                            is_type_only: false,
+                        }
+                        .into(),
+                    ],
+                    src: Box::new(quote_str!("module")),
+                    type_only: false,
+                    with: None,
+                    phase: Default::default(),
+                }
+                .into(),
+                // const __require = _createRequire(import.meta.url);
+                create_require
+                    .as_call(
+                        DUMMY_SP,
+                        vec![
+                            MetaPropExpr {
+                                span: DUMMY_SP,
+                                kind: MetaPropKind::ImportMeta,
+                            }
+                            .make_member(quote_ident!("url"))
+                            .as_arg(),
+                        ],
+                    )
+                    .into_var_decl(VarDeclKind::Const, require.clone().into())
+                    .into(),
+            ]);
+        }
+
+        if let Some(cjs_export_assign) = cjs_export_assign {
+            match self.options.import_export_assign_config {
+                TsImportExportAssignConfig::Classic => {
+                    let TsExportAssignment { expr, span } = cjs_export_assign;
+
+                    let stmt = ExprStmt {
+                        span,
+                        expr: Box::new(
+                            expr.make_assign_to(
+                                op!("="),
+                                member_expr!(unresolved_ctxt, Default::default(), module.exports)
+                                    .into(),
+                            ),
+                        ),
+                    }
+                    .into();
+
+                    if let Some(item) = node
+                        .body
+                        .last_mut()
+                        .and_then(ModuleItem::as_mut_stmt)
+                        .filter(|stmt| stmt.is_empty())
+                    {
+                        *item = stmt;
+                    } else {
+                        node.body.push(stmt.into());
+                    }
+                }
+                TsImportExportAssignConfig::Preserve => {
+                    node.body.push(cjs_export_assign.into());
+                }
+                TsImportExportAssignConfig::NodeNext | TsImportExportAssignConfig::EsNext => {
+                    // TS1203
+                    if HANDLER.is_set() {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(
+                                    cjs_export_assign.span,
+                                    r#"Export assignment cannot be used when targeting ECMAScript modules. Consider using `export default` or another module format instead."#,
+                                )
+                                .emit()
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,17 +1,769 @@
+//! TypeScript: Type stripping
+//!
+//! This plugin removes TypeScript specific syntax, so the output can be
+//! processed by the ES transforms in the same traversal.
+//!
+//! - Type annotations, type-only declarations and type-only imports/exports
+//!   are removed.
+//! - `enum` and `namespace` declarations are lowered to IIFEs.
+//! - Constructor parameter properties are converted to assignments to `this`.
+//! - `import foo = require("foo")` and `export = foo` are rewritten according
+//!   to [TsImportExportAssignConfig].
+//!
+//! ## Example
+//!
+//! Input:
+//! ```ts
+//! enum Direction {
+//!     Up = 1,
+//!     Down,
+//! }
+//!
+//! class Foo {
+//!     constructor(public x: number) {}
+//! }
+//! ```
+//!
+//! Output:
+//! ```js
+//! var Direction = /*#__PURE__*/ function(Direction) {
+//!     Direction[Direction["Up"] = 1] = "Up";
+//!     Direction[Direction["Down"] = 2] = "Down";
+//!     return Direction;
+//! }(Direction || {});
+//!
+//! class Foo {
+//!     x;
+//!     constructor(x) {
+//!         this.x = x;
+//!     }
+//! }
+//! ```
+//!
+//! ## Implementation
+//!
+//! Implementation based on `swc_ecma_transforms_typescript`.
+//!
+//! The semantic information (usage of imports, values of enum members and
+//! bindings exported from namespaces) is collected when entering the program.
+//! Declarations are lowered when their statement list is entered, so the
+//! generated code is visited by the ES transforms.
+
+use rustc_hash::FxHashSet;
+use swc_common::{DUMMY_SP, Span, Spanned, SyntaxContext, util::take::Take};
+use swc_ecma_ast::*;
 use swc_ecma_hooks::VisitMutHook;
+use swc_ecma_utils::{ExprFactory, QueryRef, RefRewriter};
 
+use self::{
+    retain::{should_retain_module_item, should_retain_stmt},
+    semantic::{SemanticInfo, analyze_program},
+    ts_enum::TsEnumRecordKey,
+};
 use crate::TraverseCtx;
 
+mod class;
+mod decl;
+mod import_export;
+pub mod retain;
+pub mod semantic;
+pub mod shared;
+pub mod ts_enum;
+
 #[derive(Debug, Default)]
 #[non_exhaustive]
-pub struct TypescriptOptions {}
+pub struct TypescriptOptions {
+    /// Syntax context of top-level bindings, as applied by the resolver.
+    pub top_level_ctxt: SyntaxContext,
+
+    /// https://www.typescriptlang.org/tsconfig#verbatimModuleSyntax
+    pub verbatim_module_syntax: bool,
+
+    /// Native class properties support
+    pub native_class_properties: bool,
+
+    /// https://www.typescriptlang.org/tsconfig/#importsNotUsedAsValues
+    pub import_not_used_as_values: ImportsNotUsedAsValues,
+
+    /// Don't create `export {}`.
+    /// By default, strip creates `export {}` for modules to preserve module
+    /// context.
+    pub no_empty_export: bool,
+
+    pub import_export_assign_config: TsImportExportAssignConfig,
+
+    /// Disables an optimization that inlines TS enum member values
+    /// within the same module that assumes the enum member values
+    /// are never modified.
+    pub ts_enum_is_mutable: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TsImportExportAssignConfig {
+    ///  - Rewrite `import foo = require("foo")` to `var foo = require("foo")`
+    ///  - Rewrite `export =` to `module.exports = `
+    #[default]
+    Classic,
+
+    /// preserve for CJS/AMD/UMD
+    Preserve,
+
+    /// Rewrite `import foo = require("foo")` to
+    /// ```javascript
+    /// import { createRequire as _createRequire } from "module";
+    /// const __require = _createRequire(import.meta.url);
+    /// const foo = __require("foo");
+    /// ```
+    ///
+    /// Report error for `export =`
+    NodeNext,
+
+    /// Both `import =` and `export =` are disabled.
+    /// An error will be reported if an import/export assignment is found.
+    EsNext,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ImportsNotUsedAsValues {
+    #[default]
+    Remove,
+    Preserve,
+}
 
-pub fn hook(options: TypescriptOptions) -> impl VisitMutHook<TraverseCtx> {
-    TypeScriptPass { options }
+pub fn hook(
+    options: TypescriptOptions,
+    unresolved_ctxt: SyntaxContext,
+) -> impl VisitMutHook<TraverseCtx> {
+    TypeScriptPass {
+        options,
+        unresolved_ctxt,
+        semantic: Default::default(),
+        last_module_span: None,
+        ref_rewriter: None,
+        decl_id_record: Default::default(),
+        var_lists: Default::default(),
+        export_var_list: Default::default(),
+        lhs_exprs: Default::default(),
+        binding_names: Default::default(),
+    }
 }
 
 struct TypeScriptPass {
     options: TypescriptOptions,
+    unresolved_ctxt: SyntaxContext,
+
+    semantic: SemanticInfo,
+
+    /// Span of the last ES module declaration, used to restore the module
+    /// context if all of them are removed.
+    last_module_span: Option<Span>,
+
+    ref_rewriter: Option<RefRewriter<ExportQuery>>,
+
+    decl_id_record: FxHashSet<Id>,
+
+    /// Bindings of lowered namespaces, which are declared at the end of each
+    /// statement list. Innermost list last.
+    var_lists: Vec<Vec<Id>>,
+    export_var_list: Vec<Id>,
+
+    /// Expressions in assignment target position, where enum members must not
+    /// be inlined.
+    lhs_exprs: FxHashSet<*const Expr>,
+
+    /// Binding patterns of variable declarators and parameters, which are not
+    /// rewritten to namespace members.
+    binding_names: Vec<*const Pat>,
 }
 
-impl VisitMutHook<TraverseCtx> for TypeScriptPass {}
+impl TypeScriptPass {
+    fn can_rewrite_ref(&self) -> bool {
+        self.binding_names.is_empty()
+    }
+
+    fn push_var(&mut self, id: Id) {
+        if let Some(var_list) = self.var_lists.last_mut() {
+            var_list.push(id);
+        }
+    }
+
+    fn take_var_decl(&mut self) -> Option<Stmt> {
+        let var_list = self.var_lists.pop()?;
+
+        if var_list.is_empty() {
+            return None;
+        }
+
+        Some(
+            VarDecl {
+                decls: var_list.into_iter().map(id_to_var_declarator).collect(),
+                ..Default::default()
+            }
+            .into(),
+        )
+    }
+
+    /// Marks `expr` and the expressions it wraps as assignment targets.
+    fn mark_lhs(&mut self, mut expr: &Expr) {
+        loop {
+            self.lhs_exprs.insert(expr);
+
+            match expr {
+                Expr::Paren(ParenExpr { expr: inner, .. })
+                | Expr::TsAs(TsAsExpr { expr: inner, .. })
+                | Expr::TsNonNull(TsNonNullExpr { expr: inner, .. })
+                | Expr::TsTypeAssertion(TsTypeAssertion { expr: inner, .. })
+                | Expr::TsConstAssertion(TsConstAssertion { expr: inner, .. })
+                | Expr::TsInstantiation(TsInstantiation { expr: inner, .. })
+                | Expr::TsSatisfies(TsSatisfiesExpr { expr: inner, .. }) => expr = inner,
+                _ => break,
+            }
+        }
+    }
+
+    fn inline_enum_member(&self, node: &mut Expr) {
+        let Expr::Member(MemberExpr { span, obj, prop }) = node else {
+            return;
+        };
+
+        // Member references generated inside enum bodies are kept as is.
+        if span.is_dummy() {
+            return;
+        }
+
+        let Expr::Ident(obj) = &**obj else {
+            return;
+        };
+        let enum_id = obj.to_id();
+
+        if self.options.ts_enum_is_mutable && !self.semantic.const_enum.contains(&enum_id) {
+            return;
+        }
+
+        let Some(member_name) = get_member_key(prop) else {
+            return;
+        };
+
+        let key = TsEnumRecordKey {
+            enum_id,
+            member_name,
+        };
+
+        if let Some(value) = self.semantic.enum_record.get(&key) {
+            if value.is_const() {
+                *node = value.clone().into();
+            }
+        }
+    }
+
+    /// Removes declarations without runtime semantics and lowers the
+    /// remaining TypeScript declarations of a statement list.
+    fn strip_stmts(&mut self, stmts: &mut Vec<Stmt>) {
+        stmts.retain(|stmt| stmt.is_empty() || should_retain_stmt(stmt));
+
+        for stmt in stmts.iter_mut() {
+            let Stmt::Decl(decl) = stmt else {
+                continue;
+            };
+
+            match self.fold_decl(decl.take(), false, None) {
+                FoldedDecl::Decl(folded) => *decl = folded,
+                FoldedDecl::Expr(folded) => *stmt = folded,
+                FoldedDecl::Empty => {
+                    stmt.take();
+                }
+            }
+        }
+
+        stmts.retain(|stmt| !is_folded_empty(stmt));
+    }
+
+    fn strip_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        items.retain(|item| should_retain_module_item(item, false));
+
+        for item in items.iter_mut() {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                    match self.fold_decl(decl.take(), false, None) {
+                        FoldedDecl::Decl(folded) => *decl = folded,
+                        FoldedDecl::Expr(folded) => *item = folded.into(),
+                        FoldedDecl::Empty => *item = Stmt::dummy().into(),
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                    match self.fold_decl(decl.take(), true, None) {
+                        FoldedDecl::Decl(folded) => *decl = folded,
+                        FoldedDecl::Expr(folded) => *item = folded.into(),
+                        FoldedDecl::Empty => *item = Stmt::dummy().into(),
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_default_decl)) => {
+                    match &mut export_default_decl.decl {
+                        DefaultDecl::Class(ClassExpr { ident, class }) => {
+                            if let Some(ident) = ident {
+                                self.decl_id_record.insert(ident.to_id());
+                            }
+                            self.strip_class(class);
+                        }
+                        DefaultDecl::Fn(FnExpr {
+                            ident: Some(ident), ..
+                        }) => {
+                            self.decl_id_record.insert(ident.to_id());
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        items.retain(|item| !item.as_stmt().is_some_and(is_folded_empty));
+    }
+}
+
+impl VisitMutHook<TraverseCtx> for TypeScriptPass {
+    fn enter_program(&mut self, node: &mut Program, _: &mut TraverseCtx) {
+        if !self.options.no_empty_export {
+            self.last_module_span = node.as_module().and_then(|module| {
+                module
+                    .body
+                    .iter()
+                    .rev()
+                    .find(|item| is_es_module_decl(item))
+                    .map(Spanned::span)
+            });
+        }
+
+        self.semantic = analyze_program(node, self.unresolved_ctxt, Default::default());
+
+        if !self.semantic.exported_binding.is_empty() {
+            self.ref_rewriter = Some(RefRewriter {
+                query: ExportQuery {
+                    export_name: self.semantic.exported_binding.clone(),
+                },
+            });
+        }
+    }
+
+    fn exit_program(&mut self, node: &mut Program, _: &mut TraverseCtx) {
+        let Some(span) = self.last_module_span.take() else {
+            return;
+        };
+
+        let Program::Module(module) = node else {
+            return;
+        };
+
+        if module.body.iter().any(is_es_module_decl) {
+            return;
+        }
+
+        module.body.push(
+            NamedExport {
+                span,
+                ..NamedExport::dummy()
+            }
+            .into(),
+        );
+    }
+
+    fn enter_module(&mut self, node: &mut Module, _: &mut TraverseCtx) {
+        if !self.options.verbatim_module_syntax {
+            self.strip_module_items_with_semantic(&mut node.body);
+        }
+
+        self.transform_import_export_assign(node);
+    }
+
+    fn exit_module(&mut self, node: &mut Module, _: &mut TraverseCtx) {
+        if self.export_var_list.is_empty() {
+            return;
+        }
+
+        let decls = self
+            .export_var_list
+            .take()
+            .into_iter()
+            .map(id_to_var_declarator)
+            .collect();
+
+        node.body.push(
+            ExportDecl {
+                span: DUMMY_SP,
+                decl: VarDecl {
+                    decls,
+                    ..Default::default()
+                }
+                .into(),
+            }
+            .into(),
+        );
+    }
+
+    fn enter_module_items(&mut self, node: &mut Vec<ModuleItem>, _: &mut TraverseCtx) {
+        self.var_lists.push(Vec::new());
+        self.strip_module_items(node);
+    }
+
+    fn exit_module_items(&mut self, node: &mut Vec<ModuleItem>, _: &mut TraverseCtx) {
+        node.retain(|item| !item.as_stmt().is_some_and(is_folded_empty));
+
+        if let Some(var_decl) = self.take_var_decl() {
+            node.push(var_decl.into());
+        }
+    }
+
+    fn enter_stmts(&mut self, node: &mut Vec<Stmt>, _: &mut TraverseCtx) {
+        self.var_lists.push(Vec::new());
+        self.strip_stmts(node);
+    }
+
+    fn exit_stmts(&mut self, node: &mut Vec<Stmt>, _: &mut TraverseCtx) {
+        node.retain(|stmt| !is_folded_empty(stmt));
+
+        if let Some(var_decl) = self.take_var_decl() {
+            node.push(var_decl);
+        }
+    }
+
+    fn enter_block_stmt(&mut self, node: &mut BlockStmt, _: &mut TraverseCtx) {
+        // Overload signatures must be removed before the block is processed by
+        // other transforms.
+        node.stmts
+            .retain(|stmt| stmt.is_empty() || should_retain_stmt(stmt));
+    }
+
+    fn enter_stmt(&mut self, node: &mut Stmt, _: &mut TraverseCtx) {
+        if !should_retain_stmt(node) && !node.is_empty() {
+            node.take();
+            return;
+        }
+
+        // Declarations in statement lists are folded on entering the list, so
+        // only those in a single statement position (e.g. `if (x) enum A {}`)
+        // are left here.
+        if let Stmt::Decl(decl @ (Decl::TsEnum(..) | Decl::TsModule(..))) = node {
+            match self.fold_decl(decl.take(), false, None) {
+                FoldedDecl::Decl(folded) => *decl = folded,
+                FoldedDecl::Expr(folded) => *node = folded,
+                FoldedDecl::Empty => {
+                    node.take();
+                }
+            }
+        }
+    }
+
+    fn enter_class(&mut self, node: &mut Class, _: &mut TraverseCtx) {
+        self.strip_class(node);
+    }
+
+    fn enter_function(&mut self, node: &mut Function, _: &mut TraverseCtx) {
+        if node.params.first().is_some_and(|param| {
+            matches!(
+                &param.pat,
+                Pat::Ident(BindingIdent {
+                    id: Ident { sym, .. },
+                    ..
+                }) if &**sym == "this"
+            )
+        }) {
+            node.params.remove(0);
+        }
+    }
+
+    fn enter_setter_prop(&mut self, node: &mut SetterProp, _: &mut TraverseCtx) {
+        node.this_param = None;
+    }
+
+    fn enter_expr(&mut self, node: &mut Expr, _: &mut TraverseCtx) {
+        while let Expr::TsAs(TsAsExpr { expr, .. })
+        | Expr::TsNonNull(TsNonNullExpr { expr, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { expr, .. })
+        | Expr::TsConstAssertion(TsConstAssertion { expr, .. })
+        | Expr::TsInstantiation(TsInstantiation { expr, .. })
+        | Expr::TsSatisfies(TsSatisfiesExpr { expr, .. }) = node
+        {
+            *node = *expr.take();
+        }
+
+        match node {
+            Expr::Class(ClassExpr { class, .. }) => self.strip_class(class),
+            Expr::Update(UpdateExpr { arg, .. }) => self.mark_lhs(arg),
+            _ => {}
+        }
+
+        if !self.lhs_exprs.remove(&(node as *const Expr)) {
+            self.inline_enum_member(node);
+        }
+    }
+
+    fn exit_expr(&mut self, node: &mut Expr, _: &mut TraverseCtx) {
+        if !self.can_rewrite_ref() {
+            return;
+        }
+
+        // References created by the lowering of enums and namespaces have dummy
+        // spans, and they refer to the parameter of the IIFE.
+        if matches!(node, Expr::Ident(ident) if ident.span.is_dummy()) {
+            return;
+        }
+
+        if let Some(ref_rewriter) = &mut self.ref_rewriter {
+            ref_rewriter.exit_expr(node);
+        }
+    }
+
+    fn enter_simple_assign_target(&mut self, node: &mut SimpleAssignTarget, _: &mut TraverseCtx) {
+        while let SimpleAssignTarget::TsAs(TsAsExpr { expr, .. })
+        | SimpleAssignTarget::TsNonNull(TsNonNullExpr { expr, .. })
+        | SimpleAssignTarget::TsTypeAssertion(TsTypeAssertion { expr, .. })
+        | SimpleAssignTarget::TsInstantiation(TsInstantiation { expr, .. })
+        | SimpleAssignTarget::TsSatisfies(TsSatisfiesExpr { expr, .. }) = node
+        {
+            *node = expr.take().try_into().unwrap();
+        }
+    }
+
+    fn exit_simple_assign_target(&mut self, node: &mut SimpleAssignTarget, _: &mut TraverseCtx) {
+        if !self.can_rewrite_ref() {
+            return;
+        }
+
+        if let Some(ref_rewriter) = &mut self.ref_rewriter {
+            ref_rewriter.exit_simple_assign_target(node);
+        }
+    }
+
+    fn enter_var_declarator(&mut self, node: &mut VarDeclarator, _: &mut TraverseCtx) {
+        self.binding_names.push(&node.name);
+    }
+
+    fn exit_var_declarator(&mut self, node: &mut VarDeclarator, _: &mut TraverseCtx) {
+        // The name may be replaced by other transforms before it's visited.
+        if self.binding_names.last() == Some(&(&node.name as *const Pat)) {
+            self.binding_names.pop();
+        }
+    }
+
+    fn enter_param(&mut self, node: &mut Param, _: &mut TraverseCtx) {
+        self.binding_names.push(&node.pat);
+    }
+
+    fn exit_param(&mut self, node: &mut Param, _: &mut TraverseCtx) {
+        if self.binding_names.last() == Some(&(&node.pat as *const Pat)) {
+            self.binding_names.pop();
+        }
+    }
+
+    fn enter_pat(&mut self, node: &mut Pat, _: &mut TraverseCtx) {
+        match node {
+            Pat::Expr(expr) => self.mark_lhs(expr),
+            Pat::Array(ArrayPat { optional, .. }) | Pat::Object(ObjectPat { optional, .. }) => {
+                *optional = false
+            }
+            _ => {}
+        }
+    }
+
+    fn exit_pat(&mut self, node: &mut Pat, _: &mut TraverseCtx) {
+        if self.binding_names.last() == Some(&(node as *const Pat)) {
+            self.binding_names.pop();
+            return;
+        }
+
+        if !self.can_rewrite_ref() {
+            return;
+        }
+
+        if let Some(ref_rewriter) = &mut self.ref_rewriter {
+            ref_rewriter.exit_pat(node);
+        }
+    }
+
+    fn exit_prop(&mut self, node: &mut Prop, _: &mut TraverseCtx) {
+        if let Some(ref_rewriter) = &mut self.ref_rewriter {
+            ref_rewriter.exit_prop(node);
+        }
+    }
+
+    fn exit_object_pat_prop(&mut self, node: &mut ObjectPatProp, _: &mut TraverseCtx) {
+        if !self.can_rewrite_ref() {
+            return;
+        }
+
+        if let Some(ref_rewriter) = &mut self.ref_rewriter {
+            ref_rewriter.exit_object_pat_prop(node);
+        }
+
+        // The rewriter represents a default value as an assignment expression,
+        // but the hooks after this one expect an assignment pattern.
+        if let ObjectPatProp::KeyValue(KeyValuePatProp { value, .. }) = node {
+            if let Pat::Expr(expr) = &mut **value {
+                if let Expr::Assign(AssignExpr {
+                    span,
+                    op: op!("="),
+                    left: AssignTarget::Simple(left),
+                    right,
+                }) = &mut **expr
+                {
+                    **value = AssignPat {
+                        span: *span,
+                        left: Box::new(Pat::Expr(left.take().into())),
+                        right: right.take(),
+                    }
+                    .into();
+                }
+            }
+        }
+    }
+
+    fn exit_jsx_element_name(&mut self, node: &mut JSXElementName, _: &mut TraverseCtx) {
+        if let Some(ref_rewriter) = &mut self.ref_rewriter {
+            ref_rewriter.exit_jsx_element_name(node);
+        }
+    }
+
+    fn exit_jsx_object(&mut self, node: &mut JSXObject, _: &mut TraverseCtx) {
+        if let Some(ref_rewriter) = &mut self.ref_rewriter {
+            ref_rewriter.exit_jsx_object(node);
+        }
+    }
+
+    fn enter_ident(&mut self, node: &mut Ident, _: &mut TraverseCtx) {
+        node.optional = false;
+    }
+
+    fn enter_import_specifiers(&mut self, node: &mut Vec<ImportSpecifier>, _: &mut TraverseCtx) {
+        node.retain(|specifier| {
+            !matches!(
+                specifier,
+                ImportSpecifier::Named(ImportNamedSpecifier {
+                    is_type_only: true,
+                    ..
+                })
+            )
+        });
+    }
+
+    fn enter_export_specifiers(&mut self, node: &mut Vec<ExportSpecifier>, _: &mut TraverseCtx) {
+        node.retain(|specifier| {
+            !matches!(
+                specifier,
+                ExportSpecifier::Named(ExportNamedSpecifier {
+                    is_type_only: true,
+                    ..
+                })
+            )
+        });
+    }
+
+    fn enter_opt_ts_type(&mut self, node: &mut Option<Box<TsType>>, _: &mut TraverseCtx) {
+        *node = None;
+    }
+
+    fn enter_opt_ts_type_ann(&mut self, node: &mut Option<Box<TsTypeAnn>>, _: &mut TraverseCtx) {
+        *node = None;
+    }
+
+    fn enter_opt_ts_type_param_decl(
+        &mut self,
+        node: &mut Option<Box<TsTypeParamDecl>>,
+        _: &mut TraverseCtx,
+    ) {
+        *node = None;
+    }
+
+    fn enter_opt_ts_type_param_instantiation(
+        &mut self,
+        node: &mut Option<Box<TsTypeParamInstantiation>>,
+        _: &mut TraverseCtx,
+    ) {
+        *node = None;
+    }
+}
+
+enum FoldedDecl {
+    Empty,
+    Decl(Decl),
+    Expr(Stmt),
+}
+
+struct ExportQuery {
+    export_name: rustc_hash::FxHashMap<Id, Option<Id>>,
+}
+
+impl QueryRef for ExportQuery {
+    fn query_ref(&self, export_name: &Ident) -> Option<Box<Expr>> {
+        self.export_name
+            .get(&export_name.to_id())?
+            .clone()
+            .map(|namespace_id| namespace_id.make_member(export_name.clone().into()).into())
+    }
+
+    fn query_lhs(&self, ident: &Ident) -> Option<Box<Expr>> {
+        self.query_ref(ident)
+    }
+
+    fn query_jsx(&self, ident: &Ident) -> Option<JSXElementName> {
+        self.export_name
+            .get(&ident.to_id())?
+            .clone()
+            .map(|namespace_id| {
+                JSXMemberExpr {
+                    span: DUMMY_SP,
+                    obj: JSXObject::Ident(namespace_id.into()),
+                    prop: ident.clone().into(),
+                }
+                .into()
+            })
+    }
+}
+
+/// Statements removed while lowering declarations are replaced with empty
+/// statements with a dummy span.
+fn is_folded_empty(stmt: &Stmt) -> bool {
+    matches!(stmt, Stmt::Empty(EmptyStmt { span }) if span.is_dummy())
+}
+
+fn is_es_module_decl(item: &ModuleItem) -> bool {
+    matches!(
+        item,
+        ModuleItem::ModuleDecl(
+            ModuleDecl::Import(..)
+                | ModuleDecl::ExportDecl(..)
+                | ModuleDecl::ExportNamed(..)
+                | ModuleDecl::ExportDefaultDecl(..)
+                | ModuleDecl::ExportDefaultExpr(..)
+                | ModuleDecl::ExportAll(..),
+        )
+    )
+}
+
+fn id_to_var_declarator(id: Id) -> VarDeclarator {
+    VarDeclarator {
+        span: DUMMY_SP,
+        name: id.into(),
+        init: None,
+        definite: false,
+    }
+}
+
+fn get_member_key(prop: &MemberProp) -> Option<swc_atoms::Atom> {
+    match prop {
+        MemberProp::Ident(ident) => Some(ident.sym.clone()),
+        MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+            Expr::Lit(Lit::Str(Str { value, .. })) => Some(value.to_atom_lossy().into_owned()),
+            Expr::Tpl(Tpl { exprs, quasis, .. }) => match (exprs.len(), quasis.len()) {
+                (0, 1) => quasis[0]
+                    .cooked
+                    .as_ref()
+                    .map(|cooked| cooked.to_atom_lossy().into_owned())
+                    .or_else(|| Some(quasis[0].raw.clone())),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
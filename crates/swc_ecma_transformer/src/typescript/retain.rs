@@ -1,7 +1,7 @@
 use swc_ecma_ast::*;
 
 /// Returns true if a module item should survive TS type-stripping.
-pub(crate) fn should_retain_module_item(module_item: &ModuleItem, in_namespace: bool) -> bool {
+pub fn should_retain_module_item(module_item: &ModuleItem, in_namespace: bool) -> bool {
     match module_item {
         ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
             // Keep `export declare var` in namespace blocks for downstream transforms.
@@ -17,7 +17,7 @@ pub(crate) fn should_retain_module_item(module_item: &ModuleItem, in_namespace:
 }
 
 /// Returns true if a statement should survive TS type-stripping.
-pub(crate) fn should_retain_stmt(stmt: &Stmt) -> bool {
+pub fn should_retain_stmt(stmt: &Stmt) -> bool {
     match stmt {
         Stmt::Decl(decl) => should_retain_decl(decl),
         _ => stmt.is_concrete(),
@@ -25,7 +25,7 @@ pub(crate) fn should_retain_stmt(stmt: &Stmt) -> bool {
 }
 
 /// Returns true if a declaration has runtime semantics.
-pub(crate) fn should_retain_decl(decl: &Decl) -> bool {
+pub fn should_retain_decl(decl: &Decl) -> bool {
     if decl.is_declare() {
         return false;
     }
@@ -33,7 +33,7 @@ pub(crate) fn should_retain_decl(decl: &Decl) -> bool {
     decl.is_concrete()
 }
 
-pub(crate) trait IsConcrete {
+pub trait IsConcrete {
     fn is_concrete(&self) -> bool;
 }
 
@@ -1,17 +1,17 @@
 use rustc_hash::{FxHashMap, FxHashSet};
-use swc_common::{Mark, Span, SyntaxContext};
+use swc_common::{Span, SyntaxContext};
 use swc_ecma_ast::*;
 use swc_ecma_utils::{find_pat_ids, stack_size::maybe_grow_default};
 use swc_ecma_visit::{Visit, VisitWith, noop_visit_type};
 
-use crate::{
+use super::{
     retain::{IsConcrete, should_retain_decl},
     shared::{enum_member_id_atom, get_module_ident},
     ts_enum::{EnumValueComputer, TsEnumRecord, TsEnumRecordKey, TsEnumRecordValue},
 };
 
 #[derive(Debug, Default)]
-pub(crate) struct SemanticInfo {
+pub struct SemanticInfo {
     pub usage: FxHashSet<Id>,
     pub id_type: FxHashSet<Id>,
     pub id_value: FxHashSet<Id>,
@@ -43,13 +43,13 @@ impl SemanticInfo {
     }
 }
 
-pub(crate) fn analyze_program(
+pub fn analyze_program(
     program: &Program,
-    unresolved_mark: Mark,
+    unresolved_ctxt: SyntaxContext,
     seed_usage: FxHashSet<Id>,
 ) -> SemanticInfo {
     let mut analyzer = SemanticAnalyzer {
-        unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+        unresolved_ctxt,
         info: SemanticInfo {
             usage: seed_usage,
             ..Default::default()
@@ -17,15 +17,15 @@ fn atom_from_wtf8_atom(value: &Wtf8Atom) -> Atom {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub(crate) struct TsEnumRecordKey {
+pub struct TsEnumRecordKey {
     pub enum_id: Id,
     pub member_name: Atom,
 }
 
-pub(crate) type TsEnumRecord = FxHashMap<TsEnumRecordKey, TsEnumRecordValue>;
+pub type TsEnumRecord = FxHashMap<TsEnumRecordKey, TsEnumRecordValue>;
 
 #[derive(Debug, Clone)]
-pub(crate) enum TsEnumRecordValue {
+pub enum TsEnumRecordValue {
     String(Atom),
     Number(JsNumber),
     Opaque(Box<Expr>),
@@ -103,7 +103,7 @@ impl From<f64> for TsEnumRecordValue {
     }
 }
 
-pub(crate) struct EnumValueComputer<'a> {
+pub struct EnumValueComputer<'a> {
     pub enum_id: &'a Id,
     pub unresolved_ctxt: SyntaxContext,
     pub record: &'a TsEnumRecord,
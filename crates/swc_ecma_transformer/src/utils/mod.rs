@@ -4,6 +4,10 @@ use swc_atoms::Atom;
 use swc_common::{DUMMY_SP, Mark, SyntaxContext};
 use swc_ecma_ast::*;
 
+pub(crate) use self::stmt_vars::StmtVars;
+
+mod stmt_vars;
+
 pub(crate) fn normalize_module_export_name(module_export_name: &ModuleExportName) -> Cow<'_, Atom> {
     match module_export_name {
         ModuleExportName::Ident(Ident { sym: name, .. }) => Cow::Borrowed(name),
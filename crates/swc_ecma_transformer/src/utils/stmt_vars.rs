@@ -0,0 +1,112 @@
+use swc_ecma_ast::*;
+
+use crate::TraverseCtx;
+
+/// Variables to be declared right before the statement which contains the
+/// current node.
+///
+/// Unlike `VarDeclarationsStore`, which declares variables at the top of the
+/// statement list, the declaration is inserted before the item of the
+/// statement list which contains the node.
+#[derive(Default)]
+pub(crate) struct StmtVars {
+    /// One frame for each statement list.
+    frames: Vec<Frame>,
+}
+
+/// The state of a statement list.
+#[derive(Default)]
+struct Frame {
+    /// Depth of the current statement, relative to the statement list.
+    depth: usize,
+
+    /// Variables to be declared before the current item of the statement
+    /// list.
+    vars: Vec<VarDeclarator>,
+
+    /// The statement list is the expression body of an arrow function, which
+    /// is converted to a block by `StmtInjector`. Variables are declared in
+    /// the enclosing statement list instead, so the body can be converted
+    /// back to an expression.
+    transparent: bool,
+}
+
+impl StmtVars {
+    pub fn enter_stmts(&mut self, stmts: &[Stmt]) {
+        self.frames.push(Frame {
+            transparent: is_arrow_expr_body(stmts),
+            ..Default::default()
+        });
+    }
+
+    pub fn exit_stmts(&mut self) {
+        self.frames.pop();
+    }
+
+    pub fn enter_item(&mut self) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.depth += 1;
+        }
+    }
+
+    pub fn exit_stmt(&mut self, stmt: &Stmt, ctx: &mut TraverseCtx) {
+        if let Some(vars) = self.exit_item() {
+            ctx.statement_injector
+                .insert_before(stmt as *const Stmt, var_decl(vars));
+        }
+    }
+
+    pub fn exit_module_item(&mut self, item: &ModuleItem, ctx: &mut TraverseCtx) {
+        if let Some(vars) = self.exit_item() {
+            ctx.statement_injector
+                .insert_before_module_item(item as *const ModuleItem, var_decl(vars));
+        }
+    }
+
+    /// Declares `vars` before the current item of the innermost statement
+    /// list.
+    pub fn extend(&mut self, vars: impl IntoIterator<Item = VarDeclarator>) {
+        if let Some(frame) = self
+            .frames
+            .iter_mut()
+            .rev()
+            .find(|frame| !frame.transparent)
+        {
+            frame.vars.extend(vars);
+        }
+    }
+
+    /// Returns the variables to be declared before the current item, if the
+    /// item is in the statement list.
+    fn exit_item(&mut self) -> Option<Vec<VarDeclarator>> {
+        let frame = self.frames.last_mut()?;
+        frame.depth -= 1;
+
+        if frame.depth == 0 && !frame.vars.is_empty() {
+            Some(std::mem::take(&mut frame.vars))
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns true if `stmts` is the body of an arrow function with an expression
+/// body, converted by `StmtInjector`.
+fn is_arrow_expr_body(stmts: &[Stmt]) -> bool {
+    matches!(
+        stmts,
+        [Stmt::Return(ReturnStmt {
+            span,
+            arg: Some(..),
+        })] if span.is_dummy()
+    )
+}
+
+fn var_decl(decls: Vec<VarDeclarator>) -> Stmt {
+    VarDecl {
+        kind: VarDeclKind::Var,
+        decls,
+        ..Default::default()
+    }
+    .into()
+}
@@ -288,6 +288,32 @@ impl Scope {
         V: RenamedVariable,
     {
         let mut n = 0;
+        let mut queue = queue.into_iter().collect::<Vec<_>>();
+
+        // Names from the previous run are assigned first, so they are stable and
+        // new names do not collide with them.
+        if !previous.is_empty() {
+            queue.retain(|id| {
+                let Some(prev) = previous.get(id) else {
+                    return true;
+                };
+                let sym = prev.atom();
+
+                if renamer.preserve_name(id)
+                    || preserved.contains(id)
+                    || to.contains_key(id)
+                    || preserved_symbols.contains(sym)
+                    || !self.can_rename(id, sym, reverse)
+                {
+                    return true;
+                }
+
+                reverse.push_entry(sym.clone(), id.clone());
+                to.insert(id.clone(), prev.clone());
+
+                false
+            });
+        }
 
         for id in queue {
             if renamer.preserve_name(&id)
@@ -0,0 +1,331 @@
+//! ES modules for browsers.
+//!
+//! This pass keeps the module syntax, but rewrites the module specifiers so
+//! the output can be served to browsers as is:
+//!
+//! 1. The [ImportResolver](crate::path::ImportResolver) is applied, if any.
+//! 2. The import map of [Config::import_map] is applied.
+//! 3. The extension of relative specifiers which are not mapped is replaced
+//!    with [Config::out_file_extension].
+//!
+//! This applies to import and export declarations, `import()` and
+//! `import.meta.resolve()`.
+
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use swc_atoms::Atom;
+use swc_common::{FileName, Span, errors::HANDLER};
+use swc_ecma_ast::*;
+use swc_ecma_visit::{VisitMut, VisitMutWith, visit_mut_pass};
+use swc_transform_common::output::experimental_emit;
+use url::{Position, Url};
+
+use crate::{
+    import_map::{ImportMap, RawImportMap, is_relative, parse_url_like},
+    path::Resolver,
+    wtf8::wtf8_to_cow_str,
+};
+
+/// Origin used for [Config::base_url] when it does not have one.
+const LOCAL_ORIGIN: &str = "http://localhost/";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// The import map to apply.
+    ///
+    /// Relative addresses are resolved against [Config::base_url].
+    #[serde(default)]
+    pub import_map: Option<RawImportMap>,
+
+    /// The URL at which the current working directory is served.
+    ///
+    /// This is used to compute the URL of the module being transformed, which
+    /// selects the `scopes` of the import map.
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+
+    /// If set, the extension of relative specifiers is replaced with this,
+    /// and relative specifiers without an extension get it appended.
+    ///
+    /// Specifiers with an extension which is not a JavaScript or TypeScript
+    /// one, like `.css`, are left as is.
+    #[serde(default)]
+    pub out_file_extension: Option<String>,
+
+    /// Emits the URLs of the static imports under the key
+    /// `__swc_modulepreload__`, as a JSON array.
+    ///
+    /// This can be used to add `<link rel="modulepreload">` tags to HTML
+    /// documents.
+    #[serde(default)]
+    pub modulepreload: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            import_map: None,
+            base_url: default_base_url(),
+            out_file_extension: None,
+            modulepreload: false,
+        }
+    }
+}
+
+fn default_base_url() -> String {
+    "/".into()
+}
+
+/// `base` is the file being transformed, and `cwd` is the directory served at
+/// [Config::base_url].
+///
+/// If [Config::base_url] is invalid, an error is emitted while transforming
+/// and [LOCAL_ORIGIN] is used instead.
+pub fn browser_esm(base: FileName, cwd: PathBuf, resolver: Resolver, config: Config) -> impl Pass {
+    let local_origin = Url::parse(LOCAL_ORIGIN).unwrap();
+
+    let (base_url, base_url_error) =
+        match Url::parse(&config.base_url).or_else(|_| local_origin.join(&config.base_url)) {
+            Ok(base_url) => (base_url, None),
+            Err(err) => (
+                local_origin,
+                Some(format!("invalid base url `{}`: {}", config.base_url, err)),
+            ),
+        };
+
+    let module_url = module_url(&base, &cwd, &base_url);
+
+    visit_mut_pass(BrowserEsm {
+        import_map: config
+            .import_map
+            .as_ref()
+            .map(|raw| ImportMap::new(raw, &base_url)),
+        module_url,
+        resolver,
+        out_file_extension: config.out_file_extension,
+        modulepreload: config.modulepreload.then(Vec::new),
+        base_url_error,
+    })
+}
+
+/// Returns the URL of `base`, or `base_url` if it is not in `cwd`.
+fn module_url(base: &FileName, cwd: &Path, base_url: &Url) -> Url {
+    let FileName::Real(path) = base else {
+        return base_url.clone();
+    };
+
+    let Ok(relative) = path.strip_prefix(cwd) else {
+        return base_url.clone();
+    };
+
+    let mut url = base_url.clone();
+
+    {
+        // Segments are percent-encoded, so `#`, `?` and `%` in file names are
+        // kept in the path.
+        let Ok(mut segments) = url.path_segments_mut() else {
+            return base_url.clone();
+        };
+
+        // Like `Url::join`, the last segment of the base URL is replaced.
+        segments.pop();
+
+        for component in relative.components() {
+            let Component::Normal(name) = component else {
+                return base_url.clone();
+            };
+
+            segments.push(&name.to_string_lossy());
+        }
+    }
+
+    url.set_query(None);
+    url.set_fragment(None);
+
+    url
+}
+
+struct BrowserEsm {
+    import_map: Option<ImportMap>,
+    module_url: Url,
+    resolver: Resolver,
+    out_file_extension: Option<String>,
+    /// [Some] if [Config::modulepreload] is enabled.
+    modulepreload: Option<Vec<String>>,
+    /// Reported when the pass is applied, because [HANDLER] is not available
+    /// while the pass is created.
+    base_url_error: Option<String>,
+}
+
+impl BrowserEsm {
+    /// Rewrites `src`, and records it as a static import if `is_static` is
+    /// true.
+    fn rewrite(&mut self, src: &mut Str, is_static: bool) {
+        let specifier = self
+            .resolver
+            .resolve(Atom::from(&*wtf8_to_cow_str(&src.value)));
+
+        let rewritten = match self.resolve(&specifier, src.span) {
+            Some(url) => self.to_specifier(&url),
+            None => match &self.out_file_extension {
+                Some(ext) if is_relative(&specifier) => rewrite_extension(&specifier, ext)
+                    .map(Atom::from)
+                    .unwrap_or(specifier),
+                _ => specifier,
+            },
+        };
+
+        let preload = if is_static && self.modulepreload.is_some() {
+            parse_url_like(&rewritten, &self.module_url).map(|url| self.to_specifier(&url))
+        } else {
+            None
+        };
+
+        if let (Some(url), Some(modulepreload)) = (preload, &mut self.modulepreload) {
+            let url = url.to_string();
+
+            if !modulepreload.contains(&url) {
+                modulepreload.push(url);
+            }
+        }
+
+        if src.value.as_str() != Some(&*rewritten) {
+            src.raw = None;
+            src.value = rewritten.into();
+        }
+    }
+
+    fn resolve(&self, specifier: &str, span: Span) -> Option<Url> {
+        let import_map = self.import_map.as_ref()?;
+
+        match import_map.resolve(specifier, &self.module_url) {
+            Ok(url) => url,
+            Err(err) => {
+                if HANDLER.is_set() {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(span, &format!("failed to resolve import: {err}"))
+                            .emit()
+                    });
+                }
+
+                None
+            }
+        }
+    }
+
+    /// Omits the origin of `url` if it is the same as the module.
+    fn to_specifier(&self, url: &Url) -> Atom {
+        if url.origin() == self.module_url.origin() {
+            url[Position::BeforePath..].into()
+        } else {
+            url.as_str().into()
+        }
+    }
+
+    fn rewrite_call_arg(&mut self, args: &mut [ExprOrSpread]) {
+        if let Some(ExprOrSpread { spread: None, expr }) = args.first_mut() {
+            if let Expr::Lit(Lit::Str(src)) = &mut **expr {
+                self.rewrite(src, false);
+            }
+        }
+    }
+}
+
+impl VisitMut for BrowserEsm {
+    fn visit_mut_call_expr(&mut self, n: &mut CallExpr) {
+        n.visit_mut_children_with(self);
+
+        let takes_specifier = match &n.callee {
+            Callee::Import(..) => true,
+            Callee::Expr(callee) => callee.as_member().is_some_and(|member| {
+                member
+                    .obj
+                    .as_meta_prop()
+                    .is_some_and(|meta| matches!(meta.kind, MetaPropKind::ImportMeta))
+                    && member
+                        .prop
+                        .as_ident()
+                        .is_some_and(|prop| prop.sym == "resolve")
+            }),
+            _ => false,
+        };
+
+        if takes_specifier {
+            self.rewrite_call_arg(&mut n.args);
+        }
+    }
+
+    fn visit_mut_export_all(&mut self, n: &mut ExportAll) {
+        if !n.type_only {
+            self.rewrite(&mut n.src, true);
+        }
+    }
+
+    fn visit_mut_import_decl(&mut self, n: &mut ImportDecl) {
+        if !n.type_only {
+            self.rewrite(&mut n.src, n.phase == ImportPhase::Evaluation);
+        }
+    }
+
+    fn visit_mut_program(&mut self, n: &mut Program) {
+        if let Some(err) = self.base_url_error.take() {
+            if HANDLER.is_set() {
+                HANDLER.with(|handler| handler.err(&err));
+            }
+        }
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_module(&mut self, n: &mut Module) {
+        n.visit_mut_children_with(self);
+
+        if let Some(modulepreload) = self.modulepreload.take() {
+            experimental_emit(
+                "__swc_modulepreload__".into(),
+                serde_json::to_string(&modulepreload).unwrap(),
+            );
+        }
+    }
+
+    fn visit_mut_named_export(&mut self, n: &mut NamedExport) {
+        if n.type_only {
+            return;
+        }
+
+        if let Some(src) = &mut n.src {
+            self.rewrite(src, true);
+        }
+    }
+}
+
+/// Returns [None] if `specifier` should not be changed.
+fn rewrite_extension(specifier: &str, ext: &str) -> Option<String> {
+    let (path, suffix) = specifier.split_at(specifier.find(['?', '#']).unwrap_or(specifier.len()));
+
+    let file_name = path.rsplit('/').next()?;
+    if matches!(file_name, "" | "." | "..") {
+        return None;
+    }
+
+    match file_name.rsplit_once('.') {
+        Some((stem, cur)) if !stem.is_empty() => {
+            if cur == ext
+                || stem.ends_with(".d")
+                || !matches!(
+                    cur,
+                    "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts"
+                )
+            {
+                return None;
+            }
+
+            let path = &path[..path.len() - cur.len()];
+            Some(format!("{path}{ext}{suffix}"))
+        }
+        _ => Some(format!("{path}.{ext}{suffix}")),
+    }
+}
@@ -0,0 +1,185 @@
+//! [Import maps](https://html.spec.whatwg.org/multipage/webappapis.html#import-maps)
+
+use anyhow::{Context, Error, bail};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// An import map, in the format of `<script type="importmap">`.
+///
+/// Use [ImportMap::new] to normalize it.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RawImportMap {
+    #[serde(default)]
+    pub imports: IndexMap<String, Option<String>>,
+
+    #[serde(default)]
+    pub scopes: IndexMap<String, IndexMap<String, Option<String>>>,
+}
+
+/// Keys sorted in descending order, so a longer prefix comes before a
+/// shorter one.
+///
+/// [None] if the specifier is blocked.
+type SpecifierMap = Vec<(String, Option<Url>)>;
+
+/// A normalized import map.
+#[derive(Debug, Clone)]
+pub struct ImportMap {
+    imports: SpecifierMap,
+    scopes: Vec<(String, SpecifierMap)>,
+}
+
+impl ImportMap {
+    /// Normalizes `raw`, resolving the relative addresses against `base_url`.
+    ///
+    /// Like browsers, this ignores invalid entries.
+    ///
+    /// See <https://html.spec.whatwg.org/multipage/webappapis.html#parse-an-import-map-string>
+    pub fn new(raw: &RawImportMap, base_url: &Url) -> Self {
+        let imports = sort_and_normalize_specifier_map(&raw.imports, base_url);
+
+        let mut scopes = raw
+            .scopes
+            .iter()
+            .filter_map(|(prefix, map)| {
+                let Ok(prefix) = base_url.join(prefix) else {
+                    tracing::warn!("invalid scope prefix in import map: {}", prefix);
+                    return None;
+                };
+
+                Some((
+                    prefix.to_string(),
+                    sort_and_normalize_specifier_map(map, base_url),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        scopes.sort_by(|a, b| b.0.cmp(&a.0));
+
+        Self { imports, scopes }
+    }
+
+    /// Parses an import map from JSON.
+    pub fn from_json(json: &str, base_url: &Url) -> Result<Self, Error> {
+        let raw = serde_json::from_str(json).context("failed to parse import map")?;
+
+        Ok(Self::new(&raw, base_url))
+    }
+
+    /// Resolves `specifier` imported from the module at `referrer`.
+    ///
+    /// Returns [None] if no entry of the import map matches `specifier`, and
+    /// an error if the matching entry blocks it.
+    ///
+    /// See <https://html.spec.whatwg.org/multipage/webappapis.html#resolve-a-module-specifier>
+    pub fn resolve(&self, specifier: &str, referrer: &Url) -> Result<Option<Url>, Error> {
+        let as_url = parse_url_like(specifier, referrer);
+        let normalized = as_url.as_ref().map_or(specifier, |url| url.as_str());
+
+        for (prefix, map) in &self.scopes {
+            let referrer = referrer.as_str();
+
+            if prefix == referrer || (prefix.ends_with('/') && referrer.starts_with(&**prefix)) {
+                if let Some(url) = resolve_imports_match(normalized, as_url.as_ref(), map)? {
+                    return Ok(Some(url));
+                }
+            }
+        }
+
+        resolve_imports_match(normalized, as_url.as_ref(), &self.imports)
+    }
+}
+
+/// See <https://html.spec.whatwg.org/multipage/webappapis.html#sorting-and-normalizing-a-module-specifier-map>
+fn sort_and_normalize_specifier_map(
+    map: &IndexMap<String, Option<String>>,
+    base_url: &Url,
+) -> SpecifierMap {
+    let mut normalized = map
+        .iter()
+        .filter(|(key, _)| !key.is_empty())
+        .map(|(key, value)| {
+            let key = match parse_url_like(key, base_url) {
+                Some(url) => url.to_string(),
+                None => key.clone(),
+            };
+
+            let Some(value) = value else {
+                return (key, None);
+            };
+
+            let Some(address) = parse_url_like(value, base_url) else {
+                tracing::warn!("invalid address in import map: {}", value);
+                return (key, None);
+            };
+
+            if key.ends_with('/') && !address.as_str().ends_with('/') {
+                tracing::warn!(
+                    "invalid address in import map: {} (the key `{}` ends with a slash)",
+                    value,
+                    key
+                );
+                return (key, None);
+            }
+
+            (key, Some(address))
+        })
+        .collect::<Vec<_>>();
+
+    normalized.sort_by(|a, b| b.0.cmp(&a.0));
+    normalized
+}
+
+/// See <https://html.spec.whatwg.org/multipage/webappapis.html#resolving-an-imports-match>
+fn resolve_imports_match(
+    normalized: &str,
+    as_url: Option<&Url>,
+    map: &SpecifierMap,
+) -> Result<Option<Url>, Error> {
+    for (key, value) in map {
+        if key == normalized {
+            let Some(value) = value else {
+                bail!("`{}` is blocked by the import map", normalized);
+            };
+
+            return Ok(Some(value.clone()));
+        }
+
+        if key.ends_with('/')
+            && normalized.starts_with(&**key)
+            && as_url.map_or(true, |url| url.is_special())
+        {
+            let Some(value) = value else {
+                bail!("`{}` is blocked by the import map", normalized);
+            };
+
+            let after_prefix = &normalized[key.len()..];
+
+            let url = value
+                .join(after_prefix)
+                .with_context(|| format!("failed to resolve `{normalized}` using `{key}`"))?;
+
+            if !url.as_str().starts_with(value.as_str()) {
+                bail!("`{}` backtracks above its prefix `{}`", normalized, key);
+            }
+
+            return Ok(Some(url));
+        }
+    }
+
+    Ok(None)
+}
+
+/// See <https://html.spec.whatwg.org/multipage/webappapis.html#resolve-a-url-like-module-specifier>
+pub(crate) fn parse_url_like(specifier: &str, base_url: &Url) -> Option<Url> {
+    if is_relative(specifier) {
+        return base_url.join(specifier).ok();
+    }
+
+    Url::parse(specifier).ok()
+}
+
+pub(crate) fn is_relative(specifier: &str) -> bool {
+    specifier.starts_with('/') || specifier.starts_with("./") || specifier.starts_with("../")
+}
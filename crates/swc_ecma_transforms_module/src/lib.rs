@@ -7,13 +7,17 @@ use serde::{Deserialize, Serialize};
 use swc_common::{Span, SyntaxContext};
 use util::Config;
 
-pub use self::{amd::amd, common_js::common_js, system_js::system_js, umd::umd};
+pub use self::{
+    amd::amd, browser_esm::browser_esm, common_js::common_js, system_js::system_js, umd::umd,
+};
 
 #[macro_use]
 pub mod util;
 pub mod amd;
+pub mod browser_esm;
 pub mod common_js;
 pub mod import_analysis;
+pub mod import_map;
 pub(crate) mod module_decl_strip;
 pub(crate) mod module_ref_rewriter;
 pub mod path;
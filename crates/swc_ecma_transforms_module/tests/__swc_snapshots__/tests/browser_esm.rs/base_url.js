@@ -0,0 +1,3 @@
+import React from "https://esm.sh/react@19";
+import debounce from "/vendor/lodash/debounce.js";
+import { Button } from "/app/src/components/button.js";
@@ -0,0 +1 @@
+import React from "https://esm.sh/react@18";
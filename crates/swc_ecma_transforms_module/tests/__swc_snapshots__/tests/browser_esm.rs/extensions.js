@@ -0,0 +1,9 @@
+import "./a.js";
+import "./b.js";
+import "./c.js?raw";
+import "../d.js";
+import "./e.js";
+import "./style.css";
+import "./types.d.ts";
+import "./dir/";
+import "unmapped";
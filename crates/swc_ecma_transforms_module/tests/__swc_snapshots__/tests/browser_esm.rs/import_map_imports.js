@@ -0,0 +1,7 @@
+import React from "https://esm.sh/react@19";
+import debounce from "/vendor/lodash/debounce.js";
+import { Button } from "/src/components/button.js";
+export * from "/vendor/lodash/array.js";
+export { useState } from "https://esm.sh/react@19";
+const lazy = import("/src/pages/home.js");
+const url = import.meta.resolve("https://esm.sh/react@19");
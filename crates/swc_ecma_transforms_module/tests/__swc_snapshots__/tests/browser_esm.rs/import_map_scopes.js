@@ -0,0 +1,2 @@
+import React from "https://esm.sh/react@17";
+import debounce from "/vendor/lodash/debounce.js";
@@ -0,0 +1,289 @@
+use std::path::Path;
+
+use swc_common::{FileName, Mark};
+use swc_ecma_ast::Pass;
+use swc_ecma_parser::Syntax;
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_transforms_module::{
+    browser_esm::{self, browser_esm},
+    import_map::{ImportMap, RawImportMap},
+    path::Resolver,
+};
+use swc_ecma_transforms_testing::test;
+use url::Url;
+
+fn import_map() -> RawImportMap {
+    serde_json::from_str(
+        r#"{
+            "imports": {
+                "react": "https://esm.sh/react@19",
+                "lodash/": "/vendor/lodash/",
+                "@/": "./src/"
+            },
+            "scopes": {
+                "/src/legacy/": {
+                    "react": "https://esm.sh/react@17"
+                }
+            }
+        }"#,
+    )
+    .unwrap()
+}
+
+fn tr(file: &'static str, config: browser_esm::Config) -> impl Pass {
+    let unresolved_mark = Mark::new();
+    let top_level_mark = Mark::new();
+
+    (
+        resolver(unresolved_mark, top_level_mark, false),
+        browser_esm(
+            FileName::Real(Path::new("/app").join(file)),
+            "/app".into(),
+            Resolver::Default,
+            config,
+        ),
+    )
+}
+
+fn config() -> browser_esm::Config {
+    browser_esm::Config {
+        import_map: Some(import_map()),
+        out_file_extension: Some("js".into()),
+        ..Default::default()
+    }
+}
+
+test!(
+    module,
+    Syntax::default(),
+    |_| tr("src/main.js", config()),
+    import_map_imports,
+    r#"
+    import React from "react";
+    import debounce from "lodash/debounce.js";
+    import { Button } from "@/components/button.js";
+    export * from "lodash/array.js";
+    export { useState } from "react";
+
+    const lazy = import("@/pages/home.js");
+    const url = import.meta.resolve("react");
+    "#
+);
+
+test!(
+    module,
+    Syntax::default(),
+    |_| tr("src/legacy/main.js", config()),
+    import_map_scopes,
+    r#"
+    import React from "react";
+    import debounce from "lodash/debounce.js";
+    "#
+);
+
+test!(
+    module,
+    Syntax::default(),
+    |_| tr("src/main.js", config()),
+    extensions,
+    r#"
+    import "./a";
+    import "./b.ts";
+    import "./c.tsx?raw";
+    import "../d.mjs";
+    import "./e.js";
+    import "./style.css";
+    import "./types.d.ts";
+    import "./dir/";
+    import "unmapped";
+    "#
+);
+
+test!(
+    module,
+    Syntax::default(),
+    |_| tr(
+        "src/main.js",
+        browser_esm::Config {
+            base_url: "http://localhost:3000/app/".into(),
+            ..config()
+        }
+    ),
+    base_url,
+    r#"
+    import React from "react";
+    import debounce from "lodash/debounce.js";
+    import { Button } from "@/components/button.js";
+    "#
+);
+
+test!(
+    module,
+    Syntax::default(),
+    |_| tr(
+        "src/a#b%c/main.js",
+        browser_esm::Config {
+            import_map: Some(
+                serde_json::from_str(
+                    r#"{
+                        "imports": {
+                            "react": "https://esm.sh/react@19"
+                        },
+                        "scopes": {
+                            "/src/a%23b%25c/": {
+                                "react": "https://esm.sh/react@18"
+                            }
+                        }
+                    }"#,
+                )
+                .unwrap()
+            ),
+            ..config()
+        }
+    ),
+    escaped_module_path,
+    r#"
+    import React from "react";
+    "#
+);
+
+#[test]
+fn import_map_resolve() {
+    let base_url = Url::parse("https://example.com/").unwrap();
+    let referrer = base_url.join("src/main.js").unwrap();
+    let legacy_referrer = base_url.join("src/legacy/main.js").unwrap();
+
+    let import_map = ImportMap::new(&import_map(), &base_url);
+
+    let resolve = |specifier: &str, referrer: &Url| {
+        import_map
+            .resolve(specifier, referrer)
+            .unwrap()
+            .map(|url| url.to_string())
+    };
+
+    assert_eq!(
+        resolve("react", &referrer).as_deref(),
+        Some("https://esm.sh/react@19")
+    );
+    assert_eq!(
+        resolve("react", &legacy_referrer).as_deref(),
+        Some("https://esm.sh/react@17")
+    );
+    assert_eq!(
+        resolve("lodash/fp/map.js", &referrer).as_deref(),
+        Some("https://example.com/vendor/lodash/fp/map.js")
+    );
+    assert_eq!(resolve("lodash", &referrer), None);
+    assert_eq!(resolve("./utils.js", &referrer), None);
+}
+
+#[test]
+fn import_map_errors() {
+    let base_url = Url::parse("https://example.com/").unwrap();
+
+    let import_map = ImportMap::from_json(
+        r#"{
+            "imports": {
+                "blocked": null,
+                "pkg/": "/vendor/pkg/"
+            }
+        }"#,
+        &base_url,
+    )
+    .unwrap();
+
+    assert!(import_map.resolve("blocked", &base_url).is_err());
+    assert!(
+        import_map
+            .resolve("pkg/../../secret.js", &base_url)
+            .is_err()
+    );
+}
+
+#[test]
+fn modulepreload() {
+    ::testing::run_test(false, |cm, _| {
+        let fm = cm.new_source_file(
+            FileName::Real("/app/src/main.js".into()).into(),
+            r#"
+            import React from "react";
+            import "./a";
+            import "./a.js";
+            export * from "lodash/array.js";
+            import("./lazy.js");
+            "#,
+        );
+
+        let module = swc_ecma_parser::parse_file_as_module(
+            &fm,
+            Syntax::default(),
+            Default::default(),
+            None,
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        let config = browser_esm::Config {
+            modulepreload: true,
+            ..config()
+        };
+
+        let (_, output) = swc_transform_common::output::capture(|| {
+            swc_ecma_ast::Program::Module(module).apply(browser_esm(
+                (*fm.name).clone(),
+                "/app".into(),
+                Resolver::Default,
+                config,
+            ))
+        });
+
+        assert_eq!(
+            output["__swc_modulepreload__"],
+            r#"["https://esm.sh/react@19","/src/a.js","/vendor/lodash/array.js"]"#
+        );
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn invalid_base_url() {
+    let stderr = ::testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(
+            FileName::Real("/app/src/main.js".into()).into(),
+            r#"import React from "react";"#,
+        );
+
+        let module = swc_ecma_parser::parse_file_as_module(
+            &fm,
+            Syntax::default(),
+            Default::default(),
+            None,
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        let config = browser_esm::Config {
+            base_url: "http://exa mple.com/".into(),
+            ..config()
+        };
+
+        swc_ecma_ast::Program::Module(module).apply(browser_esm(
+            (*fm.name).clone(),
+            "/app".into(),
+            Resolver::Default,
+            config,
+        ));
+
+        if handler.has_errors() {
+            Err(())
+        } else {
+            Ok(())
+        }
+    })
+    .unwrap_err();
+
+    assert!(stderr.to_string().contains("invalid base url"));
+}
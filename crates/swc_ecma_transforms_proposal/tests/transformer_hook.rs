@@ -0,0 +1,175 @@
+//! Checks that the decorator hook of `swc_ecma_transformer` produces the same
+//! output as the [decorators] and [decorator_2022_03] passes.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use swc_common::{Mark, SourceFile};
+use swc_ecma_ast::*;
+use swc_ecma_codegen::to_code_default;
+use swc_ecma_parser::{EsSyntax, Parser, Syntax, TsSyntax, lexer::Lexer};
+use swc_ecma_transforms_base::{
+    fixer::fixer,
+    helpers::{HELPERS, Helpers},
+    hygiene::hygiene,
+    resolver,
+};
+use swc_ecma_transforms_proposal::{
+    DecoratorVersion, decorator_2022_03::decorator_2022_03, decorators,
+};
+
+#[derive(Debug, Deserialize)]
+struct BabelTestOptions {
+    #[serde(default)]
+    plugins: Vec<BabelPluginEntry>,
+
+    #[serde(default)]
+    throws: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BabelPluginEntry {
+    NameOnly(#[allow(dead_code)] String),
+    WithConfig(#[allow(dead_code)] String, BabelPluginOption),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BabelPluginOption {
+    Decorator { version: DecoratorVersion },
+}
+
+#[testing::fixture("tests/decorators/**/input.js")]
+#[testing::fixture("tests/decorators/**/input.mjs")]
+#[testing::fixture("tests/decorators/**/input.ts")]
+fn same_as_decorator_2022_03_pass(input: PathBuf) {
+    let options: BabelTestOptions =
+        swc_ecma_transforms_testing::parse_options(input.parent().unwrap());
+
+    // Only the fixtures for the decorator pass alone are compared.
+    let only_2022_03 = matches!(
+        &*options.plugins,
+        [BabelPluginEntry::WithConfig(
+            _,
+            BabelPluginOption::Decorator {
+                version: DecoratorVersion::V202203
+            }
+        )]
+    );
+
+    if !only_2022_03 || options.throws.is_some() {
+        return;
+    }
+
+    compare(
+        &input,
+        decorator_2022_03,
+        swc_ecma_transformer::DecoratorVersion::V202203,
+        false,
+    );
+}
+
+#[testing::fixture("../swc_ecma_transforms/tests/fixture/legacy-only/**/input.ts")]
+fn same_as_legacy_decorators_pass(input: PathBuf) {
+    compare(
+        &input,
+        || legacy_decorators(false),
+        swc_ecma_transformer::DecoratorVersion::Legacy,
+        false,
+    );
+}
+
+#[testing::fixture("../swc_ecma_transforms/tests/fixture/legacy-metadata/**/input.ts")]
+fn same_as_legacy_decorators_pass_with_metadata(input: PathBuf) {
+    compare(
+        &input,
+        || legacy_decorators(true),
+        swc_ecma_transformer::DecoratorVersion::Legacy,
+        true,
+    );
+}
+
+fn legacy_decorators(emit_metadata: bool) -> impl Pass {
+    decorators(decorators::Config {
+        legacy: true,
+        emit_metadata,
+        use_define_for_class_fields: false,
+    })
+}
+
+fn compare<P: Pass>(
+    input: &Path,
+    pass: impl FnOnce() -> P,
+    version: swc_ecma_transformer::DecoratorVersion,
+    emit_metadata: bool,
+) {
+    ::testing::run_test(false, |cm, _| -> Result<(), ()> {
+        let fm = cm.load_file(input).expect("failed to load file");
+
+        let Some(module) = parse(&fm) else {
+            return Ok(());
+        };
+
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+
+        let program =
+            Program::Module(module).apply(resolver(unresolved_mark, top_level_mark, true));
+
+        let mut decorator = swc_ecma_transformer::DecoratorOptions::default();
+        decorator.version = version;
+        decorator.emit_metadata = emit_metadata;
+
+        let mut options = swc_ecma_transformer::Options::default();
+        options.decorator = Some(decorator);
+
+        // The decorator passes inject helpers.
+        let (expected, actual) = HELPERS.set(&Helpers::new(false), || {
+            let expected = program
+                .clone()
+                .apply(pass())
+                .apply(hygiene())
+                .apply(fixer(None));
+
+            let actual = program
+                .apply(options.into_pass())
+                .apply(hygiene())
+                .apply(fixer(None));
+
+            (expected, actual)
+        });
+
+        let expected = to_code_default(cm.clone(), None, &expected);
+        let actual = to_code_default(cm.clone(), None, &actual);
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    })
+    .expect("failed to run test");
+}
+
+fn parse(fm: &SourceFile) -> Option<Module> {
+    let syntax = if fm.name.to_string().ends_with(".ts") {
+        Syntax::Typescript(TsSyntax {
+            decorators: true,
+            ..Default::default()
+        })
+    } else {
+        Syntax::Es(EsSyntax {
+            decorators: true,
+            auto_accessors: true,
+            allow_super_outside_method: true,
+            decorators_before_export: true,
+            explicit_resource_management: true,
+            ..Default::default()
+        })
+    };
+
+    let mut parser: Parser<Lexer> = Parser::new(syntax, fm.into(), None);
+
+    let module = parser.parse_module().ok()?;
+
+    parser.take_errors().is_empty().then_some(module)
+}
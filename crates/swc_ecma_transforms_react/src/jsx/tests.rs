@@ -6,9 +6,10 @@ use std::{
     rc::Rc,
 };
 
-use swc_common::{SourceMap, comments::SingleThreadedComments, sync::Lrc};
+use swc_common::{FileName, SourceMap, comments::SingleThreadedComments, sync::Lrc};
 use swc_ecma_codegen::{Config, Emitter};
-use swc_ecma_parser::{EsSyntax, Parser, StringInput};
+use swc_ecma_parser::{EsSyntax, Parser, StringInput, Syntax};
+use swc_ecma_transformer::jsx_text_to_str;
 use swc_ecma_transforms_base::{fixer::fixer, hygiene, resolver};
 #[cfg(feature = "es3")]
 use swc_ecma_transforms_compat::es3::property_literals;
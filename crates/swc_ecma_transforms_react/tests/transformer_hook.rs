@@ -0,0 +1,280 @@
+//! Checks that the JSX hook of `swc_ecma_transformer` produces the same output
+//! as the [jsx], [jsx_src] and [jsx_self] passes, and that running it in the
+//! same traversal as the ES hooks does not change the output.
+
+use std::{path::PathBuf, rc::Rc};
+
+use serde::Deserialize;
+use swc_common::{
+    Mark, SourceFile, SourceMap, SyntaxContext, comments::SingleThreadedComments, sync::Lrc,
+};
+use swc_ecma_ast::*;
+use swc_ecma_codegen::{Emitter, text_writer::JsWriter};
+use swc_ecma_parser::{EsSyntax, Parser, Syntax, lexer::Lexer};
+use swc_ecma_transforms_base::{
+    fixer::fixer,
+    helpers::{HELPERS, Helpers},
+    hygiene::hygiene,
+    resolver,
+};
+use swc_ecma_transforms_react::{Options, Runtime, jsx, jsx_self, jsx_src};
+use swc_ecma_transforms_testing::parse_options;
+
+#[derive(Debug, Deserialize)]
+struct FixtureOptions {
+    #[serde(flatten)]
+    options: Options,
+
+    #[serde(default, rename = "BABEL_8_BREAKING")]
+    babel_8_breaking: bool,
+
+    #[serde(default)]
+    throws: Option<String>,
+}
+
+#[testing::fixture("tests/jsx/fixture/**/input.js")]
+#[testing::fixture("tests/integration/fixture/**/input.js")]
+fn same_as_jsx_pass(input: PathBuf) {
+    let Some(options) = fixture_options(&input) else {
+        return;
+    };
+
+    ::testing::run_test(false, |cm, _| -> Result<(), ()> {
+        let fm = cm.load_file(&input).expect("failed to load file");
+
+        let expected = {
+            let comments = Rc::new(SingleThreadedComments::default());
+            let Some(module) = parse(&fm, &comments) else {
+                return Ok(());
+            };
+
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+            let development = options.development.unwrap_or_default();
+
+            let program = Program::Module(module)
+                .apply(resolver(unresolved_mark, top_level_mark, false))
+                .apply(jsx_src(development, cm.clone()))
+                .apply(jsx_self(development))
+                .apply(jsx(
+                    cm.clone(),
+                    Some(comments.clone()),
+                    options.clone(),
+                    top_level_mark,
+                    unresolved_mark,
+                ))
+                .apply(hygiene())
+                .apply(fixer(Some(&comments)));
+
+            print(&cm, &comments, &program)
+        };
+
+        let actual = {
+            let comments = Rc::new(SingleThreadedComments::default());
+            let module = parse(&fm, &comments).unwrap();
+
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            let program = Program::Module(module)
+                .apply(resolver(unresolved_mark, top_level_mark, false))
+                .apply(
+                    transformer_options(
+                        &cm,
+                        &comments,
+                        &options,
+                        unresolved_mark,
+                        Some(top_level_mark),
+                        false,
+                    )
+                    .into_pass(),
+                )
+                .apply(hygiene())
+                .apply(fixer(Some(&comments)));
+
+            print(&cm, &comments, &program)
+        };
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    })
+    .expect("failed to run test");
+}
+
+#[testing::fixture("tests/jsx/fixture/**/input.js")]
+#[testing::fixture("tests/integration/fixture/**/input.js")]
+fn same_as_separate_passes(input: PathBuf) {
+    // Only the name hygiene picks for the user's `_jsx` differs.
+    if input.to_string_lossy().contains("complicated-scope-module") {
+        return;
+    }
+
+    let Some(options) = fixture_options(&input) else {
+        return;
+    };
+
+    ::testing::run_test(false, |cm, _| -> Result<(), ()> {
+        let fm = cm.load_file(&input).expect("failed to load file");
+
+        let comments = Rc::new(SingleThreadedComments::default());
+        let Some(module) = parse(&fm, &comments) else {
+            return Ok(());
+        };
+
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+
+        let program =
+            Program::Module(module).apply(resolver(unresolved_mark, top_level_mark, false));
+
+        // The ES2015 hooks inject helpers.
+        let (expected, actual) = HELPERS.set(&Helpers::new(false), || {
+            let expected = program
+                .clone()
+                .apply(
+                    transformer_options(
+                        &cm,
+                        &comments,
+                        &options,
+                        unresolved_mark,
+                        Some(top_level_mark),
+                        false,
+                    )
+                    .into_pass(),
+                )
+                .apply(
+                    transformer_options(&cm, &comments, &options, unresolved_mark, None, true)
+                        .into_pass(),
+                )
+                .apply(hygiene())
+                .apply(fixer(None));
+
+            let actual = program
+                .apply(
+                    transformer_options(
+                        &cm,
+                        &comments,
+                        &options,
+                        unresolved_mark,
+                        Some(top_level_mark),
+                        true,
+                    )
+                    .into_pass(),
+                )
+                .apply(hygiene())
+                .apply(fixer(None));
+
+            (expected, actual)
+        });
+
+        let comments = SingleThreadedComments::default();
+        let expected = print(&cm, &comments, &expected);
+        let actual = print(&cm, &comments, &actual);
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    })
+    .expect("failed to run test");
+}
+
+/// Returns [None] if the fixture is expected to fail or preserves JSX.
+fn fixture_options(input: &std::path::Path) -> Option<Options> {
+    let FixtureOptions {
+        mut options,
+        babel_8_breaking,
+        throws,
+    } = parse_options(input.parent().unwrap());
+
+    if throws.is_some() || options.refresh.is_some() {
+        return None;
+    }
+
+    if !babel_8_breaking && options.runtime.is_none() {
+        options.runtime = Some(Runtime::Classic);
+    }
+
+    (options.runtime != Some(Runtime::Preserve)).then_some(options)
+}
+
+fn parse(fm: &SourceFile, comments: &SingleThreadedComments) -> Option<Module> {
+    let mut parser: Parser<Lexer> = Parser::new(
+        Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+        fm.into(),
+        Some(comments),
+    );
+
+    let module = parser.parse_module().ok()?;
+
+    parser.take_errors().is_empty().then_some(module)
+}
+
+fn print(cm: &Lrc<SourceMap>, comments: &SingleThreadedComments, program: &Program) -> String {
+    let mut buf = Vec::new();
+
+    let mut emitter = Emitter {
+        cfg: Default::default(),
+        cm: cm.clone(),
+        wr: Box::new(JsWriter::new(cm.clone(), "\n", &mut buf, None)),
+        comments: Some(comments),
+    };
+
+    emitter.emit_program(program).unwrap();
+
+    String::from_utf8(buf).unwrap()
+}
+
+/// Enables the JSX hook if `top_level_mark` is given, and the ES2015 hooks if
+/// `es2015` is true.
+///
+/// Object rest and spread are lowered along with ES2015, because the ES2015
+/// destructuring hook expects them to be removed.
+fn transformer_options(
+    cm: &Lrc<SourceMap>,
+    comments: &Rc<SingleThreadedComments>,
+    options: &Options,
+    unresolved_mark: Mark,
+    top_level_mark: Option<Mark>,
+    es2015: bool,
+) -> swc_ecma_transformer::Options {
+    let mut transformer = swc_ecma_transformer::Options::default();
+    transformer.unresolved_ctxt = SyntaxContext::empty().apply_mark(unresolved_mark);
+
+    if let Some(top_level_mark) = top_level_mark {
+        let mut jsx: swc_ecma_transformer::JsxOptions = Default::default();
+        jsx.top_level_ctxt = SyntaxContext::empty().apply_mark(top_level_mark);
+        jsx.runtime = options.runtime.map(|runtime| match runtime {
+            Runtime::Automatic => swc_ecma_transformer::JsxRuntime::Automatic,
+            _ => swc_ecma_transformer::JsxRuntime::Classic,
+        });
+        jsx.import_source = options.import_source.clone();
+        jsx.pragma = options.pragma.clone();
+        jsx.pragma_frag = options.pragma_frag.clone();
+        jsx.throw_if_namespace = options.throw_if_namespace;
+        jsx.development = options.development;
+        jsx.cm = cm.clone();
+        jsx.comments = Some(Box::new(comments.clone()));
+        transformer.jsx = Some(jsx);
+    }
+
+    if es2015 {
+        transformer.env.es2018.object_rest_spread = true;
+
+        let es2015 = &mut transformer.env.es2015;
+        es2015.shorthand = true;
+        es2015.template_literal = true;
+        es2015.classes = true;
+        es2015.spread = true;
+        es2015.arrow = true;
+        es2015.parameters = true;
+        es2015.computed_props = true;
+        es2015.destructuring = true;
+        es2015.block_scoping = true;
+    }
+
+    transformer
+}
@@ -2,13 +2,11 @@
 #![allow(clippy::vec_box)]
 #![allow(clippy::mutable_key_type)]
 
+use swc_ecma_transformer::typescript_strip::{retain, semantic, shared, ts_enum};
+
 pub use self::typescript::*;
 mod config;
 mod macros;
-mod retain;
-mod semantic;
-mod shared;
 mod transform;
-mod ts_enum;
 pub mod typescript;
 mod utils;
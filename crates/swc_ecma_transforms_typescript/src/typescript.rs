@@ -2,7 +2,9 @@ use std::mem;
 
 use rustc_hash::FxHashSet;
 use swc_atoms::atom;
-use swc_common::{Mark, SourceMap, Span, Spanned, comments::Comments, sync::Lrc, util::take::Take};
+use swc_common::{
+    Mark, SourceMap, Span, Spanned, SyntaxContext, comments::Comments, sync::Lrc, util::take::Take,
+};
 use swc_ecma_ast::*;
 use swc_ecma_transforms_react::{JsxDirectives, parse_expr_for_jsx};
 use swc_ecma_visit::{VisitMut, VisitMutWith, visit_mut_pass};
@@ -42,7 +44,11 @@ pub(crate) struct TypeScript {
 impl Pass for TypeScript {
     fn process(&mut self, n: &mut Program) {
         let was_module = n.as_module().and_then(|m| self.get_last_module_span(m));
-        let semantic = analyze_program(n, self.unresolved_mark, mem::take(&mut self.id_usage));
+        let semantic = analyze_program(
+            n,
+            SyntaxContext::empty().apply_mark(self.unresolved_mark),
+            mem::take(&mut self.id_usage),
+        );
 
         n.mutate(transform(
             self.unresolved_mark,
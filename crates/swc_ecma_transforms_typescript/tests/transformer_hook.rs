@@ -0,0 +1,171 @@
+//! Checks that the TypeScript hook of `swc_ecma_transformer` produces the same
+//! output as the [typescript] pass, and that running it in the same traversal
+//! as the ES hooks does not change the output.
+
+use std::path::PathBuf;
+
+use swc_common::{Mark, SourceFile, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_codegen::to_code_default;
+use swc_ecma_parser::{Parser, Syntax, TsSyntax, lexer::Lexer};
+use swc_ecma_transforms_base::{
+    fixer::fixer,
+    helpers::{HELPERS, Helpers},
+    hygiene::hygiene,
+    resolver,
+};
+use swc_ecma_transforms_typescript::typescript;
+
+#[testing::fixture("tests/fixture/**/input.ts")]
+#[testing::fixture("../swc_ecma_parser/tests/typescript/**/*.ts")]
+#[testing::fixture("../swc_ecma_parser/tests/tsc/**/*.ts")]
+fn same_as_typescript_pass(entry: PathBuf) {
+    let file_name = entry.to_string_lossy().replace('\\', "/");
+
+    let ignored = &["stack-size", "issue-716", ".d.ts"];
+
+    if ignored.iter().any(|ignored| file_name.contains(ignored)) {
+        return;
+    }
+
+    ::testing::run_test(false, |cm, _| -> Result<(), ()> {
+        let fm = cm.load_file(&entry).expect("failed to load file");
+
+        // We are not testing parser issues
+        let Some(module) = parse(&fm) else {
+            return Ok(());
+        };
+
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+
+        let program =
+            Program::Module(module).apply(resolver(unresolved_mark, top_level_mark, true));
+
+        let expected = program
+            .clone()
+            .apply(typescript(
+                typescript::Config {
+                    no_empty_export: true,
+                    ..Default::default()
+                },
+                unresolved_mark,
+                top_level_mark,
+            ))
+            .apply(hygiene())
+            .apply(fixer(None));
+
+        let actual = program
+            .apply(transformer_options(unresolved_mark, Some(top_level_mark), false).into_pass())
+            .apply(hygiene())
+            .apply(fixer(None));
+
+        let expected = to_code_default(cm.clone(), None, &expected);
+        let actual = to_code_default(cm.clone(), None, &actual);
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    })
+    .expect("failed to run test");
+}
+
+#[testing::fixture("tests/fixture/**/input.ts")]
+fn same_as_separate_passes(entry: PathBuf) {
+    let file_name = entry.to_string_lossy().replace('\\', "/");
+
+    // The body of `for (const _ of x) const enum I {}` is lowered by for-of
+    // before it's stripped, which prints `{}` instead of `;`.
+    if file_name.contains("issue-10097") {
+        return;
+    }
+
+    ::testing::run_test(false, |cm, _| -> Result<(), ()> {
+        let fm = cm.load_file(&entry).expect("failed to load file");
+
+        let Some(module) = parse(&fm) else {
+            return Ok(());
+        };
+
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+
+        let program =
+            Program::Module(module).apply(resolver(unresolved_mark, top_level_mark, true));
+
+        // The ES2015 hooks inject helpers.
+        let (expected, actual) = HELPERS.set(&Helpers::new(false), || {
+            let expected = program
+                .clone()
+                .apply(
+                    transformer_options(unresolved_mark, Some(top_level_mark), false).into_pass(),
+                )
+                .apply(transformer_options(unresolved_mark, None, true).into_pass())
+                .apply(hygiene())
+                .apply(fixer(None));
+
+            let actual = program
+                .apply(transformer_options(unresolved_mark, Some(top_level_mark), true).into_pass())
+                .apply(hygiene())
+                .apply(fixer(None));
+
+            (expected, actual)
+        });
+
+        let expected = to_code_default(cm.clone(), None, &expected);
+        let actual = to_code_default(cm.clone(), None, &actual);
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    })
+    .expect("failed to run test");
+}
+
+fn parse(fm: &SourceFile) -> Option<Module> {
+    let mut parser: Parser<Lexer> = Parser::new(
+        Syntax::Typescript(TsSyntax {
+            decorators: true,
+            ..Default::default()
+        }),
+        fm.into(),
+        None,
+    );
+
+    let module = parser.parse_typescript_module().ok()?;
+
+    parser.take_errors().is_empty().then_some(module)
+}
+
+/// Enables the TypeScript hook if `top_level_mark` is given, and the ES2015
+/// hooks if `es2015` is true.
+fn transformer_options(
+    unresolved_mark: Mark,
+    top_level_mark: Option<Mark>,
+    es2015: bool,
+) -> swc_ecma_transformer::Options {
+    let mut options = swc_ecma_transformer::Options::default();
+    options.unresolved_ctxt = SyntaxContext::empty().apply_mark(unresolved_mark);
+
+    if let Some(top_level_mark) = top_level_mark {
+        let mut typescript = swc_ecma_transformer::TypescriptOptions::default();
+        typescript.top_level_ctxt = SyntaxContext::empty().apply_mark(top_level_mark);
+        typescript.no_empty_export = true;
+        options.typescript = Some(typescript);
+    }
+
+    if es2015 {
+        let es2015 = &mut options.env.es2015;
+        es2015.shorthand = true;
+        es2015.template_literal = true;
+        es2015.classes = true;
+        es2015.spread = true;
+        es2015.arrow = true;
+        es2015.parameters = true;
+        es2015.computed_props = true;
+        es2015.destructuring = true;
+        es2015.block_scoping = true;
+    }
+
+    options
+}
@@ -0,0 +1,200 @@
+use std::hash::Hash;
+
+use petgraph::Direction::{Incoming, Outgoing};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{DepGraph, GraphResult};
+
+/// Dominator tree of a module graph, computed by
+/// [GraphResult::dominator_tree].
+///
+/// A module `a` dominates a module `b` if every chain of imports from the
+/// entries to `b` goes through `a`.
+#[derive(Debug, Clone)]
+pub struct DominatorTree<I> {
+    entries: FxHashSet<I>,
+    /// [None] if the module is dominated only by the entries as a whole.
+    idom: FxHashMap<I, Option<I>>,
+}
+
+impl<I> DominatorTree<I>
+where
+    I: Copy + Eq + Hash + Ord,
+{
+    /// Returns `true` if `module_id` is reachable from the entries.
+    pub fn is_reachable(&self, module_id: I) -> bool {
+        self.idom.contains_key(&module_id)
+    }
+
+    /// Returns the nearest module which dominates `module_id`.
+    ///
+    /// Returns [None] if `module_id` is not reachable, or if it is reachable
+    /// from multiple entries without a common module.
+    pub fn immediate_dominator(&self, module_id: I) -> Option<I> {
+        self.idom.get(&module_id).copied().flatten()
+    }
+
+    /// Returns the modules which dominate `module_id`, starting with
+    /// `module_id` itself and ending with the outermost one.
+    ///
+    /// Returns an empty list if `module_id` is not reachable.
+    pub fn dominators(&self, module_id: I) -> Vec<I> {
+        if !self.is_reachable(module_id) {
+            return Vec::new();
+        }
+
+        let mut dominators = vec![module_id];
+        let mut cur = module_id;
+
+        while let Some(idom) = self.immediate_dominator(cur) {
+            dominators.push(idom);
+            cur = idom;
+        }
+
+        dominators
+    }
+
+    /// Returns the sorted list of modules dominated by `module_id`, excluding
+    /// `module_id` itself.
+    ///
+    /// For an entry, these are the modules which only that entry reaches.
+    pub fn dominated_by(&self, module_id: I) -> Vec<I> {
+        let mut modules = self
+            .idom
+            .keys()
+            .copied()
+            .filter(|&id| id != module_id && self.dominators(id).contains(&module_id))
+            .collect::<Vec<_>>();
+
+        modules.sort();
+        modules
+    }
+
+    /// Returns the entry if `module_id` is reachable from only one entry.
+    pub fn entry_of(&self, module_id: I) -> Option<I> {
+        self.dominators(module_id)
+            .last()
+            .copied()
+            .filter(|id| self.entries.contains(id))
+    }
+}
+
+impl<G> GraphResult<G>
+where
+    G: DepGraph,
+{
+    /// Computes the dominator tree of the modules reachable from `entries`.
+    ///
+    /// Entries which are not in the graph are ignored.
+    pub fn dominator_tree(&self, entries: &[G::ModuleId]) -> DominatorTree<G::ModuleId> {
+        let entries = entries
+            .iter()
+            .copied()
+            .filter(|&id| self.graph.contains_node(id))
+            .collect::<Vec<_>>();
+
+        // Modules in postorder. A virtual root which imports all entries gets
+        // the index `postorder.len()`.
+        let mut postorder = Vec::new();
+        let mut visited = FxHashSet::default();
+
+        for &entry in &entries {
+            if !visited.insert(entry) {
+                continue;
+            }
+
+            let mut stack = vec![(entry, self.graph.neighbors_directed(entry, Outgoing))];
+
+            while let Some((_, deps)) = stack.last_mut() {
+                match deps.next() {
+                    Some(dep) => {
+                        if visited.insert(dep) {
+                            stack.push((dep, self.graph.neighbors_directed(dep, Outgoing)));
+                        }
+                    }
+                    None => {
+                        let (id, _) = stack.pop().unwrap();
+                        postorder.push(id);
+                    }
+                }
+            }
+        }
+
+        let root = postorder.len();
+        let index_of = postorder
+            .iter()
+            .enumerate()
+            .map(|(idx, &id)| (id, idx))
+            .collect::<FxHashMap<_, _>>();
+
+        let entries = entries.into_iter().collect::<FxHashSet<_>>();
+        let preds = postorder
+            .iter()
+            .map(|&id| {
+                let mut preds = self
+                    .graph
+                    .neighbors_directed(id, Incoming)
+                    .filter_map(|pred| index_of.get(&pred).copied())
+                    .collect::<Vec<_>>();
+
+                if entries.contains(&id) {
+                    preds.push(root);
+                }
+
+                preds
+            })
+            .collect::<Vec<_>>();
+
+        // https://www.cs.tufts.edu/comp/150FP/archive/keith-cooper/dom14.pdf
+        let mut idom = vec![None; root + 1];
+        idom[root] = Some(root);
+
+        let intersect = |idom: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while a < b {
+                    a = idom[a].unwrap();
+                }
+                while b < a {
+                    b = idom[b].unwrap();
+                }
+            }
+            a
+        };
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for idx in (0..root).rev() {
+                let mut new_idom = None;
+
+                for &pred in &preds[idx] {
+                    if idom[pred].is_none() {
+                        continue;
+                    }
+
+                    new_idom = Some(match new_idom {
+                        Some(cur) => intersect(&idom, pred, cur),
+                        None => pred,
+                    });
+                }
+
+                if new_idom.is_some() && idom[idx] != new_idom {
+                    idom[idx] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        let idom = postorder
+            .iter()
+            .zip(idom)
+            .map(|(&id, idom)| {
+                let idom = idom.filter(|&idx| idx != root).map(|idx| postorder[idx]);
+                (id, idom)
+            })
+            .collect();
+
+        DominatorTree { entries, idom }
+    }
+}
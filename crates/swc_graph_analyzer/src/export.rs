@@ -0,0 +1,89 @@
+use std::fmt::Write;
+
+use rustc_hash::FxHashMap;
+use serde_json::json;
+
+use crate::{DepGraph, GraphResult};
+
+impl<G> GraphResult<G>
+where
+    G: DepGraph,
+{
+    /// Renders the graph in the [DOT] format, using `label` for the names of
+    /// the modules.
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn to_dot<F>(&self, mut label: F) -> String
+    where
+        F: FnMut(G::ModuleId) -> String,
+    {
+        let index_of = self.node_indices();
+
+        let mut buf = String::from("digraph {\n");
+
+        for (idx, id) in self.graph.nodes().enumerate() {
+            let label = label(id).replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(buf, "    {idx} [ label = \"{label}\" ]").unwrap();
+        }
+
+        for (src, dst, _) in self.graph.all_edges() {
+            writeln!(buf, "    {} -> {}", index_of[&src], index_of[&dst]).unwrap();
+        }
+
+        buf.push_str("}\n");
+        buf
+    }
+
+    /// Serializes the graph as JSON, using `label` for the names of the
+    /// modules.
+    ///
+    /// Modules are referenced by their index in `nodes`:
+    ///
+    /// ```json
+    /// {
+    ///   "nodes": [{ "id": 0, "label": "a.js" }, { "id": 1, "label": "b.js" }],
+    ///   "edges": [{ "from": 0, "to": 1 }],
+    ///   "cycles": []
+    /// }
+    /// ```
+    pub fn to_json<F>(&self, mut label: F) -> String
+    where
+        F: FnMut(G::ModuleId) -> String,
+    {
+        let index_of = self.node_indices();
+
+        let nodes = self
+            .graph
+            .nodes()
+            .enumerate()
+            .map(|(idx, id)| json!({ "id": idx, "label": label(id) }))
+            .collect::<Vec<_>>();
+
+        let edges = self
+            .graph
+            .all_edges()
+            .map(|(src, dst, _)| json!({ "from": index_of[&src], "to": index_of[&dst] }))
+            .collect::<Vec<_>>();
+
+        let cycles = self
+            .cycles
+            .iter()
+            .map(|cycle| cycle.iter().map(|id| index_of[id]).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        json!({
+            "nodes": nodes,
+            "edges": edges,
+            "cycles": cycles,
+        })
+        .to_string()
+    }
+
+    fn node_indices(&self) -> FxHashMap<G::ModuleId, usize> {
+        self.graph
+            .nodes()
+            .enumerate()
+            .map(|(idx, id)| (id, idx))
+            .collect()
+    }
+}
@@ -4,6 +4,12 @@ use auto_impl::auto_impl;
 use petgraph::{Directed, prelude::GraphMap};
 use rustc_hash::{FxBuildHasher, FxHashSet};
 
+pub use self::dominators::DominatorTree;
+
+mod dominators;
+mod export;
+mod query;
+
 #[auto_impl(&, Box, Rc, Arc)]
 pub trait DepGraph {
     type ModuleId: Debug + Copy + Eq + Hash + Ord;
@@ -12,6 +18,8 @@ pub trait DepGraph {
 }
 
 /// Utility to detect cycles in a dependency graph.
+///
+/// The loaded graph can be queried using the methods of [GraphResult].
 pub struct GraphAnalyzer<G>
 where
     G: DepGraph,
@@ -0,0 +1,160 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use petgraph::{
+    Direction::{Incoming, Outgoing},
+    algo::tarjan_scc,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{DepGraph, GraphResult};
+
+impl<G> GraphResult<G>
+where
+    G: DepGraph,
+{
+    /// Returns the strongly connected components of the graph.
+    ///
+    /// A module which is not part of a cycle is a component by itself. The
+    /// modules of a component are sorted, and the dependencies of a component
+    /// come before the component.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<G::ModuleId>> {
+        let mut components = tarjan_scc(&self.graph);
+
+        for component in &mut components {
+            component.sort();
+        }
+
+        components
+    }
+
+    /// Returns the modules in topological order, where the modules of a cycle
+    /// are condensed into a single entry.
+    ///
+    /// Dependencies come before their dependents. Unlike
+    /// [GraphResult::strongly_connected_components], the order is stable: if
+    /// multiple components are ready, the one with the smallest module comes
+    /// first.
+    pub fn condensed_toposort(&self) -> Vec<Vec<G::ModuleId>> {
+        let components = self.strongly_connected_components();
+
+        let component_of = components
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, component)| component.iter().map(move |&id| (id, idx)))
+            .collect::<FxHashMap<_, _>>();
+
+        // Number of dependencies of each component which are not emitted yet.
+        let mut pending = vec![0usize; components.len()];
+        let mut dependents = vec![Vec::new(); components.len()];
+
+        let mut edges = FxHashSet::default();
+        for (src, dst, _) in self.graph.all_edges() {
+            let (src, dst) = (component_of[&src], component_of[&dst]);
+
+            if src != dst && edges.insert((src, dst)) {
+                pending[src] += 1;
+                dependents[dst].push(src);
+            }
+        }
+
+        let mut ready = pending
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count == 0)
+            .map(|(idx, _)| Reverse((components[idx][0], idx)))
+            .collect::<BinaryHeap<_>>();
+
+        let mut order = Vec::with_capacity(components.len());
+
+        while let Some(Reverse((_, idx))) = ready.pop() {
+            order.push(idx);
+
+            for &dependent in &dependents[idx] {
+                pending[dependent] -= 1;
+
+                if pending[dependent] == 0 {
+                    ready.push(Reverse((components[dependent][0], dependent)));
+                }
+            }
+        }
+
+        debug_assert_eq!(order.len(), components.len());
+
+        let mut components = components.into_iter().map(Some).collect::<Vec<_>>();
+
+        order
+            .into_iter()
+            .map(|idx| components[idx].take().unwrap())
+            .collect()
+    }
+
+    /// Returns the modules which import `module_id` directly.
+    pub fn dependents_of(&self, module_id: G::ModuleId) -> Vec<G::ModuleId> {
+        if !self.graph.contains_node(module_id) {
+            return Vec::new();
+        }
+
+        self.graph.neighbors_directed(module_id, Incoming).collect()
+    }
+
+    /// Returns the modules which import `module_id` directly or indirectly,
+    /// nearest first.
+    ///
+    /// `module_id` itself is included only if it is part of a cycle.
+    pub fn transitive_dependents_of(&self, module_id: G::ModuleId) -> Vec<G::ModuleId> {
+        let mut visited = FxHashSet::default();
+        let mut dependents = Vec::new();
+        let mut queue = VecDeque::from([module_id]);
+
+        while let Some(id) = queue.pop_front() {
+            for dependent in self.dependents_of(id) {
+                if visited.insert(dependent) {
+                    dependents.push(dependent);
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        dependents
+    }
+
+    /// Returns the shortest chain of imports from `from` to `to`, including
+    /// both ends.
+    ///
+    /// Returns [None] if `to` is not reachable from `from`.
+    pub fn shortest_path(&self, from: G::ModuleId, to: G::ModuleId) -> Option<Vec<G::ModuleId>> {
+        if !self.graph.contains_node(from) || !self.graph.contains_node(to) {
+            return None;
+        }
+
+        let mut parents = FxHashMap::default();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(id) = queue.pop_front() {
+            if id == to {
+                let mut path = vec![to];
+                let mut cur = to;
+
+                while cur != from {
+                    cur = parents[&cur];
+                    path.push(cur);
+                }
+
+                path.reverse();
+                return Some(path);
+            }
+
+            for dep in self.graph.neighbors_directed(id, Outgoing) {
+                if dep != from && !parents.contains_key(&dep) {
+                    parents.insert(dep, id);
+                    queue.push_back(dep);
+                }
+            }
+        }
+
+        None
+    }
+}
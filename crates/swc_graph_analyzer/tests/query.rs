@@ -0,0 +1,194 @@
+use swc_graph_analyzer::{DepGraph, GraphAnalyzer, GraphResult};
+
+struct Deps<'a> {
+    deps: &'a [(usize, Vec<usize>)],
+}
+
+impl DepGraph for Deps<'_> {
+    type ModuleId = usize;
+
+    fn deps_of(&self, module_id: Self::ModuleId) -> Vec<Self::ModuleId> {
+        self.deps
+            .iter()
+            .find_map(|(id, deps)| {
+                if *id == module_id {
+                    Some(deps.clone())
+                } else {
+                    None
+                }
+            })
+            .unwrap()
+    }
+}
+
+fn load<'a>(deps: &'a [(usize, Vec<usize>)], entries: &[usize]) -> GraphResult<Deps<'a>> {
+    let _logger = testing::init();
+
+    let mut analyzer = GraphAnalyzer::new(Deps { deps });
+
+    for &entry in entries {
+        analyzer.load(entry);
+    }
+
+    analyzer.into_result()
+}
+
+/// ```text
+/// 0 -> 1 -> 2 -> 3
+///      ^    |
+///      +----+
+/// 4 -> 2
+/// ```
+fn with_cycle() -> Vec<(usize, Vec<usize>)> {
+    vec![
+        (0, vec![1]),
+        (1, vec![2]),
+        (2, vec![1, 3]),
+        (3, Vec::new()),
+        (4, vec![2]),
+    ]
+}
+
+#[test]
+fn strongly_connected_components() {
+    let deps = with_cycle();
+    let res = load(&deps, &[0, 4]);
+
+    assert_eq!(
+        res.strongly_connected_components(),
+        vec![vec![3], vec![1, 2], vec![0], vec![4]]
+    );
+}
+
+#[test]
+fn condensed_toposort() {
+    let deps = with_cycle();
+    let res = load(&deps, &[4, 0]);
+
+    assert_eq!(
+        res.condensed_toposort(),
+        vec![vec![3], vec![1, 2], vec![0], vec![4]]
+    );
+}
+
+#[test]
+fn condensed_toposort_is_stable() {
+    let deps = [
+        (0, vec![3, 2, 1]),
+        (1, Vec::new()),
+        (2, Vec::new()),
+        (3, vec![1]),
+    ];
+    let res = load(&deps, &[0]);
+
+    assert_eq!(
+        res.condensed_toposort(),
+        vec![vec![1], vec![2], vec![3], vec![0]]
+    );
+}
+
+#[test]
+fn dependents() {
+    let deps = with_cycle();
+    let res = load(&deps, &[0, 4]);
+
+    let mut direct = res.dependents_of(2);
+    direct.sort();
+    assert_eq!(direct, vec![1, 4]);
+
+    let mut transitive = res.transitive_dependents_of(3);
+    transitive.sort();
+    assert_eq!(transitive, vec![0, 1, 2, 4]);
+
+    assert_eq!(res.dependents_of(0), Vec::<usize>::new());
+    assert_eq!(res.transitive_dependents_of(0), Vec::<usize>::new());
+}
+
+#[test]
+fn shortest_path() {
+    let deps = [
+        (0, vec![1, 4]),
+        (1, vec![2]),
+        (2, vec![3]),
+        (3, Vec::new()),
+        (4, vec![3]),
+    ];
+    let res = load(&deps, &[0]);
+
+    assert_eq!(res.shortest_path(0, 3), Some(vec![0, 4, 3]));
+    assert_eq!(res.shortest_path(1, 3), Some(vec![1, 2, 3]));
+    assert_eq!(res.shortest_path(0, 0), Some(vec![0]));
+    assert_eq!(res.shortest_path(3, 0), None);
+}
+
+#[test]
+fn dominator_tree() {
+    // 0 -> 1 -> 3
+    // 0 -> 2 -> 3 -> 4
+    // 5 -> 4, 5 -> 6
+    let deps = [
+        (0, vec![1, 2]),
+        (1, vec![3]),
+        (2, vec![3]),
+        (3, vec![4]),
+        (4, Vec::new()),
+        (5, vec![4, 6]),
+        (6, Vec::new()),
+    ];
+    let res = load(&deps, &[0, 5]);
+
+    let tree = res.dominator_tree(&[0, 5]);
+
+    assert_eq!(tree.immediate_dominator(3), Some(0));
+    assert_eq!(tree.dominators(3), vec![3, 0]);
+    assert_eq!(tree.immediate_dominator(4), None);
+    assert_eq!(tree.dominators(4), vec![4]);
+
+    assert_eq!(tree.dominated_by(0), vec![1, 2, 3]);
+    assert_eq!(tree.dominated_by(5), vec![6]);
+
+    assert_eq!(tree.entry_of(3), Some(0));
+    assert_eq!(tree.entry_of(6), Some(5));
+    assert_eq!(tree.entry_of(4), None);
+
+    // With a single entry, modules reachable only from the other one are
+    // not part of the tree.
+    let tree = res.dominator_tree(&[0]);
+
+    assert_eq!(tree.immediate_dominator(4), Some(3));
+    assert!(!tree.is_reachable(6));
+}
+
+#[test]
+fn dominator_tree_with_cycle() {
+    let deps = with_cycle();
+    let res = load(&deps, &[0]);
+
+    let tree = res.dominator_tree(&[0]);
+
+    assert_eq!(tree.dominators(3), vec![3, 2, 1, 0]);
+    assert_eq!(tree.dominated_by(1), vec![2, 3]);
+}
+
+#[test]
+fn to_dot() {
+    let deps = [(0, vec![1]), (1, Vec::new())];
+    let res = load(&deps, &[0]);
+
+    assert_eq!(
+        res.to_dot(|id| format!("\"{id}\".js")),
+        "digraph {\n    0 [ label = \"\\\"0\\\".js\" ]\n    1 [ label = \"\\\"1\\\".js\" ]\n    0 \
+         -> 1\n}\n"
+    );
+}
+
+#[test]
+fn to_json() {
+    let deps = [(0, vec![1, 2]), (1, vec![2]), (2, Vec::new())];
+    let res = load(&deps, &[0]);
+
+    assert_eq!(
+        res.to_json(|id| format!("{id}.js")),
+        r#"{"cycles":[],"edges":[{"from":0,"to":1},{"from":1,"to":2},{"from":0,"to":2}],"nodes":[{"id":0,"label":"0.js"},{"id":1,"label":"1.js"},{"id":2,"label":"2.js"}]}"#
+    );
+}
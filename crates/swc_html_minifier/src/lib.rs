@@ -2053,6 +2053,7 @@ impl<C: MinifyCss> Minifier<'_, C> {
                 unresolved_mark,
                 top_level_mark,
                 mangle_name_cache: None,
+                size_reporter: None,
             },
         );
 
@@ -0,0 +1,576 @@
+//! Runner backend for swc plugins built as native shared libraries.
+//!
+//! A native plugin is a `cdylib` built from the same `#[plugin_transform]`
+//! source as a Wasm plugin. It receives the same serialized program, but runs
+//! in the process of the host without sandboxing, so only trusted plugins
+//! should be loaded.
+//!
+//! Native plugins call the functions imported from the host through
+//! [NativePluginHost], so they have access to the same comments, source map
+//! and metadata as Wasm plugins. [runtime::Limits] can't be enforced, though.
+
+use std::{
+    ffi::c_void,
+    io::Write,
+    panic::{AssertUnwindSafe, catch_unwind},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, bail, ensure};
+use rustc_hash::FxHashMap;
+use swc_common::plugin::native::{
+    NATIVE_PLUGIN_ABI_VERSION, NATIVE_PLUGIN_PANICKED, NativePluginHost,
+};
+use swc_plugin_runner::runtime;
+
+const IDENTIFIER: &str = concat!("native", "-", "v1");
+
+/// Magic number of Wasm modules, used by [NativeOrWasmRuntime] to tell them
+/// apart from shared libraries.
+const WASM_MAGIC: &[u8] = b"\0asm";
+
+/// Magic numbers of the shared libraries which can be loaded as native
+/// plugins: ELF, Mach-O (32/64-bit in both byte orders, and universal
+/// binaries) and PE.
+const SHARED_LIBRARY_MAGICS: &[&[u8]] = &[
+    b"\x7fELF",
+    b"\xfe\xed\xfa\xce",
+    b"\xfe\xed\xfa\xcf",
+    b"\xce\xfa\xed\xfe",
+    b"\xcf\xfa\xed\xfe",
+    b"\xca\xfe\xba\xbe",
+    b"\xca\xfe\xba\xbf",
+    b"MZ",
+];
+
+/// Offset of the first allocation in [NativeMemory], so a valid pointer is
+/// never `0`.
+const RESERVED_MEMORY: usize = 8;
+
+type CorePkgDiagFn = unsafe extern "C" fn(host: *const NativePluginHost) -> u32;
+
+type ProcessFn = unsafe extern "C" fn(
+    host: *const NativePluginHost,
+    ast_ptr: *const u8,
+    ast_ptr_len: usize,
+    unresolved_mark: u32,
+    should_enable_comments_proxy: i32,
+) -> u32;
+
+/// Runs plugins built as native shared libraries.
+#[derive(Clone, Copy, Debug)]
+pub struct NativeRuntime;
+
+/// Runs plugins built as native shared libraries, and delegates Wasm plugins
+/// to another runtime.
+#[derive(Clone, Debug)]
+pub struct NativeOrWasmRuntime {
+    wasm: Arc<dyn runtime::Runtime>,
+    identifier: &'static str,
+}
+
+struct NativeLibrary {
+    core_pkg_diag: CorePkgDiagFn,
+    process: ProcessFn,
+
+    // The library must be unloaded before its file is removed.
+    _library: libloading::Library,
+    _path: tempfile::TempPath,
+}
+
+#[derive(Clone)]
+struct NativeCache(Arc<NativeLibrary>);
+
+struct NativeInstance {
+    library: Arc<NativeLibrary>,
+    imports: FxHashMap<String, runtime::Func>,
+    memory: NativeMemory,
+}
+
+/// Memory shared with the functions imported from the host.
+///
+/// Native plugins use their own allocator, so this only holds the bytes
+/// exchanged with the host during a call to the plugin. It's cleared once the
+/// call returns.
+struct NativeMemory {
+    data: Vec<u8>,
+}
+
+/// State of a call to a native plugin, pointed to by [NativePluginHost::ctx].
+struct CallContext<'a> {
+    imports: &'a FxHashMap<String, runtime::Func>,
+    memory: &'a mut NativeMemory,
+    error: Option<anyhow::Error>,
+}
+
+fn is_shared_library(bytes: &[u8]) -> bool {
+    SHARED_LIBRARY_MAGICS
+        .iter()
+        .any(|magic| bytes.starts_with(magic))
+}
+
+/// Returns whether `bytes` is a native plugin, or an error if it's neither a
+/// Wasm module nor a shared library.
+fn is_native_module(bytes: &[u8]) -> anyhow::Result<bool> {
+    if bytes.starts_with(WASM_MAGIC) {
+        Ok(false)
+    } else if is_shared_library(bytes) {
+        Ok(true)
+    } else {
+        bail!("plugin is neither a Wasm module nor a shared library")
+    }
+}
+
+impl NativeLibrary {
+    fn load(bytes: &[u8]) -> anyhow::Result<Self> {
+        ensure!(
+            is_shared_library(bytes),
+            "native plugin is not a shared library"
+        );
+
+        // The runtime only receives the bytes of the plugin, so they are copied
+        // to a file which can be loaded.
+        let mut file = tempfile::Builder::new()
+            .prefix("swc-plugin-")
+            .suffix(std::env::consts::DLL_SUFFIX)
+            .tempfile()?;
+        file.write_all(bytes)?;
+        let path = file.into_temp_path();
+
+        unsafe {
+            let library =
+                libloading::Library::new(&path).context("failed to load native plugin")?;
+
+            let abi_version = library
+                .get::<unsafe extern "C" fn() -> u32>(b"__swc_native_plugin_abi_version")
+                .context("not a native swc plugin")?();
+            ensure!(
+                abi_version == NATIVE_PLUGIN_ABI_VERSION,
+                "native plugin ABI version mismatch (plugin: {abi_version}, host: \
+                 {NATIVE_PLUGIN_ABI_VERSION})"
+            );
+
+            let core_pkg_diag = *library
+                .get::<CorePkgDiagFn>(b"__swc_native_get_transform_plugin_core_pkg_diag")?;
+            let process =
+                *library.get::<ProcessFn>(b"__swc_native_transform_plugin_process_impl")?;
+
+            Ok(NativeLibrary {
+                core_pkg_diag,
+                process,
+                _library: library,
+                _path: path,
+            })
+        }
+    }
+}
+
+impl runtime::Runtime for NativeRuntime {
+    fn identifier(&self) -> &'static str {
+        IDENTIFIER
+    }
+
+    fn prepare_module(&self, bytes: &[u8]) -> anyhow::Result<runtime::ModuleCache> {
+        let library = NativeLibrary::load(bytes)?;
+        Ok(runtime::ModuleCache(Box::new(NativeCache(Arc::new(
+            library,
+        )))))
+    }
+
+    fn clone_cache(&self, cache: &runtime::ModuleCache) -> Option<runtime::ModuleCache> {
+        let cache: &NativeCache = cache.0.downcast_ref().unwrap();
+        Some(runtime::ModuleCache(Box::new(cache.clone())))
+    }
+
+    // Loaded libraries can't be stored, so `load_cache` and `store_cache` are
+    // no-ops.
+
+    fn init(
+        &self,
+        _name: &str,
+        imports: Vec<(String, runtime::Func)>,
+        _envs: Vec<(String, String)>,
+        module: runtime::Module,
+        limits: runtime::Limits,
+    ) -> anyhow::Result<Box<dyn runtime::Instance>> {
+        // Native code can't be metered.
+        if limits != runtime::Limits::default() {
+            bail!("resource limits are not supported by native plugins");
+        }
+
+        let NativeCache(library) = match module {
+            runtime::Module::Cache(cache) => *cache.0.downcast::<NativeCache>().unwrap(),
+            runtime::Module::Bytes(bytes) => NativeCache(Arc::new(NativeLibrary::load(&bytes)?)),
+        };
+
+        let mut instance = NativeInstance {
+            library,
+            imports: imports.into_iter().collect(),
+            memory: NativeMemory::new(),
+        };
+
+        let core_pkg_diag = instance.library.core_pkg_diag;
+        instance.call(|host| unsafe { core_pkg_diag(host) })?;
+
+        Ok(Box::new(instance))
+    }
+}
+
+impl NativeInstance {
+    /// Calls the plugin with a [NativePluginHost] which gives access to the
+    /// imported functions.
+    fn call(&mut self, f: impl FnOnce(*const NativePluginHost) -> u32) -> anyhow::Result<u32> {
+        let mut ctx = CallContext {
+            imports: &self.imports,
+            memory: &mut self.memory,
+            error: None,
+        };
+        let host = NativePluginHost {
+            ctx: &mut ctx as *mut CallContext as *mut c_void,
+            send: host_send,
+            request: host_request,
+            call: host_call,
+        };
+
+        let ret = f(&host);
+        let error = ctx.error;
+
+        self.memory.clear();
+        if let Some(err) = error {
+            return Err(err);
+        }
+        if ret == NATIVE_PLUGIN_PANICKED {
+            bail!("native plugin panicked");
+        }
+
+        Ok(ret)
+    }
+}
+
+impl runtime::Instance for NativeInstance {
+    fn transform(
+        &mut self,
+        program_ptr: u32,
+        program_len: u32,
+        unresolved_mark: u32,
+        should_enable_comments_proxy: u32,
+    ) -> anyhow::Result<u32> {
+        // The memory may be reallocated while the plugin runs.
+        let program = self.memory.slice(program_ptr, program_len)?.to_vec();
+        let process = self.library.process;
+
+        self.call(|host| unsafe {
+            process(
+                host,
+                program.as_ptr(),
+                program.len(),
+                unresolved_mark,
+                should_enable_comments_proxy as i32,
+            )
+        })
+    }
+
+    fn caller(&mut self) -> anyhow::Result<Box<dyn runtime::Caller<'_> + '_>> {
+        Ok(Box::new(&mut self.memory))
+    }
+
+    fn cache(&self) -> Option<runtime::ModuleCache> {
+        Some(runtime::ModuleCache(Box::new(NativeCache(
+            self.library.clone(),
+        ))))
+    }
+
+    fn snapshot(&mut self) -> anyhow::Result<bool> {
+        // The memory is cleared after each call, so there's nothing to save.
+        Ok(true)
+    }
+
+    fn reset(&mut self) -> anyhow::Result<bool> {
+        self.memory.clear();
+        Ok(true)
+    }
+}
+
+impl NativeMemory {
+    fn new() -> Self {
+        NativeMemory {
+            data: vec![0; RESERVED_MEMORY],
+        }
+    }
+
+    fn clear(&mut self) {
+        self.data.truncate(RESERVED_MEMORY);
+    }
+
+    fn range(&self, ptr: u32, len: usize) -> anyhow::Result<std::ops::Range<usize>> {
+        let start = ptr as usize;
+        let end = start
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .context("out of bounds memory access")?;
+        Ok(start..end)
+    }
+
+    fn slice(&self, ptr: u32, len: u32) -> anyhow::Result<&[u8]> {
+        let range = self.range(ptr, len as usize)?;
+        Ok(&self.data[range])
+    }
+
+    fn write(&mut self, ptr: u32, buf: &[u8]) -> anyhow::Result<()> {
+        let range = self.range(ptr, buf.len())?;
+        self.data[range].copy_from_slice(buf);
+        Ok(())
+    }
+
+    fn alloc(&mut self, size: u32) -> anyhow::Result<u32> {
+        let start = self.data.len().next_multiple_of(8);
+        let end = start + size as usize;
+        let ptr = u32::try_from(start)
+            .ok()
+            .filter(|_| u32::try_from(end).is_ok())
+            .context("out of memory")?;
+
+        self.data.resize(end, 0);
+        Ok(ptr)
+    }
+
+    /// Copies `bytes` to a new allocation.
+    fn alloc_bytes(&mut self, bytes: &[u8]) -> anyhow::Result<u32> {
+        let ptr = self.alloc(u32::try_from(bytes.len())?)?;
+        self.write(ptr, bytes)?;
+        Ok(ptr)
+    }
+}
+
+impl<'a> runtime::Caller<'a> for &mut NativeMemory {
+    fn read_buf(&self, ptr: u32, buf: &mut [u8]) -> anyhow::Result<()> {
+        buf.copy_from_slice(self.slice(ptr, buf.len() as u32)?);
+        Ok(())
+    }
+
+    fn write_buf(&mut self, ptr: u32, buf: &[u8]) -> anyhow::Result<()> {
+        self.write(ptr, buf)
+    }
+
+    fn alloc(&mut self, size: u32) -> anyhow::Result<u32> {
+        NativeMemory::alloc(self, size)
+    }
+
+    fn free(&mut self, _ptr: u32, _size: u32) -> anyhow::Result<u32> {
+        // Freed all at once by `NativeMemory::clear`.
+        Ok(0)
+    }
+}
+
+impl CallContext<'_> {
+    fn call_import(&mut self, name: &str, args: &[i32]) -> anyhow::Result<i32> {
+        let func = self
+            .imports
+            .get(name)
+            .with_context(|| format!("unknown host function `{name}`"))?;
+        ensure!(
+            func.sign.0 as usize == args.len(),
+            "host function `{name}` expects {} arguments, got {}",
+            func.sign.0,
+            args.len()
+        );
+
+        let mut rets = vec![0; func.sign.1 as usize];
+        (func.func)(&mut self.memory, args, &mut rets);
+
+        Ok(rets.first().copied().unwrap_or(0))
+    }
+}
+
+/// Runs a callback of [NativePluginHost], which must not unwind into the
+/// plugin.
+///
+/// Errors are stored in the [CallContext] to be returned once the plugin
+/// returns, as they can't be passed through the C ABI.
+unsafe fn with_context(
+    ctx: *mut c_void,
+    name_ptr: *const u8,
+    name_len: usize,
+    f: impl FnOnce(&mut CallContext, &str) -> anyhow::Result<i64>,
+) -> i64 {
+    let ctx = unsafe { &mut *(ctx as *mut CallContext) };
+
+    let ret = catch_unwind(AssertUnwindSafe(|| {
+        let name = unsafe { std::slice::from_raw_parts(name_ptr, name_len) };
+        let name = std::str::from_utf8(name).context("invalid host function name")?;
+        f(ctx, name)
+    }))
+    .unwrap_or_else(|_| Err(anyhow::anyhow!("host function panicked")));
+
+    ret.unwrap_or_else(|err| {
+        ctx.error.get_or_insert(err);
+        -1
+    })
+}
+
+unsafe extern "C" fn host_send(
+    ctx: *mut c_void,
+    name_ptr: *const u8,
+    name_len: usize,
+    bytes_ptr: *const u8,
+    bytes_len: usize,
+) -> i32 {
+    let ret = unsafe {
+        with_context(ctx, name_ptr, name_len, |ctx, name| {
+            let bytes = std::slice::from_raw_parts(bytes_ptr, bytes_len);
+            let ptr = ctx.memory.alloc_bytes(bytes)?;
+
+            ctx.call_import(name, &[ptr as i32, bytes.len() as i32])?;
+            Ok(0)
+        })
+    };
+    ret as i32
+}
+
+#[allow(clippy::too_many_arguments)]
+unsafe extern "C" fn host_request(
+    ctx: *mut c_void,
+    name_ptr: *const u8,
+    name_len: usize,
+    args_ptr: *const i32,
+    args_len: usize,
+    ret_ptr: *mut *const u8,
+    ret_len: *mut usize,
+) -> i32 {
+    let ret = unsafe {
+        with_context(ctx, name_ptr, name_len, |ctx, name| {
+            // The host writes a `[ptr, len]` pair to the last argument.
+            let slot = ctx.memory.alloc(8)?;
+
+            let mut args = std::slice::from_raw_parts(args_ptr, args_len).to_vec();
+            args.push(slot as i32);
+
+            let ret = ctx.call_import(name, &args)?;
+            if ret != 0 {
+                let fatptr = ctx.memory.slice(slot, 8)?;
+                let ptr = u32::from_le_bytes(fatptr[..4].try_into().unwrap());
+                let len = u32::from_le_bytes(fatptr[4..].try_into().unwrap());
+
+                let bytes = ctx.memory.slice(ptr, len)?;
+                *ret_ptr = bytes.as_ptr();
+                *ret_len = bytes.len();
+            }
+
+            Ok(ret as i64)
+        })
+    };
+    ret as i32
+}
+
+unsafe extern "C" fn host_call(
+    ctx: *mut c_void,
+    name_ptr: *const u8,
+    name_len: usize,
+    args_ptr: *const i32,
+    args_len: usize,
+    buf_ptr: *mut u8,
+    buf_len: usize,
+) -> i64 {
+    unsafe {
+        with_context(ctx, name_ptr, name_len, |ctx, name| {
+            let mut args = std::slice::from_raw_parts(args_ptr, args_len).to_vec();
+
+            if buf_ptr.is_null() {
+                return Ok(ctx.call_import(name, &args)? as u32 as i64);
+            }
+
+            let buf = std::slice::from_raw_parts_mut(buf_ptr, buf_len);
+            let ptr = ctx.memory.alloc_bytes(buf)?;
+            args.push(ptr as i32);
+
+            let ret = ctx.call_import(name, &args)?;
+            buf.copy_from_slice(ctx.memory.slice(ptr, buf.len() as u32)?);
+
+            Ok(ret as u32 as i64)
+        })
+    }
+}
+
+impl NativeOrWasmRuntime {
+    pub fn new(wasm: Arc<dyn runtime::Runtime>) -> Self {
+        // Identifiers are `&'static str`, so they are interned.
+        static IDENTIFIERS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+        let identifier = format!("{IDENTIFIER}+{}", wasm.identifier());
+        let mut identifiers = IDENTIFIERS.lock().unwrap();
+        let identifier = match identifiers.iter().find(|&&id| id == identifier) {
+            Some(&identifier) => identifier,
+            None => {
+                let identifier = &*Box::leak(identifier.into_boxed_str());
+                identifiers.push(identifier);
+                identifier
+            }
+        };
+
+        NativeOrWasmRuntime { wasm, identifier }
+    }
+}
+
+fn is_native(cache: &runtime::ModuleCache) -> bool {
+    cache.0.is::<NativeCache>()
+}
+
+impl runtime::Runtime for NativeOrWasmRuntime {
+    fn identifier(&self) -> &'static str {
+        self.identifier
+    }
+
+    fn prepare_module(&self, bytes: &[u8]) -> anyhow::Result<runtime::ModuleCache> {
+        if is_native_module(bytes)? {
+            NativeRuntime.prepare_module(bytes)
+        } else {
+            self.wasm.prepare_module(bytes)
+        }
+    }
+
+    fn init(
+        &self,
+        name: &str,
+        imports: Vec<(String, runtime::Func)>,
+        envs: Vec<(String, String)>,
+        module: runtime::Module,
+        limits: runtime::Limits,
+    ) -> anyhow::Result<Box<dyn runtime::Instance>> {
+        let native = match &module {
+            runtime::Module::Cache(cache) => is_native(cache),
+            runtime::Module::Bytes(bytes) => is_native_module(bytes)?,
+        };
+
+        if native {
+            NativeRuntime.init(name, imports, envs, module, limits)
+        } else {
+            self.wasm.init(name, imports, envs, module, limits)
+        }
+    }
+
+    fn clone_cache(&self, cache: &runtime::ModuleCache) -> Option<runtime::ModuleCache> {
+        if is_native(cache) {
+            NativeRuntime.clone_cache(cache)
+        } else {
+            self.wasm.clone_cache(cache)
+        }
+    }
+
+    unsafe fn load_cache(&self, path: &Path) -> Option<runtime::ModuleCache> {
+        unsafe { self.wasm.load_cache(path) }
+    }
+
+    fn store_cache(&self, path: &Path, cache: &runtime::ModuleCache) -> anyhow::Result<()> {
+        if is_native(cache) {
+            return Ok(());
+        }
+
+        self.wasm.store_cache(path, cache)
+    }
+
+    fn metered(&self) -> Option<Arc<dyn runtime::Runtime>> {
+        let wasm = self.wasm.metered()?;
+        Some(Arc::new(NativeOrWasmRuntime::new(wasm)))
+    }
+}
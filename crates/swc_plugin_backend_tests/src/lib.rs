@@ -1 +1,79 @@
+/// Assembles a module exporting the interface of a plugin, whose transform
+/// runs `transform`.
+///
+/// The module has a memory of one page, and a mutable `i32` global which is
+/// not exported.
+///
+/// Plugins can't be built in all environments, so the module is written by
+/// hand.
+pub fn plugin_module(transform: &[u8]) -> Box<[u8]> {
+    fn section(module: &mut Vec<u8>, id: u8, content: &[u8]) {
+        module.push(id);
+        leb128(module, content.len());
+        module.extend_from_slice(content);
+    }
 
+    fn leb128(buf: &mut Vec<u8>, mut value: usize) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                buf.push(byte);
+                break;
+            }
+            buf.push(byte | 0x80);
+        }
+    }
+
+    fn code(code: &mut Vec<u8>, body: &[u8]) {
+        // No locals, and the trailing `end`.
+        leb128(code, body.len() + 2);
+        code.push(0x00);
+        code.extend_from_slice(body);
+        code.push(0x0b);
+    }
+
+    let mut module = b"\0asm\x01\0\0\0".to_vec();
+
+    section(
+        &mut module,
+        1,
+        &[
+            0x04, // types
+            0x60, 0x01, 0x7f, 0x01, 0x7f, // (i32) -> i32
+            0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, // (i32, i32) -> i32
+            0x60, 0x04, 0x7f, 0x7f, 0x7f, 0x7f, 0x01, 0x7f, // (i32, i32, i32, i32) -> i32
+            0x60, 0x00, 0x01, 0x7f, // () -> i32
+        ],
+    );
+    section(&mut module, 3, &[0x04, 0x00, 0x01, 0x02, 0x03]);
+    // A single memory of one page.
+    section(&mut module, 5, &[0x01, 0x00, 0x01]);
+    // `(global (mut i32) (i32.const 0))`
+    section(&mut module, 6, &[0x01, 0x7f, 0x01, 0x41, 0x00, 0x0b]);
+
+    let exports: [(&str, u8, u8); 5] = [
+        ("memory", 0x02, 0),
+        ("__alloc", 0x00, 0),
+        ("__free", 0x00, 1),
+        ("__transform_plugin_process_impl", 0x00, 2),
+        ("__get_transform_plugin_core_pkg_diag", 0x00, 3),
+    ];
+    let mut content = vec![exports.len() as u8];
+    for (name, kind, index) in exports {
+        leb128(&mut content, name.len());
+        content.extend_from_slice(name.as_bytes());
+        content.extend_from_slice(&[kind, index]);
+    }
+    section(&mut module, 7, &content);
+
+    let mut content = vec![0x04];
+    // `__alloc`, `__free` and `__get_transform_plugin_core_pkg_diag` return 0.
+    code(&mut content, &[0x41, 0x00]);
+    code(&mut content, &[0x41, 0x00]);
+    code(&mut content, transform);
+    code(&mut content, &[0x41, 0x00]);
+    section(&mut module, 10, &content);
+
+    module.into_boxed_slice()
+}
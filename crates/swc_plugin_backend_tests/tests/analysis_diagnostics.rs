@@ -0,0 +1,97 @@
+use swc_common::{
+    BytePos, DUMMY_SP, FileName, Span,
+    errors::{DiagnosticId, Level},
+    plugin::{
+        diagnostics::{
+            PluginAnalysisDiagnostic, PluginDiagnosticSeverity, PluginSuggestedFix, PluginTextEdit,
+        },
+        serialized::{PluginSerializedBytes, VersionedSerializable},
+    },
+};
+use swc_transform_common::diagnostics::{capture, report};
+
+fn diagnostic(severity: PluginDiagnosticSeverity, span: Span) -> PluginAnalysisDiagnostic {
+    PluginAnalysisDiagnostic {
+        span,
+        severity,
+        code: Some("no-console".into()),
+        message: "Unexpected console statement".into(),
+        fixes: vec![PluginSuggestedFix {
+            message: "Remove the statement".into(),
+            edits: vec![PluginTextEdit {
+                span,
+                text: String::new(),
+            }],
+        }],
+    }
+}
+
+#[test]
+fn serialization_roundtrip() {
+    let diagnostic = diagnostic(PluginDiagnosticSeverity::Warning, DUMMY_SP);
+
+    let serialized =
+        PluginSerializedBytes::try_serialize(&VersionedSerializable::new(diagnostic.clone()))
+            .unwrap();
+    let deserialized = serialized
+        .deserialize::<PluginAnalysisDiagnostic>()
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(deserialized, diagnostic);
+}
+
+#[test]
+fn captured() {
+    let ((), diagnostics) = capture(|| {
+        report(diagnostic(PluginDiagnosticSeverity::Error, DUMMY_SP));
+        report(diagnostic(PluginDiagnosticSeverity::Note, DUMMY_SP));
+    });
+
+    assert_eq!(
+        diagnostics.iter().map(|d| d.severity).collect::<Vec<_>>(),
+        [
+            PluginDiagnosticSeverity::Error,
+            PluginDiagnosticSeverity::Note
+        ]
+    );
+}
+
+#[test]
+fn to_diagnostic() {
+    let span = Span::new(BytePos(1), BytePos(20));
+    let diagnostic = diagnostic(PluginDiagnosticSeverity::Warning, span).to_diagnostic();
+
+    assert_eq!(diagnostic.level, Level::Warning);
+    assert_eq!(
+        diagnostic.code,
+        Some(DiagnosticId::Lint("no-console".into()))
+    );
+    assert_eq!(diagnostic.span.primary_span(), Some(span));
+    assert_eq!(diagnostic.children[0].level, Level::Help);
+    assert_eq!(diagnostic.suggestions[0].msg, "Remove the statement");
+}
+
+#[test]
+fn emitted_to_handler_when_not_captured() {
+    let err = testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(FileName::Anon.into(), "console.log(foo)");
+
+        report(diagnostic(
+            PluginDiagnosticSeverity::Error,
+            Span::new(fm.start_pos, fm.end_pos),
+        ));
+
+        if handler.has_errors() {
+            Err(())
+        } else {
+            Ok(())
+        }
+    })
+    .unwrap_err();
+
+    assert!(
+        err.to_string().contains("Unexpected console statement"),
+        "{err}"
+    );
+}
@@ -17,7 +17,9 @@ use swc_common::{
 use swc_ecma_ast::{CallExpr, Callee, EsVersion, Expr, Lit, MemberExpr, Program, Str};
 use swc_ecma_parser::{Syntax, parse_file_as_program};
 use swc_ecma_visit::{Visit, VisitWith};
-use swc_plugin_runner::{plugin_module_bytes::CompiledPluginModuleBytes, runtime::Runtime};
+use swc_plugin_runner::{
+    instance_pool::InstancePool, plugin_module_bytes::CompiledPluginModuleBytes, runtime::Runtime,
+};
 use testing::CARGO_TARGET_DIR;
 
 /// Returns the path to the built plugin
@@ -73,7 +75,9 @@ impl Visit for TestVisitor {
 }
 
 fn internal(rt: Arc<dyn Runtime>, module: &'static CompiledPluginModuleBytes) {
-    use swc_common::plugin::serialized::VersionedSerializable;
+    use swc_common::plugin::{
+        diagnostics::PluginDiagnosticSeverity, serialized::VersionedSerializable,
+    };
     use swc_transform_common::output::capture;
 
     // run single plugin
@@ -124,14 +128,22 @@ fn internal(rt: Arc<dyn Runtime>, module: &'static CompiledPluginModuleBytes) {
             .is_empty());
          */
 
-        let (program_bytes, captured_output) = capture(|| {
-            plugin_transform_executor
-                .transform(&program, Some(false))
-                .expect("Plugin should apply transform")
-        });
+        let ((program_bytes, captured_output), diagnostics) =
+            swc_transform_common::diagnostics::capture(|| {
+                capture(|| {
+                    plugin_transform_executor
+                        .transform(&program, Some(false))
+                        .expect("Plugin should apply transform")
+                })
+            });
         let captured_output = serde_json::to_string(&captured_output).unwrap();
         assert_eq!(captured_output, "{\"foo\":\"bar\"}");
 
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, PluginDiagnosticSeverity::Warning);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("test-rule"));
+        assert_eq!(diagnostics[0].fixes.len(), 1);
+
         let program: Program = program_bytes
             .deserialize()
             .expect("Should able to deserialize")
@@ -203,6 +215,76 @@ fn internal(rt: Arc<dyn Runtime>, module: &'static CompiledPluginModuleBytes) {
         Ok(())
     })
     .expect("Should able to run single plugin transform with handler");
+
+    // run single plugin with an instance pool
+    testing::run_test(false, |cm, _handler| {
+        let pool = Arc::new(InstancePool::new(1));
+
+        for _ in 0..2 {
+            let fm = cm.new_source_file(FileName::Anon.into(), "console.log(foo)");
+
+            let program = parse_file_as_program(
+                &fm,
+                Syntax::Es(Default::default()),
+                EsVersion::latest(),
+                None,
+                &mut Vec::new(),
+            )
+            .unwrap();
+
+            let program =
+                PluginSerializedBytes::try_serialize(&VersionedSerializable::new(program))
+                    .expect("Should serializable");
+            let experimental_metadata: FxHashMap<String, String> = [
+                (
+                    "TestExperimental".to_string(),
+                    "ExperimentalValue".to_string(),
+                ),
+                ("OtherTest".to_string(), "OtherVal".to_string()),
+            ]
+            .into_iter()
+            .collect();
+
+            let mut plugin_transform_executor =
+                swc_plugin_runner::create_plugin_transform_executor(
+                    &cm,
+                    &Mark::new(),
+                    &Arc::new(TransformPluginMetadataContext::new(
+                        None,
+                        "development".to_string(),
+                        Some(experimental_metadata),
+                    )),
+                    None,
+                    Box::new(module.clone_module(&*rt)),
+                    Some(json!({ "pluginConfig": "testValue" })),
+                    rt.clone(),
+                )
+                .with_instance_pool(pool.clone());
+
+            let (program_bytes, _) = capture(|| {
+                plugin_transform_executor
+                    .transform(&program, Some(false))
+                    .expect("Plugin should apply transform")
+            });
+
+            let program: Program = program_bytes
+                .deserialize()
+                .expect("Should able to deserialize")
+                .into_inner();
+            let mut visitor = TestVisitor {
+                plugin_transform_found: false,
+            };
+            program.visit_with(&mut visitor);
+            assert!(visitor.plugin_transform_found);
+        }
+
+        let stats = pool.stats();
+        assert_eq!(stats.created, 1);
+        assert_eq!(stats.reused, 1);
+
+        Ok(())
+    })
+    .expect("Should able to reuse plugin instances");
 }
 
 #[test]
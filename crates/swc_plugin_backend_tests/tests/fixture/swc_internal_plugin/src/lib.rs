@@ -1,5 +1,11 @@
 use swc_core::{
-    common::{SourceMapper, DUMMY_SP},
+    common::{
+        plugin::diagnostics::{
+            PluginAnalysisDiagnostic, PluginDiagnosticSeverity, PluginSuggestedFix,
+            PluginTextEdit,
+        },
+        Mark, SourceMapper, SyntaxContext, DUMMY_SP,
+    },
     ecma::{ast::*, atoms::*, visit::*},
     plugin::{
         errors::HANDLER,
@@ -8,7 +14,7 @@ use swc_core::{
     },
     quote,
 };
-use swc_transform_common::output::experimental_emit;
+use swc_transform_common::{diagnostics::report, output::experimental_emit};
 
 struct ConsoleOutputReplacer {
     metadata: TransformPluginProgramMetadata,
@@ -135,10 +141,33 @@ pub fn process(mut program: Program, metadata: TransformPluginProgramMetadata) -
 
     dbg!();
 
+    let mark = Mark::fresh(metadata.unresolved_mark);
+    let ctxt = SyntaxContext::empty().apply_mark(mark);
+    assert_eq!(ctxt.outer(), mark);
+    assert!(mark.is_descendant_of(metadata.unresolved_mark));
+
+    dbg!();
+
     experimental_emit("foo".into(), "bar".into());
 
     dbg!();
 
+    report(PluginAnalysisDiagnostic {
+        span: DUMMY_SP,
+        severity: PluginDiagnosticSeverity::Warning,
+        code: Some("test-rule".into()),
+        message: "Test analysis diagnostic from plugin".into(),
+        fixes: vec![PluginSuggestedFix {
+            message: "Remove it".into(),
+            edits: vec![PluginTextEdit {
+                span: DUMMY_SP,
+                text: String::new(),
+            }],
+        }],
+    });
+
+    dbg!();
+
     program.visit_mut_with(&mut ConsoleOutputReplacer { metadata });
     program
 }
@@ -0,0 +1,95 @@
+use swc_plugin_backend_tests::plugin_module;
+use swc_plugin_runner::runtime::{Instance, Limits, Module, Runtime};
+
+/// Increments the `i32` at `0` and returns it.
+const INCREMENT: &[u8] = &[
+    0x41, 0x00, 0x41, 0x00, 0x28, 0x02, 0x00, 0x41, 0x01, 0x6a, 0x36, 0x02, 0x00, 0x41, 0x00, 0x28,
+    0x02, 0x00,
+];
+
+/// Increments the non-exported global and returns it.
+const INCREMENT_GLOBAL: &[u8] = &[0x23, 0x00, 0x41, 0x01, 0x6a, 0x24, 0x00, 0x23, 0x00];
+
+/// Grows the memory by a page, then increments the `i32` at `65536` and returns
+/// it.
+const GROW_AND_INCREMENT: &[u8] = &[
+    0x41, 0x01, 0x40, 0x00, 0x1a, 0x41, 0x80, 0x80, 0x04, 0x41, 0x80, 0x80, 0x04, 0x28, 0x02, 0x00,
+    0x41, 0x01, 0x6a, 0x36, 0x02, 0x00, 0x41, 0x80, 0x80, 0x04, 0x28, 0x02, 0x00,
+];
+
+fn init(runtime: &dyn Runtime, body: &[u8], limits: Limits) -> Box<dyn Instance> {
+    let metered = limits.fuel.and_then(|_| runtime.metered());
+    let runtime = metered.as_deref().unwrap_or(runtime);
+
+    let module = Module::Bytes(plugin_module(body));
+    let mut instance = runtime
+        .init("reset", Vec::new(), Vec::new(), module, limits)
+        .unwrap();
+    assert!(instance.snapshot().unwrap(), "{runtime:?}");
+
+    instance
+}
+
+fn runtimes() -> [&'static dyn Runtime; 2] {
+    [
+        &swc_plugin_backend_wasmer::WasmerRuntime,
+        &swc_plugin_backend_wasmtime::WasmtimeRuntime,
+    ]
+}
+
+#[test]
+fn memory_is_restored() {
+    for runtime in runtimes() {
+        let mut instance = init(runtime, INCREMENT, Limits::default());
+
+        assert_eq!(instance.transform(0, 0, 0, 0).unwrap(), 1, "{runtime:?}");
+        assert_eq!(instance.transform(0, 0, 0, 0).unwrap(), 2, "{runtime:?}");
+
+        assert!(instance.reset().unwrap(), "{runtime:?}");
+        assert_eq!(instance.transform(0, 0, 0, 0).unwrap(), 1, "{runtime:?}");
+    }
+}
+
+#[test]
+fn globals_are_restored() {
+    for runtime in runtimes() {
+        let mut instance = init(runtime, INCREMENT_GLOBAL, Limits::default());
+
+        assert_eq!(instance.transform(0, 0, 0, 0).unwrap(), 1, "{runtime:?}");
+        assert_eq!(instance.transform(0, 0, 0, 0).unwrap(), 2, "{runtime:?}");
+
+        assert!(instance.reset().unwrap(), "{runtime:?}");
+        assert_eq!(instance.transform(0, 0, 0, 0).unwrap(), 1, "{runtime:?}");
+    }
+}
+
+#[test]
+fn grown_memory_is_not_reused() {
+    for runtime in runtimes() {
+        let mut instance = init(runtime, GROW_AND_INCREMENT, Limits::default());
+
+        assert_eq!(instance.transform(0, 0, 0, 0).unwrap(), 1, "{runtime:?}");
+
+        // Runtimes may either drop the instance or start over from a fresh one.
+        if instance.reset().unwrap() {
+            assert_eq!(instance.transform(0, 0, 0, 0).unwrap(), 1, "{runtime:?}");
+        }
+    }
+}
+
+#[test]
+fn fuel_is_refilled() {
+    let limits = Limits {
+        fuel: Some(30),
+        ..Default::default()
+    };
+
+    for runtime in runtimes() {
+        let mut instance = init(runtime, INCREMENT, limits);
+
+        for _ in 0..5 {
+            instance.transform(0, 0, 0, 0).unwrap();
+            assert!(instance.reset().unwrap(), "{runtime:?}");
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use swc_plugin_backend_tests::plugin_module;
+use swc_plugin_runner::runtime::{LimitExceeded, Limits, Module, Runtime};
+
+/// `i32.const 0`
+const RETURN: &[u8] = &[0x41, 0x00];
+
+/// `loop br 0 end i32.const 0`
+const INFINITE_LOOP: &[u8] = &[0x03, 0x40, 0x0c, 0x00, 0x0b, 0x41, 0x00];
+
+/// `i32.const 1000 memory.grow i32.const -1 i32.eq if unreachable end i32.const
+/// 0`, which traps like an allocator running out of memory.
+const GROW_MEMORY: &[u8] = &[
+    0x41, 0xe8, 0x07, 0x40, 0x00, 0x41, 0x7f, 0x46, 0x04, 0x40, 0x00, 0x0b, 0x41, 0x00,
+];
+
+fn transform(runtime: &dyn Runtime, body: &[u8], limits: Limits) -> anyhow::Result<u32> {
+    let metered = limits.fuel.and_then(|_| runtime.metered());
+    let runtime = metered.as_deref().unwrap_or(runtime);
+
+    let module = Module::Bytes(plugin_module(body));
+    let mut instance = runtime.init("limits", Vec::new(), Vec::new(), module, limits)?;
+
+    instance.transform(0, 0, 0, 0)
+}
+
+fn runtimes() -> [&'static dyn Runtime; 2] {
+    [
+        &swc_plugin_backend_wasmer::WasmerRuntime,
+        &swc_plugin_backend_wasmtime::WasmtimeRuntime,
+    ]
+}
+
+#[test]
+fn within_limits() {
+    let limits = Limits {
+        fuel: Some(1000),
+        max_memory: Some(1 << 20),
+    };
+
+    for runtime in runtimes() {
+        assert_eq!(
+            transform(runtime, RETURN, limits).unwrap(),
+            0,
+            "{runtime:?}"
+        );
+    }
+}
+
+#[test]
+fn fuel_limit() {
+    let limits = Limits {
+        fuel: Some(1000),
+        ..Default::default()
+    };
+
+    for runtime in runtimes() {
+        let err = transform(runtime, INFINITE_LOOP, limits).unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Fuel(1000)),
+            "{runtime:?}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn fuel_limit_without_metering() {
+    let limits = Limits {
+        fuel: Some(1000),
+        ..Default::default()
+    };
+
+    for runtime in runtimes() {
+        let module = Module::Bytes(plugin_module(RETURN));
+        let err = match runtime.init("limits", Vec::new(), Vec::new(), module, limits) {
+            Ok(_) => panic!("{runtime:?}: instance should not be created"),
+            Err(err) => err,
+        };
+
+        assert!(
+            format!("{err:#}").contains("module is not metered"),
+            "{runtime:?}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn memory_limit() {
+    let limits = Limits {
+        max_memory: Some(1 << 20),
+        ..Default::default()
+    };
+
+    for runtime in runtimes() {
+        let err = transform(runtime, GROW_MEMORY, limits).unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Memory(1 << 20)),
+            "{runtime:?}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn initial_memory_over_limit() {
+    let limits = Limits {
+        max_memory: Some(1000),
+        ..Default::default()
+    };
+
+    for runtime in runtimes() {
+        let err = match transform(runtime, RETURN, limits) {
+            Ok(_) => panic!("{runtime:?}: instance should not be created"),
+            Err(err) => err,
+        };
+
+        assert_eq!(
+            err.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Memory(1000)),
+            "{runtime:?}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn no_limits() {
+    for runtime in runtimes() {
+        assert_eq!(
+            transform(runtime, GROW_MEMORY, Limits::default()).unwrap(),
+            0,
+            "{runtime:?}"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::Arc,
+};
+
+use anyhow::{Error, anyhow};
+use rustc_hash::FxHashMap;
+use serde_json::json;
+use swc_common::{
+    FileName, Mark,
+    plugin::{
+        diagnostics::PluginDiagnosticSeverity,
+        metadata::TransformPluginMetadataContext,
+        serialized::{PluginSerializedBytes, VersionedSerializable},
+    },
+    sync::Lazy,
+};
+use swc_ecma_ast::{EsVersion, Program, Str};
+use swc_ecma_parser::{Syntax, parse_file_as_program};
+use swc_ecma_visit::{Visit, VisitWith};
+use swc_plugin_backend_native::{NativeOrWasmRuntime, NativeRuntime};
+use swc_plugin_runner::{
+    plugin_module_bytes::{CompiledPluginModuleBytes, RawPluginModuleBytes},
+    runtime::{Limits, Module, Runtime},
+};
+use testing::CARGO_TARGET_DIR;
+
+/// Returns the path to the plugin built as a shared library.
+fn build_plugin(dir: &Path) -> Result<PathBuf, Error> {
+    let status = Command::new("cargo")
+        .env("CARGO_TARGET_DIR", &*CARGO_TARGET_DIR)
+        .current_dir(dir)
+        .arg("build")
+        .stderr(Stdio::inherit())
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("Failed to build plugin"));
+    }
+
+    let path = CARGO_TARGET_DIR.join("debug").join(format!(
+        "{}swc_internal_plugin{}",
+        env::consts::DLL_PREFIX,
+        env::consts::DLL_SUFFIX
+    ));
+    if !path.is_file() {
+        return Err(anyhow!("Could not find built plugin"));
+    }
+
+    Ok(path)
+}
+
+static PLUGIN_BYTES: Lazy<Vec<u8>> = Lazy::new(|| {
+    let path = build_plugin(
+        &PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+            .join("tests")
+            .join("fixture")
+            .join("swc_internal_plugin"),
+    )
+    .unwrap();
+
+    std::fs::read(path).unwrap()
+});
+
+struct StrVisitor {
+    found: bool,
+}
+
+impl Visit for StrVisitor {
+    fn visit_str(&mut self, s: &Str) {
+        self.found |= s.value == "changed_via_plugin";
+    }
+}
+
+fn transform(rt: Arc<dyn Runtime>, module: CompiledPluginModuleBytes) {
+    testing::run_test(false, |cm, _handler| {
+        let fm = cm.new_source_file(FileName::Anon.into(), "console.log(foo)");
+
+        let program = parse_file_as_program(
+            &fm,
+            Syntax::Es(Default::default()),
+            EsVersion::latest(),
+            None,
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+        let program = PluginSerializedBytes::try_serialize(&VersionedSerializable::new(program))
+            .expect("Should serializable");
+        let experimental_metadata: FxHashMap<String, String> = [
+            (
+                "TestExperimental".to_string(),
+                "ExperimentalValue".to_string(),
+            ),
+            ("OtherTest".to_string(), "OtherVal".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut plugin_transform_executor = swc_plugin_runner::create_plugin_transform_executor(
+            &cm,
+            &Mark::new(),
+            &Arc::new(TransformPluginMetadataContext::new(
+                None,
+                "development".to_string(),
+                Some(experimental_metadata),
+            )),
+            None,
+            Box::new(module),
+            Some(json!({ "pluginConfig": "testValue" })),
+            rt,
+        );
+
+        let ((program_bytes, captured_output), diagnostics) =
+            swc_transform_common::diagnostics::capture(|| {
+                swc_transform_common::output::capture(|| {
+                    plugin_transform_executor
+                        .transform(&program, Some(false))
+                        .expect("Plugin should apply transform")
+                })
+            });
+
+        assert_eq!(captured_output.get("foo").map(|s| &**s), Some("bar"));
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, PluginDiagnosticSeverity::Warning);
+
+        let program: Program = program_bytes
+            .deserialize()
+            .expect("Should able to deserialize")
+            .into_inner();
+        let mut visitor = StrVisitor { found: false };
+        program.visit_with(&mut visitor);
+        assert!(visitor.found);
+
+        Ok(())
+    })
+    .expect("Should able to run native plugin transform");
+}
+
+#[test]
+fn native() {
+    let module = NativeRuntime.prepare_module(&PLUGIN_BYTES).unwrap();
+
+    transform(
+        Arc::new(NativeRuntime),
+        CompiledPluginModuleBytes::new("swc_internal_plugin".into(), module),
+    );
+}
+
+#[test]
+fn native_or_wasm() {
+    let rt: Arc<dyn Runtime> = Arc::new(NativeOrWasmRuntime::new(Arc::new(
+        swc_plugin_backend_wasmtime::WasmtimeRuntime,
+    )));
+
+    transform(
+        rt.clone(),
+        CompiledPluginModuleBytes::from_raw_module(
+            &*rt,
+            RawPluginModuleBytes::new("swc_internal_plugin".into(), PLUGIN_BYTES.clone()),
+        ),
+    );
+}
+
+#[test]
+fn limits_are_rejected() {
+    let module = NativeRuntime.prepare_module(&PLUGIN_BYTES).unwrap();
+
+    let err = NativeRuntime
+        .init(
+            "swc_internal_plugin",
+            Vec::new(),
+            Vec::new(),
+            Module::Cache(module),
+            Limits {
+                fuel: Some(1000),
+                max_memory: None,
+            },
+        )
+        .err()
+        .expect("Limits should be rejected");
+
+    assert!(err.to_string().contains("not supported"), "{err}");
+}
+
+#[test]
+fn not_a_plugin() {
+    assert!(
+        NativeRuntime
+            .prepare_module(b"not a shared library")
+            .is_err()
+    );
+}
+
+#[test]
+fn neither_wasm_nor_native() {
+    let rt = NativeOrWasmRuntime::new(Arc::new(swc_plugin_backend_wasmtime::WasmtimeRuntime));
+
+    let err = rt
+        .prepare_module(b"#!/bin/sh\necho not a plugin")
+        .err()
+        .expect("Unknown files should be rejected");
+    assert!(err.to_string().contains("neither"), "{err}");
+
+    let err = rt
+        .init(
+            "swc_internal_plugin",
+            Vec::new(),
+            Vec::new(),
+            Module::Bytes(b"#!/bin/sh\necho not a plugin".to_vec().into_boxed_slice()),
+            Limits::default(),
+        )
+        .err()
+        .expect("Unknown files should be rejected");
+    assert!(err.to_string().contains("neither"), "{err}");
+}
@@ -1,14 +1,23 @@
 use std::{
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 
+use anyhow::{Context, bail};
 use parking_lot::Mutex;
 use swc_common::sync::Lazy;
-use swc_plugin_runner::runtime;
+use swc_plugin_runner::runtime::{self, LimitExceeded};
 use wasmer::{AsStoreMut, Store};
 use wasmer_wasix::{Runtime, default_fs_backing};
 
+#[cfg(not(target_arch = "wasm32"))]
+mod metering;
+#[cfg(not(target_arch = "wasm32"))]
+mod tunables;
+
 /// Identifier for bytecode cache stored in local filesystem.
 ///
 /// This MUST be updated when bump up wasmer.
@@ -19,9 +28,20 @@ use wasmer_wasix::{Runtime, default_fs_backing};
 /// https://github.com/wasmerio/wasmer/issues/2781
 const MODULE_SERIALIZATION_IDENTIFIER: &str = concat!("wasmer", "-", "v7");
 
+/// Identifier for bytecode cache of the modules compiled with fuel metering.
+///
+/// This MUST be updated as well when the metering middleware changes, as it's
+/// compiled into the module.
+#[cfg(not(target_arch = "wasm32"))]
+const METERED_MODULE_SERIALIZATION_IDENTIFIER: &str = concat!("wasmer", "-", "v7", "-", "metered");
+
 /// A shared instance to plugin runtime engine.
 /// ref: https://github.com/wasmerio/wasmer/issues/3793#issuecomment-1607117480
-static ENGINE: Lazy<Mutex<wasmer::Engine>> = Lazy::new(|| {
+static ENGINE: Lazy<Mutex<wasmer::Engine>> =
+    Lazy::new(|| parking_lot::Mutex::new(new_engine(None)));
+
+/// Creates an engine compiling modules with `middleware`.
+fn new_engine(middleware: Option<Arc<metering::Metering>>) -> wasmer::Engine {
     // Use empty enumset to disable simd.
     use enumset::EnumSet;
     use wasmer::sys::{BaseTunables, CompilerConfig, EngineBuilder, Target, Triple};
@@ -33,14 +53,17 @@ static ENGINE: Lazy<Mutex<wasmer::Engine>> = Lazy::new(|| {
     set.insert(wasmer::sys::CpuFeature::SSE2);
     let target = Target::new(Triple::host(), set);
 
-    let config = wasmer_compiler_cranelift::Cranelift::default();
+    let mut config = wasmer_compiler_cranelift::Cranelift::default();
+    if let Some(middleware) = middleware {
+        config.push_middleware(middleware);
+    }
     let mut engine = EngineBuilder::new(Box::new(config) as Box<dyn CompilerConfig>)
         .set_target(Some(target))
         .engine();
     let tunables = BaseTunables::for_target(engine.target());
     engine.set_tunables(tunables);
-    parking_lot::Mutex::new(wasmer::Engine::from(engine))
-});
+    wasmer::Engine::from(engine)
+}
 
 /// Construct a runtime for the wasix engine depends on the compilation
 /// features.
@@ -92,9 +115,44 @@ pub(crate) fn new_store() -> Store {
     Store::default()
 }
 
+/// Creates an instance of [Store] with a new engine, which instruments the
+/// module it compiles with fuel metering.
+#[cfg(not(target_arch = "wasm32"))]
+fn new_metered_store() -> Store {
+    Store::new(new_engine(Some(Default::default())))
+}
+
+/// Creates an instance of [Store] whose memories can't grow beyond
+/// `max_memory` bytes, and the flag set when one of them tries to.
+#[cfg(not(target_arch = "wasm32"))]
+fn new_limited_store(engine: &wasmer::Engine, max_memory: u64) -> (Store, Arc<AtomicBool>) {
+    use wasmer::sys::{BaseTunables, NativeEngineExt};
+
+    let limit_exceeded = Arc::new(AtomicBool::new(false));
+    let mut engine = engine.clone();
+    let tunables = BaseTunables::for_target(engine.target());
+    engine.set_tunables(tunables::LimitingTunables::new(
+        tunables,
+        max_memory,
+        limit_exceeded.clone(),
+    ));
+    (Store::new(engine), limit_exceeded)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn new_limited_store(_: &wasmer::Engine, _: u64) -> (Store, Arc<AtomicBool>) {
+    unreachable!("limits are rejected before creating a store")
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct WasmerRuntime;
 
+/// [WasmerRuntime] compiling modules with fuel metering, returned by
+/// [runtime::Runtime::metered].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug)]
+struct MeteredWasmerRuntime;
+
 #[derive(Default)]
 struct WasmerTable {
     memory: Option<wasmer::Memory>,
@@ -107,6 +165,7 @@ struct WasmerCaller<'a> {
     alloc_func: &'a wasmer::TypedFunction<u32, u32>,
     free_func: &'a wasmer::TypedFunction<(u32, u32), u32>,
     store: &'a mut wasmer::Store,
+    limiter: &'a WasmerLimiter,
 }
 
 struct WasmerCallerRef<'a> {
@@ -121,6 +180,100 @@ struct WasmerCache {
     module: wasmer::Module,
 }
 
+/// Enforces [runtime::Limits] on an instance and tells whether a failure was
+/// caused by one of them.
+struct WasmerLimiter {
+    limits: runtime::Limits,
+    remaining_fuel: Option<wasmer::Global>,
+    fuel_exhausted: Option<wasmer::Global>,
+    memory_limit_exceeded: Option<Arc<AtomicBool>>,
+}
+
+impl WasmerLimiter {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn new(
+        store: &mut Store,
+        instance: &wasmer::Instance,
+        limits: runtime::Limits,
+        memory_limit_exceeded: Option<Arc<AtomicBool>>,
+    ) -> anyhow::Result<Self> {
+        let global = |name: &str| instance.exports.get_global(name).ok().cloned();
+
+        let limiter = Self {
+            limits,
+            remaining_fuel: global(metering::REMAINING_FUEL),
+            fuel_exhausted: global(metering::FUEL_EXHAUSTED),
+            memory_limit_exceeded,
+        };
+        if limits.fuel.is_some() && limiter.remaining_fuel.is_none() {
+            bail!(
+                "module is not metered, fuel limit can't be enforced. Compile it with the runtime \
+                 returned by `Runtime::metered`"
+            );
+        }
+        limiter.refill(store)?;
+
+        Ok(limiter)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn new(
+        _: &mut Store,
+        _: &wasmer::Instance,
+        limits: runtime::Limits,
+        _: Option<Arc<AtomicBool>>,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            limits,
+            remaining_fuel: None,
+            fuel_exhausted: None,
+            memory_limit_exceeded: None,
+        })
+    }
+
+    /// Refills the fuel and clears the flags raised by the limits.
+    fn refill(&self, store: &mut impl AsStoreMut) -> anyhow::Result<()> {
+        if let (Some(fuel), Some(remaining_fuel)) = (self.limits.fuel, &self.remaining_fuel) {
+            // Fuel is compared as unsigned.
+            remaining_fuel.set(store, wasmer::Value::I64(fuel as i64))?;
+        }
+
+        if let Some(fuel_exhausted) = &self.fuel_exhausted {
+            fuel_exhausted.set(store, wasmer::Value::I32(0))?;
+        }
+        if let Some(memory_limit_exceeded) = &self.memory_limit_exceeded {
+            memory_limit_exceeded.store(false, Ordering::Relaxed);
+        }
+
+        Ok(())
+    }
+
+    /// Replaces `err` with [LimitExceeded] if a limit was hit.
+    fn map_err(&self, store: &mut impl AsStoreMut, err: wasmer::RuntimeError) -> anyhow::Error {
+        if let Some(fuel) = self.limits.fuel {
+            let fuel_exhausted = self
+                .fuel_exhausted
+                .as_ref()
+                .is_some_and(|flag| flag.get(store) == wasmer::Value::I32(1));
+            if fuel_exhausted {
+                return LimitExceeded::Fuel(fuel).into();
+            }
+        }
+
+        if let Some(max_memory) = self.limits.max_memory {
+            let memory_limit_exceeded = self
+                .memory_limit_exceeded
+                .as_ref()
+                .is_some_and(|flag| flag.load(Ordering::Relaxed));
+            if memory_limit_exceeded {
+                return LimitExceeded::Memory(max_memory).into();
+            }
+        }
+
+        err.into()
+    }
+}
+
 struct WasmerInstance {
     _instance: wasmer::Instance,
     _table: wasmer::FunctionEnv<WasmerTable>,
@@ -133,6 +286,16 @@ struct WasmerInstance {
     alloc_func: wasmer::TypedFunction<u32, u32>,
     free_func: wasmer::TypedFunction<(u32, u32), u32>,
     transform_func: wasmer::TypedFunction<(u32, u32, u32, u32), u32>,
+    limiter: WasmerLimiter,
+    snapshot: Option<WasmerSnapshot>,
+}
+
+/// The state of a [WasmerInstance] restored by [runtime::Instance::reset].
+struct WasmerSnapshot {
+    memory: Vec<u8>,
+    /// All globals of the store, including the ones which are not exported
+    /// like `__stack_pointer`.
+    globals: wasmer_wasix::StoreSnapshot,
 }
 
 impl runtime::Runtime for WasmerRuntime {
@@ -155,160 +318,247 @@ impl runtime::Runtime for WasmerRuntime {
         imports: Vec<(String, runtime::Func)>,
         envs: Vec<(String, String)>,
         module: runtime::Module,
+        limits: runtime::Limits,
     ) -> anyhow::Result<Box<dyn runtime::Instance>> {
-        let (mut store, module) = match module {
-            runtime::Module::Cache(cache) => {
-                let cache = cache.0.downcast::<WasmerCache>().unwrap();
-                (cache.store, cache.module)
-            }
-            runtime::Module::Bytes(buf) => {
-                let store = new_store();
-                let module = wasmer::Module::new(&store, &buf)?;
-                (store, module)
-            }
-        };
+        init(new_store, name, imports, envs, module, limits)
+    }
 
-        let table = wasmer::FunctionEnv::new(&mut store, WasmerTable::default());
-        let mut ns = wasmer::Exports::new();
-        for (name, func) in imports {
-            let sign = wasmer::FunctionType::new(
-                vec![wasmer::Type::I32; func.sign.0 as usize],
-                vec![wasmer::Type::I32; func.sign.1 as usize],
-            );
-            let func =
-                wasmer::Function::new_with_env(&mut store, &table, sign, move |env, args| {
-                    wasmer_func_call(env, args, &func)
-                });
+    fn clone_cache(&self, cache: &runtime::ModuleCache) -> Option<runtime::ModuleCache> {
+        let cache = cache.0.downcast_ref::<WasmerCache>()?;
 
-            ns.insert(name, func);
-        }
-        let mut imports = wasmer::Imports::new();
-        imports.register_namespace("env", ns);
+        let store = wasmer::Store::new(cache.store.engine().clone());
+        let module = cache.module.clone();
+        Some(runtime::ModuleCache(Box::new(WasmerCache {
+            store,
+            module,
+        })))
+    }
 
-        let (instance, wasi_env) = if wasmer_wasix::is_wasi_module(&module) {
-            let mut builder = wasmer_wasix::WasiEnv::builder(name);
+    unsafe fn load_cache(&self, path: &Path) -> Option<runtime::ModuleCache> {
+        unsafe { load_cache(new_store(), path) }
+    }
 
-            if let Some(runtime) = build_wasi_runtime(None) {
-                builder = builder.runtime(runtime);
-            }
+    fn store_cache(&self, path: &Path, cache: &runtime::ModuleCache) -> anyhow::Result<()> {
+        let cache = cache.0.downcast_ref::<WasmerCache>().unwrap();
+        cache.module.serialize_to_file(path)?;
+        Ok(())
+    }
 
-            // Implicitly enable filesystem access for the wasi plugin to cwd.
-            //
-            // This allows wasi plugin can read arbitary data (i.e node_modules) or produce
-            // output for post process (i.e .lcov coverage data) directly.
-            //
-            // TODO: this is not finalized decision
-            // - should we support this?
-            // - can we limit to allowlisted input / output only?
-            // - should there be a top-level config from .swcrc to manually override this?
-            let mut builder = if let Ok(cwd) = std::env::current_dir() {
-                builder
-                    .fs(default_fs_backing())
-                    .map_dirs(Some(("/cwd".to_string(), cwd)))?
-            } else {
-                builder
-            };
-
-            builder.add_envs(envs);
-
-            let mut wasi_env = builder.finalize(&mut store)?;
-
-            // Then, we get the import object related to our WASI,
-            // overwrite into imported_object
-            // and attach it to the Wasm instance.
-            let wasi_env_import_object = wasi_env.import_object(&mut store, &module)?;
-            imports.extend(&wasi_env_import_object);
-
-            let instance = wasmer::Instance::new(&mut store, &module, &imports)?;
-            wasi_env.initialize(&mut store, instance.clone())?;
-            (instance, Some(wasi_env))
-        } else {
-            let instance = wasmer::Instance::new(&mut store, &module, &imports)?;
-            (instance, None)
-        };
+    #[cfg(not(target_arch = "wasm32"))]
+    fn metered(&self) -> Option<Arc<dyn runtime::Runtime>> {
+        Some(Arc::new(MeteredWasmerRuntime))
+    }
+}
 
-        // Attach the memory export
-        let memory = instance.exports.get_memory("memory")?.clone();
-        imports.define("env", "memory", memory.clone());
-
-        // `__alloc` function automatically exported via swc_plugin sdk to allow
-        // allocation in guest memory space
-        let alloc_func: wasmer::TypedFunction<u32, u32> =
-            instance.exports.get_typed_function(&store, "__alloc")?;
-
-        // `__free` function automatically exported via swc_plugin sdk to allow
-        // deallocation in guest memory space
-        let free_func: wasmer::TypedFunction<(u32, u32), u32> =
-            instance.exports.get_typed_function(&store, "__free")?;
-
-        // Main transform interface plugin exports
-        let transform_func: wasmer::TypedFunction<(u32, u32, u32, u32), u32> = instance
-            .exports
-            .get_typed_function(&store, "__transform_plugin_process_impl")?;
-
-        table.as_mut(&mut store).memory = Some(memory.clone());
-        table.as_mut(&mut store).alloc_func = Some(alloc_func.clone());
-        table.as_mut(&mut store).free_func = Some(free_func.clone());
-
-        // As soon as instance is ready, host calls a fn to read plugin's swc_core pkg
-        // diagnostics as `handshake`. Once read those values will be available across
-        // whole plugin transform execution.
-
-        // IMPORTANT NOTE
-        // Note this is `handshake`, which we expect to success ALL TIME. Do not try to
-        // expand `PluginCorePkgDiagnostics` as it'll cause deserialization failure
-        // until we have forward-compat schema changes.
-        instance
-            .exports
-            .get_typed_function::<(), u32>(&store, "__get_transform_plugin_core_pkg_diag")?
-            .call(&mut store)?;
-
-        Ok(Box::new(WasmerInstance {
-            _instance: instance,
-            _table: table,
-            _wasi_env: wasi_env,
+#[cfg(not(target_arch = "wasm32"))]
+impl runtime::Runtime for MeteredWasmerRuntime {
+    fn identifier(&self) -> &'static str {
+        METERED_MODULE_SERIALIZATION_IDENTIFIER
+    }
 
+    fn prepare_module(&self, bytes: &[u8]) -> anyhow::Result<runtime::ModuleCache> {
+        let store = new_metered_store();
+        let module = wasmer::Module::new(&store, bytes)?;
+        Ok(runtime::ModuleCache(Box::new(WasmerCache {
             store,
             module,
-            memory,
-            alloc_func,
-            free_func,
-            transform_func,
-        }))
+        })))
+    }
+
+    fn init(
+        &self,
+        name: &str,
+        imports: Vec<(String, runtime::Func)>,
+        envs: Vec<(String, String)>,
+        module: runtime::Module,
+        limits: runtime::Limits,
+    ) -> anyhow::Result<Box<dyn runtime::Instance>> {
+        init(new_metered_store, name, imports, envs, module, limits)
     }
 
     fn clone_cache(&self, cache: &runtime::ModuleCache) -> Option<runtime::ModuleCache> {
-        let cache = cache.0.downcast_ref::<WasmerCache>()?;
+        WasmerRuntime.clone_cache(cache)
+    }
 
-        let store = wasmer::Store::new(cache.store.engine().clone());
-        let module = cache.module.clone();
-        Some(runtime::ModuleCache(Box::new(WasmerCache {
-            store,
-            module,
-        })))
+    unsafe fn load_cache(&self, path: &Path) -> Option<runtime::ModuleCache> {
+        unsafe { load_cache(new_metered_store(), path) }
     }
 
-    unsafe fn load_cache(&self, path: &std::path::Path) -> Option<runtime::ModuleCache> {
-        unsafe {
-            let store = new_store();
-            let module = wasmer::Module::deserialize_from_file(store.engine(), path);
+    fn store_cache(&self, path: &Path, cache: &runtime::ModuleCache) -> anyhow::Result<()> {
+        WasmerRuntime.store_cache(path, cache)
+    }
+}
 
-            if module.is_err() {
-                // If an error occurs while deserializing then we can not trust it anymore
-                // so delete the cache file
-                let _ = std::fs::remove_file(path);
-            }
+/// Instantiates `module`, compiling it with the engine of `new_store` if
+/// needed.
+fn init(
+    new_store: fn() -> Store,
+    name: &str,
+    imports: Vec<(String, runtime::Func)>,
+    envs: Vec<(String, String)>,
+    module: runtime::Module,
+    limits: runtime::Limits,
+) -> anyhow::Result<Box<dyn runtime::Instance>> {
+    if cfg!(target_arch = "wasm32") && limits != runtime::Limits::default() {
+        bail!("limits are not supported by this runtime");
+    }
+
+    let (mut store, module) = match module {
+        runtime::Module::Cache(cache) => {
+            let cache = cache.0.downcast::<WasmerCache>().unwrap();
+            (cache.store, cache.module)
+        }
+        runtime::Module::Bytes(buf) => {
+            let store = new_store();
+            let module = wasmer::Module::new(&store, buf)?;
+            (store, module)
+        }
+    };
 
-            module
-                .ok()
-                .map(|module| runtime::ModuleCache(Box::new(WasmerCache { store, module })))
+    let mut memory_limit_exceeded = None;
+    if let Some(max_memory) = limits.max_memory {
+        let min_memory = module
+            .exports()
+            .memories()
+            .map(|memory| memory.ty().minimum.bytes().0 as u64)
+            .max()
+            .unwrap_or_default();
+        if min_memory > max_memory {
+            return Err(LimitExceeded::Memory(max_memory).into());
         }
+
+        let (limited_store, limit_exceeded) = new_limited_store(store.engine(), max_memory);
+        store = limited_store;
+        memory_limit_exceeded = Some(limit_exceeded);
     }
 
-    fn store_cache(&self, path: &Path, cache: &runtime::ModuleCache) -> anyhow::Result<()> {
-        let cache = cache.0.downcast_ref::<WasmerCache>().unwrap();
-        cache.module.serialize_to_file(path)?;
-        Ok(())
+    let table = wasmer::FunctionEnv::new(&mut store, WasmerTable::default());
+    let mut ns = wasmer::Exports::new();
+    for (name, func) in imports {
+        let sign = wasmer::FunctionType::new(
+            vec![wasmer::Type::I32; func.sign.0 as usize],
+            vec![wasmer::Type::I32; func.sign.1 as usize],
+        );
+        let func = wasmer::Function::new_with_env(&mut store, &table, sign, move |env, args| {
+            wasmer_func_call(env, args, &func)
+        });
+
+        ns.insert(name, func);
+    }
+    let mut imports = wasmer::Imports::new();
+    imports.register_namespace("env", ns);
+
+    let (instance, wasi_env) = if wasmer_wasix::is_wasi_module(&module) {
+        let mut builder = wasmer_wasix::WasiEnv::builder(name);
+
+        if let Some(runtime) = build_wasi_runtime(None) {
+            builder = builder.runtime(runtime);
+        }
+
+        // Implicitly enable filesystem access for the wasi plugin to cwd.
+        //
+        // This allows wasi plugin can read arbitary data (i.e node_modules) or produce
+        // output for post process (i.e .lcov coverage data) directly.
+        //
+        // TODO: this is not finalized decision
+        // - should we support this?
+        // - can we limit to allowlisted input / output only?
+        // - should there be a top-level config from .swcrc to manually override this?
+        let mut builder = if let Ok(cwd) = std::env::current_dir() {
+            builder
+                .fs(default_fs_backing())
+                .map_dirs(Some(("/cwd".to_string(), cwd)))?
+        } else {
+            builder
+        };
+
+        builder.add_envs(envs);
+
+        let mut wasi_env = builder.finalize(&mut store)?;
+
+        // Then, we get the import object related to our WASI,
+        // overwrite into imported_object
+        // and attach it to the Wasm instance.
+        let wasi_env_import_object = wasi_env.import_object(&mut store, &module)?;
+        imports.extend(&wasi_env_import_object);
+
+        let instance = wasmer::Instance::new(&mut store, &module, &imports)?;
+        wasi_env.initialize(&mut store, instance.clone())?;
+        (instance, Some(wasi_env))
+    } else {
+        let instance = wasmer::Instance::new(&mut store, &module, &imports)?;
+        (instance, None)
+    };
+
+    let limiter = WasmerLimiter::new(&mut store, &instance, limits, memory_limit_exceeded)?;
+
+    // Attach the memory export
+    let memory = instance.exports.get_memory("memory")?.clone();
+    imports.define("env", "memory", memory.clone());
+
+    // `__alloc` function automatically exported via swc_plugin sdk to allow
+    // allocation in guest memory space
+    let alloc_func: wasmer::TypedFunction<u32, u32> =
+        instance.exports.get_typed_function(&store, "__alloc")?;
+
+    // `__free` function automatically exported via swc_plugin sdk to allow
+    // deallocation in guest memory space
+    let free_func: wasmer::TypedFunction<(u32, u32), u32> =
+        instance.exports.get_typed_function(&store, "__free")?;
+
+    // Main transform interface plugin exports
+    let transform_func: wasmer::TypedFunction<(u32, u32, u32, u32), u32> = instance
+        .exports
+        .get_typed_function(&store, "__transform_plugin_process_impl")?;
+
+    table.as_mut(&mut store).memory = Some(memory.clone());
+    table.as_mut(&mut store).alloc_func = Some(alloc_func.clone());
+    table.as_mut(&mut store).free_func = Some(free_func.clone());
+
+    // As soon as instance is ready, host calls a fn to read plugin's swc_core pkg
+    // diagnostics as `handshake`. Once read those values will be available across
+    // whole plugin transform execution.
+
+    // IMPORTANT NOTE
+    // Note this is `handshake`, which we expect to success ALL TIME. Do not try to
+    // expand `PluginCorePkgDiagnostics` as it'll cause deserialization failure
+    // until we have forward-compat schema changes.
+    instance
+        .exports
+        .get_typed_function::<(), u32>(&store, "__get_transform_plugin_core_pkg_diag")?
+        .call(&mut store)
+        .map_err(|err| limiter.map_err(&mut store, err))?;
+
+    Ok(Box::new(WasmerInstance {
+        _instance: instance,
+        _table: table,
+        _wasi_env: wasi_env,
+
+        store,
+        module,
+        memory,
+        alloc_func,
+        free_func,
+        transform_func,
+        limiter,
+        snapshot: None,
+    }))
+}
+
+/// Loads a module compiled by the engine of `store`.
+unsafe fn load_cache(store: Store, path: &Path) -> Option<runtime::ModuleCache> {
+    unsafe {
+        let module = wasmer::Module::deserialize_from_file(store.engine(), path);
+
+        if module.is_err() {
+            // If an error occurs while deserializing then we can not trust it anymore
+            // so delete the cache file
+            let _ = std::fs::remove_file(path);
+        }
+
+        module
+            .ok()
+            .map(|module| runtime::ModuleCache(Box::new(WasmerCache { store, module })))
     }
 }
 
@@ -328,7 +578,7 @@ impl runtime::Instance for WasmerInstance {
                 unresolved_mark,
                 should_enable_comments_proxy,
             )
-            .map_err(Into::into)
+            .map_err(|err| self.limiter.map_err(&mut self.store, err))
     }
 
     fn caller(&mut self) -> anyhow::Result<Box<dyn runtime::Caller<'_> + '_>> {
@@ -337,6 +587,7 @@ impl runtime::Instance for WasmerInstance {
             store: &mut self.store,
             alloc_func: &self.alloc_func,
             free_func: &self.free_func,
+            limiter: &self.limiter,
         }))
     }
 
@@ -361,6 +612,30 @@ impl runtime::Instance for WasmerInstance {
 
         Ok(())
     }
+
+    fn snapshot(&mut self) -> anyhow::Result<bool> {
+        self.snapshot = Some(WasmerSnapshot {
+            memory: self.memory.view(&self.store).copy_to_vec()?,
+            globals: wasmer_wasix::capture_store_snapshot(&mut self.store),
+        });
+        Ok(true)
+    }
+
+    fn reset(&mut self) -> anyhow::Result<bool> {
+        let snapshot = self.snapshot.as_ref().context("no snapshot to reset to")?;
+
+        // Memory can't shrink, so an instance whose memory grew is dropped instead
+        // of keeping the grown pages around.
+        let view = self.memory.view(&self.store);
+        if view.data_size() != snapshot.memory.len() as u64 {
+            return Ok(false);
+        }
+        view.write(0, &snapshot.memory)?;
+        wasmer_wasix::restore_store_snapshot(&mut self.store, &snapshot.globals);
+
+        self.limiter.refill(&mut self.store)?;
+        Ok(true)
+    }
 }
 
 impl<'a> runtime::Caller<'a> for WasmerCaller<'a> {
@@ -381,13 +656,13 @@ impl<'a> runtime::Caller<'a> for WasmerCaller<'a> {
     fn alloc(&mut self, size: u32) -> anyhow::Result<u32> {
         self.alloc_func
             .call(&mut self.store, size)
-            .map_err(Into::into)
+            .map_err(|err| self.limiter.map_err(&mut self.store, err))
     }
 
     fn free(&mut self, ptr: u32, size: u32) -> anyhow::Result<u32> {
         self.free_func
             .call(&mut self.store, ptr, size)
-            .map_err(Into::into)
+            .map_err(|err| self.limiter.map_err(&mut self.store, err))
     }
 }
 
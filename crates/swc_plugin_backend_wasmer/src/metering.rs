@@ -0,0 +1,147 @@
+//! Instrumentation used to enforce fuel limits.
+//!
+//! This charges one unit of fuel per instruction. The cost of a basic block is
+//! charged when leaving it, and the instance traps once the remaining fuel
+//! isn't enough to cover it.
+//!
+//! Like the middleware of `wasmer-middlewares`, a [Metering] instruments a
+//! single module, so each metered module is compiled by its own engine.
+
+use parking_lot::Mutex;
+use wasmer::{
+    ExportIndex, GlobalInit, GlobalType, LocalFunctionIndex, Mutability, Type,
+    sys::{
+        FunctionMiddleware, MiddlewareError, MiddlewareReaderState, ModuleMiddleware,
+        wasmparser::{BlockType, Operator},
+    },
+};
+use wasmer_types::{GlobalIndex, ModuleInfo};
+
+/// Name of the exported `i64` global holding the remaining fuel.
+pub(crate) const REMAINING_FUEL: &str = "__swc_metering_remaining_fuel";
+
+/// Name of the exported `i32` global set to `1` when fuel is exhausted.
+pub(crate) const FUEL_EXHAUSTED: &str = "__swc_metering_fuel_exhausted";
+
+#[derive(Debug, Clone, Copy)]
+struct Globals {
+    remaining_fuel: GlobalIndex,
+    fuel_exhausted: GlobalIndex,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Metering {
+    globals: Mutex<Option<Globals>>,
+}
+
+#[derive(Debug)]
+struct FunctionMetering {
+    globals: Globals,
+    cost: u64,
+}
+
+impl ModuleMiddleware for Metering {
+    fn generate_function_middleware(&self, _: LocalFunctionIndex) -> Box<dyn FunctionMiddleware> {
+        Box::new(FunctionMetering {
+            globals: self
+                .globals
+                .lock()
+                .expect("module info should be transformed before functions"),
+            cost: 0,
+        })
+    }
+
+    fn transform_module_info(&self, info: &mut ModuleInfo) -> Result<(), MiddlewareError> {
+        let mut globals = self.globals.lock();
+        if globals.is_some() {
+            return Err(MiddlewareError::new(
+                "Metering",
+                "a metering middleware can't be used for multiple modules",
+            ));
+        }
+
+        // Fuel is unlimited until the runtime sets it.
+        let remaining_fuel = info
+            .globals
+            .push(GlobalType::new(Type::I64, Mutability::Var));
+        info.global_initializers
+            .push(GlobalInit::I64Const(u64::MAX as i64));
+        info.exports
+            .insert(REMAINING_FUEL.into(), ExportIndex::Global(remaining_fuel));
+
+        let fuel_exhausted = info
+            .globals
+            .push(GlobalType::new(Type::I32, Mutability::Var));
+        info.global_initializers.push(GlobalInit::I32Const(0));
+        info.exports
+            .insert(FUEL_EXHAUSTED.into(), ExportIndex::Global(fuel_exhausted));
+
+        *globals = Some(Globals {
+            remaining_fuel,
+            fuel_exhausted,
+        });
+
+        Ok(())
+    }
+}
+
+impl FunctionMiddleware for FunctionMetering {
+    fn feed<'a>(
+        &mut self,
+        operator: Operator<'a>,
+        state: &mut MiddlewareReaderState<'a>,
+    ) -> Result<(), MiddlewareError> {
+        self.cost += 1;
+
+        if matches!(
+            operator,
+            Operator::Loop { .. }
+                | Operator::End
+                | Operator::Else
+                | Operator::Br { .. }
+                | Operator::BrIf { .. }
+                | Operator::BrTable { .. }
+                | Operator::Call { .. }
+                | Operator::CallIndirect { .. }
+                | Operator::ReturnCall { .. }
+                | Operator::ReturnCallIndirect { .. }
+                | Operator::Return
+        ) {
+            let remaining_fuel = self.globals.remaining_fuel.as_u32();
+            let cost = self.cost as i64;
+
+            for operator in [
+                Operator::GlobalGet {
+                    global_index: remaining_fuel,
+                },
+                Operator::I64Const { value: cost },
+                Operator::I64LtU,
+                Operator::If {
+                    blockty: BlockType::Empty,
+                },
+                Operator::I32Const { value: 1 },
+                Operator::GlobalSet {
+                    global_index: self.globals.fuel_exhausted.as_u32(),
+                },
+                Operator::Unreachable,
+                Operator::End,
+                Operator::GlobalGet {
+                    global_index: remaining_fuel,
+                },
+                Operator::I64Const { value: cost },
+                Operator::I64Sub,
+                Operator::GlobalSet {
+                    global_index: remaining_fuel,
+                },
+            ] {
+                state.push_operator(operator);
+            }
+
+            self.cost = 0;
+        }
+
+        state.push_operator(operator);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+//! Tunables capping the size of linear memories, used to enforce memory
+//! limits.
+
+use std::{
+    ptr::NonNull,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use wasmer::{
+    MemoryError, MemoryStyle, MemoryType, Pages, TableStyle, TableType, WASM_PAGE_SIZE,
+    sys::{
+        Tunables,
+        vm::{LinearMemory, VMMemory, VMMemoryDefinition, VMTable, VMTableDefinition},
+    },
+};
+
+pub(crate) struct LimitingTunables<T> {
+    base: T,
+    limit: Pages,
+    /// Set when a memory fails to grow beyond `limit`. The guest usually traps
+    /// on its own when it runs out of memory, so this tells why.
+    limit_exceeded: Arc<AtomicBool>,
+}
+
+impl<T: Tunables> LimitingTunables<T> {
+    pub fn new(base: T, max_memory: u64, limit_exceeded: Arc<AtomicBool>) -> Self {
+        let limit = (max_memory / WASM_PAGE_SIZE as u64).min(u32::MAX as u64) as u32;
+
+        Self {
+            base,
+            limit: Pages(limit),
+            limit_exceeded,
+        }
+    }
+
+    fn wrap_memory(&self, memory: VMMemory) -> VMMemory {
+        VMMemory(Box::new(LimitedMemory {
+            inner: memory,
+            limit: self.limit,
+            limit_exceeded: self.limit_exceeded.clone(),
+        }))
+    }
+
+    fn adjust_memory(&self, requested: &MemoryType) -> Result<MemoryType, MemoryError> {
+        if requested.minimum > self.limit {
+            return Err(MemoryError::MinimumMemoryTooLarge {
+                min_requested: requested.minimum,
+                max_allowed: self.limit,
+            });
+        }
+
+        let mut adjusted = *requested;
+        adjusted.maximum = Some(
+            requested
+                .maximum
+                .map_or(self.limit, |maximum| maximum.min(self.limit)),
+        );
+
+        Ok(adjusted)
+    }
+}
+
+impl<T: Tunables> Tunables for LimitingTunables<T> {
+    fn memory_style(&self, memory: &MemoryType) -> MemoryStyle {
+        self.base.memory_style(memory)
+    }
+
+    fn table_style(&self, table: &TableType) -> TableStyle {
+        self.base.table_style(table)
+    }
+
+    fn create_host_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+    ) -> Result<VMMemory, MemoryError> {
+        let memory = self
+            .base
+            .create_host_memory(&self.adjust_memory(ty)?, style)?;
+        Ok(self.wrap_memory(memory))
+    }
+
+    unsafe fn create_vm_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+        vm_definition_location: NonNull<VMMemoryDefinition>,
+    ) -> Result<VMMemory, MemoryError> {
+        let memory = unsafe {
+            self.base
+                .create_vm_memory(&self.adjust_memory(ty)?, style, vm_definition_location)?
+        };
+        Ok(self.wrap_memory(memory))
+    }
+
+    fn create_host_table(&self, ty: &TableType, style: &TableStyle) -> Result<VMTable, String> {
+        self.base.create_host_table(ty, style)
+    }
+
+    unsafe fn create_vm_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+        vm_definition_location: NonNull<VMTableDefinition>,
+    ) -> Result<VMTable, String> {
+        unsafe { self.base.create_vm_table(ty, style, vm_definition_location) }
+    }
+}
+
+/// A memory recording whether it failed to grow because of the limit.
+#[derive(Debug)]
+struct LimitedMemory {
+    inner: VMMemory,
+    limit: Pages,
+    limit_exceeded: Arc<AtomicBool>,
+}
+
+impl LinearMemory for LimitedMemory {
+    fn ty(&self) -> MemoryType {
+        self.inner.ty()
+    }
+
+    fn size(&self) -> Pages {
+        self.inner.size()
+    }
+
+    fn style(&self) -> MemoryStyle {
+        self.inner.style()
+    }
+
+    fn grow(&mut self, delta: Pages) -> Result<Pages, MemoryError> {
+        if self.inner.size().0.saturating_add(delta.0) > self.limit.0 {
+            self.limit_exceeded.store(true, Ordering::Relaxed);
+        }
+
+        self.inner.grow(delta)
+    }
+
+    fn grow_at_least(&mut self, min_size: u64) -> Result<(), MemoryError> {
+        self.inner.grow_at_least(min_size)
+    }
+
+    fn reset(&mut self) -> Result<(), MemoryError> {
+        self.inner.reset()
+    }
+
+    fn vmmemory(&self) -> NonNull<VMMemoryDefinition> {
+        self.inner.vmmemory()
+    }
+
+    fn try_clone(&self) -> Result<Box<dyn LinearMemory + 'static>, MemoryError> {
+        self.inner.try_clone()
+    }
+
+    fn copy(&mut self) -> Result<Box<dyn LinearMemory + 'static>, MemoryError> {
+        self.inner.copy()
+    }
+}
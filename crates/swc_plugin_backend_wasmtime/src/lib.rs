@@ -1,19 +1,30 @@
-use std::path::Path;
+use std::{path::Path, sync::Arc};
 
 use anyhow::Context;
 use swc_common::sync::OnceCell;
-use swc_plugin_runner::runtime;
+use swc_plugin_runner::runtime::{self, LimitExceeded};
 
 /// Identifier for bytecode cache stored in local filesystem.
 ///
 /// This MUST be updated when bump up wasmtime.
 const MODULE_SERIALIZATION_IDENTIFIER: &str = concat!("wasmtime", "-", "v35");
 
+/// Identifier for bytecode cache of the modules compiled with fuel metering.
+const METERED_MODULE_SERIALIZATION_IDENTIFIER: &str = concat!("wasmtime", "-", "v35", "-", "fuel");
+
 static ENGINE: OnceCell<wasmtime::Engine> = OnceCell::new();
 
+/// The engine of [MeteredWasmtimeRuntime], which consumes fuel.
+static METERED_ENGINE: OnceCell<wasmtime::Engine> = OnceCell::new();
+
 #[derive(Clone, Copy, Debug)]
 pub struct WasmtimeRuntime;
 
+/// [WasmtimeRuntime] compiling modules with fuel metering, returned by
+/// [runtime::Runtime::metered].
+#[derive(Clone, Copy, Debug)]
+struct MeteredWasmtimeRuntime;
+
 struct WasmtimeCache(wasmtime::Module);
 
 struct WasmtimeTable {
@@ -22,9 +33,45 @@ struct WasmtimeTable {
     free_func: Option<wasmtime::TypedFunc<(u32, u32), u32>>,
 
     wasi: wasi_common::WasiCtx,
+    limiter: WasmtimeLimiter,
+}
+
+/// Enforces the memory limit of [runtime::Limits].
+struct WasmtimeLimiter {
+    limits: runtime::Limits,
+}
+
+impl wasmtime::ResourceLimiter for WasmtimeLimiter {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> anyhow::Result<bool> {
+        if let Some(max_memory) = self.limits.max_memory {
+            if desired as u64 > max_memory {
+                return Err(LimitExceeded::Memory(max_memory).into());
+            }
+        }
+
+        Ok(maximum.map_or(true, |maximum| desired <= maximum))
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        maximum: Option<usize>,
+    ) -> anyhow::Result<bool> {
+        Ok(maximum.map_or(true, |maximum| desired <= maximum))
+    }
 }
 
 struct WasmtimeInstance {
+    /// Used to instantiate the module again on [runtime::Instance::reset].
+    instance_pre: wasmtime::InstancePre<WasmtimeTable>,
+    envs: Vec<(String, String)>,
+
     instance: wasmtime::Instance,
     store: wasmtime::Store<WasmtimeTable>,
 
@@ -32,6 +79,7 @@ struct WasmtimeInstance {
     alloc_func: wasmtime::TypedFunc<u32, u32>,
     free_func: wasmtime::TypedFunc<(u32, u32), u32>,
     transform_func: wasmtime::TypedFunc<(u32, u32, u32, u32), u32>,
+    limits: runtime::Limits,
 }
 
 struct WasmtimeCaller<'a> {
@@ -39,6 +87,7 @@ struct WasmtimeCaller<'a> {
     memory: &'a wasmtime::Memory,
     alloc_func: &'a wasmtime::TypedFunc<u32, u32>,
     free_func: &'a wasmtime::TypedFunc<(u32, u32), u32>,
+    limits: runtime::Limits,
 }
 
 struct WasmtimeCallerRef<'a> {
@@ -53,6 +102,12 @@ fn init_engine() -> anyhow::Result<wasmtime::Engine> {
     wasmtime::Engine::new(&config)
 }
 
+fn init_metered_engine() -> anyhow::Result<wasmtime::Engine> {
+    let mut config = wasmtime::Config::default();
+    config.consume_fuel(true);
+    wasmtime::Engine::new(&config)
+}
+
 impl runtime::Runtime for WasmtimeRuntime {
     fn identifier(&self) -> &'static str {
         MODULE_SERIALIZATION_IDENTIFIER
@@ -60,8 +115,7 @@ impl runtime::Runtime for WasmtimeRuntime {
 
     fn prepare_module(&self, bytes: &[u8]) -> anyhow::Result<runtime::ModuleCache> {
         let engine = ENGINE.get_or_try_init(init_engine)?;
-        let cache = WasmtimeCache(wasmtime::Module::new(engine, bytes)?);
-        Ok(runtime::ModuleCache(Box::new(cache)))
+        prepare_module(engine, bytes)
     }
 
     fn clone_cache(&self, cache: &runtime::ModuleCache) -> Option<runtime::ModuleCache> {
@@ -72,11 +126,8 @@ impl runtime::Runtime for WasmtimeRuntime {
 
     unsafe fn load_cache(&self, path: &Path) -> Option<runtime::ModuleCache> {
         unsafe {
-            let module = std::fs::read(path).ok()?;
             let engine = ENGINE.get_or_try_init(init_engine).ok()?;
-            let cache = wasmtime::Module::deserialize(engine, module).ok()?;
-            let cache = WasmtimeCache(cache);
-            Some(runtime::ModuleCache(Box::new(cache)))
+            load_cache(engine, path)
         }
     }
 
@@ -118,21 +169,116 @@ impl runtime::Runtime for WasmtimeRuntime {
 
     fn init(
         &self,
-        _name: &str,
+        name: &str,
         imports: Vec<(String, runtime::Func)>,
         envs: Vec<(String, String)>,
         module: runtime::Module,
+        limits: runtime::Limits,
     ) -> anyhow::Result<Box<dyn runtime::Instance>> {
         let engine = ENGINE.get_or_try_init(init_engine)?;
+        init(engine, name, imports, envs, module, limits)
+    }
 
-        let module = match module {
-            runtime::Module::Cache(cache) => {
-                let cache = cache.0.downcast::<WasmtimeCache>().unwrap();
-                cache.0
-            }
-            runtime::Module::Bytes(buf) => wasmtime::Module::new(engine, buf)?,
-        };
+    fn metered(&self) -> Option<Arc<dyn runtime::Runtime>> {
+        Some(Arc::new(MeteredWasmtimeRuntime))
+    }
+}
+
+impl runtime::Runtime for MeteredWasmtimeRuntime {
+    fn identifier(&self) -> &'static str {
+        METERED_MODULE_SERIALIZATION_IDENTIFIER
+    }
+
+    fn prepare_module(&self, bytes: &[u8]) -> anyhow::Result<runtime::ModuleCache> {
+        let engine = METERED_ENGINE.get_or_try_init(init_metered_engine)?;
+        prepare_module(engine, bytes)
+    }
+
+    fn clone_cache(&self, cache: &runtime::ModuleCache) -> Option<runtime::ModuleCache> {
+        WasmtimeRuntime.clone_cache(cache)
+    }
+
+    unsafe fn load_cache(&self, path: &Path) -> Option<runtime::ModuleCache> {
+        unsafe {
+            let engine = METERED_ENGINE.get_or_try_init(init_metered_engine).ok()?;
+            load_cache(engine, path)
+        }
+    }
 
+    fn store_cache(&self, path: &Path, cache: &runtime::ModuleCache) -> anyhow::Result<()> {
+        WasmtimeRuntime.store_cache(path, cache)
+    }
+
+    fn init(
+        &self,
+        name: &str,
+        imports: Vec<(String, runtime::Func)>,
+        envs: Vec<(String, String)>,
+        module: runtime::Module,
+        limits: runtime::Limits,
+    ) -> anyhow::Result<Box<dyn runtime::Instance>> {
+        let engine = METERED_ENGINE.get_or_try_init(init_metered_engine)?;
+        init(engine, name, imports, envs, module, limits)
+    }
+}
+
+fn prepare_module(engine: &wasmtime::Engine, bytes: &[u8]) -> anyhow::Result<runtime::ModuleCache> {
+    let cache = WasmtimeCache(wasmtime::Module::new(engine, bytes)?);
+    Ok(runtime::ModuleCache(Box::new(cache)))
+}
+
+/// Loads a module compiled by `engine`.
+unsafe fn load_cache(engine: &wasmtime::Engine, path: &Path) -> Option<runtime::ModuleCache> {
+    unsafe {
+        let module = std::fs::read(path).ok()?;
+        let cache = wasmtime::Module::deserialize(engine, module).ok()?;
+        let cache = WasmtimeCache(cache);
+        Some(runtime::ModuleCache(Box::new(cache)))
+    }
+}
+
+/// Instantiates `module`, compiling it with `engine` if needed.
+fn init(
+    engine: &wasmtime::Engine,
+    _name: &str,
+    imports: Vec<(String, runtime::Func)>,
+    envs: Vec<(String, String)>,
+    module: runtime::Module,
+    limits: runtime::Limits,
+) -> anyhow::Result<Box<dyn runtime::Instance>> {
+    let module = match module {
+        runtime::Module::Cache(cache) => {
+            let cache = cache.0.downcast::<WasmtimeCache>().unwrap();
+            cache.0
+        }
+        runtime::Module::Bytes(buf) => wasmtime::Module::new(engine, buf)?,
+    };
+    let engine = module.engine();
+
+    let mut linker: wasmtime::Linker<WasmtimeTable> = wasmtime::Linker::new(engine);
+    for (name, func) in imports {
+        let ty = wasmtime::FuncType::new(
+            engine,
+            (0..func.sign.0).map(|_| wasmtime::ValType::I32),
+            (0..func.sign.1).map(|_| wasmtime::ValType::I32),
+        );
+        linker.func_new("env", &name, ty, move |caller, input, output| {
+            wasmtime_func_call(caller, input, output, &func)
+        })?;
+    }
+
+    wasi_common::sync::add_to_linker(&mut linker, |t| &mut t.wasi)?;
+
+    let instance_pre = linker.instantiate_pre(&module)?;
+    Ok(Box::new(WasmtimeInstance::new(instance_pre, envs, limits)?))
+}
+
+impl WasmtimeInstance {
+    fn new(
+        instance_pre: wasmtime::InstancePre<WasmtimeTable>,
+        envs: Vec<(String, String)>,
+        limits: runtime::Limits,
+    ) -> anyhow::Result<Self> {
         let current_dir = std::env::current_dir()?;
         let dir = wasi_common::sync::Dir::open_ambient_dir(
             &current_dir,
@@ -149,23 +295,20 @@ impl runtime::Runtime for WasmtimeRuntime {
             free_func: None,
 
             wasi,
+            limiter: WasmtimeLimiter { limits },
         };
-        let mut linker: wasmtime::Linker<WasmtimeTable> = wasmtime::Linker::new(engine);
-        for (name, func) in imports {
-            let ty = wasmtime::FuncType::new(
-                engine,
-                (0..func.sign.0).map(|_| wasmtime::ValType::I32),
-                (0..func.sign.1).map(|_| wasmtime::ValType::I32),
-            );
-            linker.func_new("env", &name, ty, move |caller, input, output| {
-                wasmtime_func_call(caller, input, output, &func)
-            })?;
-        }
-
-        wasi_common::sync::add_to_linker(&mut linker, |t| &mut t.wasi)?;
 
-        let mut store = wasmtime::Store::new(engine, table);
-        let instance = linker.instantiate(&mut store, &module)?;
+        let mut store = wasmtime::Store::new(instance_pre.module().engine(), table);
+        store.limiter(|t| &mut t.limiter);
+        if let Some(fuel) = limits.fuel {
+            store.set_fuel(fuel).context(
+                "module is not metered, fuel limit can't be enforced. Compile it with the runtime \
+                 returned by `Runtime::metered`",
+            )?;
+        }
+        let instance = instance_pre
+            .instantiate(&mut store)
+            .map_err(|err| map_limit_error(limits, err))?;
 
         let memory = instance
             .get_memory(&mut store, "memory")
@@ -183,16 +326,20 @@ impl runtime::Runtime for WasmtimeRuntime {
 
         instance
             .get_typed_func::<(), u32>(&mut store, "__get_transform_plugin_core_pkg_diag")?
-            .call(&mut store, ())?;
+            .call(&mut store, ())
+            .map_err(|err| map_limit_error(limits, err))?;
 
-        Ok(Box::new(WasmtimeInstance {
+        Ok(WasmtimeInstance {
+            instance_pre,
+            envs,
             store,
             instance,
             memory,
             alloc_func,
             free_func,
             transform_func,
-        }))
+            limits,
+        })
     }
 }
 
@@ -204,15 +351,17 @@ impl runtime::Instance for WasmtimeInstance {
         unresolved_mark: u32,
         should_enable_comments_proxy: u32,
     ) -> anyhow::Result<u32> {
-        self.transform_func.call(
-            &mut self.store,
-            (
-                program_ptr,
-                program_len,
-                unresolved_mark,
-                should_enable_comments_proxy,
-            ),
-        )
+        self.transform_func
+            .call(
+                &mut self.store,
+                (
+                    program_ptr,
+                    program_len,
+                    unresolved_mark,
+                    should_enable_comments_proxy,
+                ),
+            )
+            .map_err(|err| map_limit_error(self.limits, err))
     }
 
     fn caller(&mut self) -> anyhow::Result<Box<dyn runtime::Caller<'_> + '_>> {
@@ -221,6 +370,7 @@ impl runtime::Instance for WasmtimeInstance {
             memory: &self.memory,
             alloc_func: &self.alloc_func,
             free_func: &self.free_func,
+            limits: self.limits,
         }))
     }
 
@@ -229,6 +379,18 @@ impl runtime::Instance for WasmtimeInstance {
         let cache = WasmtimeCache(module.clone());
         Some(runtime::ModuleCache(Box::new(cache)))
     }
+
+    fn snapshot(&mut self) -> anyhow::Result<bool> {
+        // The module is instantiated again on reset, so there's nothing to save.
+        Ok(true)
+    }
+
+    fn reset(&mut self) -> anyhow::Result<bool> {
+        // Instantiation is cheap once imports are resolved, and unlike restoring the
+        // memory, it also restores the globals which are not exported.
+        *self = WasmtimeInstance::new(self.instance_pre.clone(), self.envs.clone(), self.limits)?;
+        Ok(true)
+    }
 }
 
 impl<'a> runtime::Caller<'a> for WasmtimeCaller<'a> {
@@ -243,11 +405,15 @@ impl<'a> runtime::Caller<'a> for WasmtimeCaller<'a> {
     }
 
     fn alloc(&mut self, size: u32) -> anyhow::Result<u32> {
-        self.alloc_func.call(&mut self.store, size)
+        self.alloc_func
+            .call(&mut self.store, size)
+            .map_err(|err| map_limit_error(self.limits, err))
     }
 
     fn free(&mut self, ptr: u32, size: u32) -> anyhow::Result<u32> {
-        self.free_func.call(&mut self.store, (ptr, size))
+        self.free_func
+            .call(&mut self.store, (ptr, size))
+            .map_err(|err| map_limit_error(self.limits, err))
     }
 }
 
@@ -271,6 +437,17 @@ impl<'a> runtime::Caller<'a> for WasmtimeCallerRef<'a> {
     }
 }
 
+/// Replaces `err` with [LimitExceeded] if it's caused by running out of fuel.
+///
+/// [LimitExceeded::Memory] is raised by [WasmtimeLimiter], so it's already
+/// part of the error.
+fn map_limit_error(limits: runtime::Limits, err: anyhow::Error) -> anyhow::Error {
+    match (limits.fuel, err.downcast_ref::<wasmtime::Trap>()) {
+        (Some(fuel), Some(wasmtime::Trap::OutOfFuel)) => LimitExceeded::Fuel(fuel).into(),
+        _ => err,
+    }
+}
+
 fn wasmtime_func_call(
     caller: wasmtime::Caller<'_, WasmtimeTable>,
     input: &[wasmtime::Val],
@@ -24,6 +24,15 @@ fn handle_func(func: ItemFn, ast_type: Ident) -> TokenStream {
         Ident::new("__transform_plugin_process_impl", Span::call_site());
     let transform_core_pkg_diag_ident =
         Ident::new("__get_transform_plugin_core_pkg_diag", Span::call_site());
+    let native_abi_version_ident = Ident::new("__swc_native_plugin_abi_version", Span::call_site());
+    let native_core_pkg_diag_ident = Ident::new(
+        "__swc_native_get_transform_plugin_core_pkg_diag",
+        Span::call_site(),
+    );
+    let native_process_impl_ident = Ident::new(
+        "__swc_native_transform_plugin_process_impl",
+        Span::call_site(),
+    );
 
     let ret = quote! {
         #func
@@ -45,38 +54,40 @@ fn handle_func(func: ItemFn, ast_type: Ident) -> TokenStream {
         pub struct PluginDiagnosticsEmitter;
 
         impl swc_core::common::errors::Emitter for PluginDiagnosticsEmitter {
-            #[cfg_attr(not(target_arch = "wasm32"), allow(unused))]
             fn emit(&mut self, db: &mut swc_core::common::errors::DiagnosticBuilder<'_>) {
                 let diag = swc_core::common::plugin::serialized::PluginSerializedBytes::try_serialize(&swc_core::common::plugin::serialized::VersionedSerializable::new(*db.diagnostic.clone()))
                     .expect("Should able to serialize Diagnostic");
-                let (ptr, len) = diag.as_ptr();
 
                 #[cfg(target_arch = "wasm32")] // Allow testing
                 unsafe {
+                    let (ptr, len) = diag.as_ptr();
                     __emit_diagnostics(ptr as u32, len as u32);
                 }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                swc_core::plugin::proxies::native::send_to_host("__emit_diagnostics", diag.as_slice());
             }
         }
 
 
         /// Call hosts's imported fn to set transform results.
         /// __set_transform_result is host side imported fn, which read and copies guest's byte into host.
-        fn send_transform_result_to_host(bytes_ptr: u32, bytes_ptr_len: u32) {
+        fn send_transform_result_to_host(bytes: &swc_core::common::plugin::serialized::PluginSerializedBytes) {
             #[cfg(target_arch = "wasm32")] // Allow testing
             unsafe {
-                __set_transform_result(bytes_ptr, bytes_ptr_len);
+                let (bytes_ptr, bytes_ptr_len) = bytes.as_ptr();
+                __set_transform_result(bytes_ptr as u32, bytes_ptr_len as u32);
             }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            swc_core::plugin::proxies::native::send_to_host("__set_transform_result", bytes.as_slice());
         }
 
         /// Internal function plugin_macro uses to create ptr to PluginError.
         fn construct_error_ptr(plugin_error: swc_core::common::plugin::serialized::PluginError) -> u32 {
             let ret = swc_core::common::plugin::serialized::PluginSerializedBytes::try_serialize(&swc_core::common::plugin::serialized::VersionedSerializable::new(plugin_error)).expect("Should able to serialize PluginError");
-            let (ptr, len) = ret.as_ptr();
 
-            send_transform_result_to_host(
-                ptr as _,
-                len as u32
-            );
+            send_transform_result_to_host(&ret);
             1
         }
 
@@ -97,12 +108,14 @@ fn handle_func(func: ItemFn, ast_type: Ident) -> TokenStream {
                 &swc_core::common::plugin::serialized::VersionedSerializable::new(result)
             ).expect("Diagnostics should be always serializable");
 
-            let (serialized_result_ptr, serialized_result_ptr_len) = serialized_result.as_ptr();
-
             #[cfg(target_arch = "wasm32")] // Allow testing
             unsafe {
+                let (serialized_result_ptr, serialized_result_ptr_len) = serialized_result.as_ptr();
                 __set_transform_plugin_core_pkg_diagnostics(serialized_result_ptr as _, serialized_result_ptr_len as u32);
             }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            swc_core::plugin::proxies::native::send_to_host("__set_transform_plugin_core_pkg_diagnostics", serialized_result.as_slice());
             0
         }
 
@@ -155,11 +168,49 @@ fn handle_func(func: ItemFn, ast_type: Ident) -> TokenStream {
             }
 
             let serialized_result = serialized_result.expect("Should be a realized transformed program");
-            let (serialized_result_ptr, serialized_result_ptr_len) = serialized_result.as_ptr();
 
-            send_transform_result_to_host(serialized_result_ptr as _, serialized_result_ptr_len as u32);
+            send_transform_result_to_host(&serialized_result);
             0
         }
+
+        // Entrypoints of the plugin built as a native shared library, wrapping the ones above.
+        // Refer swc_plugin_proxy::native for the C ABI.
+        #[cfg(not(target_arch = "wasm32"))]
+        #[no_mangle]
+        pub extern "C" fn #native_abi_version_ident() -> u32 {
+            swc_core::plugin::proxies::native::NATIVE_PLUGIN_ABI_VERSION
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        #[no_mangle]
+        pub extern "C" fn #native_core_pkg_diag_ident(
+            host: *const swc_core::plugin::proxies::native::NativePluginHost,
+        ) -> u32 {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                swc_core::plugin::proxies::native::with_host(host, #transform_core_pkg_diag_ident)
+            }))
+            .unwrap_or(swc_core::plugin::proxies::native::NATIVE_PLUGIN_PANICKED)
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        #[no_mangle]
+        pub extern "C" fn #native_process_impl_ident(
+            host: *const swc_core::plugin::proxies::native::NativePluginHost,
+            ast_ptr: *const u8, ast_ptr_len: usize,
+            unresolved_mark: u32, should_enable_comments_proxy: i32) -> u32 {
+            // Unwinding across the C ABI is undefined behavior.
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                swc_core::plugin::proxies::native::with_host(host, || {
+                    let Ok(ast_ptr_len) = u32::try_from(ast_ptr_len) else {
+                        let err = swc_core::common::plugin::serialized::PluginError::SizeInteropFailure("Program received from host is too large".to_string());
+                        return construct_error_ptr(err);
+                    };
+
+                    #transform_process_impl_ident(ast_ptr, ast_ptr_len, unresolved_mark, should_enable_comments_proxy)
+                })
+            }))
+            .unwrap_or(swc_core::plugin::proxies::native::NATIVE_PLUGIN_PANICKED)
+        }
     };
 
     ret.into()
@@ -1,3 +1,5 @@
+#[cfg(all(feature = "__plugin_mode", not(target_arch = "wasm32")))]
+use swc_common::plugin::native;
 #[cfg(feature = "__plugin_mode")]
 use swc_common::{
     BytePos,
@@ -49,7 +51,6 @@ impl PluginCommentsProxy {
     /// Copy guest memory's struct into host via CommentHostEnvironment's
     /// comment_buffer as serialized to pass param from guest to the host for
     /// the fn like add_leading*.
-    #[cfg_attr(not(target_arch = "wasm32"), allow(unused))]
     fn allocate_comments_buffer_to_host<T>(&self, value: T)
     where
         T: cbor4ii::core::enc::Encode,
@@ -74,73 +75,168 @@ impl PluginCommentsProxy {
                 );
             }
         }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let serialized = swc_common::plugin::serialized::PluginSerializedBytes::try_serialize(
+                &swc_common::plugin::serialized::VersionedSerializable::new(value),
+            )
+            .expect("Should able to serialize value");
+            native::send_to_host("__copy_comment_to_host_env", serialized.as_slice());
+        }
     }
 }
 
+// Outside of Wasm, the proxy calls the host of a native plugin if there's one,
+// or falls back to `COMMENTS` to allow testing.
 #[cfg(all(feature = "__plugin_mode", not(target_arch = "wasm32")))]
 #[swc_trace]
 impl Comments for PluginCommentsProxy {
     fn add_leading(&self, pos: BytePos, cmt: Comment) {
+        if native::has_host() {
+            self.allocate_comments_buffer_to_host(cmt);
+            native::call_host("__add_leading_comment_proxy", &[pos.0 as _], None);
+            return;
+        }
+
         swc_common::comments::COMMENTS.with(|c| {
             c.add_leading(pos, cmt);
         });
     }
 
     fn add_leading_comments(&self, pos: BytePos, comments: Vec<Comment>) {
+        if native::has_host() {
+            self.allocate_comments_buffer_to_host(comments);
+            native::call_host("__add_leading_comments_proxy", &[pos.0 as _], None);
+            return;
+        }
+
         swc_common::comments::COMMENTS.with(|c| {
             c.add_leading_comments(pos, comments);
         });
     }
 
     fn has_leading(&self, pos: BytePos) -> bool {
+        if let Some(ret) = native::call_host("__has_leading_comments_proxy", &[pos.0 as _], None) {
+            return ret != 0;
+        }
+
         swc_common::comments::COMMENTS.with(|c| c.has_leading(pos))
     }
 
     fn move_leading(&self, from: BytePos, to: BytePos) {
+        if native::call_host(
+            "__move_leading_comments_proxy",
+            &[from.0 as _, to.0 as _],
+            None,
+        )
+        .is_some()
+        {
+            return;
+        }
+
         swc_common::comments::COMMENTS.with(|c| {
             c.move_leading(from, to);
         });
     }
 
     fn take_leading(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        if native::has_host() {
+            return native::read_returned_result_from_host(
+                "__take_leading_comments_proxy",
+                &[pos.0 as _],
+            );
+        }
+
         swc_common::comments::COMMENTS.with(|c| c.take_leading(pos))
     }
 
     fn get_leading(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        if native::has_host() {
+            return native::read_returned_result_from_host(
+                "__get_leading_comments_proxy",
+                &[pos.0 as _],
+            );
+        }
+
         swc_common::comments::COMMENTS.with(|c| c.get_leading(pos))
     }
 
     fn add_trailing(&self, pos: BytePos, cmt: Comment) {
+        if native::has_host() {
+            self.allocate_comments_buffer_to_host(cmt);
+            native::call_host("__add_trailing_comment_proxy", &[pos.0 as _], None);
+            return;
+        }
+
         swc_common::comments::COMMENTS.with(|c| {
             c.add_trailing(pos, cmt);
         });
     }
 
     fn add_trailing_comments(&self, pos: BytePos, comments: Vec<Comment>) {
+        if native::has_host() {
+            self.allocate_comments_buffer_to_host(comments);
+            native::call_host("__add_trailing_comments_proxy", &[pos.0 as _], None);
+            return;
+        }
+
         swc_common::comments::COMMENTS.with(|c| {
             c.add_trailing_comments(pos, comments);
         });
     }
 
     fn has_trailing(&self, pos: BytePos) -> bool {
+        if let Some(ret) = native::call_host("__has_trailing_comments_proxy", &[pos.0 as _], None) {
+            return ret != 0;
+        }
+
         swc_common::comments::COMMENTS.with(|c| c.has_trailing(pos))
     }
 
     fn move_trailing(&self, from: BytePos, to: BytePos) {
+        if native::call_host(
+            "__move_trailing_comments_proxy",
+            &[from.0 as _, to.0 as _],
+            None,
+        )
+        .is_some()
+        {
+            return;
+        }
+
         swc_common::comments::COMMENTS.with(|c| {
             c.move_trailing(from, to);
         });
     }
 
     fn take_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        if native::has_host() {
+            return native::read_returned_result_from_host(
+                "__take_trailing_comments_proxy",
+                &[pos.0 as _],
+            );
+        }
+
         swc_common::comments::COMMENTS.with(|c| c.take_trailing(pos))
     }
 
     fn get_trailing(&self, pos: BytePos) -> Option<Vec<Comment>> {
+        if native::has_host() {
+            return native::read_returned_result_from_host(
+                "__get_trailing_comments_proxy",
+                &[pos.0 as _],
+            );
+        }
+
         swc_common::comments::COMMENTS.with(|c| c.get_trailing(pos))
     }
 
     fn add_pure_comment(&self, pos: BytePos) {
+        if native::call_host("__add_pure_comment_proxy", &[pos.0 as _], None).is_some() {
+            return;
+        }
+
         swc_common::comments::COMMENTS.with(|c| {
             c.add_pure_comment(pos);
         });
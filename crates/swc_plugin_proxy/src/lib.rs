@@ -13,3 +13,5 @@ pub use memory_interop::AllocatedBytesPtr;
 pub use metadata::TransformPluginProgramMetadata;
 #[cfg(feature = "__plugin_mode")]
 pub use source_map::PluginSourceMapProxy;
+#[cfg(not(target_arch = "wasm32"))]
+pub use swc_common::plugin::native;
@@ -51,11 +51,13 @@ impl TransformPluginProgramMetadata {
         });
 
         #[cfg(not(target_arch = "wasm32"))]
-        None
+        swc_common::plugin::native::read_returned_result_from_host(
+            "__get_transform_plugin_config",
+            &[],
+        )
     }
 
     /// Returns metadata value for given key.
-    #[cfg_attr(not(target_arch = "wasm32"), allow(unused))]
     pub fn get_context(
         &self,
         key: &swc_common::plugin::metadata::TransformPluginMetadataContextKind,
@@ -66,7 +68,10 @@ impl TransformPluginProgramMetadata {
         });
 
         #[cfg(not(target_arch = "wasm32"))]
-        None
+        swc_common::plugin::native::read_returned_result_from_host(
+            "__get_transform_context",
+            &[*key as i32],
+        )
     }
 
     /// Returns an experimental metadata value if exists. Returned value is
@@ -78,7 +83,6 @@ impl TransformPluginProgramMetadata {
     ///
     /// Note these metadata values can be changed anytime. There is no gaurantee
     /// values will be available across different @swc/core versions.
-    #[cfg_attr(not(target_arch = "wasm32"), allow(unused))]
     pub fn get_experimental_context(&self, key: &str) -> Option<String> {
         #[cfg(target_arch = "wasm32")]
         return read_returned_result_from_host(|serialized_ptr| unsafe {
@@ -93,7 +97,21 @@ impl TransformPluginProgramMetadata {
         });
 
         #[cfg(not(target_arch = "wasm32"))]
-        None
+        {
+            let serialized = swc_common::plugin::serialized::PluginSerializedBytes::try_serialize(
+                &swc_common::plugin::serialized::VersionedSerializable::new(key.to_string()),
+            )
+            .expect("Should be serializable");
+            swc_common::plugin::native::send_to_host(
+                "__copy_context_key_to_host_env",
+                serialized.as_slice(),
+            );
+
+            swc_common::plugin::native::read_returned_result_from_host(
+                "__get_experimental_transform_context",
+                &[],
+            )
+        }
     }
 
     /// Returns experimental metadata context, but returns whole value as a
@@ -58,11 +58,27 @@ pub struct PluginSourceMapProxy {
     pub source_file: OnceCell<swc_common::sync::Lrc<SourceFile>>,
 }
 
-#[cfg(all(
-    feature = "encoding-impl",
-    feature = "__plugin_mode",
-    target_arch = "wasm32"
-))]
+/// Calls the host function `$name`, which writes its serialized return value
+/// to the pointer passed as the last argument.
+#[cfg(all(feature = "encoding-impl", feature = "__plugin_mode"))]
+macro_rules! read_from_host {
+    ($name:ident($($arg:expr),*)) => {{
+        #[cfg(target_arch = "wasm32")]
+        let ret = read_returned_result_from_host(|serialized_ptr| unsafe {
+            $name($($arg,)* serialized_ptr)
+        });
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let ret = swc_common::plugin::native::read_returned_result_from_host(
+            stringify!($name),
+            &[$($arg as i32),*],
+        );
+
+        ret
+    }};
+}
+
+#[cfg(all(feature = "encoding-impl", feature = "__plugin_mode"))]
 #[swc_trace]
 impl PluginSourceMapProxy {
     pub fn span_to_source<F, Ret>(
@@ -73,148 +89,102 @@ impl PluginSourceMapProxy {
     where
         F: FnOnce(&str, usize, usize) -> Ret,
     {
-        #[cfg(target_arch = "wasm32")]
-        {
-            use swc_common::plugin::serialized::ResultValue;
+        use swc_common::plugin::serialized::ResultValue;
 
-            let src: ResultValue<String, Box<SpanSnippetError>> =
-                read_returned_result_from_host(|serialized_ptr| unsafe {
-                    __span_to_source_proxy(sp.lo.0, sp.hi.0, serialized_ptr)
-                })
+        let src: ResultValue<String, Box<SpanSnippetError>> =
+            read_from_host!(__span_to_source_proxy(sp.lo.0, sp.hi.0))
                 .expect("Host should return source code");
 
-            let src = src.0?;
-            return Ok(extract_source(&src, 0, src.len()));
-        }
-
-        #[cfg(not(target_arch = "wasm32"))]
-        unimplemented!("Sourcemap proxy cannot be called in this context")
+        let src = src.0?;
+        Ok(extract_source(&src, 0, src.len()))
     }
 }
 
 /// Subset of SourceMap interface supported in plugin.
 /// Unlike `Comments`, this does not fully implement `SourceMap`.
-#[cfg(feature = "__plugin_mode")]
+#[cfg(all(feature = "encoding-impl", feature = "__plugin_mode"))]
 impl SourceMapper for PluginSourceMapProxy {
-    #[cfg_attr(not(target_arch = "wasm32"), allow(unused))]
     fn lookup_char_pos(&self, pos: BytePos) -> Loc {
-        #[cfg(target_arch = "wasm32")]
-        {
-            let should_request_source_file = if self.source_file.get().is_none() {
-                1
-            } else {
-                0
-            };
-            let partial_loc: PartialLoc = read_returned_result_from_host(|serialized_ptr| unsafe {
-                __lookup_char_pos_source_map_proxy(
-                    pos.0,
-                    should_request_source_file,
-                    serialized_ptr,
-                )
-            })
-            .expect("Host should return PartialLoc");
+        let should_request_source_file = if self.source_file.get().is_none() {
+            1
+        } else {
+            0
+        };
+        let partial_loc: PartialLoc = read_from_host!(__lookup_char_pos_source_map_proxy(
+            pos.0,
+            should_request_source_file
+        ))
+        .expect("Host should return PartialLoc");
 
-            if self.source_file.get().is_none() {
-                if let Some(source_file) = partial_loc.source_file {
-                    self.source_file
-                        .set(source_file)
-                        .expect("Should able to set source file");
-                }
+        if self.source_file.get().is_none() {
+            if let Some(source_file) = partial_loc.source_file {
+                self.source_file
+                    .set(source_file)
+                    .expect("Should able to set source file");
             }
-
-            return Loc {
-                file: self
-                    .source_file
-                    .get()
-                    .expect("SourceFile should exist")
-                    .clone(),
-                line: partial_loc.line,
-                col: CharPos(partial_loc.col),
-                col_display: partial_loc.col_display,
-            };
         }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        unimplemented!("Sourcemap proxy cannot be called in this context")
+        Loc {
+            file: self
+                .source_file
+                .get()
+                .expect("SourceFile should exist")
+                .clone(),
+            line: partial_loc.line,
+            col: CharPos(partial_loc.col),
+            col_display: partial_loc.col_display,
+        }
     }
 
     fn span_to_lines(&self, sp: Span) -> FileLinesResult {
-        #[cfg(target_arch = "wasm32")]
-        {
-            use swc_common::plugin::serialized::ResultValue;
+        use swc_common::plugin::serialized::ResultValue;
 
-            let should_request_source_file = if self.source_file.get().is_none() {
-                1
-            } else {
-                0
-            };
-            let partial_files: ResultValue<PartialFileLines, Box<SpanLinesError>> =
-                read_returned_result_from_host(|serialized_ptr| unsafe {
-                    __span_to_lines_proxy(
-                        sp.lo.0,
-                        sp.hi.0,
-                        should_request_source_file,
-                        serialized_ptr,
-                    )
-                })
-                .expect("Host should return PartialFileLinesResult");
+        let should_request_source_file = if self.source_file.get().is_none() {
+            1
+        } else {
+            0
+        };
+        let partial_files: ResultValue<PartialFileLines, Box<SpanLinesError>> = read_from_host!(
+            __span_to_lines_proxy(sp.lo.0, sp.hi.0, should_request_source_file)
+        )
+        .expect("Host should return PartialFileLinesResult");
 
-            if self.source_file.get().is_none() {
-                if let Ok(p) = &partial_files.0 {
-                    if let Some(source_file) = &p.file {
-                        self.source_file
-                            .set(source_file.clone())
-                            .expect("Should able to set source file");
-                    }
+        if self.source_file.get().is_none() {
+            if let Ok(p) = &partial_files.0 {
+                if let Some(source_file) = &p.file {
+                    self.source_file
+                        .set(source_file.clone())
+                        .expect("Should able to set source file");
                 }
             }
-
-            return partial_files.0.map(|files| FileLines {
-                file: self
-                    .source_file
-                    .get()
-                    .expect("SourceFile should exist")
-                    .clone(),
-                lines: files.lines,
-            });
         }
 
-        #[cfg(not(target_arch = "wasm32"))]
-        unimplemented!("Sourcemap proxy cannot be called in this context")
+        partial_files.0.map(|files| FileLines {
+            file: self
+                .source_file
+                .get()
+                .expect("SourceFile should exist")
+                .clone(),
+            lines: files.lines,
+        })
     }
 
     fn span_to_string(&self, sp: Span) -> String {
-        #[cfg(target_arch = "wasm32")]
-        return read_returned_result_from_host(|serialized_ptr| unsafe {
-            __span_to_string_proxy(sp.lo.0, sp.hi.0, serialized_ptr)
-        })
-        .expect("Host should return String");
-
-        #[cfg(not(target_arch = "wasm32"))]
-        unimplemented!("Sourcemap proxy cannot be called in this context")
+        read_from_host!(__span_to_string_proxy(sp.lo.0, sp.hi.0))
+            .expect("Host should return String")
     }
 
     fn span_to_filename(&self, sp: Span) -> Lrc<FileName> {
-        #[cfg(target_arch = "wasm32")]
-        return Lrc::new(
-            read_returned_result_from_host(|serialized_ptr| unsafe {
-                __span_to_filename_proxy(sp.lo.0, sp.hi.0, serialized_ptr)
-            })
-            .expect("Host should return Filename"),
-        );
-
-        #[cfg(not(target_arch = "wasm32"))]
-        unimplemented!("Sourcemap proxy cannot be called in this context")
+        Lrc::new(
+            read_from_host!(__span_to_filename_proxy(sp.lo.0, sp.hi.0))
+                .expect("Host should return Filename"),
+        )
     }
 
     fn span_to_snippet(&self, sp: Span) -> Result<String, Box<SpanSnippetError>> {
-        #[cfg(target_arch = "wasm32")]
-        return self.span_to_source(sp, |src, start_index, end_index| {
+        self.span_to_source(sp, |src, start_index, end_index| {
             src[start_index..end_index].to_string()
-        });
-
-        #[cfg(not(target_arch = "wasm32"))]
-        unimplemented!("Sourcemap proxy cannot be called in this context")
+        })
     }
 
     fn merge_spans(&self, sp_lhs: Span, sp_rhs: Span) -> Option<Span> {
@@ -239,7 +209,42 @@ impl SourceMapper for PluginSourceMapProxy {
         };
 
         #[cfg(not(target_arch = "wasm32"))]
-        unimplemented!("Sourcemap proxy cannot be called in this context")
+        {
+            // The span written by the host is never larger than this one.
+            let span = Span {
+                lo: BytePos(u32::MAX),
+                hi: BytePos(u32::MAX),
+            };
+
+            let mut buf = swc_common::plugin::serialized::PluginSerializedBytes::try_serialize(
+                &swc_common::plugin::serialized::VersionedSerializable::new(span),
+            )
+            .expect("Should be serializable")
+            .as_slice()
+            .to_vec();
+            let ret = swc_common::plugin::native::call_host(
+                "__merge_spans_proxy",
+                &[
+                    sp_lhs.lo.0 as _,
+                    sp_lhs.hi.0 as _,
+                    sp_rhs.lo.0 as _,
+                    sp_rhs.hi.0 as _,
+                ],
+                Some(&mut buf),
+            )
+            .expect("Host should merge spans");
+
+            if ret != 1 {
+                return None;
+            }
+
+            Some(
+                swc_common::plugin::serialized::PluginSerializedBytes::from_bytes(buf)
+                    .deserialize()
+                    .expect("Should able to deserialize")
+                    .into_inner(),
+            )
+        }
     }
 
     fn call_span_if_macro(&self, sp: Span) -> Span {
@@ -255,7 +260,12 @@ impl SourceMapper for PluginSourceMapProxy {
         };
 
         #[cfg(not(target_arch = "wasm32"))]
-        unimplemented!("Sourcemap proxy cannot be called in this context")
+        return swc_common::plugin::native::call_host(
+            "__doctest_offset_line_proxy",
+            &[line as _],
+            None,
+        )
+        .expect("Host should return line") as usize;
     }
 }
 
@@ -4,6 +4,7 @@ use std::{
     env::current_dir,
     path::{Path, PathBuf},
     str::FromStr,
+    sync::Arc,
 };
 
 use anyhow::{Context, Error};
@@ -12,6 +13,7 @@ use rustc_hash::FxHashMap;
 use swc_common::sync::{Lazy, OnceCell};
 
 use crate::{
+    instance_pool::InstancePool,
     plugin_module_bytes::{CompiledPluginModuleBytes, PluginModuleBytes, RawPluginModuleBytes},
     runtime,
 };
@@ -189,6 +191,10 @@ pub struct PluginModuleCache {
     /// This is a precaution only yet, for the preparation of wasm thread
     /// support in the future.
     instantiation_lock: Mutex<()>,
+    /// Instances of the cached plugin modules, reused across the transforms
+    /// opting in with
+    /// [TransformExecutor::with_instance_pool](crate::transform_executor::TransformExecutor::with_instance_pool).
+    pub instance_pool: Arc<InstancePool>,
 }
 
 impl PluginModuleCache {
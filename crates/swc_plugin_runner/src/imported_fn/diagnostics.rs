@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
 use parking_lot::Mutex;
+use swc_common::plugin::{
+    diagnostics::PluginAnalysisDiagnostic, serialized::PluginSerializedBytes,
+};
 
 use crate::{memory_interop::copy_bytes_into_host, runtime};
 
@@ -29,3 +32,21 @@ pub fn set_plugin_core_pkg_diagnostics(
     let mut buf = env.core_diag_buffer.lock();
     copy_bytes_into_host(caller, bytes_ptr, bytes_ptr_len, &mut buf);
 }
+
+/// Receives a [PluginAnalysisDiagnostic] reported by the plugin, and forwards
+/// it to [swc_transform_common::diagnostics::report].
+#[tracing::instrument(level = "info", skip_all)]
+pub fn emit_analysis_diagnostic(
+    caller: &mut dyn runtime::Caller<'_>,
+    _env: &DiagnosticContextHostEnvironment,
+    bytes_ptr: i32,
+    bytes_ptr_len: i32,
+) {
+    let mut diagnostic_bytes = Vec::new();
+    copy_bytes_into_host(caller, bytes_ptr, bytes_ptr_len, &mut diagnostic_bytes);
+    let serialized = PluginSerializedBytes::from_bytes(diagnostic_bytes);
+    let diagnostic = PluginSerializedBytes::deserialize::<PluginAnalysisDiagnostic>(&serialized)
+        .expect("Should able to be deserialized into analysis diagnostic");
+
+    swc_transform_common::diagnostics::report(diagnostic.into_inner());
+}
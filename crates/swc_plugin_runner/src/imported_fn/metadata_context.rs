@@ -13,7 +13,7 @@ use crate::{
 
 #[derive(Clone)]
 pub struct MetadataContextHostEnvironment {
-    pub metadata_context: Arc<TransformPluginMetadataContext>,
+    pub metadata_context: Arc<Mutex<Arc<TransformPluginMetadataContext>>>,
     pub transform_plugin_config: Option<serde_json::Value>,
     /// A buffer to string key to the context plugin need to pass to the host.
     pub mutable_context_key_buffer: Arc<Mutex<Vec<u8>>>,
@@ -21,7 +21,7 @@ pub struct MetadataContextHostEnvironment {
 
 impl MetadataContextHostEnvironment {
     pub fn new(
-        metadata_context: &Arc<TransformPluginMetadataContext>,
+        metadata_context: &Arc<Mutex<Arc<TransformPluginMetadataContext>>>,
         plugin_config: &Option<serde_json::Value>,
         mutable_context_key_buffer: &Arc<Mutex<Vec<u8>>>,
     ) -> Self {
@@ -79,6 +79,7 @@ pub fn get_transform_context(
 ) -> i32 {
     let Some(value) = env
         .metadata_context
+        .lock()
         .get(&TransformPluginMetadataContextKind::from(key))
     else {
         return 0;
@@ -105,6 +106,7 @@ pub fn get_experimental_transform_context(
 
     let value = env
         .metadata_context
+        .lock()
         .experimental
         .get(&key)
         .map(|v| v.to_string());
@@ -128,7 +130,7 @@ pub fn get_raw_experiemtal_transform_context(
     allocated_ret_ptr: u32,
 ) -> i32 {
     let experimental_context =
-        swc_common::plugin::metadata::Context(env.metadata_context.experimental.clone());
+        swc_common::plugin::metadata::Context(env.metadata_context.lock().experimental.clone());
     let experimental_context = VersionedSerializable::new(experimental_context);
     let serialized_experimental_context_bytes =
         PluginSerializedBytes::try_serialize(&experimental_context)
@@ -54,7 +54,10 @@ use crate::{
             move_trailing_comments_proxy, take_leading_comments_proxy,
             take_trailing_comments_proxy,
         },
-        diagnostics::{DiagnosticContextHostEnvironment, set_plugin_core_pkg_diagnostics},
+        diagnostics::{
+            DiagnosticContextHostEnvironment, emit_analysis_diagnostic,
+            set_plugin_core_pkg_diagnostics,
+        },
         metadata_context::get_raw_experiemtal_transform_context,
         set_transform_result::{TransformResultHostEnvironment, set_transform_result},
         source_map::span_to_source_proxy,
@@ -134,6 +137,7 @@ pub(crate) fn build_import_object(
 
     // core_diagnostics
     define!(fn set_plugin_core_pkg_diagnostics(env diagnostics_env, bytes_ptr, bytes_ptr_len));
+    define!(fn emit_analysis_diagnostic(env diagnostics_env, bytes_ptr, bytes_ptr_len));
 
     // metadata
     define!(fn copy_context_key_to_host_env(env metadata_env, bytes_ptr, bytes_ptr_len));
@@ -192,6 +196,7 @@ pub(crate) fn build_import_object(
             "__set_transform_plugin_core_pkg_diagnostics",
             set_plugin_core_pkg_diagnostics,
         ),
+        ("__emit_analysis_diagnostic", emit_analysis_diagnostic),
         // metadata
         (
             "__copy_context_key_to_host_env",
@@ -0,0 +1,138 @@
+//! A pool of plugin instances, reused across transforms.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+
+use crate::{runtime, transform_executor::PluginTransformState};
+
+/// Identifies instances which can be used interchangeably, as they were
+/// created from the same plugin with the same configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct InstanceKey {
+    plugin: String,
+    config_hash: u64,
+}
+
+impl InstanceKey {
+    pub fn new(
+        plugin: &str,
+        plugin_config: &Option<serde_json::Value>,
+        envs: &[(String, String)],
+        limits: runtime::Limits,
+    ) -> Self {
+        let mut hasher = DefaultHasher::new();
+        plugin_config
+            .as_ref()
+            .map(|config| config.to_string())
+            .hash(&mut hasher);
+        envs.hash(&mut hasher);
+        limits.fuel.hash(&mut hasher);
+        limits.max_memory.hash(&mut hasher);
+
+        Self {
+            plugin: plugin.to_string(),
+            config_hash: hasher.finish(),
+        }
+    }
+}
+
+/// Counters of an [InstancePool].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct InstancePoolStats {
+    /// Number of instances created by transforms using the pool.
+    pub created: u64,
+    /// Number of transforms which reused an idle instance.
+    pub reused: u64,
+    /// Number of instances dropped instead of being returned to the pool,
+    /// either because they can't be reset or because the pool is full.
+    pub discarded: u64,
+}
+
+/// A pool of idle plugin instances, shared by transforms running in parallel.
+///
+/// Instantiating a plugin is expensive compared to transforming a single file,
+/// so a transform takes an idle instance if there's one and returns it once
+/// done. The linear memory of an instance is reset before it's returned, so a
+/// transform never observes the state left by another one.
+pub struct InstancePool {
+    idle: Mutex<FxHashMap<InstanceKey, Vec<PluginTransformState>>>,
+    /// Maximum number of idle instances kept per key.
+    max_idle: usize,
+
+    created: AtomicU64,
+    reused: AtomicU64,
+    discarded: AtomicU64,
+}
+
+impl Default for InstancePool {
+    /// Keeps an idle instance per available thread.
+    fn default() -> Self {
+        Self::new(
+            std::thread::available_parallelism()
+                .map(usize::from)
+                .unwrap_or(1),
+        )
+    }
+}
+
+impl InstancePool {
+    /// Creates a pool keeping at most `max_idle` idle instances for each
+    /// plugin and configuration, which should match the number of threads
+    /// running transforms.
+    pub fn new(max_idle: usize) -> Self {
+        Self {
+            idle: Default::default(),
+            max_idle,
+            created: Default::default(),
+            reused: Default::default(),
+            discarded: Default::default(),
+        }
+    }
+
+    pub fn stats(&self) -> InstancePoolStats {
+        InstancePoolStats {
+            created: self.created.load(Ordering::Relaxed),
+            reused: self.reused.load(Ordering::Relaxed),
+            discarded: self.discarded.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Drops all idle instances.
+    pub fn clear(&self) {
+        self.idle.lock().clear();
+    }
+
+    pub(crate) fn take(&self, key: &InstanceKey) -> Option<PluginTransformState> {
+        let state = self.idle.lock().get_mut(key)?.pop()?;
+        self.reused.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!("Reusing an instance of {}", key.plugin);
+
+        Some(state)
+    }
+
+    pub(crate) fn record_created(&self) {
+        self.created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_discarded(&self) {
+        self.discarded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn put(&self, key: InstanceKey, state: PluginTransformState) {
+        let mut idle = self.idle.lock();
+        let instances = idle.entry(key).or_default();
+
+        if instances.len() < self.max_idle {
+            instances.push(state);
+        } else {
+            drop(idle);
+            self.record_discarded();
+        }
+    }
+}
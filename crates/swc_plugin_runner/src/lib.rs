@@ -9,6 +9,7 @@ pub mod cache;
 mod host_environment;
 #[cfg(feature = "encoding-impl")]
 mod imported_fn;
+pub mod instance_pool;
 #[cfg(feature = "encoding-impl")]
 mod memory_interop;
 pub mod plugin_module_bytes;
@@ -1,6 +1,8 @@
 //! Plugin runtime abstract
 
-use std::{any::Any, fmt, path::Path};
+use std::{any::Any, fmt, path::Path, sync::Arc};
+
+pub use swc_common::plugin::limits::Limits;
 
 /// Runtime FFI Value
 pub type Value = i32;
@@ -22,6 +24,31 @@ pub struct Func {
     pub func: Box<dyn Fn(&mut dyn Caller<'_>, &[Value], &mut [Value]) + Send + Sync>,
 }
 
+/// Error returned by a [Runtime] when a plugin is cancelled because it hit
+/// one of its [Limits].
+///
+/// Runtimes should make it downcastable from the returned [anyhow::Error].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// The fuel limit was exhausted.
+    Fuel(u64),
+    /// The linear memory tried to grow beyond the limit.
+    Memory(u64),
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::Fuel(limit) => write!(f, "ran out of fuel (limit: {limit})"),
+            LimitExceeded::Memory(limit) => {
+                write!(f, "exceeded the memory limit (limit: {limit} bytes)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
 /// Plugin runtime abstract
 pub trait Runtime: fmt::Debug + Send + Sync {
     /// An identifier used to identify the runtime implement,
@@ -42,12 +69,15 @@ pub trait Runtime: fmt::Debug + Send + Sync {
     /// * The runtime should call the `__get_transform_plugin_core_pkg_diag`
     ///   function once after instantiation to check that initialization was
     ///   completed correctly.
+    /// * `limits` apply to the whole lifetime of the instance. Exceeding one of
+    ///   them should cancel the execution with [LimitExceeded].
     fn init(
         &self,
         name: &str,
         imports: Vec<(String, Func)>,
         envs: Vec<(String, String)>,
         module: Module,
+        limits: Limits,
     ) -> anyhow::Result<Box<dyn Instance>>;
 
     /// Clone module cache
@@ -71,6 +101,15 @@ pub trait Runtime: fmt::Debug + Send + Sync {
     fn store_cache(&self, _path: &Path, _cache: &ModuleCache) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Returns the runtime to use instead of this one when [Limits::fuel] is
+    /// set, if the modules compiled by this runtime can't be metered.
+    ///
+    /// Metering slows down every plugin, so runtimes should compile metered
+    /// modules only when they are asked to.
+    fn metered(&self) -> Option<Arc<dyn Runtime>> {
+        None
+    }
 }
 
 /// Instance Accessor
@@ -112,6 +151,24 @@ pub trait Instance: Send + Sync {
     fn cleanup(&mut self) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Take a snapshot of the state of the instance, i.e. its linear memory
+    /// and globals, to be restored by [Instance::reset].
+    ///
+    /// Returns `false` if the runtime can't reset instances, in which case the
+    /// instance is not reused across transforms.
+    fn snapshot(&mut self) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    /// Restore the state of the instance from the last [Instance::snapshot],
+    /// and refill the [Limits] passed to [Runtime::init].
+    ///
+    /// Returns `false` if the state can't be restored, e.g. because the memory
+    /// grew since the snapshot, in which case the instance should be dropped.
+    fn reset(&mut self) -> anyhow::Result<bool> {
+        Ok(false)
+    }
 }
 
 impl Func {
@@ -25,12 +25,23 @@ use crate::{
     },
     memory_interop::write_into_memory_view,
 };
-use crate::{plugin_module_bytes::PluginModuleBytes, runtime};
+use crate::{
+    instance_pool::{InstanceKey, InstancePool},
+    plugin_module_bytes::PluginModuleBytes,
+    runtime,
+};
 
 /// An internal state to the plugin transform.
-struct PluginTransformState {
+pub(crate) struct PluginTransformState {
     instance: Box<dyn runtime::Instance>,
     transform_result: Arc<Mutex<Vec<u8>>>,
+    /// Contexts of the file being transformed, replaced when the instance is
+    /// reused for another file.
+    metadata_context: Arc<Mutex<Arc<TransformPluginMetadataContext>>>,
+    source_map: Arc<Mutex<Arc<SourceMap>>>,
+    /// Whether [runtime::Instance::snapshot] succeeded, so the instance can be
+    /// reset and reused.
+    resettable: bool,
     #[allow(unused)]
     plugin_core_diag: PluginCorePkgDiagnostics,
 }
@@ -38,7 +49,7 @@ struct PluginTransformState {
 #[cfg(feature = "encoding-impl")]
 impl PluginTransformState {
     fn run(
-        mut self,
+        &mut self,
         program: &PluginSerializedBytes,
         unresolved_mark: swc_common::Mark,
         should_enable_comments_proxy: Option<bool>,
@@ -46,19 +57,18 @@ impl PluginTransformState {
         let should_enable_comments_proxy =
             u32::from(should_enable_comments_proxy.unwrap_or_default());
 
+        // Allocation may hit the memory limit of the instance, so it's done before
+        // writing to propagate the error.
+        let serialized_len = program
+            .as_slice()
+            .len()
+            .try_into()
+            .expect("Should able to convert size");
+        let allocated_ptr = self.instance.caller()?.alloc(serialized_len)?;
+
         // Copy host's serialized bytes into guest (plugin)'s allocated memory.
-        let guest_program_ptr = write_into_memory_view(
-            &mut *self.instance.caller()?,
-            program,
-            |caller, serialized_len| {
-                let serialized_len = serialized_len
-                    .try_into()
-                    .expect("Should able to convert size");
-                caller.alloc(serialized_len).unwrap_or_else(|_| {
-                    panic!("Should able to allocate memory for the size of {serialized_len}")
-                })
-            },
-        );
+        let guest_program_ptr =
+            write_into_memory_view(&mut *self.instance.caller()?, program, |_, _| allocated_ptr);
 
         let returned_ptr_result = self.instance.transform(
             guest_program_ptr.0,
@@ -70,17 +80,11 @@ impl PluginTransformState {
         self.instance
             .caller()?
             .free(guest_program_ptr.0, guest_program_ptr.1)?;
-        self.instance.cleanup()?;
 
         // Construct serialized struct from raw bytes.
-        // Since we have finished transformation, it's safe to fetch the data from
-        // Arc<Mutex<T>>
-        drop(self.instance);
-        let transformed_result = Arc::try_unwrap(self.transform_result)
-            .map_err(|_| {
-                anyhow!("Failed to unwrap Arc: other references to transform_result exist")
-            })?
-            .into_inner();
+        // Since we have finished transformation, the buffer can be taken and left
+        // empty for the next transform if the instance is reused.
+        let transformed_result = std::mem::take(&mut *self.transform_result.lock());
         let ret = PluginSerializedBytes::from_bytes(transformed_result);
 
         if returned_ptr_result == 0 {
@@ -150,6 +154,8 @@ pub struct TransformExecutor {
     plugin_config: Option<serde_json::Value>,
     module_bytes: Box<dyn PluginModuleBytes>,
     runtime: Arc<dyn runtime::Runtime>,
+    limits: runtime::Limits,
+    instance_pool: Option<Arc<InstancePool>>,
 }
 
 #[cfg(feature = "encoding-impl")]
@@ -175,9 +181,47 @@ impl TransformExecutor {
             plugin_config,
             module_bytes,
             runtime,
+            limits: Default::default(),
+            instance_pool: None,
         }
     }
 
+    /// Sets the resource limits applied to each [TransformExecutor::transform]
+    /// call.
+    pub fn with_limits(mut self, limits: runtime::Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Reuses instances from `instance_pool` across
+    /// [TransformExecutor::transform] calls, instead of creating a new one for
+    /// each call.
+    pub fn with_instance_pool(mut self, instance_pool: Arc<InstancePool>) -> Self {
+        self.instance_pool = Some(instance_pool);
+        self
+    }
+
+    fn envs(&self) -> Vec<(String, String)> {
+        self.plugin_env_vars
+            .iter()
+            .flat_map(|list| list.iter())
+            .filter_map(|name| {
+                std::env::var(name.as_str())
+                    .ok()
+                    .map(|value| (name.as_str().into(), value))
+            })
+            .collect()
+    }
+
+    fn instance_key(&self) -> InstanceKey {
+        InstanceKey::new(
+            self.module_bytes.get_module_name(),
+            &self.plugin_config,
+            &self.envs(),
+            self.limits,
+        )
+    }
+
     // Import, export, and create memory for the plugin to communicate between host
     // and guest then acquire necessary exports from the plugin.
     fn setup_plugin_env_exports(&mut self) -> Result<PluginTransformState, Error> {
@@ -187,8 +231,9 @@ impl TransformExecutor {
         let module = self.module_bytes.compile_module(&*self.runtime);
 
         let context_key_buffer = Arc::new(Mutex::new(Vec::new()));
+        let metadata_context = Arc::new(Mutex::new(self.metadata_context.clone()));
         let metadata_env = Arc::new(MetadataContextHostEnvironment::new(
-            &self.metadata_context,
+            &metadata_context,
             &self.plugin_config,
             &context_key_buffer,
         ));
@@ -219,19 +264,10 @@ impl TransformExecutor {
             source_map_host_env,
             diagnostics_env,
         );
-        let envs = self
-            .plugin_env_vars
-            .iter()
-            .flat_map(|list| list.iter())
-            .filter_map(|name| {
-                std::env::var(name.as_str())
-                    .ok()
-                    .map(|value| (name.as_str().into(), value))
-            })
-            .collect::<Vec<_>>();
+        let envs = self.envs();
         let instance = self
             .runtime
-            .init(module_name, import_object, envs, module)?;
+            .init(module_name, import_object, envs, module, self.limits)?;
 
         let diag_result: PluginCorePkgDiagnostics =
             PluginSerializedBytes::from_bytes(diagnostics_buffer.lock().clone())
@@ -241,21 +277,101 @@ impl TransformExecutor {
         Ok(PluginTransformState {
             instance,
             transform_result,
+            metadata_context,
+            source_map,
+            resettable: false,
             plugin_core_diag: diag_result,
         })
     }
 
+    /// Takes an idle instance from the pool if there's one, or creates a new
+    /// instance.
+    fn acquire(&mut self) -> Result<PluginTransformState, Error> {
+        let Some(pool) = self.instance_pool.clone() else {
+            return self.setup_plugin_env_exports();
+        };
+
+        if let Some(state) = pool.take(&self.instance_key()) {
+            *state.metadata_context.lock() = self.metadata_context.clone();
+            *state.source_map.lock() = self.source_map.clone();
+            return Ok(state);
+        }
+
+        let mut state = self.setup_plugin_env_exports()?;
+        pool.record_created();
+        state.resettable = state.instance.snapshot()?;
+
+        Ok(state)
+    }
+
+    /// Returns the instance to the pool if it can be reset, or cleans it up.
+    fn release(&self, mut state: PluginTransformState) -> Result<(), Error> {
+        let Some(pool) = &self.instance_pool else {
+            return state.instance.cleanup();
+        };
+
+        if state.resettable && matches!(state.instance.reset(), Ok(true)) {
+            pool.put(self.instance_key(), state);
+        } else {
+            pool.record_discarded();
+            state.instance.cleanup()?;
+        }
+
+        Ok(())
+    }
+
+    /// Drops an instance whose transform failed, as it may have been left in
+    /// any state.
+    fn discard(&self, mut state: PluginTransformState) {
+        if let Some(pool) = &self.instance_pool {
+            pool.record_discarded();
+        }
+
+        // The error of the transform is more relevant.
+        let _ = state.instance.cleanup();
+    }
+
+    /// Names the plugin in errors caused by [runtime::LimitExceeded].
+    fn cancelled(&self, err: Error) -> Error {
+        if err.downcast_ref::<runtime::LimitExceeded>().is_some() {
+            let module_name = self.module_bytes.get_module_name();
+            err.context(format!("plugin `{module_name}` was cancelled"))
+        } else {
+            err
+        }
+    }
+
     #[tracing::instrument(level = "info", skip_all)]
     pub fn transform(
         &mut self,
         program: &PluginSerializedBytes,
         should_enable_comments_proxy: Option<bool>,
     ) -> Result<PluginSerializedBytes, Error> {
-        let mut transform_state = self.setup_plugin_env_exports()?;
-        transform_state.is_transform_schema_compatible()?;
-        transform_state
-            .run(program, self.unresolved_mark, should_enable_comments_proxy)
-            .with_context(|| {
+        let mut transform_state = self.acquire().map_err(|err| self.cancelled(err))?;
+        if let Err(err) = transform_state.is_transform_schema_compatible() {
+            self.discard(transform_state);
+            return Err(err);
+        }
+
+        let result = match transform_state.run(
+            program,
+            self.unresolved_mark,
+            should_enable_comments_proxy,
+        ) {
+            Ok(ret) => self.release(transform_state).map(|_| ret),
+            Err(err) => {
+                self.discard(transform_state);
+                Err(err)
+            }
+        };
+
+        match result {
+            // Hitting a limit is not a compatibility issue, so the hint below would be
+            // misleading.
+            Err(err) if err.downcast_ref::<runtime::LimitExceeded>().is_some() => {
+                Err(self.cancelled(err))
+            }
+            result => result.with_context(|| {
                 format!(
                     "failed to run Wasm plugin transform. Please ensure the version of `swc_core` \
                      used by the plugin is compatible with the host runtime. See the \
@@ -278,7 +394,8 @@ impl TransformExecutor {
                     Dependencies: {PKG_DEPS}
                 "
                 )
-            })
+            }),
+        }
     }
 }
 
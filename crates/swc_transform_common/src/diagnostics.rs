@@ -0,0 +1,70 @@
+//! Diagnostics reported by analysis plugins.
+//!
+//! Unlike [crate::output], diagnostics are typed, so the host can aggregate
+//! them across files and render them like its own errors.
+
+use std::cell::RefCell;
+
+use better_scoped_tls::scoped_tls;
+use swc_common::plugin::diagnostics::PluginAnalysisDiagnostic;
+
+scoped_tls!(static DIAGNOSTICS: RefCell<Vec<PluginAnalysisDiagnostic>>);
+
+/// Captures the diagnostics reported while running `f`.
+pub fn capture<Ret>(f: impl FnOnce() -> Ret) -> (Ret, Vec<PluginAnalysisDiagnostic>) {
+    let diagnostics = RefCell::new(Default::default());
+
+    let ret = DIAGNOSTICS.set(&diagnostics, f);
+
+    (ret, diagnostics.into_inner())
+}
+
+#[cfg(all(feature = "plugin-mode", target_arch = "wasm32"))]
+unsafe extern "C" {
+    fn __emit_analysis_diagnostic(bytes_ptr: u32, bytes_len: u32);
+}
+
+/// Reports a diagnostic to the host.
+#[cfg(all(feature = "plugin-mode", target_arch = "wasm32"))]
+pub fn report(diagnostic: PluginAnalysisDiagnostic) {
+    let diagnostic = swc_common::plugin::serialized::PluginSerializedBytes::try_serialize(
+        &swc_common::plugin::serialized::VersionedSerializable::new(diagnostic),
+    )
+    .expect("Should able to serialize PluginAnalysisDiagnostic");
+    let (ptr, len) = diagnostic.as_ptr();
+
+    unsafe {
+        __emit_analysis_diagnostic(ptr as u32, len as u32);
+    }
+}
+
+/// Reports a diagnostic to the caller.
+///
+/// If diagnostics are not being [capture]d, the diagnostic is emitted to
+/// [swc_common::errors::HANDLER] instead, and dropped if it's not set either.
+#[cfg(not(all(feature = "plugin-mode", target_arch = "wasm32")))]
+pub fn report(diagnostic: PluginAnalysisDiagnostic) {
+    use swc_common::errors::{DiagnosticBuilder, HANDLER};
+
+    // Native plugins report to their host, like Wasm plugins.
+    #[cfg(feature = "plugin-mode")]
+    if swc_common::plugin::native::has_host() {
+        let diagnostic = swc_common::plugin::serialized::PluginSerializedBytes::try_serialize(
+            &swc_common::plugin::serialized::VersionedSerializable::new(diagnostic),
+        )
+        .expect("Should able to serialize PluginAnalysisDiagnostic");
+        swc_common::plugin::native::send_to_host(
+            "__emit_analysis_diagnostic",
+            diagnostic.as_slice(),
+        );
+        return;
+    }
+
+    if DIAGNOSTICS.is_set() {
+        DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().push(diagnostic));
+    } else if HANDLER.is_set() {
+        HANDLER.with(|handler| {
+            DiagnosticBuilder::new_diagnostic(handler, diagnostic.to_diagnostic()).emit();
+        });
+    }
+}
@@ -48,6 +48,19 @@ pub fn experimental_emit(key: String, value: String) {
 /// This is not stable and may be removed in the future.
 #[cfg(not(all(feature = "plugin-mode", target_arch = "wasm32")))]
 pub fn experimental_emit(key: String, value: String) {
+    // Native plugins emit to their host, like Wasm plugins.
+    #[cfg(feature = "plugin-mode")]
+    if swc_common::plugin::native::has_host() {
+        let output = swc_common::plugin::emit::PluginEmitOutput { key, value };
+
+        let output = swc_common::plugin::serialized::PluginSerializedBytes::try_serialize(
+            &swc_common::plugin::serialized::VersionedSerializable::new(output),
+        )
+        .expect("Should able to serialize String");
+        swc_common::plugin::native::send_to_host("__emit_output", output.as_slice());
+        return;
+    }
+
     OUTPUT.with(|output| {
         let previous = output.borrow_mut().insert(key, value);
 
@@ -5,7 +5,7 @@ use bytes_str::BytesStr;
 use serde::{Deserialize, Serialize};
 use swc_common::{
     BytePos, FileName, Mark, SourceMap, Span, Spanned,
-    comments::SingleThreadedComments,
+    comments::{Comments, SingleThreadedComments},
     errors::{DiagnosticId, HANDLER, Handler},
     source_map::DefaultSourceMapGenConfig,
     sync::Lrc,
@@ -13,13 +13,13 @@ use swc_common::{
 use swc_ecma_ast::{
     ArrayPat, ArrowExpr, AutoAccessor, BindingIdent, Class, ClassDecl, ClassMethod, ClassProp,
     Constructor, Decl, DefaultDecl, DoWhileStmt, EsVersion, ExportAll, ExportDecl,
-    ExportDefaultDecl, ExportSpecifier, FnDecl, ForInStmt, ForOfStmt, ForStmt, GetterProp, IfStmt,
-    ImportDecl, ImportSpecifier, ModuleDecl, ModuleItem, NamedExport, ObjectPat, Param, Pat,
-    PrivateMethod, PrivateProp, Program, ReturnStmt, SetterProp, Stmt, ThrowStmt, TsAsExpr,
+    ExportDefaultDecl, ExportSpecifier, Expr, FnDecl, ForInStmt, ForOfStmt, ForStmt, GetterProp,
+    IfStmt, ImportDecl, ImportSpecifier, ModuleDecl, ModuleItem, NamedExport, ObjectPat, Param,
+    Pat, PrivateMethod, PrivateProp, Program, ReturnStmt, SetterProp, Stmt, ThrowStmt, TsAsExpr,
     TsConstAssertion, TsEnumDecl, TsExportAssignment, TsImportEqualsDecl, TsIndexSignature,
     TsInstantiation, TsModuleDecl, TsModuleName, TsNamespaceBody, TsNonNullExpr, TsParamPropParam,
     TsSatisfiesExpr, TsTypeAliasDecl, TsTypeAnn, TsTypeAssertion, TsTypeParamDecl,
-    TsTypeParamInstantiation, VarDeclarator, WhileStmt, YieldExpr,
+    TsTypeParamInstantiation, VarDecl, VarDeclarator, WhileStmt, YieldExpr,
 };
 use swc_ecma_parser::{
     Parser, StringInput, Syntax, TsSyntax,
@@ -33,7 +33,7 @@ use swc_ecma_transforms_base::{
     resolver,
 };
 use swc_ecma_transforms_typescript::typescript;
-use swc_ecma_visit::{Visit, VisitWith};
+use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
 #[cfg(feature = "wasm-bindgen")]
 use wasm_bindgen::prelude::*;
 
@@ -59,6 +59,13 @@ pub struct Options {
 
     #[serde(default)]
     pub source_map: bool,
+
+    /// A source map of the input, as a JSON string.
+    ///
+    /// If set, the generated source map is chained onto it so that mappings
+    /// point at the original sources instead of `input`.
+    #[serde(default)]
+    pub input_source_map: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -104,6 +111,14 @@ interface Options {
     transform?: TransformConfig;
     deprecatedTsModuleAsError?: boolean;
     sourceMap?: boolean;
+    /**
+     * A source map of the input, as a JSON string.
+     *
+     * If set, the generated source map is chained onto it.
+     * In `strip-only` mode, positions are preserved and this map is returned
+     * as is.
+     */
+    inputSourceMap?: string;
 }
 
 interface TransformConfig {
@@ -329,10 +344,18 @@ pub fn operate(
             let replacements = ts_strip.replacements;
             let overwrites = ts_strip.overwrites;
 
+            // Stripping preserves every position, including UTF-16 columns, so the input
+            // source map is still valid for the output.
+            let map = if options.source_map {
+                options.input_source_map
+            } else {
+                None
+            };
+
             if replacements.is_empty() && overwrites.is_empty() {
                 return Ok(TransformOutput {
                     code: fm.src.to_string(),
-                    map: Default::default(),
+                    map,
                 });
             }
 
@@ -401,16 +424,32 @@ pub fn operate(
                 unsafe { String::from_utf8_unchecked(code) }
             };
 
-            Ok(TransformOutput {
-                code,
-                map: Default::default(),
-            })
+            Ok(TransformOutput { code, map })
         }
 
         Mode::Transform => {
             let unresolved_mark = Mark::new();
             let top_level_mark = Mark::new();
 
+            let orig = options
+                .input_source_map
+                .as_deref()
+                .filter(|_| options.source_map)
+                .map(|map| {
+                    swc_sourcemap::SourceMap::from_slice(map.as_bytes())
+                        .context("failed to parse input source map")
+                })
+                .transpose()?;
+
+            let lowered_spans = if options.source_map {
+                let mut collector = LoweredDeclCollector::default();
+                program.visit_with(&mut collector);
+                collector.spans.sort();
+                collector.spans
+            } else {
+                Vec::new()
+            };
+
             HELPERS.set(&Helpers::new(false), || {
                 program.mutate(&mut resolver(unresolved_mark, top_level_mark, true));
 
@@ -471,6 +510,16 @@ pub fn operate(
                 Ok(())
             })?;
 
+            if options.source_map {
+                program.visit_mut_with(&mut LoweredSpanFiller {
+                    src: &fm.src,
+                    start_pos: fm.start_pos,
+                    comments: &comments,
+                    lowered_spans,
+                    fill: None,
+                });
+            }
+
             let mut src = std::vec::Vec::with_capacity(source_len);
             let mut src_map_buf = if options.source_map {
                 Some(Vec::new())
@@ -499,7 +548,7 @@ pub fn operate(
 
                 let map = src_map_buf
                     .map(|map| {
-                        let map = cm.build_source_map(&map, None, DefaultSourceMapGenConfig);
+                        let map = cm.build_source_map(&map, orig, DefaultSourceMapGenConfig);
 
                         let mut s = std::vec::Vec::new();
                         map.to_writer(&mut s)
@@ -518,6 +567,93 @@ pub fn operate(
     }
 }
 
+/// Collects the spans of enums and namespaces, which are lowered to
+/// JavaScript in transform mode.
+#[derive(Default)]
+struct LoweredDeclCollector {
+    spans: Vec<Span>,
+}
+
+impl Visit for LoweredDeclCollector {
+    fn visit_ts_enum_decl(&mut self, n: &TsEnumDecl) {
+        if !n.declare {
+            self.spans.push(n.span);
+        }
+    }
+
+    fn visit_ts_module_decl(&mut self, n: &TsModuleDecl) {
+        if !n.declare && !n.global {
+            self.spans.push(n.span);
+        }
+
+        n.visit_children_with(self);
+    }
+}
+
+/// Enum and namespace lowering creates nodes without spans, which would leave
+/// most of the generated tokens unmapped.
+///
+/// This assigns the position of the nearest enclosing node with a span to
+/// those nodes, but only inside the lowered enums and namespaces.
+struct LoweredSpanFiller<'a> {
+    src: &'a str,
+    start_pos: BytePos,
+    comments: &'a SingleThreadedComments,
+    lowered_spans: Vec<Span>,
+    fill: Option<Span>,
+}
+
+impl LoweredSpanFiller<'_> {
+    fn with_span(&mut self, span: Span, op: impl FnOnce(&mut Self)) {
+        let prev = self.fill;
+
+        if self.lowered_spans.binary_search(&span).is_ok() || (prev.is_some() && !span.is_dummy()) {
+            self.fill = Some(self.fill_span(span.lo));
+        }
+
+        op(self);
+
+        self.fill = prev;
+    }
+
+    /// The codegen maps closing tokens to `hi - 1`, so the span covers the
+    /// first byte of the enclosing node. It's empty if that would split a
+    /// character or pull comments after the first byte into the lowered code.
+    fn fill_span(&self, lo: BytePos) -> Span {
+        let hi = lo + BytePos(1);
+        let idx = (hi - self.start_pos).0 as usize;
+
+        if self.src.is_char_boundary(idx) && !self.comments.has_trailing(hi) {
+            Span::new(lo, hi)
+        } else {
+            Span::new(lo, lo)
+        }
+    }
+}
+
+impl VisitMut for LoweredSpanFiller<'_> {
+    fn visit_mut_expr(&mut self, n: &mut Expr) {
+        self.with_span(n.span(), |v| n.visit_mut_children_with(v));
+    }
+
+    fn visit_mut_span(&mut self, n: &mut Span) {
+        if let Some(fill) = self.fill {
+            // Spans reserved for comments, like `PURE_SP`, are kept.
+            if n.is_dummy_ignoring_cmt() {
+                *n = fill;
+            }
+        }
+    }
+
+    fn visit_mut_stmt(&mut self, n: &mut Stmt) {
+        self.with_span(n.span(), |v| n.visit_mut_children_with(v));
+    }
+
+    fn visit_mut_var_decl(&mut self, n: &mut VarDecl) {
+        self.with_span(n.span, |v| n.visit_mut_children_with(v));
+    }
+}
+
 struct ErrorOnTsModule<'a> {
     src: &'a str,
     tokens: &'a [TokenAndSpan],
@@ -0,0 +1,145 @@
+use swc_sourcemap::{SourceMap, SourceMapBuilder};
+use swc_ts_fast_strip::{Mode, Options, operate};
+
+fn run(input: &str, mode: Mode, input_source_map: Option<String>) -> (String, Option<String>) {
+    testing::run_test(false, |cm, handler| {
+        let output = operate(
+            &cm,
+            handler,
+            input.to_string(),
+            Options {
+                filename: Some("input.ts".into()),
+                mode,
+                source_map: true,
+                input_source_map,
+                ..Default::default()
+            },
+        )
+        .expect("should not return Err()");
+
+        Ok((output.code, output.map))
+    })
+    .unwrap()
+}
+
+fn transform(input: &str, input_source_map: Option<String>) -> (String, SourceMap) {
+    let (code, map) = run(input, Mode::Transform, input_source_map);
+    let map = SourceMap::from_slice(map.expect("should have a source map").as_bytes()).unwrap();
+
+    (code, map)
+}
+
+/// Returns the source lines of the tokens on each generated line.
+fn src_lines(code: &str, map: &SourceMap) -> Vec<(String, Vec<u32>)> {
+    code.lines()
+        .enumerate()
+        .map(|(line, text)| {
+            let lines = map
+                .tokens()
+                .filter(|token| token.get_dst_line() == line as u32)
+                .map(|token| token.get_src_line())
+                .collect();
+
+            (text.trim().to_string(), lines)
+        })
+        .collect()
+}
+
+#[test]
+fn enum_tokens_are_mapped() {
+    let (code, map) = transform(
+        "let x = 1;\nenum E {\n    A,\n    B = \"b\",\n    C = x,\n}\n",
+        None,
+    );
+
+    for (text, lines) in src_lines(&code, &map).into_iter().skip(1) {
+        let expected = match &*text {
+            "E[E[\"A\"] = 0] = \"A\";" => vec![2],
+            "E[\"B\"] = \"b\";" => vec![3],
+            "E[E[\"C\"] = x] = \"C\";" => vec![4],
+            _ => vec![1],
+        };
+
+        assert!(!lines.is_empty(), "`{text}` is not mapped");
+        assert!(
+            lines.iter().all(|line| expected.contains(line)),
+            "`{text}` is mapped to {lines:?}"
+        );
+    }
+}
+
+#[test]
+fn namespace_tokens_are_mapped() {
+    let (code, map) = transform(
+        "namespace N {\n    export const a = 1;\n    export function f() {}\n}\n",
+        None,
+    );
+
+    for (text, lines) in src_lines(&code, &map) {
+        let expected = match &*text {
+            "N.a = 1;" => vec![1],
+            "function f() {}" | "N.f = f;" => vec![2],
+            "var N;" => continue,
+            "})(N || (N = {}));" => vec![0, 3],
+            _ => vec![0],
+        };
+
+        assert!(!lines.is_empty(), "`{text}` is not mapped");
+        assert!(
+            lines.iter().all(|line| expected.contains(line)),
+            "`{text}` is mapped to {lines:?}"
+        );
+    }
+}
+
+#[test]
+fn input_source_map_is_chained() {
+    let input = "enum E {\n    A,\n}\n";
+
+    // Maps the first column of each line of the input to 10 lines below in
+    // `original.ts`.
+    let mut builder = SourceMapBuilder::new(Some("input.ts".into()));
+    for line in 0..input.lines().count() as u32 {
+        for col in 0..16 {
+            builder.add(
+                line,
+                col,
+                line + 10,
+                col,
+                Some("original.ts".into()),
+                None,
+                false,
+            );
+        }
+    }
+    let mut input_map = Vec::new();
+    builder.into_sourcemap().to_writer(&mut input_map).unwrap();
+
+    let (code, map) = transform(input, Some(String::from_utf8(input_map).unwrap()));
+
+    assert_eq!(map.get_source(0).map(|s| &**s), Some("original.ts"));
+    assert_eq!(map.get_source_count(), 1);
+
+    for (text, lines) in src_lines(&code, &map) {
+        let expected = if text.starts_with("E[") { 11 } else { 10 };
+
+        assert!(
+            lines.iter().all(|line| *line == expected),
+            "`{text}` is mapped to {lines:?}"
+        );
+    }
+}
+
+#[test]
+fn strip_only_returns_input_source_map() {
+    let input_map = r#"{"version":3,"sources":["original.ts"],"names":[],"mappings":"AAAA"}"#;
+
+    let (code, map) = run(
+        "let x: number = 1;\n",
+        Mode::StripOnly,
+        Some(input_map.to_string()),
+    );
+
+    assert_eq!(code, "let x         = 1;\n");
+    assert_eq!(map.as_deref(), Some(input_map));
+}
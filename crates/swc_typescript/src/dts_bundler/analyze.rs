@@ -0,0 +1,606 @@
+use std::sync::Arc;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_atoms::{Atom, Wtf8Atom, atom};
+use swc_common::{BytePos, FileName, Span, Spanned, SyntaxContext};
+use swc_ecma_ast::*;
+use swc_ecma_utils::find_pat_ids;
+use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+
+pub(super) type ModuleId = usize;
+
+/// The module a declaration is imported from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum Source {
+    Internal(ModuleId),
+    External(Wtf8Atom),
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Import {
+    /// `import { name } from 'src'` or `import name from 'src'`, with `default`
+    /// as the name.
+    Named(Source, Atom, Span),
+    /// `import * as ns from 'src'`
+    Namespace(Source),
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Export {
+    /// `export { local as name }`
+    Local(Id, Span),
+    /// `export { orig as name } from 'src'`
+    Reexport(Source, Atom, Span),
+    /// `export * as name from 'src'`
+    Namespace(Source),
+}
+
+/// A top-level statement of a declaration file, without the `export` keyword.
+#[derive(Debug)]
+pub(super) struct Item {
+    pub node: ModuleItem,
+    /// Top-level bindings declared by this item.
+    pub ids: Vec<Id>,
+    /// Top-level bindings and imports referenced by this item.
+    pub refs: Vec<Id>,
+    /// Annotated with `@internal`.
+    pub internal: bool,
+    /// `declare global` or `declare module 'name'`, which are kept regardless
+    /// of the exports.
+    pub augmentation: bool,
+}
+
+#[derive(Debug)]
+pub(super) struct ModuleInfo {
+    pub file: Arc<FileName>,
+    pub items: Vec<Item>,
+    /// Items declaring each top-level binding. There can be multiple items
+    /// because of overloads and declaration merging.
+    pub decls: FxHashMap<Id, Vec<usize>>,
+    pub imports: FxHashMap<Id, Import>,
+    /// Explicit exports, in the order of the source.
+    pub exports: Vec<(Atom, Export)>,
+    /// Sources of `export * from 'src'`.
+    pub stars: Vec<Source>,
+    /// Sources of `import 'src'`.
+    pub side_effects: Vec<Source>,
+    /// Internal modules this module depends on, in the order of the source.
+    pub deps: Vec<ModuleId>,
+    /// `export = local`
+    pub export_assign: Option<(Id, Span)>,
+    /// `export as namespace Name`
+    pub namespace_export: Option<TsNamespaceExportDecl>,
+    /// Names of the global bindings referenced by the module, which must not
+    /// be shadowed by the bundle.
+    pub globals: FxHashSet<Atom>,
+}
+
+/// Resolves the module specifiers of a declaration file.
+pub(super) trait SourceResolver {
+    fn resolve(&mut self, base: &Arc<FileName>, src: &Str) -> Source;
+
+    fn issue(&mut self, file: &Arc<FileName>, span: Span, message: String);
+}
+
+pub(super) fn analyze(
+    file: Arc<FileName>,
+    module: Module,
+    internal_annotations: Option<&FxHashSet<BytePos>>,
+    resolver: &mut dyn SourceResolver,
+) -> ModuleInfo {
+    let mut info = ModuleInfo {
+        file,
+        items: Vec::new(),
+        decls: Default::default(),
+        imports: Default::default(),
+        exports: Vec::new(),
+        stars: Vec::new(),
+        side_effects: Vec::new(),
+        deps: Vec::new(),
+        export_assign: None,
+        namespace_export: None,
+        globals: Default::default(),
+    };
+
+    let is_internal =
+        |pos: BytePos| internal_annotations.is_some_and(|annotations| annotations.contains(&pos));
+
+    for item in module.body {
+        let span_lo = item.span_lo();
+
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                let source = resolve(&mut info, resolver, &import.src);
+
+                if import.specifiers.is_empty() {
+                    info.side_effects.push(source);
+                    continue;
+                }
+
+                for specifier in import.specifiers {
+                    let (local, import) = match specifier {
+                        ImportSpecifier::Named(named) => {
+                            let imported = match &named.imported {
+                                Some(imported) => imported.atom().into_owned(),
+                                None => named.local.sym.clone(),
+                            };
+
+                            (
+                                named.local,
+                                Import::Named(source.clone(), imported, named.span),
+                            )
+                        }
+                        ImportSpecifier::Default(default) => (
+                            default.local,
+                            Import::Named(source.clone(), atom!("default"), default.span),
+                        ),
+                        ImportSpecifier::Namespace(namespace) => {
+                            (namespace.local, Import::Namespace(source.clone()))
+                        }
+                        #[cfg(swc_ast_unknown)]
+                        _ => panic!("unable to access unknown nodes"),
+                    };
+
+                    info.imports.insert(local.to_id(), import);
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                let ids = decl_ids(&export.decl);
+
+                for id in &ids {
+                    info.exports
+                        .push((id.0.clone(), Export::Local(id.clone(), export.span)));
+                }
+
+                info.add_item(
+                    ModuleItem::Stmt(Stmt::Decl(into_declare(export.decl))),
+                    ids,
+                    is_internal(span_lo),
+                );
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => {
+                let source = export
+                    .src
+                    .as_deref()
+                    .map(|src| resolve(&mut info, resolver, src));
+
+                for specifier in export.specifiers {
+                    match (specifier, &source) {
+                        (ExportSpecifier::Named(named), Some(source)) => {
+                            let orig = named.orig.atom().into_owned();
+                            let exported = match &named.exported {
+                                Some(exported) => exported.atom().into_owned(),
+                                None => orig.clone(),
+                            };
+
+                            info.exports.push((
+                                exported,
+                                Export::Reexport(source.clone(), orig, named.span),
+                            ));
+                        }
+                        (ExportSpecifier::Named(named), None) => {
+                            let ModuleExportName::Ident(orig) = &named.orig else {
+                                continue;
+                            };
+                            let exported = match &named.exported {
+                                Some(exported) => exported.atom().into_owned(),
+                                None => orig.sym.clone(),
+                            };
+
+                            info.exports
+                                .push((exported, Export::Local(orig.to_id(), named.span)));
+                        }
+                        (ExportSpecifier::Default(default), Some(source)) => {
+                            info.exports.push((
+                                default.exported.sym.clone(),
+                                Export::Reexport(
+                                    source.clone(),
+                                    atom!("default"),
+                                    default.exported.span,
+                                ),
+                            ));
+                        }
+                        (ExportSpecifier::Namespace(namespace), Some(source)) => {
+                            info.exports.push((
+                                namespace.name.atom().into_owned(),
+                                Export::Namespace(source.clone()),
+                            ));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                let decl = match export.decl {
+                    DefaultDecl::Class(ClassExpr { ident, class }) => Decl::Class(ClassDecl {
+                        ident: ident.unwrap_or_else(default_ident),
+                        declare: true,
+                        class,
+                    }),
+                    DefaultDecl::Fn(FnExpr { ident, function }) => Decl::Fn(FnDecl {
+                        ident: ident.unwrap_or_else(default_ident),
+                        declare: true,
+                        function,
+                    }),
+                    DefaultDecl::TsInterfaceDecl(decl) => Decl::TsInterface(decl),
+                    #[cfg(swc_ast_unknown)]
+                    _ => panic!("unable to access unknown nodes"),
+                };
+                let ids = decl_ids(&decl);
+
+                for id in &ids {
+                    info.exports
+                        .push((atom!("default"), Export::Local(id.clone(), export.span)));
+                }
+
+                info.add_item(
+                    ModuleItem::Stmt(Stmt::Decl(decl)),
+                    ids,
+                    is_internal(span_lo),
+                );
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => match &*export.expr {
+                Expr::Ident(ident) => {
+                    info.exports
+                        .push((atom!("default"), Export::Local(ident.to_id(), export.span)));
+                }
+                _ => resolver.issue(
+                    &info.file,
+                    export.span,
+                    "Default exports of bundled declarations must be identifiers.".into(),
+                ),
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => {
+                let source = resolve(&mut info, resolver, &export.src);
+                info.stars.push(source);
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(mut import)) => {
+                if let TsModuleRef::TsExternalModuleRef(module_ref) = &import.module_ref {
+                    if let Source::Internal(..) = resolve(&mut info, resolver, &module_ref.expr) {
+                        resolver.issue(
+                            &info.file,
+                            import.span,
+                            "`import = require()` of bundled modules is not supported.".into(),
+                        );
+                        continue;
+                    }
+                }
+
+                let id = import.id.to_id();
+                if import.is_export {
+                    import.is_export = false;
+                    info.exports
+                        .push((id.0.clone(), Export::Local(id.clone(), import.span)));
+                }
+
+                info.add_item(
+                    ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)),
+                    vec![id],
+                    is_internal(span_lo),
+                );
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(export)) => match &*export.expr {
+                Expr::Ident(ident) => info.export_assign = Some((ident.to_id(), export.span)),
+                _ => resolver.issue(
+                    &info.file,
+                    export.span,
+                    "`export =` of bundled declarations must export an identifier.".into(),
+                ),
+            },
+            ModuleItem::ModuleDecl(ModuleDecl::TsNamespaceExport(export)) => {
+                info.namespace_export = Some(export);
+            }
+            ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                let ids = decl_ids(&decl);
+                let augmentation = matches!(
+                    &decl,
+                    Decl::TsModule(module) if module.global || module.id.is_str()
+                );
+
+                let internal = is_internal(span_lo);
+                let index = info.add_item(ModuleItem::Stmt(Stmt::Decl(decl)), ids, internal);
+                info.items[index].augmentation = augmentation;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(annotations) = internal_annotations {
+        for item in &mut info.items {
+            item.node.visit_mut_with(&mut StripInternal(annotations));
+        }
+    }
+
+    let bindings = info
+        .decls
+        .keys()
+        .chain(info.imports.keys())
+        .cloned()
+        .collect::<FxHashSet<_>>();
+
+    for index in 0..info.items.len() {
+        let mut collector = RefCollector::default();
+        info.items[index].node.visit_with(&mut collector);
+
+        for id in collector.used {
+            if bindings.contains(&id) {
+                info.items[index].refs.push(id);
+            } else if !collector.declared.contains(&id) {
+                info.globals.insert(id.0);
+            }
+        }
+
+        for arg in collector.import_types {
+            if let Source::Internal(..) = resolve(&mut info, resolver, &arg) {
+                resolver.issue(
+                    &info.file,
+                    arg.span,
+                    "Import types of bundled modules are not supported.".into(),
+                );
+            }
+        }
+    }
+
+    info
+}
+
+fn resolve(info: &mut ModuleInfo, resolver: &mut dyn SourceResolver, src: &Str) -> Source {
+    let source = resolver.resolve(&info.file, src);
+    if let Source::Internal(dep) = source {
+        if !info.deps.contains(&dep) {
+            info.deps.push(dep);
+        }
+    }
+    source
+}
+
+impl ModuleInfo {
+    fn add_item(&mut self, node: ModuleItem, ids: Vec<Id>, internal: bool) -> usize {
+        let index = self.items.len();
+
+        for id in &ids {
+            self.decls.entry(id.clone()).or_default().push(index);
+        }
+
+        self.items.push(Item {
+            node,
+            ids,
+            refs: Vec::new(),
+            internal,
+            augmentation: false,
+        });
+
+        index
+    }
+}
+
+/// The name of an anonymous default export.
+fn default_ident() -> Ident {
+    Ident::new(
+        atom!("_default"),
+        Default::default(),
+        SyntaxContext::empty(),
+    )
+}
+
+fn decl_ids(decl: &Decl) -> Vec<Id> {
+    match decl {
+        Decl::Class(c) => vec![c.ident.to_id()],
+        Decl::Fn(f) => vec![f.ident.to_id()],
+        Decl::Var(v) => find_pat_ids(&v.decls),
+        Decl::TsInterface(i) => vec![i.id.to_id()],
+        Decl::TsTypeAlias(t) => vec![t.id.to_id()],
+        Decl::TsEnum(e) => vec![e.id.to_id()],
+        Decl::TsModule(m) => match &m.id {
+            TsModuleName::Ident(id) if !m.global => vec![id.to_id()],
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// Top-level declarations without the `export` keyword should be ambient.
+fn into_declare(mut decl: Decl) -> Decl {
+    match &mut decl {
+        Decl::Class(c) => c.declare = true,
+        Decl::Fn(f) => f.declare = true,
+        Decl::Var(v) => v.declare = true,
+        Decl::TsEnum(e) => e.declare = true,
+        Decl::TsModule(m) => m.declare = true,
+        _ => {}
+    }
+
+    decl
+}
+
+/// Removes members annotated with `@internal`.
+struct StripInternal<'a>(&'a FxHashSet<BytePos>);
+
+impl VisitMut for StripInternal<'_> {
+    fn visit_mut_class_members(&mut self, members: &mut Vec<ClassMember>) {
+        members.retain(|member| !self.0.contains(&member.span_lo()));
+        members.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        items.retain(|item| !self.0.contains(&item.span_lo()));
+        items.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_ts_type_elements(&mut self, elements: &mut Vec<TsTypeElement>) {
+        elements.retain(|element| !self.0.contains(&element.span_lo()));
+        elements.visit_mut_children_with(self);
+    }
+}
+
+/// Collects identifiers referenced by a declaration, and bindings declared in
+/// it.
+#[derive(Default)]
+struct RefCollector {
+    used: Vec<Id>,
+    seen: FxHashSet<Id>,
+    declared: FxHashSet<Id>,
+    import_types: Vec<Str>,
+}
+
+impl Visit for RefCollector {
+    fn visit_binding_ident(&mut self, n: &BindingIdent) {
+        self.declared.insert(n.id.to_id());
+        n.type_ann.visit_with(self);
+    }
+
+    fn visit_class_decl(&mut self, n: &ClassDecl) {
+        self.declared.insert(n.ident.to_id());
+        n.class.visit_with(self);
+    }
+
+    fn visit_export_named_specifier(&mut self, n: &ExportNamedSpecifier) {
+        n.orig.visit_with(self);
+    }
+
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        self.declared.insert(n.ident.to_id());
+        n.function.visit_with(self);
+    }
+
+    fn visit_ident(&mut self, n: &Ident) {
+        let id = n.to_id();
+        if self.seen.insert(id.clone()) {
+            self.used.push(id);
+        }
+    }
+
+    fn visit_import_specifier(&mut self, n: &ImportSpecifier) {
+        self.declared.insert(n.local().to_id());
+    }
+
+    fn visit_ts_enum_decl(&mut self, n: &TsEnumDecl) {
+        self.declared.insert(n.id.to_id());
+        n.members.visit_with(self);
+    }
+
+    fn visit_ts_enum_member(&mut self, n: &TsEnumMember) {
+        n.init.visit_with(self);
+    }
+
+    fn visit_ts_getter_signature(&mut self, n: &TsGetterSignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_import_equals_decl(&mut self, n: &TsImportEqualsDecl) {
+        self.declared.insert(n.id.to_id());
+        n.module_ref.visit_with(self);
+    }
+
+    fn visit_ts_import_type(&mut self, n: &TsImportType) {
+        // The qualifier belongs to the imported module.
+        self.import_types.push(n.arg.clone());
+        n.type_args.visit_with(self);
+    }
+
+    fn visit_ts_interface_decl(&mut self, n: &TsInterfaceDecl) {
+        self.declared.insert(n.id.to_id());
+        n.type_params.visit_with(self);
+        n.extends.visit_with(self);
+        n.body.visit_with(self);
+    }
+
+    fn visit_ts_method_signature(&mut self, n: &TsMethodSignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.type_params.visit_with(self);
+        n.params.visit_with(self);
+        n.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_module_decl(&mut self, n: &TsModuleDecl) {
+        if let TsModuleName::Ident(id) = &n.id {
+            self.declared.insert(id.to_id());
+        }
+        n.body.visit_with(self);
+    }
+
+    fn visit_ts_namespace_decl(&mut self, n: &TsNamespaceDecl) {
+        self.declared.insert(n.id.to_id());
+        n.body.visit_with(self);
+    }
+
+    fn visit_ts_property_signature(&mut self, n: &TsPropertySignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_setter_signature(&mut self, n: &TsSetterSignature) {
+        if n.computed {
+            n.key.visit_with(self);
+        }
+        n.param.visit_with(self);
+    }
+
+    fn visit_ts_type_alias_decl(&mut self, n: &TsTypeAliasDecl) {
+        self.declared.insert(n.id.to_id());
+        n.type_params.visit_with(self);
+        n.type_ann.visit_with(self);
+    }
+
+    fn visit_ts_type_param(&mut self, n: &TsTypeParam) {
+        self.declared.insert(n.name.to_id());
+        n.constraint.visit_with(self);
+        n.default.visit_with(self);
+    }
+}
+
+/// Renames the top-level bindings of a module to the names in the bundle.
+pub(super) struct Renamer<'a>(pub &'a FxHashMap<Id, Atom>);
+
+impl VisitMut for Renamer<'_> {
+    fn visit_mut_ident(&mut self, n: &mut Ident) {
+        if let Some(name) = self.0.get(&n.to_id()) {
+            n.sym = name.clone();
+        }
+    }
+
+    fn visit_mut_ts_enum_member(&mut self, n: &mut TsEnumMember) {
+        n.init.visit_mut_with(self);
+    }
+
+    fn visit_mut_ts_getter_signature(&mut self, n: &mut TsGetterSignature) {
+        if n.computed {
+            n.key.visit_mut_with(self);
+        }
+        n.type_ann.visit_mut_with(self);
+    }
+
+    fn visit_mut_ts_import_type(&mut self, n: &mut TsImportType) {
+        n.type_args.visit_mut_with(self);
+    }
+
+    fn visit_mut_ts_method_signature(&mut self, n: &mut TsMethodSignature) {
+        if n.computed {
+            n.key.visit_mut_with(self);
+        }
+        n.type_params.visit_mut_with(self);
+        n.params.visit_mut_with(self);
+        n.type_ann.visit_mut_with(self);
+    }
+
+    fn visit_mut_ts_property_signature(&mut self, n: &mut TsPropertySignature) {
+        if n.computed {
+            n.key.visit_mut_with(self);
+        }
+        n.type_ann.visit_mut_with(self);
+    }
+
+    fn visit_mut_ts_setter_signature(&mut self, n: &mut TsSetterSignature) {
+        if n.computed {
+            n.key.visit_mut_with(self);
+        }
+        n.param.visit_mut_with(self);
+    }
+}
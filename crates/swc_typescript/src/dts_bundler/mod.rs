@@ -0,0 +1,855 @@
+//! Bundling of declaration files.
+//!
+//! [DtsBundler] merges the outputs of [FastDts](crate::fast_dts::FastDts) into
+//! a single declaration file, like `api-extractor` or `rollup-plugin-dts`.
+//! Internal modules are inlined, colliding names are renamed and only the
+//! exports of the entry are exported by the bundle.
+
+use std::{collections::VecDeque, sync::Arc};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_atoms::{Atom, Wtf8Atom};
+use swc_common::{BytePos, DUMMY_SP, FileName, Span, util::take::Take};
+use swc_ecma_ast::*;
+use swc_ecma_visit::VisitMutWith;
+
+use self::analyze::{
+    Export, Import, ModuleId, ModuleInfo, Renamer, Source, SourceResolver, analyze,
+};
+use crate::diagnostic::{DtsIssue, SourceRange};
+
+mod analyze;
+
+/// Resolves module specifiers of declaration files.
+pub trait Resolve {
+    /// Resolves `specifier` imported by `base`.
+    ///
+    /// Returns [None] if the module is external, so it's imported by the
+    /// bundle as is.
+    fn resolve(&self, base: &FileName, specifier: &str) -> Option<Arc<FileName>>;
+}
+
+impl<F> Resolve for F
+where
+    F: Fn(&FileName, &str) -> Option<Arc<FileName>>,
+{
+    fn resolve(&self, base: &FileName, specifier: &str) -> Option<Arc<FileName>> {
+        self(base, specifier)
+    }
+}
+
+/// Bundles declaration files into one.
+///
+/// The modules should be resolved by `resolver` before they are passed to
+/// [FastDts](crate::fast_dts::FastDts), because top-level bindings are
+/// identified by their syntax context.
+///
+/// Declarations annotated with `@internal` are not exported by the bundle,
+/// and they are kept only if a public declaration references them. Members
+/// annotated with `@internal` are removed.
+pub struct DtsBundler<R> {
+    resolver: R,
+    files: FxHashMap<Arc<FileName>, DtsFile>,
+}
+
+struct DtsFile {
+    module: Module,
+    internal_annotations: Option<FxHashSet<BytePos>>,
+}
+
+impl<R> DtsBundler<R>
+where
+    R: Resolve,
+{
+    pub fn new(resolver: R) -> Self {
+        Self {
+            resolver,
+            files: Default::default(),
+        }
+    }
+
+    /// Adds the output of [FastDts](crate::fast_dts::FastDts) for `filename`.
+    ///
+    /// `internal_annotations` can be created by
+    /// [FastDts::get_internal_annotations](crate::fast_dts::FastDts::get_internal_annotations).
+    pub fn add_file(
+        &mut self,
+        filename: Arc<FileName>,
+        module: Module,
+        internal_annotations: Option<FxHashSet<BytePos>>,
+    ) {
+        self.files.insert(
+            filename,
+            DtsFile {
+                module,
+                internal_annotations,
+            },
+        );
+    }
+
+    /// Bundles the modules imported by `entry`, and exports the exports of
+    /// `entry`.
+    pub fn bundle(self, entry: Arc<FileName>) -> (Module, Vec<DtsIssue>) {
+        let mut loader = Loader {
+            resolver: &self.resolver,
+            files: self.files,
+            ids: Default::default(),
+            queue: Default::default(),
+            issues: Vec::new(),
+        };
+
+        if !loader.files.contains_key(&entry) {
+            loader.issue(
+                &entry,
+                DUMMY_SP,
+                format!("Cannot find the declaration file of `{entry}`."),
+            );
+            return (Module::dummy(), loader.issues);
+        }
+
+        loader.ids.insert(entry.clone(), 0);
+        loader.queue.push_back(entry);
+
+        let mut modules = Vec::new();
+        while let Some(file) = loader.queue.pop_front() {
+            let DtsFile {
+                module,
+                internal_annotations,
+            } = loader.files.remove(&file).unwrap();
+
+            modules.push(analyze(
+                file,
+                module,
+                internal_annotations.as_ref(),
+                &mut loader,
+            ));
+        }
+
+        let mut linker = Linker {
+            included: vec![Default::default(); modules.len()],
+            modules,
+            issues: loader.issues,
+            resolved: Default::default(),
+            queue: Vec::new(),
+            seen: Default::default(),
+            hints: Default::default(),
+            namespaces: Vec::new(),
+            externals: Vec::new(),
+        };
+        let module = linker.link();
+
+        (module, linker.issues)
+    }
+}
+
+/// Loads the modules imported by the entry.
+struct Loader<'a, R> {
+    resolver: &'a R,
+    files: FxHashMap<Arc<FileName>, DtsFile>,
+    ids: FxHashMap<Arc<FileName>, ModuleId>,
+    queue: VecDeque<Arc<FileName>>,
+    issues: Vec<DtsIssue>,
+}
+
+impl<R> SourceResolver for Loader<'_, R>
+where
+    R: Resolve,
+{
+    fn resolve(&mut self, base: &Arc<FileName>, src: &Str) -> Source {
+        let specifier = src.value.to_string_lossy();
+
+        let Some(file) = self.resolver.resolve(base, &specifier) else {
+            return Source::External(src.value.clone());
+        };
+
+        if let Some(&id) = self.ids.get(&file) {
+            return Source::Internal(id);
+        }
+
+        if !self.files.contains_key(&file) {
+            self.issue(
+                base,
+                src.span,
+                format!("Cannot find the declaration file of `{specifier}`."),
+            );
+            return Source::External(src.value.clone());
+        }
+
+        let id = self.ids.len();
+        self.ids.insert(file.clone(), id);
+        self.queue.push_back(file);
+
+        Source::Internal(id)
+    }
+
+    fn issue(&mut self, file: &Arc<FileName>, span: Span, message: String) {
+        self.issues.push(DtsIssue {
+            range: SourceRange {
+                filename: file.clone(),
+                span,
+            },
+            message: message.into(),
+        });
+    }
+}
+
+/// A binding of the bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Target {
+    /// A top-level binding of a module.
+    Decl(ModuleId, Id),
+    /// The namespace object of a module, created by `import * as ns`.
+    Namespace(ModuleId),
+    External(Wtf8Atom, ExternalName),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ExternalName {
+    Named(Atom),
+    Namespace,
+}
+
+struct Linker {
+    modules: Vec<ModuleInfo>,
+    issues: Vec<DtsIssue>,
+
+    /// Targets of the references in each module.
+    resolved: FxHashMap<(ModuleId, Id), Option<Target>>,
+    /// Indices of the items included in the bundle, for each module.
+    included: Vec<FxHashSet<usize>>,
+    queue: Vec<Target>,
+    seen: FxHashSet<Target>,
+    /// Preferred names of the bindings.
+    hints: FxHashMap<Target, Atom>,
+    namespaces: Vec<ModuleId>,
+    externals: Vec<(Wtf8Atom, ExternalName)>,
+}
+
+impl Linker {
+    fn issue(&mut self, module: ModuleId, span: Span, message: String) {
+        self.issues.push(DtsIssue {
+            range: SourceRange {
+                filename: self.modules[module].file.clone(),
+                span,
+            },
+            message: message.into(),
+        });
+    }
+
+    fn link(&mut self) -> Module {
+        let entry = 0;
+
+        let (exports, external_stars) = self.public_exports(entry);
+
+        let export_assign = self.modules[entry]
+            .export_assign
+            .clone()
+            .and_then(|(id, span)| {
+                if !exports.is_empty() || !external_stars.is_empty() {
+                    self.issue(
+                        entry,
+                        span,
+                        "An export assignment cannot be used in a module with other exported \
+                         elements."
+                            .into(),
+                    );
+                }
+
+                Some((self.resolve_ref(entry, &id)?, id.0))
+            });
+
+        for (name, target) in &exports {
+            self.include(target.clone(), name);
+        }
+        if let Some((target, name)) = &export_assign {
+            self.include(target.clone(), name);
+        }
+
+        // Augmentations are applied by importing the module.
+        let mut side_effects = Vec::new();
+        for module in 0..self.modules.len() {
+            for index in 0..self.modules[module].items.len() {
+                if self.modules[module].items[index].augmentation {
+                    self.include_item(module, index);
+                }
+            }
+
+            for source in &self.modules[module].side_effects {
+                if let Source::External(src) = source {
+                    if !side_effects.contains(src) {
+                        side_effects.push(src.clone());
+                    }
+                }
+            }
+        }
+
+        self.drain_queue();
+
+        let order = self.output_order(entry);
+        let names = self.assign_names(entry, &order);
+
+        let mut body = self.emit_imports(&names, side_effects);
+
+        for &module in &order {
+            body.extend(self.emit_items(module, &names));
+
+            if self.namespaces.contains(&module) {
+                body.push(self.emit_namespace(module, &names));
+            }
+        }
+
+        let module_info = &self.modules[entry];
+        match export_assign {
+            Some((target, _)) => {
+                body.push(
+                    ModuleDecl::TsExportAssignment(TsExportAssignment {
+                        span: DUMMY_SP,
+                        expr: Ident::new_no_ctxt(names[&target].clone(), DUMMY_SP).into(),
+                    })
+                    .into(),
+                );
+            }
+            None => {
+                if !exports.is_empty() || external_stars.is_empty() {
+                    let specifiers = exports
+                        .iter()
+                        .map(|(name, target)| export_specifier(&names[target], name))
+                        .collect();
+
+                    body.push(named_export(specifiers));
+                }
+
+                for src in external_stars {
+                    body.push(
+                        ModuleDecl::ExportAll(ExportAll {
+                            span: DUMMY_SP,
+                            src: Box::new(str_lit(src)),
+                            type_only: false,
+                            with: None,
+                        })
+                        .into(),
+                    );
+                }
+            }
+        }
+
+        if let Some(namespace_export) = &module_info.namespace_export {
+            body.push(ModuleDecl::TsNamespaceExport(namespace_export.clone()).into());
+        }
+
+        Module {
+            span: DUMMY_SP,
+            body,
+            shebang: None,
+        }
+    }
+
+    /// Resolves a reference to a top-level binding or an import of `module`.
+    fn resolve_ref(&mut self, module: ModuleId, id: &Id) -> Option<Target> {
+        if let Some(target) = self.resolved.get(&(module, id.clone())) {
+            return target.clone();
+        }
+
+        let target = if self.modules[module].decls.contains_key(id) {
+            Some(Target::Decl(module, id.clone()))
+        } else {
+            match self.modules[module].imports.get(id).cloned() {
+                Some(Import::Named(Source::Internal(dep), name, span)) => {
+                    let target = self.resolve_export(dep, &name, &mut Default::default());
+                    if target.is_none() {
+                        self.issue(
+                            module,
+                            span,
+                            format!(
+                                "Module `{}` has no exported member `{name}`.",
+                                self.modules[dep].file
+                            ),
+                        );
+                    }
+                    target
+                }
+                Some(Import::Named(Source::External(src), name, _)) => {
+                    Some(Target::External(src, ExternalName::Named(name)))
+                }
+                Some(Import::Namespace(Source::Internal(dep))) => Some(Target::Namespace(dep)),
+                Some(Import::Namespace(Source::External(src))) => {
+                    Some(Target::External(src, ExternalName::Namespace))
+                }
+                None => None,
+            }
+        };
+
+        self.resolved.insert((module, id.clone()), target.clone());
+        target
+    }
+
+    /// Resolves the export `name` of `module`.
+    fn resolve_export(
+        &mut self,
+        module: ModuleId,
+        name: &Atom,
+        visited: &mut FxHashSet<(ModuleId, Atom)>,
+    ) -> Option<Target> {
+        if !visited.insert((module, name.clone())) {
+            return None;
+        }
+
+        let export = self.modules[module]
+            .exports
+            .iter()
+            .find(|(exported, _)| exported == name)
+            .map(|(_, export)| export.clone());
+
+        if let Some(export) = export {
+            return match export {
+                Export::Local(id, span) => {
+                    let target = self.resolve_ref(module, &id);
+                    if target.is_none() {
+                        self.issue(module, span, format!("Cannot find name `{}`.", id.0));
+                    }
+                    target
+                }
+                Export::Reexport(Source::Internal(dep), orig, span) => {
+                    let target = self.resolve_export(dep, &orig, visited);
+                    if target.is_none() {
+                        self.issue(
+                            module,
+                            span,
+                            format!(
+                                "Module `{}` has no exported member `{orig}`.",
+                                self.modules[dep].file
+                            ),
+                        );
+                    }
+                    target
+                }
+                Export::Reexport(Source::External(src), orig, _) => {
+                    Some(Target::External(src, ExternalName::Named(orig)))
+                }
+                Export::Namespace(Source::Internal(dep)) => Some(Target::Namespace(dep)),
+                Export::Namespace(Source::External(src)) => {
+                    Some(Target::External(src, ExternalName::Namespace))
+                }
+            };
+        }
+
+        if *name == "default" {
+            return None;
+        }
+
+        // Names not found in the internal modules should come from the external
+        // one.
+        let mut external = None;
+        for star in self.modules[module].stars.clone() {
+            match star {
+                Source::Internal(dep) => {
+                    if let Some(target) = self.resolve_export(dep, name, visited) {
+                        return Some(target);
+                    }
+                }
+                Source::External(src) => {
+                    external.get_or_insert(src);
+                }
+            }
+        }
+
+        external.map(|src| Target::External(src, ExternalName::Named(name.clone())))
+    }
+
+    /// Returns the exports of `module`, and the sources of `export *` which
+    /// can't be listed.
+    fn module_exports(
+        &mut self,
+        module: ModuleId,
+        visited: &mut FxHashSet<ModuleId>,
+    ) -> (Vec<(Atom, Target)>, Vec<Wtf8Atom>) {
+        let mut exports = Vec::new();
+        let mut external_stars = Vec::new();
+
+        if !visited.insert(module) {
+            return (exports, external_stars);
+        }
+
+        let mut names = FxHashSet::default();
+
+        let explicit = self.modules[module]
+            .exports
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        for name in explicit {
+            if !names.insert(name.clone()) {
+                continue;
+            }
+
+            if let Some(target) = self.resolve_export(module, &name, &mut Default::default()) {
+                exports.push((name, target));
+            }
+        }
+
+        for star in self.modules[module].stars.clone() {
+            match star {
+                Source::Internal(dep) => {
+                    let (dep_exports, dep_external_stars) = self.module_exports(dep, visited);
+
+                    for (name, target) in dep_exports {
+                        if name != "default" && names.insert(name.clone()) {
+                            exports.push((name, target));
+                        }
+                    }
+                    for src in dep_external_stars {
+                        if !external_stars.contains(&src) {
+                            external_stars.push(src);
+                        }
+                    }
+                }
+                Source::External(src) => {
+                    if !external_stars.contains(&src) {
+                        external_stars.push(src);
+                    }
+                }
+            }
+        }
+
+        (exports, external_stars)
+    }
+
+    /// Returns the exports of `module` except the ones annotated with
+    /// `@internal`.
+    fn public_exports(&mut self, module: ModuleId) -> (Vec<(Atom, Target)>, Vec<Wtf8Atom>) {
+        let (mut exports, external_stars) = self.module_exports(module, &mut Default::default());
+        exports.retain(|(_, target)| !self.is_internal(target));
+
+        (exports, external_stars)
+    }
+
+    /// Returns true if all declarations of `target` are annotated with
+    /// `@internal`.
+    fn is_internal(&self, target: &Target) -> bool {
+        match target {
+            Target::Decl(module, id) => {
+                let module = &self.modules[*module];
+
+                module.decls[id]
+                    .iter()
+                    .all(|&index| module.items[index].internal)
+            }
+            _ => false,
+        }
+    }
+
+    fn include(&mut self, target: Target, hint: &Atom) {
+        self.hints
+            .entry(target.clone())
+            .or_insert_with(|| hint.clone());
+
+        if self.seen.insert(target.clone()) {
+            self.queue.push(target);
+        }
+    }
+
+    fn include_item(&mut self, module: ModuleId, index: usize) {
+        if !self.included[module].insert(index) {
+            return;
+        }
+
+        for id in self.modules[module].items[index].refs.clone() {
+            if let Some(target) = self.resolve_ref(module, &id) {
+                self.include(target, &id.0);
+            }
+        }
+    }
+
+    fn drain_queue(&mut self) {
+        while let Some(target) = self.queue.pop() {
+            match target {
+                Target::Decl(module, id) => {
+                    for index in self.modules[module].decls[&id].clone() {
+                        self.include_item(module, index);
+                    }
+                }
+                Target::Namespace(module) => {
+                    self.namespaces.push(module);
+
+                    let (exports, external_stars) = self.public_exports(module);
+
+                    for (name, target) in exports {
+                        self.include(target, &name);
+                    }
+                    for src in external_stars {
+                        self.issue(
+                            module,
+                            DUMMY_SP,
+                            format!(
+                                "Cannot create the namespace of `{}`, because it re-exports \
+                                 `{}`.",
+                                self.modules[module].file,
+                                src.to_string_lossy()
+                            ),
+                        );
+                    }
+                }
+                Target::External(src, name) => self.externals.push((src, name)),
+            }
+        }
+    }
+
+    /// Dependencies come before the modules importing them.
+    fn output_order(&self, entry: ModuleId) -> Vec<ModuleId> {
+        fn visit(
+            modules: &[ModuleInfo],
+            module: ModuleId,
+            visited: &mut [bool],
+            order: &mut Vec<ModuleId>,
+        ) {
+            if visited[module] {
+                return;
+            }
+            visited[module] = true;
+
+            for &dep in &modules[module].deps {
+                visit(modules, dep, visited, order);
+            }
+            order.push(module);
+        }
+
+        let mut visited = vec![false; self.modules.len()];
+        let mut order = Vec::with_capacity(self.modules.len());
+        visit(&self.modules, entry, &mut visited, &mut order);
+
+        order
+    }
+
+    /// Names the bindings of the bundle. Declarations of the entry take
+    /// precedence, so exported names are kept if possible.
+    fn assign_names(&self, entry: ModuleId, order: &[ModuleId]) -> FxHashMap<Target, Atom> {
+        let mut used = self
+            .modules
+            .iter()
+            .flat_map(|module| module.globals.iter().cloned())
+            .collect::<FxHashSet<_>>();
+        let mut names = FxHashMap::default();
+
+        self.name_items(&mut names, &mut used, entry);
+
+        for (src, name) in &self.externals {
+            let target = Target::External(src.clone(), name.clone());
+            let name = unique_name(&mut used, &self.hints[&target]);
+            names.insert(target, name);
+        }
+
+        for &module in order {
+            if module != entry {
+                self.name_items(&mut names, &mut used, module);
+            }
+        }
+
+        for &module in &self.namespaces {
+            let target = Target::Namespace(module);
+            let name = unique_name(&mut used, &self.hints[&target]);
+            names.insert(target, name);
+        }
+
+        names
+    }
+
+    fn name_items(
+        &self,
+        names: &mut FxHashMap<Target, Atom>,
+        used: &mut FxHashSet<Atom>,
+        module: ModuleId,
+    ) {
+        let mut indices = self.included[module].iter().copied().collect::<Vec<_>>();
+        indices.sort_unstable();
+
+        for index in indices {
+            for id in &self.modules[module].items[index].ids {
+                names
+                    .entry(Target::Decl(module, id.clone()))
+                    .or_insert_with(|| unique_name(used, &id.0));
+            }
+        }
+    }
+
+    fn emit_imports(
+        &self,
+        names: &FxHashMap<Target, Atom>,
+        side_effects: Vec<Wtf8Atom>,
+    ) -> Vec<ModuleItem> {
+        let mut sources = Vec::<(Wtf8Atom, Vec<ImportSpecifier>)>::new();
+        let mut namespaces = Vec::new();
+
+        for (src, name) in &self.externals {
+            let local = Ident::new_no_ctxt(
+                names[&Target::External(src.clone(), name.clone())].clone(),
+                DUMMY_SP,
+            );
+
+            let specifier = match name {
+                ExternalName::Named(name) if *name == "default" => {
+                    ImportSpecifier::Default(ImportDefaultSpecifier {
+                        span: DUMMY_SP,
+                        local,
+                    })
+                }
+                ExternalName::Named(name) => ImportSpecifier::Named(ImportNamedSpecifier {
+                    span: DUMMY_SP,
+                    imported: (local.sym != *name).then(|| {
+                        ModuleExportName::Ident(Ident::new_no_ctxt(name.clone(), DUMMY_SP))
+                    }),
+                    local,
+                    is_type_only: false,
+                }),
+                ExternalName::Namespace => {
+                    namespaces.push((
+                        src.clone(),
+                        ImportSpecifier::Namespace(ImportStarAsSpecifier {
+                            span: DUMMY_SP,
+                            local,
+                        }),
+                    ));
+                    continue;
+                }
+            };
+
+            match sources.iter_mut().find(|(s, _)| s == src) {
+                Some((_, specifiers)) => {
+                    if specifier.is_default() {
+                        specifiers.insert(0, specifier);
+                    } else {
+                        specifiers.push(specifier);
+                    }
+                }
+                None => sources.push((src.clone(), vec![specifier])),
+            }
+        }
+
+        for src in side_effects {
+            if !sources.iter().any(|(s, _)| *s == src) && !namespaces.iter().any(|(s, _)| *s == src)
+            {
+                sources.push((src, Vec::new()));
+            }
+        }
+
+        sources
+            .into_iter()
+            .chain(
+                namespaces
+                    .into_iter()
+                    .map(|(src, specifier)| (src, vec![specifier])),
+            )
+            .map(|(src, specifiers)| {
+                ModuleDecl::Import(ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers,
+                    src: Box::new(str_lit(src)),
+                    type_only: false,
+                    with: None,
+                    phase: Default::default(),
+                })
+                .into()
+            })
+            .collect()
+    }
+
+    fn emit_items(&mut self, module: ModuleId, names: &FxHashMap<Target, Atom>) -> Vec<ModuleItem> {
+        let mut indices = self.included[module].iter().copied().collect::<Vec<_>>();
+        indices.sort_unstable();
+
+        let mut renames = FxHashMap::default();
+        for &index in &indices {
+            let item = &self.modules[module].items[index];
+
+            for id in &item.ids {
+                let name = &names[&Target::Decl(module, id.clone())];
+                if *name != id.0 {
+                    renames.insert(id.clone(), name.clone());
+                }
+            }
+
+            for id in &item.refs {
+                if let Some(Some(target)) = self.resolved.get(&(module, id.clone())) {
+                    let name = &names[target];
+                    if *name != id.0 {
+                        renames.insert(id.clone(), name.clone());
+                    }
+                }
+            }
+        }
+
+        indices
+            .into_iter()
+            .map(|index| {
+                let mut node = self.modules[module].items[index].node.take();
+                node.visit_mut_with(&mut Renamer(&renames));
+                node
+            })
+            .collect()
+    }
+
+    /// `declare namespace ns { export { a, b }; }`
+    fn emit_namespace(&mut self, module: ModuleId, names: &FxHashMap<Target, Atom>) -> ModuleItem {
+        let (exports, _) = self.public_exports(module);
+        let specifiers = exports
+            .iter()
+            .map(|(name, target)| export_specifier(&names[target], name))
+            .collect();
+
+        Decl::TsModule(Box::new(TsModuleDecl {
+            span: DUMMY_SP,
+            declare: true,
+            global: false,
+            namespace: true,
+            id: TsModuleName::Ident(Ident::new_no_ctxt(
+                names[&Target::Namespace(module)].clone(),
+                DUMMY_SP,
+            )),
+            body: Some(TsNamespaceBody::TsModuleBlock(TsModuleBlock {
+                span: DUMMY_SP,
+                body: vec![named_export(specifiers)],
+            })),
+        }))
+        .into()
+    }
+}
+
+/// Returns `name`, or `name$1`, `name$2`, ... if it's already used.
+fn unique_name(used: &mut FxHashSet<Atom>, name: &Atom) -> Atom {
+    if used.insert(name.clone()) {
+        return name.clone();
+    }
+
+    (1..)
+        .map(|i| Atom::from(format!("{name}${i}")))
+        .find(|name| used.insert(name.clone()))
+        .unwrap()
+}
+
+fn export_specifier(local: &Atom, exported: &Atom) -> ExportSpecifier {
+    ExportSpecifier::Named(ExportNamedSpecifier {
+        span: DUMMY_SP,
+        orig: ModuleExportName::Ident(Ident::new_no_ctxt(local.clone(), DUMMY_SP)),
+        exported: (local != exported)
+            .then(|| ModuleExportName::Ident(Ident::new_no_ctxt(exported.clone(), DUMMY_SP))),
+        is_type_only: false,
+    })
+}
+
+fn named_export(specifiers: Vec<ExportSpecifier>) -> ModuleItem {
+    ModuleDecl::ExportNamed(NamedExport {
+        span: DUMMY_SP,
+        specifiers,
+        src: None,
+        type_only: false,
+        with: None,
+    })
+    .into()
+}
+
+fn str_lit(value: Wtf8Atom) -> Str {
+    Str {
+        span: DUMMY_SP,
+        value,
+        raw: None,
+    }
+}
@@ -0,0 +1,14 @@
+import type { EventEmitter } from "events";
+
+export interface Options {
+  url: string;
+  emitter?: EventEmitter;
+}
+
+export interface Client {
+  close(): void;
+}
+
+export function createClient(options: Options): Client {
+  return { close() {} };
+}
@@ -0,0 +1,9 @@
+import { Options } from "./client";
+
+export { createClient } from "./client";
+export type { Options as ServerOptions } from "./server";
+export * from "./utils";
+
+export function configure(options: Options): void {}
+
+export function on(listener: (event: Event) => void): void {}
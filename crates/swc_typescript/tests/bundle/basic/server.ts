@@ -0,0 +1,3 @@
+export interface Options {
+  port: number;
+}
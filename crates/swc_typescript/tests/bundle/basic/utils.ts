@@ -0,0 +1,9 @@
+export type Maybe<T> = T | undefined;
+
+export const VERSION: string = "1.0.0";
+
+export interface Event {
+  name: string;
+}
+
+export * from "stream";
@@ -0,0 +1,22 @@
+import * as shapes from "./shapes";
+import { Registry } from "./registry";
+
+export { shapes };
+
+/** @internal */
+export function helper(): void {}
+
+export class Widget {
+  /** @internal */
+  secret: number = 1;
+
+  registry: Registry = new Registry();
+
+  area(shape: shapes.Shape): number {
+    return 0;
+  }
+}
+
+export default function (): Widget {
+  return new Widget();
+}
@@ -0,0 +1,4 @@
+/** @internal */
+export class Registry {
+  items: string[] = [];
+}
@@ -0,0 +1,8 @@
+export interface Shape {
+  kind: Kind;
+}
+
+export type Kind = "circle" | "square";
+
+/** @internal */
+export interface Hidden {}
@@ -0,0 +1,6 @@
+import { Missing } from "./a";
+
+export { Present } from "./a";
+export { Other } from "./missing";
+
+export function use(value: Missing): void {}
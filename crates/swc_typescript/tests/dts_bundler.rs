@@ -0,0 +1,97 @@
+use std::{fs, path::PathBuf, sync::Arc};
+
+use swc_common::{FileName, Mark, comments::SingleThreadedComments};
+use swc_ecma_codegen::to_code_with_comments;
+use swc_ecma_parser::{Syntax, TsSyntax, parse_file_as_program};
+use swc_ecma_transforms_base::{fixer::paren_remover, resolver};
+use swc_typescript::{dts_bundler::DtsBundler, fast_dts::FastDts};
+use testing::NormalizedOutput;
+
+#[testing::fixture("tests/bundle/**/index.ts")]
+fn fixture(input: PathBuf) {
+    let dir = input.parent().unwrap().to_path_buf();
+    let mut dts_code = String::new();
+
+    let res = testing::run_test2(false, |cm, handler| {
+        let resolve = |_: &FileName, specifier: &str| {
+            let path = specifier.strip_prefix("./")?;
+            let path = dir.join(path).with_extension("ts");
+
+            Some(Arc::new(FileName::Real(path)))
+        };
+        let mut bundler = DtsBundler::new(resolve);
+
+        let mut entry = None;
+        let mut paths = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ts"))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        for path in paths {
+            let fm = cm.load_file(&path).expect("failed to load test case");
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            let comments = SingleThreadedComments::default();
+            let mut program = parse_file_as_program(
+                &fm,
+                Syntax::Typescript(TsSyntax::default()),
+                Default::default(),
+                Some(&comments),
+                &mut Vec::new(),
+            )
+            .map_err(|err| err.into_diagnostic(&handler).emit())
+            .map(|program| program.apply(resolver(unresolved_mark, top_level_mark, true)))
+            .map(|program| program.apply(paren_remover(None)))
+            .unwrap();
+
+            let mut checker = FastDts::new(fm.name.clone(), unresolved_mark, Default::default());
+            let issues = checker.transform(&mut program);
+            for issue in issues {
+                handler
+                    .struct_span_err(issue.range.span, &issue.message)
+                    .emit();
+            }
+
+            if path == input {
+                entry = Some(fm.name.clone());
+            }
+
+            bundler.add_file(
+                fm.name.clone(),
+                program.expect_module(),
+                Some(FastDts::get_internal_annotations(&comments)),
+            );
+        }
+
+        let (module, issues) = bundler.bundle(entry.unwrap());
+        dts_code = to_code_with_comments(None, &module);
+
+        for issue in issues {
+            handler
+                .struct_span_err(issue.range.span, &issue.message)
+                .emit();
+        }
+
+        if handler.has_errors() {
+            Err(())
+        } else {
+            Ok(())
+        }
+    });
+
+    let mut output =
+        format!("```==================== .D.TS ====================\n\n{dts_code}\n\n");
+
+    if let Err(issues) = res {
+        output.push_str(&format!(
+            "==================== Errors ====================\n{issues}\n\n```"
+        ));
+    }
+
+    NormalizedOutput::from(output)
+        .compare_to_file(dir.join("output.snap"))
+        .unwrap();
+}
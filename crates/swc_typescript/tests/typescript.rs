@@ -7,8 +7,8 @@ use swc_ecma_transforms_base::{fixer::paren_remover, resolver};
 use swc_typescript::fast_dts::{FastDts, FastDtsOptions};
 use testing::NormalizedOutput;
 
-#[testing::fixture("tests/**/*.ts")]
-#[testing::fixture("tests/**/*.tsx")]
+#[testing::fixture("tests/**/*.ts", exclude("bundle"))]
+#[testing::fixture("tests/**/*.tsx", exclude("bundle"))]
 fn fixture(input: PathBuf) {
     let mut dts_code = String::new();
     let res = testing::run_test2(false, |cm, handler| {
@@ -63,30 +63,17 @@ pub struct DocumentType {
     pub raw: Option<Atom>,
 }
 
-#[derive(StringEnum, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash, EqIgnoreSpan)]
-#[cfg_attr(
-    feature = "encoding-impl",
-    derive(::swc_common::Encode, ::swc_common::Decode)
-)]
-pub enum Namespace {
-    /// `http://www.w3.org/1999/xhtml`
-    HTML,
-    /// `http://www.w3.org/1998/Math/MathML`
-    MATHML,
-    /// `http://www.w3.org/2000/svg`
-    SVG,
-    /// `http://www.w3.org/1999/xlink`
-    XLINK,
-    /// `http://www.w3.org/XML/1998/namespace`
-    XML,
-    /// `http://www.w3.org/2000/xmlns/`
-    XMLNS,
-}
-
 #[ast_node("Element")]
 #[derive(Eq, Hash, EqIgnoreSpan)]
 pub struct Element {
     pub span: Span,
+    /// The URI of the namespace of the element, set by the namespace
+    /// resolution of the parser
+    #[cfg_attr(
+        feature = "encoding-impl",
+        encoding(with = "cbor4ii::core::types::Maybe")
+    )]
+    pub namespace: Option<Atom>,
     pub tag_name: Atom,
     pub attributes: Vec<Attribute>,
     pub children: Vec<Child>,
@@ -96,11 +83,13 @@ pub struct Element {
 #[derive(Eq, Hash, EqIgnoreSpan)]
 pub struct Attribute {
     pub span: Span,
+    /// The URI of the namespace of the attribute, set by the namespace
+    /// resolution of the parser
     #[cfg_attr(
         feature = "encoding-impl",
         encoding(with = "cbor4ii::core::types::Maybe")
     )]
-    pub namespace: Option<Namespace>,
+    pub namespace: Option<Atom>,
     #[cfg_attr(
         feature = "encoding-impl",
         encoding(with = "cbor4ii::core::types::Maybe")
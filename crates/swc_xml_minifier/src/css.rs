@@ -0,0 +1,105 @@
+use swc_common::{sync::Lrc, FileName, FilePathMapping, SourceMap, DUMMY_SP};
+
+use crate::option::{CssOptions, MinifyCssOption};
+
+pub(crate) enum CssMinificationMode {
+    /// The content of a `style` element
+    Stylesheet,
+    /// The value of a `style` attribute
+    ListOfDeclarations,
+}
+
+/// Minifies CSS, returns `None` if it is disabled or the CSS is invalid.
+pub(crate) fn minify_css(
+    options: &MinifyCssOption,
+    data: String,
+    mode: CssMinificationMode,
+) -> Option<String> {
+    let mut options = match options {
+        MinifyCssOption::Bool(false) => return None,
+        MinifyCssOption::Bool(true) => CssOptions::default(),
+        MinifyCssOption::Options(options) => (**options).clone(),
+    };
+
+    let mut errors: Vec<_> = Vec::new();
+
+    let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    let fm = cm.new_source_file(FileName::Anon.into(), data);
+
+    let mut stylesheet = match mode {
+        CssMinificationMode::Stylesheet => {
+            swc_css_parser::parse_file(&fm, None, options.parser, &mut errors).ok()?
+        }
+        CssMinificationMode::ListOfDeclarations => {
+            let list_of_declarations = swc_css_parser::parse_file::<
+                Vec<swc_css_ast::DeclarationOrAtRule>,
+            >(&fm, None, options.parser, &mut errors)
+            .ok()?;
+
+            swc_css_ast::Stylesheet {
+                span: Default::default(),
+                rules: vec![swc_css_ast::Rule::QualifiedRule(
+                    swc_css_ast::QualifiedRule {
+                        span: Default::default(),
+                        prelude: swc_css_ast::QualifiedRulePrelude::SelectorList(
+                            swc_css_ast::SelectorList {
+                                span: Default::default(),
+                                children: Vec::new(),
+                            },
+                        ),
+                        block: swc_css_ast::SimpleBlock {
+                            span: Default::default(),
+                            name: swc_css_ast::TokenAndSpan {
+                                span: DUMMY_SP,
+                                token: swc_css_ast::Token::LBrace,
+                            },
+                            value: list_of_declarations
+                                .into_iter()
+                                .map(|node| node.into())
+                                .collect(),
+                        },
+                    }
+                    .into(),
+                )],
+            }
+        }
+    };
+
+    // Avoid compress potential invalid CSS
+    if !errors.is_empty() {
+        return None;
+    }
+
+    swc_css_minifier::minify(&mut stylesheet, options.minifier);
+
+    let mut minified = String::new();
+    let wr = swc_css_codegen::writer::basic::BasicCssWriter::new(
+        &mut minified,
+        None,
+        swc_css_codegen::writer::basic::BasicCssWriterConfig::default(),
+    );
+
+    options.codegen.minify = true;
+
+    let mut gen = swc_css_codegen::CodeGenerator::new(wr, options.codegen);
+
+    match mode {
+        CssMinificationMode::Stylesheet => {
+            swc_css_codegen::Emit::emit(&mut gen, &stylesheet).unwrap();
+        }
+        CssMinificationMode::ListOfDeclarations => {
+            let swc_css_ast::Stylesheet { rules, .. } = &stylesheet;
+
+            // Because CSS is grammar free, protect for fails
+            let Some(swc_css_ast::Rule::QualifiedRule(qualified_rule)) = rules.first() else {
+                return None;
+            };
+
+            swc_css_codegen::Emit::emit(&mut gen, &qualified_rule.block).unwrap();
+
+            minified = minified[1..minified.len() - 1].to_string();
+        }
+    }
+
+    Some(minified)
+}
@@ -0,0 +1,860 @@
+#![deny(clippy::all)]
+
+use std::mem::take;
+
+use rustc_hash::FxHashSet;
+use swc_atoms::Atom;
+use swc_xml_ast::*;
+use swc_xml_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+
+#[cfg(feature = "default-css-minifier")]
+use crate::css::{minify_css, CssMinificationMode};
+use crate::{
+    number::{format_number, parse_number_with_unit},
+    option::MinifyOptions,
+    path::minify_path_data,
+};
+
+#[cfg(feature = "default-css-minifier")]
+mod css;
+mod number;
+pub mod option;
+mod path;
+
+/// Namespaces of data used only by editors.
+static EDITORS_NAMESPACES: &[&str] = &[
+    "http://creativecommons.org/ns#",
+    "http://inkscape.sourceforge.net/DTD/sodipodi-0.dtd",
+    "http://ns.adobe.com/AdobeIllustrator/10.0/",
+    "http://ns.adobe.com/AdobeSVGViewerExtensions/3.0/",
+    "http://ns.adobe.com/Extensibility/1.0/",
+    "http://ns.adobe.com/Flows/1.0/",
+    "http://ns.adobe.com/GenericCustomNamespace/1.0/",
+    "http://ns.adobe.com/Graphs/1.0/",
+    "http://ns.adobe.com/ImageReplacement/1.0/",
+    "http://ns.adobe.com/SaveForWeb/1.0/",
+    "http://ns.adobe.com/Variables/1.0/",
+    "http://ns.adobe.com/XPath/1.0/",
+    "http://purl.org/dc/elements/1.1/",
+    "http://schemas.microsoft.com/visio/2003/SVGExtensions/",
+    "http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd",
+    "http://taptrix.com/vectorillustrator/svg_extensions",
+    "http://www.bohemiancoding.com/sketch/ns",
+    "http://www.figma.com/figma/ns",
+    "http://www.inkscape.org/namespaces/inkscape",
+    "http://www.serif.com/",
+    "http://www.vector.evaxdesign.sk",
+    "http://www.w3.org/1999/02/22-rdf-syntax-ns#",
+];
+
+/// SVG elements where whitespaces are rendered or otherwise significant.
+static TEXT_ELEMENTS: &[&str] = &[
+    "desc", "script", "style", "text", "textPath", "title", "tspan",
+];
+
+static ANIMATION_ELEMENTS: &[&str] = &[
+    "animate",
+    "animateColor",
+    "animateMotion",
+    "animateTransform",
+    "set",
+];
+
+/// Presentation attributes which are inherited by descendants.
+static INHERITED_ATTRIBUTES: &[&str] = &[
+    "clip-rule",
+    "color",
+    "color-interpolation",
+    "color-interpolation-filters",
+    "color-rendering",
+    "cursor",
+    "direction",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "font",
+    "font-family",
+    "font-size",
+    "font-size-adjust",
+    "font-stretch",
+    "font-style",
+    "font-variant",
+    "font-weight",
+    "image-rendering",
+    "letter-spacing",
+    "marker",
+    "marker-end",
+    "marker-mid",
+    "marker-start",
+    "paint-order",
+    "pointer-events",
+    "shape-rendering",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "text-anchor",
+    "text-rendering",
+    "visibility",
+    "word-spacing",
+    "writing-mode",
+];
+
+/// Default values of attributes, for all SVG elements (`*`) or a specific
+/// element.
+static DEFAULT_ATTRIBUTES: &[(&str, &str, &[&str])] = &[
+    // Presentation attributes
+    ("*", "clip-path", &["none"]),
+    ("*", "clip-rule", &["nonzero"]),
+    ("*", "color-interpolation", &["sRGB"]),
+    ("*", "color-interpolation-filters", &["linearRGB"]),
+    ("*", "direction", &["ltr"]),
+    ("*", "fill", &["#000", "#000000", "black"]),
+    ("*", "fill-opacity", &["1"]),
+    ("*", "fill-rule", &["nonzero"]),
+    ("*", "filter", &["none"]),
+    ("*", "flood-color", &["#000", "#000000", "black"]),
+    ("*", "flood-opacity", &["1"]),
+    ("*", "font-stretch", &["normal"]),
+    ("*", "font-style", &["normal"]),
+    ("*", "font-variant", &["normal"]),
+    ("*", "font-weight", &["normal", "400"]),
+    ("*", "letter-spacing", &["normal"]),
+    ("*", "lighting-color", &["#fff", "#ffffff", "white"]),
+    ("*", "marker-end", &["none"]),
+    ("*", "marker-mid", &["none"]),
+    ("*", "marker-start", &["none"]),
+    ("*", "mask", &["none"]),
+    ("*", "opacity", &["1"]),
+    ("*", "paint-order", &["normal"]),
+    ("*", "stop-color", &["#000", "#000000", "black"]),
+    ("*", "stop-opacity", &["1"]),
+    ("*", "stroke", &["none"]),
+    ("*", "stroke-dasharray", &["none"]),
+    ("*", "stroke-dashoffset", &["0"]),
+    ("*", "stroke-linecap", &["butt"]),
+    ("*", "stroke-linejoin", &["miter"]),
+    ("*", "stroke-miterlimit", &["4"]),
+    ("*", "stroke-opacity", &["1"]),
+    ("*", "stroke-width", &["1"]),
+    ("*", "text-anchor", &["start"]),
+    ("*", "visibility", &["visible"]),
+    ("*", "word-spacing", &["normal"]),
+    // Element attributes
+    ("circle", "cx", &["0"]),
+    ("circle", "cy", &["0"]),
+    ("clipPath", "clipPathUnits", &["userSpaceOnUse"]),
+    ("ellipse", "cx", &["0"]),
+    ("ellipse", "cy", &["0"]),
+    (
+        "feImage",
+        "preserveAspectRatio",
+        &["xMidYMid", "xMidYMid meet"],
+    ),
+    ("filter", "filterUnits", &["objectBoundingBox"]),
+    ("filter", "primitiveUnits", &["userSpaceOnUse"]),
+    ("foreignObject", "x", &["0"]),
+    ("foreignObject", "y", &["0"]),
+    (
+        "image",
+        "preserveAspectRatio",
+        &["xMidYMid", "xMidYMid meet"],
+    ),
+    ("image", "x", &["0"]),
+    ("image", "y", &["0"]),
+    ("line", "x1", &["0"]),
+    ("line", "x2", &["0"]),
+    ("line", "y1", &["0"]),
+    ("line", "y2", &["0"]),
+    ("linearGradient", "gradientUnits", &["objectBoundingBox"]),
+    ("linearGradient", "spreadMethod", &["pad"]),
+    ("linearGradient", "x1", &["0", "0%"]),
+    ("linearGradient", "x2", &["100%"]),
+    ("linearGradient", "y1", &["0", "0%"]),
+    ("linearGradient", "y2", &["0", "0%"]),
+    ("marker", "markerHeight", &["3"]),
+    ("marker", "markerUnits", &["strokeWidth"]),
+    ("marker", "markerWidth", &["3"]),
+    ("marker", "orient", &["0"]),
+    (
+        "marker",
+        "preserveAspectRatio",
+        &["xMidYMid", "xMidYMid meet"],
+    ),
+    ("marker", "refX", &["0"]),
+    ("marker", "refY", &["0"]),
+    ("mask", "maskContentUnits", &["userSpaceOnUse"]),
+    ("mask", "maskUnits", &["objectBoundingBox"]),
+    ("pattern", "patternContentUnits", &["userSpaceOnUse"]),
+    ("pattern", "patternUnits", &["objectBoundingBox"]),
+    (
+        "pattern",
+        "preserveAspectRatio",
+        &["xMidYMid", "xMidYMid meet"],
+    ),
+    ("pattern", "x", &["0"]),
+    ("pattern", "y", &["0"]),
+    ("radialGradient", "cx", &["50%"]),
+    ("radialGradient", "cy", &["50%"]),
+    ("radialGradient", "gradientUnits", &["objectBoundingBox"]),
+    ("radialGradient", "r", &["50%"]),
+    ("radialGradient", "spreadMethod", &["pad"]),
+    ("rect", "x", &["0"]),
+    ("rect", "y", &["0"]),
+    ("stop", "offset", &["0"]),
+    ("svg", "height", &["100%"]),
+    ("svg", "preserveAspectRatio", &["xMidYMid", "xMidYMid meet"]),
+    ("svg", "width", &["100%"]),
+    ("svg", "x", &["0"]),
+    ("svg", "y", &["0"]),
+    (
+        "symbol",
+        "preserveAspectRatio",
+        &["xMidYMid", "xMidYMid meet"],
+    ),
+    ("use", "x", &["0"]),
+    ("use", "y", &["0"]),
+    (
+        "view",
+        "preserveAspectRatio",
+        &["xMidYMid", "xMidYMid meet"],
+    ),
+];
+
+/// Attributes with a number or a list of numbers as value.
+static NUMERIC_ATTRIBUTES: &[&str] = &[
+    "cx",
+    "cy",
+    "dx",
+    "dy",
+    "fill-opacity",
+    "flood-opacity",
+    "font-size",
+    "fr",
+    "fx",
+    "fy",
+    "height",
+    "markerHeight",
+    "markerWidth",
+    "opacity",
+    "pathLength",
+    "points",
+    "r",
+    "refX",
+    "refY",
+    "rx",
+    "ry",
+    "startOffset",
+    "stdDeviation",
+    "stop-opacity",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "viewBox",
+    "width",
+    "x",
+    "x1",
+    "x2",
+    "y",
+    "y1",
+    "y2",
+];
+
+/// Units which can follow numbers in [NUMERIC_ATTRIBUTES], `px` is removed.
+static UNITS: &[&str] = &["", "%", "cm", "em", "ex", "in", "mm", "pc", "pt", "px"];
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn prefix(name: &str) -> Option<&str> {
+    name.split_once(':').map(|(prefix, _)| prefix)
+}
+
+fn get_attribute<'a>(element: &'a Element, name: &str) -> Option<&'a Attribute> {
+    element
+        .attributes
+        .iter()
+        .find(|attribute| attribute.name == name)
+}
+
+fn set_attribute_value(attribute: &mut Attribute, value: Atom) {
+    attribute.value = Some(value);
+    attribute.raw_value = None;
+}
+
+fn is_whitespace(data: &str) -> bool {
+    data.chars().all(|c| matches!(c, ' ' | '\t' | '\n' | '\r'))
+}
+
+fn is_animation_element(child: &Child) -> bool {
+    matches!(child, Child::Element(element) if ANIMATION_ELEMENTS.contains(&local_name(&element.tag_name)))
+}
+
+/// Returns whether `element` has an animation element changing the attribute
+/// `name`.
+fn has_animated_attribute(element: &Element, name: &str) -> bool {
+    element.children.iter().any(|child| match child {
+        Child::Element(child) if ANIMATION_ELEMENTS.contains(&local_name(&child.tag_name)) => {
+            get_attribute(child, "attributeName").and_then(|a| a.value.as_deref()) == Some(name)
+        }
+        _ => false,
+    })
+}
+
+/// Returns the namespace declared by `attribute` and its prefix (an empty
+/// string for the default namespace).
+fn namespace_declaration(attribute: &Attribute) -> Option<(&str, &str)> {
+    let prefix = if attribute.name == "xmlns" {
+        ""
+    } else {
+        attribute.name.strip_prefix("xmlns:")?
+    };
+
+    Some((prefix, attribute.value.as_deref().unwrap_or_default()))
+}
+
+fn is_default_value(element: &str, name: &str, value: &str) -> bool {
+    DEFAULT_ATTRIBUTES
+        .iter()
+        .any(|(e, n, values)| (*e == "*" || *e == element) && *n == name && values.contains(&value))
+}
+
+/// Rounds numbers and removes the `px` unit from a value of
+/// [NUMERIC_ATTRIBUTES]. Returns `None` if the value is not a list of numbers.
+fn minify_numeric_value(value: &str, precision: u8) -> Option<String> {
+    let mut minified = String::with_capacity(value.len());
+
+    for (idx, item) in value
+        .split(|c: char| c == ',' || c.is_ascii_whitespace())
+        .filter(|item| !item.is_empty())
+        .enumerate()
+    {
+        let (number, unit) = parse_number_with_unit(item)?;
+
+        if !UNITS.contains(&unit) {
+            return None;
+        }
+
+        if idx != 0 {
+            minified.push(' ');
+        }
+
+        minified.push_str(&format_number(number, precision));
+
+        if unit != "px" {
+            minified.push_str(unit);
+        }
+    }
+
+    if minified.is_empty() {
+        return None;
+    }
+
+    Some(minified)
+}
+
+struct Minifier<'a> {
+    options: &'a MinifyOptions,
+
+    /// Whether the document has a `style` or `use` element, so inherited
+    /// attributes can't be removed safely
+    has_style_or_use: bool,
+
+    /// Whether the root element is an `svg` element
+    is_svg_document: bool,
+    /// Whether the current element is an SVG element
+    in_svg: bool,
+    /// Whether the current element is, or is a descendant of, a
+    /// [TEXT_ELEMENTS]
+    in_text: bool,
+    /// Prefixes of namespaces declared by the ancestors, and whether they
+    /// are editors namespaces
+    namespaces: Vec<(Atom, bool)>,
+    /// Names of the attributes of the SVG ancestors
+    ancestor_attributes: Vec<Atom>,
+}
+
+impl Minifier<'_> {
+    fn is_preserved_comment(&self, data: &str) -> bool {
+        if let Some(preserve_comments) = &self.options.preserve_comments {
+            return preserve_comments.iter().any(|regex| regex.is_match(data));
+        }
+
+        false
+    }
+
+    /// Returns whether `prefix` is bound to one of [EDITORS_NAMESPACES],
+    /// taking the declarations in `attributes` into account first.
+    fn is_editors_prefix(&self, prefix: &str, attributes: &[Attribute]) -> bool {
+        if let Some((_, namespace)) = attributes
+            .iter()
+            .filter_map(namespace_declaration)
+            .find(|(p, _)| *p == prefix)
+        {
+            return EDITORS_NAMESPACES.contains(&namespace);
+        }
+
+        self.namespaces
+            .iter()
+            .rev()
+            .find(|(p, _)| p == prefix)
+            .is_some_and(|(_, is_editors)| *is_editors)
+    }
+
+    fn is_removable_child(&self, child: &Child, is_document: bool) -> bool {
+        match child {
+            Child::Comment(comment) => {
+                self.options.remove_comments && !self.is_preserved_comment(&comment.data)
+            }
+            Child::ProcessingInstruction(processing_instruction) => {
+                is_document
+                    && self.options.remove_xml_declaration
+                    && processing_instruction.target == "xml"
+            }
+            Child::DocumentType(document_type) => {
+                self.options.remove_doctype
+                    && self.is_svg_document
+                    && !document_type
+                        .raw
+                        .as_ref()
+                        .is_some_and(|raw| raw.contains('['))
+            }
+            Child::Text(text) => {
+                self.options.remove_whitespaces
+                    && (is_document || (self.in_svg && !self.in_text))
+                    && is_whitespace(&text.data)
+            }
+            Child::Element(element) => {
+                (self.options.remove_metadata
+                    && self.in_svg
+                    && local_name(&element.tag_name) == "metadata")
+                    || (self.options.remove_editors_namespaces
+                        && self.is_editors_prefix(
+                            prefix(&element.tag_name).unwrap_or_default(),
+                            &element.attributes,
+                        ))
+            }
+            _ => false,
+        }
+    }
+
+    fn minify_children(&mut self, children: &mut Vec<Child>, parent: Option<&str>) {
+        let is_document = parent.is_none();
+
+        children.retain(|child| !self.is_removable_child(child, is_document));
+
+        for child in children.iter_mut() {
+            child.visit_mut_with(self);
+        }
+
+        if self.options.collapse_groups
+            && self.in_svg
+            && parent.is_some_and(|parent| local_name(parent) != "switch")
+        {
+            self.collapse_groups(children);
+        }
+    }
+
+    /// Moves the attributes of `g` elements to their only child and replaces
+    /// `g` elements without attributes with their children.
+    fn collapse_groups(&mut self, children: &mut Vec<Child>) {
+        if !children.iter().any(|child| {
+            matches!(child, Child::Element(element) if local_name(&element.tag_name) == "g")
+        }) {
+            return;
+        }
+
+        for child in take(children) {
+            match child {
+                Child::Element(mut element) if local_name(&element.tag_name) == "g" => {
+                    self.move_attributes_to_child(&mut element);
+
+                    if element.attributes.is_empty()
+                        && !element.children.iter().any(is_animation_element)
+                    {
+                        children.extend(element.children);
+                    } else {
+                        children.push(Child::Element(element));
+                    }
+                }
+                _ => children.push(child),
+            }
+        }
+    }
+
+    fn move_attributes_to_child(&self, group: &mut Element) {
+        if group.attributes.is_empty() {
+            return;
+        }
+
+        let group_has = |name: &str| get_attribute(group, name).is_some();
+        let has_filter = group_has("filter");
+        let has_class = group_has("class");
+        let has_clip_path_or_mask = group_has("clip-path") || group_has("mask");
+        let has_transform = group_has("transform");
+
+        let [Child::Element(child)] = &mut group.children[..] else {
+            return;
+        };
+
+        if get_attribute(child, "id").is_some()
+            || has_filter
+            || (has_class && get_attribute(child, "class").is_some())
+        {
+            return;
+        }
+
+        if has_clip_path_or_mask
+            && !(local_name(&child.tag_name) == "g"
+                && !has_transform
+                && get_attribute(child, "transform").is_none())
+        {
+            return;
+        }
+
+        let mut remaining = Vec::new();
+
+        for attribute in take(&mut group.attributes) {
+            if !remaining.is_empty() || has_animated_attribute(child, &attribute.name) {
+                remaining.push(attribute);
+
+                continue;
+            }
+
+            let Some(child_attribute) = child
+                .attributes
+                .iter_mut()
+                .find(|child_attribute| child_attribute.name == attribute.name)
+            else {
+                child.attributes.push(attribute);
+
+                continue;
+            };
+
+            if attribute.name == "transform" {
+                let value = format!(
+                    "{} {}",
+                    attribute.value.as_deref().unwrap_or_default(),
+                    child_attribute.value.as_deref().unwrap_or_default()
+                );
+
+                set_attribute_value(child_attribute, value.into());
+            } else if child_attribute.value.as_deref() == Some("inherit") {
+                child_attribute.value = attribute.value;
+                child_attribute.raw_value = None;
+            } else if !INHERITED_ATTRIBUTES.contains(&&*attribute.name)
+                && child_attribute.value != attribute.value
+            {
+                remaining.push(attribute);
+            }
+        }
+
+        group.attributes = remaining;
+    }
+
+    fn minify_attributes(&self, element: &mut Element) {
+        if self.options.remove_editors_namespaces {
+            element.attributes.retain(|attribute| {
+                if let Some((_, namespace)) = namespace_declaration(attribute) {
+                    return !EDITORS_NAMESPACES.contains(&namespace);
+                }
+
+                !prefix(&attribute.name).is_some_and(|prefix| self.is_editors_prefix(prefix, &[]))
+            });
+        }
+
+        if !self.in_svg {
+            return;
+        }
+
+        let tag_name = local_name(&element.tag_name);
+        let precision = self.options.float_precision;
+        let can_remove_inherited =
+            !self.has_style_or_use && !self.ancestor_attributes.iter().any(|name| name == "style");
+
+        for attribute in element.attributes.iter_mut() {
+            let Some(value) = &attribute.value else {
+                continue;
+            };
+
+            match &*attribute.name {
+                "d" if self.options.convert_path_data && tag_name == "path" => {
+                    if let Some(minified) = minify_path_data(value, precision) {
+                        if minified.len() <= value.len() {
+                            set_attribute_value(attribute, minified.into());
+                        }
+                    }
+                }
+                #[cfg(feature = "default-css-minifier")]
+                "style" => {
+                    if let Some(minified) = minify_css(
+                        &self.options.minify_css,
+                        value.to_string(),
+                        CssMinificationMode::ListOfDeclarations,
+                    ) {
+                        set_attribute_value(attribute, minified.into());
+                    }
+                }
+                name if self.options.cleanup_numeric_values
+                    && NUMERIC_ATTRIBUTES.contains(&name) =>
+                {
+                    if let Some(minified) = minify_numeric_value(value, precision) {
+                        if minified.len() <= value.len() {
+                            set_attribute_value(attribute, minified.into());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        element.attributes.retain(|attribute| {
+            let value = attribute.value.as_deref().unwrap_or_default();
+
+            if attribute.name == "style" && value.trim().is_empty() {
+                return false;
+            }
+
+            if !self.options.remove_default_attributes
+                || !is_default_value(tag_name, &attribute.name, value.trim())
+            {
+                return true;
+            }
+
+            if INHERITED_ATTRIBUTES.contains(&&*attribute.name) {
+                // Otherwise, the value of the attribute would be inherited from an ancestor
+                return !can_remove_inherited || self.ancestor_attributes.contains(&attribute.name);
+            }
+
+            false
+        });
+    }
+
+    #[cfg(feature = "default-css-minifier")]
+    fn minify_style_element(&self, element: &mut Element) {
+        if get_attribute(element, "type")
+            .and_then(|attribute| attribute.value.as_deref())
+            .is_some_and(|value| !value.is_empty() && value != "text/css")
+        {
+            return;
+        }
+
+        let mut data = String::new();
+
+        for child in &element.children {
+            match child {
+                Child::Text(Text { data: text, .. })
+                | Child::CdataSection(CdataSection { data: text, .. }) => data.push_str(text),
+                _ => return,
+            }
+        }
+
+        let Some(minified) = minify_css(
+            &self.options.minify_css,
+            data,
+            CssMinificationMode::Stylesheet,
+        ) else {
+            return;
+        };
+
+        element.children = if minified.is_empty() {
+            Vec::new()
+        } else {
+            // Use a CDATA section if it is shorter than escaping
+            let escaped_len = minified.len()
+                + minified
+                    .chars()
+                    .map(|c| match c {
+                        '<' | '>' => 3,
+                        '&' => 4,
+                        _ => 0,
+                    })
+                    .sum::<usize>();
+
+            if escaped_len > minified.len() + 12 && !minified.contains("]]>") {
+                vec![Child::CdataSection(CdataSection {
+                    span: Default::default(),
+                    data: minified.into(),
+                    raw: None,
+                })]
+            } else {
+                vec![Child::Text(Text {
+                    span: Default::default(),
+                    data: minified.into(),
+                    raw: None,
+                })]
+            }
+        };
+    }
+}
+
+impl VisitMut for Minifier<'_> {
+    fn visit_mut_document(&mut self, n: &mut Document) {
+        let mut finder = StyleOrUseFinder::default();
+
+        n.visit_with(&mut finder);
+
+        self.has_style_or_use = finder.found;
+        self.is_svg_document = n.children.iter().any(
+            |child| matches!(child, Child::Element(element) if local_name(&element.tag_name) == "svg"),
+        );
+
+        self.minify_children(&mut n.children, None);
+    }
+
+    fn visit_mut_element(&mut self, n: &mut Element) {
+        let old_in_svg = self.in_svg;
+        let old_in_text = self.in_text;
+        let old_namespaces_len = self.namespaces.len();
+        let old_ancestor_attributes_len = self.ancestor_attributes.len();
+
+        let tag_name = n.tag_name.clone();
+
+        if local_name(&tag_name) == "svg" {
+            self.in_svg = true;
+        }
+
+        for (prefix, namespace) in n.attributes.iter().filter_map(namespace_declaration) {
+            self.namespaces
+                .push((prefix.into(), EDITORS_NAMESPACES.contains(&namespace)));
+        }
+
+        self.minify_attributes(n);
+
+        if self.in_svg {
+            self.ancestor_attributes
+                .extend(n.attributes.iter().map(|attribute| attribute.name.clone()));
+
+            match local_name(&tag_name) {
+                // The content of `foreignObject` is not SVG
+                "foreignObject" => {
+                    self.in_svg = false;
+                }
+                #[cfg(feature = "default-css-minifier")]
+                "style" => {
+                    self.minify_style_element(n);
+                }
+                _ => {}
+            }
+
+            if TEXT_ELEMENTS.contains(&local_name(&tag_name)) {
+                self.in_text = true;
+            }
+        }
+
+        self.minify_children(&mut n.children, Some(&tag_name));
+
+        self.in_svg = old_in_svg;
+        self.in_text = old_in_text;
+        self.namespaces.truncate(old_namespaces_len);
+        self.ancestor_attributes
+            .truncate(old_ancestor_attributes_len);
+    }
+}
+
+#[derive(Default)]
+struct StyleOrUseFinder {
+    found: bool,
+}
+
+impl Visit for StyleOrUseFinder {
+    fn visit_element(&mut self, n: &Element) {
+        if matches!(local_name(&n.tag_name), "style" | "use") {
+            self.found = true;
+
+            return;
+        }
+
+        n.visit_children_with(self);
+    }
+}
+
+/// Collects the prefixes used by elements and attributes.
+#[derive(Default)]
+struct UsedPrefixes {
+    prefixes: FxHashSet<Atom>,
+}
+
+impl Visit for UsedPrefixes {
+    fn visit_element(&mut self, n: &Element) {
+        if let Some(prefix) = prefix(&n.tag_name) {
+            self.prefixes.insert(prefix.into());
+        }
+
+        for attribute in &n.attributes {
+            if let Some(prefix) = prefix(&attribute.name) {
+                if prefix != "xmlns" {
+                    self.prefixes.insert(prefix.into());
+                }
+            }
+        }
+
+        n.visit_children_with(self);
+    }
+}
+
+/// Removes declarations of namespaces whose prefixes are not used on SVG
+/// elements. The prefixes may be used in attribute values of other XML
+/// vocabularies.
+struct UnusedNamespacesRemover {
+    used: FxHashSet<Atom>,
+    in_svg: bool,
+}
+
+impl VisitMut for UnusedNamespacesRemover {
+    fn visit_mut_element(&mut self, n: &mut Element) {
+        let old_in_svg = self.in_svg;
+
+        if local_name(&n.tag_name) == "svg" {
+            self.in_svg = true;
+        }
+
+        if self.in_svg {
+            n.attributes.retain(|attribute| {
+                !attribute
+                    .name
+                    .strip_prefix("xmlns:")
+                    .is_some_and(|prefix| !self.used.contains(&Atom::from(prefix)))
+            });
+        }
+
+        if local_name(&n.tag_name) == "foreignObject" {
+            self.in_svg = false;
+        }
+
+        n.visit_mut_children_with(self);
+
+        self.in_svg = old_in_svg;
+    }
+}
+
+/// Minifies an XML document, optimizing the content of `svg` elements.
+pub fn minify_document(document: &mut Document, options: &MinifyOptions) {
+    document.visit_mut_with(&mut Minifier {
+        options,
+        has_style_or_use: false,
+        is_svg_document: false,
+        in_svg: false,
+        in_text: false,
+        namespaces: Vec::new(),
+        ancestor_attributes: Vec::new(),
+    });
+
+    if options.remove_unused_namespaces {
+        let mut used_prefixes = UsedPrefixes::default();
+
+        document.visit_with(&mut used_prefixes);
+
+        document.visit_mut_with(&mut UnusedNamespacesRemover {
+            used: used_prefixes.prefixes,
+            in_svg: false,
+        });
+    }
+}
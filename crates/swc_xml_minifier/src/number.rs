@@ -0,0 +1,107 @@
+/// Rounds `value` to `precision` digits after the decimal point and prints it
+/// in the shortest form, i.e. without trailing zeros and the leading zero
+/// (`0.50` -> `.5`, `-0.5` -> `-.5`).
+pub(crate) fn format_number(value: f64, precision: u8) -> String {
+    let mut s = format!("{:.*}", precision as usize, round(value, precision));
+
+    if s.contains('.') {
+        let trimmed = s.trim_end_matches('0').trim_end_matches('.').len();
+
+        s.truncate(trimmed);
+    }
+
+    if s == "-0" {
+        return "0".into();
+    }
+
+    if let Some(rest) = s.strip_prefix("0.") {
+        format!(".{rest}")
+    } else if let Some(rest) = s.strip_prefix("-0.") {
+        format!("-.{rest}")
+    } else {
+        s
+    }
+}
+
+/// Rounds `value` in the same way as [format_number].
+pub(crate) fn round(value: f64, precision: u8) -> f64 {
+    // More digits can't be represented by `f64`
+    if precision > 15 {
+        return value;
+    }
+
+    let multiplier = 10f64.powi(precision as i32);
+
+    (value * multiplier).round() / multiplier
+}
+
+/// A list of numbers and commands (i.e. path data), which only adds the
+/// separators required to split numbers.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct NumberList {
+    pub buf: String,
+    /// Whether the last pushed number contains a decimal point, or `None` if
+    /// the list doesn't end with a number.
+    last_has_dot: Option<bool>,
+}
+
+impl NumberList {
+    pub fn push_command(&mut self, command: char) {
+        self.buf.push(command);
+        self.last_has_dot = None;
+    }
+
+    pub fn push_number(&mut self, number: &str) {
+        if let Some(last_has_dot) = self.last_has_dot {
+            if !(number.starts_with('-') || (number.starts_with('.') && last_has_dot)) {
+                self.buf.push(' ');
+            }
+        }
+
+        self.buf.push_str(number);
+        self.last_has_dot = Some(number.contains('.'));
+    }
+
+    /// Creates an empty list which can be appended to this list with
+    /// [NumberList::append].
+    pub fn fork(&self) -> Self {
+        Self {
+            buf: String::new(),
+            last_has_dot: self.last_has_dot,
+        }
+    }
+
+    pub fn append(&mut self, other: Self) {
+        if !other.buf.is_empty() {
+            self.buf.push_str(&other.buf);
+            self.last_has_dot = other.last_has_dot;
+        }
+    }
+}
+
+/// Parses a number with an optional unit, e.g. `10.5px`.
+pub(crate) fn parse_number_with_unit(value: &str) -> Option<(f64, &str)> {
+    let value = value.trim();
+    let end = value
+        .char_indices()
+        .find(|&(idx, c)| {
+            !(c.is_ascii_digit()
+                || c == '.'
+                || ((c == '-' || c == '+')
+                    && (idx == 0 || matches!(value.as_bytes()[idx - 1], b'e' | b'E')))
+                || ((c == 'e' || c == 'E')
+                    && value[idx + 1..]
+                        .trim_start_matches(['-', '+'])
+                        .starts_with(|c: char| c.is_ascii_digit())))
+        })
+        .map(|(idx, _)| idx)
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(end);
+    let number = number.parse::<f64>().ok()?;
+
+    if !number.is_finite() {
+        return None;
+    }
+
+    Some((number, unit))
+}
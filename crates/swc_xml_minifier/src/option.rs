@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use swc_config::regex::CachedRegex;
+#[cfg(feature = "default-css-minifier")]
+use swc_css_codegen::CodegenConfig as CssCodegenOptions;
+#[cfg(feature = "default-css-minifier")]
+use swc_css_minifier::options::MinifyOptions as CssMinifyOptions;
+#[cfg(feature = "default-css-minifier")]
+use swc_css_parser::parser::ParserConfig as CssParserOptions;
+
+#[cfg(feature = "default-css-minifier")]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+#[serde(untagged)]
+pub enum MinifyCssOption {
+    Bool(bool),
+    Options(Box<CssOptions>),
+}
+
+#[cfg(feature = "default-css-minifier")]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct CssOptions {
+    #[serde(default)]
+    pub parser: CssParserOptions,
+    #[serde(default)]
+    pub minifier: CssMinifyOptions,
+    #[serde(default)]
+    pub codegen: CssCodegenOptions,
+}
+
+/// Options of the minifier.
+///
+/// Optimizations specific to SVG are only applied to the content of `svg`
+/// elements, except the content of `foreignObject` elements.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct MinifyOptions {
+    #[serde(default = "true_by_default")]
+    pub remove_comments: bool,
+    /// Comments matching one of these regexes are kept by `removeComments`
+    #[serde(default = "default_preserve_comments")]
+    pub preserve_comments: Option<Vec<CachedRegex>>,
+    /// Remove the XML declaration, i.e. `<?xml version="1.0"?>`
+    #[serde(default = "true_by_default")]
+    pub remove_xml_declaration: bool,
+    /// Remove the document type declaration of SVG documents, unless it has an
+    /// internal subset (which may declare entities)
+    #[serde(default = "true_by_default")]
+    pub remove_doctype: bool,
+    /// Remove text consisting only of whitespace outside of the root element
+    /// and in SVG elements which don't render text (i.e. all except `text`,
+    /// `tspan`, `textPath`, `title`, `desc`, `style` and `script`)
+    #[serde(default = "true_by_default")]
+    pub remove_whitespaces: bool,
+    /// Remove the `metadata` elements
+    #[serde(default = "true_by_default")]
+    pub remove_metadata: bool,
+    /// Remove elements and attributes in namespaces of editors (Inkscape,
+    /// Sodipodi, Adobe Illustrator, Sketch, Figma and etc) and the declarations
+    /// of these namespaces
+    #[serde(default = "true_by_default")]
+    pub remove_editors_namespaces: bool,
+    /// Remove the `xmlns:*` attributes of prefixes which are not used in the
+    /// document
+    #[serde(default = "true_by_default")]
+    pub remove_unused_namespaces: bool,
+    /// Remove attributes which are set to their default value. Inherited
+    /// attributes are only removed if they are not set on an ancestor and the
+    /// document doesn't contain `style` and `use` elements
+    #[serde(default = "true_by_default")]
+    pub remove_default_attributes: bool,
+    /// Move the attributes of a `g` element to its only child and replace `g`
+    /// elements without attributes with their children
+    #[serde(default = "true_by_default")]
+    pub collapse_groups: bool,
+    /// Shorten the `d` attribute of `path` elements, i.e. round numbers,
+    /// choose between absolute and relative commands, convert lines to
+    /// horizontal and vertical lines and remove redundant commands and
+    /// separators
+    #[serde(default = "true_by_default")]
+    pub convert_path_data: bool,
+    /// Round numbers and remove the `px` unit in attributes with numeric
+    /// values, i.e. `x`, `width`, `viewBox`, `points` and etc
+    #[serde(default = "true_by_default")]
+    pub cleanup_numeric_values: bool,
+    /// Number of digits after the decimal point used by `convertPathData` and
+    /// `cleanupNumericValues`
+    #[serde(default = "default_float_precision")]
+    pub float_precision: u8,
+    /// Minify the `style` elements and attributes
+    #[cfg(feature = "default-css-minifier")]
+    #[serde(default = "minify_css_by_default")]
+    pub minify_css: MinifyCssOption,
+}
+
+/// Implement default using serde.
+impl Default for MinifyOptions {
+    fn default() -> Self {
+        serde_json::from_value(serde_json::Value::Object(Default::default())).unwrap()
+    }
+}
+
+const fn true_by_default() -> bool {
+    true
+}
+
+const fn default_float_precision() -> u8 {
+    3
+}
+
+#[cfg(feature = "default-css-minifier")]
+const fn minify_css_by_default() -> MinifyCssOption {
+    MinifyCssOption::Bool(true)
+}
+
+fn default_preserve_comments() -> Option<Vec<CachedRegex>> {
+    Some(vec![
+        // License comments
+        CachedRegex::new("@preserve").unwrap(),
+        CachedRegex::new("@copyright").unwrap(),
+        CachedRegex::new("@lic").unwrap(),
+        // Allow to keep custom comments
+        CachedRegex::new("^!").unwrap(),
+    ])
+}
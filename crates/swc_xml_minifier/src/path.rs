@@ -0,0 +1,417 @@
+//! Minification of path data, i.e. the `d` attribute of `path` elements.
+
+use crate::number::{format_number, round, NumberList};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    MoveTo,
+    LineTo,
+    HorizontalLineTo,
+    VerticalLineTo,
+    CurveTo,
+    SmoothCurveTo,
+    QuadraticCurveTo,
+    SmoothQuadraticCurveTo,
+    Arc,
+    ClosePath,
+}
+
+impl Command {
+    fn from_char(c: u8) -> Option<(Self, bool)> {
+        let command = match c.to_ascii_uppercase() {
+            b'M' => Command::MoveTo,
+            b'L' => Command::LineTo,
+            b'H' => Command::HorizontalLineTo,
+            b'V' => Command::VerticalLineTo,
+            b'C' => Command::CurveTo,
+            b'S' => Command::SmoothCurveTo,
+            b'Q' => Command::QuadraticCurveTo,
+            b'T' => Command::SmoothQuadraticCurveTo,
+            b'A' => Command::Arc,
+            b'Z' => Command::ClosePath,
+            _ => return None,
+        };
+
+        Some((command, c.is_ascii_lowercase()))
+    }
+
+    fn letter(self, relative: bool) -> char {
+        let letter = match self {
+            Command::MoveTo => 'M',
+            Command::LineTo => 'L',
+            Command::HorizontalLineTo => 'H',
+            Command::VerticalLineTo => 'V',
+            Command::CurveTo => 'C',
+            Command::SmoothCurveTo => 'S',
+            Command::QuadraticCurveTo => 'Q',
+            Command::SmoothQuadraticCurveTo => 'T',
+            Command::Arc => 'A',
+            Command::ClosePath => 'Z',
+        };
+
+        if relative {
+            letter.to_ascii_lowercase()
+        } else {
+            letter
+        }
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Command::MoveTo | Command::LineTo | Command::SmoothQuadraticCurveTo => 2,
+            Command::HorizontalLineTo | Command::VerticalLineTo => 1,
+            Command::CurveTo => 6,
+            Command::SmoothCurveTo | Command::QuadraticCurveTo => 4,
+            Command::Arc => 7,
+            Command::ClosePath => 0,
+        }
+    }
+
+    /// Returns whether the argument at `idx` is an x coordinate (`Some(true)`),
+    /// an y coordinate (`Some(false)`) or not a coordinate (`None`).
+    fn coordinate(self, idx: usize) -> Option<bool> {
+        match self {
+            Command::HorizontalLineTo => Some(true),
+            Command::VerticalLineTo => Some(false),
+            Command::Arc => match idx {
+                5 => Some(true),
+                6 => Some(false),
+                _ => None,
+            },
+            _ => Some(idx % 2 == 0),
+        }
+    }
+}
+
+/// A segment of a path, with absolute coordinates.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    command: Command,
+    args: [f64; 7],
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r' | b'\x0C')) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_separator(&mut self) {
+        self.skip_whitespaces();
+
+        if self.peek() == Some(b',') {
+            self.pos += 1;
+            self.skip_whitespaces();
+        }
+    }
+
+    fn is_number_start(&self) -> bool {
+        matches!(self.peek(), Some(b'0'..=b'9' | b'.' | b'-' | b'+'))
+    }
+
+    fn parse_number(&mut self) -> Option<f64> {
+        let start = self.pos;
+
+        if matches!(self.peek(), Some(b'-' | b'+')) {
+            self.pos += 1;
+        }
+
+        let mut has_digits = false;
+
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+            has_digits = true;
+        }
+
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+
+            while matches!(self.peek(), Some(b'0'..=b'9')) {
+                self.pos += 1;
+                has_digits = true;
+            }
+        }
+
+        if !has_digits {
+            return None;
+        }
+
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let mantissa_end = self.pos;
+
+            self.pos += 1;
+
+            if matches!(self.peek(), Some(b'-' | b'+')) {
+                self.pos += 1;
+            }
+
+            if matches!(self.peek(), Some(b'0'..=b'9')) {
+                while matches!(self.peek(), Some(b'0'..=b'9')) {
+                    self.pos += 1;
+                }
+            } else {
+                self.pos = mantissa_end;
+            }
+        }
+
+        let number = std::str::from_utf8(&self.input[start..self.pos])
+            .ok()?
+            .parse::<f64>()
+            .ok()?;
+
+        number.is_finite().then_some(number)
+    }
+
+    fn parse_flag(&mut self) -> Option<f64> {
+        match self.peek()? {
+            b'0' => {
+                self.pos += 1;
+                Some(0.0)
+            }
+            b'1' => {
+                self.pos += 1;
+                Some(1.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses path data into segments with absolute coordinates. Returns
+    /// `None` on errors, because the path data up to the error would have to
+    /// be preserved exactly.
+    fn parse(mut self) -> Option<Vec<Segment>> {
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut current = (0.0, 0.0);
+        let mut start = (0.0, 0.0);
+
+        loop {
+            self.skip_whitespaces();
+
+            let Some(c) = self.peek() else {
+                break;
+            };
+
+            self.pos += 1;
+
+            let (mut command, relative) = Command::from_char(c)?;
+
+            if segments.is_empty() && command != Command::MoveTo {
+                return None;
+            }
+
+            if command == Command::ClosePath {
+                segments.push(Segment {
+                    command,
+                    args: Default::default(),
+                });
+                current = start;
+
+                continue;
+            }
+
+            loop {
+                let mut args = [0.0; 7];
+
+                for (idx, arg) in args.iter_mut().enumerate().take(command.arity()) {
+                    if idx != 0 {
+                        self.skip_separator();
+                    } else {
+                        self.skip_whitespaces();
+                    }
+
+                    *arg = if command == Command::Arc && (idx == 3 || idx == 4) {
+                        self.parse_flag()?
+                    } else {
+                        self.parse_number()?
+                    };
+
+                    if relative {
+                        match command.coordinate(idx) {
+                            Some(true) => *arg += current.0,
+                            Some(false) => *arg += current.1,
+                            None => {}
+                        }
+                    }
+                }
+
+                current = match command {
+                    Command::HorizontalLineTo => (args[0], current.1),
+                    Command::VerticalLineTo => (current.0, args[0]),
+                    _ => {
+                        let arity = command.arity();
+
+                        (args[arity - 2], args[arity - 1])
+                    }
+                };
+
+                segments.push(Segment { command, args });
+
+                if command == Command::MoveTo {
+                    start = current;
+                    // Subsequent pairs of coordinates are implicit line commands
+                    command = Command::LineTo;
+                }
+
+                let pos = self.pos;
+
+                self.skip_separator();
+
+                if !self.is_number_start() {
+                    self.pos = pos;
+
+                    break;
+                }
+            }
+        }
+
+        Some(segments)
+    }
+}
+
+/// Minifies path data, rounding numbers to `precision` digits after the
+/// decimal point. Returns `None` if the path data is invalid.
+pub(crate) fn minify_path_data(data: &str, precision: u8) -> Option<String> {
+    let segments = Parser {
+        input: data.as_bytes(),
+        pos: 0,
+    }
+    .parse()?;
+
+    if segments.is_empty() {
+        return None;
+    }
+
+    let mut output = NumberList::default();
+    // The command which is implied if the next segment doesn't have a command
+    let mut implicit_letter = None;
+    // The current point as interpreted from the output, which may differ
+    // from the original one because of rounding
+    let mut current = (0.0, 0.0);
+    let mut start = (0.0, 0.0);
+
+    for (idx, segment) in segments.iter().enumerate() {
+        if segment.command == Command::ClosePath {
+            output.push_command('z');
+            implicit_letter = None;
+            current = start;
+
+            continue;
+        }
+
+        // A relative `m` is absolute at the start of path data, so only try
+        // relative commands after it
+        let candidates = if idx == 0 {
+            &[false][..]
+        } else {
+            &[false, true][..]
+        };
+
+        let mut best: Option<(NumberList, char, (f64, f64))> = None;
+
+        for &relative in candidates {
+            let (letter, numbers, end) = to_output(segment, relative, current, precision);
+            let mut list = output.fork();
+
+            if implicit_letter != Some(letter) {
+                list.push_command(letter);
+            }
+
+            for number in &numbers {
+                list.push_number(number);
+            }
+
+            if best
+                .as_ref()
+                .map_or(true, |(best, ..)| list.buf.len() < best.buf.len())
+            {
+                best = Some((list, letter, end));
+            }
+        }
+
+        let (list, letter, end) = best.unwrap();
+
+        output.append(list);
+        implicit_letter = Some(match letter {
+            'M' => 'L',
+            'm' => 'l',
+            letter => letter,
+        });
+        current = end;
+
+        if segment.command == Command::MoveTo {
+            start = current;
+        }
+    }
+
+    Some(output.buf)
+}
+
+/// Returns the command letter, the formatted arguments and the new current
+/// point of `segment`, using absolute or relative coordinates.
+fn to_output(
+    segment: &Segment,
+    relative: bool,
+    current: (f64, f64),
+    precision: u8,
+) -> (char, Vec<String>, (f64, f64)) {
+    let mut command = segment.command;
+    let mut args = segment.args;
+
+    for (idx, arg) in args.iter_mut().enumerate().take(command.arity()) {
+        match command.coordinate(idx) {
+            Some(true) if relative => *arg = round(*arg - current.0, precision),
+            Some(false) if relative => *arg = round(*arg - current.1, precision),
+            _ if command == Command::Arc && (idx == 3 || idx == 4) => {}
+            _ => *arg = round(*arg, precision),
+        }
+    }
+
+    // Use shorter horizontal and vertical line commands
+    if command == Command::LineTo {
+        let (zero_x, zero_y) = if relative {
+            (args[0] == 0.0, args[1] == 0.0)
+        } else {
+            (args[0] == current.0, args[1] == current.1)
+        };
+
+        if zero_y {
+            command = Command::HorizontalLineTo;
+        } else if zero_x {
+            command = Command::VerticalLineTo;
+            args[0] = args[1];
+        }
+    }
+
+    let arity = command.arity();
+    let end = match command {
+        Command::HorizontalLineTo => (args[0], 0.0),
+        Command::VerticalLineTo => (0.0, args[0]),
+        _ => (args[arity - 2], args[arity - 1]),
+    };
+    let end = match command {
+        Command::HorizontalLineTo if !relative => (end.0, current.1),
+        Command::VerticalLineTo if !relative => (current.0, end.1),
+        _ if relative => (
+            round(current.0 + end.0, precision),
+            round(current.1 + end.1, precision),
+        ),
+        _ => end,
+    };
+
+    let numbers = args[..arity]
+        .iter()
+        .map(|&arg| format_number(arg, precision))
+        .collect();
+
+    (command.letter(relative), numbers, end)
+}
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 
+use swc_atoms::Atom;
 use swc_common::{
     errors::{DiagnosticBuilder, Handler},
     Span,
@@ -108,6 +109,17 @@ impl Error {
             ErrorKind::UnescapedCharacterInAttributeValue(c) => {
                 format!("Unescaped \"{c}\" not allowed in attribute values").into()
             }
+            ErrorKind::UndeclaredEntity(name) => format!("Undeclared entity \"{name}\"").into(),
+            ErrorKind::RecursiveEntityReference(name) => {
+                format!("Recursive reference to entity \"{name}\"").into()
+            }
+            ErrorKind::EntityReferenceTooDeep => "Entity references are nested too deeply".into(),
+            ErrorKind::EntityExpansionLimitExceeded => {
+                "Expanded entities exceed the maximum length".into()
+            }
+            ErrorKind::MarkupInEntityReplacementText => {
+                "Markup in the replacement text of entities is not supported".into()
+            }
 
             // Parser errors
             ErrorKind::UnexpectedTokenInStartPhase => "Unexpected token in start phase".into(),
@@ -119,6 +131,11 @@ impl Error {
             ErrorKind::UnexpectedCharacter => {
                 "Unexpected character, only whitespace character allowed".into()
             }
+
+            // Namespace errors
+            ErrorKind::UnboundNamespacePrefix(prefix) => {
+                format!("Namespace prefix \"{prefix}\" is not bound").into()
+            }
         }
     }
 
@@ -178,6 +195,11 @@ pub enum ErrorKind {
     NoTargetNameInProcessingInstruction,
     MissingWhitespaceBeforeQuestionInProcessingInstruction,
     UnescapedCharacterInAttributeValue(char),
+    UndeclaredEntity(Atom),
+    RecursiveEntityReference(Atom),
+    EntityReferenceTooDeep,
+    EntityExpansionLimitExceeded,
+    MarkupInEntityReplacementText,
 
     // Parser errors
     UnexpectedTokenInStartPhase,
@@ -187,4 +209,7 @@ pub enum ErrorKind {
     UnexpectedEofInMainPhase,
     OpeningAndEndingTagMismatch,
     UnexpectedCharacter,
+
+    // Namespace errors
+    UnboundNamespacePrefix(Atom),
 }
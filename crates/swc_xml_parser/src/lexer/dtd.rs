@@ -0,0 +1,215 @@
+//! Parsing of the entity declarations in the internal subset of a document
+//! type declaration.
+
+use rustc_hash::FxHashMap;
+
+use crate::error::ErrorKind;
+
+/// Returns the general internal entities declared in `internal_subset`,
+/// mapped to their literal values with character references replaced.
+///
+/// Parameter entities and external entities are ignored. If an entity is
+/// declared multiple times, the first declaration is used.
+pub(super) fn parse_entity_declarations(internal_subset: &str) -> FxHashMap<String, String> {
+    let mut entities = FxHashMap::default();
+    let mut rest = internal_subset;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |idx| &after[idx + 3..]);
+        } else if let Some(after) = rest.strip_prefix("<?") {
+            rest = after.find("?>").map_or("", |idx| &after[idx + 2..]);
+        } else if let Some(after) = rest.strip_prefix("<!ENTITY") {
+            let (declaration, after) = split_declaration(after);
+
+            if let Some((name, value)) = parse_entity_declaration(declaration) {
+                entities
+                    .entry(name.to_string())
+                    .or_insert_with(|| replace_character_references(value));
+            }
+
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            rest = split_declaration(after).1;
+        } else {
+            let mut chars = rest.chars();
+
+            chars.next();
+            rest = chars.as_str();
+        }
+    }
+
+    entities
+}
+
+/// Splits the content of a markup declaration (until `>`, ignoring `>` in
+/// quoted strings) from the rest of the internal subset.
+fn split_declaration(input: &str) -> (&str, &str) {
+    let mut quote = None;
+
+    for (idx, c) in input.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('>', None) => return (&input[..idx], &input[idx + 1..]),
+            _ => {}
+        }
+    }
+
+    (input, "")
+}
+
+/// Parses the content of `<!ENTITY ... >`, returning `None` for parameter
+/// entities and external entities.
+fn parse_entity_declaration(declaration: &str) -> Option<(&str, &str)> {
+    let declaration = declaration.trim_start();
+
+    if declaration.starts_with('%') {
+        return None;
+    }
+
+    let name_end = declaration
+        .find(|c: char| c.is_ascii_whitespace())
+        .unwrap_or(declaration.len());
+    let (name, rest) = declaration.split_at(name_end);
+    let rest = rest.trim_start();
+    let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let value = &rest[1..];
+    let value_end = value.find(quote)?;
+
+    if name.is_empty() {
+        return None;
+    }
+
+    Some((name, &value[..value_end]))
+}
+
+/// Replaces character references in the literal value of an entity, which
+/// are replaced when an entity is declared, unlike entity references.
+fn replace_character_references(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(idx) = rest.find("&#") {
+        result.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+
+        let parsed = rest[2..].find(';').and_then(|end| {
+            let reference = &rest[2..2 + end];
+            let code = match reference.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => reference.parse().ok()?,
+            };
+
+            Some((char::from_u32(code)?, 2 + end + 1))
+        });
+
+        match parsed {
+            Some((c, len)) => {
+                result.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+
+    result
+}
+
+/// Expands the entity `name` into `output`, including the references to
+/// other entities in its replacement text.
+///
+/// `budget` is the remaining number of bytes of replacement text which may be
+/// expanded, references to empty entities count as one byte.
+pub(super) fn expand_entity(
+    entities: &FxHashMap<String, String>,
+    name: &str,
+    max_depth: usize,
+    budget: &mut usize,
+    output: &mut String,
+) -> Result<(), ErrorKind> {
+    let mut stack = Vec::new();
+
+    expand(entities, name, max_depth, budget, &mut stack, output)
+}
+
+fn expand<'a>(
+    entities: &'a FxHashMap<String, String>,
+    name: &str,
+    max_depth: usize,
+    budget: &mut usize,
+    stack: &mut Vec<&'a str>,
+    output: &mut String,
+) -> Result<(), ErrorKind> {
+    let Some((name, value)) = entities.get_key_value(name) else {
+        return Err(ErrorKind::UndeclaredEntity(name.into()));
+    };
+
+    if stack.contains(&name.as_str()) {
+        return Err(ErrorKind::RecursiveEntityReference(name.as_str().into()));
+    }
+
+    if stack.len() >= max_depth {
+        return Err(ErrorKind::EntityReferenceTooDeep);
+    }
+
+    let cost = value.len().max(1);
+
+    if *budget < cost {
+        return Err(ErrorKind::EntityExpansionLimitExceeded);
+    }
+
+    *budget -= cost;
+    stack.push(name);
+
+    let mut rest = value.as_str();
+
+    while let Some(idx) = rest.find(['&', '<']) {
+        output.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+
+        if rest.starts_with('<') {
+            return Err(ErrorKind::MarkupInEntityReplacementText);
+        }
+
+        let Some(end) = rest.find(';') else {
+            return Err(ErrorKind::InvalidEntityCharacter);
+        };
+        let reference = &rest[1..end];
+
+        rest = &rest[end + 1..];
+
+        if let Some(code) = reference.strip_prefix('#') {
+            let code = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => code.parse().ok(),
+            };
+
+            match code.and_then(char::from_u32) {
+                Some(c) => output.push(c),
+                None => return Err(ErrorKind::InvalidEntityCharacter),
+            }
+
+            continue;
+        }
+
+        match reference {
+            "lt" => output.push('<'),
+            "gt" => output.push('>'),
+            "amp" => output.push('&'),
+            "apos" => output.push('\''),
+            "quot" => output.push('"'),
+            _ => expand(entities, reference, max_depth, budget, stack, output)?,
+        }
+    }
+
+    output.push_str(rest);
+    stack.pop();
+
+    Ok(())
+}
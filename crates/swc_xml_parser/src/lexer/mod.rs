@@ -1,15 +1,17 @@
 use std::{collections::VecDeque, mem::take};
 
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use swc_atoms::Atom;
 use swc_common::{input::Input, BytePos, Span};
 use swc_xml_ast::{AttributeToken, Token, TokenAndSpan};
 
 use crate::{
     error::{Error, ErrorKind},
-    parser::input::ParserInput,
+    parser::{input::ParserInput, ParserConfig},
 };
 
+mod dtd;
+
 #[derive(Debug, Clone)]
 pub enum State {
     Data,
@@ -74,6 +76,7 @@ struct Doctype {
     name: Option<String>,
     public_id: Option<String>,
     system_id: Option<String>,
+    internal_subset: Option<String>,
 }
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
@@ -124,6 +127,7 @@ where
     I: Input<'a>,
 {
     input: I,
+    config: ParserConfig,
     cur: Option<char>,
     cur_pos: BytePos,
     last_token_pos: BytePos,
@@ -140,6 +144,11 @@ where
     current_tag_token: Option<Tag>,
     current_cdata_token: Option<Cdata>,
     attribute_start_position: Option<BytePos>,
+    /// Entities declared in the internal subset of the document type
+    /// declaration
+    entities: FxHashMap<String, String>,
+    /// Remaining number of bytes which may be expanded from entities
+    entity_expansion_budget: usize,
     phantom: std::marker::PhantomData<&'a ()>,
 }
 
@@ -147,11 +156,12 @@ impl<'a, I> Lexer<'a, I>
 where
     I: Input<'a>,
 {
-    pub fn new(input: I) -> Self {
+    pub fn new(input: I, config: ParserConfig) -> Self {
         let start_pos = input.last_pos();
 
         let mut lexer = Lexer {
             input,
+            config,
             cur: None,
             cur_pos: start_pos,
             last_token_pos: start_pos,
@@ -168,6 +178,8 @@ where
             current_tag_token: None,
             current_cdata_token: None,
             attribute_start_position: None,
+            entities: Default::default(),
+            entity_expansion_budget: config.max_entity_expansion,
             phantom: std::marker::PhantomData,
         };
 
@@ -547,6 +559,69 @@ where
         None
     }
 
+    /// Attempts to consume a reference to an entity declared in the internal
+    /// subset, returns its expanded replacement text and the raw reference.
+    ///
+    /// If the expansion fails, an error is emitted and the replacement text is
+    /// empty.
+    fn consume_entity_reference(&mut self) -> Option<(String, String)> {
+        if self.entities.is_empty() {
+            return None;
+        }
+
+        let cur_pos = self.input.cur_pos();
+        let mut name = String::with_capacity(16);
+
+        loop {
+            match self.consume_next_char() {
+                Some(';') if !name.is_empty() => {
+                    break;
+                }
+                Some(c) if name.is_empty() && is_name_start_char(c) => {
+                    name.push(c);
+                }
+                Some(c) if !name.is_empty() && is_name_char(c) => {
+                    name.push(c);
+                }
+                _ => {
+                    name.clear();
+
+                    break;
+                }
+            }
+        }
+
+        if !self.entities.contains_key(&name) {
+            self.cur_pos = cur_pos;
+            unsafe {
+                // Safety: We got cur_post from self.input
+                self.input.reset_to(cur_pos);
+            }
+
+            return None;
+        }
+
+        let mut replacement = String::new();
+
+        if let Err(kind) = dtd::expand_entity(
+            &self.entities,
+            &name,
+            self.config.max_entity_depth,
+            &mut self.entity_expansion_budget,
+            &mut replacement,
+        ) {
+            // Include the `&` character in the span
+            self.errors.push(Error::new(
+                Span::new(cur_pos - BytePos(1), self.input.cur_pos()),
+                kind,
+            ));
+
+            replacement.clear();
+        }
+
+        Some((replacement, format!("&{name};")))
+    }
+
     fn create_doctype_token(&mut self, name_c: Option<char>) {
         let mut new_name = None;
 
@@ -561,6 +636,7 @@ where
             name: new_name,
             public_id: None,
             system_id: None,
+            internal_subset: None,
         });
     }
 
@@ -643,6 +719,26 @@ where
         }
     }
 
+    fn set_doctype_token_internal_subset(&mut self) {
+        if let Some(Doctype {
+            internal_subset, ..
+        }) = &mut self.current_doctype_token
+        {
+            *internal_subset = Some(String::new());
+        }
+    }
+
+    fn append_to_doctype_token_internal_subset(&mut self, c: char) {
+        if let Some(Doctype {
+            internal_subset: Some(internal_subset),
+            ..
+        }) = &mut self.current_doctype_token
+        {
+            // Line breaks are normalized to U+000A LINE FEED (LF)
+            internal_subset.push(if c == '\r' { '\n' } else { c });
+        }
+    }
+
     fn emit_doctype_token(&mut self) {
         let current_doctype_token = self.current_doctype_token.take().unwrap();
 
@@ -653,6 +749,10 @@ where
             }
         };
 
+        if let Some(internal_subset) = &current_doctype_token.internal_subset {
+            self.entities = dtd::parse_entity_declarations(internal_subset);
+        }
+
         let token = Token::Doctype {
             name: current_doctype_token.name.map(Atom::from),
             public_id: current_doctype_token.public_id.map(Atom::from),
@@ -1093,9 +1193,14 @@ where
                 // Otherwise, emit character tokens that were returned.
                 self.state = State::Data;
 
-                let character_reference = self.consume_character_reference();
+                if let Some((replacement, raw)) = self.consume_entity_reference() {
+                    // The raw reference is attached to the first character
+                    let mut raw = Some(raw);
 
-                if let Some((c, raw)) = character_reference {
+                    for c in replacement.chars() {
+                        self.emit_character_token_with_entity(c, &raw.take().unwrap_or_default());
+                    }
+                } else if let Some((c, raw)) = self.consume_character_reference() {
                     self.emit_character_token_with_entity(c, &raw);
                 } else {
                     self.emit_character_token(('&', '&'));
@@ -2246,9 +2351,18 @@ where
                 //
                 // Finally, switch back to attribute value state that switched to this state.
 
-                let character_reference = self.consume_character_reference();
+                if let Some((replacement, raw)) = self.consume_entity_reference() {
+                    let mut raw = Some(raw);
+
+                    for c in replacement.chars() {
+                        self.append_to_attribute_with_entity(Some((Some(c), raw.as_deref())));
+                        raw = None;
+                    }
 
-                if let Some((c, raw)) = character_reference {
+                    if let Some(raw) = raw {
+                        self.append_to_attribute_with_entity(Some((None, Some(&raw))));
+                    }
+                } else if let Some((c, raw)) = self.consume_character_reference() {
                     self.append_to_attribute_with_entity(Some((Some(c), Some(&raw))));
                 } else {
                     self.append_to_attribute(None, Some((false, Some('&'), Some('&'))));
@@ -2435,6 +2549,7 @@ where
                     // Switch to the doctype internal subset state.
                     Some(c @ '[') => {
                         self.append_raw_to_doctype_token(c);
+                        self.set_doctype_token_internal_subset();
                         self.state = State::DoctypeTypeInternalSubSet;
                     }
                     // EOF
@@ -3024,6 +3139,7 @@ where
                     // Switch to the doctype internal subset state.
                     Some(c @ '[') => {
                         self.append_raw_to_doctype_token(c);
+                        self.set_doctype_token_internal_subset();
                         self.state = State::DoctypeTypeInternalSubSet;
                     }
                     // EOF
@@ -3068,6 +3184,7 @@ where
                     Some(c) => {
                         // TODO improve parse legacy declarations
                         self.validate_input_stream_character(c);
+                        self.append_to_doctype_token_internal_subset(c);
                         self.append_raw_to_doctype_token(c);
                     }
                 }
@@ -16,7 +16,9 @@ use crate::{
 
 pub mod error;
 pub mod lexer;
+pub mod namespace;
 pub mod parser;
+pub mod selector;
 
 /// Parse a given file as `Document`.
 ///
@@ -27,7 +29,7 @@ pub fn parse_file_as_document(
     config: ParserConfig,
     errors: &mut Vec<Error>,
 ) -> PResult<Document> {
-    let lexer = Lexer::new(StringInput::from(fm));
+    let lexer = Lexer::new(StringInput::from(fm), config);
     let mut parser = Parser::new(lexer, config);
     let result = parser.parse_document();
 
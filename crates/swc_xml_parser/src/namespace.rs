@@ -0,0 +1,134 @@
+//! Resolution of [namespaces](https://www.w3.org/TR/xml-names/).
+
+use swc_atoms::{atom, Atom};
+use swc_xml_ast::{Child, Document, Element};
+
+use crate::error::{Error, ErrorKind};
+
+/// The namespace bound to the `xml` prefix.
+pub const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// The namespace of the `xmlns` attributes, which declare namespaces.
+pub const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
+
+/// Sets the `namespace` of every element and attribute of `document` to the
+/// URI of its namespace.
+///
+/// The default namespace only applies to elements, so attributes without a
+/// prefix don't have a namespace. Names using a prefix which is not bound
+/// don't have a namespace either, and an error is appended to `errors`.
+pub fn resolve_namespaces(document: &mut Document, errors: &mut Vec<Error>) {
+    let mut resolver = NamespaceResolver {
+        bindings: Vec::new(),
+        errors,
+    };
+
+    resolver.resolve_children(&mut document.children);
+}
+
+/// Splits a qualified name into its prefix and its local name.
+pub fn split_qualified_name(name: &str) -> (Option<&str>, &str) {
+    match name.split_once(':') {
+        Some((prefix, local_name)) if !prefix.is_empty() && !local_name.is_empty() => {
+            (Some(prefix), local_name)
+        }
+        _ => (None, name),
+    }
+}
+
+struct NamespaceResolver<'a> {
+    /// Namespaces declared by the ancestors of the current element, the empty
+    /// prefix is used for the default namespace and `None` for undeclared
+    /// namespaces (i.e. `xmlns=""`)
+    bindings: Vec<(Atom, Option<Atom>)>,
+    errors: &'a mut Vec<Error>,
+}
+
+impl NamespaceResolver<'_> {
+    fn lookup(&self, prefix: &str) -> Option<Atom> {
+        match prefix {
+            "xml" => Some(Atom::from(XML_NAMESPACE)),
+            "xmlns" => Some(Atom::from(XMLNS_NAMESPACE)),
+            _ => self
+                .bindings
+                .iter()
+                .rev()
+                .find(|(bound_prefix, _)| bound_prefix == prefix)
+                .and_then(|(_, namespace)| namespace.clone()),
+        }
+    }
+
+    fn resolve_children(&mut self, children: &mut [Child]) {
+        for child in children {
+            if let Child::Element(element) = child {
+                self.resolve_element(element);
+            }
+        }
+    }
+
+    fn resolve_element(&mut self, element: &mut Element) {
+        let len = self.bindings.len();
+
+        for attribute in &element.attributes {
+            let prefix = if &*attribute.name == "xmlns" {
+                atom!("")
+            } else if let Some(prefix) = attribute.name.strip_prefix("xmlns:") {
+                Atom::from(prefix)
+            } else {
+                continue;
+            };
+            let namespace = attribute
+                .value
+                .clone()
+                .filter(|namespace| !namespace.is_empty());
+
+            self.bindings.push((prefix, namespace));
+        }
+
+        element.namespace = match split_qualified_name(&element.tag_name) {
+            (Some(prefix), _) => {
+                let namespace = self.lookup(prefix);
+
+                if namespace.is_none() {
+                    self.errors.push(Error::new(
+                        element.span,
+                        ErrorKind::UnboundNamespacePrefix(prefix.into()),
+                    ));
+                }
+
+                namespace
+            }
+            (None, _) => self.lookup(""),
+        };
+
+        for attribute in &mut element.attributes {
+            attribute.namespace = match split_qualified_name(&attribute.name) {
+                _ if &*attribute.name == "xmlns" => Some(Atom::from(XMLNS_NAMESPACE)),
+                (Some(prefix), _) => {
+                    let namespace = self.lookup(prefix);
+
+                    if namespace.is_none() {
+                        // The spans of quoted attributes are not tracked yet
+                        let span = if attribute.span.is_dummy() {
+                            element.span
+                        } else {
+                            attribute.span
+                        };
+
+                        self.errors.push(Error::new(
+                            span,
+                            ErrorKind::UnboundNamespacePrefix(prefix.into()),
+                        ));
+                    }
+
+                    namespace
+                }
+                (None, _) => None,
+            };
+        }
+
+        self.resolve_children(&mut element.children);
+
+        self.bindings.truncate(len);
+    }
+}
@@ -16,8 +16,32 @@ mod open_elements_stack;
 
 pub type PResult<T> = Result<T, Error>;
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct ParserConfig {}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ParserConfig {
+    /// Maximum nesting of references in the replacement text of entities
+    /// declared in the internal subset of the document type declaration.
+    ///
+    /// Defaults to `16`.
+    pub max_entity_depth: usize,
+
+    /// Maximum total length (in bytes) of the replacement text of entities
+    /// declared in the internal subset of the document type declaration,
+    /// including nested references, expanded in the whole document. This
+    /// protects against exponential expansion, i.e. the "billion laughs"
+    /// attack.
+    ///
+    /// Defaults to `1_000_000`.
+    pub max_entity_expansion: usize,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        ParserConfig {
+            max_entity_depth: 16,
+            max_entity_expansion: 1_000_000,
+        }
+    }
+}
 
 #[derive(Debug, Default)]
 pub enum Phase {
@@ -154,6 +178,7 @@ where
 
                 Child::Element(Element {
                     span,
+                    namespace: None,
                     tag_name,
                     attributes,
                     children: new_children,
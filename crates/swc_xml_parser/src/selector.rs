@@ -0,0 +1,413 @@
+//! A small subset of [XPath](https://www.w3.org/TR/xpath-10/) to select
+//! elements of a document.
+//!
+//! A selector is a path of steps separated by `/` (children) or `//`
+//! (descendants). A step is a name test, i.e. `name`, `prefix:name`,
+//! `prefix:*` or `*`, followed by any number of predicates:
+//!
+//! - `[@name]` selects elements with the attribute `name`
+//! - `[@name='value']` selects elements whose attribute `name` is `value`
+//! - `[n]` selects the `n`-th element (starting at 1) matched by the step among
+//!   the children of the same parent
+//!
+//! Names without a prefix and names with a prefix which is not bound with
+//! [Selector::namespace] are compared with the qualified names of the document.
+//! Names with a bound prefix are compared with the local names and the
+//! namespaces of the document, which requires running
+//! [resolve_namespaces](crate::namespace::resolve_namespaces) first.
+//!
+//! ```
+//! use swc_xml_parser::selector::Selector;
+//!
+//! let selector = Selector::parse("//s:url[@priority]/s:loc")
+//!     .unwrap()
+//!     .namespace("s", "http://www.sitemaps.org/schemas/sitemap/0.9");
+//! ```
+
+use std::fmt;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_atoms::Atom;
+use swc_xml_ast::{Attribute, Child, Document, Element};
+
+use crate::namespace::split_qualified_name;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    steps: Vec<Step>,
+    namespaces: FxHashMap<Atom, Atom>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    /// Whether the step selects descendants (`//`) or children (`/`)
+    descendants: bool,
+    name: NameTest,
+    predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NameTest {
+    Any,
+    Name {
+        prefix: Option<Atom>,
+        /// `None` for `prefix:*`
+        local_name: Option<Atom>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Predicate {
+    HasAttribute(NameTest),
+    AttributeEquals(NameTest, Atom),
+    Position(usize),
+}
+
+/// An error in the syntax of a selector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectorError {
+    UnexpectedEnd,
+    /// An unexpected character and its byte offset in the selector
+    UnexpectedCharacter(char, usize),
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectorError::UnexpectedEnd => write!(f, "Unexpected end of selector"),
+            SelectorError::UnexpectedCharacter(c, offset) => {
+                write!(
+                    f,
+                    "Unexpected character \"{c}\" at offset {offset} of selector"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+impl Selector {
+    pub fn parse(selector: &str) -> Result<Self, SelectorError> {
+        let mut parser = SelectorParser {
+            input: selector,
+            pos: 0,
+        };
+        let mut steps = Vec::new();
+
+        loop {
+            // The first step may omit `/`
+            let descendants = if parser.eat("//") {
+                true
+            } else if parser.eat("/") || steps.is_empty() {
+                false
+            } else {
+                return Err(parser.unexpected());
+            };
+
+            steps.push(parser.parse_step(descendants)?);
+
+            if parser.is_eof() {
+                break;
+            }
+        }
+
+        Ok(Selector {
+            steps,
+            namespaces: Default::default(),
+        })
+    }
+
+    /// Binds `prefix` to the namespace `uri` in names of the selector.
+    pub fn namespace(mut self, prefix: &str, uri: &str) -> Self {
+        self.namespaces.insert(prefix.into(), uri.into());
+
+        self
+    }
+
+    /// Returns the elements of `document` matched by the selector, in document
+    /// order.
+    pub fn select<'a>(&self, document: &'a Document) -> Vec<&'a Element> {
+        let mut contexts = vec![&document.children[..]];
+        let mut selected = Vec::new();
+
+        for step in &self.steps {
+            let mut matched = FxHashSet::default();
+
+            for children in contexts {
+                self.match_step(step, children, &mut matched);
+            }
+
+            selected.clear();
+            collect_in_document_order(&document.children, &matched, &mut selected);
+            contexts = selected
+                .iter()
+                .map(|element| &element.children[..])
+                .collect();
+        }
+
+        selected
+    }
+
+    /// Returns the first element of `document` matched by the selector.
+    pub fn select_first<'a>(&self, document: &'a Document) -> Option<&'a Element> {
+        self.select(document).into_iter().next()
+    }
+
+    fn match_step(&self, step: &Step, children: &[Child], matched: &mut FxHashSet<*const Element>) {
+        let mut elements: Vec<&Element> = children
+            .iter()
+            .filter_map(|child| match child {
+                Child::Element(element) => Some(element),
+                _ => None,
+            })
+            .filter(|element| {
+                self.match_name(&step.name, &element.tag_name, element.namespace.as_ref())
+            })
+            .collect();
+
+        for predicate in &step.predicates {
+            match predicate {
+                Predicate::Position(position) => {
+                    elements = elements.into_iter().skip(position - 1).take(1).collect();
+                }
+                Predicate::HasAttribute(name) => {
+                    elements.retain(|element| self.find_attribute(element, name).is_some());
+                }
+                Predicate::AttributeEquals(name, value) => {
+                    elements.retain(|element| {
+                        self.find_attribute(element, name)
+                            .and_then(|attribute| attribute.value.as_ref())
+                            == Some(value)
+                    });
+                }
+            }
+        }
+
+        matched.extend(
+            elements
+                .into_iter()
+                .map(|element| element as *const Element),
+        );
+
+        if step.descendants {
+            for child in children {
+                if let Child::Element(element) = child {
+                    self.match_step(step, &element.children, matched);
+                }
+            }
+        }
+    }
+
+    fn find_attribute<'a>(&self, element: &'a Element, name: &NameTest) -> Option<&'a Attribute> {
+        element
+            .attributes
+            .iter()
+            .find(|attribute| self.match_name(name, &attribute.name, attribute.namespace.as_ref()))
+    }
+
+    fn match_name(&self, test: &NameTest, name: &str, namespace: Option<&Atom>) -> bool {
+        let NameTest::Name { prefix, local_name } = test else {
+            return true;
+        };
+        let bound_namespace = prefix
+            .as_ref()
+            .and_then(|prefix| self.namespaces.get(prefix));
+
+        match bound_namespace {
+            Some(bound_namespace) => {
+                namespace == Some(bound_namespace)
+                    && local_name.as_ref().map_or(true, |local_name| {
+                        split_qualified_name(name).1 == local_name
+                    })
+            }
+            None => match (prefix, local_name) {
+                (Some(prefix), Some(local_name)) => {
+                    split_qualified_name(name) == (Some(prefix), local_name)
+                }
+                (Some(prefix), None) => split_qualified_name(name).0 == Some(prefix),
+                (None, Some(local_name)) => name == local_name,
+                (None, None) => true,
+            },
+        }
+    }
+}
+
+fn collect_in_document_order<'a>(
+    children: &'a [Child],
+    matched: &FxHashSet<*const Element>,
+    selected: &mut Vec<&'a Element>,
+) {
+    if matched.len() == selected.len() {
+        return;
+    }
+
+    for child in children {
+        if let Child::Element(element) = child {
+            if matched.contains(&(element as *const Element)) {
+                selected.push(element);
+            }
+
+            collect_in_document_order(&element.children, matched, selected);
+        }
+    }
+}
+
+struct SelectorParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl SelectorParser<'_> {
+    fn is_eof(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.input[self.pos..].starts_with(s) {
+            self.pos += s.len();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), SelectorError> {
+        if self.eat(s) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn unexpected(&self) -> SelectorError {
+        match self.peek() {
+            Some(c) => SelectorError::UnexpectedCharacter(c, self.pos),
+            None => SelectorError::UnexpectedEnd,
+        }
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_step(&mut self, descendants: bool) -> Result<Step, SelectorError> {
+        let name = self.parse_name_test()?;
+        let mut predicates = Vec::new();
+
+        while self.eat("[") {
+            self.skip_whitespaces();
+
+            let predicate = if self.eat("@") {
+                let name = self.parse_name_test()?;
+
+                self.skip_whitespaces();
+
+                if self.eat("=") {
+                    self.skip_whitespaces();
+
+                    Predicate::AttributeEquals(name, self.parse_string()?)
+                } else {
+                    Predicate::HasAttribute(name)
+                }
+            } else {
+                Predicate::Position(self.parse_position()?)
+            };
+
+            self.skip_whitespaces();
+            self.expect("]")?;
+            predicates.push(predicate);
+        }
+
+        Ok(Step {
+            descendants,
+            name,
+            predicates,
+        })
+    }
+
+    fn parse_name_test(&mut self) -> Result<NameTest, SelectorError> {
+        if self.eat("*") {
+            return Ok(NameTest::Any);
+        }
+
+        let name = self.parse_name()?;
+
+        if !self.eat(":") {
+            return Ok(NameTest::Name {
+                prefix: None,
+                local_name: Some(name),
+            });
+        }
+
+        let local_name = if self.eat("*") {
+            None
+        } else {
+            Some(self.parse_name()?)
+        };
+
+        Ok(NameTest::Name {
+            prefix: Some(name),
+            local_name,
+        })
+    }
+
+    fn parse_name(&mut self) -> Result<Atom, SelectorError> {
+        let start = self.pos;
+
+        while self.peek().is_some_and(|c| {
+            !matches!(c, '/' | '[' | ']' | '@' | '=' | ':' | '*' | '\'' | '"')
+                && !c.is_ascii_whitespace()
+        }) {
+            self.pos += self.peek().unwrap().len_utf8();
+        }
+
+        if start == self.pos {
+            return Err(self.unexpected());
+        }
+
+        Ok(self.input[start..self.pos].into())
+    }
+
+    fn parse_string(&mut self) -> Result<Atom, SelectorError> {
+        let quote = match self.peek() {
+            Some(quote @ ('\'' | '"')) => quote,
+            _ => return Err(self.unexpected()),
+        };
+
+        self.pos += 1;
+
+        let Some(len) = self.input[self.pos..].find(quote) else {
+            return Err(SelectorError::UnexpectedEnd);
+        };
+        let value = &self.input[self.pos..self.pos + len];
+
+        self.pos += len + 1;
+
+        Ok(value.into())
+    }
+
+    fn parse_position(&mut self) -> Result<usize, SelectorError> {
+        let start = self.pos;
+
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+
+        match self.input[start..self.pos].parse() {
+            Ok(position) if position > 0 => Ok(position),
+            _ => {
+                self.pos = start;
+
+                Err(self.unexpected())
+            }
+        }
+    }
+}
@@ -10,6 +10,8 @@ use swc_common::{errors::Handler, input::SourceFileInput, Spanned};
 use swc_xml_ast::*;
 use swc_xml_parser::{
     lexer::Lexer,
+    namespace::resolve_namespaces,
+    parse_file_as_document,
     parser::{PResult, Parser, ParserConfig},
 };
 use swc_xml_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
@@ -19,7 +21,7 @@ fn document_test(input: PathBuf, config: ParserConfig) {
     testing::run_test2(false, |cm, handler| {
         let json_path = input.parent().unwrap().join("output.json");
         let fm = cm.load_file(&input).unwrap();
-        let lexer = Lexer::new(SourceFileInput::from(&*fm));
+        let lexer = Lexer::new(SourceFileInput::from(&*fm), config);
         let mut parser = Parser::new(lexer, config);
         let document: PResult<Document> = parser.parse_document();
         let errors = parser.take_errors();
@@ -67,7 +69,7 @@ fn document_recovery_test(input: PathBuf, config: ParserConfig) {
 
         let json_path = input.parent().unwrap().join("output.json");
         let fm = cm.load_file(&input).unwrap();
-        let lexer = Lexer::new(SourceFileInput::from(&*fm));
+        let lexer = Lexer::new(SourceFileInput::from(&*fm), config);
         let mut parser = Parser::new(lexer, config);
         let document: PResult<Document> = parser.parse_document();
         let errors = parser.take_errors();
@@ -112,6 +114,42 @@ fn document_recovery_test(input: PathBuf, config: ParserConfig) {
     stderr.compare_to_file(stderr_path).unwrap();
 }
 
+fn document_namespace_test(input: PathBuf, config: ParserConfig) {
+    let dir = input.parent().unwrap().to_path_buf();
+
+    let result = testing::run_test2(false, |cm, handler| {
+        let fm = cm.load_file(&input).unwrap();
+        let mut errors = Vec::new();
+        let mut document = parse_file_as_document(&fm, config, &mut errors).unwrap();
+
+        resolve_namespaces(&mut document, &mut errors);
+
+        let actual_json = serde_json::to_string_pretty(&document)
+            .map(NormalizedOutput::from)
+            .expect("failed to serialize document");
+
+        actual_json
+            .compare_to_file(dir.join("output.json"))
+            .unwrap();
+
+        for err in &errors {
+            err.to_diagnostics(&handler).emit();
+        }
+
+        if !errors.is_empty() {
+            return Err(());
+        }
+
+        Ok(())
+    });
+
+    if let Err(stderr) = result {
+        stderr
+            .compare_to_file(dir.join("output.swc-stderr"))
+            .unwrap();
+    }
+}
+
 fn document_span_visualizer(input: PathBuf, config: ParserConfig) {
     let dir = input.parent().unwrap().to_path_buf();
 
@@ -122,7 +160,7 @@ fn document_span_visualizer(input: PathBuf, config: ParserConfig) {
         }
 
         let fm = cm.load_file(&input).unwrap();
-        let lexer = Lexer::new(SourceFileInput::from(&*fm));
+        let lexer = Lexer::new(SourceFileInput::from(&*fm), config);
         let mut parser = Parser::new(lexer, config);
 
         let document: PResult<Document> = parser.parse_document();
@@ -158,7 +196,7 @@ fn document_dom_visualizer(input: PathBuf, config: ParserConfig) {
         }
 
         let fm = cm.load_file(&input).unwrap();
-        let lexer = Lexer::new(SourceFileInput::from(&*fm));
+        let lexer = Lexer::new(SourceFileInput::from(&*fm), config);
         let mut parser = Parser::new(lexer, config);
 
         let document: PResult<Document> = parser.parse_document();
@@ -389,6 +427,11 @@ fn recovery(input: PathBuf) {
     document_recovery_test(input, Default::default())
 }
 
+#[testing::fixture("tests/namespace/**/*.xml")]
+fn namespace(input: PathBuf) {
+    document_namespace_test(input, Default::default())
+}
+
 #[testing::fixture("tests/fixture/**/*.xml")]
 #[testing::fixture("tests/recovery/**/*.xml")]
 fn span_visualizer(input: PathBuf) {
@@ -0,0 +1,168 @@
+#![deny(warnings)]
+
+use swc_common::{sync::Lrc, FileName, SourceMap};
+use swc_xml_ast::{Child, Document, Element};
+use swc_xml_parser::{
+    namespace::resolve_namespaces,
+    parse_file_as_document,
+    selector::{Selector, SelectorError},
+};
+
+const SITEMAP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+    <url>
+        <loc>https://example.com/</loc>
+        <priority>1.0</priority>
+    </url>
+    <url>
+        <loc>https://example.com/about</loc>
+    </url>
+</urlset>"#;
+
+const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+    <g id="first">
+        <rect id="a"/>
+        <g id="nested">
+            <rect id="b"/>
+            <rect id="c" fill="red"/>
+        </g>
+    </g>
+    <use id="d" xlink:href="#a"/>
+</svg>"##;
+
+fn parse(source: &str) -> Document {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon.into(), source.to_string());
+    let mut errors = Vec::new();
+    let mut document = parse_file_as_document(&fm, Default::default(), &mut errors).unwrap();
+
+    resolve_namespaces(&mut document, &mut errors);
+
+    assert!(errors.is_empty(), "{errors:?}");
+
+    document
+}
+
+fn text(element: &Element) -> String {
+    element
+        .children
+        .iter()
+        .filter_map(|child| match child {
+            Child::Text(text) => Some(&*text.data),
+            _ => None,
+        })
+        .collect()
+}
+
+fn ids(elements: Vec<&Element>) -> Vec<&str> {
+    elements
+        .into_iter()
+        .filter_map(|element| {
+            element
+                .attributes
+                .iter()
+                .find(|attribute| attribute.name == "id")
+                .and_then(|attribute| attribute.value.as_deref())
+        })
+        .collect()
+}
+
+#[test]
+fn select_children() {
+    let document = parse(SITEMAP);
+    let selector = Selector::parse("/urlset/url/loc").unwrap();
+
+    assert_eq!(
+        selector
+            .select(&document)
+            .into_iter()
+            .map(text)
+            .collect::<Vec<_>>(),
+        vec!["https://example.com/", "https://example.com/about"]
+    );
+}
+
+#[test]
+fn select_with_namespaces() {
+    let document = parse(SITEMAP);
+    let selector = Selector::parse("//s:url/s:loc")
+        .unwrap()
+        .namespace("s", "http://www.sitemaps.org/schemas/sitemap/0.9");
+
+    assert_eq!(selector.select(&document).len(), 2);
+
+    let selector = Selector::parse("//s:loc")
+        .unwrap()
+        .namespace("s", "http://example.com/other");
+
+    assert!(selector.select_first(&document).is_none());
+}
+
+#[test]
+fn select_descendants_in_document_order() {
+    let document = parse(SVG);
+
+    assert_eq!(
+        ids(Selector::parse("//rect").unwrap().select(&document)),
+        vec!["a", "b", "c"]
+    );
+    assert_eq!(
+        ids(Selector::parse("//g//rect").unwrap().select(&document)),
+        vec!["a", "b", "c"]
+    );
+    assert_eq!(
+        ids(Selector::parse("/svg/*").unwrap().select(&document)),
+        vec!["first", "d"]
+    );
+}
+
+#[test]
+fn select_with_predicates() {
+    let document = parse(SVG);
+
+    assert_eq!(
+        ids(Selector::parse("//rect[1]").unwrap().select(&document)),
+        vec!["a", "b"]
+    );
+    assert_eq!(
+        ids(Selector::parse("//rect[@fill]").unwrap().select(&document)),
+        vec!["c"]
+    );
+    assert_eq!(
+        ids(Selector::parse("//g[@id='nested']/rect[2]")
+            .unwrap()
+            .select(&document)),
+        vec!["c"]
+    );
+    assert_eq!(
+        ids(Selector::parse("//*[@xlink:href=\"#a\"]")
+            .unwrap()
+            .select(&document)),
+        vec!["d"]
+    );
+    assert_eq!(
+        ids(Selector::parse("//*[@x:href]")
+            .unwrap()
+            .namespace("x", "http://www.w3.org/1999/xlink")
+            .select(&document)),
+        vec!["d"]
+    );
+}
+
+#[test]
+fn invalid_selectors() {
+    assert_eq!(Selector::parse(""), Err(SelectorError::UnexpectedEnd));
+    assert_eq!(Selector::parse("/svg/"), Err(SelectorError::UnexpectedEnd));
+    assert_eq!(
+        Selector::parse("//url[priority]"),
+        Err(SelectorError::UnexpectedCharacter('p', 6))
+    );
+    assert_eq!(
+        Selector::parse("//rect[0]"),
+        Err(SelectorError::UnexpectedCharacter('0', 7))
+    );
+    assert_eq!(
+        Selector::parse("//rect[@fill='red'"),
+        Err(SelectorError::UnexpectedEnd)
+    );
+}
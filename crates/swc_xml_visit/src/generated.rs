@@ -94,13 +94,6 @@ pub trait Visit {
     fn visit_element(&mut self, node: &Element) {
         <Element as VisitWith<Self>>::visit_children_with(node, self)
     }
-    #[doc = "Visit a node of type `Namespace`.\n\nBy default, this method calls \
-             [`Namespace::visit_children_with`]. If you want to recurse, you need to call it \
-             manually."]
-    #[inline]
-    fn visit_namespace(&mut self, node: &Namespace) {
-        <Namespace as VisitWith<Self>>::visit_children_with(node, self)
-    }
     #[doc = "Visit a node of type `Option < swc_atoms :: Atom >`.\n\nBy default, this method calls \
              [`Option < swc_atoms :: Atom >::visit_children_with`]. If you want to recurse, you \
              need to call it manually."]
@@ -108,13 +101,6 @@ pub trait Visit {
     fn visit_opt_atom(&mut self, node: &Option<swc_atoms::Atom>) {
         <Option<swc_atoms::Atom> as VisitWith<Self>>::visit_children_with(node, self)
     }
-    #[doc = "Visit a node of type `Option < Namespace >`.\n\nBy default, this method calls \
-             [`Option < Namespace >::visit_children_with`]. If you want to recurse, you need to \
-             call it manually."]
-    #[inline]
-    fn visit_opt_namespace(&mut self, node: &Option<Namespace>) {
-        <Option<Namespace> as VisitWith<Self>>::visit_children_with(node, self)
-    }
     #[doc = "Visit a node of type `ProcessingInstruction`.\n\nBy default, this method calls \
              [`ProcessingInstruction::visit_children_with`]. If you want to recurse, you need to \
              call it manually."]
@@ -218,21 +204,11 @@ where
         <V as Visit>::visit_element(&mut **self, node)
     }
 
-    #[inline]
-    fn visit_namespace(&mut self, node: &Namespace) {
-        <V as Visit>::visit_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn visit_opt_atom(&mut self, node: &Option<swc_atoms::Atom>) {
         <V as Visit>::visit_opt_atom(&mut **self, node)
     }
 
-    #[inline]
-    fn visit_opt_namespace(&mut self, node: &Option<Namespace>) {
-        <V as Visit>::visit_opt_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn visit_processing_instruction(&mut self, node: &ProcessingInstruction) {
         <V as Visit>::visit_processing_instruction(&mut **self, node)
@@ -327,21 +303,11 @@ where
         <V as Visit>::visit_element(&mut **self, node)
     }
 
-    #[inline]
-    fn visit_namespace(&mut self, node: &Namespace) {
-        <V as Visit>::visit_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn visit_opt_atom(&mut self, node: &Option<swc_atoms::Atom>) {
         <V as Visit>::visit_opt_atom(&mut **self, node)
     }
 
-    #[inline]
-    fn visit_opt_namespace(&mut self, node: &Option<Namespace>) {
-        <V as Visit>::visit_opt_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn visit_processing_instruction(&mut self, node: &ProcessingInstruction) {
         <V as Visit>::visit_processing_instruction(&mut **self, node)
@@ -476,14 +442,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_namespace(&mut self, node: &Namespace) {
-        match self {
-            swc_visit::Either::Left(visitor) => Visit::visit_namespace(visitor, node),
-            swc_visit::Either::Right(visitor) => Visit::visit_namespace(visitor, node),
-        }
-    }
-
     #[inline]
     fn visit_opt_atom(&mut self, node: &Option<swc_atoms::Atom>) {
         match self {
@@ -492,14 +450,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_opt_namespace(&mut self, node: &Option<Namespace>) {
-        match self {
-            swc_visit::Either::Left(visitor) => Visit::visit_opt_namespace(visitor, node),
-            swc_visit::Either::Right(visitor) => Visit::visit_opt_namespace(visitor, node),
-        }
-    }
-
     #[inline]
     fn visit_processing_instruction(&mut self, node: &ProcessingInstruction) {
         match self {
@@ -648,14 +598,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_namespace(&mut self, node: &Namespace) {
-        if self.enabled {
-            <V as Visit>::visit_namespace(&mut self.visitor, node)
-        } else {
-        }
-    }
-
     #[inline]
     fn visit_opt_atom(&mut self, node: &Option<swc_atoms::Atom>) {
         if self.enabled {
@@ -664,14 +606,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_opt_namespace(&mut self, node: &Option<Namespace>) {
-        if self.enabled {
-            <V as Visit>::visit_opt_namespace(&mut self.visitor, node)
-        } else {
-        }
-    }
-
     #[inline]
     fn visit_processing_instruction(&mut self, node: &ProcessingInstruction) {
         if self.enabled {
@@ -740,7 +674,7 @@ impl<V: ?Sized + Visit> VisitWith<V> for Attribute {
                     <swc_common::Span as VisitWith<V>>::visit_with(span, visitor)
                 };
                 {
-                    <Option<Namespace> as VisitWith<V>>::visit_with(namespace, visitor)
+                    <Option<swc_atoms::Atom> as VisitWith<V>>::visit_with(namespace, visitor)
                 };
                 {
                     <Option<swc_atoms::Atom> as VisitWith<V>>::visit_with(prefix, visitor)
@@ -945,6 +879,7 @@ impl<V: ?Sized + Visit> VisitWith<V> for Element {
         match self {
             Element {
                 span,
+                namespace,
                 tag_name,
                 attributes,
                 children,
@@ -952,6 +887,9 @@ impl<V: ?Sized + Visit> VisitWith<V> for Element {
                 {
                     <swc_common::Span as VisitWith<V>>::visit_with(span, visitor)
                 };
+                {
+                    <Option<swc_atoms::Atom> as VisitWith<V>>::visit_with(namespace, visitor)
+                };
                 {
                     <swc_atoms::Atom as VisitWith<V>>::visit_with(tag_name, visitor)
                 };
@@ -965,23 +903,6 @@ impl<V: ?Sized + Visit> VisitWith<V> for Element {
         }
     }
 }
-impl<V: ?Sized + Visit> VisitWith<V> for Namespace {
-    #[doc = "Calls [Visit`::visit_namespace`] with `self`."]
-    fn visit_with(&self, visitor: &mut V) {
-        <V as Visit>::visit_namespace(visitor, self)
-    }
-
-    fn visit_children_with(&self, visitor: &mut V) {
-        match self {
-            Namespace::HTML => {}
-            Namespace::MATHML => {}
-            Namespace::SVG => {}
-            Namespace::XLINK => {}
-            Namespace::XML => {}
-            Namespace::XMLNS => {}
-        }
-    }
-}
 impl<V: ?Sized + Visit> VisitWith<V> for ProcessingInstruction {
     #[doc = "Calls [Visit`::visit_processing_instruction`] with `self`."]
     fn visit_with(&self, visitor: &mut V) {
@@ -1204,21 +1125,6 @@ impl<V: ?Sized + Visit> VisitWith<V> for Option<swc_atoms::Atom> {
         }
     }
 }
-impl<V: ?Sized + Visit> VisitWith<V> for Option<Namespace> {
-    #[doc = "Calls [Visit`::visit_opt_namespace`] with `self`. (Extra impl)"]
-    #[inline]
-    fn visit_with(&self, visitor: &mut V) {
-        <V as Visit>::visit_opt_namespace(visitor, self)
-    }
-
-    #[inline]
-    fn visit_children_with(&self, visitor: &mut V) {
-        match self {
-            Some(inner) => <Namespace as VisitWith<V>>::visit_with(inner, visitor),
-            None => {}
-        }
-    }
-}
 impl<V: ?Sized + Visit> VisitWith<V> for swc_common::Span {
     #[doc = "Calls [Visit`::visit_span`] with `self`. (Extra impl)"]
     #[inline]
@@ -1422,17 +1328,6 @@ pub trait VisitAstPath {
     ) {
         <Element as VisitWithAstPath<Self>>::visit_children_with_ast_path(node, self, __ast_path)
     }
-    #[doc = "Visit a node of type `Namespace`.\n\nBy default, this method calls \
-             [`Namespace::visit_children_with_ast_path`]. If you want to recurse, you need to call \
-             it manually."]
-    #[inline]
-    fn visit_namespace<'ast: 'r, 'r>(
-        &mut self,
-        node: &'ast Namespace,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        <Namespace as VisitWithAstPath<Self>>::visit_children_with_ast_path(node, self, __ast_path)
-    }
     #[doc = "Visit a node of type `Option < swc_atoms :: Atom >`.\n\nBy default, this method calls \
              [`Option < swc_atoms :: Atom >::visit_children_with_ast_path`]. If you want to \
              recurse, you need to call it manually."]
@@ -1446,19 +1341,6 @@ pub trait VisitAstPath {
             node, self, __ast_path,
         )
     }
-    #[doc = "Visit a node of type `Option < Namespace >`.\n\nBy default, this method calls \
-             [`Option < Namespace >::visit_children_with_ast_path`]. If you want to recurse, you \
-             need to call it manually."]
-    #[inline]
-    fn visit_opt_namespace<'ast: 'r, 'r>(
-        &mut self,
-        node: &'ast Option<Namespace>,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        <Option<Namespace> as VisitWithAstPath<Self>>::visit_children_with_ast_path(
-            node, self, __ast_path,
-        )
-    }
     #[doc = "Visit a node of type `ProcessingInstruction`.\n\nBy default, this method calls \
              [`ProcessingInstruction::visit_children_with_ast_path`]. If you want to recurse, you \
              need to call it manually."]
@@ -1632,15 +1514,6 @@ where
         <V as VisitAstPath>::visit_element(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn visit_namespace<'ast: 'r, 'r>(
-        &mut self,
-        node: &'ast Namespace,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        <V as VisitAstPath>::visit_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn visit_opt_atom<'ast: 'r, 'r>(
         &mut self,
@@ -1650,15 +1523,6 @@ where
         <V as VisitAstPath>::visit_opt_atom(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn visit_opt_namespace<'ast: 'r, 'r>(
-        &mut self,
-        node: &'ast Option<Namespace>,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        <V as VisitAstPath>::visit_opt_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn visit_processing_instruction<'ast: 'r, 'r>(
         &mut self,
@@ -1815,15 +1679,6 @@ where
         <V as VisitAstPath>::visit_element(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn visit_namespace<'ast: 'r, 'r>(
-        &mut self,
-        node: &'ast Namespace,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        <V as VisitAstPath>::visit_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn visit_opt_atom<'ast: 'r, 'r>(
         &mut self,
@@ -1833,15 +1688,6 @@ where
         <V as VisitAstPath>::visit_opt_atom(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn visit_opt_namespace<'ast: 'r, 'r>(
-        &mut self,
-        node: &'ast Option<Namespace>,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        <V as VisitAstPath>::visit_opt_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn visit_processing_instruction<'ast: 'r, 'r>(
         &mut self,
@@ -2088,22 +1934,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_namespace<'ast: 'r, 'r>(
-        &mut self,
-        node: &'ast Namespace,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        match self {
-            swc_visit::Either::Left(visitor) => {
-                VisitAstPath::visit_namespace(visitor, node, __ast_path)
-            }
-            swc_visit::Either::Right(visitor) => {
-                VisitAstPath::visit_namespace(visitor, node, __ast_path)
-            }
-        }
-    }
-
     #[inline]
     fn visit_opt_atom<'ast: 'r, 'r>(
         &mut self,
@@ -2120,22 +1950,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_opt_namespace<'ast: 'r, 'r>(
-        &mut self,
-        node: &'ast Option<Namespace>,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        match self {
-            swc_visit::Either::Left(visitor) => {
-                VisitAstPath::visit_opt_namespace(visitor, node, __ast_path)
-            }
-            swc_visit::Either::Right(visitor) => {
-                VisitAstPath::visit_opt_namespace(visitor, node, __ast_path)
-            }
-        }
-    }
-
     #[inline]
     fn visit_processing_instruction<'ast: 'r, 'r>(
         &mut self,
@@ -2362,18 +2176,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_namespace<'ast: 'r, 'r>(
-        &mut self,
-        node: &'ast Namespace,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        if self.enabled {
-            <V as VisitAstPath>::visit_namespace(&mut self.visitor, node, __ast_path)
-        } else {
-        }
-    }
-
     #[inline]
     fn visit_opt_atom<'ast: 'r, 'r>(
         &mut self,
@@ -2386,18 +2188,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_opt_namespace<'ast: 'r, 'r>(
-        &mut self,
-        node: &'ast Option<Namespace>,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        if self.enabled {
-            <V as VisitAstPath>::visit_opt_namespace(&mut self.visitor, node, __ast_path)
-        } else {
-        }
-    }
-
     #[inline]
     fn visit_processing_instruction<'ast: 'r, 'r>(
         &mut self,
@@ -2510,7 +2300,7 @@ impl<V: ?Sized + VisitAstPath> VisitWithAstPath<V> for Attribute {
                         self,
                         self::fields::AttributeField::Namespace,
                     ));
-                    <Option<Namespace> as VisitWithAstPath<V>>::visit_with_ast_path(
+                    <Option<swc_atoms::Atom> as VisitWithAstPath<V>>::visit_with_ast_path(
                         namespace,
                         visitor,
                         &mut *__ast_path,
@@ -3031,6 +2821,7 @@ impl<V: ?Sized + VisitAstPath> VisitWithAstPath<V> for Element {
         match self {
             Element {
                 span,
+                namespace,
                 tag_name,
                 attributes,
                 children,
@@ -3046,6 +2837,17 @@ impl<V: ?Sized + VisitAstPath> VisitWithAstPath<V> for Element {
                         &mut *__ast_path,
                     )
                 };
+                {
+                    let mut __ast_path = __ast_path.with_guard(AstParentNodeRef::Element(
+                        self,
+                        self::fields::ElementField::Namespace,
+                    ));
+                    <Option<swc_atoms::Atom> as VisitWithAstPath<V>>::visit_with_ast_path(
+                        namespace,
+                        visitor,
+                        &mut *__ast_path,
+                    )
+                };
                 {
                     let mut __ast_path = __ast_path.with_guard(AstParentNodeRef::Element(
                         self,
@@ -3085,33 +2887,6 @@ impl<V: ?Sized + VisitAstPath> VisitWithAstPath<V> for Element {
 }
 #[cfg(any(docsrs, feature = "path"))]
 #[cfg_attr(docsrs, doc(cfg(feature = "path")))]
-impl<V: ?Sized + VisitAstPath> VisitWithAstPath<V> for Namespace {
-    #[doc = "Calls [VisitAstPath`::visit_namespace`] with `self`."]
-    fn visit_with_ast_path<'ast: 'r, 'r>(
-        &'ast self,
-        visitor: &mut V,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        <V as VisitAstPath>::visit_namespace(visitor, self, __ast_path)
-    }
-
-    fn visit_children_with_ast_path<'ast: 'r, 'r>(
-        &'ast self,
-        visitor: &mut V,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        match self {
-            Namespace::HTML => {}
-            Namespace::MATHML => {}
-            Namespace::SVG => {}
-            Namespace::XLINK => {}
-            Namespace::XML => {}
-            Namespace::XMLNS => {}
-        }
-    }
-}
-#[cfg(any(docsrs, feature = "path"))]
-#[cfg_attr(docsrs, doc(cfg(feature = "path")))]
 impl<V: ?Sized + VisitAstPath> VisitWithAstPath<V> for ProcessingInstruction {
     #[doc = "Calls [VisitAstPath`::visit_processing_instruction`] with `self`."]
     fn visit_with_ast_path<'ast: 'r, 'r>(
@@ -3657,33 +3432,6 @@ impl<V: ?Sized + VisitAstPath> VisitWithAstPath<V> for Option<swc_atoms::Atom> {
 }
 #[cfg(any(docsrs, feature = "path"))]
 #[cfg_attr(docsrs, doc(cfg(feature = "path")))]
-impl<V: ?Sized + VisitAstPath> VisitWithAstPath<V> for Option<Namespace> {
-    #[doc = "Calls [VisitAstPath`::visit_opt_namespace`] with `self`. (Extra impl)"]
-    #[inline]
-    fn visit_with_ast_path<'ast: 'r, 'r>(
-        &'ast self,
-        visitor: &mut V,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        <V as VisitAstPath>::visit_opt_namespace(visitor, self, __ast_path)
-    }
-
-    #[inline]
-    fn visit_children_with_ast_path<'ast: 'r, 'r>(
-        &'ast self,
-        visitor: &mut V,
-        __ast_path: &mut AstNodePath<'r>,
-    ) {
-        match self {
-            Some(inner) => {
-                <Namespace as VisitWithAstPath<V>>::visit_with_ast_path(inner, visitor, __ast_path)
-            }
-            None => {}
-        }
-    }
-}
-#[cfg(any(docsrs, feature = "path"))]
-#[cfg_attr(docsrs, doc(cfg(feature = "path")))]
 impl<V: ?Sized + VisitAstPath> VisitWithAstPath<V> for swc_common::Span {
     #[doc = "Calls [VisitAstPath`::visit_span`] with `self`. (Extra impl)"]
     #[inline]
@@ -3852,13 +3600,6 @@ pub trait VisitMut {
     fn visit_mut_element(&mut self, node: &mut Element) {
         <Element as VisitMutWith<Self>>::visit_mut_children_with(node, self)
     }
-    #[doc = "Visit a node of type `Namespace`.\n\nBy default, this method calls \
-             [`Namespace::visit_mut_children_with`]. If you want to recurse, you need to call it \
-             manually."]
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace) {
-        <Namespace as VisitMutWith<Self>>::visit_mut_children_with(node, self)
-    }
     #[doc = "Visit a node of type `Option < swc_atoms :: Atom >`.\n\nBy default, this method calls \
              [`Option < swc_atoms :: Atom >::visit_mut_children_with`]. If you want to recurse, \
              you need to call it manually."]
@@ -3866,13 +3607,6 @@ pub trait VisitMut {
     fn visit_mut_opt_atom(&mut self, node: &mut Option<swc_atoms::Atom>) {
         <Option<swc_atoms::Atom> as VisitMutWith<Self>>::visit_mut_children_with(node, self)
     }
-    #[doc = "Visit a node of type `Option < Namespace >`.\n\nBy default, this method calls \
-             [`Option < Namespace >::visit_mut_children_with`]. If you want to recurse, you need \
-             to call it manually."]
-    #[inline]
-    fn visit_mut_opt_namespace(&mut self, node: &mut Option<Namespace>) {
-        <Option<Namespace> as VisitMutWith<Self>>::visit_mut_children_with(node, self)
-    }
     #[doc = "Visit a node of type `ProcessingInstruction`.\n\nBy default, this method calls \
              [`ProcessingInstruction::visit_mut_children_with`]. If you want to recurse, you need \
              to call it manually."]
@@ -3978,21 +3712,11 @@ where
         <V as VisitMut>::visit_mut_element(&mut **self, node)
     }
 
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace) {
-        <V as VisitMut>::visit_mut_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn visit_mut_opt_atom(&mut self, node: &mut Option<swc_atoms::Atom>) {
         <V as VisitMut>::visit_mut_opt_atom(&mut **self, node)
     }
 
-    #[inline]
-    fn visit_mut_opt_namespace(&mut self, node: &mut Option<Namespace>) {
-        <V as VisitMut>::visit_mut_opt_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn visit_mut_processing_instruction(&mut self, node: &mut ProcessingInstruction) {
         <V as VisitMut>::visit_mut_processing_instruction(&mut **self, node)
@@ -4087,21 +3811,11 @@ where
         <V as VisitMut>::visit_mut_element(&mut **self, node)
     }
 
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace) {
-        <V as VisitMut>::visit_mut_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn visit_mut_opt_atom(&mut self, node: &mut Option<swc_atoms::Atom>) {
         <V as VisitMut>::visit_mut_opt_atom(&mut **self, node)
     }
 
-    #[inline]
-    fn visit_mut_opt_namespace(&mut self, node: &mut Option<Namespace>) {
-        <V as VisitMut>::visit_mut_opt_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn visit_mut_processing_instruction(&mut self, node: &mut ProcessingInstruction) {
         <V as VisitMut>::visit_mut_processing_instruction(&mut **self, node)
@@ -4238,14 +3952,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace) {
-        match self {
-            swc_visit::Either::Left(visitor) => VisitMut::visit_mut_namespace(visitor, node),
-            swc_visit::Either::Right(visitor) => VisitMut::visit_mut_namespace(visitor, node),
-        }
-    }
-
     #[inline]
     fn visit_mut_opt_atom(&mut self, node: &mut Option<swc_atoms::Atom>) {
         match self {
@@ -4254,14 +3960,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_mut_opt_namespace(&mut self, node: &mut Option<Namespace>) {
-        match self {
-            swc_visit::Either::Left(visitor) => VisitMut::visit_mut_opt_namespace(visitor, node),
-            swc_visit::Either::Right(visitor) => VisitMut::visit_mut_opt_namespace(visitor, node),
-        }
-    }
-
     #[inline]
     fn visit_mut_processing_instruction(&mut self, node: &mut ProcessingInstruction) {
         match self {
@@ -4414,14 +4112,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace) {
-        if self.enabled {
-            <V as VisitMut>::visit_mut_namespace(&mut self.visitor, node)
-        } else {
-        }
-    }
-
     #[inline]
     fn visit_mut_opt_atom(&mut self, node: &mut Option<swc_atoms::Atom>) {
         if self.enabled {
@@ -4430,14 +4120,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_mut_opt_namespace(&mut self, node: &mut Option<Namespace>) {
-        if self.enabled {
-            <V as VisitMut>::visit_mut_opt_namespace(&mut self.visitor, node)
-        } else {
-        }
-    }
-
     #[inline]
     fn visit_mut_processing_instruction(&mut self, node: &mut ProcessingInstruction) {
         if self.enabled {
@@ -4506,7 +4188,7 @@ impl<V: ?Sized + VisitMut> VisitMutWith<V> for Attribute {
                     <swc_common::Span as VisitMutWith<V>>::visit_mut_with(span, visitor)
                 };
                 {
-                    <Option<Namespace> as VisitMutWith<V>>::visit_mut_with(namespace, visitor)
+                    <Option<swc_atoms::Atom> as VisitMutWith<V>>::visit_mut_with(namespace, visitor)
                 };
                 {
                     <Option<swc_atoms::Atom> as VisitMutWith<V>>::visit_mut_with(prefix, visitor)
@@ -4711,6 +4393,7 @@ impl<V: ?Sized + VisitMut> VisitMutWith<V> for Element {
         match self {
             Element {
                 span,
+                namespace,
                 tag_name,
                 attributes,
                 children,
@@ -4718,6 +4401,9 @@ impl<V: ?Sized + VisitMut> VisitMutWith<V> for Element {
                 {
                     <swc_common::Span as VisitMutWith<V>>::visit_mut_with(span, visitor)
                 };
+                {
+                    <Option<swc_atoms::Atom> as VisitMutWith<V>>::visit_mut_with(namespace, visitor)
+                };
                 {
                     <swc_atoms::Atom as VisitMutWith<V>>::visit_mut_with(tag_name, visitor)
                 };
@@ -4731,23 +4417,6 @@ impl<V: ?Sized + VisitMut> VisitMutWith<V> for Element {
         }
     }
 }
-impl<V: ?Sized + VisitMut> VisitMutWith<V> for Namespace {
-    #[doc = "Calls [VisitMut`::visit_mut_namespace`] with `self`."]
-    fn visit_mut_with(&mut self, visitor: &mut V) {
-        <V as VisitMut>::visit_mut_namespace(visitor, self)
-    }
-
-    fn visit_mut_children_with(&mut self, visitor: &mut V) {
-        match self {
-            Namespace::HTML => {}
-            Namespace::MATHML => {}
-            Namespace::SVG => {}
-            Namespace::XLINK => {}
-            Namespace::XML => {}
-            Namespace::XMLNS => {}
-        }
-    }
-}
 impl<V: ?Sized + VisitMut> VisitMutWith<V> for ProcessingInstruction {
     #[doc = "Calls [VisitMut`::visit_mut_processing_instruction`] with `self`."]
     fn visit_mut_with(&mut self, visitor: &mut V) {
@@ -4970,21 +4639,6 @@ impl<V: ?Sized + VisitMut> VisitMutWith<V> for Option<swc_atoms::Atom> {
         }
     }
 }
-impl<V: ?Sized + VisitMut> VisitMutWith<V> for Option<Namespace> {
-    #[doc = "Calls [VisitMut`::visit_mut_opt_namespace`] with `self`. (Extra impl)"]
-    #[inline]
-    fn visit_mut_with(&mut self, visitor: &mut V) {
-        <V as VisitMut>::visit_mut_opt_namespace(visitor, self)
-    }
-
-    #[inline]
-    fn visit_mut_children_with(&mut self, visitor: &mut V) {
-        match self {
-            Some(inner) => <Namespace as VisitMutWith<V>>::visit_mut_with(inner, visitor),
-            None => {}
-        }
-    }
-}
 impl<V: ?Sized + VisitMut> VisitMutWith<V> for swc_common::Span {
     #[doc = "Calls [VisitMut`::visit_mut_span`] with `self`. (Extra impl)"]
     #[inline]
@@ -5143,15 +4797,6 @@ pub trait VisitMutAstPath {
             node, self, __ast_path,
         )
     }
-    #[doc = "Visit a node of type `Namespace`.\n\nBy default, this method calls \
-             [`Namespace::visit_mut_children_with_ast_path`]. If you want to recurse, you need to \
-             call it manually."]
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace, __ast_path: &mut AstKindPath) {
-        <Namespace as VisitMutWithAstPath<Self>>::visit_mut_children_with_ast_path(
-            node, self, __ast_path,
-        )
-    }
     #[doc = "Visit a node of type `Option < swc_atoms :: Atom >`.\n\nBy default, this method calls \
              [`Option < swc_atoms :: Atom >::visit_mut_children_with_ast_path`]. If you want to \
              recurse, you need to call it manually."]
@@ -5165,19 +4810,6 @@ pub trait VisitMutAstPath {
             node, self, __ast_path,
         )
     }
-    #[doc = "Visit a node of type `Option < Namespace >`.\n\nBy default, this method calls \
-             [`Option < Namespace >::visit_mut_children_with_ast_path`]. If you want to recurse, \
-             you need to call it manually."]
-    #[inline]
-    fn visit_mut_opt_namespace(
-        &mut self,
-        node: &mut Option<Namespace>,
-        __ast_path: &mut AstKindPath,
-    ) {
-        <Option<Namespace> as VisitMutWithAstPath<Self>>::visit_mut_children_with_ast_path(
-            node, self, __ast_path,
-        )
-    }
     #[doc = "Visit a node of type `ProcessingInstruction`.\n\nBy default, this method calls \
              [`ProcessingInstruction::visit_mut_children_with_ast_path`]. If you want to recurse, \
              you need to call it manually."]
@@ -5307,11 +4939,6 @@ where
         <V as VisitMutAstPath>::visit_mut_element(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace, __ast_path: &mut AstKindPath) {
-        <V as VisitMutAstPath>::visit_mut_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn visit_mut_opt_atom(
         &mut self,
@@ -5321,15 +4948,6 @@ where
         <V as VisitMutAstPath>::visit_mut_opt_atom(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn visit_mut_opt_namespace(
-        &mut self,
-        node: &mut Option<Namespace>,
-        __ast_path: &mut AstKindPath,
-    ) {
-        <V as VisitMutAstPath>::visit_mut_opt_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn visit_mut_processing_instruction(
         &mut self,
@@ -5438,11 +5056,6 @@ where
         <V as VisitMutAstPath>::visit_mut_element(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace, __ast_path: &mut AstKindPath) {
-        <V as VisitMutAstPath>::visit_mut_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn visit_mut_opt_atom(
         &mut self,
@@ -5452,15 +5065,6 @@ where
         <V as VisitMutAstPath>::visit_mut_opt_atom(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn visit_mut_opt_namespace(
-        &mut self,
-        node: &mut Option<Namespace>,
-        __ast_path: &mut AstKindPath,
-    ) {
-        <V as VisitMutAstPath>::visit_mut_opt_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn visit_mut_processing_instruction(
         &mut self,
@@ -5661,18 +5265,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace, __ast_path: &mut AstKindPath) {
-        match self {
-            swc_visit::Either::Left(visitor) => {
-                VisitMutAstPath::visit_mut_namespace(visitor, node, __ast_path)
-            }
-            swc_visit::Either::Right(visitor) => {
-                VisitMutAstPath::visit_mut_namespace(visitor, node, __ast_path)
-            }
-        }
-    }
-
     #[inline]
     fn visit_mut_opt_atom(
         &mut self,
@@ -5689,22 +5281,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_mut_opt_namespace(
-        &mut self,
-        node: &mut Option<Namespace>,
-        __ast_path: &mut AstKindPath,
-    ) {
-        match self {
-            swc_visit::Either::Left(visitor) => {
-                VisitMutAstPath::visit_mut_opt_namespace(visitor, node, __ast_path)
-            }
-            swc_visit::Either::Right(visitor) => {
-                VisitMutAstPath::visit_mut_opt_namespace(visitor, node, __ast_path)
-            }
-        }
-    }
-
     #[inline]
     fn visit_mut_processing_instruction(
         &mut self,
@@ -5887,14 +5463,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace, __ast_path: &mut AstKindPath) {
-        if self.enabled {
-            <V as VisitMutAstPath>::visit_mut_namespace(&mut self.visitor, node, __ast_path)
-        } else {
-        }
-    }
-
     #[inline]
     fn visit_mut_opt_atom(
         &mut self,
@@ -5907,18 +5475,6 @@ where
         }
     }
 
-    #[inline]
-    fn visit_mut_opt_namespace(
-        &mut self,
-        node: &mut Option<Namespace>,
-        __ast_path: &mut AstKindPath,
-    ) {
-        if self.enabled {
-            <V as VisitMutAstPath>::visit_mut_opt_namespace(&mut self.visitor, node, __ast_path)
-        } else {
-        }
-    }
-
     #[inline]
     fn visit_mut_processing_instruction(
         &mut self,
@@ -6008,7 +5564,7 @@ impl<V: ?Sized + VisitMutAstPath> VisitMutWithAstPath<V> for Attribute {
                     let mut __ast_path = __ast_path.with_guard(AstParentKind::Attribute(
                         self::fields::AttributeField::Namespace,
                     ));
-                    <Option<Namespace> as VisitMutWithAstPath<V>>::visit_mut_with_ast_path(
+                    <Option<swc_atoms::Atom> as VisitMutWithAstPath<V>>::visit_mut_with_ast_path(
                         namespace,
                         visitor,
                         &mut *__ast_path,
@@ -6426,6 +5982,7 @@ impl<V: ?Sized + VisitMutAstPath> VisitMutWithAstPath<V> for Element {
         match self {
             Element {
                 span,
+                namespace,
                 tag_name,
                 attributes,
                 children,
@@ -6439,6 +5996,16 @@ impl<V: ?Sized + VisitMutAstPath> VisitMutWithAstPath<V> for Element {
                         &mut *__ast_path,
                     )
                 };
+                {
+                    let mut __ast_path = __ast_path.with_guard(AstParentKind::Element(
+                        self::fields::ElementField::Namespace,
+                    ));
+                    <Option<swc_atoms::Atom> as VisitMutWithAstPath<V>>::visit_mut_with_ast_path(
+                        namespace,
+                        visitor,
+                        &mut *__ast_path,
+                    )
+                };
                 {
                     let mut __ast_path = __ast_path
                         .with_guard(AstParentKind::Element(self::fields::ElementField::TagName));
@@ -6474,25 +6041,6 @@ impl<V: ?Sized + VisitMutAstPath> VisitMutWithAstPath<V> for Element {
 }
 #[cfg(any(docsrs, feature = "path"))]
 #[cfg_attr(docsrs, doc(cfg(feature = "path")))]
-impl<V: ?Sized + VisitMutAstPath> VisitMutWithAstPath<V> for Namespace {
-    #[doc = "Calls [VisitMutAstPath`::visit_mut_namespace`] with `self`."]
-    fn visit_mut_with_ast_path(&mut self, visitor: &mut V, __ast_path: &mut AstKindPath) {
-        <V as VisitMutAstPath>::visit_mut_namespace(visitor, self, __ast_path)
-    }
-
-    fn visit_mut_children_with_ast_path(&mut self, visitor: &mut V, __ast_path: &mut AstKindPath) {
-        match self {
-            Namespace::HTML => {}
-            Namespace::MATHML => {}
-            Namespace::SVG => {}
-            Namespace::XLINK => {}
-            Namespace::XML => {}
-            Namespace::XMLNS => {}
-        }
-    }
-}
-#[cfg(any(docsrs, feature = "path"))]
-#[cfg_attr(docsrs, doc(cfg(feature = "path")))]
 impl<V: ?Sized + VisitMutAstPath> VisitMutWithAstPath<V> for ProcessingInstruction {
     #[doc = "Calls [VisitMutAstPath`::visit_mut_processing_instruction`] with `self`."]
     fn visit_mut_with_ast_path(&mut self, visitor: &mut V, __ast_path: &mut AstKindPath) {
@@ -6931,25 +6479,6 @@ impl<V: ?Sized + VisitMutAstPath> VisitMutWithAstPath<V> for Option<swc_atoms::A
 }
 #[cfg(any(docsrs, feature = "path"))]
 #[cfg_attr(docsrs, doc(cfg(feature = "path")))]
-impl<V: ?Sized + VisitMutAstPath> VisitMutWithAstPath<V> for Option<Namespace> {
-    #[doc = "Calls [VisitMutAstPath`::visit_mut_opt_namespace`] with `self`. (Extra impl)"]
-    #[inline]
-    fn visit_mut_with_ast_path(&mut self, visitor: &mut V, __ast_path: &mut AstKindPath) {
-        <V as VisitMutAstPath>::visit_mut_opt_namespace(visitor, self, __ast_path)
-    }
-
-    #[inline]
-    fn visit_mut_children_with_ast_path(&mut self, visitor: &mut V, __ast_path: &mut AstKindPath) {
-        match self {
-            Some(inner) => <Namespace as VisitMutWithAstPath<V>>::visit_mut_with_ast_path(
-                inner, visitor, __ast_path,
-            ),
-            None => {}
-        }
-    }
-}
-#[cfg(any(docsrs, feature = "path"))]
-#[cfg_attr(docsrs, doc(cfg(feature = "path")))]
 impl<V: ?Sized + VisitMutAstPath> VisitMutWithAstPath<V> for swc_common::Span {
     #[doc = "Calls [VisitMutAstPath`::visit_mut_span`] with `self`. (Extra impl)"]
     #[inline]
@@ -7078,13 +6607,6 @@ pub trait Fold {
     fn fold_element(&mut self, node: Element) -> Element {
         <Element as FoldWith<Self>>::fold_children_with(node, self)
     }
-    #[doc = "Visit a node of type `Namespace`.\n\nBy default, this method calls \
-             [`Namespace::fold_children_with`]. If you want to recurse, you need to call it \
-             manually."]
-    #[inline]
-    fn fold_namespace(&mut self, node: Namespace) -> Namespace {
-        <Namespace as FoldWith<Self>>::fold_children_with(node, self)
-    }
     #[doc = "Visit a node of type `Option < swc_atoms :: Atom >`.\n\nBy default, this method calls \
              [`Option < swc_atoms :: Atom >::fold_children_with`]. If you want to recurse, you \
              need to call it manually."]
@@ -7092,13 +6614,6 @@ pub trait Fold {
     fn fold_opt_atom(&mut self, node: Option<swc_atoms::Atom>) -> Option<swc_atoms::Atom> {
         <Option<swc_atoms::Atom> as FoldWith<Self>>::fold_children_with(node, self)
     }
-    #[doc = "Visit a node of type `Option < Namespace >`.\n\nBy default, this method calls \
-             [`Option < Namespace >::fold_children_with`]. If you want to recurse, you need to \
-             call it manually."]
-    #[inline]
-    fn fold_opt_namespace(&mut self, node: Option<Namespace>) -> Option<Namespace> {
-        <Option<Namespace> as FoldWith<Self>>::fold_children_with(node, self)
-    }
     #[doc = "Visit a node of type `ProcessingInstruction`.\n\nBy default, this method calls \
              [`ProcessingInstruction::fold_children_with`]. If you want to recurse, you need to \
              call it manually."]
@@ -7205,21 +6720,11 @@ where
         <V as Fold>::fold_element(&mut **self, node)
     }
 
-    #[inline]
-    fn fold_namespace(&mut self, node: Namespace) -> Namespace {
-        <V as Fold>::fold_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn fold_opt_atom(&mut self, node: Option<swc_atoms::Atom>) -> Option<swc_atoms::Atom> {
         <V as Fold>::fold_opt_atom(&mut **self, node)
     }
 
-    #[inline]
-    fn fold_opt_namespace(&mut self, node: Option<Namespace>) -> Option<Namespace> {
-        <V as Fold>::fold_opt_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn fold_processing_instruction(
         &mut self,
@@ -7317,21 +6822,11 @@ where
         <V as Fold>::fold_element(&mut **self, node)
     }
 
-    #[inline]
-    fn fold_namespace(&mut self, node: Namespace) -> Namespace {
-        <V as Fold>::fold_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn fold_opt_atom(&mut self, node: Option<swc_atoms::Atom>) -> Option<swc_atoms::Atom> {
         <V as Fold>::fold_opt_atom(&mut **self, node)
     }
 
-    #[inline]
-    fn fold_opt_namespace(&mut self, node: Option<Namespace>) -> Option<Namespace> {
-        <V as Fold>::fold_opt_namespace(&mut **self, node)
-    }
-
     #[inline]
     fn fold_processing_instruction(
         &mut self,
@@ -7469,14 +6964,6 @@ where
         }
     }
 
-    #[inline]
-    fn fold_namespace(&mut self, node: Namespace) -> Namespace {
-        match self {
-            swc_visit::Either::Left(visitor) => Fold::fold_namespace(visitor, node),
-            swc_visit::Either::Right(visitor) => Fold::fold_namespace(visitor, node),
-        }
-    }
-
     #[inline]
     fn fold_opt_atom(&mut self, node: Option<swc_atoms::Atom>) -> Option<swc_atoms::Atom> {
         match self {
@@ -7485,14 +6972,6 @@ where
         }
     }
 
-    #[inline]
-    fn fold_opt_namespace(&mut self, node: Option<Namespace>) -> Option<Namespace> {
-        match self {
-            swc_visit::Either::Left(visitor) => Fold::fold_opt_namespace(visitor, node),
-            swc_visit::Either::Right(visitor) => Fold::fold_opt_namespace(visitor, node),
-        }
-    }
-
     #[inline]
     fn fold_processing_instruction(
         &mut self,
@@ -7657,15 +7136,6 @@ where
         }
     }
 
-    #[inline]
-    fn fold_namespace(&mut self, node: Namespace) -> Namespace {
-        if self.enabled {
-            <V as Fold>::fold_namespace(&mut self.visitor, node)
-        } else {
-            node
-        }
-    }
-
     #[inline]
     fn fold_opt_atom(&mut self, node: Option<swc_atoms::Atom>) -> Option<swc_atoms::Atom> {
         if self.enabled {
@@ -7675,15 +7145,6 @@ where
         }
     }
 
-    #[inline]
-    fn fold_opt_namespace(&mut self, node: Option<Namespace>) -> Option<Namespace> {
-        if self.enabled {
-            <V as Fold>::fold_opt_namespace(&mut self.visitor, node)
-        } else {
-            node
-        }
-    }
-
     #[inline]
     fn fold_processing_instruction(
         &mut self,
@@ -7758,7 +7219,7 @@ impl<V: ?Sized + Fold> FoldWith<V> for Attribute {
             } => {
                 let span = { <swc_common::Span as FoldWith<V>>::fold_with(span, visitor) };
                 let namespace =
-                    { <Option<Namespace> as FoldWith<V>>::fold_with(namespace, visitor) };
+                    { <Option<swc_atoms::Atom> as FoldWith<V>>::fold_with(namespace, visitor) };
                 let prefix =
                     { <Option<swc_atoms::Atom> as FoldWith<V>>::fold_with(prefix, visitor) };
                 let name = { <swc_atoms::Atom as FoldWith<V>>::fold_with(name, visitor) };
@@ -7955,17 +7416,21 @@ impl<V: ?Sized + Fold> FoldWith<V> for Element {
         match self {
             Element {
                 span,
+                namespace,
                 tag_name,
                 attributes,
                 children,
             } => {
                 let span = { <swc_common::Span as FoldWith<V>>::fold_with(span, visitor) };
+                let namespace =
+                    { <Option<swc_atoms::Atom> as FoldWith<V>>::fold_with(namespace, visitor) };
                 let tag_name = { <swc_atoms::Atom as FoldWith<V>>::fold_with(tag_name, visitor) };
                 let attributes =
                     { <Vec<Attribute> as FoldWith<V>>::fold_with(attributes, visitor) };
                 let children = { <Vec<Child> as FoldWith<V>>::fold_with(children, visitor) };
                 Element {
                     span,
+                    namespace,
                     tag_name,
                     attributes,
                     children,
@@ -7974,23 +7439,6 @@ impl<V: ?Sized + Fold> FoldWith<V> for Element {
         }
     }
 }
-impl<V: ?Sized + Fold> FoldWith<V> for Namespace {
-    #[doc = "Calls [Fold`::fold_namespace`] with `self`."]
-    fn fold_with(self, visitor: &mut V) -> Self {
-        <V as Fold>::fold_namespace(visitor, self)
-    }
-
-    fn fold_children_with(self, visitor: &mut V) -> Self {
-        match self {
-            Namespace::HTML => Namespace::HTML,
-            Namespace::MATHML => Namespace::MATHML,
-            Namespace::SVG => Namespace::SVG,
-            Namespace::XLINK => Namespace::XLINK,
-            Namespace::XML => Namespace::XML,
-            Namespace::XMLNS => Namespace::XMLNS,
-        }
-    }
-}
 impl<V: ?Sized + Fold> FoldWith<V> for ProcessingInstruction {
     #[doc = "Calls [Fold`::fold_processing_instruction`] with `self`."]
     fn fold_with(self, visitor: &mut V) -> Self {
@@ -8193,18 +7641,6 @@ impl<V: ?Sized + Fold> FoldWith<V> for Option<swc_atoms::Atom> {
         self.map(|inner| <swc_atoms::Atom as FoldWith<V>>::fold_with(inner, visitor))
     }
 }
-impl<V: ?Sized + Fold> FoldWith<V> for Option<Namespace> {
-    #[doc = "Calls [Fold`::fold_opt_namespace`] with `self`. (Extra impl)"]
-    #[inline]
-    fn fold_with(self, visitor: &mut V) -> Self {
-        <V as Fold>::fold_opt_namespace(visitor, self)
-    }
-
-    #[inline]
-    fn fold_children_with(self, visitor: &mut V) -> Self {
-        self.map(|inner| <Namespace as FoldWith<V>>::fold_with(inner, visitor))
-    }
-}
 impl<V: ?Sized + Fold> FoldWith<V> for swc_common::Span {
     #[doc = "Calls [Fold`::fold_span`] with `self`. (Extra impl)"]
     #[inline]
@@ -8365,13 +7801,6 @@ pub trait FoldAstPath {
     fn fold_element(&mut self, node: Element, __ast_path: &mut AstKindPath) -> Element {
         <Element as FoldWithAstPath<Self>>::fold_children_with_ast_path(node, self, __ast_path)
     }
-    #[doc = "Visit a node of type `Namespace`.\n\nBy default, this method calls \
-             [`Namespace::fold_children_with_ast_path`]. If you want to recurse, you need to call \
-             it manually."]
-    #[inline]
-    fn fold_namespace(&mut self, node: Namespace, __ast_path: &mut AstKindPath) -> Namespace {
-        <Namespace as FoldWithAstPath<Self>>::fold_children_with_ast_path(node, self, __ast_path)
-    }
     #[doc = "Visit a node of type `Option < swc_atoms :: Atom >`.\n\nBy default, this method calls \
              [`Option < swc_atoms :: Atom >::fold_children_with_ast_path`]. If you want to \
              recurse, you need to call it manually."]
@@ -8385,19 +7814,6 @@ pub trait FoldAstPath {
             node, self, __ast_path,
         )
     }
-    #[doc = "Visit a node of type `Option < Namespace >`.\n\nBy default, this method calls \
-             [`Option < Namespace >::fold_children_with_ast_path`]. If you want to recurse, you \
-             need to call it manually."]
-    #[inline]
-    fn fold_opt_namespace(
-        &mut self,
-        node: Option<Namespace>,
-        __ast_path: &mut AstKindPath,
-    ) -> Option<Namespace> {
-        <Option<Namespace> as FoldWithAstPath<Self>>::fold_children_with_ast_path(
-            node, self, __ast_path,
-        )
-    }
     #[doc = "Visit a node of type `ProcessingInstruction`.\n\nBy default, this method calls \
              [`ProcessingInstruction::fold_children_with_ast_path`]. If you want to recurse, you \
              need to call it manually."]
@@ -8549,11 +7965,6 @@ where
         <V as FoldAstPath>::fold_element(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn fold_namespace(&mut self, node: Namespace, __ast_path: &mut AstKindPath) -> Namespace {
-        <V as FoldAstPath>::fold_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn fold_opt_atom(
         &mut self,
@@ -8563,15 +7974,6 @@ where
         <V as FoldAstPath>::fold_opt_atom(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn fold_opt_namespace(
-        &mut self,
-        node: Option<Namespace>,
-        __ast_path: &mut AstKindPath,
-    ) -> Option<Namespace> {
-        <V as FoldAstPath>::fold_opt_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn fold_processing_instruction(
         &mut self,
@@ -8708,11 +8110,6 @@ where
         <V as FoldAstPath>::fold_element(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn fold_namespace(&mut self, node: Namespace, __ast_path: &mut AstKindPath) -> Namespace {
-        <V as FoldAstPath>::fold_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn fold_opt_atom(
         &mut self,
@@ -8722,15 +8119,6 @@ where
         <V as FoldAstPath>::fold_opt_atom(&mut **self, node, __ast_path)
     }
 
-    #[inline]
-    fn fold_opt_namespace(
-        &mut self,
-        node: Option<Namespace>,
-        __ast_path: &mut AstKindPath,
-    ) -> Option<Namespace> {
-        <V as FoldAstPath>::fold_opt_namespace(&mut **self, node, __ast_path)
-    }
-
     #[inline]
     fn fold_processing_instruction(
         &mut self,
@@ -8949,18 +8337,6 @@ where
         }
     }
 
-    #[inline]
-    fn fold_namespace(&mut self, node: Namespace, __ast_path: &mut AstKindPath) -> Namespace {
-        match self {
-            swc_visit::Either::Left(visitor) => {
-                FoldAstPath::fold_namespace(visitor, node, __ast_path)
-            }
-            swc_visit::Either::Right(visitor) => {
-                FoldAstPath::fold_namespace(visitor, node, __ast_path)
-            }
-        }
-    }
-
     #[inline]
     fn fold_opt_atom(
         &mut self,
@@ -8977,22 +8353,6 @@ where
         }
     }
 
-    #[inline]
-    fn fold_opt_namespace(
-        &mut self,
-        node: Option<Namespace>,
-        __ast_path: &mut AstKindPath,
-    ) -> Option<Namespace> {
-        match self {
-            swc_visit::Either::Left(visitor) => {
-                FoldAstPath::fold_opt_namespace(visitor, node, __ast_path)
-            }
-            swc_visit::Either::Right(visitor) => {
-                FoldAstPath::fold_opt_namespace(visitor, node, __ast_path)
-            }
-        }
-    }
-
     #[inline]
     fn fold_processing_instruction(
         &mut self,
@@ -9204,15 +8564,6 @@ where
         }
     }
 
-    #[inline]
-    fn fold_namespace(&mut self, node: Namespace, __ast_path: &mut AstKindPath) -> Namespace {
-        if self.enabled {
-            <V as FoldAstPath>::fold_namespace(&mut self.visitor, node, __ast_path)
-        } else {
-            node
-        }
-    }
-
     #[inline]
     fn fold_opt_atom(
         &mut self,
@@ -9226,19 +8577,6 @@ where
         }
     }
 
-    #[inline]
-    fn fold_opt_namespace(
-        &mut self,
-        node: Option<Namespace>,
-        __ast_path: &mut AstKindPath,
-    ) -> Option<Namespace> {
-        if self.enabled {
-            <V as FoldAstPath>::fold_opt_namespace(&mut self.visitor, node, __ast_path)
-        } else {
-            node
-        }
-    }
-
     #[inline]
     fn fold_processing_instruction(
         &mut self,
@@ -9337,7 +8675,7 @@ impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for Attribute {
                     let mut __ast_path = __ast_path.with_guard(AstParentKind::Attribute(
                         self::fields::AttributeField::Namespace,
                     ));
-                    <Option<Namespace> as FoldWithAstPath<V>>::fold_with_ast_path(
+                    <Option<swc_atoms::Atom> as FoldWithAstPath<V>>::fold_with_ast_path(
                         namespace,
                         visitor,
                         &mut *__ast_path,
@@ -9787,6 +9125,7 @@ impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for Element {
         match self {
             Element {
                 span,
+                namespace,
                 tag_name,
                 attributes,
                 children,
@@ -9800,6 +9139,16 @@ impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for Element {
                         &mut *__ast_path,
                     )
                 };
+                let namespace = {
+                    let mut __ast_path = __ast_path.with_guard(AstParentKind::Element(
+                        self::fields::ElementField::Namespace,
+                    ));
+                    <Option<swc_atoms::Atom> as FoldWithAstPath<V>>::fold_with_ast_path(
+                        namespace,
+                        visitor,
+                        &mut *__ast_path,
+                    )
+                };
                 let tag_name = {
                     let mut __ast_path = __ast_path
                         .with_guard(AstParentKind::Element(self::fields::ElementField::TagName));
@@ -9831,6 +9180,7 @@ impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for Element {
                 };
                 Element {
                     span,
+                    namespace,
                     tag_name,
                     attributes,
                     children,
@@ -9841,25 +9191,6 @@ impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for Element {
 }
 #[cfg(any(docsrs, feature = "path"))]
 #[cfg_attr(docsrs, doc(cfg(feature = "path")))]
-impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for Namespace {
-    #[doc = "Calls [FoldAstPath`::fold_namespace`] with `self`."]
-    fn fold_with_ast_path(self, visitor: &mut V, __ast_path: &mut AstKindPath) -> Self {
-        <V as FoldAstPath>::fold_namespace(visitor, self, __ast_path)
-    }
-
-    fn fold_children_with_ast_path(self, visitor: &mut V, __ast_path: &mut AstKindPath) -> Self {
-        match self {
-            Namespace::HTML => Namespace::HTML,
-            Namespace::MATHML => Namespace::MATHML,
-            Namespace::SVG => Namespace::SVG,
-            Namespace::XLINK => Namespace::XLINK,
-            Namespace::XML => Namespace::XML,
-            Namespace::XMLNS => Namespace::XMLNS,
-        }
-    }
-}
-#[cfg(any(docsrs, feature = "path"))]
-#[cfg_attr(docsrs, doc(cfg(feature = "path")))]
 impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for ProcessingInstruction {
     #[doc = "Calls [FoldAstPath`::fold_processing_instruction`] with `self`."]
     fn fold_with_ast_path(self, visitor: &mut V, __ast_path: &mut AstKindPath) -> Self {
@@ -10325,32 +9656,16 @@ impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for Option<swc_atoms::Atom> {
 }
 #[cfg(any(docsrs, feature = "path"))]
 #[cfg_attr(docsrs, doc(cfg(feature = "path")))]
-impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for Option<Namespace> {
-    #[doc = "Calls [FoldAstPath`::fold_opt_namespace`] with `self`. (Extra impl)"]
+impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for swc_common::Span {
+    #[doc = "Calls [FoldAstPath`::fold_span`] with `self`. (Extra impl)"]
     #[inline]
     fn fold_with_ast_path(self, visitor: &mut V, __ast_path: &mut AstKindPath) -> Self {
-        <V as FoldAstPath>::fold_opt_namespace(visitor, self, __ast_path)
+        <V as FoldAstPath>::fold_span(visitor, self, __ast_path)
     }
 
     #[inline]
     fn fold_children_with_ast_path(self, visitor: &mut V, __ast_path: &mut AstKindPath) -> Self {
-        self.map(|inner| {
-            <Namespace as FoldWithAstPath<V>>::fold_with_ast_path(inner, visitor, __ast_path)
-        })
-    }
-}
-#[cfg(any(docsrs, feature = "path"))]
-#[cfg_attr(docsrs, doc(cfg(feature = "path")))]
-impl<V: ?Sized + FoldAstPath> FoldWithAstPath<V> for swc_common::Span {
-    #[doc = "Calls [FoldAstPath`::fold_span`] with `self`. (Extra impl)"]
-    #[inline]
-    fn fold_with_ast_path(self, visitor: &mut V, __ast_path: &mut AstKindPath) -> Self {
-        <V as FoldAstPath>::fold_span(visitor, self, __ast_path)
-    }
-
-    #[inline]
-    fn fold_children_with_ast_path(self, visitor: &mut V, __ast_path: &mut AstKindPath) -> Self {
-        self
+        self
     }
 }
 #[cfg(any(docsrs, feature = "path"))]
@@ -10374,632 +9689,6 @@ where
         })
     }
 }
-#[doc = r" A hook trait for composable AST visitors."]
-#[doc = r""]
-#[doc = r" This trait provides `enter_xxx` and `exit_xxx` methods for each AST node type."]
-#[doc = r" The enter method is called before visiting children, and the exit method is called after."]
-pub trait VisitMutHook {
-    #[doc = "Called when entering a node of type `swc_atoms :: Atom` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_atom(&mut self, node: &mut swc_atoms::Atom) {}
-    #[doc = "Called when exiting a node of type `swc_atoms :: Atom` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_atom(&mut self, node: &mut swc_atoms::Atom) {}
-    #[doc = "Called when entering a node of type `Attribute` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_attribute(&mut self, node: &mut Attribute) {}
-    #[doc = "Called when exiting a node of type `Attribute` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_attribute(&mut self, node: &mut Attribute) {}
-    #[doc = "Called when entering a node of type `AttributeToken` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_attribute_token(&mut self, node: &mut AttributeToken) {}
-    #[doc = "Called when exiting a node of type `AttributeToken` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_attribute_token(&mut self, node: &mut AttributeToken) {}
-    #[doc = "Called when entering a node of type `Vec < AttributeToken >` before visiting its \
-             children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_attribute_tokens(&mut self, node: &mut Vec<AttributeToken>) {}
-    #[doc = "Called when exiting a node of type `Vec < AttributeToken >` after visiting its \
-             children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_attribute_tokens(&mut self, node: &mut Vec<AttributeToken>) {}
-    #[doc = "Called when entering a node of type `Vec < Attribute >` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_attributes(&mut self, node: &mut Vec<Attribute>) {}
-    #[doc = "Called when exiting a node of type `Vec < Attribute >` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_attributes(&mut self, node: &mut Vec<Attribute>) {}
-    #[doc = "Called when entering a node of type `CdataSection` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_cdata_section(&mut self, node: &mut CdataSection) {}
-    #[doc = "Called when exiting a node of type `CdataSection` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_cdata_section(&mut self, node: &mut CdataSection) {}
-    #[doc = "Called when entering a node of type `Child` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_child(&mut self, node: &mut Child) {}
-    #[doc = "Called when exiting a node of type `Child` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_child(&mut self, node: &mut Child) {}
-    #[doc = "Called when entering a node of type `Vec < Child >` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_childs(&mut self, node: &mut Vec<Child>) {}
-    #[doc = "Called when exiting a node of type `Vec < Child >` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_childs(&mut self, node: &mut Vec<Child>) {}
-    #[doc = "Called when entering a node of type `Comment` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_comment(&mut self, node: &mut Comment) {}
-    #[doc = "Called when exiting a node of type `Comment` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_comment(&mut self, node: &mut Comment) {}
-    #[doc = "Called when entering a node of type `Document` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_document(&mut self, node: &mut Document) {}
-    #[doc = "Called when exiting a node of type `Document` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_document(&mut self, node: &mut Document) {}
-    #[doc = "Called when entering a node of type `DocumentMode` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_document_mode(&mut self, node: &mut DocumentMode) {}
-    #[doc = "Called when exiting a node of type `DocumentMode` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_document_mode(&mut self, node: &mut DocumentMode) {}
-    #[doc = "Called when entering a node of type `DocumentType` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_document_type(&mut self, node: &mut DocumentType) {}
-    #[doc = "Called when exiting a node of type `DocumentType` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_document_type(&mut self, node: &mut DocumentType) {}
-    #[doc = "Called when entering a node of type `Element` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_element(&mut self, node: &mut Element) {}
-    #[doc = "Called when exiting a node of type `Element` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_element(&mut self, node: &mut Element) {}
-    #[doc = "Called when entering a node of type `Namespace` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_namespace(&mut self, node: &mut Namespace) {}
-    #[doc = "Called when exiting a node of type `Namespace` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_namespace(&mut self, node: &mut Namespace) {}
-    #[doc = "Called when entering a node of type `Option < swc_atoms :: Atom >` before visiting \
-             its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_opt_atom(&mut self, node: &mut Option<swc_atoms::Atom>) {}
-    #[doc = "Called when exiting a node of type `Option < swc_atoms :: Atom >` after visiting its \
-             children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_opt_atom(&mut self, node: &mut Option<swc_atoms::Atom>) {}
-    #[doc = "Called when entering a node of type `Option < Namespace >` before visiting its \
-             children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_opt_namespace(&mut self, node: &mut Option<Namespace>) {}
-    #[doc = "Called when exiting a node of type `Option < Namespace >` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_opt_namespace(&mut self, node: &mut Option<Namespace>) {}
-    #[doc = "Called when entering a node of type `ProcessingInstruction` before visiting its \
-             children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_processing_instruction(&mut self, node: &mut ProcessingInstruction) {}
-    #[doc = "Called when exiting a node of type `ProcessingInstruction` after visiting its \
-             children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_processing_instruction(&mut self, node: &mut ProcessingInstruction) {}
-    #[doc = "Called when entering a node of type `swc_common :: Span` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_span(&mut self, node: &mut swc_common::Span) {}
-    #[doc = "Called when exiting a node of type `swc_common :: Span` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_span(&mut self, node: &mut swc_common::Span) {}
-    #[doc = "Called when entering a node of type `Text` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_text(&mut self, node: &mut Text) {}
-    #[doc = "Called when exiting a node of type `Text` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_text(&mut self, node: &mut Text) {}
-    #[doc = "Called when entering a node of type `Token` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_token(&mut self, node: &mut Token) {}
-    #[doc = "Called when exiting a node of type `Token` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_token(&mut self, node: &mut Token) {}
-    #[doc = "Called when entering a node of type `TokenAndSpan` before visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn enter_token_and_span(&mut self, node: &mut TokenAndSpan) {}
-    #[doc = "Called when exiting a node of type `TokenAndSpan` after visiting its children."]
-    #[inline]
-    #[allow(unused_variables)]
-    fn exit_token_and_span(&mut self, node: &mut TokenAndSpan) {}
-}
-#[doc = r" A composable hook that combines two hooks."]
-#[doc = r""]
-#[doc = r" Executes hooks in nested order:"]
-#[doc = r" - Enter: first.enter -> second.enter"]
-#[doc = r" - Exit: second.exit -> first.exit"]
-pub struct CompositeHook<A, B> {
-    pub first: A,
-    pub second: B,
-}
-impl<A, B> VisitMutHook for CompositeHook<A, B>
-where
-    A: VisitMutHook,
-    B: VisitMutHook,
-{
-    #[inline]
-    fn enter_atom(&mut self, node: &mut swc_atoms::Atom) {
-        self.first.enter_atom(node);
-        self.second.enter_atom(node);
-    }
-
-    #[inline]
-    fn exit_atom(&mut self, node: &mut swc_atoms::Atom) {
-        self.second.exit_atom(node);
-        self.first.exit_atom(node);
-    }
-
-    #[inline]
-    fn enter_attribute(&mut self, node: &mut Attribute) {
-        self.first.enter_attribute(node);
-        self.second.enter_attribute(node);
-    }
-
-    #[inline]
-    fn exit_attribute(&mut self, node: &mut Attribute) {
-        self.second.exit_attribute(node);
-        self.first.exit_attribute(node);
-    }
-
-    #[inline]
-    fn enter_attribute_token(&mut self, node: &mut AttributeToken) {
-        self.first.enter_attribute_token(node);
-        self.second.enter_attribute_token(node);
-    }
-
-    #[inline]
-    fn exit_attribute_token(&mut self, node: &mut AttributeToken) {
-        self.second.exit_attribute_token(node);
-        self.first.exit_attribute_token(node);
-    }
-
-    #[inline]
-    fn enter_attribute_tokens(&mut self, node: &mut Vec<AttributeToken>) {
-        self.first.enter_attribute_tokens(node);
-        self.second.enter_attribute_tokens(node);
-    }
-
-    #[inline]
-    fn exit_attribute_tokens(&mut self, node: &mut Vec<AttributeToken>) {
-        self.second.exit_attribute_tokens(node);
-        self.first.exit_attribute_tokens(node);
-    }
-
-    #[inline]
-    fn enter_attributes(&mut self, node: &mut Vec<Attribute>) {
-        self.first.enter_attributes(node);
-        self.second.enter_attributes(node);
-    }
-
-    #[inline]
-    fn exit_attributes(&mut self, node: &mut Vec<Attribute>) {
-        self.second.exit_attributes(node);
-        self.first.exit_attributes(node);
-    }
-
-    #[inline]
-    fn enter_cdata_section(&mut self, node: &mut CdataSection) {
-        self.first.enter_cdata_section(node);
-        self.second.enter_cdata_section(node);
-    }
-
-    #[inline]
-    fn exit_cdata_section(&mut self, node: &mut CdataSection) {
-        self.second.exit_cdata_section(node);
-        self.first.exit_cdata_section(node);
-    }
-
-    #[inline]
-    fn enter_child(&mut self, node: &mut Child) {
-        self.first.enter_child(node);
-        self.second.enter_child(node);
-    }
-
-    #[inline]
-    fn exit_child(&mut self, node: &mut Child) {
-        self.second.exit_child(node);
-        self.first.exit_child(node);
-    }
-
-    #[inline]
-    fn enter_childs(&mut self, node: &mut Vec<Child>) {
-        self.first.enter_childs(node);
-        self.second.enter_childs(node);
-    }
-
-    #[inline]
-    fn exit_childs(&mut self, node: &mut Vec<Child>) {
-        self.second.exit_childs(node);
-        self.first.exit_childs(node);
-    }
-
-    #[inline]
-    fn enter_comment(&mut self, node: &mut Comment) {
-        self.first.enter_comment(node);
-        self.second.enter_comment(node);
-    }
-
-    #[inline]
-    fn exit_comment(&mut self, node: &mut Comment) {
-        self.second.exit_comment(node);
-        self.first.exit_comment(node);
-    }
-
-    #[inline]
-    fn enter_document(&mut self, node: &mut Document) {
-        self.first.enter_document(node);
-        self.second.enter_document(node);
-    }
-
-    #[inline]
-    fn exit_document(&mut self, node: &mut Document) {
-        self.second.exit_document(node);
-        self.first.exit_document(node);
-    }
-
-    #[inline]
-    fn enter_document_mode(&mut self, node: &mut DocumentMode) {
-        self.first.enter_document_mode(node);
-        self.second.enter_document_mode(node);
-    }
-
-    #[inline]
-    fn exit_document_mode(&mut self, node: &mut DocumentMode) {
-        self.second.exit_document_mode(node);
-        self.first.exit_document_mode(node);
-    }
-
-    #[inline]
-    fn enter_document_type(&mut self, node: &mut DocumentType) {
-        self.first.enter_document_type(node);
-        self.second.enter_document_type(node);
-    }
-
-    #[inline]
-    fn exit_document_type(&mut self, node: &mut DocumentType) {
-        self.second.exit_document_type(node);
-        self.first.exit_document_type(node);
-    }
-
-    #[inline]
-    fn enter_element(&mut self, node: &mut Element) {
-        self.first.enter_element(node);
-        self.second.enter_element(node);
-    }
-
-    #[inline]
-    fn exit_element(&mut self, node: &mut Element) {
-        self.second.exit_element(node);
-        self.first.exit_element(node);
-    }
-
-    #[inline]
-    fn enter_namespace(&mut self, node: &mut Namespace) {
-        self.first.enter_namespace(node);
-        self.second.enter_namespace(node);
-    }
-
-    #[inline]
-    fn exit_namespace(&mut self, node: &mut Namespace) {
-        self.second.exit_namespace(node);
-        self.first.exit_namespace(node);
-    }
-
-    #[inline]
-    fn enter_opt_atom(&mut self, node: &mut Option<swc_atoms::Atom>) {
-        self.first.enter_opt_atom(node);
-        self.second.enter_opt_atom(node);
-    }
-
-    #[inline]
-    fn exit_opt_atom(&mut self, node: &mut Option<swc_atoms::Atom>) {
-        self.second.exit_opt_atom(node);
-        self.first.exit_opt_atom(node);
-    }
-
-    #[inline]
-    fn enter_opt_namespace(&mut self, node: &mut Option<Namespace>) {
-        self.first.enter_opt_namespace(node);
-        self.second.enter_opt_namespace(node);
-    }
-
-    #[inline]
-    fn exit_opt_namespace(&mut self, node: &mut Option<Namespace>) {
-        self.second.exit_opt_namespace(node);
-        self.first.exit_opt_namespace(node);
-    }
-
-    #[inline]
-    fn enter_processing_instruction(&mut self, node: &mut ProcessingInstruction) {
-        self.first.enter_processing_instruction(node);
-        self.second.enter_processing_instruction(node);
-    }
-
-    #[inline]
-    fn exit_processing_instruction(&mut self, node: &mut ProcessingInstruction) {
-        self.second.exit_processing_instruction(node);
-        self.first.exit_processing_instruction(node);
-    }
-
-    #[inline]
-    fn enter_span(&mut self, node: &mut swc_common::Span) {
-        self.first.enter_span(node);
-        self.second.enter_span(node);
-    }
-
-    #[inline]
-    fn exit_span(&mut self, node: &mut swc_common::Span) {
-        self.second.exit_span(node);
-        self.first.exit_span(node);
-    }
-
-    #[inline]
-    fn enter_text(&mut self, node: &mut Text) {
-        self.first.enter_text(node);
-        self.second.enter_text(node);
-    }
-
-    #[inline]
-    fn exit_text(&mut self, node: &mut Text) {
-        self.second.exit_text(node);
-        self.first.exit_text(node);
-    }
-
-    #[inline]
-    fn enter_token(&mut self, node: &mut Token) {
-        self.first.enter_token(node);
-        self.second.enter_token(node);
-    }
-
-    #[inline]
-    fn exit_token(&mut self, node: &mut Token) {
-        self.second.exit_token(node);
-        self.first.exit_token(node);
-    }
-
-    #[inline]
-    fn enter_token_and_span(&mut self, node: &mut TokenAndSpan) {
-        self.first.enter_token_and_span(node);
-        self.second.enter_token_and_span(node);
-    }
-
-    #[inline]
-    fn exit_token_and_span(&mut self, node: &mut TokenAndSpan) {
-        self.second.exit_token_and_span(node);
-        self.first.exit_token_and_span(node);
-    }
-}
-#[doc = r" An adapter that implements VisitMut using a VisitMutHook."]
-#[doc = r""]
-#[doc = r" This allows any hook to be used as a visitor by calling:"]
-#[doc = r" - hook.enter_xxx before visiting children"]
-#[doc = r" - hook.exit_xxx after visiting children"]
-pub struct VisitMutWithHook<H> {
-    pub hook: H,
-}
-impl<H: VisitMutHook> VisitMut for VisitMutWithHook<H> {
-    #[doc = "Visits a node of type `swc_atoms :: Atom` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_atom(&mut self, node: &mut swc_atoms::Atom) {
-        self.hook.enter_atom(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_atom(node);
-    }
-
-    #[doc = "Visits a node of type `Attribute` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_attribute(&mut self, node: &mut Attribute) {
-        self.hook.enter_attribute(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_attribute(node);
-    }
-
-    #[doc = "Visits a node of type `AttributeToken` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_attribute_token(&mut self, node: &mut AttributeToken) {
-        self.hook.enter_attribute_token(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_attribute_token(node);
-    }
-
-    #[doc = "Visits a node of type `Vec < AttributeToken >` using the hook's enter and exit \
-             methods."]
-    #[inline]
-    fn visit_mut_attribute_tokens(&mut self, node: &mut Vec<AttributeToken>) {
-        self.hook.enter_attribute_tokens(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_attribute_tokens(node);
-    }
-
-    #[doc = "Visits a node of type `Vec < Attribute >` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_attributes(&mut self, node: &mut Vec<Attribute>) {
-        self.hook.enter_attributes(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_attributes(node);
-    }
-
-    #[doc = "Visits a node of type `CdataSection` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_cdata_section(&mut self, node: &mut CdataSection) {
-        self.hook.enter_cdata_section(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_cdata_section(node);
-    }
-
-    #[doc = "Visits a node of type `Child` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_child(&mut self, node: &mut Child) {
-        self.hook.enter_child(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_child(node);
-    }
-
-    #[doc = "Visits a node of type `Vec < Child >` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_childs(&mut self, node: &mut Vec<Child>) {
-        self.hook.enter_childs(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_childs(node);
-    }
-
-    #[doc = "Visits a node of type `Comment` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_comment(&mut self, node: &mut Comment) {
-        self.hook.enter_comment(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_comment(node);
-    }
-
-    #[doc = "Visits a node of type `Document` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_document(&mut self, node: &mut Document) {
-        self.hook.enter_document(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_document(node);
-    }
-
-    #[doc = "Visits a node of type `DocumentMode` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_document_mode(&mut self, node: &mut DocumentMode) {
-        self.hook.enter_document_mode(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_document_mode(node);
-    }
-
-    #[doc = "Visits a node of type `DocumentType` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_document_type(&mut self, node: &mut DocumentType) {
-        self.hook.enter_document_type(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_document_type(node);
-    }
-
-    #[doc = "Visits a node of type `Element` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_element(&mut self, node: &mut Element) {
-        self.hook.enter_element(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_element(node);
-    }
-
-    #[doc = "Visits a node of type `Namespace` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_namespace(&mut self, node: &mut Namespace) {
-        self.hook.enter_namespace(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_namespace(node);
-    }
-
-    #[doc = "Visits a node of type `Option < swc_atoms :: Atom >` using the hook's enter and exit \
-             methods."]
-    #[inline]
-    fn visit_mut_opt_atom(&mut self, node: &mut Option<swc_atoms::Atom>) {
-        self.hook.enter_opt_atom(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_opt_atom(node);
-    }
-
-    #[doc = "Visits a node of type `Option < Namespace >` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_opt_namespace(&mut self, node: &mut Option<Namespace>) {
-        self.hook.enter_opt_namespace(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_opt_namespace(node);
-    }
-
-    #[doc = "Visits a node of type `ProcessingInstruction` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_processing_instruction(&mut self, node: &mut ProcessingInstruction) {
-        self.hook.enter_processing_instruction(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_processing_instruction(node);
-    }
-
-    #[doc = "Visits a node of type `swc_common :: Span` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_span(&mut self, node: &mut swc_common::Span) {
-        self.hook.enter_span(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_span(node);
-    }
-
-    #[doc = "Visits a node of type `Text` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_text(&mut self, node: &mut Text) {
-        self.hook.enter_text(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_text(node);
-    }
-
-    #[doc = "Visits a node of type `Token` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_token(&mut self, node: &mut Token) {
-        self.hook.enter_token(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_token(node);
-    }
-
-    #[doc = "Visits a node of type `TokenAndSpan` using the hook's enter and exit methods."]
-    #[inline]
-    fn visit_mut_token_and_span(&mut self, node: &mut TokenAndSpan) {
-        self.hook.enter_token_and_span(node);
-        node.visit_mut_children_with(self);
-        self.hook.exit_token_and_span(node);
-    }
-}
 #[cfg(any(docsrs, feature = "path"))]
 pub type AstKindPath = swc_visit::AstKindPath<AstParentKind>;
 #[cfg(any(docsrs, feature = "path"))]
@@ -11194,6 +9883,8 @@ pub mod fields {
     pub enum ElementField {
         #[doc = "Represents [`Element::span`]"]
         Span,
+        #[doc = "Represents [`Element::namespace`]"]
+        Namespace,
         #[doc = "Represents [`Element::tag_name`]"]
         TagName,
         #[doc = "Represents [`Element::attributes`]"]
@@ -11201,28 +9892,6 @@ pub mod fields {
         #[doc = "Represents [`Element::children`]"]
         Children(usize),
     }
-    impl NamespaceField {
-        #[inline(always)]
-        pub(crate) fn set_index(&mut self, _: usize) {
-            swc_visit::wrong_ast_path();
-        }
-    }
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    #[cfg_attr(feature = "serde-impl", derive(serde::Serialize, serde::Deserialize))]
-    pub enum NamespaceField {
-        #[doc = "Represents [`Namespace::HTML`]"]
-        Html,
-        #[doc = "Represents [`Namespace::MATHML`]"]
-        Mathml,
-        #[doc = "Represents [`Namespace::SVG`]"]
-        Svg,
-        #[doc = "Represents [`Namespace::XLINK`]"]
-        Xlink,
-        #[doc = "Represents [`Namespace::XML`]"]
-        Xml,
-        #[doc = "Represents [`Namespace::XMLNS`]"]
-        Xmlns,
-    }
     impl ProcessingInstructionField {
         pub(crate) fn set_index(&mut self, index: usize) {
             match self {
@@ -11258,14 +9927,9 @@ pub mod fields {
         Raw,
     }
     impl TokenField {
-        pub(crate) fn set_index(&mut self, index: usize) {
-            match self {
-                Self::Attributes(idx) => {
-                    assert_initial_index(*idx, index);
-                    *idx = index;
-                }
-                _ => swc_visit::wrong_ast_path(),
-            }
+        #[inline(always)]
+        pub(crate) fn set_index(&mut self, _: usize) {
+            swc_visit::wrong_ast_path();
         }
     }
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -11273,14 +9937,6 @@ pub mod fields {
     pub enum TokenField {
         #[doc = "Represents [`Token::Doctype`]"]
         Doctype,
-        #[doc = "Represents [`Token::Doctype::name`]"]
-        Name,
-        #[doc = "Represents [`Token::Doctype::public_id`]"]
-        PublicId,
-        #[doc = "Represents [`Token::Doctype::system_id`]"]
-        SystemId,
-        #[doc = "Represents [`Token::Doctype::raw`]"]
-        Raw,
         #[doc = "Represents [`Token::StartTag`]"]
         StartTag,
         #[doc = "Represents [`Token::EndTag`]"]
@@ -11297,14 +9953,6 @@ pub mod fields {
         Cdata,
         #[doc = "Represents [`Token::Eof`]"]
         Eof,
-        #[doc = "Represents [`Token::StartTag::tag_name`]"]
-        TagName,
-        #[doc = "Represents [`Token::StartTag::attributes`]"]
-        Attributes(usize),
-        #[doc = "Represents [`Token::Comment::data`]"]
-        Data,
-        #[doc = "Represents [`Token::ProcessingInstruction::target`]"]
-        Target,
     }
     impl TokenAndSpanField {
         pub(crate) fn set_index(&mut self, index: usize) {
@@ -11333,7 +9981,6 @@ pub mod fields {
         DocumentMode(DocumentModeField),
         DocumentType(DocumentTypeField),
         Element(ElementField),
-        Namespace(NamespaceField),
         ProcessingInstruction(ProcessingInstructionField),
         Text(TextField),
         Token(TokenField),
@@ -11352,7 +9999,6 @@ pub mod fields {
                 Self::DocumentMode(v) => v.set_index(index),
                 Self::DocumentType(v) => v.set_index(index),
                 Self::Element(v) => v.set_index(index),
-                Self::Namespace(v) => v.set_index(index),
                 Self::ProcessingInstruction(v) => v.set_index(index),
                 Self::Text(v) => v.set_index(index),
                 Self::Token(v) => v.set_index(index),
@@ -11371,7 +10017,6 @@ pub mod fields {
         DocumentMode(&'ast DocumentMode, DocumentModeField),
         DocumentType(&'ast DocumentType, DocumentTypeField),
         Element(&'ast Element, ElementField),
-        Namespace(&'ast Namespace, NamespaceField),
         ProcessingInstruction(&'ast ProcessingInstruction, ProcessingInstructionField),
         Text(&'ast Text, TextField),
         Token(&'ast Token, TokenField),
@@ -11396,7 +10041,6 @@ pub mod fields {
                 Self::DocumentMode(_, __field_kind) => __field_kind.set_index(index),
                 Self::DocumentType(_, __field_kind) => __field_kind.set_index(index),
                 Self::Element(_, __field_kind) => __field_kind.set_index(index),
-                Self::Namespace(_, __field_kind) => __field_kind.set_index(index),
                 Self::ProcessingInstruction(_, __field_kind) => __field_kind.set_index(index),
                 Self::Text(_, __field_kind) => __field_kind.set_index(index),
                 Self::Token(_, __field_kind) => __field_kind.set_index(index),
@@ -11420,7 +10064,6 @@ pub mod fields {
                 Self::DocumentMode(_, __field_kind) => AstParentKind::DocumentMode(*__field_kind),
                 Self::DocumentType(_, __field_kind) => AstParentKind::DocumentType(*__field_kind),
                 Self::Element(_, __field_kind) => AstParentKind::Element(*__field_kind),
-                Self::Namespace(_, __field_kind) => AstParentKind::Namespace(*__field_kind),
                 Self::ProcessingInstruction(_, __field_kind) => {
                     AstParentKind::ProcessingInstruction(*__field_kind)
                 }
@@ -11476,11 +10119,6 @@ impl<'ast> From<&'ast Element> for NodeRef<'ast> {
         NodeRef::Element(node)
     }
 }
-impl<'ast> From<&'ast Namespace> for NodeRef<'ast> {
-    fn from(node: &'ast Namespace) -> Self {
-        NodeRef::Namespace(node)
-    }
-}
 impl<'ast> From<&'ast ProcessingInstruction> for NodeRef<'ast> {
     fn from(node: &'ast ProcessingInstruction) -> Self {
         NodeRef::ProcessingInstruction(node)
@@ -11512,7 +10150,6 @@ pub enum NodeRef<'ast> {
     DocumentMode(&'ast DocumentMode),
     DocumentType(&'ast DocumentType),
     Element(&'ast Element),
-    Namespace(&'ast Namespace),
     ProcessingInstruction(&'ast ProcessingInstruction),
     Text(&'ast Text),
     Token(&'ast Token),
@@ -11524,11 +10161,7 @@ impl<'ast> NodeRef<'ast> {
     pub fn experimental_raw_children<'a>(&'a self) -> Box<dyn 'a + Iterator<Item = NodeRef<'ast>>> {
         match self {
             NodeRef::Attribute(node) => {
-                let iterator = ::std::iter::empty::<NodeRef<'ast>>().chain(
-                    node.namespace
-                        .iter()
-                        .flat_map(|item| ::std::iter::once(NodeRef::Namespace(&item))),
-                );
+                let iterator = ::std::iter::empty::<NodeRef<'ast>>();
                 Box::new(iterator)
             }
             NodeRef::AttributeToken(node) => {
@@ -11583,9 +10216,6 @@ impl<'ast> NodeRef<'ast> {
                     );
                 Box::new(iterator)
             }
-            NodeRef::Namespace(node) => match node {
-                _ => Box::new(::std::iter::empty::<NodeRef<'ast>>()),
-            },
             NodeRef::ProcessingInstruction(node) => {
                 let iterator = ::std::iter::empty::<NodeRef<'ast>>();
                 Box::new(iterator)
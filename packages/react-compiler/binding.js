@@ -401,3 +401,5 @@ if (!nativeBinding) {
 }
 module.exports.isReactCompilerRequired = nativeBinding.isReactCompilerRequired;
 module.exports.isReactCompilerRequiredSync = nativeBinding.isReactCompilerRequiredSync;
+module.exports.transform = nativeBinding.transform;
+module.exports.transformSync = nativeBinding.transformSync;
@@ -3,3 +3,30 @@
 export declare function isReactCompilerRequired(code: Buffer, signal?: AbortSignal | undefined | null): Promise<boolean>
 
 export declare function isReactCompilerRequiredSync(code: Buffer): boolean
+
+/**
+ * Compiles components and hooks in `code`.
+ *
+ * `options` is a JSON-encoded object, which may contain `filename`,
+ * `sourceMaps`, `runtimeModule` and `compilationMode`.
+ */
+export declare function transform(code: Buffer, options: Buffer, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
+
+export declare function transformSync(code: Buffer, options: Buffer): TransformOutput
+
+export interface Diagnostic {
+  /** `error` or `warning` */
+  level: string
+  message: string
+  /** 1-based line of the start of the span. */
+  line?: number
+  /** 0-based column of the start of the span. */
+  column?: number
+}
+
+export interface TransformOutput {
+  code: string
+  map?: string
+  /** Functions which were not compiled, with the reason. */
+  diagnostics: Array<Diagnostic>
+}
@@ -578,3 +578,5 @@ if (!nativeBinding) {
 module.exports = nativeBinding
 module.exports.isReactCompilerRequired = nativeBinding.isReactCompilerRequired
 module.exports.isReactCompilerRequiredSync = nativeBinding.isReactCompilerRequiredSync
+module.exports.transform = nativeBinding.transform
+module.exports.transformSync = nativeBinding.transformSync
@@ -16,3 +16,47 @@ export function isReactCompilerRequiredSync(code: Buffer): boolean {
     return binding.isReactCompilerRequiredSync(code)
 }
 
+
+export type { Diagnostic, TransformOutput } from './binding'
+
+export interface TransformOptions {
+    /**
+     * Used for diagnostics and source maps.
+     */
+    filename?: string;
+    sourceMaps?: boolean;
+    /**
+     * The module to import the memo cache hook from.
+     *
+     * Defaults to `react/compiler-runtime`.
+     */
+    runtimeModule?: string;
+    /**
+     * - `infer`: compile functions which look like components or hooks.
+     * - `annotation`: compile only functions with a `"use memo"` directive.
+     *
+     * Defaults to `infer`.
+     */
+    compilationMode?: "infer" | "annotation";
+}
+
+/**
+ * Memoizes JSX elements and values derived in components and hooks.
+ *
+ * Functions violating the rules of hooks are left as-is, and reported in
+ * `diagnostics`.
+ */
+export async function transform(code: string | Buffer, options: TransformOptions = {}) {
+    return await binding.transform(toBuffer(code), toBuffer(JSON.stringify(options)))
+}
+
+/**
+ * Synchronous version of `transform`.
+ */
+export function transformSync(code: string | Buffer, options: TransformOptions = {}): binding.TransformOutput {
+    return binding.transformSync(toBuffer(code), toBuffer(JSON.stringify(options)))
+}
+
+function toBuffer(t: string | Buffer): Buffer {
+    return typeof t === "string" ? Buffer.from(t) : t;
+}
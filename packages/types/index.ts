@@ -668,6 +668,27 @@ export interface JscConfig {
          */
         plugins?: WasmPlugin[];
 
+        /**
+         * Resource limits applied to each invocation of a plugin. A plugin
+         * hitting one of these limits is cancelled with an error.
+         */
+        pluginLimits?: {
+            /**
+             * Maximum number of instructions a plugin may execute.
+             */
+            fuel?: number;
+            /**
+             * Maximum size of the linear memory of a plugin, in bytes.
+             */
+            maxMemory?: number;
+        };
+
+        /**
+         * Reuse instances of plugins across files instead of creating them
+         * for each file. Defaults to `false`.
+         */
+        reusePluginInstances?: boolean;
+
         /**
          * Run Wasm plugins before stripping TypeScript or decorators.
          *
@@ -1019,7 +1040,8 @@ export type ModuleConfig =
     | UmdConfig
     | AmdConfig
     | NodeNextConfig
-    | SystemjsConfig;
+    | SystemjsConfig
+    | BrowserConfig;
 
 export interface BaseModuleConfig {
     /**
@@ -1210,6 +1232,38 @@ export interface NodeNextConfig extends BaseModuleConfig {
     type: "nodenext";
 }
 
+/**
+ * Keeps ES modules, but rewrites the module specifiers so the output can be
+ * served to browsers as is.
+ */
+export interface BrowserConfig {
+    type: "browser";
+    /**
+     * Import map applied to the module specifiers, in the format of
+     * `<script type="importmap">`.
+     */
+    importMap?: {
+        imports?: Record<string, string | null>;
+        scopes?: Record<string, Record<string, string | null>>;
+    };
+    /**
+     * URL at which the current working directory is served.
+     *
+     * Defaults to `/`.
+     */
+    baseUrl?: string;
+    /**
+     * If set, the extension of relative specifiers is replaced with this, and
+     * relative specifiers without an extension get it appended.
+     */
+    outFileExtension?: string;
+    /**
+     * Emits the URLs of the static imports as `__swc_modulepreload__` in
+     * `output`, as a JSON array.
+     */
+    modulepreload?: boolean;
+}
+
 export interface CommonJsConfig extends BaseModuleConfig {
     type: "commonjs";
 }